@@ -0,0 +1,79 @@
+//! Benchmarks for the text cleanup pipeline.
+//!
+//! Run with: cargo bench --bench cleanup_benchmark
+//!
+//! `cleanup_document` runs one pass over a multi-megabyte Markdown document;
+//! `cleanup_paragraphs` runs one pass per paragraph, as the plain text
+//! renderer does, so per-call setup cost dominates.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use unpdf::render::{CleanupPipeline, CleanupPreset};
+
+/// Paragraph mix exercising every cleanup stage: hard-wrapped English with
+/// hyphenation, Korean broken mid-sentence, list markers on their own line,
+/// TOC dot leaders, page numbers and runs of spaces.
+const SAMPLE: &str = "\
+## Section heading
+
+The extraction pipeline reads every page and re-\nassembles the text into
+paragraphs that were wrapped by the original layout engine   and
+sometimes carry ligatures like \u{FB01}le and \u{FB02}ow.
+
+한국어 문장은 줄 끝에서 끊어지는 경우가 많아 반드시 지키\n십시오 같은 형태로 남는다.
+
+•\nFirst bullet item
+1.\nNumbered item
+가.\n한글 목록 항목
+
+Chapter 1 ........................ 12
+Chapter 2 ........................ 27
+
+- 42 -
+
+";
+
+fn sample_document(min_bytes: usize) -> String {
+    let mut doc = String::with_capacity(min_bytes + SAMPLE.len());
+    while doc.len() < min_bytes {
+        doc.push_str(SAMPLE);
+    }
+    doc
+}
+
+fn bench_cleanup_document(c: &mut Criterion) {
+    let doc = sample_document(4 * 1024 * 1024);
+    let mut group = c.benchmark_group("cleanup_document");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(doc.len() as u64));
+
+    for preset in [CleanupPreset::Standard, CleanupPreset::Aggressive] {
+        let pipeline = CleanupPipeline::from_preset(preset);
+        group.bench_function(format!("{:?}", preset), |b| {
+            b.iter(|| pipeline.process(black_box(&doc)));
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_cleanup_paragraphs(c: &mut Criterion) {
+    let doc = sample_document(256 * 1024);
+    let paragraphs: Vec<&str> = doc.split("\n\n").filter(|p| !p.is_empty()).collect();
+    let mut group = c.benchmark_group("cleanup_paragraphs");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(doc.len() as u64));
+
+    let pipeline = CleanupPipeline::from_preset(CleanupPreset::Standard);
+    group.bench_function("Standard", |b| {
+        b.iter(|| {
+            for paragraph in &paragraphs {
+                black_box(pipeline.process(black_box(paragraph)));
+            }
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_cleanup_document, bench_cleanup_paragraphs);
+criterion_main!(benches);
@@ -53,6 +53,7 @@ internal static class NativeMethods
     // JSON format options
     public const int UNPDF_JSON_PRETTY = 0;
     public const int UNPDF_JSON_COMPACT = 1;
+    public const int UNPDF_JSON_DETAILED = 2;
 
     /// <summary>
     /// Get the library version.
@@ -144,6 +145,21 @@ internal static class NativeMethods
     [DllImport(LibraryName, CallingConvention = CallingConvention.Cdecl)]
     public static extern IntPtr unpdf_page_to_text(IntPtr doc, int pageNum);
 
+    /// <summary>
+    /// Convert selected pages (e.g. "1-3,5") to Markdown.
+    /// </summary>
+    [DllImport(LibraryName, CallingConvention = CallingConvention.Cdecl, CharSet = CharSet.Ansi)]
+    public static extern IntPtr unpdf_to_markdown_pages(
+        IntPtr doc,
+        [MarshalAs(UnmanagedType.LPUTF8Str)] string pageSpec,
+        int flags);
+
+    /// <summary>
+    /// Get the rendered text of a single page, cleaned up like unpdf_to_text.
+    /// </summary>
+    [DllImport(LibraryName, CallingConvention = CallingConvention.Cdecl)]
+    public static extern IntPtr unpdf_page_text(IntPtr doc, int pageNumber);
+
     /// <summary>
     /// Free a string allocated by the library.
     /// </summary>
@@ -329,6 +329,54 @@ public class UnpdfDocument : IDisposable
         }
     }
 
+    /// <summary>
+    /// Convert selected pages to Markdown, rendered as they appear in <see cref="ToMarkdown"/>.
+    /// </summary>
+    /// <param name="pageSpec">1-indexed pages, e.g. "1-3,5"</param>
+    /// <param name="options">Optional rendering options</param>
+    /// <returns>Markdown string for the selected pages</returns>
+    /// <exception cref="UnpdfException">If the spec is invalid, selects no page or rendering fails</exception>
+    public string PagesToMarkdown(string pageSpec, MarkdownOptions? options = null)
+    {
+        ThrowIfDisposed();
+        int flags = options?.ToFlags() ?? 0;
+        var ptr = NativeMethods.unpdf_to_markdown_pages(_handle, pageSpec, flags);
+        if (ptr == IntPtr.Zero)
+            throw new UnpdfException($"Failed to convert pages {pageSpec} to markdown: {GetLastError()}");
+
+        try
+        {
+            return PtrToStringUtf8(ptr);
+        }
+        finally
+        {
+            NativeMethods.unpdf_free_string(ptr);
+        }
+    }
+
+    /// <summary>
+    /// Get the text of a single page, cleaned up as it appears in <see cref="ToText"/>.
+    /// </summary>
+    /// <param name="pageNumber">Page number (1-indexed)</param>
+    /// <returns>Text string for the specified page</returns>
+    /// <exception cref="UnpdfException">If the page number is out of range</exception>
+    public string GetPageText(int pageNumber)
+    {
+        ThrowIfDisposed();
+        var ptr = NativeMethods.unpdf_page_text(_handle, pageNumber);
+        if (ptr == IntPtr.Zero)
+            throw new UnpdfException($"Failed to render text for page {pageNumber}: {GetLastError()}");
+
+        try
+        {
+            return PtrToStringUtf8(ptr);
+        }
+        finally
+        {
+            NativeMethods.unpdf_free_string(ptr);
+        }
+    }
+
     /// <summary>
     /// Get extraction quality diagnostics for the document.
     /// </summary>
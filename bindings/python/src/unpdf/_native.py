@@ -188,6 +188,7 @@ UNPDF_FLAG_PARAGRAPH_SPACING = 4
 
 UNPDF_JSON_PRETTY = 0
 UNPDF_JSON_COMPACT = 1
+UNPDF_JSON_DETAILED = 2
 
 
 def get_library():
@@ -29,14 +29,15 @@ extern "C" {
 /** Opaque handle to a parsed document. */
 typedef struct UnpdfDocument UnpdfDocument;
 
-/* Flags for unpdf_to_markdown / unpdf_page_to_markdown. */
+/* Flags for unpdf_to_markdown / unpdf_page_to_markdown / unpdf_to_markdown_pages. */
 #define UNPDF_FLAG_FRONTMATTER       1u
 #define UNPDF_FLAG_ESCAPE_SPECIAL    2u
 #define UNPDF_FLAG_PARAGRAPH_SPACING 4u
 
 /* Format selector for unpdf_to_json. */
-#define UNPDF_JSON_PRETTY  0
-#define UNPDF_JSON_COMPACT 1
+#define UNPDF_JSON_PRETTY   0
+#define UNPDF_JSON_COMPACT  1
+#define UNPDF_JSON_DETAILED 2
 
 /**
  * Get the library version.
@@ -83,7 +84,8 @@ char* unpdf_to_text(const UnpdfDocument* doc);
 
 /**
  * Convert the document to JSON.
- * @param format UNPDF_JSON_PRETTY or UNPDF_JSON_COMPACT.
+ * @param format UNPDF_JSON_PRETTY, UNPDF_JSON_COMPACT or UNPDF_JSON_DETAILED
+ *               (pretty, with tables also as header-keyed `records`).
  * @return JSON string (must be freed with unpdf_free_string), or NULL.
  */
 char* unpdf_to_json(const UnpdfDocument* doc, int format);
@@ -182,6 +184,23 @@ char* unpdf_page_to_markdown(const UnpdfDocument* doc, int page_num, uint32_t fl
  */
 char* unpdf_page_to_text(const UnpdfDocument* doc, int page_num);
 
+/**
+ * Convert selected pages to Markdown, rendered in the context of the whole
+ * document so the output matches the same pages of unpdf_to_markdown.
+ * @param page_spec 1-indexed pages, e.g. "1-3,5"; "" or "all" selects every page.
+ * @param flags Bitwise OR of UNPDF_FLAG_* values.
+ * @return Markdown string (must be freed with unpdf_free_string), or NULL if
+ *         the spec is invalid or selects no page.
+ */
+char* unpdf_to_markdown_pages(const UnpdfDocument* doc, const char* page_spec, uint32_t flags);
+
+/**
+ * Render the text of a single page with the cleanup and layout of unpdf_to_text.
+ * @param page_number 1-indexed page number.
+ * @return Text string (must be freed with unpdf_free_string), or NULL.
+ */
+char* unpdf_page_text(const UnpdfDocument* doc, int page_number);
+
 /** Free a string allocated by the library. Safe to call with NULL. */
 void unpdf_free_string(char* s);
 
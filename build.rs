@@ -98,6 +98,23 @@ fn main() {
     let dest = Path::new(&out_dir).join("cmap_tables.rs");
     let mut f = fs::File::create(&dest).unwrap();
     f.write_all(all_code.as_bytes()).unwrap();
+
+    write_schema(&out_dir);
+}
+
+/// Stamp the crate version into the JSON Schema for document output.
+///
+/// `schema/document.schema.json` carries a `{VERSION}` placeholder in its `$id`, so
+/// each release publishes a schema of its own that `unpdf::schema()` embeds.
+fn write_schema(out_dir: &str) {
+    let path = "schema/document.schema.json";
+    println!("cargo:rerun-if-changed={}", path);
+
+    let version = env::var("CARGO_PKG_VERSION").unwrap();
+    let schema =
+        fs::read_to_string(path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path, e));
+    let dest = Path::new(out_dir).join("document.schema.json");
+    fs::write(dest, schema.replace("{VERSION}", &version)).unwrap();
 }
 
 /// Pick the best Unicode code point from a `cid2code.txt` cell.
@@ -0,0 +1,181 @@
+//! Encrypted outputs for `--encrypt-key`.
+//!
+//! 각 출력 파일(Markdown, 텍스트, JSON, 이미지)을 AES-256-GCM 으로 따로
+//! 암호화해 `<name>.enc` 로 쓴다. 평문은 디스크에 닿지 않는다.
+//!
+//! File layout: `UNPDFENC` magic, a version byte, a random 12-byte nonce,
+//! then the ciphertext with its 16-byte tag. `unpdf decrypt` reverses it.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+
+/// Extension appended to encrypted files.
+pub const EXTENSION: &str = "enc";
+
+/// Environment variable read when `--encrypt-key` / `--key` is not given.
+pub const KEY_ENV: &str = "UNPDF_ENCRYPT_KEY";
+
+const MAGIC: &[u8; 8] = b"UNPDFENC";
+const VERSION: u8 = 1;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = MAGIC.len() + 1 + NONCE_LEN;
+
+/// A 256-bit AES-GCM key.
+#[derive(Clone)]
+pub struct OutputKey(Key<Aes256Gcm>);
+
+impl OutputKey {
+    /// Parse a key given as 64 hex digits, or as `@FILE` naming a file that
+    /// holds the 64 hex digits or the 32 raw key bytes.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let bytes = match spec.strip_prefix('@') {
+            Some(path) => {
+                let data = std::fs::read(path)
+                    .map_err(|e| format!("cannot read key file {}: {}", path, e))?;
+                if data.len() == 32 {
+                    data
+                } else {
+                    decode_hex(String::from_utf8_lossy(&data).trim())?
+                }
+            }
+            None => decode_hex(spec.trim())?,
+        };
+        if bytes.len() != 32 {
+            return Err(format!(
+                "key must be 32 bytes (64 hex digits), got {} bytes",
+                bytes.len()
+            ));
+        }
+        Ok(Self(*Key::<Aes256Gcm>::from_slice(&bytes)))
+    }
+
+    /// Hex form of the key, for handing it to a sandboxed child process.
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Encrypt `plaintext` under a fresh random nonce.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = Aes256Gcm::new(&self.0)
+            .encrypt(&nonce, plaintext)
+            .expect("AES-GCM encryption of an in-memory buffer cannot fail");
+        let mut out = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&ciphertext);
+        out
+    }
+
+    /// Decrypt a buffer produced by [`OutputKey::encrypt`].
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, String> {
+        if data.len() < HEADER_LEN || &data[..MAGIC.len()] != MAGIC {
+            return Err("not an unpdf encrypted file".to_string());
+        }
+        if data[MAGIC.len()] != VERSION {
+            return Err(format!(
+                "unsupported encrypted file version {}",
+                data[MAGIC.len()]
+            ));
+        }
+        let nonce = Nonce::from_slice(&data[MAGIC.len() + 1..HEADER_LEN]);
+        Aes256Gcm::new(&self.0)
+            .decrypt(nonce, &data[HEADER_LEN..])
+            .map_err(|_| "wrong key or corrupted file".to_string())
+    }
+
+    /// Encrypt `data` and write it to `<path>.enc`, returning that path.
+    pub fn write(&self, path: &Path, data: &[u8]) -> std::io::Result<PathBuf> {
+        let path = encrypted_path(path);
+        std::fs::write(&path, self.encrypt(data))?;
+        Ok(path)
+    }
+}
+
+// 키가 로그나 `{:?}` 출력에 새지 않도록 감춘다.
+impl fmt::Debug for OutputKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OutputKey(..)")
+    }
+}
+
+/// `path` with `.enc` appended (`extract.md` → `extract.md.enc`).
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(EXTENSION);
+    PathBuf::from(name)
+}
+
+/// `path` without its `.enc` suffix, or `None` if it has none.
+pub fn decrypted_path(path: &Path) -> Option<PathBuf> {
+    (path.extension()? == EXTENSION).then(|| path.with_extension(""))
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>, String> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .filter(|digits| digits.len() == 2)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .ok_or_else(|| "key must be hex digits".to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEX: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_round_trip_and_wrong_key() {
+        let key = OutputKey::parse(HEX).unwrap();
+        let sealed = key.encrypt(b"confidential");
+        assert!(sealed.starts_with(MAGIC));
+        assert_eq!(key.decrypt(&sealed).unwrap(), b"confidential");
+        // 같은 평문이라도 nonce 가 달라 암호문이 달라야 한다.
+        assert_ne!(key.encrypt(b"confidential"), sealed);
+
+        let other = OutputKey::parse(&HEX.replace("00", "ff")).unwrap();
+        assert!(other.decrypt(&sealed).is_err());
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(key.decrypt(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_key_parsing() {
+        assert_eq!(OutputKey::parse(HEX).unwrap().to_hex(), HEX);
+        assert!(OutputKey::parse("abcd").is_err());
+        assert!(OutputKey::parse(&HEX.replace('0', "g")).is_err());
+        assert_eq!(
+            format!("{:?}", OutputKey::parse(HEX).unwrap()),
+            "OutputKey(..)"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("key");
+        std::fs::write(&file, [7u8; 32]).unwrap();
+        let key = OutputKey::parse(&format!("@{}", file.display())).unwrap();
+        assert_eq!(key.to_hex(), "07".repeat(32));
+    }
+
+    #[test]
+    fn test_paths() {
+        let sealed = encrypted_path(Path::new("out/images/p1.png"));
+        assert_eq!(sealed, Path::new("out/images/p1.png.enc"));
+        assert_eq!(
+            decrypted_path(&sealed).unwrap(),
+            Path::new("out/images/p1.png")
+        );
+        assert_eq!(decrypted_path(Path::new("extract.md")), None);
+    }
+}
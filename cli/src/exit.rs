@@ -0,0 +1,107 @@
+//! Process exit codes and error reporting.
+//!
+//! The codes are stable so batch orchestrators can branch on the kind of
+//! failure; `--error-format json` prints the error to stderr as one JSON line
+//! instead of colored text.
+
+use clap::ValueEnum;
+use colored::Colorize;
+
+/// Success.
+pub const OK: i32 = 0;
+/// Any failure not covered below: I/O, bad arguments, rendering.
+pub const FAILURE: i32 = 1;
+/// The PDF could not be parsed (corrupted, malformed, timed out).
+pub const PARSE_ERROR: i32 = 2;
+/// The PDF is encrypted, the password was wrong, or its permissions forbid
+/// extraction.
+pub const ENCRYPTED: i32 = 3;
+/// The input is not a PDF, or a PDF version that is not supported.
+pub const UNSUPPORTED_FORMAT: i32 = 4;
+/// Output was written, but with quality warnings or with some files failing.
+pub const PARTIAL_SUCCESS: i32 = 5;
+
+/// How errors are printed to stderr.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// `Error: message`, colored on a terminal
+    #[default]
+    Text,
+    /// `{"error":{"kind":...,"code":...,"message":...}}` on one line
+    Json,
+}
+
+/// Exit code and kind name for `err`.
+pub fn classify(err: &(dyn std::error::Error + 'static)) -> (i32, &'static str) {
+    use unpdf::Error;
+    let Some(err) = err.downcast_ref::<Error>() else {
+        return match err.downcast_ref::<std::io::Error>() {
+            Some(_) => (FAILURE, "io"),
+            None => (FAILURE, "error"),
+        };
+    };
+    match err {
+        Error::Encrypted | Error::InvalidPassword => (ENCRYPTED, "encrypted"),
+        Error::ExtractionForbidden => (ENCRYPTED, "extraction_forbidden"),
+        Error::UnknownFormat | Error::NotPdf(_) | Error::UnsupportedVersion(_) => {
+            (UNSUPPORTED_FORMAT, "unsupported_format")
+        }
+        Error::PdfParse(_)
+        | Error::Corrupted(_)
+        | Error::MissingObject(_)
+        | Error::FontDecode(_)
+        | Error::TextExtract(_)
+        | Error::Encoding(_) => (PARSE_ERROR, "parse"),
+        Error::Timeout(_) => (PARSE_ERROR, "timeout"),
+        Error::Io(_) => (FAILURE, "io"),
+        Error::PageOutOfRange(..) | Error::InvalidPageRange(_) => (FAILURE, "usage"),
+        _ => (FAILURE, "error"),
+    }
+}
+
+/// Print `err` to stderr in `format` and return the exit code for it.
+pub fn report(err: &(dyn std::error::Error + 'static), format: ErrorFormat) -> i32 {
+    let (code, kind) = classify(err);
+    match format {
+        ErrorFormat::Text => eprintln!("{}: {}", "Error".red().bold(), err),
+        ErrorFormat::Json => {
+            let error = serde_json::json!({
+                "error": { "kind": kind, "code": code, "message": err.to_string() }
+            });
+            eprintln!("{}", error);
+        }
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let code = |err: unpdf::Error| classify(&err);
+        assert_eq!(code(unpdf::Error::Encrypted), (ENCRYPTED, "encrypted"));
+        assert_eq!(
+            code(unpdf::Error::ExtractionForbidden),
+            (ENCRYPTED, "extraction_forbidden")
+        );
+        assert_eq!(
+            code(unpdf::Error::UnknownFormat),
+            (UNSUPPORTED_FORMAT, "unsupported_format")
+        );
+        assert_eq!(
+            code(unpdf::Error::Corrupted("xref".into())),
+            (PARSE_ERROR, "parse")
+        );
+        assert_eq!(
+            code(unpdf::Error::InvalidPageRange("9-1".into())),
+            (FAILURE, "usage")
+        );
+
+        let boxed: Box<dyn std::error::Error> = Box::new(unpdf::Error::InvalidPassword);
+        assert_eq!(classify(boxed.as_ref()).0, ENCRYPTED);
+        let other: Box<dyn std::error::Error> = "no output".into();
+        assert_eq!(classify(other.as_ref()), (FAILURE, "error"));
+    }
+}
@@ -1,19 +1,30 @@
 //! unpdf CLI - PDF content extraction tool
 
+mod crypt;
+mod exit;
+mod preview;
+mod profile;
+mod progress;
+mod sandbox;
 mod update;
+mod watch;
 mod writer;
 
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use clap::{Parser, Subcommand, ValueEnum};
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
+use progress::{Progress, ProgressMode};
+use unicode_width::UnicodeWidthStr;
 
 use unpdf::{
     parse_file_with_options, CleanupPreset, JsonFormat, PageSelection, ParseOptions, RenderOptions,
 };
-use unpdf::{PageStreamOptions, ParseEvent, PdfParser};
+use unpdf::{ImageConversion, ImageFormat};
+use unpdf::{PageStreamOptions, ParseEvent, PdfParser, ResourceNaming, SafeFileNames, TextLayout};
 
 /// Arguments for the `convert` subcommand.
 #[derive(Parser, Debug)]
@@ -55,6 +66,43 @@ pub struct ConvertArgs {
     #[arg(long, value_name = "PX", default_value = "64")]
     pub min_image_size: u32,
 
+    /// Name extracted images from a template instead of their PDF ids, so
+    /// links stay the same across re-runs: `hash` for content-hash names,
+    /// or placeholders {page} {index} {hash} {id} {ext} (e.g. "{page:03}_{index:02}.{ext}")
+    #[arg(long, value_name = "TEMPLATE")]
+    pub image_names: Option<String>,
+
+    /// Embed images of at most this many bytes in the Markdown as base64
+    /// data URIs; only larger images are written to the image directory
+    #[arg(long, value_name = "BYTES")]
+    pub inline_images: Option<usize>,
+
+    /// Convert extracted images to one format (WebP is lossless)
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub image_format: Option<ImageFormatArg>,
+
+    /// Downscale extracted images larger than this many pixels in either
+    /// dimension, keeping the aspect ratio
+    #[arg(long, value_name = "PX")]
+    pub max_image_size: Option<u32>,
+
+    /// Remove embedded ICC color profiles from extracted images
+    #[arg(long)]
+    pub strip_color_profiles: bool,
+
+    /// Also export path-drawn charts and diagrams as SVG into the image
+    /// directory (ignored with --no-images)
+    #[arg(long)]
+    pub vectors: bool,
+
+    /// Stop parsing after this many seconds; pages not reached are reported
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<u64>,
+
+    /// Give up on any single page that takes longer than this many seconds
+    #[arg(long, value_name = "SECS")]
+    pub page_timeout: Option<u64>,
+
     /// Override streaming window size (pages in-flight)
     #[arg(long, value_name = "N")]
     pub window: Option<usize>,
@@ -63,11 +111,93 @@ pub struct ConvertArgs {
     #[arg(long)]
     pub page_markers: bool,
 
+    /// Mark low-confidence tables, undecodable text and scanned pages with
+    /// <!-- unpdf: ... --> comments in the Markdown output
+    #[arg(long)]
+    pub inline_warnings: bool,
+
+    /// Dump each page's content stream, spans and layout boundaries into
+    /// DIR, to attach to reading-order bug reports
+    #[arg(long, value_name = "DIR")]
+    pub debug_artifacts: Option<PathBuf>,
+
+    /// Encrypt every output, images included, with AES-256-GCM into
+    /// `<name>.enc` files. KEY is 64 hex digits or @FILE (see `unpdf decrypt`)
+    #[arg(
+        long,
+        value_name = "KEY",
+        env = crypt::KEY_ENV,
+        hide_env_values = true,
+        value_parser = crypt::OutputKey::parse,
+        conflicts_with = "debug_artifacts"
+    )]
+    pub encrypt_key: Option<crypt::OutputKey>,
+
+    /// Progress output: a bar with stage, pages/sec and ETA, JSON lines on
+    /// stderr, or none [default: bar, none with --quiet]
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub progress: Option<ProgressMode>,
+
     /// Suppress warning messages
     #[arg(short, long)]
     pub quiet: bool,
 }
 
+/// `--chapter` / `--bookmark`: pick pages by the document's bookmarks.
+#[derive(clap::Args, Debug, Clone)]
+pub struct OutlineArgs {
+    /// Only the pages of the bookmark with this title (e.g., "Introduction")
+    #[arg(long, value_name = "TITLE", conflicts_with_all = ["pages", "bookmark"])]
+    pub chapter: Option<String>,
+
+    /// Only the pages of the Nth bookmark, counting nested ones, from 1
+    #[arg(long, value_name = "N", conflicts_with = "pages")]
+    pub bookmark: Option<usize>,
+}
+
+impl OutlineArgs {
+    /// Pages of the chosen bookmark, or `None` when neither flag is given.
+    fn selection(&self, doc: &unpdf::Document) -> Result<Option<PageSelection>, String> {
+        let range = if let Some(title) = &self.chapter {
+            doc.pages_for_bookmark(title)
+                .ok_or_else(|| bookmark_error(doc, &format!("No bookmark matches \"{}\"", title)))?
+        } else if let Some(n) = self.bookmark {
+            doc.outline_page_ranges()
+                .get(n.wrapping_sub(1))
+                .and_then(|(_, range)| range.clone())
+                .ok_or_else(|| bookmark_error(doc, &format!("No bookmark {} with pages", n)))?
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(PageSelection::Range(range)))
+    }
+}
+
+/// `message` followed by the numbered bookmarks to choose from.
+fn bookmark_error(doc: &unpdf::Document, message: &str) -> String {
+    let ranges = doc.outline_page_ranges();
+    if ranges.is_empty() {
+        return "The document has no bookmarks; try --section or --pages".to_string();
+    }
+    let mut out = format!("{}. Bookmarks:", message);
+    for (i, (item, range)) in ranges.iter().enumerate() {
+        let pages = match range {
+            Some(r) if r.start() == r.end() => format!("page {}", r.start()),
+            Some(r) => format!("pages {}-{}", r.start(), r.end()),
+            None => "no page".to_string(),
+        };
+        let indent = "  ".repeat(item.level as usize + 1);
+        out.push_str(&format!(
+            "\n{}{}. {} ({})",
+            indent,
+            i + 1,
+            item.title,
+            pages
+        ));
+    }
+    out
+}
+
 #[derive(Parser)]
 #[command(name = "unpdf")]
 #[command(author = "iyulab")]
@@ -90,6 +220,10 @@ struct Cli {
     #[arg(short, long)]
     quiet: bool,
 
+    /// How errors are printed to stderr
+    #[arg(long, value_enum, global = true, default_value = "text")]
+    error_format: exit::ErrorFormat,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -130,9 +264,71 @@ enum Commands {
         #[arg(long)]
         pages: Option<String>,
 
+        /// Only the pages of the bookmark or heading with this title (e.g., "Chapter 3")
+        #[arg(long, value_name = "TITLE", conflicts_with_all = ["pages", "chapter", "bookmark"])]
+        section: Option<String>,
+
+        #[command(flatten)]
+        outline: OutlineArgs,
+
         /// Insert HTML page boundary markers (<!-- page N -->)
         #[arg(long)]
         page_markers: bool,
+
+        /// Mark low-confidence tables, undecodable text and scanned pages
+        /// with <!-- unpdf: ... --> comments
+        #[arg(long)]
+        inline_warnings: bool,
+
+        /// Embed provenance (source SHA-256, unpdf version, options, timestamp) in frontmatter
+        #[arg(long)]
+        provenance: bool,
+
+        /// Use a title guessed from page 1 when the PDF has none (marked in frontmatter)
+        #[arg(long)]
+        infer_title: bool,
+
+        /// Drop paragraphs repeated from earlier pages (e.g. a title block on
+        /// every slide); with --dedup=PAGES, keep one copy in every PAGES pages
+        #[arg(
+            long,
+            value_name = "PAGES",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "0"
+        )]
+        dedup: Option<u32>,
+
+        /// Include highlights, comments and sticky notes
+        #[arg(long, value_enum)]
+        annotations: Option<AnnotationMode>,
+
+        /// Keep the output within this many bytes, cutting between blocks
+        #[arg(long, value_name = "BYTES")]
+        max_bytes: Option<usize>,
+
+        /// What to do with output over --max-bytes (split writes FILE.partN.md)
+        #[arg(long, value_enum, default_value = "truncate", requires = "max_bytes")]
+        overflow: OverflowMode,
+    },
+
+    /// Show a PDF as styled Markdown in a pager, to check extraction quality
+    Preview {
+        /// Input PDF file
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
+        /// Text cleanup preset
+        #[arg(long, value_enum)]
+        cleanup: Option<CleanupLevel>,
+
+        /// Page range (e.g., "1-10", "1,3,5")
+        #[arg(long)]
+        pages: Option<String>,
+
+        /// Print to stdout instead of opening a pager
+        #[arg(long)]
+        no_pager: bool,
     },
 
     /// Convert PDF to plain text
@@ -152,6 +348,40 @@ enum Commands {
         /// Page range (e.g., "1-10", "1,3,5")
         #[arg(long)]
         pages: Option<String>,
+
+        #[command(flatten)]
+        outline: OutlineArgs,
+
+        /// One line per block, no line breaks inside blocks (for embedding models)
+        #[arg(long)]
+        single_line: bool,
+
+        /// Block separator for --single-line (`\n` and `\t` escapes are expanded)
+        #[arg(
+            long,
+            value_name = "SEP",
+            default_value = "\\n\\n",
+            requires = "single_line"
+        )]
+        separator: String,
+
+        /// Keep the page layout, padding words to their columns (like `pdftotext -layout`)
+        #[arg(long, conflicts_with = "single_line")]
+        layout: bool,
+
+        /// Re-wrap paragraphs at this many columns (default: the terminal width when printing
+        /// to a terminal)
+        #[arg(
+            long,
+            value_name = "COLS",
+            value_parser = clap::value_parser!(u16).range(1..),
+            conflicts_with_all = ["single_line", "layout"]
+        )]
+        width: Option<u16>,
+
+        /// Keep the source line breaks, also when printing to a terminal
+        #[arg(long, conflicts_with = "width")]
+        no_wrap: bool,
     },
 
     /// Convert PDF to JSON
@@ -167,6 +397,133 @@ enum Commands {
         /// Output compact JSON
         #[arg(long)]
         compact: bool,
+
+        /// Add derived views (tables as `records` keyed by header)
+        #[arg(long, conflicts_with = "compact")]
+        detailed: bool,
+
+        /// Embed provenance (source SHA-256, unpdf version, options, timestamp)
+        #[arg(long)]
+        provenance: bool,
+
+        /// Add every word with its bounding box and line to each page (`words`)
+        #[arg(long)]
+        words: bool,
+
+        /// Wrap consecutive list items in `list` blocks
+        #[arg(long)]
+        group_lists: bool,
+    },
+
+    /// Apply a cleanup preset to a text file from any source (e.g. OCR output)
+    Clean {
+        /// Input text file (`-` reads stdin)
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
+        /// Output file (stdout if not specified)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Text cleanup preset
+        #[arg(long, value_enum, default_value = "standard")]
+        cleanup: CleanupLevel,
+    },
+
+    /// Merge several PDFs into one Markdown document (one H1 section per file)
+    Merge {
+        /// Input PDF files, in section order
+        #[arg(value_name = "FILE", required = true, num_args = 2..)]
+        inputs: Vec<PathBuf>,
+
+        /// Output file (stdout if not specified)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Include YAML frontmatter
+        #[arg(short, long)]
+        frontmatter: bool,
+
+        /// Text cleanup preset
+        #[arg(long, value_enum)]
+        cleanup: Option<CleanupLevel>,
+    },
+
+    /// Convert many PDFs, one output directory per file, and summarize failures
+    Batch {
+        /// Input PDF files
+        #[arg(value_name = "FILE", required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// Output root (each PDF converts into `<DIR>/<stem>/`; repeated stems
+        /// get `-2`, `-3`, ...)
+        #[arg(short, long, value_name = "DIR")]
+        output: PathBuf,
+
+        /// Output formats (comma-separated: md,txt,json)
+        #[arg(long, value_delimiter = ',', default_value = "md")]
+        formats: Vec<String>,
+
+        /// Text cleanup preset
+        #[arg(long, value_enum)]
+        cleanup: Option<CleanupLevel>,
+
+        /// Encrypt every output into `<name>.enc` files (see `convert --encrypt-key`)
+        #[arg(
+            long,
+            value_name = "KEY",
+            env = crypt::KEY_ENV,
+            hide_env_values = true,
+            value_parser = crypt::OutputKey::parse
+        )]
+        encrypt_key: Option<crypt::OutputKey>,
+
+        #[command(flatten)]
+        sandbox: sandbox::SandboxArgs,
+    },
+
+    /// Watch a directory and convert new or modified PDFs automatically
+    Watch {
+        /// Directory to watch
+        #[arg(value_name = "DIR")]
+        dir: PathBuf,
+
+        /// Output root (each PDF converts into `<DIR>/<stem>/`)
+        #[arg(short, long, value_name = "DIR")]
+        output: PathBuf,
+
+        /// Output formats (comma-separated: md,txt,json)
+        #[arg(long, value_delimiter = ',', default_value = "md")]
+        formats: Vec<String>,
+
+        /// Text cleanup preset
+        #[arg(long, value_enum)]
+        cleanup: Option<CleanupLevel>,
+
+        /// Quiet period after the last change before converting (milliseconds)
+        #[arg(long, value_name = "MS", default_value = "1000")]
+        debounce: u64,
+
+        /// Watch subdirectories too, mirroring them under the output root
+        #[arg(short, long)]
+        recursive: bool,
+
+        /// Don't convert PDFs already in the directory at startup
+        #[arg(long)]
+        no_initial: bool,
+
+        /// Encrypt every output into `<name>.enc` files (see `convert --encrypt-key`)
+        #[arg(
+            long,
+            value_name = "KEY",
+            env = crypt::KEY_ENV,
+            hide_env_values = true,
+            value_parser = crypt::OutputKey::parse
+        )]
+        encrypt_key: Option<crypt::OutputKey>,
+
+        #[command(flatten)]
+        sandbox: sandbox::SandboxArgs,
     },
 
     /// Show document information
@@ -174,6 +531,45 @@ enum Commands {
         /// Input PDF file
         #[arg(value_name = "FILE")]
         input: PathBuf,
+
+        /// Print the report as JSON (metadata, stats, fonts, quality flags)
+        #[arg(long)]
+        json: bool,
+
+        /// Read only metadata, page count, encryption and bookmarks, without
+        /// parsing pages — fast enough for scanning thousands of files
+        #[arg(long)]
+        metadata_only: bool,
+    },
+
+    /// Check that a PDF opens and extracts cleanly, as a pre-flight gate
+    ///
+    /// Exits 0 on pass, 5 on warnings (damaged xref, unsupported filters,
+    /// pages failing extraction) and with the error code on failure.
+    Validate {
+        /// Input PDF file
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Profile a conversion: time and memory per stage, the slowest pages
+    /// and suspicious page characteristics, for performance reports
+    Profile {
+        /// Input PDF file
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
+        /// Number of slowest pages to list
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        top: usize,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Extract images from PDF
@@ -191,6 +587,50 @@ enum Commands {
         pages: Option<String>,
     },
 
+    /// Extract figures: each image with a JSON sidecar holding its caption,
+    /// page and bounding box
+    Figures {
+        /// Input PDF file
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
+        /// Output directory
+        #[arg(short, long, value_name = "DIR")]
+        output: Option<PathBuf>,
+
+        /// Page range (e.g., "1-10", "1,3,5")
+        #[arg(long)]
+        pages: Option<String>,
+    },
+
+    /// Decrypt `.enc` outputs written with `--encrypt-key`
+    Decrypt {
+        /// Encrypted file, or a directory searched recursively for `.enc` files
+        #[arg(value_name = "PATH")]
+        input: PathBuf,
+
+        /// Output directory (defaults to next to each encrypted file)
+        #[arg(short, long, value_name = "DIR")]
+        output: Option<PathBuf>,
+
+        /// Key the outputs were encrypted with: 64 hex digits or @FILE
+        #[arg(
+            long,
+            value_name = "KEY",
+            env = crypt::KEY_ENV,
+            hide_env_values = true,
+            value_parser = crypt::OutputKey::parse
+        )]
+        key: crypt::OutputKey,
+    },
+
+    /// Print the JSON Schema of the `json` command's output
+    Schema {
+        /// Write the schema to a file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
     /// Self-update to latest version
     Update {
         /// Only check for updates, don't install
@@ -226,6 +666,23 @@ impl From<CleanupLevel> for CleanupPreset {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum ImageFormatArg {
+    /// PNG
+    Png,
+    /// Lossless WebP
+    Webp,
+}
+
+impl From<ImageFormatArg> for ImageFormat {
+    fn from(format: ImageFormatArg) -> Self {
+        match format {
+            ImageFormatArg::Png => ImageFormat::Png,
+            ImageFormatArg::Webp => ImageFormat::Webp,
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
 enum TableMode {
     /// Standard Markdown tables
@@ -246,6 +703,43 @@ impl From<TableMode> for unpdf::TableFallback {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum AnnotationMode {
+    /// A blockquote per annotation after each page
+    Quote,
+    /// Footnotes anchored to the annotated paragraph
+    Footnote,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum OverflowMode {
+    /// Drop the blocks that do not fit
+    Truncate,
+    /// Drop them and end with a note of how much was left out
+    Summarize,
+    /// Write consecutive parts, each within the limit
+    Split,
+}
+
+impl From<OverflowMode> for unpdf::OverflowStrategy {
+    fn from(mode: OverflowMode) -> Self {
+        match mode {
+            OverflowMode::Truncate => unpdf::OverflowStrategy::Truncate,
+            OverflowMode::Summarize => unpdf::OverflowStrategy::SummarizeTail,
+            OverflowMode::Split => unpdf::OverflowStrategy::Split,
+        }
+    }
+}
+
+impl From<AnnotationMode> for unpdf::AnnotationStyle {
+    fn from(mode: AnnotationMode) -> Self {
+        match mode {
+            AnnotationMode::Quote => unpdf::AnnotationStyle::Blockquote,
+            AnnotationMode::Footnote => unpdf::AnnotationStyle::Footnote,
+        }
+    }
+}
+
 /// Check extraction quality and print warnings to stderr.
 /// Returns true if quality warnings were emitted.
 fn check_quality(doc: &unpdf::Document, quiet: bool) -> bool {
@@ -268,10 +762,23 @@ fn should_check_update(cli: &Cli) -> bool {
     )
 }
 
+// `unpdf profile` 의 스테이지별 메모리 측정용. 그 명령에서만 센다.
+#[global_allocator]
+static ALLOC: profile::CountingAlloc = profile::CountingAlloc;
+
 fn main() {
     env_logger::init();
 
-    let cli = Cli::parse();
+    // clap 은 사용법 오류에 2 를 쓰므로 직접 처리해 파싱 오류 코드와 겹치지 않게 한다.
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) if !e.use_stderr() => e.exit(),
+        Err(e) => {
+            let _ = e.print();
+            std::process::exit(exit::FAILURE);
+        }
+    };
+    let error_format = cli.error_format;
 
     // Start background update check (except for update/version commands)
     let update_rx = if should_check_update(&cli) {
@@ -298,7 +805,16 @@ fn main() {
             cleanup,
             max_heading,
             pages,
+            section,
+            outline,
             page_markers,
+            inline_warnings,
+            provenance,
+            infer_title,
+            dedup,
+            annotations,
+            max_bytes,
+            overflow,
         }) => cmd_markdown(
             &input,
             output.as_deref(),
@@ -307,30 +823,144 @@ fn main() {
             cleanup,
             max_heading,
             pages.as_deref(),
+            section.as_deref(),
+            &outline,
             page_markers,
+            inline_warnings,
+            provenance,
+            infer_title,
+            dedup,
+            annotations,
+            max_bytes.map(|bytes| (bytes, overflow)),
             quiet,
         ),
+        Some(Commands::Preview {
+            input,
+            cleanup,
+            pages,
+            no_pager,
+        }) => cmd_preview(&input, cleanup, pages.as_deref(), no_pager, quiet),
         Some(Commands::Text {
             input,
             output,
             cleanup,
             pages,
-        }) => cmd_text(&input, output.as_deref(), cleanup, pages.as_deref(), quiet),
+            outline,
+            single_line,
+            separator,
+            layout,
+            width,
+            no_wrap,
+        }) => cmd_text(
+            &input,
+            output.as_deref(),
+            cleanup,
+            pages.as_deref(),
+            &outline,
+            single_line.then(|| unescape_separator(&separator)),
+            layout,
+            width.map(usize::from),
+            no_wrap,
+            quiet,
+        ),
         Some(Commands::Json {
             input,
             output,
             compact,
-        }) => cmd_json(&input, output.as_deref(), compact, quiet),
-        Some(Commands::Info { input }) => cmd_info(&input, quiet),
+            detailed,
+            provenance,
+            words,
+            group_lists,
+        }) => cmd_json(
+            &input,
+            output.as_deref(),
+            compact,
+            detailed,
+            provenance,
+            words,
+            group_lists,
+            quiet,
+        ),
+        Some(Commands::Clean {
+            input,
+            output,
+            cleanup,
+        }) => cmd_clean(&input, output.as_deref(), cleanup),
+        Some(Commands::Merge {
+            inputs,
+            output,
+            frontmatter,
+            cleanup,
+        }) => cmd_merge(&inputs, output.as_deref(), frontmatter, cleanup, quiet),
+        Some(Commands::Batch {
+            inputs,
+            output,
+            formats,
+            cleanup,
+            encrypt_key,
+            sandbox,
+        }) => cmd_batch(
+            &inputs,
+            &output,
+            formats,
+            cleanup,
+            encrypt_key.as_ref(),
+            &sandbox,
+            quiet,
+        ),
+        Some(Commands::Watch {
+            dir,
+            output,
+            formats,
+            cleanup,
+            debounce,
+            recursive,
+            no_initial,
+            encrypt_key,
+            sandbox,
+        }) => cmd_watch(
+            watch::WatchConfig {
+                dir,
+                out: output,
+                debounce: std::time::Duration::from_millis(debounce),
+                recursive,
+                initial_scan: !no_initial,
+            },
+            formats,
+            cleanup,
+            encrypt_key.as_ref(),
+            &sandbox,
+        ),
+        Some(Commands::Info {
+            input,
+            json,
+            metadata_only,
+        }) => {
+            if metadata_only {
+                cmd_info_metadata(&input, json)
+            } else {
+                cmd_info(&input, json, quiet)
+            }
+        }
+        Some(Commands::Validate { input, json }) => cmd_validate(&input, json),
+        Some(Commands::Profile { input, top, json }) => profile::run(&input, top, json),
         Some(Commands::Extract {
             input,
             output,
             pages,
         }) => cmd_extract(&input, output.as_deref(), pages.as_deref(), quiet),
+        Some(Commands::Figures {
+            input,
+            output,
+            pages,
+        }) => cmd_figures(&input, output.as_deref(), pages.as_deref(), quiet),
+        Some(Commands::Decrypt { input, output, key }) => {
+            cmd_decrypt(&input, output.as_deref(), &key, quiet)
+        }
+        Some(Commands::Schema { output }) => cmd_schema(output.as_deref()),
         Some(Commands::Update { check, force }) => {
             if let Err(e) = update::run_update(check, force) {
-                eprintln!("{}: {}", "Error".red().bold(), e);
-                std::process::exit(1);
+                std::process::exit(exit::report(e.as_ref(), error_format));
             }
             Ok(false)
         }
@@ -351,8 +981,20 @@ fn main() {
                     keep_ocr_text: false,
                     image_dir: None,
                     min_image_size: 64,
+                    image_names: None,
+                    inline_images: None,
+                    image_format: None,
+                    max_image_size: None,
+                    strip_color_profiles: false,
+                    vectors: false,
+                    timeout: None,
+                    page_timeout: None,
                     window: None,
                     page_markers: false,
+                    inline_warnings: false,
+                    debug_artifacts: None,
+                    encrypt_key: None,
+                    progress: None,
                     quiet,
                 };
                 cmd_convert(&args)
@@ -374,13 +1016,10 @@ fn main() {
     match result {
         Ok(had_warnings) => {
             if had_warnings {
-                std::process::exit(2);
+                std::process::exit(exit::PARTIAL_SUCCESS);
             }
         }
-        Err(e) => {
-            eprintln!("{}: {}", "Error".red().bold(), e);
-            std::process::exit(1);
-        }
+        Err(e) => std::process::exit(exit::report(e.as_ref(), error_format)),
     }
 }
 
@@ -445,6 +1084,25 @@ fn cmd_convert(args: &ConvertArgs) -> Result<bool, Box<dyn std::error::Error>> {
     if args.page_markers {
         render_opts = render_opts.with_page_markers(unpdf::PageMarkerStyle::Comment);
     }
+    render_opts = render_opts.with_inline_warnings(args.inline_warnings);
+    if let Some(template) = &args.image_names {
+        render_opts = render_opts.with_resource_naming(match template.as_str() {
+            "hash" => ResourceNaming::content_hash(),
+            _ => ResourceNaming::template(template.as_str()),
+        });
+    }
+    if let Some(max_bytes) = args.inline_images {
+        render_opts = render_opts.with_inline_images_max_bytes(max_bytes);
+    }
+    let mut conversion =
+        ImageConversion::new().with_stripped_color_profiles(args.strip_color_profiles);
+    if let Some(format) = args.image_format {
+        conversion = conversion.with_format(format.into());
+    }
+    if let Some(max) = args.max_image_size {
+        conversion = conversion.with_max_dimension(max);
+    }
+    render_opts = render_opts.with_image_conversion(conversion);
 
     // Open parser
     let mut parse_options = ParseOptions::new()
@@ -456,33 +1114,37 @@ fn cmd_convert(args: &ConvertArgs) -> Result<bool, Box<dyn std::error::Error>> {
     let parser = PdfParser::open_with_options(&args.input, parse_options)?;
 
     // Set up writer
-    let mut mfw =
-        writer::MultiFormatWriter::new(&out_dir, &formats, render_opts, image_dir.clone())?;
+    let mut mfw = writer::MultiFormatWriter::new(
+        &out_dir,
+        &formats,
+        render_opts,
+        image_dir.clone(),
+        args.encrypt_key.clone(),
+    )?;
 
     // Stream options
     let mut stream_opts = PageStreamOptions {
         extract_resources: image_dir.is_some(),
         min_image_dimension: args.min_image_size,
         suppress_low_confidence_ocr: !args.keep_ocr_text,
+        extract_vector_graphics: args.vectors && image_dir.is_some(),
+        timeout: args.timeout.map(Duration::from_secs),
+        page_timeout: args.page_timeout.map(Duration::from_secs),
+        debug_artifacts: args.debug_artifacts.clone(),
         ..PageStreamOptions::default()
     };
     if let Some(w) = args.window {
         stream_opts.window_size = w.max(1);
     }
 
-    // Progress bar
-    let total_pages = parser.page_count();
-    let pb = if args.quiet {
-        ProgressBar::hidden()
+    // Progress: every page is reported, so the bar and JSON lines advance per page.
+    let mode = args.progress.unwrap_or(if args.quiet {
+        ProgressMode::None
     } else {
-        let b = ProgressBar::new(total_pages as u64);
-        b.set_style(
-            ProgressStyle::default_bar()
-                .template("{bar:40.cyan/blue} {pos}/{len} pages ({eta})")
-                .unwrap(),
-        );
-        b
-    };
+        ProgressMode::Bar
+    });
+    let mut progress = Progress::new(mode, parser.page_count());
+    stream_opts.emit_progress_every = 1;
 
     let mut quality = None;
     let mut write_err: Option<String> = None;
@@ -498,22 +1160,21 @@ fn cmd_convert(args: &ConvertArgs) -> Result<bool, Box<dyn std::error::Error>> {
                     write_err = Some(format!("document start: {}", e));
                     return ControlFlow::Break(());
                 }
+                progress.stage("parsing");
             }
             ParseEvent::PageParsed(mut page) => {
                 if let Err(e) = mfw.write_page(&mut page) {
                     write_err = Some(format!("page {}: {}", page.number, e));
                     return ControlFlow::Break(());
                 }
-                pb.inc(1);
             }
             ParseEvent::PageFailed { page, error } => {
                 eprintln!("page {} failed: {}", page, error);
-                pb.inc(1);
             }
             ParseEvent::DocumentEnd { quality: q } => {
                 quality = Some(q);
             }
-            ParseEvent::Progress { .. } => {}
+            ParseEvent::Progress { done, total } => progress.pages(done, total),
         }
         ControlFlow::Continue(())
     })?;
@@ -522,8 +1183,9 @@ fn cmd_convert(args: &ConvertArgs) -> Result<bool, Box<dyn std::error::Error>> {
         return Err(e.into());
     }
 
+    progress.stage("writing");
     let summary = mfw.finish()?;
-    pb.finish_with_message("Done");
+    progress.finish();
 
     if !args.quiet {
         for path in [&summary.md_path, &summary.txt_path, &summary.json_path]
@@ -565,10 +1227,18 @@ fn cmd_markdown(
     cleanup: Option<CleanupLevel>,
     max_heading: u8,
     pages: Option<&str>,
+    section: Option<&str>,
+    outline: &OutlineArgs,
     page_markers: bool,
+    inline_warnings: bool,
+    provenance: bool,
+    infer_title: bool,
+    dedup: Option<u32>,
+    annotations: Option<AnnotationMode>,
+    max_bytes: Option<(usize, OverflowMode)>,
     quiet: bool,
 ) -> Result<bool, Box<dyn std::error::Error>> {
-    let page_selection = if let Some(p) = pages {
+    let mut page_selection = if let Some(p) = pages {
         PageSelection::parse(p).map_err(|e| format!("Invalid page range: {}", e))?
     } else {
         PageSelection::All
@@ -577,41 +1247,133 @@ fn cmd_markdown(
     // Use lenient mode to continue even if some text extraction fails
     let options = ParseOptions::new()
         .lenient()
-        .with_pages(page_selection.clone());
+        .with_pages(page_selection.clone())
+        .with_source_hash(provenance);
     let doc = parse_file_with_options(input, options)?;
     let had_warnings = check_quality(&doc, quiet);
 
+    // 절 제목은 책갈피나 본문 머리말에서 찾으므로 문서 전체를 읽은 뒤 쪽 범위로 바꾼다.
+    if let Some(title) = section {
+        let range = doc
+            .pages_for_heading(title)
+            .ok_or_else(|| format!("No bookmark or heading matches \"{}\"", title))?;
+        page_selection = PageSelection::Range(range);
+    }
+    if let Some(selection) = outline.selection(&doc)? {
+        page_selection = selection;
+    }
+
     let mut render_options = RenderOptions::new()
         .with_frontmatter(frontmatter)
         .with_table_fallback(table_mode.into())
         .with_max_heading(max_heading)
-        .with_pages(page_selection);
+        .with_pages(page_selection)
+        .with_provenance(provenance)
+        .with_inferred_title(infer_title)
+        .with_inline_warnings(inline_warnings);
 
     if page_markers {
         render_options = render_options.with_page_markers(unpdf::PageMarkerStyle::Comment);
     }
 
+    if let Some(pages) = dedup {
+        let scope = match pages {
+            0 => unpdf::DedupScope::Document,
+            n => unpdf::DedupScope::Pages(n),
+        };
+        render_options =
+            render_options.with_block_dedup(unpdf::BlockDedup::new().with_scope(scope));
+    }
+
+    if let Some(mode) = annotations {
+        render_options = render_options.with_annotations(mode.into());
+    }
+
     if let Some(level) = cleanup {
         render_options = render_options.with_cleanup_preset(level.into());
     }
 
-    let markdown = unpdf::render::to_markdown(&doc, &render_options)?;
+    if let Some((bytes, overflow)) = max_bytes {
+        render_options = render_options.with_max_output_bytes(bytes, overflow.into());
+    }
 
-    if let Some(path) = output {
-        fs::write(path, &markdown)?;
-        println!("{} {}", "Saved to".green(), path.display());
+    let parts = unpdf::render::to_markdown_parts(&doc, &render_options)?;
+
+    match output {
+        Some(path) if parts.len() > 1 => {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let ext = path
+                .extension()
+                .map_or("md".into(), |e| e.to_string_lossy());
+            for (i, part) in parts.iter().enumerate() {
+                let part_path = path.with_file_name(format!("{}.part{}.{}", stem, i + 1, ext));
+                fs::write(&part_path, part)?;
+                println!("{} {}", "Saved to".green(), part_path.display());
+            }
+        }
+        Some(path) => {
+            fs::write(path, &parts[0])?;
+            println!("{} {}", "Saved to".green(), path.display());
+        }
+        None => {
+            for part in &parts {
+                println!("{}", part);
+            }
+        }
+    }
+
+    Ok(had_warnings)
+}
+
+fn cmd_preview(
+    input: &Path,
+    cleanup: Option<CleanupLevel>,
+    pages: Option<&str>,
+    no_pager: bool,
+    quiet: bool,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let page_selection = if let Some(p) = pages {
+        PageSelection::parse(p).map_err(|e| format!("Invalid page range: {}", e))?
     } else {
-        println!("{}", markdown);
+        PageSelection::All
+    };
+
+    let options = ParseOptions::new()
+        .lenient()
+        .with_pages(page_selection.clone());
+    let doc = parse_file_with_options(input, options)?;
+    let had_warnings = check_quality(&doc, quiet);
+
+    // 쪽 경계와 경고를 함께 보여 추출 품질을 눈으로 확인하기 쉽게 한다.
+    let mut render_options = RenderOptions::new()
+        .with_pages(page_selection)
+        .with_page_markers(unpdf::PageMarkerStyle::Comment)
+        .with_inline_warnings(true);
+    if let Some(level) = cleanup {
+        render_options = render_options.with_cleanup_preset(level.into());
     }
 
+    let markdown = unpdf::render::to_markdown(&doc, &render_options)?;
+    let styled = preview::style_markdown(&markdown);
+    if no_pager {
+        print!("{}", styled);
+    } else {
+        preview::page(&styled)?;
+    }
     Ok(had_warnings)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn cmd_text(
     input: &Path,
     output: Option<&Path>,
     cleanup: Option<CleanupLevel>,
     pages: Option<&str>,
+    outline: &OutlineArgs,
+    single_line: Option<String>,
+    layout: bool,
+    width: Option<usize>,
+    no_wrap: bool,
     quiet: bool,
 ) -> Result<bool, Box<dyn std::error::Error>> {
     let page_selection = if let Some(p) = pages {
@@ -621,16 +1383,36 @@ fn cmd_text(
     };
 
     // Use lenient mode to continue even if some text extraction fails
-    let options = ParseOptions::new().lenient().with_pages(page_selection);
+    let options = ParseOptions::new()
+        .lenient()
+        .with_pages(page_selection)
+        .with_words(layout);
     let doc = parse_file_with_options(input, options)?;
     let had_warnings = check_quality(&doc, quiet);
 
     let mut render_options = RenderOptions::new();
+    if let Some(selection) = outline.selection(&doc)? {
+        render_options = render_options.with_pages(selection);
+    }
     if let Some(level) = cleanup {
         render_options = render_options.with_cleanup_preset(level.into());
     }
+    if layout {
+        render_options = render_options.with_text_layout(TextLayout::Preserve);
+    } else if single_line.is_none() {
+        // 터미널에 바로 출력할 때는 창 너비에 맞춰 다시 줄바꿈한다.
+        let wrap = match width {
+            Some(cols) => Some(cols),
+            None if no_wrap || output.is_some() => None,
+            None => preview::terminal_width(),
+        };
+        render_options = render_options.with_wrap_width(wrap);
+    }
 
-    let text = unpdf::render::to_text(&doc, &render_options)?;
+    let text = match single_line {
+        Some(separator) => unpdf::render::to_single_line_text(&doc, &render_options, &separator)?,
+        None => unpdf::render::to_text(&doc, &render_options)?,
+    };
 
     if let Some(path) = output {
         fs::write(path, &text)?;
@@ -642,24 +1424,55 @@ fn cmd_text(
     Ok(had_warnings)
 }
 
+/// Expand `\n`, `\t` and `\\` in a separator typed on the command line.
+fn unescape_separator(raw: &str) -> String {
+    let mut result = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
 fn cmd_json(
     input: &Path,
     output: Option<&Path>,
     compact: bool,
+    detailed: bool,
+    provenance: bool,
+    words: bool,
+    group_lists: bool,
     quiet: bool,
 ) -> Result<bool, Box<dyn std::error::Error>> {
     // Use lenient mode to continue even if some text extraction fails
-    let options = ParseOptions::new().lenient();
+    let options = ParseOptions::new()
+        .lenient()
+        .with_words(words)
+        .with_list_grouping(group_lists)
+        .with_source_hash(provenance);
     let doc = unpdf::parse_file_with_options(input, options)?;
     let had_warnings = check_quality(&doc, quiet);
 
     let format = if compact {
         JsonFormat::Compact
+    } else if detailed {
+        JsonFormat::Detailed
     } else {
         JsonFormat::Pretty
     };
 
-    let json = unpdf::render::to_json(&doc, format)?;
+    let render_options = RenderOptions::new().with_provenance(provenance);
+    let json = unpdf::render::to_json_with_options(&doc, format, &render_options)?;
 
     if let Some(path) = output {
         fs::write(path, &json)?;
@@ -671,12 +1484,300 @@ fn cmd_json(
     Ok(had_warnings)
 }
 
-fn cmd_info(input: &Path, quiet: bool) -> Result<bool, Box<dyn std::error::Error>> {
+fn cmd_merge(
+    inputs: &[PathBuf],
+    output: Option<&Path>,
+    frontmatter: bool,
+    cleanup: Option<CleanupLevel>,
+    quiet: bool,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let mut builder = unpdf::Unpdf::new().lenient();
+    if frontmatter {
+        builder = builder.with_frontmatter();
+    }
+    if let Some(level) = cleanup {
+        builder = builder.with_cleanup(level.into());
+    }
+
+    let result = builder.parse_many(inputs)?;
+    let had_warnings = check_quality(result.document(), quiet);
+    let markdown = result.to_markdown()?;
+
+    if let Some(path) = output {
+        fs::write(path, &markdown)?;
+        println!("{} {}", "Saved to".green(), path.display());
+    } else {
+        println!("{}", markdown);
+    }
+
+    Ok(had_warnings)
+}
+
+/// Convert one file for a multi-file command: in-process, or in a child
+/// process when `limits` is set.
+fn convert_one(
+    input: &Path,
+    out_dir: &Path,
+    formats: &[String],
+    cleanup: Option<CleanupLevel>,
+    encrypt_key: Option<&crypt::OutputKey>,
+    limits: Option<&sandbox::Limits>,
+) -> sandbox::Outcome {
+    if let Some(limits) = limits {
+        let args = sandbox_child_args(input, out_dir, formats, cleanup);
+        // 키는 프로세스 목록에 보이지 않도록 인자 대신 환경 변수로 넘긴다.
+        let envs: Vec<_> = encrypt_key
+            .map(|key| (crypt::KEY_ENV, key.to_hex()))
+            .into_iter()
+            .collect();
+        return sandbox::run(&args, &envs, limits)
+            .unwrap_or_else(|e| sandbox::Outcome::Failed(format!("cannot spawn: {}", e)));
+    }
+
+    let args = ConvertArgs {
+        input: input.to_path_buf(),
+        output: Some(out_dir.to_path_buf()),
+        cleanup,
+        formats: formats.to_vec(),
+        all: false,
+        no_images: false,
+        keep_ocr_text: false,
+        image_dir: None,
+        min_image_size: 64,
+        image_names: None,
+        inline_images: None,
+        image_format: None,
+        max_image_size: None,
+        strip_color_profiles: false,
+        vectors: false,
+        timeout: None,
+        page_timeout: None,
+        window: None,
+        page_markers: false,
+        inline_warnings: false,
+        debug_artifacts: None,
+        encrypt_key: encrypt_key.cloned(),
+        progress: None,
+        quiet: true,
+    };
+    match cmd_convert(&args) {
+        Ok(_) => sandbox::Outcome::Ok,
+        Err(e) => sandbox::Outcome::Failed(e.to_string()),
+    }
+}
+
+/// Arguments for the `unpdf convert` child that mirror [`convert_one`]'s
+/// in-process conversion.
+fn sandbox_child_args(
+    input: &Path,
+    out_dir: &Path,
+    formats: &[String],
+    cleanup: Option<CleanupLevel>,
+) -> Vec<std::ffi::OsString> {
+    let mut args: Vec<std::ffi::OsString> = vec![
+        "convert".into(),
+        input.into(),
+        "--output".into(),
+        out_dir.into(),
+        "--formats".into(),
+        formats.join(",").into(),
+        "--quiet".into(),
+    ];
+    if let Some(level) = cleanup.and_then(|l| l.to_possible_value()) {
+        args.push("--cleanup".into());
+        args.push(level.get_name().into());
+    }
+    args
+}
+
+fn cmd_batch(
+    inputs: &[PathBuf],
+    output: &Path,
+    formats: Vec<String>,
+    cleanup: Option<CleanupLevel>,
+    encrypt_key: Option<&crypt::OutputKey>,
+    sandbox: &sandbox::SandboxArgs,
+    quiet: bool,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    fs::create_dir_all(output)?;
+    let limits = sandbox.limits();
+
+    let pb = if quiet {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new(inputs.len() as u64)
+    };
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{bar:40.cyan/blue} {pos}/{len} {msg}")
+            .unwrap(),
+    );
+
+    let out_dirs = watch::unique_output_dirs(output, inputs);
+    for (input, out_dir) in inputs.iter().zip(&out_dirs) {
+        if *out_dir != watch::output_dir_for(output, input) && !quiet {
+            eprintln!(
+                "{} {} shares its name with an earlier input; writing to {}",
+                "Note:".yellow(),
+                input.display(),
+                out_dir.display()
+            );
+        }
+    }
+
+    let mut failures = Vec::new();
+    for (input, out_dir) in inputs.iter().zip(&out_dirs) {
+        pb.set_message(input.display().to_string());
+        let outcome = convert_one(
+            input,
+            out_dir,
+            &formats,
+            cleanup,
+            encrypt_key,
+            limits.as_ref(),
+        );
+        if outcome != sandbox::Outcome::Ok {
+            failures.push((input, outcome));
+        }
+        pb.inc(1);
+    }
+    pb.finish_and_clear();
+
+    let count = |status: &str| {
+        failures
+            .iter()
+            .filter(|(_, o)| o.status() == status)
+            .count()
+    };
+    let summary = format!(
+        "{} converted, {} failed, {} crashed, {} timed out",
+        inputs.len() - failures.len(),
+        count("error"),
+        count("crash"),
+        count("timeout")
+    );
+    if failures.is_empty() {
+        println!("{} {}", "Done:".green(), summary);
+    } else {
+        eprintln!("{} {}", "Done:".yellow(), summary);
+        for (input, outcome) in &failures {
+            eprintln!("  {:<7} {}: {}", outcome.status(), input.display(), outcome);
+        }
+    }
+
+    Ok(!failures.is_empty())
+}
+
+fn cmd_watch(
+    config: watch::WatchConfig,
+    formats: Vec<String>,
+    cleanup: Option<CleanupLevel>,
+    encrypt_key: Option<&crypt::OutputKey>,
+    sandbox: &sandbox::SandboxArgs,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let limits = sandbox.limits();
+    watch::run(&config, |input, out_dir| {
+        match convert_one(
+            input,
+            out_dir,
+            &formats,
+            cleanup,
+            encrypt_key,
+            limits.as_ref(),
+        ) {
+            sandbox::Outcome::Ok => Ok(()),
+            outcome => Err(outcome.to_string()),
+        }
+    })?;
+    Ok(false)
+}
+
+fn cmd_info_metadata(input: &Path, json: bool) -> Result<bool, Box<dyn std::error::Error>> {
+    let info = unpdf::peek(input)?;
+    if json {
+        let mut report = serde_json::to_value(&info)?;
+        report["file"] = input.display().to_string().into();
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(false);
+    }
+
+    let meta = &info.metadata;
+    println!("{}", "Document Information".cyan().bold());
+    println!("{}", "─".repeat(40).dimmed());
+    println!("{}: {}", "File".bold(), input.display());
+    println!("{}: {} bytes", "Size".bold(), info.file_size);
+    println!("{}: PDF {}", "Format".bold(), meta.pdf_version);
+    println!("{}: {}", "Pages".bold(), meta.page_count);
+    println!(
+        "{}: {}",
+        "Encrypted".bold(),
+        if meta.encrypted { "Yes" } else { "No" }
+    );
+    let fields = [
+        ("Title", &meta.title),
+        ("Author", &meta.author),
+        ("Creator", &meta.creator),
+        ("Producer", &meta.producer),
+        ("Language", &meta.language),
+    ];
+    for (name, value) in fields {
+        if let Some(value) = value {
+            println!("{}: {}", name.bold(), value);
+        }
+    }
+    if let Some(ref created) = meta.created {
+        println!("{}: {}", "Created".bold(), created);
+    }
+    if let Some(ref modified) = meta.modified {
+        println!("{}: {}", "Modified".bold(), modified);
+    }
+    println!("{}: {}", "Bookmarks".bold(), info.outline_count);
+    Ok(false)
+}
+
+fn cmd_info(input: &Path, json: bool, quiet: bool) -> Result<bool, Box<dyn std::error::Error>> {
     // Use lenient mode for info command - we want to show metadata even if text extraction fails
     let options = ParseOptions::new().lenient();
-    let doc = parse_file_with_options(input, options)?;
+    let parser = PdfParser::open_with_options(input, options)?;
+    let doc = parser.parse()?;
     let had_warnings = check_quality(&doc, quiet);
 
+    let text = doc.plain_text();
+    let words: usize = text.split_whitespace().count();
+    let chars = text.chars().count();
+    let images = doc.resources.values().filter(|r| r.is_image()).count();
+    let inferred = doc.inferred_properties();
+    let tables = inferred.table_count;
+    let bookmarks = doc.outline.as_ref().map(|o| o.total_items());
+
+    if json {
+        let quality = &doc.extraction_quality;
+        let report = serde_json::json!({
+            "file": input.display().to_string(),
+            "metadata": doc.metadata,
+            "inferred": inferred,
+            "stats": {
+                "words": words,
+                "characters": chars,
+                "images": images,
+                "tables": tables,
+            },
+            "fonts": parser.font_names(),
+            "outline_count": bookmarks.unwrap_or(0),
+            "revisions": doc.revisions,
+            "quality": {
+                "good": quality.is_good(),
+                "scanned": quality.is_scan_pdf,
+                "encrypted": quality.encrypted,
+                "replacement_char_ratio": quality.replacement_char_ratio(),
+                "suppressed_ocr_pages": quality.suppressed_ocr_pages,
+                "warning": quality.warning_message(),
+            },
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(had_warnings);
+    }
+
     println!("{}", "Document Information".cyan().bold());
     println!("{}", "─".repeat(40).dimmed());
 
@@ -688,6 +1789,20 @@ fn cmd_info(input: &Path, quiet: bool) -> Result<bool, Box<dyn std::error::Error
         "Encrypted".bold(),
         if doc.metadata.encrypted { "Yes" } else { "No" }
     );
+    if !doc.revisions.is_empty() {
+        let revised = doc.revised_pages();
+        if revised.is_empty() {
+            println!("{}: {}", "Revisions".bold(), doc.revisions.len());
+        } else {
+            let pages: Vec<String> = revised.iter().map(u32::to_string).collect();
+            println!(
+                "{}: {} (content replaced on page {})",
+                "Revisions".bold(),
+                doc.revisions.len(),
+                pages.join(", ")
+            );
+        }
+    }
 
     if let Some(ref title) = doc.metadata.title {
         println!("{}: {}", "Title".bold(), title);
@@ -709,25 +1824,221 @@ fn cmd_info(input: &Path, quiet: bool) -> Result<bool, Box<dyn std::error::Error
     }
 
     println!();
-    println!("{}", "Content Statistics".cyan().bold());
+    println!("{}", "Declared vs Inferred".cyan().bold());
     println!("{}", "─".repeat(40).dimmed());
+    let none = || "-".to_string();
+    let columns: Vec<String> = inferred
+        .columns
+        .iter()
+        .map(|(n, pages)| {
+            format!(
+                "{}-column: {} page{}",
+                n,
+                pages,
+                if *pages == 1 { "" } else { "s" }
+            )
+        })
+        .collect();
+    let rows = [
+        ("Property", "Declared".to_string(), "Inferred".to_string()),
+        (
+            "Title",
+            doc.metadata.title.clone().unwrap_or_else(none),
+            inferred.title.clone().unwrap_or_else(none),
+        ),
+        (
+            "Language",
+            doc.metadata.language.clone().unwrap_or_else(none),
+            inferred.language.clone().unwrap_or_else(none),
+        ),
+        (
+            "Body font size",
+            none(),
+            inferred
+                .body_font_size
+                .map_or_else(none, |s| format!("{:.1}pt", s)),
+        ),
+        (
+            "Columns",
+            none(),
+            if columns.is_empty() {
+                none()
+            } else {
+                columns.join(", ")
+            },
+        ),
+        ("Tables", none(), inferred.table_count.to_string()),
+    ];
+    let width = rows
+        .iter()
+        .map(|(_, d, _)| d.width())
+        .max()
+        .unwrap_or(0)
+        .min(40);
+    for (i, (name, declared, found)) in rows.iter().enumerate() {
+        // 한글 제목도 열이 맞도록 글자 수가 아니라 터미널 폭으로 채운다.
+        let fill = " ".repeat(width.saturating_sub(declared.width()));
+        let line = format!("{:<16}{}{}  {}", name, declared, fill, found);
+        if i == 0 {
+            println!("{}", line.dimmed());
+        } else {
+            println!("{}", line);
+        }
+    }
 
-    let text = doc.plain_text();
-    let words: usize = text.split_whitespace().count();
-    let chars = text.len();
-    let images = doc.resources.values().filter(|r| r.is_image()).count();
+    println!();
+    println!("{}", "Content Statistics".cyan().bold());
+    println!("{}", "─".repeat(40).dimmed());
 
     println!("{}: {}", "Words".bold(), words);
     println!("{}: {}", "Characters".bold(), chars);
     println!("{}: {}", "Images".bold(), images);
+    println!("{}: {}", "Tables".bold(), tables);
+
+    if let Some(count) = bookmarks {
+        println!("{}: {}", "Bookmarks".bold(), count);
+    }
 
-    if let Some(ref outline) = doc.outline {
-        println!("{}: {}", "Bookmarks".bold(), outline.total_items());
+    let fonts = parser.font_names();
+    if !fonts.is_empty() {
+        println!("{}: {}", "Fonts".bold(), fonts.join(", "));
     }
 
     Ok(had_warnings)
 }
 
+/// Outcome of `unpdf validate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValidationStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl ValidationStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Warn => "warn",
+            Self::Fail => "fail",
+        }
+    }
+
+    fn colored(self) -> colored::ColoredString {
+        match self {
+            Self::Pass => "PASS".green().bold(),
+            Self::Warn => "WARN".yellow().bold(),
+            Self::Fail => "FAIL".red().bold(),
+        }
+    }
+}
+
+/// 열 수 없거나 모든 페이지가 실패하면 fail, 손상 흔적이 있으면 warn.
+fn validation_status(
+    structure: &unpdf::parser::backend::StructureReport,
+    page_count: u32,
+    failed_pages: usize,
+) -> ValidationStatus {
+    if page_count == 0 || failed_pages >= page_count as usize {
+        ValidationStatus::Fail
+    } else if failed_pages > 0 || !structure.is_clean() {
+        ValidationStatus::Warn
+    } else {
+        ValidationStatus::Pass
+    }
+}
+
+fn cmd_validate(input: &Path, json: bool) -> Result<bool, Box<dyn std::error::Error>> {
+    let parser = match PdfParser::open(input) {
+        Ok(parser) => parser,
+        Err(e) => {
+            let (code, kind) = exit::classify(&e);
+            if json {
+                let report = serde_json::json!({
+                    "file": input.display().to_string(),
+                    "status": ValidationStatus::Fail.as_str(),
+                    "error": { "kind": kind, "code": code, "message": e.to_string() },
+                });
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                println!("{}: {}", ValidationStatus::Fail.colored(), input.display());
+            }
+            return Err(e.into());
+        }
+    };
+
+    let structure = parser.structure_report();
+    let page_count = parser.page_count();
+    let mut failed: Vec<(u32, String)> = Vec::new();
+    parser.for_each_page(PageStreamOptions::default(), |ev| {
+        if let ParseEvent::PageFailed { page, error } = ev {
+            failed.push((page, error.to_string()));
+        }
+        std::ops::ControlFlow::Continue(())
+    })?;
+    let status = validation_status(&structure, page_count, failed.len());
+
+    if json {
+        let failed_pages: Vec<_> = failed
+            .iter()
+            .map(|(page, error)| serde_json::json!({ "page": page, "error": error }))
+            .collect();
+        let report = serde_json::json!({
+            "file": input.display().to_string(),
+            "status": status.as_str(),
+            "version": parser.version(),
+            "encrypted": parser.is_encrypted(),
+            "pages": page_count,
+            "broken_xref_entries": structure.broken_xref_entries,
+            "unsupported_filters": structure.unsupported_filters,
+            "page_tree_damage": (!structure.page_tree.is_clean())
+                .then(|| structure.page_tree.to_string()),
+            "failed_pages": failed_pages,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}: {}", status.colored(), input.display());
+        println!("{}: PDF {}", "Format".bold(), parser.version());
+        println!(
+            "{}: {}",
+            "Encrypted".bold(),
+            if parser.is_encrypted() { "Yes" } else { "No" }
+        );
+        println!("{}: {}", "Pages".bold(), page_count);
+        if structure.broken_xref_entries > 0 {
+            println!(
+                "{}: {} unreadable object(s)",
+                "Damaged xref".bold(),
+                structure.broken_xref_entries
+            );
+        }
+        if !structure.unsupported_filters.is_empty() {
+            let filters: Vec<String> = structure
+                .unsupported_filters
+                .iter()
+                .map(|(name, count)| format!("{} ({})", name, count))
+                .collect();
+            println!("{}: {}", "Unsupported filters".bold(), filters.join(", "));
+        }
+        if !structure.page_tree.is_clean() {
+            println!("{}: {}", "Damaged page tree".bold(), structure.page_tree);
+        }
+        for (page, error) in &failed {
+            println!("{}: page {}: {}", "Failed".bold(), page, error);
+        }
+    }
+
+    if status == ValidationStatus::Fail {
+        let reason = if page_count == 0 {
+            "document has no pages".to_string()
+        } else {
+            format!("text extraction failed on all {} pages", page_count)
+        };
+        return Err(unpdf::Error::TextExtract(reason).into());
+    }
+    Ok(status == ValidationStatus::Warn)
+}
+
 fn cmd_extract(
     input: &Path,
     output: Option<&Path>,
@@ -750,10 +2061,11 @@ fn cmd_extract(
         .unwrap_or_else(|| PathBuf::from("."));
     fs::create_dir_all(&output_dir)?;
 
+    let mut names = SafeFileNames::new();
     let mut count = 0;
-    for (id, resource) in &doc.resources {
+    for (id, resource) in doc.resources_in_page_order() {
         if resource.is_image() {
-            let filename = resource.suggested_filename(id);
+            let filename = names.claim(&resource.suggested_filename(id));
             let path = output_dir.join(&filename);
             fs::write(&path, &resource.data)?;
             println!("{} {}", "Extracted".green(), filename);
@@ -766,6 +2078,149 @@ fn cmd_extract(
     Ok(had_warnings)
 }
 
+fn cmd_figures(
+    input: &Path,
+    output: Option<&Path>,
+    pages: Option<&str>,
+    quiet: bool,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let page_selection = if let Some(p) = pages {
+        PageSelection::parse(p).map_err(|e| format!("Invalid page range: {}", e))?
+    } else {
+        PageSelection::All
+    };
+
+    let options = ParseOptions::new().lenient().with_pages(page_selection);
+    let doc = parse_file_with_options(input, options)?;
+    let had_warnings = check_quality(&doc, quiet);
+
+    let output_dir = output
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."));
+    fs::create_dir_all(&output_dir)?;
+
+    let mut names = SafeFileNames::new();
+    let (mut count, mut captioned) = (0, 0);
+    for figure in doc.figures() {
+        let Some(resource) = figure.resource.filter(|r| !r.data.is_empty()) else {
+            continue;
+        };
+        // 이미지 id 에는 이미 확장자가 붙어 있다 (`page3_Im7.jpg`).
+        let filename = names.claim(figure.resource_id);
+        let stem = filename
+            .rsplit_once('.')
+            .map_or(filename.as_str(), |(stem, _)| stem);
+        fs::write(output_dir.join(&filename), &resource.data)?;
+        fs::write(
+            output_dir.join(names.claim(&format!("{}.json", stem))),
+            serde_json::to_string_pretty(&figure)?,
+        )?;
+        match &figure.caption {
+            Some(caption) => {
+                println!("{} {} — {}", "Extracted".green(), filename, caption);
+                captioned += 1;
+            }
+            None => println!("{} {}", "Extracted".green(), filename),
+        }
+        count += 1;
+    }
+
+    println!(
+        "\n{} {} figures extracted, {} with captions",
+        "Done!".green().bold(),
+        count,
+        captioned
+    );
+
+    Ok(had_warnings)
+}
+
+fn cmd_clean(
+    input: &Path,
+    output: Option<&Path>,
+    cleanup: CleanupLevel,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let bytes = if input == Path::new("-") {
+        let mut bytes = Vec::new();
+        std::io::Read::read_to_end(&mut std::io::stdin(), &mut bytes)?;
+        bytes
+    } else {
+        fs::read(input)?
+    };
+    let text = unpdf::cleanup_text(&String::from_utf8_lossy(&bytes), cleanup.into());
+
+    if let Some(path) = output {
+        fs::write(path, &text)?;
+        println!("{} {}", "Saved to".green(), path.display());
+    } else {
+        print!("{}", text);
+    }
+    Ok(false)
+}
+
+fn cmd_decrypt(
+    input: &Path,
+    output: Option<&Path>,
+    key: &crypt::OutputKey,
+    quiet: bool,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let (root, files) = if input.is_dir() {
+        let mut files = Vec::new();
+        collect_encrypted(input, &mut files)?;
+        (input, files)
+    } else {
+        (
+            input.parent().unwrap_or(Path::new("")),
+            vec![input.to_path_buf()],
+        )
+    };
+    if files.is_empty() {
+        return Err(format!("no .{} files in {}", crypt::EXTENSION, input.display()).into());
+    }
+
+    for file in &files {
+        let plain = crypt::decrypted_path(file)
+            .ok_or_else(|| format!("{}: not a .{} file", file.display(), crypt::EXTENSION))?;
+        let target = match output {
+            Some(dir) => dir.join(plain.strip_prefix(root).unwrap_or(&plain)),
+            None => plain,
+        };
+        let data = key
+            .decrypt(&fs::read(file)?)
+            .map_err(|e| format!("{}: {}", file.display(), e))?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, data)?;
+        if !quiet {
+            println!("{} {}", "✓".green(), target.display());
+        }
+    }
+    Ok(false)
+}
+
+fn collect_encrypted(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|e| e.path());
+    for entry in entries {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_encrypted(&path, files)?;
+        } else if crypt::decrypted_path(&path).is_some() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn cmd_schema(output: Option<&Path>) -> Result<bool, Box<dyn std::error::Error>> {
+    match output {
+        Some(path) => fs::write(path, unpdf::JSON_SCHEMA)?,
+        None => print!("{}", unpdf::JSON_SCHEMA),
+    }
+    Ok(false)
+}
+
 fn cmd_version() {
     println!("{} {}", "unpdf".cyan().bold(), env!("CARGO_PKG_VERSION"));
     println!("PDF content extraction tool");
@@ -0,0 +1,253 @@
+//! `unpdf preview`: Markdown styled for the terminal and shown in a pager.
+//!
+//! 렌더러가 만드는 Markdown만 다루면 되므로 완전한 파서 대신 줄 단위로
+//! 제목, 표, 코드 블록, 주석, 구분선만 알아본다.
+
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use colored::Colorize;
+use unicode_width::UnicodeWidthStr;
+
+/// Width of the rule drawn for `---`.
+const RULE_WIDTH: usize = 40;
+
+/// Style `markdown` for the terminal: headings bold, tables aligned, code,
+/// comments and page markers dimmed. Colors follow `colored`'s terminal
+/// detection, so piped output is plain but still aligned.
+pub fn style_markdown(markdown: &str) -> String {
+    let mut out = String::with_capacity(markdown.len());
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut in_code = false;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            in_code = !in_code;
+            out.push_str(&format!("{}\n", line.dimmed()));
+        } else if in_code {
+            out.push_str(&format!("{}\n", line.cyan()));
+        } else if line.starts_with('|') {
+            let end = (i..lines.len())
+                .find(|&j| !lines[j].starts_with('|'))
+                .unwrap_or(lines.len());
+            out.push_str(&style_table(&lines[i..end]));
+            i = end;
+            continue;
+        } else if let Some((level, title)) = heading(line) {
+            let title = strip_emphasis(title);
+            let styled = match level {
+                1 => title.bold().underline(),
+                _ => title.bold(),
+            };
+            out.push_str(&format!("{}\n", styled));
+        } else if line.trim_start().starts_with("<!--") {
+            out.push_str(&format!("{}\n", line.dimmed()));
+        } else if matches!(line.trim(), "---" | "***" | "___") {
+            out.push_str(&format!("{}\n", "─".repeat(RULE_WIDTH).dimmed()));
+        } else {
+            out.push_str(&style_inline(line));
+            out.push('\n');
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Show `text` through `$PAGER` (or `less`) when stdout is a terminal,
+/// otherwise print it. A pager that cannot be started falls back to
+/// printing.
+pub fn page(text: &str) -> io::Result<()> {
+    if io::stdout().is_terminal() {
+        if let Some(mut child) = spawn_pager() {
+            if let Some(mut stdin) = child.stdin.take() {
+                // 사용자가 페이저를 먼저 닫으면 쓰기가 끊기는데, 오류가 아니다.
+                match stdin.write_all(text.as_bytes()) {
+                    Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e),
+                    _ => {}
+                }
+            }
+            child.wait()?;
+            return Ok(());
+        }
+    }
+    let mut stdout = io::stdout().lock();
+    match stdout.write_all(text.as_bytes()) {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Columns of the terminal stdout writes to, `None` when stdout is not a
+/// terminal. `$COLUMNS` takes precedence; when the window size cannot be
+/// read 80 is assumed.
+pub fn terminal_width() -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    let columns = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.trim().parse().ok())
+        .filter(|&c| c > 0);
+    Some(columns.or_else(window_columns).unwrap_or(80))
+}
+
+#[cfg(unix)]
+fn window_columns() -> Option<usize> {
+    let mut size = libc::winsize {
+        ws_row: 0,
+        ws_col: 0,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: TIOCGWINSZ only fills in the winsize struct it is given.
+    let ok = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0;
+    (ok && size.ws_col > 0).then_some(usize::from(size.ws_col))
+}
+
+#[cfg(not(unix))]
+fn window_columns() -> Option<usize> {
+    None
+}
+
+fn spawn_pager() -> Option<std::process::Child> {
+    let pager = std::env::var("PAGER").ok().filter(|p| !p.trim().is_empty());
+    let default = if cfg!(windows) {
+        "more"
+    } else {
+        "less -R -F -X"
+    };
+    let command = pager.as_deref().unwrap_or(default);
+    let mut parts = command.split_whitespace();
+    let mut cmd = Command::new(parts.next()?);
+    cmd.args(parts).stdin(Stdio::piped());
+    cmd.spawn().ok()
+}
+
+/// Heading level and title of an ATX heading line.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let title = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then(|| (level, title.trim()))
+}
+
+/// `**bold**` spans in bold, other text as is.
+fn style_inline(line: &str) -> String {
+    let mut out = String::new();
+    let mut parts = line.split("**");
+    if let Some(first) = parts.next() {
+        out.push_str(first);
+    }
+    let rest: Vec<&str> = parts.collect();
+    // 짝이 맞지 않는 마지막 `**` 는 그대로 둔다.
+    let paired = rest.len() - rest.len() % 2;
+    for (i, part) in rest.iter().enumerate() {
+        if i >= paired {
+            out.push_str("**");
+            out.push_str(part);
+        } else if i % 2 == 0 {
+            out.push_str(&part.bold().to_string());
+        } else {
+            out.push_str(part);
+        }
+    }
+    out
+}
+
+fn strip_emphasis(text: &str) -> String {
+    text.replace("**", "")
+}
+
+/// Table rows padded to column widths, with box-drawing borders; the row
+/// before the `|---|` separator is the header.
+fn style_table(lines: &[&str]) -> String {
+    let rows: Vec<Vec<String>> = lines.iter().map(|line| split_cells(line)).collect();
+    let is_separator = |row: &Vec<String>| {
+        !row.is_empty()
+            && row
+                .iter()
+                .all(|c| !c.is_empty() && c.chars().all(|ch| matches!(ch, '-' | ':')))
+    };
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut widths = vec![0; columns];
+    for row in rows.iter().filter(|row| !is_separator(row)) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.width());
+        }
+    }
+
+    let mut out = String::new();
+    for (index, row) in rows.iter().enumerate() {
+        if is_separator(row) {
+            let rule: Vec<String> = widths.iter().map(|&w| "─".repeat(w + 2)).collect();
+            out.push_str(&format!("├{}┤\n", rule.join("┼")));
+            continue;
+        }
+        let header = rows.get(index + 1).is_some_and(is_separator);
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(col, &width)| {
+                let cell = row.get(col).map_or("", String::as_str);
+                let padded = format!(" {}{} ", cell, " ".repeat(width - cell.width()));
+                if header {
+                    padded.bold().to_string()
+                } else {
+                    padded
+                }
+            })
+            .collect();
+        out.push_str(&format!("│{}│\n", cells.join("│")));
+    }
+    out
+}
+
+/// Cells of a `| a | b |` row, with `\|` unescaped.
+fn split_cells(line: &str) -> Vec<String> {
+    let inner = line.trim().trim_start_matches('|');
+    let inner = inner
+        .strip_suffix('|')
+        .filter(|s| !s.ends_with('\\'))
+        .unwrap_or(inner);
+    let mut cells = vec![String::new()];
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cells.last_mut().unwrap().push('|');
+                chars.next();
+            }
+            '|' => cells.push(String::new()),
+            c => cells.last_mut().unwrap().push(c),
+        }
+    }
+    cells.iter().map(|c| strip_emphasis(c.trim())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_align_to_display_width() {
+        colored::control::set_override(false);
+        let styled = style_markdown("# Report\n\n| 이름 | Qty |\n|---|--:|\n| a \\| b | 12 |\n");
+        assert_eq!(
+            styled,
+            "Report\n\n\
+             │ 이름  │ Qty │\n\
+             ├───────┼─────┤\n\
+             │ a | b │ 12  │\n"
+        );
+    }
+
+    #[test]
+    fn test_inline_bold_and_rules() {
+        colored::control::set_override(false);
+        assert_eq!(style_inline("a **b** c **d"), "a b c **d");
+        assert_eq!(
+            style_markdown("---\n<!-- page 2 -->"),
+            format!("{}\n<!-- page 2 -->\n", "─".repeat(RULE_WIDTH))
+        );
+    }
+}
@@ -0,0 +1,397 @@
+//! `unpdf profile`: time and memory per conversion stage, the slowest pages
+//! and page characteristics that usually explain them.
+//!
+//! 메모리는 CLI 전역 할당자가 세는 힙 바이트로 측정한다 (RSS 가 아님).
+//! 다른 명령은 세지 않고 시스템 할당자를 그대로 쓴다.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicIsize, Ordering};
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use serde_json::json;
+use unpdf::{Document, PageStreamOptions, ParseEvent, ParseOptions, PdfParser, RenderOptions};
+
+/// A page whose decoded content streams exceed this is reported.
+const HUGE_CONTENT_BYTES: usize = 1024 * 1024;
+
+/// A page drawing at least this many XObjects is reported.
+const MANY_XOBJECT_DRAWS: u32 = 1000;
+
+/// Set by `unpdf profile` only; until then allocation is a plain passthrough.
+static COUNTING: AtomicBool = AtomicBool::new(false);
+
+// 계수 시작 전에 잡힌 블록이 해제되면 음수가 될 수 있다. 스테이지는 차이만 본다.
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+/// The system allocator, counting live heap bytes and their high-water mark
+/// while `unpdf profile` runs.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn grow(bytes: usize) {
+        if !COUNTING.load(Ordering::Relaxed) {
+            return;
+        }
+        let bytes = bytes as isize;
+        let now = CURRENT.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        PEAK.fetch_max(now, Ordering::Relaxed);
+    }
+
+    fn shrink(bytes: usize) {
+        if !COUNTING.load(Ordering::Relaxed) {
+            return;
+        }
+        CURRENT.fetch_sub(bytes as isize, Ordering::Relaxed);
+    }
+}
+
+/// Start counting heap use in [`CountingAlloc`].
+fn enable_counting() {
+    COUNTING.store(true, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            if new_size > layout.size() {
+                Self::grow(new_size - layout.size());
+            } else {
+                Self::shrink(layout.size() - new_size);
+            }
+        }
+        new
+    }
+}
+
+/// Time and heap use of one stage.
+struct Stage {
+    name: &'static str,
+    elapsed: Duration,
+    /// Highest heap use during the stage, above the use at its start.
+    peak: usize,
+    /// Heap use at the end of the stage minus the use at its start.
+    retained: isize,
+}
+
+/// Measures consecutive stages.
+struct StageClock {
+    started: Instant,
+    heap: isize,
+    stages: Vec<Stage>,
+}
+
+impl StageClock {
+    fn start() -> Self {
+        let heap = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(heap, Ordering::Relaxed);
+        Self {
+            started: Instant::now(),
+            heap,
+            stages: Vec::new(),
+        }
+    }
+
+    /// End the running stage as `name` and start the next one.
+    fn lap(&mut self, name: &'static str) {
+        let heap = CURRENT.load(Ordering::Relaxed);
+        let peak = PEAK.swap(heap, Ordering::Relaxed);
+        self.stages.push(Stage {
+            name,
+            elapsed: self.started.elapsed(),
+            peak: (peak - self.heap).max(0) as usize,
+            retained: heap - self.heap,
+        });
+        self.started = Instant::now();
+        self.heap = heap;
+    }
+}
+
+/// One parsed (or failed) page.
+struct PageProfile {
+    number: u32,
+    elapsed: Duration,
+    content_bytes: Option<usize>,
+    xobject_draws: u32,
+    skipped_bytes: u32,
+    error: Option<String>,
+}
+
+impl PageProfile {
+    /// What makes the page suspicious, if anything.
+    fn findings(&self) -> Vec<String> {
+        let mut findings = Vec::new();
+        if let Some(error) = &self.error {
+            findings.push(format!("failed: {}", error));
+        }
+        if let Some(bytes) = self.content_bytes.filter(|&b| b >= HUGE_CONTENT_BYTES) {
+            findings.push(format!("huge content stream ({})", format_bytes(bytes)));
+        }
+        if self.xobject_draws >= MANY_XOBJECT_DRAWS {
+            findings.push(format!("{} XObject draws", self.xobject_draws));
+        }
+        if self.skipped_bytes > 0 {
+            findings.push(format!(
+                "{} of undecodable content skipped",
+                format_bytes(self.skipped_bytes as usize)
+            ));
+        }
+        findings
+    }
+}
+
+/// Profile the conversion of `input` to Markdown and print the report.
+pub fn run(input: &Path, top: usize, json: bool) -> Result<bool, Box<dyn std::error::Error>> {
+    enable_counting();
+    let total = Instant::now();
+    let mut clock = StageClock::start();
+
+    let options = ParseOptions::new().lenient();
+    let parser = PdfParser::open_with_options(input, options.clone())?;
+    clock.lap("open");
+
+    // 페이지별 시간을 재려면 순차 파싱이어야 한다.
+    let mut stream = PageStreamOptions::from(&options);
+    stream.parallel = false;
+
+    let mut doc = Document::new();
+    let mut pages: Vec<PageProfile> = Vec::new();
+    let mut page_started = Instant::now();
+    let quality = parser.for_each_page(stream, |ev| {
+        match ev {
+            ParseEvent::DocumentStart {
+                metadata,
+                outline,
+                form_fields,
+                ..
+            } => {
+                doc.metadata = metadata;
+                doc.outline = outline;
+                doc.form_fields = form_fields;
+                clock.lap("document");
+            }
+            ParseEvent::PageParsed(page) => {
+                pages.push(PageProfile {
+                    number: page.number,
+                    elapsed: page_started.elapsed(),
+                    content_bytes: None,
+                    xobject_draws: page.image_op_count,
+                    skipped_bytes: page.skipped_content_bytes,
+                    error: None,
+                });
+                doc.add_page(page);
+            }
+            ParseEvent::PageFailed { page, error } => pages.push(PageProfile {
+                number: page,
+                elapsed: page_started.elapsed(),
+                content_bytes: None,
+                xobject_draws: 0,
+                skipped_bytes: 0,
+                error: Some(error.to_string()),
+            }),
+            ParseEvent::Progress { .. } | ParseEvent::DocumentEnd { .. } => {}
+        }
+        page_started = Instant::now();
+        ControlFlow::Continue(())
+    })?;
+    doc.extraction_quality = quality;
+    clock.lap("pages");
+
+    let markdown = unpdf::render::to_markdown(&doc, &RenderOptions::new())?;
+    clock.lap("render");
+    let elapsed = total.elapsed();
+
+    // 측정이 끝난 뒤에 콘텐츠 스트림 크기를 따로 잰다.
+    for page in &mut pages {
+        page.content_bytes = parser.page_content_len(page.number).ok();
+    }
+
+    let mut slowest: Vec<&PageProfile> = pages.iter().collect();
+    slowest.sort_by(|a, b| b.elapsed.cmp(&a.elapsed).then(a.number.cmp(&b.number)));
+    slowest.truncate(top);
+    let suspicious: Vec<(u32, Vec<String>)> = pages
+        .iter()
+        .map(|p| (p.number, p.findings()))
+        .filter(|(_, findings)| !findings.is_empty())
+        .collect();
+
+    if json {
+        let stages: Vec<_> = clock
+            .stages
+            .iter()
+            .map(|s| {
+                json!({
+                    "stage": s.name,
+                    "ms": millis(s.elapsed),
+                    "peak_bytes": s.peak,
+                    "retained_bytes": s.retained,
+                })
+            })
+            .collect();
+        let slowest: Vec<_> = slowest
+            .iter()
+            .map(|p| {
+                json!({
+                    "page": p.number,
+                    "ms": millis(p.elapsed),
+                    "content_bytes": p.content_bytes,
+                    "xobject_draws": p.xobject_draws,
+                })
+            })
+            .collect();
+        let suspicious: Vec<_> = suspicious
+            .iter()
+            .map(|(page, findings)| json!({ "page": page, "findings": findings }))
+            .collect();
+        let report = json!({
+            "file": input.display().to_string(),
+            "pages": doc.pages.len(),
+            "failed_pages": pages.iter().filter(|p| p.error.is_some()).count(),
+            "output_bytes": markdown.len(),
+            "total_ms": millis(elapsed),
+            "stages": stages,
+            "slowest_pages": slowest,
+            "suspicious_pages": suspicious,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(false);
+    }
+
+    println!("{}", "Profile".cyan().bold());
+    println!("{}", "─".repeat(40).dimmed());
+    println!("{}: {}", "File".bold(), input.display());
+    println!("{}: {}", "Pages".bold(), doc.pages.len());
+    println!("{}: {}", "Output".bold(), format_bytes(markdown.len()));
+    println!();
+    println!(
+        "{:<10} {:>10} {:>12} {:>12}",
+        "Stage".bold(),
+        "Time".bold(),
+        "Peak heap".bold(),
+        "Retained".bold()
+    );
+    for stage in &clock.stages {
+        println!(
+            "{:<10} {:>10} {:>12} {:>12}",
+            stage.name,
+            format_duration(stage.elapsed),
+            format!("+{}", format_bytes(stage.peak)),
+            format_signed_bytes(stage.retained)
+        );
+    }
+    println!("{:<10} {:>10}", "total", format_duration(elapsed));
+
+    if !slowest.is_empty() {
+        println!();
+        println!("{}", "Slowest pages".bold());
+        for page in &slowest {
+            let content = page
+                .content_bytes
+                .map(format_bytes)
+                .unwrap_or_else(|| "?".to_string());
+            println!(
+                "  page {:<6} {:>10}  content {:>9}  {} XObject draws",
+                page.number,
+                format_duration(page.elapsed),
+                content,
+                page.xobject_draws
+            );
+        }
+    }
+
+    if !suspicious.is_empty() {
+        println!();
+        println!("{}", "Suspicious pages".yellow().bold());
+        for (page, findings) in &suspicious {
+            println!("  page {}: {}", page, findings.join(", "));
+        }
+    }
+
+    Ok(false)
+}
+
+fn millis(d: Duration) -> f64 {
+    (d.as_secs_f64() * 1000.0 * 10.0).round() / 10.0
+}
+
+fn format_duration(d: Duration) -> String {
+    if d.as_secs() >= 1 {
+        format!("{:.2} s", d.as_secs_f64())
+    } else {
+        format!("{:.1} ms", d.as_secs_f64() * 1000.0)
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const KIB: f64 = 1024.0;
+    let b = bytes as f64;
+    if b >= KIB * KIB {
+        format!("{:.1} MiB", b / (KIB * KIB))
+    } else if b >= KIB {
+        format!("{:.1} KiB", b / KIB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+fn format_signed_bytes(bytes: isize) -> String {
+    let sign = if bytes < 0 { '-' } else { '+' };
+    format!("{}{}", sign, format_bytes(bytes.unsigned_abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_findings() {
+        let mut page = PageProfile {
+            number: 3,
+            elapsed: Duration::from_millis(5),
+            content_bytes: Some(100),
+            xobject_draws: 10,
+            skipped_bytes: 0,
+            error: None,
+        };
+        assert!(page.findings().is_empty());
+
+        page.content_bytes = Some(3 * HUGE_CONTENT_BYTES);
+        page.xobject_draws = 2500;
+        assert_eq!(
+            page.findings(),
+            vec!["huge content stream (3.0 MiB)", "2500 XObject draws"]
+        );
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_signed_bytes(-2 * 1024 * 1024), "-2.0 MiB");
+    }
+}
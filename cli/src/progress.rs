@@ -0,0 +1,117 @@
+//! Conversion progress: an indicatif bar or machine-readable JSON lines.
+//!
+//! 진행 상황은 stdout 출력과 섞이지 않도록 모두 stderr 로 보낸다.
+
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde_json::json;
+
+/// How conversion progress is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProgressMode {
+    /// No progress output
+    None,
+    /// A progress bar with the current stage, pages/sec and ETA
+    Bar,
+    /// One JSON object per line on stderr
+    Json,
+}
+
+/// Progress of one conversion, reported per page and at each stage change.
+pub struct Progress {
+    mode: ProgressMode,
+    bar: ProgressBar,
+    stage: &'static str,
+    done: u32,
+    total: u32,
+    started: Instant,
+}
+
+impl Progress {
+    pub fn new(mode: ProgressMode, total: u32) -> Self {
+        let bar = if mode == ProgressMode::Bar {
+            let bar = ProgressBar::new(total as u64);
+            bar.set_style(
+                ProgressStyle::default_bar()
+                    .template("{msg:8} {bar:40.cyan/blue} {pos}/{len} pages {per_sec} ({eta})")
+                    .unwrap(),
+            );
+            bar
+        } else {
+            ProgressBar::hidden()
+        };
+        Self {
+            mode,
+            bar,
+            stage: "opening",
+            done: 0,
+            total,
+            started: Instant::now(),
+        }
+    }
+
+    /// Enter `stage` (`parsing`, `writing`, ...).
+    pub fn stage(&mut self, stage: &'static str) {
+        self.stage = stage;
+        self.bar.set_message(stage);
+        self.emit();
+    }
+
+    /// `done` of `total` pages have been parsed.
+    pub fn pages(&mut self, done: u32, total: u32) {
+        self.done = done;
+        self.total = total;
+        self.bar.set_length(total as u64);
+        self.bar.set_position(done as u64);
+        self.emit();
+    }
+
+    /// Report the end of the conversion.
+    pub fn finish(&mut self) {
+        self.stage("done");
+        self.bar.finish_and_clear();
+    }
+
+    fn emit(&self) {
+        if self.mode == ProgressMode::Json {
+            let line = progress_line(self.stage, self.done, self.total, self.started.elapsed());
+            eprintln!("{line}");
+        }
+    }
+}
+
+/// The JSON progress line for `done` of `total` pages after `elapsed`.
+/// Rate and ETA are `null` until the first page is done.
+fn progress_line(stage: &str, done: u32, total: u32, elapsed: Duration) -> serde_json::Value {
+    let secs = elapsed.as_secs_f64();
+    let rate = (done > 0 && secs > 0.0).then(|| done as f64 / secs);
+    let eta = rate.map(|rate| (total.saturating_sub(done) as f64 / rate * 10.0).round() / 10.0);
+    json!({
+        "stage": stage,
+        "done": done,
+        "total": total,
+        "elapsed_secs": (secs * 10.0).round() / 10.0,
+        "pages_per_sec": rate.map(|rate| (rate * 10.0).round() / 10.0),
+        "eta_secs": eta,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_line() {
+        let line = progress_line("parsing", 40, 100, Duration::from_secs(4));
+        assert_eq!(line["stage"], "parsing");
+        assert_eq!(line["done"], 40);
+        assert_eq!(line["pages_per_sec"], 10.0);
+        assert_eq!(line["eta_secs"], 6.0);
+
+        let start = progress_line("opening", 0, 100, Duration::ZERO);
+        assert!(start["pages_per_sec"].is_null());
+        assert!(start["eta_secs"].is_null());
+    }
+}
@@ -0,0 +1,281 @@
+//! Subprocess isolation for untrusted PDFs.
+//!
+//! 각 PDF를 자기 자신(`unpdf convert ...`)의 자식 프로세스로 변환한다. 파서가
+//! 패닉하거나, 메모리를 다 써버리거나, 끝나지 않아도 부모 프로세스(배치/감시
+//! 루프)는 결과만 기록하고 다음 파일로 넘어간다.
+
+use std::ffi::OsString;
+use std::fmt;
+use std::io::Read;
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::Args;
+
+use crate::exit;
+
+/// `--sandbox` and its limits, shared by the multi-file commands.
+#[derive(Args, Debug, Clone)]
+pub struct SandboxArgs {
+    /// Convert each PDF in a separate child process so a crash, hang or
+    /// runaway allocation only fails that file
+    #[arg(long)]
+    pub sandbox: bool,
+
+    /// Per-file time limit in sandbox mode (seconds)
+    #[arg(long, value_name = "SECS", default_value = "120")]
+    pub timeout: u64,
+
+    /// Per-file address-space cap in sandbox mode (MiB, Unix only; 0 = none)
+    #[arg(long, value_name = "MB", default_value = "2048")]
+    pub max_memory: u64,
+}
+
+impl SandboxArgs {
+    /// Limits for [`run`], or `None` when sandboxing is off.
+    pub fn limits(&self) -> Option<Limits> {
+        self.sandbox.then(|| Limits {
+            timeout: Duration::from_secs(self.timeout),
+            max_memory_mb: (self.max_memory > 0).then_some(self.max_memory),
+        })
+    }
+}
+
+/// Resource limits applied to one child process.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// Wall-clock limit; the child is killed when it expires
+    pub timeout: Duration,
+    /// Address-space cap in MiB (enforced with `RLIMIT_AS` on Unix)
+    pub max_memory_mb: Option<u64>,
+}
+
+/// How a sandboxed conversion ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Converted (possibly with quality warnings)
+    Ok,
+    /// The child reported an ordinary error, e.g. an invalid PDF
+    Failed(String),
+    /// The child died abnormally: panic, signal or allocation failure
+    Crashed(String),
+    /// The child exceeded the time limit and was killed
+    TimedOut(Duration),
+}
+
+impl Outcome {
+    /// Short status word used in summaries and logs.
+    pub fn status(&self) -> &'static str {
+        match self {
+            Outcome::Ok => "ok",
+            Outcome::Failed(_) => "error",
+            Outcome::Crashed(_) => "crash",
+            Outcome::TimedOut(_) => "timeout",
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Ok => write!(f, "ok"),
+            Outcome::Failed(msg) => write!(f, "{}", msg),
+            Outcome::Crashed(msg) => write!(f, "crashed: {}", msg),
+            Outcome::TimedOut(limit) => write!(f, "timed out after {}s", limit.as_secs()),
+        }
+    }
+}
+
+/// Run this executable with `args` and extra environment variables `envs`
+/// in a child process under `limits`.
+pub fn run(
+    args: &[OsString],
+    envs: &[(&str, String)],
+    limits: &Limits,
+) -> std::io::Result<Outcome> {
+    let mut command = Command::new(std::env::current_exe()?);
+    command.args(args).envs(envs.iter().map(|(k, v)| (k, v)));
+    run_command(command, limits)
+}
+
+/// Spawn `command` under `limits` and wait for it to finish or time out.
+pub fn run_command(mut command: Command, limits: &Limits) -> std::io::Result<Outcome> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    apply_memory_limit(&mut command, limits.max_memory_mb);
+
+    let mut child = command.spawn()?;
+
+    // stderr 파이프가 가득 차서 자식이 멈추지 않도록 별도 스레드에서 비운다.
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stderr.read_to_end(&mut buf);
+        String::from_utf8_lossy(&buf).into_owned()
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if started.elapsed() >= limits.timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+
+    Ok(match status {
+        // 손자 프로세스가 파이프를 쥐고 있을 수 있으므로 stderr를 기다리지 않는다.
+        None => Outcome::TimedOut(limits.timeout),
+        Some(status) => classify(status, &reader.join().unwrap_or_default()),
+    })
+}
+
+/// Map a child's exit status onto an [`Outcome`].
+///
+/// The CLI exits 0 on success, 5 when only quality warnings were printed and
+/// 1–4 on a reported error (see [`crate::exit`]); anything else (Rust panics
+/// exit 101, signals have no code) is a crash.
+fn classify(status: ExitStatus, stderr: &str) -> Outcome {
+    let last_line = || {
+        stderr
+            .lines()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or("")
+            .trim()
+            .to_string()
+    };
+    match status.code() {
+        Some(exit::OK | exit::PARTIAL_SUCCESS) => Outcome::Ok,
+        Some(exit::FAILURE..=exit::UNSUPPORTED_FORMAT) => Outcome::Failed(last_line()),
+        Some(code) => {
+            let detail = last_line();
+            if detail.is_empty() {
+                Outcome::Crashed(format!("exit code {}", code))
+            } else {
+                Outcome::Crashed(format!("exit code {}: {}", code, detail))
+            }
+        }
+        None => Outcome::Crashed(signal_description(status)),
+    }
+}
+
+#[cfg(unix)]
+fn signal_description(status: ExitStatus) -> String {
+    use std::os::unix::process::ExitStatusExt;
+    match status.signal() {
+        Some(sig) => format!("signal {}", sig),
+        None => "terminated".to_string(),
+    }
+}
+
+#[cfg(not(unix))]
+fn signal_description(_status: ExitStatus) -> String {
+    "terminated".to_string()
+}
+
+#[cfg(unix)]
+fn apply_memory_limit(command: &mut Command, max_memory_mb: Option<u64>) {
+    use std::os::unix::process::CommandExt;
+
+    let Some(mb) = max_memory_mb else {
+        return;
+    };
+    let bytes = mb.saturating_mul(1024 * 1024) as libc::rlim_t;
+    // SAFETY: setrlimit is async-signal-safe and touches no parent state.
+    unsafe {
+        command.pre_exec(move || {
+            let limit = libc::rlimit {
+                rlim_cur: bytes,
+                rlim_max: bytes,
+            };
+            if libc::setrlimit(libc::RLIMIT_AS, &limit) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn apply_memory_limit(_command: &mut Command, max_memory_mb: Option<u64>) {
+    if max_memory_mb.is_some() {
+        log::debug!("--max-memory is not enforced on this platform");
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn limits(secs: u64) -> Limits {
+        Limits {
+            timeout: Duration::from_secs(secs),
+            max_memory_mb: None,
+        }
+    }
+
+    fn sh(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(run_command(sh("exit 0"), &limits(5)).unwrap(), Outcome::Ok);
+        assert_eq!(run_command(sh("exit 5"), &limits(5)).unwrap(), Outcome::Ok);
+        assert_eq!(
+            run_command(
+                sh("echo 'Error: Document is encrypted' >&2; exit 3"),
+                &limits(5)
+            )
+            .unwrap(),
+            Outcome::Failed("Error: Document is encrypted".to_string())
+        );
+        assert_eq!(
+            run_command(sh("echo 'Error: not a PDF' >&2; exit 1"), &limits(5)).unwrap(),
+            Outcome::Failed("Error: not a PDF".to_string())
+        );
+        assert_eq!(
+            run_command(sh("exit 101"), &limits(5)).unwrap().status(),
+            "crash"
+        );
+    }
+
+    #[test]
+    fn test_signal_is_crash() {
+        let outcome = run_command(sh("kill -SEGV $$"), &limits(5)).unwrap();
+        assert_eq!(outcome, Outcome::Crashed("signal 11".to_string()));
+    }
+
+    #[test]
+    fn test_timeout_kills_child() {
+        let started = Instant::now();
+        let outcome = run_command(sh("sleep 30"), &limits(1)).unwrap();
+        assert_eq!(outcome, Outcome::TimedOut(Duration::from_secs(1)));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_limits_off_without_sandbox() {
+        let args = SandboxArgs {
+            sandbox: false,
+            timeout: 120,
+            max_memory: 0,
+        };
+        assert!(args.limits().is_none());
+        let on = SandboxArgs {
+            sandbox: true,
+            ..args
+        };
+        assert_eq!(on.limits().unwrap().max_memory_mb, None);
+    }
+}
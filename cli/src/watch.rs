@@ -0,0 +1,331 @@
+//! Hot-folder watch mode: convert PDFs as they appear or change.
+//!
+//! 파일 시스템 이벤트는 복사 도중에도 여러 번 발생하므로, 경로별 마지막 이벤트
+//! 이후 `debounce` 만큼 조용해진 파일만 변환한다.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+/// Name of the conversion log written into the output directory.
+pub const LOG_FILE: &str = "unpdf-watch.log";
+
+/// Settings for [`run`].
+pub struct WatchConfig {
+    /// Directory to watch
+    pub dir: PathBuf,
+    /// Output root; each PDF converts into `<out>/<stem>/`, under the same
+    /// subdirectory it has in `dir` when watching recursively
+    pub out: PathBuf,
+    /// Quiet period after the last event before a file is converted
+    pub debounce: Duration,
+    /// Watch subdirectories too
+    pub recursive: bool,
+    /// Convert PDFs already present at startup
+    pub initial_scan: bool,
+}
+
+/// Collects change events and releases each path once it has been quiet for
+/// the debounce period.
+pub struct Debouncer {
+    delay: Duration,
+    pending: HashMap<PathBuf, Instant>,
+}
+
+impl Debouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Record an event for `path` at `now`, restarting its quiet period.
+    pub fn note(&mut self, path: PathBuf, now: Instant) {
+        self.pending.insert(path, now);
+    }
+
+    /// Remove and return the paths that have been quiet long enough, sorted.
+    pub fn ready(&mut self, now: Instant) -> Vec<PathBuf> {
+        let mut ready: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, last)| now.duration_since(**last) >= self.delay)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in &ready {
+            self.pending.remove(path);
+        }
+        ready.sort();
+        ready
+    }
+}
+
+/// Whether `path` looks like a PDF by extension.
+pub fn is_pdf_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("pdf"))
+}
+
+/// Output directory for one input: `<out>/<stem>`.
+pub fn output_dir_for(out: &Path, input: &Path) -> PathBuf {
+    let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+    out.join(stem.as_ref())
+}
+
+/// Output directory for an input under the watched directory `dir`:
+/// `<out>/<subdir>/<stem>`, mirroring its place in `dir` so files of the same
+/// name in different subdirectories stay apart.
+pub fn mirrored_output_dir(out: &Path, dir: &Path, input: &Path) -> PathBuf {
+    let parent = input
+        .parent()
+        .and_then(|p| p.strip_prefix(dir).ok())
+        .unwrap_or(Path::new(""));
+    output_dir_for(&out.join(parent), input)
+}
+
+/// PDFs in `dir`, sorted, descending into subdirectories when `recursive`
+/// is set. Anything under `skip` (the output root) is left out.
+pub fn scan_pdfs(dir: &Path, recursive: bool, skip: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir)?.flatten() {
+            let path = entry.path();
+            if path.canonicalize().is_ok_and(|p| p.starts_with(skip)) {
+                continue;
+            }
+            if path.is_dir() && recursive {
+                dirs.push(path);
+            } else if path.is_file() && is_pdf_path(&path) {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Output directories for a batch of inputs, in input order: `<out>/<stem>`,
+/// with `-2`, `-3`, ... added to later inputs whose stem is already taken
+/// (compared case-insensitively), so files of the same name from different
+/// directories do not overwrite each other.
+pub fn unique_output_dirs(out: &Path, inputs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut taken = HashSet::new();
+    inputs
+        .iter()
+        .map(|input| {
+            let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+            let mut name = stem.to_string();
+            let mut n = 1;
+            while !taken.insert(name.to_lowercase()) {
+                n += 1;
+                name = format!("{}-{}", stem, n);
+            }
+            out.join(name)
+        })
+        .collect()
+}
+
+/// Watch `config.dir` until the process is interrupted, calling `convert`
+/// for each new or modified PDF and appending the outcome to the log.
+pub fn run<F>(config: &WatchConfig, mut convert: F) -> Result<(), Box<dyn std::error::Error>>
+where
+    F: FnMut(&Path, &Path) -> Result<(), String>,
+{
+    std::fs::create_dir_all(&config.out)?;
+    let log_path = config.out.join(LOG_FILE);
+    let out_root = config.out.canonicalize()?;
+    // 이벤트 경로는 절대 경로로 올 수 있으므로 정규화한 경로로 상대 위치를 구한다.
+    let dir_root = config.dir.canonicalize()?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    let mode = if config.recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    watcher.watch(&config.dir, mode)?;
+
+    let mut debouncer = Debouncer::new(config.debounce);
+    if config.initial_scan {
+        let now = Instant::now() - config.debounce;
+        for path in scan_pdfs(&config.dir, config.recursive, &out_root)? {
+            debouncer.note(path, now);
+        }
+    }
+
+    eprintln!(
+        "Watching {} (Ctrl-C to stop), log: {}",
+        config.dir.display(),
+        log_path.display()
+    );
+
+    let poll = config.debounce.min(Duration::from_millis(250));
+    loop {
+        match rx.recv_timeout(poll) {
+            Ok(Ok(event)) => {
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    let now = Instant::now();
+                    for path in event.paths {
+                        // 출력 디렉토리가 감시 대상 안에 있어도 되돌아 변환하지 않는다.
+                        let inside_out = path
+                            .canonicalize()
+                            .map(|p| p.starts_with(&out_root))
+                            .unwrap_or(false);
+                        if is_pdf_path(&path) && !inside_out {
+                            debouncer.note(path, now);
+                        }
+                    }
+                }
+            }
+            Ok(Err(e)) => log::warn!("watch error: {}", e),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+
+        for input in debouncer.ready(Instant::now()) {
+            // 이벤트 후 삭제되었거나 이름이 바뀐 파일은 건너뛴다.
+            if !input.is_file() {
+                continue;
+            }
+            let canonical = input.canonicalize().unwrap_or_else(|_| input.clone());
+            let out_dir = mirrored_output_dir(&config.out, &dir_root, &canonical);
+            let started = Instant::now();
+            let outcome = convert(&input, &out_dir);
+            let elapsed = started.elapsed().as_millis();
+            let line = match &outcome {
+                Ok(()) => format!(
+                    "{}\tok\t{}\t{}ms\t{}",
+                    timestamp(),
+                    input.display(),
+                    elapsed,
+                    out_dir.display()
+                ),
+                Err(e) => format!(
+                    "{}\terror\t{}\t{}ms\t{}",
+                    timestamp(),
+                    input.display(),
+                    elapsed,
+                    e.replace(['\n', '\t'], " ")
+                ),
+            };
+            eprintln!("{}", line);
+            let mut log = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&log_path)?;
+            writeln!(log, "{}", line)?;
+        }
+    }
+}
+
+fn timestamp() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debouncer_waits_for_quiet_period() {
+        let mut d = Debouncer::new(Duration::from_millis(500));
+        let t0 = Instant::now();
+        d.note(PathBuf::from("a.pdf"), t0);
+        assert!(d.ready(t0 + Duration::from_millis(300)).is_empty());
+
+        // A second event restarts the quiet period.
+        d.note(PathBuf::from("a.pdf"), t0 + Duration::from_millis(300));
+        assert!(d.ready(t0 + Duration::from_millis(600)).is_empty());
+        assert_eq!(
+            d.ready(t0 + Duration::from_millis(800)),
+            vec![PathBuf::from("a.pdf")]
+        );
+        assert!(d.ready(t0 + Duration::from_secs(5)).is_empty());
+    }
+
+    #[test]
+    fn test_is_pdf_path() {
+        assert!(is_pdf_path(Path::new("in/Report.PDF")));
+        assert!(!is_pdf_path(Path::new("in/report.pdf.part")));
+        assert!(!is_pdf_path(Path::new("in/readme")));
+    }
+
+    #[test]
+    fn test_recursive_watch_mirrors_subdirectories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("hot");
+        let out = dir.join("out");
+        for sub in ["x", "y", "out"] {
+            std::fs::create_dir_all(dir.join(sub)).unwrap();
+        }
+        for file in [
+            "top.pdf",
+            "x/a.pdf",
+            "y/a.pdf",
+            "x/notes.txt",
+            "out/done.pdf",
+        ] {
+            std::fs::write(dir.join(file), b"%PDF-1.4").unwrap();
+        }
+        let skip = out.canonicalize().unwrap();
+
+        // 평면 스캔은 하위 디렉토리의 PDF 를 건너뛴다.
+        let flat = scan_pdfs(&dir, false, &skip).unwrap();
+        assert_eq!(flat, vec![dir.join("top.pdf")]);
+
+        let all = scan_pdfs(&dir, true, &skip).unwrap();
+        assert_eq!(
+            all,
+            vec![
+                dir.join("top.pdf"),
+                dir.join("x/a.pdf"),
+                dir.join("y/a.pdf")
+            ]
+        );
+
+        let outputs: Vec<PathBuf> = all
+            .iter()
+            .map(|p| mirrored_output_dir(&out, &dir, p))
+            .collect();
+        assert_eq!(
+            outputs,
+            vec![out.join("top"), out.join("x/a"), out.join("y/a")]
+        );
+    }
+
+    #[test]
+    fn test_unique_output_dirs_suffix_repeated_stems() {
+        let inputs = [
+            PathBuf::from("a/report.pdf"),
+            PathBuf::from("b/report.pdf"),
+            PathBuf::from("c/Report.pdf"),
+            PathBuf::from("summary.pdf"),
+        ];
+        assert_eq!(
+            unique_output_dirs(Path::new("out"), &inputs),
+            vec![
+                PathBuf::from("out/report"),
+                PathBuf::from("out/report-2"),
+                PathBuf::from("out/Report-3"),
+                PathBuf::from("out/summary"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_output_dir_for() {
+        assert_eq!(
+            output_dir_for(Path::new("out"), Path::new("hot/q3-report.pdf")),
+            PathBuf::from("out/q3-report")
+        );
+    }
+}
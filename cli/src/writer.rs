@@ -6,7 +6,11 @@ use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
 
 use unpdf::model::{Block, Metadata, Page};
-use unpdf::render::{CleanupPipeline, PageMarkerStyle, RenderOptions, StreamingRenderer};
+use unpdf::render::{
+    CleanupPipeline, PageMarkerStyle, RenderOptions, SafeFileNames, StreamingRenderer,
+};
+
+use crate::crypt::OutputKey;
 
 fn image_hash(data: &[u8]) -> (u64, usize) {
     // Sample head + tail instead of hashing all bytes — O(1) regardless of image size.
@@ -40,17 +44,45 @@ pub struct WriteSummary {
     pub word_count: usize,
 }
 
+/// Destination of one output format: the file itself, or an in-memory
+/// buffer that is encrypted into `<file>.enc` when the writer finishes.
+enum Sink {
+    File(File),
+    Sealed(Vec<u8>, OutputKey),
+}
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Sink::File(f) => f.write(buf),
+            Sink::Sealed(b, _) => b.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Sink::File(f) => f.flush(),
+            Sink::Sealed(..) => Ok(()),
+        }
+    }
+}
+
 /// Fan-out writer that appends MD/TXT/JSON files page-by-page.
 ///
 /// JSON is written as `{"metadata":..., "pages":[ <p1>, <p2>, ... ]}`
 /// with manual comma management.
+///
+/// With an encryption key every output, images included, is written as an
+/// AES-GCM `<name>.enc` file instead; the text formats are then held in
+/// memory until [`MultiFormatWriter::finish`].
 pub struct MultiFormatWriter {
-    md: Option<BufWriter<File>>,
+    md: Option<BufWriter<Sink>>,
     md_path: Option<PathBuf>,
-    txt: Option<BufWriter<File>>,
+    txt: Option<BufWriter<Sink>>,
     txt_path: Option<PathBuf>,
-    json: Option<BufWriter<File>>,
+    json: Option<BufWriter<Sink>>,
     json_path: Option<PathBuf>,
+    encrypt: Option<OutputKey>,
     render_opts: RenderOptions,
     json_first_page: bool,
     /// 이미지 출력 디렉토리. None 이면 이미지를 디스크에 쓰지 않음.
@@ -62,6 +94,8 @@ pub struct MultiFormatWriter {
     word_count: usize,
     /// (hash, byte_len) → canonical resource_id. 동일 바이트 이미지 중복 방지.
     image_dedup: HashMap<(u64, usize), String>,
+    /// 이미지 디렉토리에 이미 쓴 파일명. 대소문자만 다른 id 의 덮어쓰기 방지.
+    image_names: SafeFileNames,
     /// Tracks whether any content has been written to the MD file.
     /// Used to determine correct page marker spacing.
     md_written: bool,
@@ -73,26 +107,21 @@ impl MultiFormatWriter {
         formats: &[OutputFormat],
         render_opts: RenderOptions,
         images_dir: Option<PathBuf>,
+        encrypt: Option<OutputKey>,
     ) -> std::io::Result<Self> {
         let has = |f: OutputFormat| formats.contains(&f);
-        let md_path = has(OutputFormat::Markdown).then(|| out_dir.join("extract.md"));
-        let md = if let Some(ref p) = md_path {
-            Some(BufWriter::new(File::create(p)?))
-        } else {
-            None
+        let open = |p: &PathBuf| -> std::io::Result<BufWriter<Sink>> {
+            Ok(BufWriter::new(match &encrypt {
+                Some(key) => Sink::Sealed(Vec::new(), key.clone()),
+                None => Sink::File(File::create(p)?),
+            }))
         };
+        let md_path = has(OutputFormat::Markdown).then(|| out_dir.join("extract.md"));
+        let md = md_path.as_ref().map(open).transpose()?;
         let txt_path = has(OutputFormat::Text).then(|| out_dir.join("extract.txt"));
-        let txt = if let Some(ref p) = txt_path {
-            Some(BufWriter::new(File::create(p)?))
-        } else {
-            None
-        };
+        let txt = txt_path.as_ref().map(open).transpose()?;
         let json_path = has(OutputFormat::Json).then(|| out_dir.join("content.json"));
-        let json = if let Some(ref p) = json_path {
-            Some(BufWriter::new(File::create(p)?))
-        } else {
-            None
-        };
+        let json = json_path.as_ref().map(open).transpose()?;
         Ok(Self {
             md,
             md_path,
@@ -100,6 +129,7 @@ impl MultiFormatWriter {
             txt_path,
             json,
             json_path,
+            encrypt,
             render_opts,
             json_first_page: true,
             images_dir,
@@ -107,6 +137,7 @@ impl MultiFormatWriter {
             image_count: 0,
             word_count: 0,
             image_dedup: HashMap::new(),
+            image_names: SafeFileNames::new(),
             md_written: false,
         })
     }
@@ -115,6 +146,8 @@ impl MultiFormatWriter {
     ///
     /// 동일 바이트 이미지는 첫 등장 시에만 저장되며, 이후 등장분은 page의
     /// images 목록과 Block::Image resource_id 가 모두 canonical ID로 교체된다.
+    /// 파일명으로 쓸 수 없거나 앞서 쓴 파일과 대소문자만 다른 id 는
+    /// `SafeFileNames` 가 고친 이름으로 저장하고 같은 방식으로 교체한다.
     fn flush_page_images(&mut self, page: &mut Page) -> std::io::Result<()> {
         let Some(dir) = self.images_dir.clone() else {
             return Ok(());
@@ -122,24 +155,42 @@ impl MultiFormatWriter {
         if page.images.is_empty() {
             return Ok(());
         }
-        if !self.images_created {
-            std::fs::create_dir_all(&dir)?;
-            self.images_created = true;
-        }
+        self.render_opts.image_conversion.apply_to_page(page);
+        self.render_opts.resource_naming.apply_to_page(page);
 
-        // duplicate_id → canonical_id
+        // duplicate_id / unsafe_id → canonical_id
         let mut redirects: HashMap<String, String> = HashMap::new();
+        let mut duplicates = vec![false; page.images.len()];
 
-        for (id, resource) in &page.images {
+        for (i, (id, resource)) in page.images.iter_mut().enumerate() {
+            // data URI 로 Markdown 에 들어가는 작은 이미지는 파일로 쓰지 않는다.
+            if self.render_opts.inlines_image(resource) {
+                continue;
+            }
+            if !self.images_created {
+                std::fs::create_dir_all(&dir)?;
+                self.images_created = true;
+            }
             let key = image_hash(&resource.data);
             match self.image_dedup.entry(key) {
                 std::collections::hash_map::Entry::Occupied(e) => {
                     redirects.insert(id.clone(), e.get().clone());
+                    duplicates[i] = true;
                 }
                 std::collections::hash_map::Entry::Vacant(e) => {
-                    std::fs::write(dir.join(id), &resource.data)?;
+                    let name = self.image_names.claim(id);
+                    if name != *id {
+                        redirects.insert(std::mem::replace(id, name.clone()), name.clone());
+                    }
+                    let path = dir.join(&name);
+                    match &self.encrypt {
+                        Some(key) => {
+                            key.write(&path, &resource.data)?;
+                        }
+                        None => std::fs::write(&path, &resource.data)?,
+                    }
                     self.image_count += 1;
-                    e.insert(id.clone());
+                    e.insert(name);
                 }
             }
         }
@@ -148,7 +199,9 @@ impl MultiFormatWriter {
             return Ok(());
         }
 
-        page.images.retain(|(id, _)| !redirects.contains_key(id));
+        let mut duplicate = duplicates.into_iter();
+        page.images
+            .retain(|_| !duplicate.next().unwrap_or_default());
         for block in &mut page.elements {
             if let Block::Image { resource_id, .. } = block {
                 if let Some(canonical) = redirects.get(resource_id.as_str()) {
@@ -172,7 +225,11 @@ impl MultiFormatWriter {
             }
         }
         if let Some(w) = self.json.as_mut() {
-            w.write_all(b"{\"metadata\":")?;
+            write!(
+                w,
+                "{{\"schema_version\":{},\"metadata\":",
+                unpdf::SCHEMA_VERSION
+            )?;
             serde_json::to_writer(&mut *w, metadata).map_err(io_err)?;
             w.write_all(b",\"page_count\":")?;
             w.write_all(page_count.to_string().as_bytes())?;
@@ -203,8 +260,18 @@ impl MultiFormatWriter {
                 w.write_all(marker.as_bytes())?;
                 self.md_written = true;
             }
-            let placeholder = unpdf::model::Document::new();
+            let mut placeholder = unpdf::model::Document::new();
+            for (id, resource) in &page.images {
+                if self.render_opts.inlines_image(resource) {
+                    placeholder.add_resource(id.clone(), resource.clone());
+                }
+            }
             let renderer = StreamingRenderer::new(&placeholder, self.render_opts.clone());
+            let warnings = renderer.render_page_warnings(page);
+            if !warnings.is_empty() {
+                w.write_all(warnings.as_bytes())?;
+                self.md_written = true;
+            }
             for block in &page.elements {
                 let chunk = renderer.render_block_public(block);
                 if !chunk.is_empty() {
@@ -237,26 +304,19 @@ impl MultiFormatWriter {
         if let Some(w) = self.json.as_mut() {
             w.write_all(b"]}")?;
         }
-        if let Some(mut w) = self.md.take() {
-            w.flush()?;
-            drop(w);
-            // Streaming renderer bypasses the CleanupPipeline. Apply
-            // configured cleanup now as a read-modify-write pass on the
-            // completed MD file. Keeps per-page streaming memory profile
-            // while still delivering standard/aggressive cleanup semantics.
-            if let (Some(path), Some(ref cleanup_opts)) =
-                (self.md_path.as_ref(), &self.render_opts.cleanup)
-            {
-                let raw = std::fs::read_to_string(path)?;
-                let cleaned = CleanupPipeline::new(cleanup_opts.clone()).process(&raw);
-                std::fs::write(path, cleaned)?;
-            }
+        // Streaming renderer bypasses the CleanupPipeline. Apply configured
+        // cleanup now as a read-modify-write pass on the completed MD file.
+        // Keeps per-page streaming memory profile while still delivering
+        // standard/aggressive cleanup semantics.
+        let cleanup = self.render_opts.cleanup.clone().map(CleanupPipeline::new);
+        if let (Some(w), Some(path)) = (self.md.take(), self.md_path.as_mut()) {
+            close(w, path, cleanup.as_ref())?;
         }
-        if let Some(mut w) = self.txt.take() {
-            w.flush()?;
+        if let (Some(w), Some(path)) = (self.txt.take(), self.txt_path.as_mut()) {
+            close(w, path, None)?;
         }
-        if let Some(mut w) = self.json.take() {
-            w.flush()?;
+        if let (Some(w), Some(path)) = (self.json.take(), self.json_path.as_mut()) {
+            close(w, path, None)?;
         }
         Ok(WriteSummary {
             md_path: self.md_path,
@@ -268,6 +328,34 @@ impl MultiFormatWriter {
     }
 }
 
+/// Flush one output, cleaning it up if asked. An encrypted output is sealed
+/// into `<path>.enc` and `path` updated to match.
+fn close(
+    writer: BufWriter<Sink>,
+    path: &mut PathBuf,
+    cleanup: Option<&CleanupPipeline>,
+) -> std::io::Result<()> {
+    match writer.into_inner().map_err(|e| e.into_error())? {
+        Sink::File(file) => {
+            drop(file);
+            if let Some(pipeline) = cleanup {
+                let raw = std::fs::read_to_string(&*path)?;
+                std::fs::write(&*path, pipeline.process(&raw))?;
+            }
+        }
+        Sink::Sealed(buf, key) => {
+            let data = match cleanup {
+                Some(pipeline) => pipeline
+                    .process(&String::from_utf8_lossy(&buf))
+                    .into_bytes(),
+                None => buf,
+            };
+            *path = key.write(path, &data)?;
+        }
+    }
+    Ok(())
+}
+
 fn io_err(e: serde_json::Error) -> std::io::Error {
     std::io::Error::other(e)
 }
@@ -290,7 +378,7 @@ mod tests {
                 unpdf::CleanupPreset::Minimal,
             ));
         let formats = vec![OutputFormat::Markdown];
-        let mut mfw = MultiFormatWriter::new(&tmp, &formats, render_opts, None).unwrap();
+        let mut mfw = MultiFormatWriter::new(&tmp, &formats, render_opts, None, None).unwrap();
 
         mfw.write_document_start(&doc.metadata, 2).unwrap();
 
@@ -331,7 +419,7 @@ mod tests {
         let doc = unpdf::model::Document::new();
         let render_opts = RenderOptions::new();
         let formats = vec![OutputFormat::Markdown];
-        let mut mfw = MultiFormatWriter::new(&tmp, &formats, render_opts, None).unwrap();
+        let mut mfw = MultiFormatWriter::new(&tmp, &formats, render_opts, None, None).unwrap();
 
         mfw.write_document_start(&doc.metadata, 1).unwrap();
         let mut page = Page::letter(1);
@@ -369,7 +457,8 @@ mod tests {
         let render_opts = RenderOptions::new();
         let formats = vec![OutputFormat::Markdown];
         let mut mfw =
-            MultiFormatWriter::new(&tmp, &formats, render_opts, Some(images_dir.clone())).unwrap();
+            MultiFormatWriter::new(&tmp, &formats, render_opts, Some(images_dir.clone()), None)
+                .unwrap();
 
         let doc = unpdf::model::Document::new();
         mfw.write_document_start(&doc.metadata, 2).unwrap();
@@ -414,4 +503,191 @@ mod tests {
 
         std::fs::remove_dir_all(&tmp).ok();
     }
+
+    #[test]
+    fn test_images_named_by_template() {
+        use unpdf::model::Resource;
+        use unpdf::ResourceNaming;
+
+        let tmp = std::env::temp_dir().join("unpdf_writer_naming_test");
+        std::fs::create_dir_all(&tmp).unwrap();
+        let images_dir = tmp.join("images");
+
+        let render_opts = RenderOptions::new()
+            .with_image_prefix("images/")
+            .with_resource_naming(ResourceNaming::template("{page:03}_{index:02}.{ext}"));
+        let formats = vec![OutputFormat::Markdown];
+        let mut mfw =
+            MultiFormatWriter::new(&tmp, &formats, render_opts, Some(images_dir.clone()), None)
+                .unwrap();
+        mfw.write_document_start(&unpdf::model::Document::new().metadata, 1)
+            .unwrap();
+
+        let mut page = Page::letter(4);
+        for (id, byte) in [("page4_Im9.jpg", 1u8), ("page4_Im2.jpg", 2)] {
+            let data = vec![0xFF, 0xD8, byte];
+            let resource = Resource::new(
+                data,
+                "image/jpeg".to_string(),
+                unpdf::model::ResourceType::Image,
+            );
+            page.images.push((id.to_string(), resource));
+            page.elements.push(Block::image(id));
+        }
+        mfw.write_page(&mut page).unwrap();
+        mfw.finish().unwrap();
+
+        assert_eq!(std::fs::read(images_dir.join("004_01.jpg")).unwrap()[2], 1);
+        assert_eq!(std::fs::read(images_dir.join("004_02.jpg")).unwrap()[2], 2);
+        let content = std::fs::read_to_string(tmp.join("extract.md")).unwrap();
+        assert!(content.contains("](images/004_01.jpg)"), "{}", content);
+        assert!(content.contains("](images/004_02.jpg)"), "{}", content);
+
+        std::fs::remove_dir_all(&tmp).ok();
+    }
+
+    #[test]
+    fn test_unsafe_and_case_colliding_image_ids_get_safe_names() {
+        use unpdf::model::Resource;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let images_dir = tmp.path().join("images");
+        let render_opts = RenderOptions::new().with_image_prefix("images/");
+        let formats = vec![OutputFormat::Markdown];
+        let mut mfw = MultiFormatWriter::new(
+            tmp.path(),
+            &formats,
+            render_opts,
+            Some(images_dir.clone()),
+            None,
+        )
+        .unwrap();
+        mfw.write_document_start(&unpdf::model::Document::new().metadata, 1)
+            .unwrap();
+
+        let mut page = Page::letter(1);
+        for (id, byte) in [
+            ("page1_Im1.jpg", 1u8),
+            ("page1_IM1.jpg", 2),
+            ("page1_a/b:c.jpg", 3),
+        ] {
+            let data = vec![0xFF, 0xD8, byte];
+            let resource = Resource::new(
+                data,
+                "image/jpeg".to_string(),
+                unpdf::model::ResourceType::Image,
+            );
+            page.images.push((id.to_string(), resource));
+            page.elements.push(Block::image(id));
+        }
+        mfw.write_page(&mut page).unwrap();
+        assert_eq!(mfw.finish().unwrap().image_count, 3);
+
+        for (name, byte) in [
+            ("page1_Im1.jpg", 1),
+            ("page1_IM1_2.jpg", 2),
+            ("page1_a_b_c.jpg", 3),
+        ] {
+            assert_eq!(
+                std::fs::read(images_dir.join(name)).unwrap()[2],
+                byte,
+                "{}",
+                name
+            );
+        }
+        let content = std::fs::read_to_string(tmp.path().join("extract.md")).unwrap();
+        assert!(content.contains("](images/page1_IM1_2.jpg)"), "{}", content);
+        assert!(content.contains("](images/page1_a_b_c.jpg)"), "{}", content);
+    }
+
+    #[test]
+    fn test_encrypted_outputs_leave_no_plaintext() {
+        use unpdf::model::Resource;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let images_dir = tmp.path().join("images");
+        let key = OutputKey::parse(&"ab".repeat(32)).unwrap();
+
+        let render_opts = RenderOptions::new().with_cleanup(
+            unpdf::render::CleanupOptions::from_preset(unpdf::CleanupPreset::Minimal),
+        );
+        let formats = vec![OutputFormat::Markdown, OutputFormat::Json];
+        let mut mfw = MultiFormatWriter::new(
+            tmp.path(),
+            &formats,
+            render_opts,
+            Some(images_dir.clone()),
+            Some(key.clone()),
+        )
+        .unwrap();
+        mfw.write_document_start(&unpdf::model::Document::new().metadata, 1)
+            .unwrap();
+        let mut page = Page::letter(1);
+        page.add_paragraph(Paragraph::with_text("Salary table"));
+        let resource = Resource::new(
+            vec![0xFF, 0xD8, 9],
+            "image/jpeg".to_string(),
+            unpdf::model::ResourceType::Image,
+        );
+        page.images.push(("p1.jpg".to_string(), resource));
+        page.elements.push(Block::image("p1.jpg"));
+        mfw.write_page(&mut page).unwrap();
+        let summary = mfw.finish().unwrap();
+
+        let md_path = tmp.path().join("extract.md.enc");
+        assert_eq!(summary.md_path.as_deref(), Some(md_path.as_path()));
+        assert!(!tmp.path().join("extract.md").exists());
+        assert!(!tmp.path().join("content.json").exists());
+        assert!(!images_dir.join("p1.jpg").exists());
+
+        let md = key.decrypt(&std::fs::read(&md_path).unwrap()).unwrap();
+        assert!(String::from_utf8(md).unwrap().contains("Salary table"));
+        let json = std::fs::read(tmp.path().join("content.json.enc")).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&key.decrypt(&json).unwrap()).unwrap();
+        assert_eq!(json["page_count"], 1);
+        assert_eq!(json["schema_version"], unpdf::SCHEMA_VERSION);
+        let image = std::fs::read(images_dir.join("p1.jpg.enc")).unwrap();
+        assert_eq!(key.decrypt(&image).unwrap(), vec![0xFF, 0xD8, 9]);
+    }
+
+    #[test]
+    fn test_small_images_inlined_not_written() {
+        use unpdf::model::Resource;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let images_dir = tmp.path().join("images");
+        let render_opts = RenderOptions::new()
+            .with_image_prefix("images/")
+            .with_inline_images_max_bytes(16);
+        let formats = vec![OutputFormat::Markdown];
+        let mut mfw = MultiFormatWriter::new(
+            tmp.path(),
+            &formats,
+            render_opts,
+            Some(images_dir.clone()),
+            None,
+        )
+        .unwrap();
+        mfw.write_document_start(&unpdf::model::Document::new().metadata, 1)
+            .unwrap();
+
+        let mut page = Page::letter(1);
+        for (id, len) in [("icon.png", 3), ("chart.png", 64)] {
+            let resource = Resource::png(vec![b'x'; len]);
+            page.images.push((id.to_string(), resource));
+            page.elements.push(Block::image(id));
+        }
+        mfw.write_page(&mut page).unwrap();
+        let summary = mfw.finish().unwrap();
+
+        assert_eq!(summary.image_count, 1);
+        assert!(!images_dir.join("icon.png").exists());
+        assert!(images_dir.join("chart.png").exists());
+        let content = std::fs::read_to_string(tmp.path().join("extract.md")).unwrap();
+        assert!(
+            content.contains("](data:image/png;base64,eHh4)"),
+            "{content}"
+        );
+        assert!(content.contains("](images/chart.png)"), "{}", content);
+    }
 }
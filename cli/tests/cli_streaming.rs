@@ -95,3 +95,153 @@ fn convert_formats_flag_selects_subset() {
     assert!(out.join("content.json").exists());
     assert!(!out.join("extract.txt").exists());
 }
+
+#[test]
+fn convert_progress_json_reports_every_page() {
+    let fixture = fixture();
+    if !fixture.exists() {
+        return;
+    }
+    let tmp = tempfile::tempdir().unwrap();
+    let out = tmp.path().join("out");
+    let output = Command::new(bin())
+        .args([
+            "convert",
+            fixture.to_str().unwrap(),
+            "-o",
+            out.to_str().unwrap(),
+            "--progress",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let lines: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let pages: Vec<u64> = lines
+        .iter()
+        .filter(|line| line["stage"] == "parsing")
+        .filter_map(|line| line["done"].as_u64())
+        .collect();
+    let total = lines.last().unwrap()["total"].as_u64().unwrap();
+    assert_eq!(pages.last(), Some(&total));
+    assert!(pages.windows(2).all(|w| w[1] == w[0] + 1));
+    assert_eq!(lines.last().unwrap()["stage"], "done");
+}
+
+#[test]
+fn clean_applies_cleanup_preset_to_text_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let input = tmp.path().join("ocr.txt");
+    let output = tmp.path().join("clean.txt");
+    std::fs::write(&input, "ﬁnancial report\n\n\n\n\nSum of ﬂows\n").unwrap();
+    let status = Command::new(bin())
+        .args([
+            "clean",
+            input.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+            "--cleanup",
+            "aggressive",
+        ])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let text = std::fs::read_to_string(&output).unwrap();
+    assert!(
+        text.starts_with("financial report\n\nSum of flows"),
+        "{text:?}"
+    );
+}
+
+#[test]
+fn info_metadata_only_matches_full_page_count() {
+    let fixture = fixture();
+    if !fixture.exists() {
+        return;
+    }
+    let report = |extra: &[&str]| -> serde_json::Value {
+        let output = Command::new(bin())
+            .args(["info", fixture.to_str().unwrap(), "--json"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success() || output.status.code() == Some(5));
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+    let fast = report(&["--metadata-only"]);
+    let full = report(&[]);
+    assert_eq!(
+        fast["metadata"]["page_count"],
+        full["metadata"]["page_count"]
+    );
+    assert_eq!(fast["outline_count"], full["outline_count"]);
+}
+
+#[test]
+fn preview_without_terminal_prints_styled_markdown() {
+    let fixture = fixture();
+    if !fixture.exists() {
+        return;
+    }
+    // stdout is a pipe here, so no pager is started and no colors are written
+    let output = Command::new(bin())
+        .args(["preview", fixture.to_str().unwrap(), "--pages", "1"])
+        .output()
+        .unwrap();
+    assert!(output.status.success() || output.status.code() == Some(5));
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.starts_with("<!-- page 1 -->"), "{text}");
+    assert!(!text.contains('\u{1b}'));
+}
+
+#[test]
+fn profile_json_reports_stages_and_pages() {
+    let fixture = fixture();
+    if !fixture.exists() {
+        return;
+    }
+    let output = Command::new(bin())
+        .args(["profile", fixture.to_str().unwrap(), "--json", "--top", "2"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let stages: Vec<&str> = report["stages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["stage"].as_str().unwrap())
+        .collect();
+    assert_eq!(stages, ["open", "document", "pages", "render"]);
+    assert!(report["slowest_pages"].as_array().unwrap().len() <= 2);
+}
+
+#[test]
+fn text_width_rewraps_and_pipes_keep_source_lines() {
+    let fixture = fixture();
+    if !fixture.exists() {
+        return;
+    }
+    let text = |extra: &[&str]| -> String {
+        let output = Command::new(bin())
+            .args(["text", fixture.to_str().unwrap(), "--pages", "1"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success() || output.status.code() == Some(5));
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let wrapped = text(&["--width", "40"]);
+    // 한 단어가 40 칸을 넘는 줄(URL 등)만 더 길 수 있다.
+    for line in wrapped.lines() {
+        assert!(
+            line.chars().count() <= 40 || !line.contains(' '),
+            "{line:?}"
+        );
+    }
+    // stdout 이 파이프이면 기본값은 줄바꿈하지 않는다.
+    assert_eq!(text(&[]), text(&["--no-wrap"]));
+}
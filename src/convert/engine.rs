@@ -0,0 +1,146 @@
+//! Reusable conversion engine for long-running services.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::error::{Error, Result};
+use crate::model::Document;
+use crate::parser::{ParseOptions, PdfParser};
+use crate::render::{CleanupOptions, CleanupPipeline};
+
+use super::pdf::{build_parse_options, convert_document};
+use super::{ConvertOptions, ConvertResult};
+
+/// Distinct cleanup configurations kept compiled at once.
+const MAX_CACHED_PIPELINES: usize = 32;
+
+/// A PDF converter that keeps its setup between calls.
+///
+/// One-shot helpers such as [`to_markdown`](crate::to_markdown) build a new
+/// cleanup pipeline on every call and parse pages on the global rayon pool. An engine compiles each distinct [`CleanupOptions`] once and
+/// can run on a dedicated pool, so a server can size page parallelism apart
+/// from its own workers. It is `Send + Sync`: share one engine (e.g. in an
+/// `Arc`) and call [`convert`](Self::convert) from any number of threads.
+///
+/// ```no_run
+/// use unpdf::{ConvertOptions, UnpdfEngine};
+///
+/// let engine = UnpdfEngine::new().with_threads(4)?;
+/// let bytes = std::fs::read("document.pdf")?;
+/// let result = engine.convert(&bytes, &ConvertOptions::new())?;
+/// println!("{}", result.content);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct UnpdfEngine {
+    parse_options: ParseOptions,
+    pool: Option<rayon::ThreadPool>,
+    pipelines: Mutex<HashMap<String, Arc<CleanupPipeline>>>,
+}
+
+impl UnpdfEngine {
+    /// Create an engine using default parse options and the global rayon pool.
+    pub fn new() -> Self {
+        Self {
+            parse_options: ParseOptions::default(),
+            pool: None,
+            pipelines: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Parse options used for every call. Page selection and password are
+    /// taken from each call's [`ConvertOptions`] instead.
+    pub fn with_parse_options(mut self, options: ParseOptions) -> Self {
+        self.parse_options = options;
+        self
+    }
+
+    /// Parse pages on a dedicated pool of `threads` threads (0 picks the
+    /// rayon default, one per CPU).
+    pub fn with_threads(mut self, threads: usize) -> Result<Self> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|i| format!("unpdf-{}", i))
+            .build()
+            .map_err(|e| Error::Other(format!("Failed to build thread pool: {}", e)))?;
+        self.pool = Some(pool);
+        Ok(self)
+    }
+
+    /// Number of threads pages are parsed on.
+    pub fn threads(&self) -> usize {
+        match &self.pool {
+            Some(pool) => pool.current_num_threads(),
+            None => rayon::current_num_threads(),
+        }
+    }
+
+    /// Parse a PDF from bytes.
+    pub fn parse(&self, data: &[u8]) -> Result<Document> {
+        let options = self.parse_options.clone();
+        self.install(|| PdfParser::from_bytes_with_options(data, options)?.parse())
+    }
+
+    /// Convert a PDF from bytes to the format selected in `options`.
+    pub fn convert(&self, data: &[u8], options: &ConvertOptions) -> Result<ConvertResult> {
+        let parse_options = build_parse_options(self.parse_options.clone(), options);
+        let doc =
+            self.install(|| PdfParser::from_bytes_with_options(data, parse_options)?.parse())?;
+        let pipeline = options.render.cleanup.as_ref().map(|c| self.pipeline(c));
+        convert_document(doc, options, pipeline)
+    }
+
+    /// Run `f` on the engine's pool, if it has one.
+    fn install<T: Send>(&self, f: impl FnOnce() -> T + Send) -> T {
+        match &self.pool {
+            Some(pool) => pool.install(f),
+            None => f(),
+        }
+    }
+
+    /// The compiled pipeline for `options`, built on first use.
+    fn pipeline(&self, options: &CleanupOptions) -> Arc<CleanupPipeline> {
+        // 옵션 구조체는 Hash가 없어 Debug 표현을 키로 쓴다. 사용자 정규식도
+        // 패턴 문자열로 찍히므로 서로 다른 설정이 같은 키를 갖지 않는다.
+        let key = format!("{:?}", options);
+        let mut pipelines = self.pipelines.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(pipeline) = pipelines.get(&key) {
+            return Arc::clone(pipeline);
+        }
+        if pipelines.len() >= MAX_CACHED_PIPELINES {
+            pipelines.clear();
+        }
+        let pipeline = Arc::new(CleanupPipeline::new(options.clone()));
+        pipelines.insert(key, Arc::clone(&pipeline));
+        pipeline
+    }
+}
+
+impl Default for UnpdfEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::CleanupPreset;
+
+    #[test]
+    fn test_engine_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<UnpdfEngine>();
+    }
+
+    #[test]
+    fn test_pipeline_cache() {
+        let engine = UnpdfEngine::new();
+        let standard = CleanupOptions::from_preset(CleanupPreset::Standard);
+        let first = engine.pipeline(&standard);
+        assert!(Arc::ptr_eq(&first, &engine.pipeline(&standard.clone())));
+
+        let aggressive = engine.pipeline(&CleanupOptions::from_preset(CleanupPreset::Aggressive));
+        assert!(!Arc::ptr_eq(&first, &aggressive));
+        assert_eq!(engine.pipelines.lock().unwrap().len(), 2);
+    }
+}
@@ -21,10 +21,13 @@
 //! }
 //! ```
 
+mod engine;
 mod pdf;
 
+pub use engine::UnpdfEngine;
 pub use pdf::PdfConverter;
 
+use crate::detect::{sniff_format, DetectedFormat};
 use crate::error::{Error, Result};
 use crate::model::Metadata;
 use crate::render::{ExtractionStats, RenderOptions};
@@ -237,6 +240,30 @@ impl ConverterRegistry {
         converter.convert(path, options)
     }
 
+    /// Get the converter for a sniffed format, by its conventional extension.
+    pub fn get_by_format(&self, format: &DetectedFormat) -> Option<Arc<dyn DocumentConverter>> {
+        format
+            .extension()
+            .and_then(|ext| self.get_by_extension(ext))
+    }
+
+    /// Convert bytes, choosing the converter from their content rather than
+    /// a file extension.
+    ///
+    /// Fails with [`Error::NotPdf`] (or [`Error::UnknownFormat`]) when no
+    /// registered converter handles the detected format.
+    pub fn convert_bytes_detected(
+        &self,
+        bytes: &[u8],
+        options: &ConvertOptions,
+    ) -> Result<ConvertResult> {
+        let format = sniff_format(bytes);
+        match self.get_by_format(&format) {
+            Some(converter) => converter.convert_bytes(bytes, options),
+            None => Err(format.not_pdf_error()),
+        }
+    }
+
     /// Convert bytes using the specified extension to determine the converter.
     pub fn convert_bytes(
         &self,
@@ -290,6 +317,19 @@ mod tests {
         assert_eq!(converter.unwrap().name(), "pdf");
     }
 
+    #[test]
+    fn test_convert_bytes_detected_names_wrong_format() {
+        let registry = ConverterRegistry::with_defaults();
+        let err = registry
+            .convert_bytes_detected(b"PK\x03\x04....word/document.xml", &ConvertOptions::new())
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::NotPdf(DetectedFormat::Docx)),
+            "{}",
+            err
+        );
+    }
+
     #[test]
     fn test_registry_get_by_name() {
         let registry = ConverterRegistry::with_defaults();
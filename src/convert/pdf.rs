@@ -1,10 +1,14 @@
 //! PDF document converter implementation.
 
 use crate::error::Result;
+use crate::model::Document;
 use crate::parser::{ParseOptions, PdfParser};
-use crate::render::{to_json, to_markdown_with_stats, to_text, JsonFormat};
+use crate::render::{
+    to_json_with_options, to_text_with_pipeline, CleanupPipeline, JsonFormat, MarkdownRenderer,
+};
 #[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
+use std::sync::Arc;
 
 use super::{ConvertOptions, ConvertResult, DocumentConverter, OutputFormat};
 
@@ -21,45 +25,60 @@ impl PdfConverter {
     pub fn new() -> Self {
         Self { _private: () }
     }
+}
 
-    fn build_parse_options(&self, options: &ConvertOptions) -> ParseOptions {
-        let mut parse_opts = ParseOptions::new().with_pages(options.render.page_selection.clone());
-
-        if let Some(ref password) = options.password {
-            parse_opts = parse_opts.with_password(password);
-        }
+/// Parse options for a conversion: `base` with the page selection and
+/// password from `options`.
+pub(super) fn build_parse_options(base: ParseOptions, options: &ConvertOptions) -> ParseOptions {
+    let mut parse_opts = base.with_pages(options.render.page_selection.clone());
 
-        parse_opts
+    if let Some(ref password) = options.password {
+        parse_opts = parse_opts.with_password(password);
+    }
+    if let Some(ref headings) = options.render.heading_config {
+        parse_opts = parse_opts.with_heading_config(headings.clone());
+    }
+    // 언어로 거르려면 문단에 언어가 붙어 있어야 한다.
+    if !options.render.languages.is_empty() {
+        parse_opts = parse_opts.with_language_tags(true);
     }
 
-    fn convert_document(
-        &self,
-        doc: crate::model::Document,
-        options: &ConvertOptions,
-    ) -> Result<ConvertResult> {
-        let metadata = doc.metadata.clone();
-
-        match options.output_format {
-            OutputFormat::Markdown => {
-                if options.collect_stats {
-                    let render_result = to_markdown_with_stats(&doc, &options.render)?;
-                    Ok(ConvertResult::new(render_result.content, metadata)
-                        .with_stats(render_result.stats)
-                        .with_mime_type("text/markdown"))
-                } else {
-                    let content = crate::render::to_markdown(&doc, &options.render)?;
-                    Ok(ConvertResult::new(content, metadata).with_mime_type("text/markdown"))
-                }
-            }
-            OutputFormat::Text => {
-                let content = to_text(&doc, &options.render)?;
-                Ok(ConvertResult::new(content, metadata).with_mime_type("text/plain"))
+    parse_opts
+}
+
+/// Render a parsed document in the requested output format, using
+/// `pipeline` for cleanup when given.
+pub(super) fn convert_document(
+    doc: Document,
+    options: &ConvertOptions,
+    pipeline: Option<Arc<CleanupPipeline>>,
+) -> Result<ConvertResult> {
+    let metadata = doc.metadata.clone();
+
+    match options.output_format {
+        OutputFormat::Markdown => {
+            let mut renderer = MarkdownRenderer::new(options.render.clone());
+            if let Some(pipeline) = pipeline {
+                renderer = renderer.with_cleanup_pipeline(pipeline);
             }
-            OutputFormat::Json => {
-                let content = to_json(&doc, JsonFormat::Pretty)?;
-                Ok(ConvertResult::new(content, metadata).with_mime_type("application/json"))
+            if options.collect_stats {
+                let render_result = renderer.render_with_stats(&doc)?;
+                Ok(ConvertResult::new(render_result.content, metadata)
+                    .with_stats(render_result.stats)
+                    .with_mime_type("text/markdown"))
+            } else {
+                let content = renderer.render(&doc)?;
+                Ok(ConvertResult::new(content, metadata).with_mime_type("text/markdown"))
             }
         }
+        OutputFormat::Text => {
+            let content = to_text_with_pipeline(&doc, &options.render, pipeline)?;
+            Ok(ConvertResult::new(content, metadata).with_mime_type("text/plain"))
+        }
+        OutputFormat::Json => {
+            let content = to_json_with_options(&doc, JsonFormat::Pretty, &options.render)?;
+            Ok(ConvertResult::new(content, metadata).with_mime_type("application/json"))
+        }
     }
 }
 
@@ -74,17 +93,17 @@ impl DocumentConverter for PdfConverter {
 
     #[cfg(not(target_arch = "wasm32"))]
     fn convert(&self, path: &Path, options: &ConvertOptions) -> Result<ConvertResult> {
-        let parse_opts = self.build_parse_options(options);
+        let parse_opts = build_parse_options(ParseOptions::new(), options);
         let parser = PdfParser::open_with_options(path, parse_opts)?;
         let doc = parser.parse()?;
-        self.convert_document(doc, options)
+        convert_document(doc, options, None)
     }
 
     fn convert_bytes(&self, bytes: &[u8], options: &ConvertOptions) -> Result<ConvertResult> {
-        let parse_opts = self.build_parse_options(options);
+        let parse_opts = build_parse_options(ParseOptions::new(), options);
         let parser = PdfParser::from_bytes_with_options(bytes, parse_opts)?;
         let doc = parser.parse()?;
-        self.convert_document(doc, options)
+        convert_document(doc, options, None)
     }
 }
 
@@ -1,4 +1,8 @@
 //! PDF format detection and validation.
+//!
+//! Besides validating PDF headers, this module sniffs the formats most often
+//! handed to a PDF converter by mistake (Office documents, images, saved web
+//! pages) so callers can report what a file actually is.
 
 use crate::error::{Error, Result};
 #[cfg(not(target_arch = "wasm32"))]
@@ -23,6 +27,105 @@ impl std::fmt::Display for PdfFormat {
     }
 }
 
+/// A file format recognised from its leading bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetectedFormat {
+    /// A PDF document
+    Pdf(PdfFormat),
+    /// A Word document (Office Open XML)
+    Docx,
+    /// An Excel workbook (Office Open XML)
+    Xlsx,
+    /// A PowerPoint presentation (Office Open XML)
+    Pptx,
+    /// Some other ZIP archive
+    Zip,
+    /// A PNG image
+    Png,
+    /// A JPEG image
+    Jpeg,
+    /// A TIFF image
+    Tiff,
+    /// An HTML page
+    Html,
+    /// Not recognised
+    Unknown,
+}
+
+impl DetectedFormat {
+    /// Whether this is a PDF.
+    pub fn is_pdf(&self) -> bool {
+        matches!(self, Self::Pdf(_))
+    }
+
+    /// Conventional file extension (lowercase, no dot); `None` for
+    /// [`DetectedFormat::Unknown`].
+    pub fn extension(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::Pdf(_) => "pdf",
+            Self::Docx => "docx",
+            Self::Xlsx => "xlsx",
+            Self::Pptx => "pptx",
+            Self::Zip => "zip",
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+            Self::Tiff => "tiff",
+            Self::Html => "html",
+            Self::Unknown => return None,
+        })
+    }
+
+    /// MIME type; `application/octet-stream` when unknown.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Pdf(_) => "application/pdf",
+            Self::Docx => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            Self::Pptx => {
+                "application/vnd.openxmlformats-officedocument.presentationml.presentation"
+            }
+            Self::Zip => "application/zip",
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Tiff => "image/tiff",
+            Self::Html => "text/html",
+            Self::Unknown => "application/octet-stream",
+        }
+    }
+
+    /// The error to report when this format was given where a PDF was
+    /// expected: [`Error::NotPdf`] for recognised formats, otherwise
+    /// [`Error::UnknownFormat`].
+    pub fn not_pdf_error(self) -> Error {
+        match self {
+            Self::Pdf(_) | Self::Unknown => Error::UnknownFormat,
+            other => Error::NotPdf(other),
+        }
+    }
+}
+
+impl std::fmt::Display for DetectedFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pdf(pdf) => write!(f, "{}", pdf),
+            Self::Docx => f.write_str("a Word document (DOCX)"),
+            Self::Xlsx => f.write_str("an Excel workbook (XLSX)"),
+            Self::Pptx => f.write_str("a PowerPoint presentation (PPTX)"),
+            Self::Zip => f.write_str("a ZIP archive"),
+            Self::Png => f.write_str("a PNG image"),
+            Self::Jpeg => f.write_str("a JPEG image"),
+            Self::Tiff => f.write_str("a TIFF image"),
+            Self::Html => f.write_str("an HTML page"),
+            Self::Unknown => f.write_str("an unknown format"),
+        }
+    }
+}
+
+/// Bytes read from a file for sniffing. Office documents are recognised by
+/// the part names in the ZIP's local headers, which usually appear early.
+#[cfg(not(target_arch = "wasm32"))]
+const SNIFF_LEN: u64 = 64 * 1024;
+
 /// PDF magic bytes: %PDF-
 const PDF_MAGIC: &[u8] = b"%PDF-";
 const PDF_MAGIC_LEN: usize = 5;
@@ -86,6 +189,78 @@ pub fn detect_format_from_bytes(data: &[u8]) -> Result<PdfFormat> {
     })
 }
 
+/// Identify the format of `data` from its leading bytes.
+///
+/// PDFs are validated as by [`detect_format_from_bytes`]; a PDF header with
+/// a malformed version is reported as `Unknown`. ZIP archives are told apart
+/// by their part names (`word/`, `xl/`, `ppt/`), which are only visible if
+/// `data` reaches them — pass the first few kilobytes at least.
+///
+/// # Example
+/// ```
+/// use unpdf::detect::{sniff_format, DetectedFormat};
+///
+/// assert_eq!(sniff_format(b"\x89PNG\r\n\x1a\n...."), DetectedFormat::Png);
+/// assert!(sniff_format(b"%PDF-1.7\n").is_pdf());
+/// ```
+pub fn sniff_format(data: &[u8]) -> DetectedFormat {
+    if let Ok(pdf) = detect_format_from_bytes(data) {
+        return DetectedFormat::Pdf(pdf);
+    }
+    if data.starts_with(b"PK\x03\x04") {
+        return if contains(data, b"word/") {
+            DetectedFormat::Docx
+        } else if contains(data, b"xl/") {
+            DetectedFormat::Xlsx
+        } else if contains(data, b"ppt/") {
+            DetectedFormat::Pptx
+        } else {
+            DetectedFormat::Zip
+        };
+    }
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return DetectedFormat::Png;
+    }
+    if data.starts_with(b"\xFF\xD8\xFF") {
+        return DetectedFormat::Jpeg;
+    }
+    if data.starts_with(b"II*\0") || data.starts_with(b"MM\0*") {
+        return DetectedFormat::Tiff;
+    }
+    if looks_like_html(data) {
+        return DetectedFormat::Html;
+    }
+    DetectedFormat::Unknown
+}
+
+/// Identify the format of a file from its first 64 KiB.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn sniff_format_from_path<P: AsRef<Path>>(path: P) -> Result<DetectedFormat> {
+    let mut data = Vec::new();
+    File::open(path)?.take(SNIFF_LEN).read_to_end(&mut data)?;
+    Ok(sniff_format(&data))
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+/// A document starting (after a BOM and whitespace) with an HTML doctype or
+/// `<html` tag.
+fn looks_like_html(data: &[u8]) -> bool {
+    let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+    let start = data
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(data.len());
+    let head: Vec<u8> = data[start..]
+        .iter()
+        .take(14)
+        .map(u8::to_ascii_lowercase)
+        .collect();
+    head.starts_with(b"<!doctype html") || head.starts_with(b"<html")
+}
+
 /// Check if a version string is valid.
 fn is_valid_version(version: &str) -> bool {
     // Use chars().count() to count characters, not bytes.
@@ -161,6 +336,55 @@ mod tests {
         assert!(!is_pdf_bytes(b"Not a PDF"));
     }
 
+    #[test]
+    fn test_sniff_formats() {
+        assert!(sniff_format(b"%PDF-1.4\n").is_pdf());
+        assert_eq!(sniff_format(b"\x89PNG\r\n\x1a\n\0\0"), DetectedFormat::Png);
+        assert_eq!(sniff_format(b"\xFF\xD8\xFF\xE0"), DetectedFormat::Jpeg);
+        assert_eq!(sniff_format(b"II*\0\x08\0"), DetectedFormat::Tiff);
+        assert_eq!(sniff_format(b"MM\0*\0\0"), DetectedFormat::Tiff);
+        assert_eq!(
+            sniff_format(b"\xEF\xBB\xBF\n  <!DOCTYPE HTML><html>"),
+            DetectedFormat::Html
+        );
+        assert_eq!(sniff_format(b"<html lang=\"en\">"), DetectedFormat::Html);
+        assert_eq!(sniff_format(b"Not a PDF"), DetectedFormat::Unknown);
+        assert_eq!(sniff_format(b"%PDF-x.y"), DetectedFormat::Unknown);
+    }
+
+    #[test]
+    fn test_sniff_office_documents() {
+        let zip = |part: &[u8]| {
+            let mut data = b"PK\x03\x04\x14\0\0\0\x08\0[Content_Types].xml....PK\x03\x04".to_vec();
+            data.extend_from_slice(part);
+            data
+        };
+        assert_eq!(
+            sniff_format(&zip(b"word/document.xml")),
+            DetectedFormat::Docx
+        );
+        assert_eq!(sniff_format(&zip(b"xl/workbook.xml")), DetectedFormat::Xlsx);
+        assert_eq!(
+            sniff_format(&zip(b"ppt/presentation.xml")),
+            DetectedFormat::Pptx
+        );
+        assert_eq!(sniff_format(&zip(b"notes.txt")), DetectedFormat::Zip);
+        assert_eq!(DetectedFormat::Docx.extension(), Some("docx"));
+    }
+
+    #[test]
+    fn test_not_pdf_error_names_the_format() {
+        let err = DetectedFormat::Docx.not_pdf_error();
+        assert_eq!(
+            err.to_string(),
+            "Not a PDF: the file is a Word document (DOCX)"
+        );
+        assert!(matches!(
+            DetectedFormat::Unknown.not_pdf_error(),
+            Error::UnknownFormat
+        ));
+    }
+
     #[test]
     fn test_version_validation() {
         assert!(is_valid_version("1.0"));
@@ -17,6 +17,10 @@ pub enum Error {
     #[error("Unknown file format: not a valid PDF")]
     UnknownFormat,
 
+    /// The data is a recognised format other than PDF.
+    #[error("Not a PDF: the file is {0}")]
+    NotPdf(crate::detect::DetectedFormat),
+
     /// The PDF version is not supported.
     #[error("Unsupported PDF version: {0}")]
     UnsupportedVersion(String),
@@ -33,6 +37,11 @@ pub enum Error {
     #[error("Invalid password")]
     InvalidPassword,
 
+    /// The document's permissions forbid extracting its content, and
+    /// `ParseOptions::respect_extraction_permission` asked to honour them.
+    #[error("Document permissions forbid content extraction")]
+    ExtractionForbidden,
+
     /// The PDF structure is corrupted or malformed.
     #[error("Corrupted PDF structure: {0}")]
     Corrupted(String),
@@ -73,6 +82,21 @@ pub enum Error {
     #[error("Encoding error: {0}")]
     Encoding(String),
 
+    /// Document JSON could not be read back into the model.
+    #[error("Invalid document JSON: {0}")]
+    InvalidJson(String),
+
+    /// Document JSON was written with a newer schema than this build reads.
+    #[error(
+        "Unsupported document JSON schema version {0} (this build reads up to {max})",
+        max = crate::model::SCHEMA_VERSION
+    )]
+    UnsupportedSchemaVersion(u32),
+
+    /// A parse ran over its time budget.
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
     /// Generic error with message.
     #[error("{0}")]
     Other(String),
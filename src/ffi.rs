@@ -19,7 +19,7 @@ use std::panic::catch_unwind;
 use std::ptr;
 
 use crate::model::Document;
-use crate::render::{JsonFormat, RenderOptions};
+use crate::render::{JsonFormat, PageSelection, RenderOptions};
 
 // Thread-local storage for the last error message.
 thread_local! {
@@ -54,6 +54,7 @@ pub const UNPDF_FLAG_PARAGRAPH_SPACING: u32 = 4;
 /// JSON format options.
 pub const UNPDF_JSON_PRETTY: c_int = 0;
 pub const UNPDF_JSON_COMPACT: c_int = 1;
+pub const UNPDF_JSON_DETAILED: c_int = 2;
 
 /// Get the version of the library.
 ///
@@ -267,7 +268,8 @@ pub unsafe extern "C" fn unpdf_to_text(doc: *const UnpdfDocument) -> *mut c_char
 /// # Safety
 ///
 /// - `doc` must be a valid document handle.
-/// - `format` is one of `UNPDF_JSON_PRETTY` or `UNPDF_JSON_COMPACT`.
+/// - `format` is one of `UNPDF_JSON_PRETTY`, `UNPDF_JSON_COMPACT` or
+///   `UNPDF_JSON_DETAILED` (pretty, plus table `records`).
 /// - Returns null on error. Use `unpdf_last_error` to get the error message.
 /// - The returned string must be freed with `unpdf_free_string`.
 #[no_mangle]
@@ -281,10 +283,10 @@ pub unsafe extern "C" fn unpdf_to_json(doc: *const UnpdfDocument, format: c_int)
 
     let result = catch_unwind(|| {
         let document = &(*doc).inner;
-        let json_format = if format == UNPDF_JSON_COMPACT {
-            JsonFormat::Compact
-        } else {
-            JsonFormat::Pretty
+        let json_format = match format {
+            UNPDF_JSON_COMPACT => JsonFormat::Compact,
+            UNPDF_JSON_DETAILED => JsonFormat::Detailed,
+            _ => JsonFormat::Pretty,
         };
         crate::render::to_json(document, json_format).map_err(|e| e.to_string())
     });
@@ -485,7 +487,11 @@ pub unsafe extern "C" fn unpdf_get_resource_ids(doc: *const UnpdfDocument) -> *m
 
     let result = catch_unwind(|| {
         let document = &(*doc).inner;
-        let ids: Vec<&String> = document.resources.keys().collect();
+        let ids: Vec<&String> = document
+            .resources_in_page_order()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
         serde_json::to_string(&ids).map_err(|e| e.to_string())
     });
 
@@ -880,6 +886,142 @@ pub unsafe extern "C" fn unpdf_page_to_text(
     }
 }
 
+/// Convert selected pages to Markdown.
+///
+/// Unlike `unpdf_page_to_markdown`, the pages are rendered in the context of
+/// the whole document (outline, resources, running-head cleanup), so the
+/// output matches the same pages of `unpdf_to_markdown`.
+///
+/// # Safety
+///
+/// - `doc` must be a valid document handle.
+/// - `page_spec` must be a valid null-terminated UTF-8 string of 1-indexed
+///   pages, e.g. `"1-3,5"`. An empty string or `"all"` selects every page.
+/// - `flags` is a bitwise OR of `UNPDF_FLAG_*` constants.
+/// - Returns null on error, including a spec that selects no page of the
+///   document. Use `unpdf_last_error` to get the error message.
+/// - The returned string must be freed with `unpdf_free_string`.
+#[no_mangle]
+pub unsafe extern "C" fn unpdf_to_markdown_pages(
+    doc: *const UnpdfDocument,
+    page_spec: *const c_char,
+    flags: u32,
+) -> *mut c_char {
+    clear_last_error();
+
+    if doc.is_null() {
+        set_last_error("document is null");
+        return ptr::null_mut();
+    }
+
+    if page_spec.is_null() {
+        set_last_error("page_spec is null");
+        return ptr::null_mut();
+    }
+
+    let result = catch_unwind(|| {
+        let spec = CStr::from_ptr(page_spec)
+            .to_str()
+            .map_err(|e| e.to_string())?;
+        let selection = PageSelection::parse(spec)
+            .map_err(|e| format!("invalid page spec '{}': {}", spec, e))?;
+
+        let document = &(*doc).inner;
+        if !document.pages.iter().any(|p| selection.includes(p.number)) {
+            return Err(format!(
+                "page spec '{}' selects no pages (document has {} pages)",
+                spec,
+                document.page_count()
+            ));
+        }
+
+        let mut options = RenderOptions::new().with_pages(selection);
+        if flags & UNPDF_FLAG_FRONTMATTER != 0 {
+            options.include_frontmatter = true;
+        }
+        if flags & UNPDF_FLAG_ESCAPE_SPECIAL != 0 {
+            options.escape_special_chars = true;
+        }
+
+        crate::render::to_markdown(document, &options).map_err(|e| e.to_string())
+    });
+
+    match result {
+        Ok(Ok(md)) => match CString::new(md) {
+            Ok(s) => s.into_raw(),
+            Err(_) => {
+                set_last_error("output contains null byte");
+                ptr::null_mut()
+            }
+        },
+        Ok(Err(e)) => {
+            set_last_error(&e);
+            ptr::null_mut()
+        }
+        Err(_) => {
+            set_last_error("panic occurred during rendering");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Render the text of a single page.
+///
+/// Unlike `unpdf_page_to_text`, which returns the page's raw extracted text,
+/// this applies the same cleanup and layout as `unpdf_to_text`, so paging
+/// through a document yields exactly the text of the whole conversion.
+///
+/// # Safety
+///
+/// - `doc` must be a valid document handle.
+/// - `page_number` is 1-indexed.
+/// - Returns null on error. Use `unpdf_last_error` to get the error message.
+/// - The returned string must be freed with `unpdf_free_string`.
+#[no_mangle]
+pub unsafe extern "C" fn unpdf_page_text(
+    doc: *const UnpdfDocument,
+    page_number: c_int,
+) -> *mut c_char {
+    clear_last_error();
+
+    if doc.is_null() {
+        set_last_error("document is null");
+        return ptr::null_mut();
+    }
+
+    let result = catch_unwind(|| {
+        let document = &(*doc).inner;
+        if page_number < 1 || document.get_page(page_number as u32).is_none() {
+            return Err(format!(
+                "page {} out of range (document has {} pages)",
+                page_number,
+                document.page_count()
+            ));
+        }
+
+        let options = RenderOptions::default().with_page_list(vec![page_number as u32]);
+        crate::render::to_text(document, &options).map_err(|e| e.to_string())
+    });
+
+    match result {
+        Ok(Ok(text)) => match CString::new(text) {
+            Ok(s) => s.into_raw(),
+            Err(_) => {
+                set_last_error("output contains null byte");
+                ptr::null_mut()
+            }
+        },
+        Ok(Err(e)) => {
+            set_last_error(&e);
+            ptr::null_mut()
+        }
+        Err(_) => {
+            set_last_error("panic occurred during rendering");
+            ptr::null_mut()
+        }
+    }
+}
+
 /// Free a string allocated by this library.
 ///
 /// # Safety
@@ -999,6 +1141,79 @@ mod tests {
 
         let text = unsafe { unpdf_page_to_text(ptr::null(), 1) };
         assert!(text.is_null());
+
+        let spec = CString::new("1").unwrap();
+        let md = unsafe { unpdf_to_markdown_pages(ptr::null(), spec.as_ptr(), 0) };
+        assert!(md.is_null());
+
+        let text = unsafe { unpdf_page_text(ptr::null(), 1) };
+        assert!(text.is_null());
+    }
+
+    /// 세 페이지짜리 문서 핸들 (페이지마다 "Page N body" 문단 하나).
+    fn three_page_handle() -> *mut UnpdfDocument {
+        use crate::model::{Page, Paragraph};
+
+        let mut inner = Document::new();
+        for n in 1..=3 {
+            let mut page = Page::letter(n);
+            page.add_paragraph(Paragraph::with_text(format!("Page {} body", n)));
+            inner.add_page(page);
+        }
+        Box::into_raw(Box::new(UnpdfDocument { inner }))
+    }
+
+    unsafe fn take_string(s: *mut c_char) -> String {
+        assert!(!s.is_null(), "{:?}", last_error());
+        let owned = CStr::from_ptr(s).to_str().unwrap().to_string();
+        unpdf_free_string(s);
+        owned
+    }
+
+    fn last_error() -> String {
+        let err = unpdf_last_error();
+        unsafe { CStr::from_ptr(err) }.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_to_markdown_pages() {
+        let doc = three_page_handle();
+
+        let spec = CString::new("1,3").unwrap();
+        let md = unsafe { take_string(unpdf_to_markdown_pages(doc, spec.as_ptr(), 0)) };
+        assert!(md.contains("Page 1 body"));
+        assert!(!md.contains("Page 2 body"));
+        assert!(md.contains("Page 3 body"));
+
+        let spec = CString::new("2-x").unwrap();
+        let md = unsafe { unpdf_to_markdown_pages(doc, spec.as_ptr(), 0) };
+        assert!(md.is_null());
+        assert!(last_error().contains("invalid page spec"));
+
+        let spec = CString::new("7-9").unwrap();
+        let md = unsafe { unpdf_to_markdown_pages(doc, spec.as_ptr(), 0) };
+        assert!(md.is_null());
+        assert!(last_error().contains("selects no pages"));
+
+        let md = unsafe { unpdf_to_markdown_pages(doc, ptr::null(), 0) };
+        assert!(md.is_null());
+
+        unsafe { unpdf_free_document(doc) };
+    }
+
+    #[test]
+    fn test_page_text() {
+        let doc = three_page_handle();
+
+        let text = unsafe { take_string(unpdf_page_text(doc, 2)) };
+        assert_eq!(text.trim(), "Page 2 body");
+
+        for page in [0, -1, 4] {
+            assert!(unsafe { unpdf_page_text(doc, page) }.is_null());
+            assert!(last_error().contains("out of range"));
+        }
+
+        unsafe { unpdf_free_document(doc) };
     }
 
     #[test]
@@ -38,28 +38,42 @@ pub mod error;
 pub mod model;
 pub mod parser;
 pub mod render;
+pub mod vfs;
 
 #[cfg(feature = "ffi")]
 pub mod ffi;
 
 // Re-export commonly used types
 pub use convert::{
-    ConvertOptions, ConvertResult, ConverterRegistry, DocumentConverter, OutputFormat,
+    ConvertOptions, ConvertResult, ConverterRegistry, DocumentConverter, OutputFormat, UnpdfEngine,
 };
-pub use detect::{detect_format_from_bytes, PdfFormat};
+pub use detect::{detect_format_from_bytes, sniff_format, DetectedFormat, PdfFormat};
 #[cfg(not(target_arch = "wasm32"))]
-pub use detect::{detect_format_from_path, is_pdf};
+pub use detect::{detect_format_from_path, is_pdf, sniff_format_from_path};
 pub use error::{Error, Result};
 pub use model::{
-    Alignment, Block, Document, ExtractionQuality, FieldType, FieldValue, FormField, InlineContent,
-    ListInfo, Metadata, Outline, Page, Paragraph, ParagraphStyle, QualityAccumulator, Resource,
-    ResourceType, Table, TableCell, TableRow, TextRun, TextStyle,
+    Alignment, Annotation, AnnotationKind, Block, BlockDedup, BlockFilter, BlockSource, DedupScope,
+    DetectedToc, Document, EncryptionKind, ExtractionQuality, FieldType, FieldValue, Figure,
+    FormField, InferredProperties, InlineContent, ListInfo, Metadata, Outline, Page,
+    PageReconciliation, PageStats, Paragraph, ParagraphStyle, Provenance, QualityAccumulator,
+    Reconciliation, Resource, ResourceType, Revision, Section, Table, TableCell, TableDetection,
+    TableRejection, TableRow, TextRun, TextSource, TextStrategy, TextStyle, VersionedJson, Word,
+    SCHEMA_VERSION,
+};
+pub use parser::{
+    ColumnDetectionConfig, DocumentInfo, ExtractionStrategy, PageStreamOptions, ParseEvent,
+    ParseOptions, PdfParser, Script, ScriptSpacing, SpacingPolicy, TextSpan, XFA_DATASETS_ID,
 };
-pub use parser::{PageStreamOptions, ParseEvent, ParseOptions, PdfParser};
 pub use render::{
-    CleanupOptions, CleanupPreset, HeadingConfig, JsonFormat, PageMarkerStyle, PageSelection,
-    RenderOptions, TableFallback,
+    schema, AnnotationStyle, CleanupOptions, CleanupPreset, DefinitionStyle, DigitNormalization,
+    HeadingConfig, HeadingSignals, JsonFormat, LevelNormalization, ListNumbering,
+    NormalizationForm, OverflowStrategy, PageMarkerStyle, PageSelection, PiiRedaction,
+    RedactionRule, RenderOptions, ResourceNaming, SafeFileNames, TableFallback, TextLayout,
+    JSON_SCHEMA,
 };
+#[cfg(feature = "image-convert")]
+pub use render::{ImageConversion, ImageFormat};
+pub use vfs::{FileSource, MemoryFileSource};
 
 use std::io::Read;
 #[cfg(not(target_arch = "wasm32"))]
@@ -163,6 +177,61 @@ pub fn parse_reader_with_options<R: Read>(reader: R, options: ParseOptions) -> R
     parser.parse()
 }
 
+/// Parse a PDF read from `source` rather than the local file system, such
+/// as an in-memory archive or an object storage adapter; see [`vfs`].
+///
+/// # Example
+///
+/// ```no_run
+/// use unpdf::{parse_from_source, MemoryFileSource};
+///
+/// let source = MemoryFileSource::new()
+///     .with_file("report.pdf", std::fs::read("document.pdf").unwrap());
+/// let doc = parse_from_source(&source, "report.pdf").unwrap();
+/// ```
+pub fn parse_from_source<S, P>(source: &S, path: P) -> Result<Document>
+where
+    S: FileSource + ?Sized,
+    P: AsRef<std::path::Path>,
+{
+    parse_from_source_with_options(source, path, ParseOptions::default())
+}
+
+/// Parse a PDF read from `source` with custom options.
+pub fn parse_from_source_with_options<S, P>(
+    source: &S,
+    path: P,
+    options: ParseOptions,
+) -> Result<Document>
+where
+    S: FileSource + ?Sized,
+    P: AsRef<std::path::Path>,
+{
+    PdfParser::open_from_source(source, path, options)?.parse()
+}
+
+/// Parse a PDF from a seekable reader, fetching only the byte ranges that
+/// parsing needs — the xref, the page tree and the objects of the selected
+/// pages. With a reader that turns reads into HTTP range requests, a few
+/// pages of a large remote PDF are extracted without downloading all of it.
+/// See [`PdfParser::from_seekable`].
+///
+/// # Example
+///
+/// ```no_run
+/// use unpdf::{parse_seekable, ParseOptions, PageSelection};
+///
+/// let file = std::fs::File::open("large.pdf").unwrap();
+/// let options = ParseOptions::new().with_pages(PageSelection::Range(1..=3));
+/// let doc = parse_seekable(file, options).unwrap();
+/// ```
+pub fn parse_seekable<R>(reader: R, options: ParseOptions) -> Result<Document>
+where
+    R: parser::raw::ReadSeek + 'static,
+{
+    PdfParser::from_seekable(reader, options)?.parse()
+}
+
 /// Parse a password-protected PDF file.
 ///
 /// # Arguments
@@ -203,6 +272,72 @@ pub fn extract_text<P: AsRef<Path>>(path: P) -> Result<String> {
     Ok(doc.plain_text())
 }
 
+/// Read a PDF file's metadata, page count, encryption and bookmark count
+/// without parsing its pages — much faster than [`parse_file`] for scanning
+/// large collections. Objects are read only as the page tree, Info
+/// dictionary and outline reach them.
+///
+/// A document that needs a user password still opens: its
+/// [`DocumentInfo`] reports the encryption and whatever is readable
+/// without the password.
+///
+/// # Example
+///
+/// ```no_run
+/// let info = unpdf::peek("document.pdf").unwrap();
+/// println!("{} pages, {} bookmarks", info.metadata.page_count, info.outline_count);
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn peek<P: AsRef<Path>>(path: P) -> Result<DocumentInfo> {
+    let data = std::fs::read(path)?;
+    if let Err(Error::UnknownFormat) = detect_format_from_bytes(&data) {
+        return Err(sniff_format(&data).not_pdf_error());
+    }
+    parser::peek_data(data)
+}
+
+/// [`peek`] at a PDF held in memory.
+pub fn peek_bytes(data: &[u8]) -> Result<DocumentInfo> {
+    if let Err(Error::UnknownFormat) = detect_format_from_bytes(data) {
+        return Err(sniff_format(data).not_pdf_error());
+    }
+    parser::peek_data(data.to_vec())
+}
+
+/// Apply a cleanup preset to text from any source, such as OCR output or
+/// text extracted by another tool, so it matches what unpdf itself emits.
+///
+/// # Example
+///
+/// ```
+/// use unpdf::{cleanup_text, CleanupPreset};
+///
+/// let text = cleanup_text("ﬁnancial report", CleanupPreset::Aggressive);
+/// assert_eq!(text, "financial report");
+/// ```
+pub fn cleanup_text(text: &str, preset: CleanupPreset) -> String {
+    render::CleanupPipeline::from_preset(preset).process(text)
+}
+
+/// Extract only the tables of a PDF file, each with its page number.
+///
+/// Skips heading detection, cleanup and rendering; see
+/// [`PdfParser::extract_tables`].
+///
+/// # Example
+///
+/// ```no_run
+/// use unpdf::{extract_tables, ParseOptions};
+///
+/// for (page, table) in extract_tables("report.pdf", ParseOptions::new()).unwrap() {
+///     println!("page {}: {} rows", page, table.rows.len());
+/// }
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn extract_tables<P: AsRef<Path>>(path: P, options: ParseOptions) -> Result<Vec<(u32, Table)>> {
+    PdfParser::open_with_options(path, options)?.extract_tables()
+}
+
 /// Convert a PDF to Markdown.
 ///
 /// # Arguments
@@ -332,6 +467,12 @@ impl Unpdf {
         self
     }
 
+    /// Capture path-drawn charts and diagrams as SVG resources.
+    pub fn with_vector_graphics(mut self, enabled: bool) -> Self {
+        self.parse_options = self.parse_options.with_vector_graphics(enabled);
+        self
+    }
+
     /// Set image output directory.
     pub fn with_image_dir(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
         self.render_options = self.render_options.with_image_dir(dir);
@@ -356,6 +497,14 @@ impl Unpdf {
         self
     }
 
+    /// Embed extraction provenance into frontmatter and JSON output, with
+    /// the SHA-256 of the source file.
+    pub fn with_provenance(mut self) -> Self {
+        self.parse_options = self.parse_options.with_source_hash(true);
+        self.render_options = self.render_options.with_provenance(true);
+        self
+    }
+
     /// Set document password.
     pub fn with_password(mut self, password: impl Into<String>) -> Self {
         self.parse_options = self.parse_options.with_password(password);
@@ -380,6 +529,53 @@ impl Unpdf {
         })
     }
 
+    /// Parse several PDF files and merge them into one document.
+    ///
+    /// Each file becomes an H1 section titled with its metadata title, or the
+    /// file name when the title is missing. See [`Document::merge`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn parse_many<P: AsRef<Path>>(self, paths: &[P]) -> Result<UnpdfResult> {
+        let mut parts = Vec::with_capacity(paths.len());
+        for path in paths {
+            let path = path.as_ref();
+            let parser = PdfParser::open_with_options(path, self.parse_options.clone())?;
+            let document = parser.parse()?;
+            let label = document
+                .metadata
+                .title
+                .clone()
+                .filter(|t| !t.trim().is_empty())
+                .unwrap_or_else(|| {
+                    path.file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| path.display().to_string())
+                });
+            parts.push((label, document));
+        }
+        // The selection was applied per file while parsing; merged pages are
+        // renumbered, so it must not be applied again when rendering.
+        let mut render_options = self.render_options;
+        render_options.page_selection = PageSelection::All;
+        Ok(UnpdfResult {
+            document: Document::merge(parts),
+            render_options,
+        })
+    }
+
+    /// Parse a PDF read from `source`; see [`parse_from_source`].
+    pub fn parse_from_source<S, P>(self, source: &S, path: P) -> Result<UnpdfResult>
+    where
+        S: FileSource + ?Sized,
+        P: AsRef<std::path::Path>,
+    {
+        let parser = PdfParser::open_from_source(source, path, self.parse_options)?;
+        let document = parser.parse()?;
+        Ok(UnpdfResult {
+            document,
+            render_options: self.render_options,
+        })
+    }
+
     /// Parse a PDF from bytes.
     pub fn parse_bytes(self, data: &[u8]) -> Result<UnpdfResult> {
         let parser = PdfParser::from_bytes_with_options(data, self.parse_options)?;
@@ -416,9 +612,14 @@ impl UnpdfResult {
         render::to_text(&self.document, &self.render_options)
     }
 
+    /// Convert to plain text with one line per block, joined by `separator`.
+    pub fn to_single_line_text(&self, separator: &str) -> Result<String> {
+        render::to_single_line_text(&self.document, &self.render_options, separator)
+    }
+
     /// Convert to JSON.
     pub fn to_json(&self, format: JsonFormat) -> Result<String> {
-        render::to_json(&self.document, format)
+        render::to_json_with_options(&self.document, format, &self.render_options)
     }
 
     /// Get plain text without cleanup.
@@ -500,6 +701,12 @@ mod tests {
         assert!(matches!(result, Err(Error::UnknownFormat)));
     }
 
+    #[test]
+    fn test_parse_bytes_names_wrong_format() {
+        let result = parse_bytes(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR");
+        assert!(matches!(result, Err(Error::NotPdf(DetectedFormat::Png))));
+    }
+
     #[test]
     fn test_detect_valid_pdf_17() {
         let data = b"%PDF-1.7\n%test";
@@ -0,0 +1,139 @@
+//! Reviewer annotations: sticky notes and text markup.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A comment or markup annotation on a page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    /// What kind of annotation this is
+    pub kind: AnnotationKind,
+
+    /// Author (`/T`), usually the reviewer's name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    /// Last modification date (`/M`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<DateTime<Utc>>,
+
+    /// Comment text (`/Contents`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contents: Option<String>,
+
+    /// Page text under the marked regions, for highlight, underline and
+    /// strike-out annotations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub covered_text: Option<String>,
+
+    /// Bounding box `[x0, y0, x1, y1]` in PDF user space
+    pub rect: [f32; 4],
+}
+
+/// Supported annotation subtypes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationKind {
+    /// A sticky note (`/Text`)
+    Note,
+    /// Highlighted text
+    Highlight,
+    /// Underlined text
+    Underline,
+    /// Struck-out text
+    StrikeOut,
+}
+
+impl AnnotationKind {
+    /// Map a PDF `/Subtype` name; `None` for subtypes that are not extracted
+    /// (links, widgets, popups, ...).
+    pub fn from_subtype(subtype: &str) -> Option<Self> {
+        match subtype {
+            "Text" => Some(Self::Note),
+            "Highlight" => Some(Self::Highlight),
+            "Underline" => Some(Self::Underline),
+            "StrikeOut" => Some(Self::StrikeOut),
+            _ => None,
+        }
+    }
+
+    /// Whether the annotation marks up page text (and so can cover some).
+    pub fn is_markup(&self) -> bool {
+        !matches!(self, Self::Note)
+    }
+
+    /// Human-readable label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Note => "Note",
+            Self::Highlight => "Highlight",
+            Self::Underline => "Underline",
+            Self::StrikeOut => "Strike-out",
+        }
+    }
+}
+
+impl Annotation {
+    /// One-line summary: kind, author and date, quoted covered text, then
+    /// the comment. Used by the Markdown renderer.
+    pub fn summary(&self) -> String {
+        let mut byline = Vec::new();
+        if let Some(author) = self.author.as_deref().filter(|a| !a.trim().is_empty()) {
+            byline.push(author.trim().to_string());
+        }
+        if let Some(date) = self.modified {
+            byline.push(date.format("%Y-%m-%d").to_string());
+        }
+        let mut text = format!("**{}**", self.kind.label());
+        if !byline.is_empty() {
+            text.push_str(&format!(" ({})", byline.join(", ")));
+        }
+        let mut parts = Vec::new();
+        if let Some(covered) = self.covered_text.as_deref().filter(|t| !t.is_empty()) {
+            parts.push(format!("“{}”", covered));
+        }
+        if let Some(contents) = self.contents.as_deref() {
+            let contents = contents.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !contents.is_empty() {
+                parts.push(contents);
+            }
+        }
+        if !parts.is_empty() {
+            text.push_str(": ");
+            text.push_str(&parts.join(" — "));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_summary() {
+        let annotation = Annotation {
+            kind: AnnotationKind::Highlight,
+            author: Some("Dana".to_string()),
+            modified: Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).single(),
+            contents: Some("Check this\nfigure".to_string()),
+            covered_text: Some("revenue grew 12%".to_string()),
+            rect: [0.0; 4],
+        };
+        assert_eq!(
+            annotation.summary(),
+            "**Highlight** (Dana, 2024-03-01): “revenue grew 12%” — Check this figure"
+        );
+
+        let note = Annotation {
+            kind: AnnotationKind::Note,
+            author: None,
+            modified: None,
+            contents: None,
+            covered_text: None,
+            rect: [0.0; 4],
+        };
+        assert_eq!(note.summary(), "**Note**");
+    }
+}
@@ -0,0 +1,241 @@
+//! Removal of text blocks repeated across pages.
+//!
+//! Slide decks and some reports repeat the same title or boilerplate block
+//! on every page. [`Document::duplicate_blocks`] finds paragraphs and lists
+//! that repeat an earlier one, exactly or nearly (Jaccard similarity of
+//! their word 3-gram shingles), so all but the first can be dropped.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+use super::{Block, Document};
+
+/// Words per shingle.
+const SHINGLE_WORDS: usize = 3;
+
+/// Fewest shingles a block needs to be matched approximately; shorter
+/// blocks only match exactly.
+const MIN_SHINGLES: usize = 4;
+
+/// Shortest normalized text considered. Short lines ("Yes", "Example:")
+/// legitimately repeat.
+const MIN_DEDUP_CHARS: usize = 12;
+
+/// Which occurrences of a repeated block are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DedupScope {
+    /// Keep the first occurrence in the whole document
+    #[default]
+    Document,
+    /// Keep the first occurrence in every run of this many pages
+    /// (pages 1–N, N+1–2N, ...)
+    Pages(u32),
+}
+
+/// Settings for [`Document::duplicate_blocks`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockDedup {
+    /// Where a first occurrence is kept
+    pub scope: DedupScope,
+    /// Shingle similarity (0.0–1.0) at or above which two blocks count as
+    /// the same; 1.0 matches identical text only
+    pub similarity: f32,
+}
+
+impl BlockDedup {
+    /// Document-wide deduplication of blocks at least 90% similar.
+    pub fn new() -> Self {
+        Self {
+            scope: DedupScope::Document,
+            similarity: 0.9,
+        }
+    }
+
+    /// Set where first occurrences are kept.
+    pub fn with_scope(mut self, scope: DedupScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Set the similarity threshold, clamped to 0.0–1.0.
+    pub fn with_similarity(mut self, similarity: f32) -> Self {
+        self.similarity = similarity.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl Default for BlockDedup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Document {
+    /// Paragraphs and lists that repeat an earlier block within the scope
+    /// of `dedup`, as `(page number, block index)` pairs in page order.
+    /// Text is compared case-insensitively, ignoring punctuation and
+    /// numbers, so "Q3 Business Review — 4/12" repeats "Q3 business
+    /// review — 5/12".
+    pub fn duplicate_blocks(&self, dedup: &BlockDedup) -> Vec<(u32, usize)> {
+        let mut duplicates = Vec::new();
+        let mut group = None;
+        let mut exact: HashSet<String> = HashSet::new();
+        let mut seen: Vec<HashSet<u64>> = Vec::new();
+
+        for page in &self.pages {
+            let page_group = match dedup.scope {
+                DedupScope::Document => 0,
+                DedupScope::Pages(n) => page.number.saturating_sub(1) / n.max(1),
+            };
+            if group != Some(page_group) {
+                group = Some(page_group);
+                exact.clear();
+                seen.clear();
+            }
+
+            for (index, block) in page.elements.iter().enumerate() {
+                if !matches!(block, Block::Paragraph(_) | Block::List { .. }) {
+                    continue;
+                }
+                let mut text = String::new();
+                block.append_plain_text(&mut text);
+                let key = dedup_key(&text);
+                if key.len() < MIN_DEDUP_CHARS {
+                    continue;
+                }
+                if exact.contains(&key) {
+                    duplicates.push((page.number, index));
+                    continue;
+                }
+                let shingles = shingles(&key);
+                if dedup.similarity < 1.0
+                    && shingles.len() >= MIN_SHINGLES
+                    && seen
+                        .iter()
+                        .any(|other| similar(&shingles, other, dedup.similarity))
+                {
+                    duplicates.push((page.number, index));
+                    continue;
+                }
+                exact.insert(key);
+                if shingles.len() >= MIN_SHINGLES {
+                    seen.push(shingles);
+                }
+            }
+        }
+        duplicates
+    }
+
+    /// Remove the blocks [`Document::duplicate_blocks`] finds, returning
+    /// how many were removed.
+    pub fn remove_duplicate_blocks(&mut self, dedup: &BlockDedup) -> usize {
+        let duplicates = self.duplicate_blocks(dedup);
+        for page in &mut self.pages {
+            let number = page.number;
+            let mut index = 0;
+            page.retain_blocks_where(|_| {
+                index += 1;
+                duplicates.binary_search(&(number, index - 1)).is_err()
+            });
+        }
+        duplicates.len()
+    }
+}
+
+/// Lowercased words of `text`, numbers dropped, joined by single spaces.
+fn dedup_key(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty() && !w.chars().all(|c| c.is_numeric()))
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Hashes of the word 3-grams of a [`dedup_key`].
+fn shingles(key: &str) -> HashSet<u64> {
+    let words: Vec<&str> = key.split(' ').collect();
+    words
+        .windows(SHINGLE_WORDS)
+        .map(|window| {
+            let mut hasher = DefaultHasher::new();
+            window.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect()
+}
+
+/// Whether the Jaccard similarity of `a` and `b` reaches `threshold`.
+fn similar(a: &HashSet<u64>, b: &HashSet<u64>, threshold: f32) -> bool {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    // 크기 비율이 유사도의 상한이다 — 교집합을 세기 전에 거른다.
+    if (small.len() as f32) < large.len() as f32 * threshold {
+        return false;
+    }
+    let shared = small.iter().filter(|h| large.contains(h)).count();
+    shared as f32 >= (a.len() + b.len() - shared) as f32 * threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Page, Paragraph};
+
+    /// 페이지마다 같은 제목 블록을 되풀이하는 슬라이드 문서.
+    fn slides() -> Document {
+        let bodies = [
+            "Revenue grew in every region",
+            "Costs fell after the merger",
+            "Hiring slowed in the third month",
+            "Outlook for next quarter",
+        ];
+        let mut doc = Document::new();
+        for (n, body) in (1..=4).zip(bodies) {
+            let mut page = Page::letter(n);
+            page.add_paragraph(Paragraph::with_text("ACME Quarterly Business Review"));
+            page.add_paragraph(Paragraph::with_text(format!(
+                "Confidential, prepared by the finance team for internal use, slide {}",
+                n
+            )));
+            page.add_paragraph(Paragraph::with_text(body));
+            page.add_paragraph(Paragraph::with_text("Yes"));
+            doc.add_page(page);
+        }
+        doc
+    }
+
+    #[test]
+    fn test_duplicate_blocks_document_scope() {
+        let doc = slides();
+        let dups = doc.duplicate_blocks(&BlockDedup::new());
+        // 제목과 (숫자만 다른) 안내문은 첫 쪽에만 남는다. 짧은 "Yes" 와 본문은 남는다.
+        assert_eq!(dups, vec![(2, 0), (2, 1), (3, 0), (3, 1), (4, 0), (4, 1)]);
+    }
+
+    #[test]
+    fn test_near_duplicates_need_similarity() {
+        let mut doc = slides();
+        doc.pages[1].elements[1] = Block::Paragraph(Paragraph::with_text(
+            "Confidential, prepared by the finance team for internal use only, slide 2",
+        ));
+        let exact = BlockDedup::new().with_similarity(1.0);
+        assert!(!doc.duplicate_blocks(&exact).contains(&(2, 1)));
+        let near = BlockDedup::new().with_similarity(0.6);
+        assert!(doc.duplicate_blocks(&near).contains(&(2, 1)));
+    }
+
+    #[test]
+    fn test_page_scope_and_removal() {
+        let mut doc = slides();
+        let dedup = BlockDedup::new().with_scope(DedupScope::Pages(2));
+        assert_eq!(
+            doc.duplicate_blocks(&dedup),
+            vec![(2, 0), (2, 1), (4, 0), (4, 1)]
+        );
+
+        assert_eq!(doc.remove_duplicate_blocks(&dedup), 4);
+        assert_eq!(doc.pages[2].elements.len(), 4);
+        assert_eq!(doc.pages[3].elements.len(), 2);
+        assert!(doc.duplicate_blocks(&dedup).is_empty());
+    }
+}
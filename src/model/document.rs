@@ -1,9 +1,16 @@
 //! Document-level types.
 
-use super::{ExtractionQuality, FormField, Page, Resource};
+use super::section::build_sections;
+use super::{
+    sha256_hex, Annotation, Block, BlockSource, ExtractionQuality, FormField, Page, Paragraph,
+    Provenance, Resource, Revision, Section,
+};
+use crate::render::{sanitize_metadata_value, PageSelection};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::RangeInclusive;
 
 /// A parsed PDF document.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,8 +21,9 @@ pub struct Document {
     /// Pages in the document
     pub pages: Vec<Page>,
 
-    /// Embedded resources (images, fonts, etc.)
-    pub resources: HashMap<String, Resource>,
+    /// Embedded resources (images, fonts, etc.), keyed by id. Ordered so
+    /// serialization and iteration are identical across runs.
+    pub resources: BTreeMap<String, Resource>,
 
     /// Document outline (bookmarks)
     pub outline: Option<Outline>,
@@ -25,6 +33,16 @@ pub struct Document {
 
     /// Form fields extracted from AcroForm
     pub form_fields: Vec<FormField>,
+
+    /// Extraction provenance, recorded by the parser. Not serialized by
+    /// default; see `RenderOptions::with_provenance`.
+    #[serde(default, skip_serializing)]
+    pub provenance: Option<Provenance>,
+
+    /// Revisions of an incrementally updated file, oldest first, up to the
+    /// one parsed. Empty when the file was never updated in place.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revisions: Vec<Revision>,
 }
 
 impl Document {
@@ -33,10 +51,12 @@ impl Document {
         Self {
             metadata: Metadata::default(),
             pages: Vec::new(),
-            resources: HashMap::new(),
+            resources: BTreeMap::new(),
             outline: None,
             extraction_quality: ExtractionQuality::default(),
             form_fields: Vec::new(),
+            provenance: None,
+            revisions: Vec::new(),
         }
     }
 
@@ -46,11 +66,17 @@ impl Document {
     }
 
     /// Get a page by number (1-indexed).
+    ///
+    /// Looks the page up by [`Page::number`], so it still finds pages after
+    /// [`Document::remove_pages`] or a page-selected parse.
     pub fn get_page(&self, page_num: u32) -> Option<&Page> {
         if page_num == 0 {
             return None;
         }
-        self.pages.get((page_num - 1) as usize)
+        match self.pages.get((page_num - 1) as usize) {
+            Some(page) if page.number == page_num => Some(page),
+            _ => self.pages.iter().find(|page| page.number == page_num),
+        }
     }
 
     /// Add a page to the document.
@@ -68,6 +94,17 @@ impl Document {
         self.resources.get(id)
     }
 
+    /// Resources ordered by page and then by index within the page.
+    ///
+    /// Ids are compared with their digit runs read as numbers, so
+    /// `page2_Im10` follows `page2_Im9` and precedes `page10_Im1`. Use this
+    /// order when writing resources out as files.
+    pub fn resources_in_page_order(&self) -> Vec<(&String, &Resource)> {
+        let mut resources: Vec<_> = self.resources.iter().collect();
+        resources.sort_by(|a, b| natural_cmp(a.0, b.0));
+        resources
+    }
+
     /// Check if the document has any pages.
     pub fn is_empty(&self) -> bool {
         self.pages.is_empty()
@@ -81,6 +118,572 @@ impl Document {
             .collect::<Vec<_>>()
             .join("\n\n")
     }
+
+    /// Group every page's list items into `Block::List`; see
+    /// [`Page::group_lists`]. Lists do not run across pages.
+    pub fn group_lists(&mut self) {
+        for page in &mut self.pages {
+            page.group_lists();
+        }
+    }
+
+    /// Guess a title from the first page that has text.
+    ///
+    /// Takes the first H1 on that page, or failing that the first heading of
+    /// the highest level present (heading levels follow font size, so this is
+    /// the largest text on the page). Returns `None` when the page has no
+    /// usable heading; body text is never promoted to a title.
+    pub fn infer_title(&self) -> Option<String> {
+        let page = self
+            .pages
+            .iter()
+            .find(|p| !p.plain_text().trim().is_empty())?;
+        let headings: Vec<(u8, String)> = page
+            .elements
+            .iter()
+            .filter_map(|block| match block {
+                Block::Paragraph(p) => p.heading_level().map(|level| (level, p.plain_text())),
+                _ => None,
+            })
+            .map(|(level, text)| (level, text.split_whitespace().collect::<Vec<_>>().join(" ")))
+            .filter(|(_, text)| is_plausible_title(text))
+            .collect();
+        let top = headings.iter().map(|(level, _)| *level).min()?;
+        headings
+            .into_iter()
+            .find(|(level, _)| *level == top)
+            .map(|(_, text)| text)
+    }
+
+    /// Regroup the content by heading hierarchy instead of by page.
+    ///
+    /// Each heading opens a [`Section`] holding the blocks up to the next
+    /// heading, with deeper headings nested as subsections; a section may
+    /// span several pages or share a page with others. Page-level data
+    /// (annotations, words, images) and the document metadata are dropped.
+    pub fn into_sections(self) -> Vec<Section> {
+        build_sections(self.pages)
+    }
+
+    /// Pages covered by the bookmark or heading titled `title`, e.g. to
+    /// convert a single chapter with [`PageSelection::Range`].
+    ///
+    /// Titles are compared case-insensitively with whitespace collapsed; an
+    /// exact match wins over a title starting with `title`, which wins over
+    /// one containing it. The outline is searched first (see
+    /// [`pages_for_bookmark`](Self::pages_for_bookmark)). Without a matching
+    /// bookmark, headings in the text are used and the range ends on the
+    /// page of the last block before the next heading of the same or a
+    /// higher level.
+    pub fn pages_for_heading(&self, title: &str) -> Option<RangeInclusive<u32>> {
+        let query = normalize_title(title);
+        if query.is_empty() {
+            return None;
+        }
+        if let Some(range) = self.pages_for_bookmark(title) {
+            return Some(range);
+        }
+
+        let headings: Vec<(usize, u32, u8, String)> = self
+            .blocks()
+            .enumerate()
+            .filter_map(|(i, (page, block))| match block {
+                Block::Paragraph(p) => p
+                    .heading_level()
+                    .map(|level| (i, page, level, p.plain_text())),
+                _ => None,
+            })
+            .collect();
+        let h = best_match(headings.iter().map(|(_, _, _, t)| t.as_str()), &query)?;
+        let (index, start, level, _) = headings[h];
+        let stop = headings[h + 1..]
+            .iter()
+            .find(|(_, _, l, _)| *l <= level)
+            .map(|(i, _, _, _)| *i);
+        let end = self
+            .blocks()
+            .enumerate()
+            .skip(index)
+            .take_while(|(i, _)| stop.map_or(true, |stop| *i < stop))
+            .map(|(_, (page, _))| page)
+            .max()
+            .unwrap_or(start);
+        Some(start..=end)
+    }
+
+    /// Pages covered by the bookmark titled `title`, matched as in
+    /// [`pages_for_heading`](Self::pages_for_heading) but against the
+    /// outline only.
+    pub fn pages_for_bookmark(&self, title: &str) -> Option<RangeInclusive<u32>> {
+        let query = normalize_title(title);
+        if query.is_empty() {
+            return None;
+        }
+        let ranges = self.outline_page_ranges();
+        let i = best_match(ranges.iter().map(|(item, _)| item.title.as_str()), &query)?;
+        ranges[i].1.clone()
+    }
+
+    /// Every bookmark in document order, nested ones after their parent,
+    /// with the pages it covers.
+    ///
+    /// A bookmark's range runs to the page before the next bookmark at the
+    /// same or a higher level, or to the last page. One without a page of
+    /// its own starts on its first descendant's page; the range is `None`
+    /// when no descendant has one either. Empty without an outline.
+    pub fn outline_page_ranges(&self) -> Vec<(&OutlineItem, Option<RangeInclusive<u32>>)> {
+        let Some(outline) = &self.outline else {
+            return Vec::new();
+        };
+        let last_page = self.pages.iter().map(|p| p.number).max().unwrap_or(0);
+        // 책갈피를 문서 순서로 펼친다. 쪽이 없는 항목은 하위 항목의 첫 쪽을 쓴다.
+        let mut flat = Vec::new();
+        flatten_outline(&outline.items, &mut flat);
+        (0..flat.len())
+            .map(|i| {
+                let (item, start) = flat[i];
+                let range = start.map(|start| {
+                    let end = flat[i + 1..]
+                        .iter()
+                        .filter(|(next, _)| next.level <= item.level)
+                        .find_map(|(_, page)| *page)
+                        .map(|next| next.saturating_sub(1).max(start))
+                        .unwrap_or(last_page.max(start));
+                    start..=end
+                });
+                (item, range)
+            })
+            .collect()
+    }
+
+    /// Blocks repeating a bookmark title as a running head ("Chapter 2 —
+    /// Installation" at the top of every page of the chapter), as
+    /// `(page number, block index)` pairs in document order.
+    ///
+    /// A paragraph is a running head when it is among the first or last two
+    /// blocks of its page, its text is a bookmark title
+    /// once numbers, punctuation and a leading or trailing "Chapter",
+    /// "Part", "Section" or "Appendix" are set aside, and that title recurs
+    /// this way on another page. The copy on the bookmark's own first page
+    /// is the chapter heading and is left out. Empty without an outline.
+    pub fn running_heads(&self) -> Vec<(u32, usize)> {
+        let mut starts: HashMap<String, Option<u32>> = HashMap::new();
+        for (item, range) in self.outline_page_ranges() {
+            let key = running_head_key(&item.title);
+            // 너무 짧은 제목은 본문 줄과 우연히 겹치기 쉽다.
+            if key.chars().count() >= 3 {
+                starts.entry(key).or_insert(range.map(|r| *r.start()));
+            }
+        }
+        if starts.is_empty() {
+            return Vec::new();
+        }
+
+        let mut found: BTreeMap<&str, Vec<(u32, usize)>> = BTreeMap::new();
+        for page in &self.pages {
+            let count = page.elements.len();
+            for (index, block) in page.elements.iter().enumerate() {
+                if index >= RUNNING_HEAD_EDGE && index + RUNNING_HEAD_EDGE < count {
+                    continue;
+                }
+                let Block::Paragraph(p) = block else {
+                    continue;
+                };
+                if let Some((key, _)) = starts.get_key_value(&running_head_key(&p.plain_text())) {
+                    found.entry(key).or_default().push((page.number, index));
+                }
+            }
+        }
+
+        let mut heads = Vec::new();
+        for (key, places) in found {
+            let mut pages: Vec<u32> = places.iter().map(|(page, _)| *page).collect();
+            pages.dedup();
+            if pages.len() < 2 {
+                continue;
+            }
+            // 책갈피 첫 쪽의 사본이 장 제목이다. 첫 쪽을 모르면 처음 것을 남긴다.
+            let heading = match starts[key] {
+                Some(start) => places.iter().position(|(page, _)| *page == start),
+                None => Some(0),
+            };
+            heads.extend(
+                places
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| Some(*i) != heading)
+                    .map(|(_, place)| place),
+            );
+        }
+        heads.sort_unstable();
+        heads
+    }
+
+    /// Remove the blocks [`running_heads`](Self::running_heads) reports,
+    /// returning how many were removed.
+    pub fn remove_running_heads(&mut self) -> usize {
+        let heads = self.running_heads();
+        for page in &mut self.pages {
+            let number = page.number;
+            let mut index = 0;
+            page.retain_blocks_where(|_| {
+                index += 1;
+                heads.binary_search(&(number, index - 1)).is_err()
+            });
+        }
+        heads.len()
+    }
+
+    /// Annotations on all pages, paired with their page number.
+    pub fn annotations(&self) -> impl Iterator<Item = (u32, &Annotation)> {
+        self.pages
+            .iter()
+            .flat_map(|page| page.annotations.iter().map(move |a| (page.number, a)))
+    }
+
+    /// Pages whose content a later incremental update replaced, ascending.
+    ///
+    /// The text extracted for these pages is the newest version; earlier
+    /// versions can be parsed with `ParseOptions::with_revision`.
+    pub fn revised_pages(&self) -> Vec<u32> {
+        let mut pages: Vec<u32> = self
+            .revisions
+            .iter()
+            .flat_map(|r| r.revised_pages.iter().copied())
+            .collect();
+        pages.sort_unstable();
+        pages.dedup();
+        pages
+    }
+
+    /// The content-stream objects and operators `block` was extracted from.
+    ///
+    /// `block` must be a reference into this document's pages, such as one
+    /// yielded by iterating `page.elements`; a copy is not found. `None` for
+    /// blocks without a recorded source.
+    pub fn block_source(&self, block: &Block) -> Option<&BlockSource> {
+        self.pages.iter().find_map(|page| {
+            let index = page.elements.iter().position(|b| std::ptr::eq(b, block))?;
+            page.block_source(index)
+        })
+    }
+
+    /// SHA-256 of the extracted plain text (lowercase hex).
+    ///
+    /// Independent of the source bytes, so re-saved or re-encrypted copies of
+    /// the same content hash identically. Useful for downstream dedup.
+    pub fn content_hash(&self) -> String {
+        sha256_hex(self.plain_text().as_bytes())
+    }
+
+    /// Merge several parsed documents into one.
+    ///
+    /// Each part is introduced by an H1 heading carrying its label; a part
+    /// without pages has its heading put before the next part's, or only in
+    /// the outline when no pages follow. Pages are renumbered consecutively, resource ids are prefixed with `docN_` so the
+    /// parts share one namespace, and the outline gains one top-level entry
+    /// per part. The labels are recorded in `Metadata::sources`.
+    pub fn merge<I>(parts: I) -> Self
+    where
+        I: IntoIterator<Item = (String, Document)>,
+    {
+        let mut merged = Document::new();
+        let mut outline = Outline::new();
+        let mut authors: Vec<String> = Vec::new();
+        let mut all_scans = true;
+        // 페이지 없는 부분의 제목과 목차 항목 — 다음 실제 페이지를 기다린다.
+        let mut pending: Vec<Block> = Vec::new();
+        let mut pending_items: Vec<usize> = Vec::new();
+
+        for (idx, (label, mut doc)) in parts.into_iter().enumerate() {
+            let prefix = format!("doc{}_", idx + 1);
+            let offset = merged.page_count();
+
+            pending.push(Block::Paragraph(Paragraph::heading(label.clone(), 1)));
+            if let Some(first) = doc.pages.first_mut() {
+                let headings = std::mem::take(&mut pending);
+                if !first.block_sources.is_empty() {
+                    first.block_sources.splice(0..0, vec![None; headings.len()]);
+                }
+                first.elements.splice(0..0, headings);
+                pending_items.clear();
+            } else {
+                pending_items.push(outline.items.len());
+            }
+
+            for (_, block) in doc.blocks_mut() {
+                for id in block.resource_ids_mut() {
+                    id.insert_str(0, &prefix);
+                }
+            }
+            for mut page in doc.pages {
+                page.number += offset;
+                for (id, _) in &mut page.images {
+                    id.insert_str(0, &prefix);
+                }
+                merged.pages.push(page);
+            }
+            for (id, resource) in doc.resources {
+                merged.resources.insert(format!("{prefix}{id}"), resource);
+            }
+
+            let mut section = OutlineItem::new(label.clone(), Some(offset + 1), 0);
+            if let Some(inner) = doc.outline {
+                for mut item in inner.items {
+                    shift_outline_item(&mut item, offset);
+                    section.add_child(item);
+                }
+            }
+            outline.add_item(section);
+
+            if let Some(author) = doc.metadata.author.filter(|a| !a.trim().is_empty()) {
+                if !authors.contains(&author) {
+                    authors.push(author);
+                }
+            }
+            merged.metadata.encrypted |= doc.metadata.encrypted;
+            // 가장 강한 보호 방식을 남긴다.
+            merged.metadata.encryption = merged.metadata.encryption.max(doc.metadata.encryption);
+            merged.metadata.sources.push(label);
+
+            let quality = &mut merged.extraction_quality;
+            quality.char_count += doc.extraction_quality.char_count;
+            quality.word_count += doc.extraction_quality.word_count;
+            quality.replacement_char_count += doc.extraction_quality.replacement_char_count;
+            quality.encrypted |= doc.extraction_quality.encrypted;
+            quality.suppressed_ocr_pages += doc.extraction_quality.suppressed_ocr_pages;
+            all_scans &= doc.extraction_quality.is_scan_pdf;
+
+            merged.form_fields.extend(doc.form_fields);
+        }
+
+        // 뒤에 페이지가 없으면 목차 항목만 남기고 가리킬 페이지는 비운다.
+        for idx in pending_items {
+            outline.items[idx].page = None;
+        }
+
+        merged.extraction_quality.is_scan_pdf = all_scans && !merged.pages.is_empty();
+        if !authors.is_empty() {
+            merged.metadata.author = Some(authors.join("; "));
+        }
+        merged.metadata.page_count = merged.page_count();
+        merged.outline = Some(outline).filter(|o| !o.is_empty());
+        merged
+    }
+
+    /// Iterate over all blocks in reading order, paired with their page number.
+    pub fn blocks(&self) -> impl Iterator<Item = (u32, &Block)> + '_ {
+        self.pages
+            .iter()
+            .flat_map(|page| page.elements.iter().map(move |block| (page.number, block)))
+    }
+
+    /// Iterate over the blocks of pages included in `selection`.
+    pub fn blocks_in<'a>(
+        &'a self,
+        selection: &'a PageSelection,
+    ) -> impl Iterator<Item = (u32, &'a Block)> + 'a {
+        self.pages
+            .iter()
+            .filter(move |page| selection.includes(page.number))
+            .flat_map(|page| page.elements.iter().map(move |block| (page.number, block)))
+    }
+
+    /// Mutable variant of [`Document::blocks`] for post-processing passes
+    /// (custom filters, redaction).
+    pub fn blocks_mut(&mut self) -> impl Iterator<Item = (u32, &mut Block)> + '_ {
+        self.pages.iter_mut().flat_map(|page| {
+            let number = page.number;
+            page.elements.iter_mut().map(move |block| (number, block))
+        })
+    }
+
+    /// Keep only the blocks for which `keep(page_number, block)` returns
+    /// `true`.
+    ///
+    /// Block sources stay aligned with the remaining blocks, and image
+    /// resources that only removed blocks referred to are dropped from
+    /// `resources` and from their page, so a re-render neither links nor
+    /// writes them.
+    pub fn retain_blocks(&mut self, mut keep: impl FnMut(u32, &Block) -> bool) {
+        let mut dropped: Vec<String> = Vec::new();
+        for page in &mut self.pages {
+            let number = page.number;
+            page.retain_blocks_where(|block| {
+                let kept = keep(number, block);
+                if !kept {
+                    dropped.extend(block.resource_ids().into_iter().map(str::to_string));
+                }
+                kept
+            });
+        }
+        self.drop_unreferenced(dropped);
+    }
+
+    /// Apply `f` to every paragraph with its page number, including list
+    /// items. Table cells are left alone.
+    ///
+    /// For rewriting headings or text before re-rendering; a paragraph's
+    /// heading level and list info are fields `f` may change as well.
+    pub fn map_paragraphs(&mut self, mut f: impl FnMut(u32, &mut Paragraph)) {
+        for (number, block) in self.blocks_mut() {
+            match block {
+                Block::Paragraph(p) => f(number, p),
+                Block::List { items, .. } => items.iter_mut().for_each(|p| f(number, p)),
+                _ => {}
+            }
+        }
+    }
+
+    /// Remove the pages included in `selection`.
+    ///
+    /// Remaining pages keep their original numbers, so block sources,
+    /// annotations and `--pages` selections still refer to the same pages.
+    /// Resources used only by removed pages are dropped, and outline entries
+    /// pointing at a removed page are removed with their surviving children
+    /// taking their place. `Metadata::page_count` still counts the source
+    /// file's pages.
+    pub fn remove_pages(&mut self, selection: &PageSelection) {
+        let mut dropped: Vec<String> = Vec::new();
+        let mut removed: Vec<u32> = Vec::new();
+        self.pages.retain(|page| {
+            if !selection.includes(page.number) {
+                return true;
+            }
+            removed.push(page.number);
+            for block in &page.elements {
+                dropped.extend(block.resource_ids().into_iter().map(str::to_string));
+            }
+            dropped.extend(page.images.iter().map(|(id, _)| id.clone()));
+            false
+        });
+        if removed.is_empty() {
+            return;
+        }
+        self.drop_unreferenced(dropped);
+        if let Some(outline) = self.outline.as_mut() {
+            let items = std::mem::take(&mut outline.items);
+            outline.items = prune_outline(items, &removed);
+        }
+        self.outline = self.outline.take().filter(|o| !o.is_empty());
+    }
+
+    /// Drop the resources among `ids` that no remaining block refers to.
+    fn drop_unreferenced(&mut self, ids: Vec<String>) {
+        // 블록은 `page1_Im0.jpg`, 리소스 키는 `page1_Im0`처럼 확장자가
+        // 다를 수 있어 확장자를 뗀 이름으로 비교한다.
+        let in_use: HashSet<&str> = self
+            .blocks()
+            .flat_map(|(_, block)| block.resource_ids())
+            .map(id_stem)
+            .collect();
+        let unused: HashSet<String> = ids
+            .iter()
+            .map(|id| id_stem(id))
+            .filter(|stem| !in_use.contains(stem))
+            .map(str::to_string)
+            .collect();
+        if unused.is_empty() {
+            return;
+        }
+        self.resources.retain(|id, _| !unused.contains(id_stem(id)));
+        for page in &mut self.pages {
+            page.images.retain(|(id, _)| !unused.contains(id_stem(id)));
+        }
+    }
+}
+
+/// `id` without its file extension.
+fn id_stem(id: &str) -> &str {
+    id.rsplit_once('.').map_or(id, |(stem, _)| stem)
+}
+
+/// `items` without the entries pointing at a `removed` page. A removed
+/// entry's remaining children move up into its place.
+fn prune_outline(items: Vec<OutlineItem>, removed: &[u32]) -> Vec<OutlineItem> {
+    let mut kept = Vec::new();
+    for mut item in items {
+        item.children = prune_outline(std::mem::take(&mut item.children), removed);
+        if item.page.is_some_and(|page| removed.contains(&page)) {
+            kept.extend(item.children);
+        } else {
+            kept.push(item);
+        }
+    }
+    kept
+}
+
+/// Lowercase `title` with runs of whitespace collapsed to one space.
+fn normalize_title(title: &str) -> String {
+    title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Blocks at each end of a page searched for running heads.
+const RUNNING_HEAD_EDGE: usize = 2;
+
+/// `text` reduced to the words compared when matching running heads:
+/// lowercased, without numbers, punctuation or a chapter label at one end.
+fn running_head_key(text: &str) -> String {
+    const LABELS: [&str; 4] = ["chapter", "part", "section", "appendix"];
+    let mut words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty() && !w.chars().all(char::is_numeric))
+        .map(str::to_lowercase)
+        .collect();
+    if words.first().is_some_and(|w| LABELS.contains(&w.as_str())) {
+        words.remove(0);
+    } else if words.last().is_some_and(|w| LABELS.contains(&w.as_str())) {
+        words.pop();
+    }
+    words.join(" ")
+}
+
+/// Index of the title best matching `query` (already normalized): the first
+/// exact match, else the first prefix match, else the first substring match.
+fn best_match<'a>(titles: impl Iterator<Item = &'a str>, query: &str) -> Option<usize> {
+    let mut best: Option<(u8, usize)> = None;
+    for (i, title) in titles.enumerate() {
+        let title = normalize_title(title);
+        let rank = if title == query {
+            0
+        } else if title.starts_with(query) {
+            1
+        } else if title.contains(query) {
+            2
+        } else {
+            continue;
+        };
+        if best.map_or(true, |(r, _)| rank < r) {
+            best = Some((rank, i));
+        }
+    }
+    best.map(|(_, i)| i)
+}
+
+/// Outline items in document order with their first page.
+fn flatten_outline<'a>(items: &'a [OutlineItem], out: &mut Vec<(&'a OutlineItem, Option<u32>)>) {
+    for item in items {
+        let at = out.len();
+        out.push((item, item.page));
+        flatten_outline(&item.children, out);
+        if item.page.is_none() {
+            out[at].1 = out[at + 1..].iter().find_map(|(_, page)| *page);
+        }
+    }
+}
+
+fn shift_outline_item(item: &mut OutlineItem, offset: u32) {
+    item.level += 1;
+    if let Some(page) = item.page.as_mut() {
+        *page += offset;
+    }
+    for child in &mut item.children {
+        shift_outline_item(child, offset);
+    }
 }
 
 impl Default for Document {
@@ -92,9 +695,14 @@ impl Default for Document {
 /// Document metadata.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Metadata {
-    /// Document title
+    /// Document title, as authored in the PDF's Info dictionary
     pub title: Option<String>,
 
+    /// Title guessed from page content when `title` is missing (see
+    /// [`Document::infer_title`]). Never copied into `title`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inferred_title: Option<String>,
+
     /// Document author
     pub author: Option<String>,
 
@@ -125,8 +733,42 @@ pub struct Metadata {
     /// Whether the document is encrypted
     pub encrypted: bool,
 
+    /// How an encrypted document is protected: by usage restrictions only,
+    /// or by a password needed to open it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<EncryptionKind>,
+
     /// Whether the document is tagged (accessible)
     pub tagged: bool,
+
+    /// Whether the AcroForm carries an XFA form. Dynamic XFA forms draw
+    /// their content at view time, so little of it reaches the pages.
+    #[serde(default)]
+    pub has_xfa: bool,
+
+    /// Natural language declared in the document catalog (`/Lang`), e.g. "en-US"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// Source labels when several documents were merged into this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<String>,
+
+    /// Original values of the text fields that [`Metadata::sanitize`]
+    /// changed, keyed by field name ("title", "author", ...)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub raw_metadata: BTreeMap<String, String>,
+}
+
+/// How an encrypted document is protected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EncryptionKind {
+    /// Only an owner password is set: the document opens without a password,
+    /// and its permission flags restrict printing, copying or editing
+    OwnerPasswordOnly,
+    /// A user password is needed to open the document
+    UserPassword,
 }
 
 impl Metadata {
@@ -138,13 +780,65 @@ impl Metadata {
         }
     }
 
+    /// Repair the text fields (title, author, subject, keywords, creator,
+    /// producer, language): UTF-16 read as single bytes, UTF-8 read as
+    /// Latin-1, trailing NULs, control characters and stray whitespace.
+    /// Fields left empty become `None`. The original of every changed
+    /// value is kept in `raw_metadata`. Parsing already does this.
+    pub fn sanitize(&mut self) {
+        self.map_text_fields(sanitize_metadata_value);
+    }
+
+    /// Replace each text field with `f(value)`, recording the original in
+    /// `raw_metadata` when it changes. An earlier original is kept.
+    pub(crate) fn map_text_fields(&mut self, f: impl Fn(&str) -> Option<String>) {
+        let fields = [
+            ("title", &mut self.title),
+            ("author", &mut self.author),
+            ("subject", &mut self.subject),
+            ("keywords", &mut self.keywords),
+            ("creator", &mut self.creator),
+            ("producer", &mut self.producer),
+            ("language", &mut self.language),
+        ];
+        for (name, field) in fields {
+            let Some(value) = field.take() else {
+                continue;
+            };
+            let cleaned = f(&value);
+            if cleaned.as_deref() != Some(value.as_str()) {
+                self.raw_metadata.entry(name.to_string()).or_insert(value);
+            }
+            *field = cleaned;
+        }
+    }
+
     /// Convert metadata to YAML frontmatter format.
     pub fn to_yaml_frontmatter(&self) -> String {
+        self.to_yaml_frontmatter_with(None)
+    }
+
+    /// Convert metadata to YAML frontmatter, optionally embedding provenance.
+    pub fn to_yaml_frontmatter_with(&self, provenance: Option<&Provenance>) -> String {
+        self.yaml_frontmatter(provenance, false)
+    }
+
+    /// Frontmatter builder. With `use_inferred_title`, a missing authored
+    /// title is replaced by `inferred_title`, marked `title_source: inferred`.
+    pub(crate) fn yaml_frontmatter(
+        &self,
+        provenance: Option<&Provenance>,
+        use_inferred_title: bool,
+    ) -> String {
         // RAG-ready frontmatter: only essential metadata
         let mut lines = vec!["---".to_string()];
 
-        if let Some(ref title) = self.title {
+        // 공백뿐인 제목은 없는 것으로 보고 추론한 제목을 쓴다.
+        if let Some(title) = self.title.as_ref().filter(|t| !t.trim().is_empty()) {
             lines.push(format!("title: \"{}\"", escape_yaml(title)));
+        } else if let Some(title) = self.inferred_title.as_ref().filter(|_| use_inferred_title) {
+            lines.push(format!("title: \"{}\"", escape_yaml(title)));
+            lines.push("title_source: inferred".to_string());
         }
         if let Some(ref author) = self.author {
             lines.push(format!("author: \"{}\"", escape_yaml(author)));
@@ -156,6 +850,16 @@ impl Metadata {
             }
         }
         lines.push(format!("pages: {}", self.page_count));
+        if !self.sources.is_empty() {
+            lines.push("sources:".to_string());
+            for source in &self.sources {
+                lines.push(format!("  - \"{}\"", escape_yaml(source)));
+            }
+        }
+
+        if let Some(provenance) = provenance {
+            lines.extend(provenance.yaml_lines());
+        }
 
         lines.push("---".to_string());
         lines.push(String::new());
@@ -164,6 +868,41 @@ impl Metadata {
     }
 }
 
+/// Compare strings with runs of ASCII digits ordered numerically.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(ca), Some(cb)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+        if ca.is_ascii_digit() && cb.is_ascii_digit() {
+            let split = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            let (da, ra) = a.split_at(split(a));
+            let (db, rb) = b.split_at(split(b));
+            let (ta, tb) = (da.trim_start_matches('0'), db.trim_start_matches('0'));
+            let order = ta.len().cmp(&tb.len()).then_with(|| ta.cmp(tb));
+            if order != Ordering::Equal {
+                return order;
+            }
+            a = ra;
+            b = rb;
+        } else {
+            if ca != cb {
+                return ca.cmp(&cb);
+            }
+            a = &a[ca.len_utf8()..];
+            b = &b[cb.len_utf8()..];
+        }
+    }
+}
+
+/// Reject headings that are clearly not titles: page numbers, single
+/// characters, or whole paragraphs mis-sized as headings.
+fn is_plausible_title(text: &str) -> bool {
+    let chars = text.chars().count();
+    (2..=200).contains(&chars) && text.chars().any(char::is_alphabetic)
+}
+
 /// Escape special characters for YAML strings.
 fn escape_yaml(s: &str) -> String {
     s.replace('\\', "\\\\")
@@ -242,6 +981,7 @@ impl OutlineItem {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::{Paragraph, ResourceType};
 
     #[test]
     fn test_document_new() {
@@ -265,6 +1005,95 @@ mod tests {
         assert!(!yaml.contains("pdf_version"));
     }
 
+    #[test]
+    fn test_infer_title_prefers_top_heading_on_first_page() {
+        let mut doc = Document::new();
+        doc.add_page(Page::letter(1));
+        let mut page = Page::letter(2);
+        page.add_paragraph(Paragraph::heading("3", 1));
+        page.add_paragraph(Paragraph::with_text("Body text that is not a title."));
+        page.add_paragraph(Paragraph::heading("Quarterly  Report\n2024", 2));
+        page.add_paragraph(Paragraph::heading("Summary", 2));
+        doc.add_page(page);
+
+        // 빈 첫 페이지와 숫자뿐인 H1은 건너뛴다.
+        assert_eq!(doc.infer_title().as_deref(), Some("Quarterly Report 2024"));
+
+        let mut plain = Document::new();
+        let mut page = Page::letter(1);
+        page.add_paragraph(Paragraph::with_text("Only body text."));
+        plain.add_page(page);
+        assert_eq!(plain.infer_title(), None);
+    }
+
+    #[test]
+    fn test_inferred_title_frontmatter_is_marked() {
+        let mut metadata = Metadata::with_version("1.7");
+        metadata.inferred_title = Some("Guessed".to_string());
+
+        assert!(!metadata.to_yaml_frontmatter().contains("title"));
+        let yaml = metadata.yaml_frontmatter(None, true);
+        assert!(yaml.contains("title: \"Guessed\"\ntitle_source: inferred"));
+
+        metadata.title = Some("Authored".to_string());
+        let yaml = metadata.yaml_frontmatter(None, true);
+        assert!(yaml.contains("title: \"Authored\""));
+        assert!(!yaml.contains("title_source"));
+
+        metadata.title = Some("  ".to_string());
+        let yaml = metadata.yaml_frontmatter(None, true);
+        assert!(yaml.contains("title: \"Guessed\"\ntitle_source: inferred"));
+        assert!(!metadata.to_yaml_frontmatter().contains("title"));
+    }
+
+    #[test]
+    fn test_metadata_sanitize_keeps_raw_values() {
+        let mut metadata = Metadata::with_version("1.7");
+        metadata.title = Some("\0R\0e\0p\0o\0r\0t".to_string());
+        metadata.author = Some("Kim".to_string());
+        metadata.keywords = Some(" \0 ".to_string());
+        metadata.sanitize();
+
+        assert_eq!(metadata.title.as_deref(), Some("Report"));
+        assert_eq!(metadata.author.as_deref(), Some("Kim"));
+        assert_eq!(metadata.keywords, None);
+        assert_eq!(metadata.raw_metadata.len(), 2);
+        assert_eq!(metadata.raw_metadata["title"], "\0R\0e\0p\0o\0r\0t");
+
+        // 다시 정리해도 원본은 바뀌지 않는다.
+        metadata.title = Some("Report ".to_string());
+        metadata.sanitize();
+        assert_eq!(metadata.raw_metadata["title"], "\0R\0e\0p\0o\0r\0t");
+    }
+
+    #[test]
+    fn test_resources_in_page_order() {
+        let with_ids = |ids: &[&str]| {
+            let mut doc = Document::new();
+            for id in ids {
+                doc.add_resource(
+                    id.to_string(),
+                    Resource::new(Vec::new(), "image/png".to_string(), ResourceType::Image),
+                );
+            }
+            doc
+        };
+        let doc = with_ids(&["page10_Im1", "page2_Im10", "page2_Im9", "page1_Im1"]);
+        let ids: Vec<&str> = doc
+            .resources_in_page_order()
+            .into_iter()
+            .map(|(id, _)| id.as_str())
+            .collect();
+        assert_eq!(ids, ["page1_Im1", "page2_Im9", "page2_Im10", "page10_Im1"]);
+
+        // 삽입 순서와 무관하게 직렬화 결과가 같아야 한다.
+        let reversed = with_ids(&["page1_Im1", "page2_Im9", "page2_Im10", "page10_Im1"]);
+        assert_eq!(
+            serde_json::to_string(&doc).unwrap(),
+            serde_json::to_string(&reversed).unwrap()
+        );
+    }
+
     #[test]
     fn test_outline() {
         let mut outline = Outline::new();
@@ -275,4 +1104,345 @@ mod tests {
 
         assert_eq!(outline.total_items(), 3);
     }
+
+    #[test]
+    fn test_pages_for_heading_from_outline() {
+        let mut doc = Document::new();
+        for number in 1..=12 {
+            doc.add_page(Page::a4(number));
+        }
+        let mut outline = Outline::new();
+        outline.add_item(OutlineItem::new("Preface", Some(1), 0));
+        // 쪽이 없는 장은 첫 하위 항목의 쪽에서 시작한다.
+        let mut chapter = OutlineItem::new("Chapter 3: Methods", None, 0);
+        chapter.add_child(OutlineItem::new("3.1 Setup", Some(4), 1));
+        chapter.add_child(OutlineItem::new("3.2 Results", Some(7), 1));
+        outline.add_item(chapter);
+        outline.add_item(OutlineItem::new("Chapter 30", Some(10), 0));
+        doc.outline = Some(outline);
+
+        assert_eq!(doc.pages_for_heading("chapter  3"), Some(4..=9));
+        assert_eq!(doc.pages_for_heading("3.1 setup"), Some(4..=6));
+        assert_eq!(doc.pages_for_heading("Results"), Some(7..=9));
+        // An exact title beats an earlier prefix match
+        assert_eq!(doc.pages_for_heading("Chapter 30"), Some(10..=12));
+        assert_eq!(doc.pages_for_heading("Appendix"), None);
+        assert_eq!(doc.pages_for_heading(" "), None);
+    }
+
+    #[test]
+    fn test_outline_page_ranges_and_bookmarks() {
+        let mut doc = Document::new();
+        for number in 1..=8 {
+            doc.add_page(Page::a4(number));
+        }
+        // 본문 머리말은 책갈피 검색에 쓰이지 않는다.
+        doc.pages[1].add_paragraph(Paragraph::heading("Glossary", 1));
+        let mut outline = Outline::new();
+        let mut intro = OutlineItem::new("Introduction", Some(1), 0);
+        intro.add_child(OutlineItem::new("Scope", Some(2), 1));
+        outline.add_item(intro);
+        outline.add_item(OutlineItem::new("Untargeted", None, 0));
+        outline.add_item(OutlineItem::new("Appendix", Some(6), 0));
+        doc.outline = Some(outline);
+
+        let ranges: Vec<_> = doc
+            .outline_page_ranges()
+            .into_iter()
+            .map(|(item, range)| (item.title.as_str(), range))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                ("Introduction", Some(1..=5)),
+                ("Scope", Some(2..=5)),
+                ("Untargeted", None),
+                ("Appendix", Some(6..=8)),
+            ]
+        );
+        assert_eq!(doc.pages_for_bookmark("introduction"), Some(1..=5));
+        assert_eq!(doc.pages_for_bookmark("Untargeted"), None);
+        assert_eq!(doc.pages_for_bookmark("Glossary"), None);
+        assert_eq!(doc.pages_for_heading("Glossary"), Some(2..=2));
+        assert!(Document::new().outline_page_ranges().is_empty());
+    }
+
+    /// Four pages of chapter 2 ("Installation"), each headed by a running
+    /// head, the first by the chapter heading too.
+    fn book_with_running_heads() -> Document {
+        let mut doc = Document::new();
+        for number in 1..=4 {
+            let mut page = Page::a4(number);
+            if number == 1 {
+                page.add_paragraph(Paragraph::heading("2 Installation", 1));
+            } else {
+                page.add_paragraph(Paragraph::with_text(format!(
+                    "Chapter 2 — Installation {}",
+                    number + 16
+                )));
+            }
+            page.add_paragraph(Paragraph::with_text("Body text."));
+            page.add_paragraph(Paragraph::with_text("More body text."));
+            page.add_paragraph(Paragraph::with_text("Installation"));
+            page.add_paragraph(Paragraph::with_text("The end of the page."));
+            page.add_paragraph(Paragraph::with_text((number + 16).to_string()));
+            doc.add_page(page);
+        }
+        let mut outline = Outline::new();
+        outline.add_item(OutlineItem::new("2 Installation", Some(1), 0));
+        doc.outline = Some(outline);
+        doc
+    }
+
+    #[test]
+    fn test_running_heads_from_outline() {
+        let mut doc = book_with_running_heads();
+        // 쪽 가운데의 "Installation" 은 본문이므로 남는다.
+        assert_eq!(doc.running_heads(), vec![(2, 0), (3, 0), (4, 0)]);
+        assert_eq!(doc.remove_running_heads(), 3);
+        assert_eq!(doc.pages[0].elements.len(), 6);
+        assert_eq!(doc.pages[1].elements.len(), 5);
+        assert!(doc.running_heads().is_empty());
+
+        // 한 쪽에만 나오는 제목은 반복 머리말이 아니다.
+        let mut once = book_with_running_heads();
+        once.pages.truncate(2);
+        once.pages[0].elements.remove(0);
+        assert!(once.running_heads().is_empty());
+
+        assert_eq!(
+            running_head_key("17 | CHAPTER 2: Installation"),
+            "installation"
+        );
+        assert_eq!(running_head_key("Installation | Chapter 2"), "installation");
+        assert_eq!(running_head_key("Part II: Part Lists"), "ii part lists");
+        let mut untitled = book_with_running_heads();
+        untitled.outline = None;
+        assert!(untitled.running_heads().is_empty());
+    }
+
+    #[test]
+    fn test_pages_for_heading_from_text() {
+        let mut doc = Document::new();
+        let pages = [
+            vec![Paragraph::heading("Intro", 1), Paragraph::with_text("a")],
+            vec![Paragraph::with_text("b"), Paragraph::heading("Details", 2)],
+            vec![Paragraph::with_text("c")],
+            vec![Paragraph::heading("Next", 1), Paragraph::with_text("d")],
+        ];
+        for (i, blocks) in pages.into_iter().enumerate() {
+            let mut page = Page::a4(i as u32 + 1);
+            for block in blocks {
+                page.add_paragraph(block);
+            }
+            doc.add_page(page);
+        }
+        assert_eq!(doc.pages_for_heading("intro"), Some(1..=3));
+        assert_eq!(doc.pages_for_heading("details"), Some(2..=3));
+        assert_eq!(doc.pages_for_heading("next"), Some(4..=4));
+    }
+
+    fn two_page_doc() -> Document {
+        let mut doc = Document::new();
+        for number in 1..=2 {
+            let mut page = Page::a4(number);
+            page.add_paragraph(Paragraph::with_text(format!("p{number}a")));
+            page.add_paragraph(Paragraph::with_text(format!("p{number}b")));
+            doc.add_page(page);
+        }
+        doc
+    }
+
+    #[test]
+    fn test_blocks_carry_page_number() {
+        let doc = two_page_doc();
+        let pages: Vec<u32> = doc.blocks().map(|(page, _)| page).collect();
+        assert_eq!(pages, vec![1, 1, 2, 2]);
+
+        let selected = PageSelection::Pages(vec![2]);
+        assert_eq!(doc.blocks_in(&selected).count(), 2);
+        assert!(doc.blocks_in(&selected).all(|(page, _)| page == 2));
+    }
+
+    #[test]
+    fn test_merge_documents() {
+        let mut first = two_page_doc();
+        first.pages[0].add_block(Block::image("img1"));
+        first.add_resource(
+            "img1".into(),
+            Resource::new(vec![1], "image/png", crate::model::ResourceType::Image),
+        );
+        first.metadata.author = Some("Kim".into());
+        let mut second = two_page_doc();
+        second.metadata.author = Some("Kim".into());
+        let mut inner = Outline::new();
+        inner.add_item(OutlineItem::new("Intro", Some(2), 0));
+        second.outline = Some(inner);
+
+        let merged = Document::merge(vec![("a".to_string(), first), ("b".to_string(), second)]);
+
+        let numbers: Vec<u32> = merged.pages.iter().map(|p| p.number).collect();
+        assert_eq!(numbers, vec![1, 2, 3, 4]);
+        assert_eq!(merged.metadata.page_count, 4);
+        assert_eq!(merged.metadata.author.as_deref(), Some("Kim"));
+        assert_eq!(merged.metadata.sources, vec!["a", "b"]);
+
+        match &merged.pages[2].elements[0] {
+            Block::Paragraph(p) => {
+                assert_eq!(p.heading_level(), Some(1));
+                assert_eq!(p.plain_text(), "b");
+            }
+            other => panic!("expected section heading, got {other:?}"),
+        }
+        assert!(merged.get_resource("doc1_img1").is_some());
+        assert!(merged.blocks().any(
+            |(_, b)| matches!(b, Block::Image { resource_id, .. } if resource_id == "doc1_img1")
+        ));
+
+        let outline = merged.outline.unwrap();
+        assert_eq!(outline.items.len(), 2);
+        assert_eq!(outline.items[1].page, Some(3));
+        assert_eq!(outline.items[1].children[0].page, Some(4));
+        assert_eq!(outline.items[1].children[0].level, 1);
+
+        let yaml = merged.metadata.to_yaml_frontmatter();
+        assert!(yaml.contains("sources:\n  - \"a\"\n  - \"b\""));
+    }
+
+    #[test]
+    fn test_merge_empty_part_adds_no_page() {
+        let merged = Document::merge(vec![
+            ("a".to_string(), two_page_doc()),
+            ("empty".to_string(), Document::new()),
+            ("b".to_string(), two_page_doc()),
+            ("tail".to_string(), Document::new()),
+        ]);
+
+        assert_eq!(merged.page_count(), 4);
+        assert_eq!(merged.metadata.page_count, 4);
+        let headings: Vec<String> = merged.pages[2]
+            .elements
+            .iter()
+            .take(2)
+            .map(|b| match b {
+                Block::Paragraph(p) if p.heading_level() == Some(1) => p.plain_text(),
+                other => panic!("expected section heading, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(headings, vec!["empty", "b"]);
+
+        let outline = merged.outline.unwrap();
+        let pages: Vec<Option<u32>> = outline.items.iter().map(|i| i.page).collect();
+        assert_eq!(pages, vec![Some(1), Some(3), Some(3), None]);
+        assert_eq!(merged.metadata.sources, vec!["a", "empty", "b", "tail"]);
+    }
+
+    #[test]
+    fn test_content_hash_ignores_layout_metadata() {
+        let a = two_page_doc();
+        let mut b = two_page_doc();
+        b.metadata.title = Some("Other".into());
+        b.pages[0].width = 612.0;
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_eq!(a.content_hash().len(), 64);
+
+        b.pages[1].add_paragraph(Paragraph::with_text("extra"));
+        assert_ne!(a.content_hash(), b.content_hash());
+    }
+
+    #[test]
+    fn test_blocks_mut_redaction() {
+        let mut doc = two_page_doc();
+        for (page, block) in doc.blocks_mut() {
+            if page == 1 {
+                *block = Block::HorizontalRule;
+            }
+        }
+        assert!(matches!(doc.pages[0].elements[0], Block::HorizontalRule));
+        assert!(doc.pages[1].elements[0].is_paragraph());
+    }
+
+    fn image(data: u8) -> Resource {
+        Resource::new(vec![data], "image/png", ResourceType::Image)
+    }
+
+    #[test]
+    fn test_retain_blocks_drops_orphaned_resources() {
+        let mut doc = two_page_doc();
+        doc.pages[0].add_block(Block::image("logo"));
+        doc.pages[1].add_block(Block::image("logo"));
+        doc.pages[1].add_block(Block::image("chart"));
+        doc.pages[1].images.push(("chart".into(), image(2)));
+        doc.add_resource("logo".into(), image(1));
+        doc.add_resource("chart".into(), image(2));
+
+        let mut calls = 0;
+        doc.retain_blocks(|page, block| {
+            calls += 1;
+            !(page == 2 && block.is_image())
+        });
+
+        assert_eq!(calls, 7);
+        assert_eq!(doc.pages[1].elements.len(), 2);
+        // 다른 페이지가 아직 쓰는 리소스는 남는다.
+        assert!(doc.get_resource("logo").is_some());
+        assert!(doc.get_resource("chart").is_none());
+        assert!(doc.pages[1].images.is_empty());
+    }
+
+    #[test]
+    fn test_map_paragraphs_reaches_list_items() {
+        let mut doc = two_page_doc();
+        doc.pages[1].add_block(Block::List {
+            ordered: false,
+            items: vec![Paragraph::with_text("• item")],
+        });
+        doc.map_paragraphs(|page, p| {
+            if page == 1 && p.plain_text() == "p1a" {
+                *p = Paragraph::heading("Intro", 2);
+            } else {
+                *p = Paragraph::with_text(p.plain_text().to_uppercase());
+            }
+        });
+
+        match &doc.pages[0].elements[0] {
+            Block::Paragraph(p) => assert_eq!(p.heading_level(), Some(2)),
+            other => panic!("expected heading, got {other:?}"),
+        }
+        assert_eq!(doc.pages[1].plain_text(), "P2A\n\nP2B\n\n• ITEM");
+    }
+
+    #[test]
+    fn test_remove_pages_keeps_numbers_and_prunes_outline() {
+        let mut doc = two_page_doc();
+        doc.add_page(Page::a4(3));
+        doc.pages[1].add_block(Block::image("chart"));
+        doc.add_resource("chart".into(), image(1));
+        let mut chapter = OutlineItem::new("Chapter", Some(2), 0);
+        chapter.add_child(OutlineItem::new("Section", Some(3), 1));
+        let mut outline = Outline::new();
+        outline.add_item(OutlineItem::new("Intro", Some(1), 0));
+        outline.add_item(chapter);
+        doc.outline = Some(outline);
+        doc.metadata.page_count = 3;
+
+        doc.remove_pages(&PageSelection::parse("2").unwrap());
+
+        let numbers: Vec<u32> = doc.pages.iter().map(|p| p.number).collect();
+        assert_eq!(numbers, vec![1, 3]);
+        assert_eq!(doc.get_page(3).map(|p| p.number), Some(3));
+        assert!(doc.get_page(2).is_none());
+        assert!(doc.resources.is_empty());
+        assert_eq!(doc.metadata.page_count, 3);
+
+        let titles: Vec<&str> = doc
+            .outline
+            .as_ref()
+            .unwrap()
+            .items
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Intro", "Section"]);
+    }
 }
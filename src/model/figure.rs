@@ -0,0 +1,173 @@
+//! Images bundled with their captions, for figure datasets.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::{Block, Document, Page, Resource};
+
+/// An image on a page with its caption and the region it occupies.
+///
+/// Returned by [`Document::figures`]. The caption is the paragraph right
+/// below the image when it reads like one ("Figure 3.", "Fig. 2:", "그림 1",
+/// "図 4"), else the paragraph right above.
+#[derive(Debug, Clone, Serialize)]
+pub struct Figure<'a> {
+    /// Page number (1-indexed)
+    pub page: u32,
+
+    /// Id of the image, as carried by its `Block::Image`
+    pub resource_id: &'a str,
+
+    /// Caption text, if one was found next to the image
+    pub caption: Option<String>,
+
+    /// Alternative text from the document, if any
+    pub alt_text: Option<&'a str>,
+
+    /// Drawn region `[x0, y0, x1, y1]` in PDF user space, when the image's
+    /// placement is known
+    pub bbox: Option<[f32; 4]>,
+
+    /// The image itself; `None` when resources were not extracted
+    #[serde(skip)]
+    pub resource: Option<&'a Resource>,
+}
+
+impl Document {
+    /// Every image block in reading order, paired with its caption, page
+    /// and bounding box.
+    pub fn figures(&self) -> Vec<Figure<'_>> {
+        let mut figures = Vec::new();
+        for page in &self.pages {
+            for (index, block) in page.elements.iter().enumerate() {
+                let Block::Image {
+                    resource_id,
+                    alt_text,
+                    width,
+                    height,
+                    x,
+                    y,
+                } = block
+                else {
+                    continue;
+                };
+                let bbox = match (x, y, width, height) {
+                    (Some(x), Some(y), Some(w), Some(h)) => Some([*x, y - h, x + w, *y]),
+                    _ => None,
+                };
+                figures.push(Figure {
+                    page: page.number,
+                    resource_id,
+                    caption: caption_near(page, index),
+                    alt_text: alt_text.as_deref(),
+                    bbox,
+                    resource: self.figure_resource(page, resource_id),
+                });
+            }
+        }
+        figures
+    }
+
+    /// The resource an image block shows: the page's own copy, or the
+    /// document's, whose keys may lack the extension.
+    fn figure_resource<'a>(&'a self, page: &'a Page, id: &str) -> Option<&'a Resource> {
+        page.images
+            .iter()
+            .find(|(image_id, _)| image_id == id)
+            .map(|(_, resource)| resource)
+            .or_else(|| self.resources.get(id))
+            .or_else(|| {
+                let stem = id.rsplit_once('.').map_or(id, |(stem, _)| stem);
+                self.resources.get(stem)
+            })
+    }
+}
+
+/// Caption for the image at `index`: the paragraph after it, then the one
+/// before it, if it starts like a figure caption.
+fn caption_near(page: &Page, index: usize) -> Option<String> {
+    let after = page.elements.get(index + 1);
+    let before = index.checked_sub(1).and_then(|i| page.elements.get(i));
+    [after, before]
+        .into_iter()
+        .flatten()
+        .find_map(|block| match block {
+            Block::Paragraph(p) => {
+                let text = p.plain_text();
+                let text = text.trim();
+                is_caption(text).then(|| text.to_string())
+            }
+            _ => None,
+        })
+}
+
+fn is_caption(text: &str) -> bool {
+    static CAPTION: OnceLock<Regex> = OnceLock::new();
+    let caption = CAPTION.get_or_init(|| {
+        // "Figure 3", "Fig. 2", "Chart 1", "그림 1", "사진 2", "図 4", "图 5"
+        Regex::new(concat!(
+            r"^(?:(?i:figure|fig\.?|chart|plate|exhibit|illustration)\s*",
+            r"|(?:그림|사진|도표|図|图)\s*)[0-9IVX]",
+        ))
+        .unwrap()
+    });
+    caption.is_match(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Paragraph, ResourceType};
+
+    #[test]
+    fn test_is_caption() {
+        assert!(is_caption("Figure 3. Throughput by batch size"));
+        assert!(is_caption("Fig. 2: Architecture"));
+        assert!(is_caption("그림 1 시스템 구성도"));
+        assert!(is_caption("図4 実験結果"));
+        assert!(!is_caption("Figures are shown below."));
+        assert!(!is_caption("The figure 3 shows"));
+    }
+
+    #[test]
+    fn test_figures_pair_images_with_captions() {
+        let mut doc = Document::new();
+        let mut page = crate::model::Page::letter(2);
+        page.add_paragraph(Paragraph::with_text(
+            "Figure 1. Caption of an earlier image",
+        ));
+        page.elements.push(Block::Image {
+            resource_id: "page2_Im0.png".to_string(),
+            alt_text: None,
+            width: Some(200.0),
+            height: Some(100.0),
+            x: Some(72.0),
+            y: Some(600.0),
+        });
+        page.add_paragraph(Paragraph::with_text("Figure 2. Below the image"));
+        page.elements.push(Block::image("page2_Im1.png"));
+        page.add_paragraph(Paragraph::with_text("Body text follows."));
+        doc.add_page(page);
+        let png = Resource::new(vec![1], "image/png".to_string(), ResourceType::Image);
+        doc.add_resource("page2_Im0".to_string(), png);
+
+        let figures = doc.figures();
+        assert_eq!(figures.len(), 2);
+        assert_eq!(figures[0].page, 2);
+        assert_eq!(
+            figures[0].caption.as_deref(),
+            Some("Figure 2. Below the image")
+        );
+        assert_eq!(figures[0].bbox, Some([72.0, 500.0, 272.0, 600.0]));
+        assert!(figures[0].resource.is_some());
+        // 아래 문단이 캡션이 아니면 위 문단을 본다.
+        assert_eq!(
+            figures[1].caption.as_deref(),
+            Some("Figure 2. Below the image")
+        );
+        assert_eq!(figures[1].bbox, None);
+        assert!(figures[1].resource.is_none());
+    }
+}
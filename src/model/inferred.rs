@@ -0,0 +1,175 @@
+//! Document properties read from the content rather than the metadata.
+//!
+//! Declared metadata is often missing or wrong — a title left as "Untitled",
+//! no `/Lang` at all. These are the same facts as the parser sees them, to be
+//! shown next to what the document claims.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::Document;
+use crate::parser::Script;
+
+/// Characters of text looked at when guessing the language.
+const LANGUAGE_SAMPLE_CHARS: usize = 20_000;
+
+/// Frequent function words of the Latin-script languages told apart.
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &["the", "and", "of", "to", "is", "that", "with", "for"],
+    ),
+    (
+        "de",
+        &["der", "die", "und", "das", "ist", "nicht", "mit", "ein"],
+    ),
+    (
+        "fr",
+        &["le", "les", "et", "des", "est", "une", "dans", "pour"],
+    ),
+    ("es", &["el", "los", "y", "que", "del", "las", "por", "una"]),
+];
+
+/// Properties of a document inferred from its content; see
+/// [`Document::inferred_properties`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InferredProperties {
+    /// Language guessed from the text, as an ISO 639-1 code ("en", "ko")
+    pub language: Option<String>,
+
+    /// Title guessed from the top heading of the first page with text
+    pub title: Option<String>,
+
+    /// Most common body font size across pages, in points
+    pub body_font_size: Option<f32>,
+
+    /// Number of pages by the number of text columns found on them
+    pub columns: BTreeMap<u32, u32>,
+
+    /// Number of tables found
+    pub table_count: u32,
+}
+
+impl Document {
+    /// Properties inferred from the content, to compare with the declared
+    /// [`Metadata`](super::Metadata): language, title, body font size,
+    /// column layout and table count.
+    ///
+    /// Font sizes and columns are recorded during layout analysis, so pages
+    /// built by hand or extracted as raw text contribute nothing to them.
+    pub fn inferred_properties(&self) -> InferredProperties {
+        let mut sizes: BTreeMap<i32, u64> = BTreeMap::new();
+        let mut columns = BTreeMap::new();
+        let mut table_count = 0;
+        for page in &self.pages {
+            let stats = page.stats();
+            table_count += stats.table_count;
+            if let Some(size) = page.body_font_size {
+                // 글자 수로 가중해 표지처럼 글자가 적은 페이지가 좌우하지 않게 한다.
+                *sizes.entry((size * 10.0).round() as i32).or_insert(0) +=
+                    u64::from(stats.char_count);
+            }
+            if page.column_count > 0 {
+                *columns.entry(page.column_count).or_insert(0) += 1;
+            }
+        }
+        let body_font_size = sizes
+            .iter()
+            .max_by_key(|(_, chars)| **chars)
+            .map(|(size, _)| *size as f32 / 10.0);
+
+        InferredProperties {
+            language: detect_language(&self.plain_text()).map(str::to_string),
+            title: self.infer_title(),
+            body_font_size,
+            columns,
+            table_count,
+        }
+    }
+}
+
+/// Guess the language of `text` from its scripts, and for Latin text from
+/// its most frequent function words. `None` when there is too little text or
+/// no language stands out.
+pub(crate) fn detect_language(text: &str) -> Option<&'static str> {
+    let (mut hangul, mut kana, mut han, mut latin, mut cyrillic) = (0, 0, 0, 0, 0);
+    for c in text
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .take(LANGUAGE_SAMPLE_CHARS)
+    {
+        match Script::of(c) {
+            Script::Hangul => hangul += 1,
+            Script::Kana => kana += 1,
+            Script::Han => han += 1,
+            Script::Latin => latin += 1,
+            _ if ('\u{0400}'..='\u{04FF}').contains(&c) => cyrillic += 1,
+            _ => {}
+        }
+    }
+    let total = hangul + kana + han + latin + cyrillic;
+    if total < 20 {
+        return None;
+    }
+
+    // 한글·가나는 소량이어도 결정적이다. 한자만으로는 중국어로 본다.
+    if hangul * 5 >= total {
+        return Some("ko");
+    }
+    if kana * 10 >= total {
+        return Some("ja");
+    }
+    if han * 2 >= total {
+        return Some("zh");
+    }
+    if cyrillic * 2 >= total {
+        return Some("ru");
+    }
+    if latin * 2 < total {
+        return None;
+    }
+
+    let mut counts = [0usize; STOPWORDS.len()];
+    let mut words = 0usize;
+    let sample: String = text.chars().take(LANGUAGE_SAMPLE_CHARS).collect();
+    for word in sample
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+    {
+        words += 1;
+        let word = word.to_lowercase();
+        for (count, (_, list)) in counts.iter_mut().zip(STOPWORDS) {
+            if list.contains(&word.as_str()) {
+                *count += 1;
+            }
+        }
+    }
+    let (best, hits) = counts.iter().enumerate().max_by_key(|(_, n)| **n)?;
+    // 기능어가 단어의 5% 미만이면 목록에 없는 언어로 본다.
+    (*hits >= 3 && hits * 20 >= words).then_some(STOPWORDS[best].0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        let en = "The results of the survey show that most of the pages are set in one font.";
+        assert_eq!(detect_language(en), Some("en"));
+        let de = "Die Ergebnisse der Umfrage zeigen, dass die meisten Seiten nicht mit einer";
+        assert_eq!(detect_language(de), Some("de"));
+        let ko =
+            "이 문서는 한글로 작성되었으며 본문 글자 크기가 모두 같습니다. PDF 변환 결과입니다.";
+        assert_eq!(detect_language(ko), Some("ko"));
+        let ja = "この文書は日本語で書かれています。本文の文字の大きさはすべて同じです。";
+        assert_eq!(detect_language(ja), Some("ja"));
+
+        assert_eq!(detect_language("Invoice 2024"), None);
+        assert_eq!(
+            detect_language("Lorem ipsum dolor sit amet consectetur adipiscing"),
+            None
+        );
+    }
+}
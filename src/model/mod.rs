@@ -4,21 +4,48 @@
 //! PDF parsing and content rendering. The model is format-agnostic and
 //! can represent content from any PDF document.
 
+mod annotation;
+mod dedup;
 mod document;
+mod figure;
 mod form;
+mod inferred;
 mod page;
 mod paragraph;
+mod provenance;
 mod quality;
+mod reconcile;
 mod resource;
+mod revision;
+mod schema;
+mod section;
+mod source;
 mod table;
+mod toc;
+mod word;
 
-pub use document::{Document, Metadata, Outline, OutlineItem};
+pub use annotation::{Annotation, AnnotationKind};
+pub use dedup::{BlockDedup, DedupScope};
+pub use document::{Document, EncryptionKind, Metadata, Outline, OutlineItem};
+pub use figure::Figure;
 pub use form::{FieldType, FieldValue, FormField};
-pub use page::{Block, Page};
+pub(crate) use inferred::detect_language;
+pub use inferred::InferredProperties;
+pub use page::{Block, BlockFilter, Page, PageStats, TextStrategy};
 pub use paragraph::{
     Alignment, InlineContent, ListInfo, ListStyle, NumberStyle, Paragraph, ParagraphStyle, TextRun,
     TextStyle,
 };
+pub(crate) use provenance::sha256_hex;
+pub use provenance::Provenance;
 pub use quality::{ExtractionQuality, QualityAccumulator};
+pub use reconcile::{PageReconciliation, Reconciliation, TextSource};
 pub use resource::{Resource, ResourceType};
-pub use table::{Table, TableCell, TableRow};
+pub use revision::Revision;
+pub(crate) use schema::Stamped;
+pub use schema::{VersionedJson, SCHEMA_VERSION, SCHEMA_VERSION_FIELD};
+pub use section::Section;
+pub use source::BlockSource;
+pub use table::{Table, TableCell, TableDetection, TableRejection, TableRow};
+pub use toc::DetectedToc;
+pub use word::Word;
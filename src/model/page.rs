@@ -1,10 +1,16 @@
 //! Page-level types.
 
-use super::{Paragraph, Resource, Table};
-use serde::{Deserialize, Serialize};
+use super::{
+    Annotation, BlockSource, InlineContent, ListStyle, Paragraph, Resource, Table, TableDetection,
+    Word,
+};
+use serde::{Deserialize, Serialize, Serializer};
 
 /// A single page in the document.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Serializes with an extra derived `stats` object (see [`Page::stats`]);
+/// it is ignored when deserializing.
+#[derive(Debug, Clone, Deserialize)]
 pub struct Page {
     /// Page number (1-indexed)
     pub number: u32,
@@ -24,32 +30,194 @@ pub struct Page {
     /// 이 페이지에 포함된 이미지 리소스. `(resource_id, resource)` 형식.
     /// `Block::Image { resource_id, .. }` 의 id 와 동일하며, 스트리밍 writer
     /// 가 페이지 파싱 직후 즉시 디스크로 flush 할 수 있도록 제공.
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
     pub images: Vec<(String, Resource)>,
 
     /// 이 페이지의 OCR 텍스트 레이어가 읽을 수 없다고 판단되어 폐기된 경우 `true`.
     /// 스캔 이미지 위의 투명 텍스트가 아무 의미도 이루지 못할 때만 설정된다.
-    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[serde(default)]
     pub ocr_text_suppressed: bool,
 
     /// 콘텐츠 스트림의 텍스트 쇼잉 오퍼레이터(`Tj`/`TJ`/`'`/`"`) 수.
     /// 0이면서 `image_op_count > 0` 이면 텍스트 레이어 없는 스캔 페이지,
     /// 둘 다 0이면 진짜 빈 페이지 — 소비자가 이 둘을 구분하는 판별자.
     /// JSON에서는 0일 때 생략된다(부재 = 0).
-    #[serde(default, skip_serializing_if = "is_zero")]
+    #[serde(default)]
     pub text_op_count: u32,
 
     /// 콘텐츠 스트림의 XObject `Do` 호출 수. 이미지가 대부분이지만 Form
     /// XObject 도 포함될 수 있다(리소스 사전 조회 없이 집계하기 위함).
     /// JSON에서는 0일 때 생략된다(부재 = 0).
-    #[serde(default, skip_serializing_if = "is_zero")]
+    #[serde(default)]
     pub image_op_count: u32,
+
+    /// 콘텐츠 스트림에서 해석할 수 없어 건너뛴 바이트 수 — 깨진 오퍼레이터나
+    /// 바이너리 찌꺼기. 나머지는 다음 오퍼레이터부터 이어서 해석된다.
+    /// JSON에서는 0일 때 생략되고, `stats.skipped_content_bytes` 로도 나온다.
+    #[serde(default)]
+    pub skipped_content_bytes: u32,
+
+    /// 이 페이지 텍스트를 만든 추출 전략. 레이아웃 해석기가 거의 아무것도
+    /// 건지지 못해 스트림 순서 덤프로 대체된 페이지를 가려낸다.
+    /// JSON에서는 기본값(`content_stream`)일 때 생략된다.
+    #[serde(default)]
+    pub text_strategy: TextStrategy,
+
+    /// 본문 글자 크기(pt) — 레이아웃 해석에서 가장 흔한 크기.
+    /// 텍스트를 해석하지 않은 페이지는 `None`, JSON에서는 생략된다.
+    #[serde(default)]
+    pub body_font_size: Option<f32>,
+
+    /// 레이아웃 해석이 찾은 텍스트 단 수. 단일 단 페이지는 1, 해석하지
+    /// 않은 페이지는 0. JSON에서는 0일 때 생략된다.
+    #[serde(default)]
+    pub column_count: u32,
+
+    /// Sticky notes and text markup on this page, in `/Annots` order.
+    /// JSON에서는 비어 있으면 생략된다.
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+
+    /// Words with their bounding boxes, when requested with
+    /// `ParseOptions::with_words`. JSON에서는 비어 있으면 생략된다.
+    #[serde(default)]
+    pub words: Vec<Word>,
+
+    /// Where each block of `elements` was drawn, by index: `None` for blocks
+    /// with no content-stream text (images, rules) and for blocks added by
+    /// code that does not track sources. Empty when nothing was tracked, and
+    /// ignored unless it has one entry per block; see [`Page::block_source`].
+    /// Emitted per block in `JsonFormat::Detailed` output only.
+    #[serde(skip)]
+    pub block_sources: Vec<Option<BlockSource>>,
+
+    /// Regions the table detector considered and left as text, for review
+    /// of borderline tables. Emitted as `table_candidates` in
+    /// `JsonFormat::Detailed` output only.
+    #[serde(skip)]
+    pub table_candidates: Vec<TableDetection>,
+}
+
+/// How a page's text was extracted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextStrategy {
+    /// Positioned spans from the layout interpreter, grouped into lines,
+    /// paragraphs, headings and tables
+    #[default]
+    ContentStream,
+    /// Text operators decoded in content-stream order with no layout
+    /// analysis; paragraphs are line-based and positions are lost
+    RawText,
+}
+
+impl TextStrategy {
+    fn is_content_stream(&self) -> bool {
+        *self == TextStrategy::ContentStream
+    }
 }
 
 fn is_zero(n: &u32) -> bool {
     *n == 0
 }
 
+impl Serialize for Page {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // 필드 순서와 생략 규칙은 구조체 정의 그대로, 파생 값 `stats`만 끝에 붙인다.
+        #[derive(Serialize)]
+        struct Repr<'a> {
+            number: u32,
+            width: f32,
+            height: f32,
+            elements: &'a [Block],
+            rotation: u16,
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            images: &'a [(String, Resource)],
+            #[serde(skip_serializing_if = "std::ops::Not::not")]
+            ocr_text_suppressed: bool,
+            #[serde(skip_serializing_if = "is_zero")]
+            text_op_count: u32,
+            #[serde(skip_serializing_if = "is_zero")]
+            image_op_count: u32,
+            #[serde(skip_serializing_if = "is_zero")]
+            skipped_content_bytes: u32,
+            #[serde(skip_serializing_if = "TextStrategy::is_content_stream")]
+            text_strategy: TextStrategy,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            body_font_size: Option<f32>,
+            #[serde(skip_serializing_if = "is_zero")]
+            column_count: u32,
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            annotations: &'a [Annotation],
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            words: &'a [Word],
+            stats: PageStats,
+        }
+
+        Repr {
+            number: self.number,
+            width: self.width,
+            height: self.height,
+            elements: &self.elements,
+            rotation: self.rotation,
+            images: &self.images,
+            ocr_text_suppressed: self.ocr_text_suppressed,
+            text_op_count: self.text_op_count,
+            image_op_count: self.image_op_count,
+            skipped_content_bytes: self.skipped_content_bytes,
+            text_strategy: self.text_strategy,
+            body_font_size: self.body_font_size,
+            column_count: self.column_count,
+            annotations: &self.annotations,
+            words: &self.words,
+            stats: self.stats(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Per-page content counts, derived from the page's blocks, and how much of
+/// its content stream could not be decoded.
+///
+/// Emitted as `pages[].stats` in JSON output. Page dimensions and rotation
+/// are on the page object itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageStats {
+    /// Approximate word count (whitespace-separated tokens)
+    pub word_count: u32,
+
+    /// Character count (excluding whitespace)
+    pub char_count: u32,
+
+    /// Number of content blocks
+    pub block_count: u32,
+
+    /// Number of paragraphs, headings included
+    pub paragraph_count: u32,
+
+    /// Number of headings
+    pub heading_count: u32,
+
+    /// Number of tables
+    pub table_count: u32,
+
+    /// Number of images
+    pub image_count: u32,
+
+    /// Number of code blocks
+    pub code_block_count: u32,
+
+    /// Whether any table was found on the page
+    pub has_tables: bool,
+
+    /// Whether any image was found on the page
+    pub has_images: bool,
+
+    /// Bytes of the page's content streams skipped as unparseable
+    #[serde(default)]
+    pub skipped_content_bytes: u32,
+}
+
 impl Page {
     /// Create a new page with the given dimensions.
     pub fn new(number: u32, width: f32, height: f32) -> Self {
@@ -63,6 +231,14 @@ impl Page {
             ocr_text_suppressed: false,
             text_op_count: 0,
             image_op_count: 0,
+            skipped_content_bytes: 0,
+            text_strategy: TextStrategy::ContentStream,
+            body_font_size: None,
+            column_count: 0,
+            annotations: Vec::new(),
+            words: Vec::new(),
+            block_sources: Vec::new(),
+            table_candidates: Vec::new(),
         }
     }
 
@@ -78,17 +254,34 @@ impl Page {
 
     /// Add a block to the page.
     pub fn add_block(&mut self, block: Block) {
+        self.add_block_with_source(block, None);
+    }
+
+    /// Add a block to the page, recording where it was drawn.
+    pub fn add_block_with_source(&mut self, block: Block, source: Option<BlockSource>) {
+        if source.is_some() || !self.block_sources.is_empty() {
+            self.block_sources.resize(self.elements.len(), None);
+            self.block_sources.push(source);
+        }
         self.elements.push(block);
     }
 
     /// Add a paragraph to the page.
     pub fn add_paragraph(&mut self, paragraph: Paragraph) {
-        self.elements.push(Block::Paragraph(paragraph));
+        self.add_block(Block::Paragraph(paragraph));
     }
 
     /// Add a table to the page.
     pub fn add_table(&mut self, table: Table) {
-        self.elements.push(Block::Table(table));
+        self.add_block(Block::Table(table));
+    }
+
+    /// Where the block at `index` of `elements` was drawn, if recorded.
+    pub fn block_source(&self, index: usize) -> Option<&BlockSource> {
+        if self.block_sources.len() != self.elements.len() {
+            return None;
+        }
+        self.block_sources.get(index)?.as_ref()
     }
 
     /// Get plain text content of the page.
@@ -97,7 +290,9 @@ impl Page {
             .iter()
             .filter_map(|block| match block {
                 Block::Paragraph(p) => Some(p.plain_text()),
+                Block::List { items, .. } => Some(list_plain_text(items)),
                 Block::Table(t) => Some(t.plain_text()),
+                Block::CodeBlock { content, .. } => Some(content.clone()),
                 _ => None,
             })
             .collect::<Vec<_>>()
@@ -123,6 +318,223 @@ impl Page {
     pub fn is_landscape(&self) -> bool {
         self.width > self.height
     }
+
+    /// Wrap each run of consecutive list items in a [`Block::List`], so
+    /// renderers can tell where a list starts and ends.
+    ///
+    /// List items are paragraphs with `list_info`, or whose text opens with
+    /// a bullet or number marker (`•`, `-`, `1.`, `a)`, …) as extracted text
+    /// does; items are kept as they are. Nested items stay in the list above
+    /// them; a top-level item switching between numbered and bulleted starts
+    /// a new list. Definition items are left as paragraphs.
+    pub fn group_lists(&mut self) {
+        let aligned = self.block_sources.len() == self.elements.len();
+        let mut sources = std::mem::take(&mut self.block_sources).into_iter();
+        for block in std::mem::take(&mut self.elements) {
+            let source = if aligned {
+                sources.next().flatten()
+            } else {
+                None
+            };
+            let item = match block {
+                Block::Paragraph(p) if list_kind(&p).is_some() => p,
+                block => {
+                    self.add_block_with_source(block, source);
+                    continue;
+                }
+            };
+            let ordered = list_kind(&item) == Some(true);
+            if let Some(Block::List {
+                ordered: list_ordered,
+                items,
+            }) = self.elements.last_mut()
+            {
+                if list_level(&item) > list_level(&items[0]) || ordered == *list_ordered {
+                    items.push(item);
+                    let last = self.block_sources.last_mut();
+                    if let (Some(Some(last)), Some(source)) = (last, source) {
+                        last.extend(&source);
+                    }
+                    continue;
+                }
+            }
+            let list = Block::List {
+                ordered,
+                items: vec![item],
+            };
+            self.add_block_with_source(list, source);
+        }
+    }
+
+    /// Drop the blocks `filter` rejects. Image resources go with their
+    /// blocks when images are filtered out.
+    pub fn retain_blocks(&mut self, filter: &BlockFilter) {
+        if filter.is_all() {
+            return;
+        }
+        self.retain_blocks_where(|block| filter.allows(block));
+        if !filter.images {
+            self.images.clear();
+        }
+    }
+
+    /// Drop the blocks `keep` rejects, keeping block sources aligned.
+    pub(crate) fn retain_blocks_where(&mut self, mut keep: impl FnMut(&Block) -> bool) {
+        // `keep`는 블록마다 한 번만 호출한다.
+        let mask: Vec<bool> = self.elements.iter().map(&mut keep).collect();
+        if self.block_sources.len() == self.elements.len() {
+            let sources = std::mem::take(&mut self.block_sources);
+            self.block_sources = sources
+                .into_iter()
+                .zip(&mask)
+                .filter(|(_, kept)| **kept)
+                .map(|(source, _)| source)
+                .collect();
+        } else {
+            self.block_sources.clear();
+        }
+        let mut kept = mask.into_iter();
+        self.elements.retain(|_| kept.next().unwrap_or(true));
+    }
+
+    /// Count the page's words, characters and blocks by kind.
+    pub fn stats(&self) -> PageStats {
+        let mut stats = PageStats {
+            block_count: self.elements.len() as u32,
+            skipped_content_bytes: self.skipped_content_bytes,
+            ..PageStats::default()
+        };
+        for block in &self.elements {
+            match block {
+                Block::Paragraph(p) => {
+                    stats.paragraph_count += 1;
+                    if p.is_heading() {
+                        stats.heading_count += 1;
+                    }
+                }
+                Block::List { items, .. } => stats.paragraph_count += items.len() as u32,
+                Block::Table(_) => stats.table_count += 1,
+                Block::Image { .. } => stats.image_count += 1,
+                Block::CodeBlock { .. } => stats.code_block_count += 1,
+                _ => {}
+            }
+        }
+        let text = self.plain_text();
+        stats.word_count = text.split_whitespace().count() as u32;
+        stats.char_count = text.chars().filter(|c| !c.is_whitespace()).count() as u32;
+        stats.has_tables = stats.table_count > 0;
+        stats.has_images = stats.image_count > 0;
+        stats
+    }
+}
+
+/// Which kinds of blocks to keep when pruning a page or rendering.
+///
+/// Paragraphs include list items and raw text; `other` covers horizontal
+/// rules and page/section breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockFilter {
+    /// Keep body paragraphs, list items and raw text
+    pub paragraphs: bool,
+    /// Keep headings
+    pub headings: bool,
+    /// Keep tables
+    pub tables: bool,
+    /// Keep images (and their resources)
+    pub images: bool,
+    /// Keep code blocks
+    pub code_blocks: bool,
+    /// Keep horizontal rules and page/section breaks
+    pub other: bool,
+}
+
+impl BlockFilter {
+    /// Keep every block (the default).
+    pub fn all() -> Self {
+        Self {
+            paragraphs: true,
+            headings: true,
+            tables: true,
+            images: true,
+            code_blocks: true,
+            other: true,
+        }
+    }
+
+    /// Keep nothing; combine with the `with_*` methods to opt kinds back in.
+    pub fn none() -> Self {
+        Self {
+            paragraphs: false,
+            headings: false,
+            tables: false,
+            images: false,
+            code_blocks: false,
+            other: false,
+        }
+    }
+
+    /// Keep only tables.
+    pub fn only_tables() -> Self {
+        Self::none().with_tables(true)
+    }
+
+    /// Keep only headings.
+    pub fn only_headings() -> Self {
+        Self::none().with_headings(true)
+    }
+
+    /// Keep or drop body paragraphs.
+    pub fn with_paragraphs(mut self, keep: bool) -> Self {
+        self.paragraphs = keep;
+        self
+    }
+
+    /// Keep or drop headings.
+    pub fn with_headings(mut self, keep: bool) -> Self {
+        self.headings = keep;
+        self
+    }
+
+    /// Keep or drop tables.
+    pub fn with_tables(mut self, keep: bool) -> Self {
+        self.tables = keep;
+        self
+    }
+
+    /// Keep or drop images.
+    pub fn with_images(mut self, keep: bool) -> Self {
+        self.images = keep;
+        self
+    }
+
+    /// Keep or drop code blocks.
+    pub fn with_code_blocks(mut self, keep: bool) -> Self {
+        self.code_blocks = keep;
+        self
+    }
+
+    /// Whether every kind is kept.
+    pub fn is_all(&self) -> bool {
+        *self == Self::all()
+    }
+
+    /// Whether `block` passes the filter.
+    pub fn allows(&self, block: &Block) -> bool {
+        match block {
+            Block::Paragraph(p) if p.is_heading() => self.headings,
+            Block::Paragraph(_) | Block::List { .. } | Block::Raw { .. } => self.paragraphs,
+            Block::Table(_) => self.tables,
+            Block::Image { .. } => self.images,
+            Block::CodeBlock { .. } => self.code_blocks,
+            Block::HorizontalRule | Block::PageBreak | Block::SectionBreak => self.other,
+        }
+    }
+}
+
+impl Default for BlockFilter {
+    fn default() -> Self {
+        Self::all()
+    }
 }
 
 impl Default for Page {
@@ -138,6 +550,14 @@ pub enum Block {
     /// A paragraph of text
     Paragraph(Paragraph),
 
+    /// Consecutive list items, grouped by [`Page::group_lists`]
+    List {
+        /// Whether the list is numbered, as its first item is
+        ordered: bool,
+        /// The items in order; nested items keep their `list_info.level`
+        items: Vec<Paragraph>,
+    },
+
     /// A table
     Table(Table),
 
@@ -157,6 +577,14 @@ pub enum Block {
         y: Option<f32>,
     },
 
+    /// A code listing set in a monospace font, with its line structure
+    CodeBlock {
+        /// Guessed language (e.g. "rust", "python"), if recognisable
+        language: Option<String>,
+        /// Code text; lines separated by `\n`, indentation preserved
+        content: String,
+    },
+
     /// A horizontal rule / separator
     HorizontalRule,
 
@@ -182,8 +610,9 @@ impl Block {
     pub fn append_plain_text(&self, out: &mut String) {
         match self {
             Block::Paragraph(p) => out.push_str(&p.plain_text()),
+            Block::List { items, .. } => out.push_str(&list_plain_text(items)),
             Block::Table(t) => out.push_str(&t.plain_text()),
-            Block::Raw { content } => out.push_str(content),
+            Block::Raw { content } | Block::CodeBlock { content, .. } => out.push_str(content),
             // Image, HorizontalRule, PageBreak, SectionBreak contribute no text.
             _ => {}
         }
@@ -201,6 +630,14 @@ impl Block {
         }
     }
 
+    /// Create a code block.
+    pub fn code(content: impl Into<String>, language: Option<String>) -> Self {
+        Block::CodeBlock {
+            language,
+            content: content.into(),
+        }
+    }
+
     /// Create an image block with dimensions.
     pub fn image_with_size(resource_id: impl Into<String>, width: f32, height: f32) -> Self {
         Block::Image {
@@ -213,11 +650,50 @@ impl Block {
         }
     }
 
+    /// Ids of the resources this block refers to: an image block's own id
+    /// and those of inline images in its paragraphs and list items.
+    pub fn resource_ids(&self) -> Vec<&str> {
+        fn inline(p: &Paragraph) -> impl Iterator<Item = &str> {
+            p.content.iter().filter_map(|inline| match inline {
+                InlineContent::Image { resource_id, .. } => Some(resource_id.as_str()),
+                _ => None,
+            })
+        }
+        match self {
+            Block::Image { resource_id, .. } => vec![resource_id.as_str()],
+            Block::Paragraph(p) => inline(p).collect(),
+            Block::List { items, .. } => items.iter().flat_map(inline).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Mutable access to the ids returned by [`Block::resource_ids`], for
+    /// renaming resources.
+    pub(crate) fn resource_ids_mut(&mut self) -> Vec<&mut String> {
+        fn inline(p: &mut Paragraph) -> impl Iterator<Item = &mut String> {
+            p.content.iter_mut().filter_map(|inline| match inline {
+                InlineContent::Image { resource_id, .. } => Some(resource_id),
+                _ => None,
+            })
+        }
+        match self {
+            Block::Image { resource_id, .. } => vec![resource_id],
+            Block::Paragraph(p) => inline(p).collect(),
+            Block::List { items, .. } => items.iter_mut().flat_map(inline).collect(),
+            _ => Vec::new(),
+        }
+    }
+
     /// Check if this block is a paragraph.
     pub fn is_paragraph(&self) -> bool {
         matches!(self, Block::Paragraph(_))
     }
 
+    /// Check if this block is a grouped list.
+    pub fn is_list(&self) -> bool {
+        matches!(self, Block::List { .. })
+    }
+
     /// Check if this block is a table.
     pub fn is_table(&self) -> bool {
         matches!(self, Block::Table(_))
@@ -227,6 +703,80 @@ impl Block {
     pub fn is_image(&self) -> bool {
         matches!(self, Block::Image { .. })
     }
+
+    /// Check if this block is a code block.
+    pub fn is_code_block(&self) -> bool {
+        matches!(self, Block::CodeBlock { .. })
+    }
+}
+
+/// List items' text, separated as ungrouped paragraphs would be.
+fn list_plain_text(items: &[Paragraph]) -> String {
+    items
+        .iter()
+        .map(Paragraph::plain_text)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Whether `p` is a numbered (`true`) or bulleted (`false`) list item.
+fn list_kind(p: &Paragraph) -> Option<bool> {
+    if let Some(info) = &p.style.list_info {
+        return match info.style {
+            ListStyle::Ordered { .. } => Some(true),
+            ListStyle::Unordered { .. } => Some(false),
+            ListStyle::Definition { .. } => None,
+        };
+    }
+    if p.is_heading() {
+        return None;
+    }
+    // 추출된 목록 항목은 표지가 본문 앞에 붙은 문단으로 남는다.
+    let text = p.plain_text();
+    let (marker, rest) = text.trim_start().split_once(char::is_whitespace)?;
+    if rest.trim().is_empty() {
+        return None;
+    }
+    if matches!(
+        marker,
+        "•" | "·"
+            | "-"
+            | "–"
+            | "*"
+            | "○"
+            | "●"
+            | "◦"
+            | "▪"
+            | "■"
+            | "□"
+            | "◆"
+            | "◇"
+            | "▸"
+            | "►"
+            | "▶"
+            | "➤"
+    ) {
+        return Some(false);
+    }
+    is_number_marker(marker).then_some(true)
+}
+
+/// `1.`, `12)`, `(3)`, `a.` or `b)`.
+fn is_number_marker(marker: &str) -> bool {
+    let label = match marker.strip_prefix('(') {
+        Some(inner) => inner.strip_suffix(')'),
+        None => marker.strip_suffix(['.', ')']),
+    };
+    let Some(label) = label else {
+        return false;
+    };
+    let digits = !label.is_empty() && label.len() <= 3 && label.bytes().all(|b| b.is_ascii_digit());
+    let letter = label.len() == 1 && label.bytes().all(|b| b.is_ascii_lowercase());
+    digits || letter
+}
+
+fn list_level(item: &Paragraph) -> u8 {
+    item.style.list_info.as_ref().map_or(0, |info| info.level)
 }
 
 #[cfg(test)]
@@ -251,10 +801,142 @@ mod tests {
         assert!(!a4.is_landscape());
     }
 
+    #[test]
+    fn test_block_sources_follow_blocks() {
+        let source = BlockSource {
+            streams: vec![(4, 0)],
+            first_op: 3,
+            last_op: 7,
+        };
+        let mut page = Page::letter(1);
+        page.add_block(Block::image("img1"));
+        page.add_block_with_source(
+            Block::Paragraph(Paragraph::with_text("Body")),
+            Some(source.clone()),
+        );
+        page.add_block(Block::HorizontalRule);
+        assert_eq!(page.block_source(0), None);
+        assert_eq!(page.block_source(1), Some(&source));
+
+        page.retain_blocks(&BlockFilter::all().with_images(false));
+        assert_eq!(page.block_source(0), Some(&source));
+        assert_eq!(page.block_source(1), None);
+    }
+
     #[test]
     fn test_block_variants() {
         let img = Block::image("img1");
         assert!(img.is_image());
         assert!(!img.is_paragraph());
+
+        let code = Block::code("fn main() {}", Some("rust".into()));
+        assert!(code.is_code_block());
+        let json = serde_json::to_string(&code).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"code_block","language":"rust","content":"fn main() {}"}"#
+        );
+    }
+
+    #[test]
+    fn test_page_stats_serialized() {
+        let mut page = Page::letter(3);
+        page.add_paragraph(Paragraph::heading("Results", 1));
+        page.add_paragraph(Paragraph::with_text("Two tables follow."));
+        page.add_table(Table::new());
+        page.add_block(Block::image("img1"));
+
+        let stats = page.stats();
+        assert_eq!(stats.word_count, 4);
+        assert_eq!(stats.char_count, 23);
+        assert_eq!(stats.block_count, 4);
+        assert_eq!((stats.paragraph_count, stats.heading_count), (2, 1));
+        assert!(stats.has_tables && stats.has_images);
+
+        let value = serde_json::to_value(&page).unwrap();
+        assert_eq!(value["stats"]["table_count"], 1);
+        assert_eq!(value["stats"]["has_images"], true);
+        assert!(value.get("images").is_none());
+
+        // `stats`는 파생 값이므로 역직렬화 시 무시된다.
+        let back: Page = serde_json::from_value(value).unwrap();
+        assert_eq!(back.stats(), stats);
+    }
+
+    #[test]
+    fn test_block_filter() {
+        let mut page = Page::letter(1);
+        page.add_paragraph(Paragraph::heading("Glossary", 1));
+        page.add_paragraph(Paragraph::with_text("Body"));
+        page.add_table(Table::new());
+        page.add_block(Block::image("img1"));
+        page.images
+            .push(("img1".to_string(), Resource::image(Vec::new(), "image/png")));
+
+        let mut headings_and_tables = page.clone();
+        headings_and_tables.retain_blocks(&BlockFilter::only_headings().with_tables(true));
+        assert_eq!(headings_and_tables.block_count(), 2);
+        assert!(headings_and_tables.images.is_empty());
+
+        let mut no_images = page.clone();
+        no_images.retain_blocks(&BlockFilter::all().with_images(false));
+        assert_eq!(no_images.block_count(), 3);
+        assert!(!no_images.elements.iter().any(Block::is_image));
+    }
+
+    #[test]
+    fn test_group_lists() {
+        use crate::model::ListInfo;
+
+        let item = |text: &str, info: ListInfo| {
+            let mut p = Paragraph::with_text(text);
+            p.style.list_info = Some(info);
+            Block::Paragraph(p)
+        };
+        let source = |first_op, last_op| BlockSource {
+            streams: vec![(4, 0)],
+            first_op,
+            last_op,
+        };
+        let mut page = Page::letter(1);
+        page.add_block_with_source(item("First", ListInfo::numbered(0, 1)), Some(source(2, 3)));
+        page.add_block_with_source(item("Nested", ListInfo::bullet(1)), Some(source(5, 6)));
+        page.add_block_with_source(item("Second", ListInfo::numbered(0, 2)), Some(source(8, 9)));
+        page.add_block_with_source(item("Loose", ListInfo::bullet(0)), Some(source(11, 12)));
+        page.add_paragraph(Paragraph::with_text("After the list."));
+        page.add_paragraph(Paragraph::with_text("• Marked in the text"));
+        let text = page.plain_text();
+
+        page.group_lists();
+        assert_eq!(page.block_count(), 4);
+        assert!(matches!(
+            &page.elements[0],
+            Block::List { ordered: true, items } if items.len() == 3
+        ));
+        assert!(matches!(
+            &page.elements[1],
+            Block::List { ordered: false, items } if items.len() == 1
+        ));
+        assert!(page.elements[2].is_paragraph());
+        assert!(matches!(
+            &page.elements[3],
+            Block::List { ordered: false, .. }
+        ));
+        assert_eq!(page.block_source(0), Some(&source(2, 9)));
+        assert_eq!(page.plain_text(), text);
+        assert_eq!(page.stats().paragraph_count, 6);
+    }
+
+    #[test]
+    fn test_list_kind_from_text_markers() {
+        let kind = |text: &str| list_kind(&Paragraph::with_text(text));
+        assert_eq!(kind("1. Apples"), Some(true));
+        assert_eq!(kind("(2) Pears"), Some(true));
+        assert_eq!(kind("b) Plums"), Some(true));
+        assert_eq!(kind("- Butter"), Some(false));
+        assert_eq!(kind("3.5 percent of sales"), None);
+        assert_eq!(kind("2024. A good year"), None);
+        assert_eq!(kind("1."), None);
+        assert_eq!(list_kind(&Paragraph::heading("1. Introduction", 1)), None);
     }
 }
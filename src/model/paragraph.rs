@@ -35,6 +35,13 @@ impl Paragraph {
         p
     }
 
+    /// Create a definition list item: `definition` describing `term`.
+    pub fn definition(term: impl Into<String>, definition: impl Into<String>) -> Self {
+        let mut p = Self::with_text(definition);
+        p.style.list_info = Some(ListInfo::definition(term));
+        p
+    }
+
     /// Add plain text to the paragraph.
     pub fn add_text(&mut self, text: impl Into<String>) {
         self.content.push(InlineContent::Text(TextRun {
@@ -53,9 +60,11 @@ impl Paragraph {
         self.content.push(InlineContent::LineBreak);
     }
 
-    /// Get plain text content of the paragraph.
+    /// Get plain text content of the paragraph. Definition list items read
+    /// as "term: definition".
     pub fn plain_text(&self) -> String {
-        self.content
+        let text: String = self
+            .content
             .iter()
             .map(|c| match c {
                 InlineContent::Text(run) => run.text.clone(),
@@ -63,7 +72,11 @@ impl Paragraph {
                 InlineContent::Link { text, .. } => text.clone(),
                 InlineContent::Image { alt_text, .. } => alt_text.clone().unwrap_or_default(),
             })
-            .collect()
+            .collect();
+        match self.definition_term() {
+            Some(term) => format!("{}: {}", term, text),
+            None => text,
+        }
     }
 
     /// Check if the paragraph is empty.
@@ -85,6 +98,14 @@ impl Paragraph {
     pub fn is_list_item(&self) -> bool {
         self.style.list_info.is_some()
     }
+
+    /// The term of a definition list item, or None for other paragraphs.
+    pub fn definition_term(&self) -> Option<&str> {
+        match &self.style.list_info.as_ref()?.style {
+            ListStyle::Definition { term } => Some(term),
+            _ => None,
+        }
+    }
 }
 
 impl Default for Paragraph {
@@ -241,6 +262,11 @@ pub struct ParagraphStyle {
 
     /// First line indent in points
     pub first_line_indent: Option<f32>,
+
+    /// Language of the text as an ISO 639-1 code ("en", "ko"), when detected
+    /// (see `ParseOptions::tag_languages`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 /// Text alignment.
@@ -292,6 +318,15 @@ impl ListInfo {
             item_number: Some(number),
         }
     }
+
+    /// Create a definition list item for `term`.
+    pub fn definition(term: impl Into<String>) -> Self {
+        Self {
+            style: ListStyle::Definition { term: term.into() },
+            level: 0,
+            item_number: None,
+        }
+    }
 }
 
 /// List style.
@@ -310,6 +345,11 @@ pub enum ListStyle {
         /// Bullet character
         marker: char,
     },
+    /// Definition list (glossary), the paragraph holding the definition
+    Definition {
+        /// Term being defined
+        term: String,
+    },
 }
 
 /// Number style for ordered lists.
@@ -370,4 +410,15 @@ mod tests {
         let numbered = ListInfo::numbered(1, 5);
         assert_eq!(numbered.item_number, Some(5));
     }
+
+    #[test]
+    fn test_definition_plain_text() {
+        let p = Paragraph::definition("Kerning", "Adjusting the space between letter pairs.");
+        assert_eq!(p.definition_term(), Some("Kerning"));
+        assert_eq!(
+            p.plain_text(),
+            "Kerning: Adjusting the space between letter pairs."
+        );
+        assert_eq!(Paragraph::with_text("Kerning").definition_term(), None);
+    }
 }
@@ -0,0 +1,84 @@
+//! Provenance information recorded for each extraction.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Where an extracted document came from and how it was produced.
+///
+/// Recorded by the parser on every parse; emitted into Markdown frontmatter
+/// and JSON only when `RenderOptions::include_provenance` is set, so default
+/// output stays byte-identical across runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// SHA-256 of the source PDF bytes (lowercase hex)
+    pub source_sha256: Option<String>,
+
+    /// Producing library and version (e.g., "unpdf 0.9.0")
+    pub generator: String,
+
+    /// Summary of the parse options used
+    pub parse_options: String,
+
+    /// When the extraction ran
+    pub extracted_at: DateTime<Utc>,
+}
+
+impl Provenance {
+    /// Create provenance for an extraction happening now.
+    pub fn new(source_sha256: Option<String>, parse_options: impl Into<String>) -> Self {
+        Self {
+            source_sha256,
+            generator: format!("unpdf {}", env!("CARGO_PKG_VERSION")),
+            parse_options: parse_options.into(),
+            extracted_at: Utc::now(),
+        }
+    }
+
+    /// YAML lines for embedding into frontmatter (without delimiters).
+    pub(crate) fn yaml_lines(&self) -> Vec<String> {
+        let mut lines = vec!["provenance:".to_string()];
+        if let Some(ref hash) = self.source_sha256 {
+            lines.push(format!("  source_sha256: \"{}\"", hash));
+        }
+        lines.push(format!("  generator: \"{}\"", self.generator));
+        lines.push(format!("  parse_options: \"{}\"", self.parse_options));
+        lines.push(format!(
+            "  extracted_at: \"{}\"",
+            self.extracted_at
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        ));
+        lines
+    }
+}
+
+/// SHA-256 of `data` as lowercase hex.
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_yaml_lines() {
+        let p = Provenance::new(Some("00ff".into()), "mode=full");
+        let yaml = p.yaml_lines().join("\n");
+        assert!(yaml.starts_with("provenance:"));
+        assert!(yaml.contains("  source_sha256: \"00ff\""));
+        assert!(yaml.contains(&format!("unpdf {}", env!("CARGO_PKG_VERSION"))));
+        assert!(yaml.contains("  parse_options: \"mode=full\""));
+    }
+}
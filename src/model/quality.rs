@@ -30,6 +30,11 @@ pub struct ExtractionQuality {
     /// alone. Set `ParseOptions::suppress_low_confidence_ocr` to `false` to keep it.
     #[serde(default)]
     pub suppressed_ocr_pages: usize,
+
+    /// Pages left out because they ran over `ParseOptions::page_timeout` or
+    /// were not reached before `ParseOptions::timeout` ran out.
+    #[serde(default)]
+    pub timed_out_pages: Vec<u32>,
 }
 
 impl ExtractionQuality {
@@ -42,6 +47,7 @@ impl ExtractionQuality {
             encrypted: false,
             is_scan_pdf: false,
             suppressed_ocr_pages: 0,
+            timed_out_pages: Vec::new(),
         }
     }
 
@@ -72,6 +78,14 @@ impl ExtractionQuality {
                 "PDF is encrypted. Text extraction may be incomplete or unavailable.".to_string(),
             );
         }
+        if !self.timed_out_pages.is_empty() {
+            let pages: Vec<String> = self.timed_out_pages.iter().map(u32::to_string).collect();
+            return Some(format!(
+                "Parsing timed out on {} page(s) ({}); their content is missing.",
+                pages.len(),
+                pages.join(", ")
+            ));
+        }
         if self.char_count == 0 {
             if self.is_scan_pdf {
                 return Some(
@@ -116,6 +130,7 @@ pub struct QualityAccumulator {
     word_count: usize,
     last_was_non_ws: bool,
     suppressed_ocr_pages: usize,
+    timed_out_pages: Vec<u32>,
 }
 
 impl QualityAccumulator {
@@ -144,6 +159,11 @@ impl QualityAccumulator {
         self.suppressed_ocr_pages += 1;
     }
 
+    /// Record that a page was given up on after its time budget ran out.
+    pub fn note_timed_out_page(&mut self, page: u32) {
+        self.timed_out_pages.push(page);
+    }
+
     pub fn finalize(self) -> ExtractionQuality {
         ExtractionQuality {
             char_count: self.char_count,
@@ -152,6 +172,7 @@ impl QualityAccumulator {
             encrypted: false,
             is_scan_pdf: false,
             suppressed_ocr_pages: self.suppressed_ocr_pages,
+            timed_out_pages: self.timed_out_pages,
         }
     }
 }
@@ -0,0 +1,277 @@
+//! Reconciliation of a PDF's text layer with an OCR reading of the same pages.
+//!
+//! Mixed-quality scans alternate between pages with a usable embedded text
+//! layer and pages that are only images (or carry a broken OCR layer). Running
+//! an OCR engine over the document yields a second `Document`; this module
+//! compares the two per page and per block and keeps whichever reads better.
+
+use serde::{Deserialize, Serialize};
+
+use super::{Block, Document, ExtractionQuality, Page};
+use crate::parser::ocr_gate::text_confidence;
+
+/// Which reading a reconciled page's text was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextSource {
+    /// The PDF's embedded text layer
+    TextLayer,
+    /// The OCR result
+    Ocr,
+    /// Block by block from both
+    Mixed,
+}
+
+/// How one page was reconciled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageReconciliation {
+    /// Page number (1-indexed)
+    pub page: u32,
+
+    /// Where the page's text came from
+    pub source: TextSource,
+
+    /// Confidence of the text layer's page text (0.0–1.0), `None` if it had none
+    pub text_layer_confidence: Option<f32>,
+
+    /// Confidence of the OCR page text (0.0–1.0), `None` if it had none
+    pub ocr_confidence: Option<f32>,
+}
+
+/// A reconciled document and the per-page decisions behind it.
+#[derive(Debug, Clone)]
+pub struct Reconciliation {
+    /// The combined document
+    pub document: Document,
+
+    /// One entry per page, in page order
+    pub pages: Vec<PageReconciliation>,
+}
+
+impl Document {
+    /// Combine a text-layer parse and an OCR parse of the same PDF.
+    ///
+    /// Pages are matched by number. When both readings split a page into the
+    /// same number of text blocks, each block pair is compared and the more
+    /// confident one kept; otherwise the whole page's text comes from the more
+    /// confident reading. Confidence measures how much the text reads like
+    /// language (see the OCR-layer gate), and ties go to the text layer, which
+    /// is exact when it is good.
+    ///
+    /// Images, metadata, outline and form fields come from the text layer;
+    /// OCR resources are added only under ids the text layer does not use.
+    pub fn reconcile(text_layer: Document, ocr: Document) -> Reconciliation {
+        let Document {
+            pages: ocr_pages,
+            resources: ocr_resources,
+            ..
+        } = ocr;
+        let mut document = Document {
+            pages: Vec::new(),
+            ..text_layer.clone()
+        };
+        for (id, resource) in ocr_resources {
+            document.resources.entry(id).or_insert(resource);
+        }
+
+        let mut numbers: Vec<u32> = text_layer
+            .pages
+            .iter()
+            .chain(ocr_pages.iter())
+            .map(|p| p.number)
+            .collect();
+        numbers.sort_unstable();
+        numbers.dedup();
+
+        let mut pages = Vec::with_capacity(numbers.len());
+        for number in numbers {
+            let text_page = text_layer.pages.iter().find(|p| p.number == number);
+            let ocr_page = ocr_pages.iter().find(|p| p.number == number);
+            let (page, report) = reconcile_page(number, text_page, ocr_page);
+            document.pages.push(page);
+            pages.push(report);
+        }
+
+        let quality = ExtractionQuality::from_text(&document.plain_text());
+        document.extraction_quality = ExtractionQuality {
+            encrypted: text_layer.extraction_quality.encrypted,
+            is_scan_pdf: text_layer.extraction_quality.is_scan_pdf,
+            suppressed_ocr_pages: text_layer.extraction_quality.suppressed_ocr_pages,
+            timed_out_pages: text_layer.extraction_quality.timed_out_pages.clone(),
+            ..quality
+        };
+        document.metadata.page_count = document.page_count();
+
+        Reconciliation { document, pages }
+    }
+}
+
+fn reconcile_page(
+    number: u32,
+    text_page: Option<&Page>,
+    ocr_page: Option<&Page>,
+) -> (Page, PageReconciliation) {
+    let text_conf = text_page.and_then(page_confidence);
+    let ocr_conf = ocr_page.and_then(page_confidence);
+    let report = |source| PageReconciliation {
+        page: number,
+        source,
+        text_layer_confidence: text_conf,
+        ocr_confidence: ocr_conf,
+    };
+
+    let (text_page, ocr_page) = match (text_page, ocr_page) {
+        (Some(t), Some(o)) => (t, o),
+        (Some(t), None) => return (t.clone(), report(TextSource::TextLayer)),
+        (None, Some(o)) => return (o.clone(), report(TextSource::Ocr)),
+        (None, None) => unreachable!("page numbers come from one of the two documents"),
+    };
+
+    let text_blocks: Vec<&Block> = text_page.elements.iter().filter(|b| has_text(b)).collect();
+    let ocr_blocks: Vec<&Block> = ocr_page.elements.iter().filter(|b| has_text(b)).collect();
+
+    // 블록 구성이 같으면 블록 단위로 고른다.
+    if !text_blocks.is_empty() && text_blocks.len() == ocr_blocks.len() {
+        let mut ocr_iter = ocr_blocks.into_iter();
+        let (mut from_text, mut from_ocr) = (0usize, 0usize);
+        let mut page = text_page.clone();
+        for block in page.elements.iter_mut().filter(|b| has_text(b)) {
+            let Some(ocr_block) = ocr_iter.next() else {
+                break;
+            };
+            if block_confidence(ocr_block) > block_confidence(block) {
+                *block = ocr_block.clone();
+                from_ocr += 1;
+            } else {
+                from_text += 1;
+            }
+        }
+        let source = match (from_text, from_ocr) {
+            (_, 0) => TextSource::TextLayer,
+            (0, _) => TextSource::Ocr,
+            _ => TextSource::Mixed,
+        };
+        if source != TextSource::TextLayer {
+            page.ocr_text_suppressed = false;
+        }
+        return (page, report(source));
+    }
+
+    if ocr_conf.unwrap_or(0.0) > text_conf.unwrap_or(0.0) {
+        // OCR 텍스트에 텍스트 레이어의 이미지 등 비텍스트 블록을 덧붙인다.
+        let mut page = ocr_page.clone();
+        page.width = text_page.width;
+        page.height = text_page.height;
+        page.elements.extend(
+            text_page
+                .elements
+                .iter()
+                .filter(|b| b.is_image() && !has_image(&page.elements, b))
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
+        for (id, resource) in &text_page.images {
+            if !page.images.iter().any(|(existing, _)| existing == id) {
+                page.images.push((id.clone(), resource.clone()));
+            }
+        }
+        (page, report(TextSource::Ocr))
+    } else {
+        (text_page.clone(), report(TextSource::TextLayer))
+    }
+}
+
+/// Whether a block contributes text to the page.
+fn has_text(block: &Block) -> bool {
+    let mut text = String::new();
+    block.append_plain_text(&mut text);
+    !text.trim().is_empty()
+}
+
+fn block_confidence(block: &Block) -> f32 {
+    let mut text = String::new();
+    block.append_plain_text(&mut text);
+    text_confidence(&text).unwrap_or(0.0)
+}
+
+fn page_confidence(page: &Page) -> Option<f32> {
+    let mut text = String::new();
+    for block in &page.elements {
+        block.append_plain_text(&mut text);
+        text.push('\n');
+    }
+    text_confidence(&text)
+}
+
+/// Whether `blocks` already references the image `block` points to.
+fn has_image(blocks: &[Block], block: &Block) -> bool {
+    let Block::Image { resource_id, .. } = block else {
+        return false;
+    };
+    blocks
+        .iter()
+        .any(|b| matches!(b, Block::Image { resource_id: other, .. } if other == resource_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Paragraph;
+
+    const PROSE: &str = "The pump must be primed before the first start.";
+    const NOISE: &str = "Φ ,φ ∽ ㄱ υ Φ ° – ° =→ ↔";
+
+    fn doc(pages: &[&[&str]]) -> Document {
+        let mut d = Document::new();
+        for (i, blocks) in pages.iter().enumerate() {
+            let mut page = Page::a4(i as u32 + 1);
+            for text in *blocks {
+                page.add_paragraph(Paragraph::with_text(*text));
+            }
+            d.add_page(page);
+        }
+        d
+    }
+
+    #[test]
+    fn test_page_without_text_layer_takes_ocr() {
+        let text_layer = doc(&[&[PROSE], &[]]);
+        let ocr = doc(&[&[PROSE], &["Scanned page two text."]]);
+        let result = Document::reconcile(text_layer, ocr);
+
+        assert_eq!(result.pages[0].source, TextSource::TextLayer);
+        assert_eq!(result.pages[1].source, TextSource::Ocr);
+        assert_eq!(result.pages[1].text_layer_confidence, None);
+        assert!(result.document.plain_text().contains("Scanned page two"));
+    }
+
+    #[test]
+    fn test_blocks_chosen_individually() {
+        let text_layer = doc(&[&[PROSE, NOISE]]);
+        let ocr = doc(&[&["T h e p u m p", "Check the seal for leaks."]]);
+        let result = Document::reconcile(text_layer, ocr);
+
+        assert_eq!(result.pages[0].source, TextSource::Mixed);
+        let text = result.document.plain_text();
+        assert!(text.contains(PROSE));
+        assert!(text.contains("Check the seal"));
+        assert!(!text.contains('Φ'));
+    }
+
+    #[test]
+    fn test_tie_keeps_text_layer() {
+        let result = Document::reconcile(doc(&[&["Alpha beta"]]), doc(&[&["Alpha beta"]]));
+        assert_eq!(result.pages[0].source, TextSource::TextLayer);
+    }
+
+    #[test]
+    fn test_noisy_page_replaced_wholesale() {
+        let text_layer = doc(&[&[NOISE]]);
+        let ocr = doc(&[&["First recognised paragraph.", "Second one."]]);
+        let result = Document::reconcile(text_layer, ocr);
+
+        assert_eq!(result.pages[0].source, TextSource::Ocr);
+        assert_eq!(result.document.pages[0].elements.len(), 2);
+        assert_eq!(result.document.extraction_quality.word_count, 5);
+    }
+}
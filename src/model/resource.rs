@@ -5,8 +5,9 @@ use serde::{Deserialize, Serialize};
 /// An embedded resource in the document.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Resource {
-    /// Raw binary data
-    #[serde(skip_serializing)]
+    /// Raw binary data. Not part of JSON output, so empty when read back
+    /// from JSON.
+    #[serde(skip_serializing, default)]
     pub data: Vec<u8>,
 
     /// MIME type (e.g., "image/jpeg")
@@ -96,6 +97,11 @@ impl Resource {
         matches!(self.resource_type, ResourceType::Image)
     }
 
+    /// Check if this is a vector drawing (SVG) resource.
+    pub fn is_vector(&self) -> bool {
+        matches!(self.resource_type, ResourceType::Vector)
+    }
+
     /// Check if this is a font resource.
     pub fn is_font(&self) -> bool {
         matches!(self.resource_type, ResourceType::Font)
@@ -121,17 +127,39 @@ impl Resource {
             "image/bmp" => "bmp",
             "image/webp" => "webp",
             "image/jp2" | "image/jpeg2000" => "jp2",
+            "image/svg+xml" => "svg",
             "application/pdf" => "pdf",
             "font/ttf" | "font/truetype" => "ttf",
             "font/otf" | "font/opentype" => "otf",
             "font/woff" => "woff",
             "font/woff2" => "woff2",
+            "application/xml" | "text/xml" => "xml",
             // For raw image data without recognized format, use .raw
             _ if self.is_image() => "raw",
             _ => "bin",
         }
     }
 
+    /// Whether browsers and Markdown viewers display this format directly,
+    /// so it can be embedded as a data URI.
+    pub fn is_web_image(&self) -> bool {
+        matches!(
+            self.mime_type.as_str(),
+            "image/jpeg" | "image/png" | "image/gif" | "image/webp" | "image/bmp" | "image/svg+xml"
+        )
+    }
+
+    /// The resource as a `data:<mime>;base64,...` URI.
+    pub fn to_data_uri(&self) -> String {
+        let mut uri =
+            String::with_capacity(self.mime_type.len() + 13 + self.data.len() * 4 / 3 + 4);
+        uri.push_str("data:");
+        uri.push_str(&self.mime_type);
+        uri.push_str(";base64,");
+        base64_encode_into(&self.data, &mut uri);
+        uri
+    }
+
     /// Detect MIME type from data magic bytes.
     pub fn detect_mime_type(data: &[u8]) -> Option<&'static str> {
         if data.len() < 8 {
@@ -179,6 +207,26 @@ impl Resource {
     }
 }
 
+/// Standard base64 with padding (RFC 4648 §4).
+fn base64_encode_into(data: &[u8], out: &mut String) {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3F] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+}
+
 /// Type of embedded resource.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -189,6 +237,8 @@ pub enum ResourceType {
     Font,
     /// Embedded file attachment
     Attachment,
+    /// Vector drawing captured from path operators (SVG)
+    Vector,
     /// Other/unknown
     Other,
 }
@@ -199,6 +249,7 @@ impl std::fmt::Display for ResourceType {
             ResourceType::Image => write!(f, "image"),
             ResourceType::Font => write!(f, "font"),
             ResourceType::Attachment => write!(f, "attachment"),
+            ResourceType::Vector => write!(f, "vector"),
             ResourceType::Other => write!(f, "other"),
         }
     }
@@ -239,4 +290,26 @@ mod tests {
         let res2 = Resource::png(vec![]);
         assert_eq!(res2.suggested_filename("img2"), "img2.png");
     }
+
+    #[test]
+    fn test_data_uri() {
+        assert_eq!(
+            Resource::png(b"".to_vec()).to_data_uri(),
+            "data:image/png;base64,"
+        );
+        assert_eq!(
+            Resource::png(b"f".to_vec()).to_data_uri(),
+            "data:image/png;base64,Zg=="
+        );
+        assert_eq!(
+            Resource::png(b"fo".to_vec()).to_data_uri(),
+            "data:image/png;base64,Zm8="
+        );
+        assert_eq!(
+            Resource::jpeg(b"foobar".to_vec()).to_data_uri(),
+            "data:image/jpeg;base64,Zm9vYmFy"
+        );
+        assert!(Resource::png(vec![]).is_web_image());
+        assert!(!Resource::image(vec![], "image/jp2").is_web_image());
+    }
 }
@@ -0,0 +1,27 @@
+//! Incremental-update revisions.
+
+use serde::{Deserialize, Serialize};
+
+/// One revision of an incrementally updated PDF.
+///
+/// Editors that save in place append the changed objects to the end of the
+/// file instead of rewriting it, so the original content is still there,
+/// shadowed by the newer objects. Each append is a revision.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Revision {
+    /// Revision number, 1-based, oldest first
+    pub number: u32,
+
+    /// Length in bytes of the file as of this revision
+    pub byte_len: usize,
+
+    /// Objects this revision added or rewrote
+    pub changed_objects: usize,
+
+    /// Pages whose content this revision replaced, numbered as in the parsed
+    /// document. Pages the revision added are not listed, nor are pages whose
+    /// dictionary changed but whose content streams did not (a new
+    /// annotation, say).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revised_pages: Vec<u32>,
+}
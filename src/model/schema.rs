@@ -0,0 +1,212 @@
+//! Versioned document JSON.
+//!
+//! Every document JSON written by unpdf carries a top-level `schema_version`.
+//! Consumers that persist the JSON read it back with [`VersionedJson`] (or
+//! [`Document::from_json`]), which upgrades older versions to the current
+//! model step by step, so refactoring the model does not strand stored output.
+//!
+//! Compatibility policy:
+//!
+//! - Adding an optional field or a new block kind keeps the version. Readers
+//!   must ignore fields they do not know.
+//! - Renaming, removing or changing the type or meaning of a field bumps
+//!   [`SCHEMA_VERSION`] and adds a migration from the previous version.
+//! - Every version since 0 stays readable. JSON from a newer unpdf than the
+//!   reader is refused with [`Error::UnsupportedSchemaVersion`].
+//!
+//! Version history:
+//!
+//! | Version | Written by | Changes |
+//! |---------|------------|---------|
+//! | 0 | unpdf 0.9 and earlier | no `schema_version` field |
+//! | 1 | releases after 0.9 | `schema_version` added |
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+
+use super::Document;
+
+/// Schema version of the document JSON this build writes.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Name of the top-level field holding the schema version.
+pub const SCHEMA_VERSION_FIELD: &str = "schema_version";
+
+/// Upgrade steps, indexed by the version they upgrade from.
+const MIGRATIONS: [fn(&mut Map<String, Value>); SCHEMA_VERSION as usize] = [v0_to_v1];
+
+/// Version 0 → 1: the model is unchanged; only the version field was added,
+/// which [`VersionedJson::upgrade`] stamps after every step.
+fn v0_to_v1(_: &mut Map<String, Value>) {}
+
+/// Document JSON tagged with the schema version it was written in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionedJson {
+    version: u32,
+    value: Map<String, Value>,
+}
+
+impl VersionedJson {
+    /// Read document JSON of any supported version. JSON without a
+    /// `schema_version` field is version 0.
+    pub fn parse(json: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(json).map_err(invalid)?;
+        Self::from_value(value)
+    }
+
+    /// Wrap an already parsed JSON value, as [`VersionedJson::parse`] does.
+    pub fn from_value(value: Value) -> Result<Self> {
+        let Value::Object(value) = value else {
+            return Err(Error::InvalidJson(
+                "document JSON must be an object".to_string(),
+            ));
+        };
+        let version = match value.get(SCHEMA_VERSION_FIELD) {
+            None => 0,
+            Some(v) => v
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| {
+                    Error::InvalidJson(format!("bad {}: {}", SCHEMA_VERSION_FIELD, v))
+                })?,
+        };
+        if version > SCHEMA_VERSION {
+            return Err(Error::UnsupportedSchemaVersion(version));
+        }
+        Ok(Self { version, value })
+    }
+
+    /// Schema version of the JSON.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Upgrade to [`SCHEMA_VERSION`], applying each migration in turn.
+    pub fn upgrade(mut self) -> Self {
+        while self.version < SCHEMA_VERSION {
+            MIGRATIONS[self.version as usize](&mut self.value);
+            self.version += 1;
+            self.value
+                .insert(SCHEMA_VERSION_FIELD.to_string(), Value::from(self.version));
+        }
+        self
+    }
+
+    /// The JSON object, `schema_version` included (except for version 0).
+    pub fn into_value(self) -> Value {
+        Value::Object(self.value)
+    }
+}
+
+impl From<&Document> for VersionedJson {
+    /// Document JSON at the current [`SCHEMA_VERSION`].
+    fn from(doc: &Document) -> Self {
+        let value = match serde_json::to_value(Stamped::new(doc)) {
+            Ok(Value::Object(map)) => map,
+            _ => unreachable!("Document serializes to a JSON object"),
+        };
+        Self {
+            version: SCHEMA_VERSION,
+            value,
+        }
+    }
+}
+
+impl From<Document> for VersionedJson {
+    fn from(doc: Document) -> Self {
+        Self::from(&doc)
+    }
+}
+
+impl TryFrom<VersionedJson> for Document {
+    type Error = Error;
+
+    /// Upgrade the JSON to the current version and deserialize it.
+    fn try_from(json: VersionedJson) -> Result<Self> {
+        serde_json::from_value(json.upgrade().into_value()).map_err(invalid)
+    }
+}
+
+impl Document {
+    /// Read document JSON written by any unpdf version, upgrading it to the
+    /// current model. See [`VersionedJson`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        VersionedJson::parse(json)?.try_into()
+    }
+}
+
+/// A document serialized with `schema_version` as its first field.
+#[derive(Serialize)]
+pub(crate) struct Stamped<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    document: &'a Document,
+}
+
+impl<'a> Stamped<'a> {
+    pub(crate) fn new(document: &'a Document) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            document,
+        }
+    }
+}
+
+fn invalid(e: serde_json::Error) -> Error {
+    Error::InvalidJson(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Page, Paragraph};
+
+    fn sample() -> Document {
+        let mut doc = Document::new();
+        doc.metadata.title = Some("Report".to_string());
+        let mut page = Page::letter(1);
+        page.add_paragraph(Paragraph::with_text("Hello"));
+        doc.add_page(page);
+        doc
+    }
+
+    #[test]
+    fn test_round_trip_current_version() {
+        let json = VersionedJson::from(&sample());
+        assert_eq!(json.version(), SCHEMA_VERSION);
+        let value = json.clone().into_value();
+        assert_eq!(value[SCHEMA_VERSION_FIELD], SCHEMA_VERSION);
+
+        let doc = Document::from_json(&value.to_string()).unwrap();
+        assert_eq!(doc.metadata.title.as_deref(), Some("Report"));
+        assert_eq!(doc.plain_text(), sample().plain_text());
+    }
+
+    #[test]
+    fn test_unversioned_json_upgrades() {
+        // unpdf 0.9 출력에는 schema_version 이 없다.
+        let mut legacy = serde_json::to_value(sample()).unwrap();
+        legacy.as_object_mut().unwrap().remove(SCHEMA_VERSION_FIELD);
+
+        let json = VersionedJson::from_value(legacy).unwrap();
+        assert_eq!(json.version(), 0);
+        let upgraded = json.upgrade();
+        assert_eq!(upgraded.version(), SCHEMA_VERSION);
+        assert_eq!(upgraded, VersionedJson::from(&sample()));
+    }
+
+    #[test]
+    fn test_newer_version_is_refused() {
+        let json = format!(r#"{{"{}": {}}}"#, SCHEMA_VERSION_FIELD, SCHEMA_VERSION + 1);
+        assert!(matches!(
+            Document::from_json(&json),
+            Err(Error::UnsupportedSchemaVersion(v)) if v == SCHEMA_VERSION + 1
+        ));
+        assert!(matches!(
+            Document::from_json("[]"),
+            Err(Error::InvalidJson(_))
+        ));
+    }
+}
@@ -0,0 +1,192 @@
+//! Content regrouped by heading hierarchy.
+
+use serde::{Deserialize, Serialize};
+
+use super::{Block, Page};
+
+/// A heading and the content under it, up to the next heading of the same
+/// or a higher level.
+///
+/// Built by [`Document::into_sections`](super::Document::into_sections).
+/// Content before the first heading forms a section of its own with no
+/// heading and level 0.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Section {
+    /// Heading text, `None` for content before the first heading
+    pub heading: Option<String>,
+
+    /// Heading level (1-6), 0 for content before the first heading
+    pub level: u8,
+
+    /// Blocks between the heading and the first subsection, in reading
+    /// order. The heading itself is not included.
+    pub blocks: Vec<Block>,
+
+    /// First and last page the section and its subsections span
+    pub page_range: (u32, u32),
+
+    /// Subsections, one heading level or more below this one
+    pub children: Vec<Section>,
+}
+
+impl Section {
+    fn new(heading: Option<String>, level: u8, page: u32) -> Self {
+        Self {
+            heading,
+            level,
+            blocks: Vec::new(),
+            page_range: (page, page),
+            children: Vec::new(),
+        }
+    }
+
+    /// Plain text of the section's own blocks, without heading or
+    /// subsections.
+    pub fn plain_text(&self) -> String {
+        let mut out = String::new();
+        for block in &self.blocks {
+            if !out.is_empty() {
+                out.push_str("\n\n");
+            }
+            block.append_plain_text(&mut out);
+        }
+        out
+    }
+
+    /// Number of sections in this subtree, this one included.
+    pub fn total_sections(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(Section::total_sections)
+            .sum::<usize>()
+    }
+}
+
+/// Regroup the blocks of `pages` by heading.
+pub(crate) fn build_sections(pages: Vec<Page>) -> Vec<Section> {
+    let mut roots: Vec<Section> = Vec::new();
+    // 열린 절을 바깥쪽부터 쌓는다. 맨 위가 지금 블록을 받는 절이다.
+    let mut open: Vec<Section> = Vec::new();
+
+    for page in pages {
+        let number = page.number;
+        for block in page.elements {
+            let heading = match &block {
+                Block::Paragraph(p) => p
+                    .heading_level()
+                    .map(|level| (level, p.plain_text().trim().to_string()))
+                    .filter(|(_, text)| !text.is_empty()),
+                _ => None,
+            };
+            if let Some((level, text)) = heading {
+                // 머리말 없는 앞부분은 어떤 절의 부모도 되지 않는다.
+                while open
+                    .last()
+                    .is_some_and(|s| s.level >= level || s.heading.is_none())
+                {
+                    close(&mut open, &mut roots);
+                }
+                open.push(Section::new(Some(text), level, number));
+                continue;
+            }
+            if open.is_empty() {
+                open.push(Section::new(None, 0, number));
+            }
+            let section = open.last_mut().expect("a section is open");
+            section.blocks.push(block);
+            section.page_range.1 = section.page_range.1.max(number);
+        }
+    }
+    while !open.is_empty() {
+        close(&mut open, &mut roots);
+    }
+    roots
+}
+
+/// Close the innermost open section, attaching it to its parent.
+fn close(open: &mut Vec<Section>, roots: &mut Vec<Section>) {
+    let Some(section) = open.pop() else {
+        return;
+    };
+    match open.last_mut() {
+        Some(parent) => {
+            parent.page_range.1 = parent.page_range.1.max(section.page_range.1);
+            parent.children.push(section);
+        }
+        None => roots.push(section),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Paragraph;
+
+    fn page(number: u32, blocks: Vec<Paragraph>) -> Page {
+        let mut page = Page::new(number, 595.0, 842.0);
+        for block in blocks {
+            page.add_paragraph(block);
+        }
+        page
+    }
+
+    #[test]
+    fn test_build_sections_nests_by_level() {
+        let pages = vec![
+            page(
+                1,
+                vec![
+                    Paragraph::with_text("Preface"),
+                    Paragraph::heading("Chapter 1", 1),
+                    Paragraph::with_text("Intro"),
+                    Paragraph::heading("1.1", 2),
+                    Paragraph::with_text("Body"),
+                ],
+            ),
+            page(
+                2,
+                vec![
+                    Paragraph::with_text("More body"),
+                    Paragraph::heading("1.1.1", 4),
+                    Paragraph::heading("1.2", 2),
+                ],
+            ),
+            page(3, vec![Paragraph::heading("Chapter 2", 1)]),
+        ];
+        let sections = build_sections(pages);
+
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].heading, None);
+        assert_eq!(sections[0].plain_text(), "Preface");
+
+        let chapter = &sections[1];
+        assert_eq!(chapter.heading.as_deref(), Some("Chapter 1"));
+        assert_eq!(chapter.plain_text(), "Intro");
+        assert_eq!(chapter.page_range, (1, 2));
+        assert_eq!(chapter.total_sections(), 4);
+
+        let first = &chapter.children[0];
+        assert_eq!(first.heading.as_deref(), Some("1.1"));
+        assert_eq!(first.plain_text(), "Body\n\nMore body");
+        assert_eq!(first.page_range, (1, 2));
+        // 건너뛴 수준(H2 → H4)도 바로 아래 자식이 된다.
+        assert_eq!(first.children[0].heading.as_deref(), Some("1.1.1"));
+        assert_eq!(chapter.children[1].heading.as_deref(), Some("1.2"));
+
+        assert_eq!(sections[2].page_range, (3, 3));
+        assert!(sections[2].blocks.is_empty());
+    }
+
+    #[test]
+    fn test_build_sections_without_headings() {
+        let sections = build_sections(vec![
+            page(1, vec![Paragraph::with_text("a")]),
+            page(2, vec![Paragraph::with_text("b")]),
+        ]);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].level, 0);
+        assert_eq!(sections[0].page_range, (1, 2));
+        assert!(build_sections(Vec::new()).is_empty());
+    }
+}
@@ -0,0 +1,37 @@
+//! Where blocks were drawn in the source PDF.
+
+use serde::{Deserialize, Serialize};
+
+/// The content-stream operators a block was extracted from.
+///
+/// Operators are numbered from 0 across all of the page's content streams
+/// in drawing order, the way a viewer executes them. Only text-showing
+/// operators (`Tj`, `TJ`, `'`, `"`) are attributed to blocks, so the range
+/// can include operators belonging to other blocks when the content stream
+/// does not draw in reading order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockSource {
+    /// Content stream objects `(number, generation)` holding the block's
+    /// operators, in drawing order. Empty for a stream stored directly in
+    /// the page dictionary.
+    pub streams: Vec<(u32, u16)>,
+
+    /// Index of the first operator that drew the block
+    pub first_op: u32,
+
+    /// Index of the last operator that drew the block
+    pub last_op: u32,
+}
+
+impl BlockSource {
+    /// Widen the range to also cover `other`, as when blocks are grouped.
+    pub(crate) fn extend(&mut self, other: &BlockSource) {
+        for stream in &other.streams {
+            if !self.streams.contains(stream) {
+                self.streams.push(*stream);
+            }
+        }
+        self.first_op = self.first_op.min(other.first_op);
+        self.last_op = self.last_op.max(other.last_op);
+    }
+}
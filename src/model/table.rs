@@ -2,6 +2,7 @@
 
 use super::{Alignment, Paragraph};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// A table structure.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +18,47 @@ pub struct Table {
 
     /// Table caption
     pub caption: Option<String>,
+
+    /// How the detector scored this table, when it was detected from text
+    /// layout. Emitted as `detection` in `JsonFormat::Detailed` output only.
+    #[serde(skip)]
+    pub detection: Option<TableDetection>,
+}
+
+/// How the table detector judged a region: a table it kept, or one it
+/// turned down.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableDetection {
+    /// Region `[x0, y0, x1, y1]` in PDF user space, from the last row's
+    /// baseline to the first row's
+    pub bbox: [f32; 4],
+
+    /// Detection confidence (0.0 - 1.0); 0 when turned down before scoring
+    pub confidence: f32,
+
+    /// Share of each row's spans that start on a column boundary, top to
+    /// bottom
+    pub alignment: Vec<f32>,
+
+    /// Why the region was not kept as a table; `None` for kept tables
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejected: Option<TableRejection>,
+}
+
+/// Why a candidate table region was left as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableRejection {
+    /// More columns than a table plausibly has; usually words split apart
+    TooManyColumns,
+    /// A bulleted or numbered list with its markers in a column
+    ListPattern,
+    /// Page columns of running text rather than table columns
+    PageColumns,
+    /// Some column is nearly always empty
+    SparseColumns,
+    /// Confidence below the threshold for keeping a table
+    LowConfidence,
 }
 
 impl Table {
@@ -27,6 +69,7 @@ impl Table {
             header_rows: 0,
             column_widths: None,
             caption: None,
+            detection: None,
         }
     }
 
@@ -77,6 +120,93 @@ impl Table {
             .join("\n")
     }
 
+    /// Convert the body rows to records keyed by the header.
+    ///
+    /// Header text becomes the keys: multi-row headers are joined with `" / "`,
+    /// blank headers become `column_N`, and repeated headers get `_2`, `_3`, ….
+    /// A table without a marked header uses its first row. Merged cells repeat
+    /// their text in every row and column they cover; cells missing from a short
+    /// row are `null`. Blank body rows are skipped.
+    pub fn to_records(&self) -> Vec<Map<String, Value>> {
+        let grid = self.text_grid();
+        if grid.is_empty() {
+            return Vec::new();
+        }
+        let header_count = (self.header_rows as usize).clamp(1, grid.len());
+        let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut keys: Vec<String> = Vec::with_capacity(width);
+        for col in 0..width {
+            let mut parts: Vec<&str> = Vec::new();
+            for row in &grid[..header_count] {
+                if let Some(text) = row.get(col).filter(|t| !t.is_empty()) {
+                    // 세로 병합된 헤더는 같은 텍스트가 반복되므로 한 번만 쓴다.
+                    if parts.last() != Some(&text.as_str()) {
+                        parts.push(text);
+                    }
+                }
+            }
+            let base = if parts.is_empty() {
+                format!("column_{}", col + 1)
+            } else {
+                parts.join(" / ")
+            };
+            let mut key = base.clone();
+            let mut n = 2;
+            while keys.contains(&key) {
+                key = format!("{}_{}", base, n);
+                n += 1;
+            }
+            keys.push(key);
+        }
+
+        grid[header_count..]
+            .iter()
+            .filter(|row| row.iter().any(|t| !t.is_empty()))
+            .map(|row| {
+                keys.iter()
+                    .enumerate()
+                    .map(|(col, key)| {
+                        let value = row
+                            .get(col)
+                            .map_or(Value::Null, |t| Value::String(t.clone()));
+                        (key.clone(), value)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Cell text laid out on the table's column grid, with merged cells
+    /// copied into every slot they cover.
+    pub(crate) fn text_grid(&self) -> Vec<Vec<String>> {
+        let mut grid: Vec<Vec<Option<String>>> = vec![Vec::new(); self.rows.len()];
+        for (r, row) in self.rows.iter().enumerate() {
+            let mut col = 0;
+            for cell in &row.cells {
+                // 위 행의 rowspan 이 차지한 칸은 건너뛴다.
+                while grid[r].get(col).is_some_and(Option::is_some) {
+                    col += 1;
+                }
+                let text = cell.plain_text().trim().to_string();
+                let span_rows = (cell.rowspan.max(1) as usize).min(self.rows.len() - r);
+                let span_cols = cell.colspan.max(1) as usize;
+                for slots in &mut grid[r..r + span_rows] {
+                    if slots.len() < col + span_cols {
+                        slots.resize(col + span_cols, None);
+                    }
+                    for slot in &mut slots[col..col + span_cols] {
+                        *slot = Some(text.clone());
+                    }
+                }
+                col += span_cols;
+            }
+        }
+        grid.into_iter()
+            .map(|row| row.into_iter().map(Option::unwrap_or_default).collect())
+            .collect()
+    }
+
     /// Check if the table has complex structure (merged cells).
     pub fn has_merged_cells(&self) -> bool {
         self.rows
@@ -266,6 +396,50 @@ mod tests {
         assert_eq!(table.body().len(), 2);
     }
 
+    #[test]
+    fn test_to_records() {
+        let mut table = Table::with_header(1);
+        table.add_row(TableRow::header(vec![
+            TableCell::text("Name"),
+            TableCell::text("Score"),
+            TableCell::text("Score"),
+            TableCell::empty(),
+        ]));
+        table.add_row(TableRow::from_strings(["Alice", "9", "8", "x"]));
+        table.add_row(TableRow::from_strings(["", "", "", ""]));
+        table.add_row(TableRow::from_strings(["Bob", "7"]));
+
+        let records = table.to_records();
+        assert_eq!(records.len(), 2);
+        let keys: Vec<&String> = records[0].keys().collect();
+        assert_eq!(keys.len(), 4);
+        assert_eq!(records[0]["Name"], "Alice");
+        assert_eq!(records[0]["Score"], "9");
+        assert_eq!(records[0]["Score_2"], "8");
+        assert_eq!(records[0]["column_4"], "x");
+        assert_eq!(records[1]["Score_2"], Value::Null);
+    }
+
+    #[test]
+    fn test_to_records_merged_header() {
+        let mut table = Table::with_header(2);
+        table.add_row(TableRow::header(vec![
+            TableCell::text("Region").rowspan(2),
+            TableCell::text("Q1").colspan(2),
+        ]));
+        table.add_row(TableRow::header(vec![
+            TableCell::text("Jan"),
+            TableCell::text("Feb"),
+        ]));
+        table.add_row(TableRow::from_strings(["North", "10", "12"]));
+
+        let records = table.to_records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["Region"], "North");
+        assert_eq!(records[0]["Q1 / Jan"], "10");
+        assert_eq!(records[0]["Q1 / Feb"], "12");
+    }
+
     #[test]
     fn test_merged_cells() {
         let mut table = Table::new();
@@ -0,0 +1,297 @@
+//! Table of contents recovered from printed contents pages.
+//!
+//! Many PDFs carry no bookmarks (`/Outlines`) but still print a contents
+//! page: titles followed by dot leaders and page numbers. This module finds
+//! such pages near the front of a document, parses their entries into an
+//! [`Outline`], and uses the entries to give the matching paragraphs further
+//! into the document a heading level.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use super::{Block, Document, Outline, OutlineItem};
+
+/// Contents pages are only looked for among the first pages.
+const MAX_SCAN_PAGES: usize = 20;
+
+/// Leader-terminated entries a page needs to count as a contents page.
+const MIN_ENTRIES_PER_PAGE: usize = 3;
+
+/// Paragraphs longer than this are body text, never a heading.
+const MAX_HEADING_CHARS: usize = 200;
+
+/// A contents listing recovered from the document body.
+#[derive(Debug, Clone)]
+pub struct DetectedToc {
+    /// Physical pages (1-indexed) the listing is printed on
+    pub pages: Vec<u32>,
+    /// The entries, nested by their numbering. Page numbers are physical:
+    /// the offset between printed and physical numbering is worked out by
+    /// finding an entry's title later in the document.
+    pub outline: Outline,
+}
+
+#[derive(Debug, Clone)]
+struct TocEntry {
+    title: String,
+    printed_page: u32,
+    level: u8,
+}
+
+impl Document {
+    /// Find a printed table of contents.
+    ///
+    /// A page qualifies when at least three of its entries end in a leader
+    /// (dots, middle dots, an ellipsis or underscores) and a page number;
+    /// consecutive qualifying pages form one listing. Entry levels follow
+    /// their numbering (`2` → 0, `2.1` → 1); unnumbered entries are top
+    /// level. Returns `None` when no contents page is found.
+    pub fn detect_toc(&self) -> Option<DetectedToc> {
+        let mut pages = Vec::new();
+        let mut entries = Vec::new();
+        for page in self.pages.iter().take(MAX_SCAN_PAGES) {
+            let found = parse_entries(&page.plain_text());
+            if found.len() >= MIN_ENTRIES_PER_PAGE {
+                pages.push(page.number);
+                entries.extend(found);
+            } else if !pages.is_empty() {
+                break;
+            }
+        }
+        if entries.is_empty() {
+            return None;
+        }
+
+        let base = entries.iter().map(|e| e.level).min().unwrap_or(0);
+        for entry in &mut entries {
+            entry.level -= base;
+        }
+        let last_toc_page = *pages.last()?;
+        let offset = self.page_offset(&entries, last_toc_page);
+        let page_count = self.page_count();
+        let items = entries.into_iter().map(|entry| {
+            let page = i64::from(entry.printed_page) + offset;
+            let page = u32::try_from(page)
+                .ok()
+                .filter(|p| (1..=page_count).contains(p));
+            OutlineItem::new(entry.title, page, entry.level)
+        });
+        Some(DetectedToc {
+            pages,
+            outline: nest(items),
+        })
+    }
+
+    /// Turn paragraphs that repeat a contents entry into headings.
+    ///
+    /// Only pages after the listing are touched. A paragraph matches when its
+    /// text equals an entry title (ignoring case and whitespace) and, if the
+    /// entry's page is known, it sits within one page of it. Each entry is
+    /// used at most once; its level becomes the heading level (`0` → H1).
+    /// Returns the number of paragraphs changed.
+    pub fn apply_toc_headings(&mut self, toc: &DetectedToc) -> usize {
+        let mut remaining = Vec::new();
+        flatten(&toc.outline.items, &mut remaining);
+        let after = toc.pages.iter().copied().max().unwrap_or(0);
+        let mut changed = 0;
+        for page in self.pages.iter_mut().filter(|p| p.number > after) {
+            for block in &mut page.elements {
+                let Block::Paragraph(para) = block else {
+                    continue;
+                };
+                let text = normalize(&para.plain_text());
+                if text.is_empty() || text.chars().count() > MAX_HEADING_CHARS {
+                    continue;
+                }
+                let hit = remaining.iter().position(|(title, target, _)| {
+                    *title == text && target.map_or(true, |t: u32| t.abs_diff(page.number) <= 1)
+                });
+                if let Some(index) = hit {
+                    let (_, _, level) = remaining.remove(index);
+                    para.style.heading_level = Some((level + 1).min(6));
+                    changed += 1;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Physical minus printed page number, from the first entry whose title
+    /// reappears as a paragraph after the listing (0 when none does).
+    fn page_offset(&self, entries: &[TocEntry], after: u32) -> i64 {
+        for entry in entries {
+            let title = normalize(&entry.title);
+            let found = self.pages.iter().filter(|p| p.number > after).find(|p| {
+                p.elements.iter().any(|block| match block {
+                    Block::Paragraph(para) => normalize(&para.plain_text()) == title,
+                    _ => false,
+                })
+            });
+            if let Some(page) = found {
+                return i64::from(page.number) - i64::from(entry.printed_page);
+            }
+        }
+        0
+    }
+}
+
+/// Leader-terminated entries in a page's text. Lines may have been joined
+/// with spaces, so entries are split on the page numbers that end them.
+fn parse_entries(text: &str) -> Vec<TocEntry> {
+    static ENTRY: OnceLock<Regex> = OnceLock::new();
+    static CAPTION: OnceLock<Regex> = OnceLock::new();
+    let entry = ENTRY.get_or_init(|| {
+        // 제목, 점·가운뎃점·말줄임표·밑줄 리더, 쪽 번호.
+        Regex::new(concat!(
+            r"(?P<title>[^\n]*?\S)\s*",
+            r"(?:(?:\.\s?){3,}|(?:·\s?){3,}|…+|_{3,})\s*(?P<page>\d{1,4})\b",
+        ))
+        .unwrap()
+    });
+    let caption = CAPTION.get_or_init(|| {
+        Regex::new(r"^(?i)(?:table\s+of\s+contents|contents|목\s*차|차\s*례)\s+").unwrap()
+    });
+
+    let mut entries = Vec::new();
+    for cap in entry.captures_iter(text) {
+        let mut title = cap["title"].trim();
+        if entries.is_empty() {
+            title = caption
+                .find(title)
+                .map_or(title, |m| title[m.end()..].trim());
+        }
+        let Ok(printed_page) = cap["page"].parse() else {
+            continue;
+        };
+        if title.is_empty() || !title.chars().any(char::is_alphabetic) {
+            continue;
+        }
+        entries.push(TocEntry {
+            title: title.to_string(),
+            printed_page,
+            level: numbering_level(title),
+        });
+    }
+    entries
+}
+
+/// Nesting level implied by a leading section number: `3` or `3.` → 0,
+/// `3.1` → 1, `3.1.4` → 2. Unnumbered titles are top level.
+fn numbering_level(title: &str) -> u8 {
+    let number = title.split_whitespace().next().unwrap_or("");
+    let number = number.strip_suffix('.').unwrap_or(number);
+    let parts: Vec<&str> = number.split('.').collect();
+    if parts
+        .iter()
+        .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+    {
+        (parts.len() - 1).min(5) as u8
+    } else {
+        0
+    }
+}
+
+/// Build the outline tree from entries in reading order.
+fn nest(items: impl Iterator<Item = OutlineItem>) -> Outline {
+    fn attach(siblings: &mut Vec<OutlineItem>, item: OutlineItem) {
+        match siblings.last_mut() {
+            Some(last) if item.level > last.level => attach(&mut last.children, item),
+            _ => siblings.push(item),
+        }
+    }
+    let mut outline = Outline::new();
+    for item in items {
+        attach(&mut outline.items, item);
+    }
+    outline
+}
+
+fn flatten(items: &[OutlineItem], out: &mut Vec<(String, Option<u32>, u8)>) {
+    for item in items {
+        out.push((normalize(&item.title), item.page, item.level));
+        flatten(&item.children, out);
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Page, Paragraph};
+
+    fn page_with(number: u32, paragraphs: &[&str]) -> Page {
+        let mut page = Page::a4(number);
+        for text in paragraphs {
+            page.add_paragraph(Paragraph::with_text(*text));
+        }
+        page
+    }
+
+    #[test]
+    fn test_parse_joined_entries() {
+        let entries = parse_entries(
+            "Table of Contents 1 Introduction ........ 1 1.1 Scope . . . . 2 Appendix A … 9",
+        );
+        let parsed: Vec<(&str, u32, u8)> = entries
+            .iter()
+            .map(|e| (e.title.as_str(), e.printed_page, e.level))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                ("1 Introduction", 1, 0),
+                ("1.1 Scope", 2, 1),
+                ("Appendix A", 9, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_and_apply() {
+        let mut doc = Document::new();
+        doc.add_page(page_with(
+            1,
+            &["Contents 1 Intro .... 1 1.1 Goals .... 1 2 Results .... 2"],
+        ));
+        doc.add_page(page_with(
+            2,
+            &["1 Intro", "Body.", "1.1 Goals", "More body."],
+        ));
+        doc.add_page(page_with(3, &["2 Results", "Numbers."]));
+
+        let toc = doc.detect_toc().unwrap();
+        assert_eq!(toc.pages, vec![1]);
+        assert_eq!(toc.outline.items.len(), 2);
+        assert_eq!(toc.outline.items[0].page, Some(2));
+        assert_eq!(toc.outline.items[0].children[0].title, "1.1 Goals");
+        assert_eq!(toc.outline.items[1].page, Some(3));
+
+        assert_eq!(doc.apply_toc_headings(&toc), 3);
+        let levels: Vec<Option<u8>> = doc.pages[1..]
+            .iter()
+            .flat_map(|p| &p.elements)
+            .map(|b| match b {
+                Block::Paragraph(p) => p.heading_level(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(levels, vec![Some(1), None, Some(2), None, Some(1), None]);
+    }
+
+    #[test]
+    fn test_no_toc_without_leaders() {
+        let mut doc = Document::new();
+        doc.add_page(page_with(
+            1,
+            &["Chapter 1 starts on page 3 and chapter 2 on page 9."],
+        ));
+        assert!(doc.detect_toc().is_none());
+    }
+}
@@ -0,0 +1,21 @@
+//! Word-level geometry for search highlighting.
+
+use serde::{Deserialize, Serialize};
+
+/// A word on the page and the box it occupies.
+///
+/// Boxes come from glyph advances where the font carries widths, and are
+/// spread evenly over the text's width otherwise. The vertical extent is the
+/// font's nominal body, from 20% of the size below the baseline to 80% above.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Word {
+    /// The word's text, without surrounding whitespace
+    pub text: String,
+
+    /// Bounding box `[x0, y0, x1, y1]` in PDF user space
+    pub bbox: [f32; 4],
+
+    /// Index of the word's line on the page, in layout order. Words with the
+    /// same index were set on one line.
+    pub line: u32,
+}
@@ -0,0 +1,153 @@
+//! Annotation extraction: sticky notes and text markup with the text they
+//! cover.
+//!
+//! Markup annotations mark regions with `/QuadPoints`, not text. The covered
+//! text is recovered by intersecting those regions with the page's decoded
+//! spans, character by character (assuming uniform glyph widths inside a
+//! span, or half the font size when the span has no width), so a highlight
+//! over half a line yields just that half.
+
+use super::backend::RawAnnotation;
+use super::layout::TextSpan;
+use super::pdf_parser::parse_pdf_date_pub;
+use crate::model::{Annotation, AnnotationKind};
+
+/// Share of the font size below the baseline that still belongs to a
+/// glyph's box (descenders).
+const DESCENT: f32 = 0.2;
+
+/// Share of the font size above the baseline (ascenders and caps).
+const ASCENT: f32 = 0.8;
+
+/// Keep the supported annotations. `spans` provides the covered text for
+/// markup annotations; pass an empty slice to skip that.
+pub(crate) fn convert_annotations(raw: Vec<RawAnnotation>, spans: &[TextSpan]) -> Vec<Annotation> {
+    raw.into_iter().filter_map(|a| convert(a, spans)).collect()
+}
+
+/// Whether any of the raw annotations marks up text (and so needs spans).
+pub(crate) fn has_markup(raw: &[RawAnnotation]) -> bool {
+    raw.iter()
+        .filter_map(|a| AnnotationKind::from_subtype(&a.subtype))
+        .any(|kind| kind.is_markup())
+}
+
+fn convert(raw: RawAnnotation, spans: &[TextSpan]) -> Option<Annotation> {
+    let kind = AnnotationKind::from_subtype(&raw.subtype)?;
+    let covered_text = if kind.is_markup() {
+        covered_text(spans, &quads(&raw))
+    } else {
+        None
+    };
+    Some(Annotation {
+        kind,
+        author: raw.author.filter(|a| !a.trim().is_empty()),
+        modified: raw.modified.as_deref().and_then(parse_pdf_date_pub),
+        contents: raw.contents.filter(|c| !c.trim().is_empty()),
+        covered_text,
+        rect: raw.rect,
+    })
+}
+
+/// Marked regions as `[x0, y0, x1, y1]` boxes. Falls back to `/Rect` when
+/// `/QuadPoints` is missing or malformed.
+fn quads(raw: &RawAnnotation) -> Vec<[f32; 4]> {
+    let boxes: Vec<[f32; 4]> = raw
+        .quad_points
+        .chunks_exact(8)
+        .map(|q| {
+            let xs = [q[0], q[2], q[4], q[6]];
+            let ys = [q[1], q[3], q[5], q[7]];
+            [
+                xs.iter().copied().fold(f32::INFINITY, f32::min),
+                ys.iter().copied().fold(f32::INFINITY, f32::min),
+                xs.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+                ys.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+            ]
+        })
+        .collect();
+    if boxes.is_empty() {
+        vec![raw.rect]
+    } else {
+        boxes
+    }
+}
+
+/// Characters whose centre lies inside one of `regions`, region by region.
+fn covered_text(spans: &[TextSpan], regions: &[[f32; 4]]) -> Option<String> {
+    let mut pieces = Vec::new();
+    for &[x0, y0, x1, y1] in regions {
+        let mut piece = String::new();
+        for span in spans {
+            let middle = span.y + span.font_size * (ASCENT - DESCENT) / 2.0;
+            if middle < y0 || middle > y1 {
+                continue;
+            }
+            let chars: Vec<char> = span.text.chars().collect();
+            let advance = if span.width > 0.0 {
+                span.width / chars.len().max(1) as f32
+            } else {
+                span.font_size * 0.5 // 폭 정보가 없는 폰트: 글자 크기의 절반으로 가정
+            };
+            for (i, c) in chars.iter().enumerate() {
+                let centre = span.x + advance * (i as f32 + 0.5);
+                if centre >= x0 && centre <= x1 {
+                    piece.push(*c);
+                }
+            }
+            piece.push(' ');
+        }
+        pieces.push(piece);
+    }
+    let text = pieces
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str, x: f32, y: f32, width: f32) -> TextSpan {
+        let mut span = TextSpan::new(text.to_string(), x, y, 10.0, "Helvetica".to_string());
+        span.width = width;
+        span
+    }
+
+    #[test]
+    fn test_covered_text_clips_to_region() {
+        let spans = vec![
+            span("Revenue grew twelve", 100.0, 700.0, 190.0),
+            span("percent last year.", 100.0, 686.0, 180.0),
+        ];
+        // "grew" = chars 8..12 → x 180..220 on the first line.
+        let text = covered_text(&spans, &[[180.0, 698.0, 220.0, 710.0]]);
+        assert_eq!(text.as_deref(), Some("grew"));
+
+        let both_lines = covered_text(&spans, &[[100.0, 684.0, 400.0, 710.0]]);
+        assert_eq!(
+            both_lines.as_deref(),
+            Some("Revenue grew twelve percent last year.")
+        );
+        assert_eq!(covered_text(&spans, &[[0.0, 0.0, 50.0, 50.0]]), None);
+    }
+
+    #[test]
+    fn test_quads_fall_back_to_rect() {
+        let raw = RawAnnotation {
+            subtype: "Highlight".to_string(),
+            rect: [1.0, 2.0, 3.0, 4.0],
+            quad_points: vec![10.0, 20.0, 30.0, 20.0, 10.0, 5.0, 30.0, 5.0],
+            ..Default::default()
+        };
+        assert_eq!(quads(&raw), vec![[10.0, 5.0, 30.0, 20.0]]);
+        let raw = RawAnnotation {
+            quad_points: Vec::new(),
+            ..raw
+        };
+        assert_eq!(quads(&raw), vec![[1.0, 2.0, 3.0, 4.0]]);
+    }
+}
@@ -4,14 +4,19 @@
 //! the concrete PDF parser from the layout analysis logic.
 
 use std::collections::{BTreeMap, HashMap};
-use std::sync::RwLock;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, RwLock};
 
 use crate::error::{Error, Result};
-use crate::model::{FieldType, FieldValue, FormField};
+use crate::model::{EncryptionKind, FieldType, FieldValue, FormField, Revision};
+
+pub use super::raw::PageTreeDamage;
 
 use super::encoding::{build_encoding_map, decode_with_encoding_map, BaseEncoding};
+pub use super::font::FontMetrics;
 use super::font::{
-    is_likely_binary, parse_to_unicode_cmap, parse_truetype_cmap_table, ToUnicodeMap,
+    is_likely_binary, parse_cid_widths, parse_to_unicode_cmap, parse_truetype_cmap_table,
+    ToUnicodeMap, WidthItem,
 };
 
 /// Page identifier: (object number, generation number).
@@ -44,6 +49,39 @@ pub struct ContentOp {
     pub operands: Vec<PdfValue>,
 }
 
+/// A page's decoded content operations and the streams they came from.
+#[derive(Debug, Clone, Default)]
+pub struct PageOperations {
+    /// Operations of all the page's content streams, in drawing order.
+    pub ops: Vec<ContentOp>,
+    /// Indirect content stream objects in drawing order, each with the index
+    /// in `ops` of its first operation. Empty when not known.
+    pub streams: Vec<(PageId, usize)>,
+    /// Bytes of the content streams skipped as unparseable.
+    pub skipped_bytes: usize,
+}
+
+/// Structural damage found in the file itself, independent of any page.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StructureReport {
+    /// Cross-reference entries that do not point at a parsable object.
+    pub broken_xref_entries: usize,
+    /// Stream filters the backend cannot decode, with the number of streams
+    /// using each.
+    pub unsupported_filters: BTreeMap<String, usize>,
+    /// Cycles, unreadable kids and `/Count` mismatches in the page tree.
+    pub page_tree: PageTreeDamage,
+}
+
+impl StructureReport {
+    /// Whether no damage was found.
+    pub fn is_clean(&self) -> bool {
+        self.broken_xref_entries == 0
+            && self.unsupported_filters.is_empty()
+            && self.page_tree.is_clean()
+    }
+}
+
 /// Raw metadata from the PDF backend.
 #[derive(Debug, Clone, Default)]
 pub struct PdfMetadataRaw {
@@ -56,7 +94,24 @@ pub struct PdfMetadataRaw {
     pub producer: Option<String>,
     pub creation_date: Option<String>,
     pub mod_date: Option<String>,
+    pub language: Option<String>,
     pub encrypted: bool,
+    pub encryption: Option<EncryptionKind>,
+    /// The permissions forbid copying text and graphics.
+    pub copy_forbidden: bool,
+    pub has_xfa: bool,
+}
+
+impl PdfMetadataRaw {
+    /// Fail with [`Error::ExtractionForbidden`] when `respect` is set and the
+    /// permissions forbid copying. Every extraction entry point calls this
+    /// before reading content.
+    pub fn check_extraction_permitted(&self, respect: bool) -> Result<()> {
+        if respect && self.copy_forbidden {
+            return Err(Error::ExtractionForbidden);
+        }
+        Ok(())
+    }
 }
 
 /// A raw outline (bookmark) item from the PDF.
@@ -81,6 +136,101 @@ pub struct RawXObject {
     pub color_space: Option<String>,
 }
 
+/// Bounds on decoding a page's images, for
+/// [`PdfBackend::page_xobjects_bounded`]. Clones share one memory ceiling.
+#[derive(Debug, Clone, Default)]
+pub struct ImageLimits {
+    /// Images decoded at once; 0 or 1 decodes them one after another.
+    pub concurrency: usize,
+    /// Decoded image bytes allowed across the document, shared by all pages.
+    pub ceiling: Option<Arc<MemoryCeiling>>,
+}
+
+impl ImageLimits {
+    /// Limits decoding `concurrency` images at once and at most `max_bytes`
+    /// of image data in total.
+    pub fn new(concurrency: usize, max_bytes: Option<usize>) -> Self {
+        Self {
+            concurrency,
+            ceiling: max_bytes.map(|limit| Arc::new(MemoryCeiling::new(limit))),
+        }
+    }
+
+    /// Whether the memory ceiling has been reached, so no more images should
+    /// be decoded.
+    pub fn exhausted(&self) -> bool {
+        self.ceiling.as_ref().is_some_and(|c| c.is_reached())
+    }
+
+    /// Count `bytes` of decoded image data against the ceiling.
+    pub fn charge(&self, bytes: usize) {
+        if let Some(ceiling) = &self.ceiling {
+            ceiling.charge(bytes);
+        }
+    }
+}
+
+/// A running total of decoded bytes against a fixed limit, safe to share
+/// between the threads parsing pages.
+#[derive(Debug)]
+pub struct MemoryCeiling {
+    limit: usize,
+    used: AtomicUsize,
+}
+
+impl MemoryCeiling {
+    /// A ceiling of `limit` bytes, none used yet.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            used: AtomicUsize::new(0),
+        }
+    }
+
+    /// The limit in bytes.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Bytes counted so far.
+    pub fn used(&self) -> usize {
+        self.used.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Whether the bytes counted so far reach the limit.
+    pub fn is_reached(&self) -> bool {
+        self.used() >= self.limit
+    }
+
+    /// Count `bytes` more, warning once when this crosses the limit.
+    pub fn charge(&self, bytes: usize) {
+        let before = self.used.fetch_add(bytes, AtomicOrdering::Relaxed);
+        if before < self.limit && before + bytes >= self.limit {
+            log::warn!(
+                "image memory ceiling of {} bytes reached; remaining images are skipped",
+                self.limit
+            );
+        }
+    }
+}
+
+/// A raw annotation dictionary from a page's `/Annots` array.
+#[derive(Debug, Clone, Default)]
+pub struct RawAnnotation {
+    /// `/Subtype` (e.g. `Text`, `Highlight`).
+    pub subtype: String,
+    /// `/T` — the author, by convention.
+    pub author: Option<String>,
+    /// `/Contents` — the comment text.
+    pub contents: Option<String>,
+    /// `/M` — modification date as a PDF date string.
+    pub modified: Option<String>,
+    /// `/Rect` as `[x0, y0, x1, y1]`.
+    pub rect: [f32; 4],
+    /// `/QuadPoints`, eight numbers per marked region.
+    pub quad_points: Vec<f32>,
+}
+
 /// Abstract interface for PDF document access.
 ///
 /// Implementations provide page enumeration, font info, content stream
@@ -89,6 +239,12 @@ pub trait PdfBackend: Send + Sync {
     /// Return all pages as (page_number → PageId).
     fn pages(&self) -> BTreeMap<u32, PageId>;
 
+    /// Pages as [`pages`](PdfBackend::pages) does, with the damage found in
+    /// the page tree. The default reports none.
+    fn page_tree(&self) -> (BTreeMap<u32, PageId>, PageTreeDamage) {
+        (self.pages(), PageTreeDamage::default())
+    }
+
     /// Return font info for a given page.
     fn page_fonts(&self, page: PageId) -> Result<Vec<BackendFontInfo>>;
 
@@ -98,6 +254,18 @@ pub trait PdfBackend: Send + Sync {
     /// Parse raw content stream bytes into a sequence of operations.
     fn decode_content(&self, data: &[u8]) -> Result<Vec<ContentOp>>;
 
+    /// Decode a page's content into operations, noting which content stream
+    /// object each came from. The default decodes `page_content` and
+    /// records no streams.
+    fn page_operations(&self, page: PageId) -> Result<PageOperations> {
+        let ops = self.decode_content(&self.page_content(page)?)?;
+        Ok(PageOperations {
+            ops,
+            streams: Vec::new(),
+            skipped_bytes: 0,
+        })
+    }
+
     /// Decode a text byte sequence using the font's encoding on the given page.
     /// Falls back to simple decoding if the font or encoding is unavailable.
     fn decode_text(&self, page: PageId, font_name: &[u8], bytes: &[u8]) -> String;
@@ -116,10 +284,69 @@ pub trait PdfBackend: Send + Sync {
     /// Return XObjects (images) from a page.
     fn page_xobjects(&self, page: PageId) -> Result<Vec<RawXObject>>;
 
+    /// [`page_xobjects`](Self::page_xobjects) within `limits`: images past
+    /// the memory ceiling are left out. The default decodes every image and
+    /// then drops those past the ceiling; backends that can decode images
+    /// one at a time override it to decode several at once and to stop early.
+    fn page_xobjects_bounded(&self, page: PageId, limits: &ImageLimits) -> Result<Vec<RawXObject>> {
+        let mut xobjects = self.page_xobjects(page)?;
+        let keep = xobjects
+            .iter()
+            .take_while(|x| {
+                let fits = !limits.exhausted();
+                if fits {
+                    limits.charge(x.data.len());
+                }
+                fits
+            })
+            .count();
+        xobjects.truncate(keep);
+        Ok(xobjects)
+    }
+
     /// Extract AcroForm fields from the document.
     fn acroform_fields(&self) -> Vec<FormField> {
         vec![]
     }
+
+    /// The XML `datasets` packet of an XFA form: the data filled into it.
+    fn xfa_datasets(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Return the annotations listed in a page's `/Annots` array.
+    fn page_annotations(&self, _page: PageId) -> Vec<RawAnnotation> {
+        vec![]
+    }
+
+    /// SHA-256 of the source bytes (lowercase hex), if known.
+    fn source_sha256(&self) -> Option<String> {
+        None
+    }
+
+    /// The parsed document underneath, for backends built on
+    /// [`RawDocument`].
+    fn raw_document(&self) -> Option<&RawDocument> {
+        None
+    }
+
+    /// Incremental-update revisions up to the loaded one, oldest first.
+    /// Empty when the file has a single revision.
+    fn revisions(&self) -> Vec<Revision> {
+        vec![]
+    }
+
+    /// Structural damage found while loading the file. The default reports
+    /// none.
+    fn structure_report(&self) -> StructureReport {
+        StructureReport::default()
+    }
+
+    /// Return glyph advance widths for a font on the given page.
+    /// `None` when the font carries no usable width information.
+    fn font_metrics(&self, _page: PageId, _font_name: &[u8]) -> Option<Arc<FontMetrics>> {
+        None
+    }
 }
 
 // Re-export decode_text_simple as pub for external consumers.
@@ -142,13 +369,17 @@ use super::raw::content as raw_content;
 use super::raw::stream as raw_stream;
 use super::raw::tokenizer::{
     dict_get as raw_dict_get, PdfDict as RawPdfDict, PdfObject as RawPdfObject,
+    PdfStream as RawPdfStream,
 };
 use super::raw::RawDocument;
+use super::revision::{describe_revisions, revision_slice};
 
 /// Concrete [`PdfBackend`] backed by the custom `RawDocument` parser.
 pub struct RawBackend {
     doc: RawDocument,
     font_resolver: RawFontResolver,
+    source_sha256: Option<String>,
+    revisions: Vec<Revision>,
 }
 
 impl RawBackend {
@@ -160,13 +391,90 @@ impl RawBackend {
 
     /// Load from an in-memory byte slice.
     pub fn load_bytes(data: &[u8]) -> Result<Self> {
-        let doc = RawDocument::load(data)?;
+        Self::load_revision(data, None)
+    }
+
+    /// Load revision `revision` (1-based, oldest first) of an incrementally
+    /// updated file; `None` loads the latest.
+    pub fn load_revision(data: &[u8], revision: Option<u32>) -> Result<Self> {
+        Self::load_with_passwords::<&str>(data, revision, &[])
+    }
+
+    /// [`RawBackend::load_revision`], trying `passwords` on an encrypted
+    /// document; see [`RawDocument::load_with_passwords`].
+    pub fn load_with_passwords<P: AsRef<[u8]>>(
+        data: &[u8],
+        revision: Option<u32>,
+        passwords: &[P],
+    ) -> Result<Self> {
+        let (bytes, tables) = revision_slice(data, revision)?;
+        let doc = RawDocument::load_with_passwords(bytes, passwords)?;
+        let revisions = describe_revisions(&doc, &tables);
         Ok(Self {
             doc,
             font_resolver: RawFontResolver::new(),
+            source_sha256: None,
+            revisions,
         })
     }
 
+    /// Load revision `revision` of `data` without parsing every object up
+    /// front; see [`RawDocument::load_lazy`]. The backend keeps `data`.
+    pub fn load_lazy(data: Vec<u8>, revision: Option<u32>) -> Result<Self> {
+        Self::load_lazy_with_passwords::<&str>(data, revision, &[])
+    }
+
+    /// [`RawBackend::load_lazy`], trying `passwords` on an encrypted
+    /// document; see [`RawDocument::load_with_passwords`].
+    pub fn load_lazy_with_passwords<P: AsRef<[u8]>>(
+        mut data: Vec<u8>,
+        revision: Option<u32>,
+        passwords: &[P],
+    ) -> Result<Self> {
+        let (len, tables) =
+            revision_slice(&data, revision).map(|(bytes, tables)| (bytes.len(), tables))?;
+        data.truncate(len);
+        let doc = RawDocument::load_lazy_with_passwords(data, passwords)?;
+        let revisions = describe_revisions(&doc, &tables);
+        Ok(Self {
+            doc,
+            font_resolver: RawFontResolver::new(),
+            source_sha256: None,
+            revisions,
+        })
+    }
+
+    /// Load from a seekable reader, fetching objects as they are read; see
+    /// [`RawDocument::load_seekable`]. There is no source hash and no
+    /// revision history, which would need the whole file.
+    pub fn load_seekable<R, P>(reader: R, passwords: &[P]) -> Result<Self>
+    where
+        R: super::raw::ReadSeek + 'static,
+        P: AsRef<[u8]>,
+    {
+        Ok(Self::from_document(RawDocument::load_seekable(
+            reader, passwords,
+        )?))
+    }
+
+    /// Record `hash` as the SHA-256 of the source bytes. The loaders do not
+    /// hash the input themselves, since that is a full pass over it.
+    pub fn with_source_sha256(mut self, hash: Option<String>) -> Self {
+        self.source_sha256 = hash;
+        self
+    }
+
+    /// Wrap a document that is already loaded. The source bytes are not
+    /// known, so there is no source hash and no revision history.
+    pub fn from_document(doc: RawDocument) -> Self {
+        Self {
+            doc,
+            font_resolver: RawFontResolver::new(),
+            source_sha256: None,
+            revisions: Vec::new(),
+        }
+    }
+
     /// Load from a reader.
     pub fn load_reader<R: std::io::Read>(mut reader: R) -> Result<Self> {
         let mut data = Vec::new();
@@ -185,62 +493,47 @@ impl PdfBackend for RawBackend {
         self.doc.pages()
     }
 
+    fn page_tree(&self) -> (BTreeMap<u32, PageId>, PageTreeDamage) {
+        self.doc.page_tree()
+    }
+
     fn page_fonts(&self, page: PageId) -> Result<Vec<BackendFontInfo>> {
         self.font_resolver.page_fonts(&self.doc, page)
     }
 
     fn page_content(&self, page_id: PageId) -> Result<Vec<u8>> {
-        let page_dict = self
-            .doc
-            .get_dict(page_id)
-            .map_err(|e| Error::PdfParse(e.to_string()))?;
-
-        let contents = raw_dict_get(page_dict, b"Contents")
-            .ok_or_else(|| Error::PdfParse("No Contents in page".to_string()))?;
-
-        let contents = self.doc.resolve(contents);
+        let mut parts = self.content_streams(page_id)?;
+        if parts.len() == 1 {
+            return Ok(parts.pop().map(|(_, data)| data).unwrap_or_default());
+        }
+        let mut content = Vec::new();
+        for (_, data) in parts {
+            content.extend_from_slice(&data);
+            content.push(b' ');
+        }
+        Ok(content)
+    }
 
-        match contents {
-            RawPdfObject::Reference(n, g) => {
-                let obj = self
-                    .doc
-                    .get_object((*n, *g))
-                    .ok_or_else(|| Error::PdfParse("Content stream not found".to_string()))?;
-                let resolved = self.doc.resolve(obj);
-                if let Some(stream) = resolved.as_stream() {
-                    return raw_stream::decompress(stream);
-                }
-                Err(Error::PdfParse("Invalid content stream".to_string()))
-            }
-            RawPdfObject::Stream(stream) => raw_stream::decompress(stream),
-            RawPdfObject::Array(arr) => {
-                let mut content = Vec::new();
-                for item in arr {
-                    let resolved = self.doc.resolve(item);
-                    let stream_obj = match resolved {
-                        RawPdfObject::Stream(s) => s,
-                        RawPdfObject::Reference(n, g) => {
-                            if let Some(obj) = self.doc.get_object((*n, *g)) {
-                                let obj = self.doc.resolve(obj);
-                                match obj.as_stream() {
-                                    Some(s) => s,
-                                    None => continue,
-                                }
-                            } else {
-                                continue;
-                            }
-                        }
-                        _ => continue,
-                    };
-                    if let Ok(data) = raw_stream::decompress(stream_obj) {
-                        content.extend_from_slice(&data);
-                        content.push(b' ');
-                    }
-                }
-                Ok(content)
-            }
-            _ => Err(Error::PdfParse("Invalid content stream".to_string())),
+    fn page_operations(&self, page_id: PageId) -> Result<PageOperations> {
+        let parts = self.content_streams(page_id)?;
+        let mut content = Vec::new();
+        let mut starts = Vec::with_capacity(parts.len());
+        for (id, data) in parts {
+            starts.push((id, content.len()));
+            content.extend_from_slice(&data);
+            content.push(b' ');
         }
+        let decoded = raw_content::decode_content_stream(&content);
+        let offsets = decoded.offsets;
+        let streams = starts
+            .into_iter()
+            .filter_map(|(id, byte)| Some((id?, offsets.partition_point(|&o| o < byte))))
+            .collect();
+        Ok(PageOperations {
+            ops: decoded.ops,
+            streams,
+            skipped_bytes: decoded.skipped_bytes,
+        })
     }
 
     fn decode_content(&self, data: &[u8]) -> Result<Vec<ContentOp>> {
@@ -257,6 +550,14 @@ impl PdfBackend for RawBackend {
         let mut meta = PdfMetadataRaw {
             version: self.doc.version.clone(),
             encrypted: self.doc.is_encrypted(),
+            encryption: self.doc.is_encrypted().then(|| {
+                if self.doc.requires_user_password() {
+                    EncryptionKind::UserPassword
+                } else {
+                    EncryptionKind::OwnerPasswordOnly
+                }
+            }),
+            copy_forbidden: !self.doc.permits_copying(),
             ..Default::default()
         };
 
@@ -274,6 +575,12 @@ impl PdfBackend for RawBackend {
                 }
             }
         }
+        if let Some((n, g)) = raw_dict_get(trailer, b"Root").and_then(|r| r.as_reference()) {
+            if let Ok(catalog) = self.doc.get_dict((n, g)) {
+                meta.language = raw_get_string(&self.doc, catalog, b"Lang");
+            }
+        }
+        meta.has_xfa = self.xfa().is_some();
 
         meta
     }
@@ -318,126 +625,199 @@ impl PdfBackend for RawBackend {
     }
 
     fn page_xobjects(&self, page: PageId) -> Result<Vec<RawXObject>> {
-        let mut xobjects = Vec::new();
-
-        let page_dict = self
-            .doc
-            .get_dict(page)
-            .map_err(|e| Error::PdfParse(e.to_string()))?;
+        Ok(self
+            .page_image_streams(page)?
+            .into_iter()
+            .map(|(name, stream)| decode_image_xobject(name, stream))
+            .collect())
+    }
 
-        let resources = match raw_dict_get(page_dict, b"Resources") {
-            Some(r) => r,
-            None => return Ok(xobjects),
+    fn page_xobjects_bounded(&self, page: PageId, limits: &ImageLimits) -> Result<Vec<RawXObject>> {
+        let streams = self.page_image_streams(page)?;
+        // 한도에 닿으면 아직 디코딩하지 않은 이미지는 건너뛴다.
+        let decode = |&(name, stream): &(&[u8], &RawPdfStream)| {
+            if limits.exhausted() {
+                return None;
+            }
+            let xobject = decode_image_xobject(name, stream);
+            limits.charge(xobject.data.len());
+            Some(xobject)
         };
+        let mut xobjects = Vec::with_capacity(streams.len());
+        for group in streams.chunks(limits.concurrency.max(1)) {
+            #[cfg(not(target_arch = "wasm32"))]
+            let decoded: Vec<Option<RawXObject>> = if group.len() > 1 {
+                use rayon::prelude::*;
+                group.par_iter().map(decode).collect()
+            } else {
+                group.iter().map(decode).collect()
+            };
+            #[cfg(target_arch = "wasm32")]
+            let decoded: Vec<Option<RawXObject>> = group.iter().map(decode).collect();
+            xobjects.extend(decoded.into_iter().flatten());
+        }
+        Ok(xobjects)
+    }
 
-        let res_dict = raw_resolve_dict(&self.doc, resources);
-        let res_dict = match res_dict {
-            Some(d) => d,
-            None => return Ok(xobjects),
-        };
+    fn acroform_fields(&self) -> Vec<FormField> {
+        self.extract_acroform_fields()
+    }
 
-        let xobj_entry = match raw_dict_get(res_dict, b"XObject") {
-            Some(x) => x,
-            None => return Ok(xobjects),
-        };
+    fn xfa_datasets(&self) -> Option<Vec<u8>> {
+        self.extract_xfa_datasets()
+    }
 
-        let xobj_dict = raw_resolve_dict(&self.doc, xobj_entry);
-        let xobj_dict = match xobj_dict {
-            Some(d) => d,
-            None => return Ok(xobjects),
+    fn page_annotations(&self, page: PageId) -> Vec<RawAnnotation> {
+        let Ok(page_dict) = self.doc.get_dict(page) else {
+            return vec![];
+        };
+        let Some(annots) = raw_dict_get(page_dict, b"Annots") else {
+            return vec![];
+        };
+        let Some(annots) = self.doc.resolve(annots).as_array() else {
+            return vec![];
+        };
+        let numbers = |obj: Option<&RawPdfObject>| -> Vec<f32> {
+            obj.and_then(|o| self.doc.resolve(o).as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| self.doc.resolve(v).as_f32())
+                        .collect()
+                })
+                .unwrap_or_default()
         };
 
-        for (name, obj) in xobj_dict {
-            if let Some((n, g)) = obj.as_reference() {
-                if let Some(raw_obj) = self.doc.get_object((n, g)) {
-                    let resolved = self.doc.resolve(raw_obj);
-                    if let Some(stream) = resolved.as_stream() {
-                        let dict = &stream.dict;
-
-                        let subtype = raw_dict_get(dict, b"Subtype")
-                            .and_then(|s| s.as_name())
-                            .map(|n| String::from_utf8_lossy(n).to_string())
-                            .unwrap_or_default();
-
-                        if subtype != "Image" {
-                            continue;
-                        }
+        annots
+            .iter()
+            .filter_map(|annot| raw_resolve_dict(&self.doc, annot))
+            .filter_map(|dict| {
+                let subtype = raw_dict_get(dict, b"Subtype")?.as_name()?;
+                let rect = numbers(raw_dict_get(dict, b"Rect"));
+                Some(RawAnnotation {
+                    subtype: String::from_utf8_lossy(subtype).into_owned(),
+                    author: raw_get_string(&self.doc, dict, b"T"),
+                    contents: raw_get_string(&self.doc, dict, b"Contents"),
+                    modified: raw_get_string(&self.doc, dict, b"M"),
+                    rect: match rect[..] {
+                        [x0, y0, x1, y1, ..] => [x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1)],
+                        _ => [0.0; 4],
+                    },
+                    quad_points: numbers(raw_dict_get(dict, b"QuadPoints")),
+                })
+            })
+            .collect()
+    }
 
-                        let filter = raw_dict_get(dict, b"Filter")
-                            .and_then(|f| f.as_name())
-                            .map(|n| String::from_utf8_lossy(n).to_string());
-
-                        let data = match filter.as_deref() {
-                            Some("DCTDecode") | Some("JPXDecode") => stream.raw_data.clone(),
-                            _ => raw_stream::decompress(stream)
-                                .unwrap_or_else(|_| stream.raw_data.clone()),
-                        };
-
-                        let width = raw_dict_get(dict, b"Width")
-                            .and_then(|w| w.as_i64())
-                            .map(|w| w as u32);
-                        let height = raw_dict_get(dict, b"Height")
-                            .and_then(|h| h.as_i64())
-                            .map(|h| h as u32);
-                        let bits = raw_dict_get(dict, b"BitsPerComponent")
-                            .and_then(|b| b.as_i64())
-                            .map(|b| b as u8);
-
-                        let color_space =
-                            raw_dict_get(dict, b"ColorSpace").and_then(|cs| match cs {
-                                RawPdfObject::Name(n) => {
-                                    Some(String::from_utf8_lossy(n).to_string())
-                                }
-                                RawPdfObject::Array(arr) => arr
-                                    .first()
-                                    .and_then(|o| o.as_name())
-                                    .map(|n| String::from_utf8_lossy(n).to_string()),
-                                _ => None,
-                            });
-
-                        xobjects.push(RawXObject {
-                            name: String::from_utf8_lossy(name).to_string(),
-                            subtype,
-                            data,
-                            filter,
-                            width,
-                            height,
-                            bits_per_component: bits,
-                            color_space,
-                        });
-                    }
-                }
-            }
-        }
+    fn source_sha256(&self) -> Option<String> {
+        self.source_sha256.clone()
+    }
 
-        Ok(xobjects)
+    fn raw_document(&self) -> Option<&RawDocument> {
+        Some(&self.doc)
     }
 
-    fn acroform_fields(&self) -> Vec<FormField> {
-        self.extract_acroform_fields()
+    fn revisions(&self) -> Vec<Revision> {
+        self.revisions.clone()
+    }
+
+    fn structure_report(&self) -> StructureReport {
+        StructureReport {
+            broken_xref_entries: self.doc.unreadable_entries(),
+            unsupported_filters: self.doc.unsupported_filters(),
+            page_tree: self.doc.page_tree().1,
+        }
+    }
+
+    fn font_metrics(&self, page: PageId, font_name: &[u8]) -> Option<Arc<FontMetrics>> {
+        self.font_resolver.font_metrics(&self.doc, page, font_name)
     }
 }
 
 impl RawBackend {
-    /// Extract AcroForm fields from the document.
-    fn extract_acroform_fields(&self) -> Vec<FormField> {
-        let catalog = match self.doc.catalog() {
-            Ok(c) => c,
-            Err(_) => return vec![],
+    /// A page's image XObjects, by resource name, still encoded.
+    fn page_image_streams(&self, page: PageId) -> Result<Vec<(&[u8], &RawPdfStream)>> {
+        let page_dict = self
+            .doc
+            .get_dict(page)
+            .map_err(|e| Error::PdfParse(e.to_string()))?;
+        let xobj_dict = raw_dict_get(page_dict, b"Resources")
+            .and_then(|r| raw_resolve_dict(&self.doc, r))
+            .and_then(|res| raw_dict_get(res, b"XObject"))
+            .and_then(|x| raw_resolve_dict(&self.doc, x));
+        let Some(xobj_dict) = xobj_dict else {
+            return Ok(Vec::new());
         };
 
-        let acroform = match raw_dict_get(catalog, b"AcroForm") {
-            Some(obj) => self.doc.resolve(obj),
-            None => return vec![],
-        };
+        let mut streams = Vec::new();
+        for (name, obj) in xobj_dict {
+            let Some(id) = obj.as_reference() else {
+                continue;
+            };
+            let Some(stream) = self
+                .doc
+                .get_object(id)
+                .and_then(|o| self.doc.resolve(o).as_stream())
+            else {
+                continue;
+            };
+            let is_image = raw_dict_get(&stream.dict, b"Subtype")
+                .and_then(|s| s.as_name())
+                .is_some_and(|n| n == b"Image");
+            if is_image {
+                streams.push((name.as_slice(), stream));
+            }
+        }
+        Ok(streams)
+    }
 
-        let acroform_dict = match acroform {
-            RawPdfObject::Dict(d) => d,
-            RawPdfObject::Reference(n, g) => match self.doc.get_dict((*n, *g)) {
-                Ok(d) => d,
-                Err(_) => return vec![],
-            },
-            _ => return vec![],
+    /// The catalog's `/AcroForm` dictionary.
+    fn acroform_dict(&self) -> Option<&RawPdfDict> {
+        let catalog = self.doc.catalog().ok()?;
+        match self.doc.resolve(raw_dict_get(catalog, b"AcroForm")?) {
+            RawPdfObject::Dict(d) => Some(d),
+            RawPdfObject::Reference(n, g) => self.doc.get_dict((*n, *g)).ok(),
+            _ => None,
+        }
+    }
+
+    /// The `/XFA` entry of the AcroForm: one XDP stream, or an array of
+    /// packet names and streams.
+    fn xfa(&self) -> Option<&RawPdfObject> {
+        raw_dict_get(self.acroform_dict()?, b"XFA").map(|xfa| self.doc.resolve(xfa))
+    }
+
+    /// The `datasets` packet of the XFA form, as XML bytes.
+    fn extract_xfa_datasets(&self) -> Option<Vec<u8>> {
+        match self.xfa()? {
+            RawPdfObject::Array(packets) => {
+                let stream = packets
+                    .chunks_exact(2)
+                    .find(|pair| pair[0].as_str_bytes() == Some(b"datasets"))
+                    .and_then(|pair| self.doc.resolve(&pair[1]).as_stream())?;
+                raw_stream::decompress(stream).ok()
+            }
+            // 패킷이 한 스트림에 모두 들어 있으면 datasets 요소만 잘라낸다.
+            RawPdfObject::Stream(stream) => {
+                let xdp = raw_stream::decompress(stream).ok()?;
+                let find = |from: usize, tag: &[u8]| {
+                    xdp[from..]
+                        .windows(tag.len())
+                        .position(|w| w == tag)
+                        .map(|i| from + i)
+                };
+                let close = b"</xfa:datasets>";
+                let start = find(0, b"<xfa:datasets")?;
+                let end = find(start, close)? + close.len();
+                Some(xdp[start..end].to_vec())
+            }
+            _ => None,
+        }
+    }
+
+    /// Extract AcroForm fields from the document.
+    fn extract_acroform_fields(&self) -> Vec<FormField> {
+        let Some(acroform_dict) = self.acroform_dict() else {
+            return vec![];
         };
 
         let fields = match raw_dict_get(acroform_dict, b"Fields") {
@@ -593,6 +973,63 @@ impl RawBackend {
         }
     }
 
+    /// Decompressed data of each of a page's content streams in drawing
+    /// order, with the stream's object id when it is an indirect object.
+    ///
+    /// The streams are read uncached, so a lazily loaded document drops them
+    /// again once the page has been analyzed.
+    fn content_streams(&self, page_id: PageId) -> Result<Vec<(Option<PageId>, Vec<u8>)>> {
+        let page_dict = self
+            .doc
+            .get_dict(page_id)
+            .map_err(|e| Error::PdfParse(e.to_string()))?;
+
+        let contents = raw_dict_get(page_dict, b"Contents")
+            .ok_or_else(|| Error::PdfParse("No Contents in page".to_string()))?;
+        let Some(contents_id) = contents.as_reference() else {
+            return match contents {
+                RawPdfObject::Stream(stream) => Ok(vec![(None, raw_stream::decompress(stream)?)]),
+                RawPdfObject::Array(arr) => Ok(self.content_stream_parts(arr)),
+                _ => Err(Error::PdfParse("Invalid content stream".to_string())),
+            };
+        };
+
+        let obj = self
+            .doc
+            .get_object_uncached(contents_id)
+            .ok_or_else(|| Error::PdfParse("Content stream not found".to_string()))?;
+        match &*obj {
+            RawPdfObject::Stream(stream) => {
+                Ok(vec![(Some(contents_id), raw_stream::decompress(stream)?)])
+            }
+            RawPdfObject::Array(arr) => Ok(self.content_stream_parts(arr)),
+            _ => Err(Error::PdfParse("Invalid content stream".to_string())),
+        }
+    }
+
+    /// Decompressed streams of a `/Contents` array, skipping entries that are
+    /// missing or fail to decompress.
+    fn content_stream_parts(&self, arr: &[RawPdfObject]) -> Vec<(Option<PageId>, Vec<u8>)> {
+        let mut parts = Vec::new();
+        for item in arr {
+            let id = item.as_reference();
+            let obj = match id {
+                Some(id) => match self.doc.get_object_uncached(id) {
+                    Some(obj) => obj,
+                    None => continue,
+                },
+                None => std::borrow::Cow::Borrowed(item),
+            };
+            if let Some(stream) = obj.as_stream() {
+                match raw_stream::decompress(stream) {
+                    Ok(data) => parts.push((id, data)),
+                    Err(e) => log::warn!("skipping content stream {:?}: {}", id, e),
+                }
+            }
+        }
+        parts
+    }
+
     /// Find MediaBox for a page, walking up the page tree for inherited values.
     fn find_media_box(&self, page_id: PageId) -> Option<(f32, f32)> {
         let dict = self.doc.get_dict(page_id).ok()?;
@@ -679,21 +1116,10 @@ impl RawBackend {
 
     /// Resolve an outline destination to a page number.
     fn resolve_outline_dest(&self, item_dict: &RawPdfDict) -> Option<u32> {
-        let pages = self.doc.pages();
-
         // Try Dest
         if let Some(dest) = raw_dict_get(item_dict, b"Dest") {
-            let dest = self.doc.resolve(dest);
-            if let Some(arr) = dest.as_array() {
-                if let Some(first) = arr.first() {
-                    if let Some(page_ref) = first.as_reference() {
-                        for (num, id) in pages.iter() {
-                            if *id == page_ref {
-                                return Some(*num);
-                            }
-                        }
-                    }
-                }
+            if let Some(page) = self.dest_page(dest, 0) {
+                return Some(page);
             }
         }
 
@@ -706,25 +1132,89 @@ impl RawBackend {
                 _ => None,
             };
 
-            if let Some(action_dict) = action_dict {
-                if let Some(dest) = raw_dict_get(action_dict, b"D") {
-                    let dest = self.doc.resolve(dest);
-                    if let Some(arr) = dest.as_array() {
-                        if let Some(first) = arr.first() {
-                            if let Some(page_ref) = first.as_reference() {
-                                for (num, id) in pages.iter() {
-                                    if *id == page_ref {
-                                        return Some(*num);
-                                    }
-                                }
-                            }
-                        }
+            if let Some(dest) = action_dict.and_then(|d| raw_dict_get(d, b"D")) {
+                return self.dest_page(dest, 0);
+            }
+        }
+
+        None
+    }
+
+    /// Page number of an explicit (`[page /XYZ ...]`) or named destination.
+    fn dest_page(&self, dest: &RawPdfObject, depth: u8) -> Option<u32> {
+        if depth > 4 {
+            return None;
+        }
+        match self.doc.resolve(dest) {
+            RawPdfObject::Array(arr) => {
+                let page_ref = arr.first()?.as_reference()?;
+                self.doc
+                    .pages()
+                    .iter()
+                    .find(|(_, id)| **id == page_ref)
+                    .map(|(num, _)| *num)
+            }
+            // 이름 있는 목적지의 값은 배열이거나 `/D` 를 가진 딕셔너리다.
+            RawPdfObject::Dict(d) => self.dest_page(raw_dict_get(d, b"D")?, depth + 1),
+            RawPdfObject::Name(name) => {
+                // PDF 1.1 방식: 카탈로그의 /Dests 딕셔너리
+                let catalog = self.doc.catalog().ok()?;
+                let dests = self.doc.resolve(raw_dict_get(catalog, b"Dests")?);
+                let target = raw_dict_get(dests.as_dict()?, name)?;
+                self.dest_page(target, depth + 1)
+            }
+            RawPdfObject::Str(name) => {
+                // PDF 1.2+ 방식: /Names 의 /Dests 이름 트리
+                let catalog = self.doc.catalog().ok()?;
+                let names = self.doc.resolve(raw_dict_get(catalog, b"Names")?);
+                let tree = raw_dict_get(names.as_dict()?, b"Dests")?;
+                let target = self.name_tree_lookup(tree, name, 0)?;
+                self.dest_page(target, depth + 1)
+            }
+            _ => None,
+        }
+    }
+
+    /// Look `key` up in a name tree (`/Names` leaves, `/Kids` branches).
+    fn name_tree_lookup<'a>(
+        &'a self,
+        node: &'a RawPdfObject,
+        key: &[u8],
+        depth: u8,
+    ) -> Option<&'a RawPdfObject> {
+        const MAX_DEPTH: u8 = 32;
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let node = self.doc.resolve(node).as_dict()?;
+
+        if let Some(names) = raw_dict_get(node, b"Names") {
+            let names = self.doc.resolve(names).as_array()?;
+            for pair in names.chunks(2) {
+                if let [name, value] = pair {
+                    if self.doc.resolve(name).as_str_bytes() == Some(key) {
+                        return Some(value);
                     }
                 }
             }
+            return None;
         }
 
-        None
+        let kids = self.doc.resolve(raw_dict_get(node, b"Kids")?).as_array()?;
+        kids.iter().find_map(|kid| {
+            // /Limits 로 범위 밖의 자식은 건너뛴다.
+            let kid_dict = self.doc.resolve(kid).as_dict()?;
+            if let Some(limits) = raw_dict_get(kid_dict, b"Limits") {
+                if let Some([lo, hi]) = self.doc.resolve(limits).as_array() {
+                    let lo = self.doc.resolve(lo).as_str_bytes()?;
+                    let hi = self.doc.resolve(hi).as_str_bytes()?;
+                    if key < lo || key > hi {
+                        return None;
+                    }
+                }
+            }
+            self.name_tree_lookup(kid, key, depth + 1)
+        })
     }
 }
 
@@ -736,6 +1226,7 @@ struct RawFontResolver {
     cmap_cache: RwLock<HashMap<PageId, Option<ToUnicodeMap>>>,
     encoding_cache: RwLock<HashMap<PageId, Option<HashMap<u8, char>>>>,
     cid_system_info_cache: RwLock<HashMap<PageId, Option<(String, String)>>>,
+    metrics_cache: RwLock<HashMap<PageId, Option<Arc<FontMetrics>>>>,
 }
 
 impl RawFontResolver {
@@ -744,9 +1235,86 @@ impl RawFontResolver {
             cmap_cache: RwLock::new(HashMap::new()),
             encoding_cache: RwLock::new(HashMap::new()),
             cid_system_info_cache: RwLock::new(HashMap::new()),
+            metrics_cache: RwLock::new(HashMap::new()),
         }
     }
 
+    fn font_metrics(
+        &self,
+        doc: &RawDocument,
+        page: PageId,
+        font_name: &[u8],
+    ) -> Option<Arc<FontMetrics>> {
+        let fid = self.find_font_dict(doc, page, font_name)?;
+        {
+            let cache = self.metrics_cache.read().unwrap();
+            if let Some(cached) = cache.get(&fid) {
+                return cached.clone();
+            }
+        }
+
+        let result = self.parse_font_metrics(doc, fid).map(Arc::new);
+        self.metrics_cache
+            .write()
+            .unwrap()
+            .insert(fid, result.clone());
+        result
+    }
+
+    fn parse_font_metrics(&self, doc: &RawDocument, font_obj_id: PageId) -> Option<FontMetrics> {
+        let font_dict = doc.get_dict(font_obj_id).ok()?;
+        let subtype = raw_dict_get(font_dict, b"Subtype").and_then(|s| s.as_name());
+        // Type3 widths are in glyph space (scaled by /FontMatrix), not 1/1000 em.
+        if subtype == Some(b"Type3".as_slice()) {
+            return None;
+        }
+
+        if self.is_composite_font(doc, font_obj_id) {
+            // CID == code only for Identity-H; vertical fonts advance along y.
+            let encoding = raw_dict_get(font_dict, b"Encoding").and_then(|e| e.as_name());
+            if encoding != Some(b"Identity-H".as_slice()) {
+                return None;
+            }
+            let cid_font_id = self.get_cid_font_id(doc, font_obj_id)?;
+            let cid_dict = doc.get_dict(cid_font_id).ok()?;
+            let default_width = raw_dict_get(cid_dict, b"DW")
+                .and_then(|o| doc.resolve(o).as_f32())
+                .unwrap_or(1000.0);
+            let items: Vec<WidthItem> = raw_dict_get(cid_dict, b"W")
+                .and_then(|o| doc.resolve(o).as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|item| match doc.resolve(item) {
+                            RawPdfObject::Array(list) => Some(WidthItem::List(
+                                list.iter()
+                                    .map(|w| doc.resolve(w).as_f32().unwrap_or(default_width))
+                                    .collect(),
+                            )),
+                            other => other.as_f32().map(WidthItem::Number),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            return Some(FontMetrics::cid(parse_cid_widths(&items), default_width));
+        }
+
+        let first_char = raw_dict_get(font_dict, b"FirstChar")
+            .and_then(|o| doc.resolve(o).as_i64())
+            .unwrap_or(0)
+            .max(0) as u32;
+        let widths: Vec<f32> = raw_dict_get(font_dict, b"Widths")
+            .and_then(|o| doc.resolve(o).as_array())?
+            .iter()
+            .map(|w| doc.resolve(w).as_f32().unwrap_or(0.0))
+            .collect();
+        let missing_width = raw_dict_get(font_dict, b"FontDescriptor")
+            .and_then(|fd| raw_resolve_dict(doc, fd))
+            .and_then(|fd| raw_dict_get(fd, b"MissingWidth"))
+            .and_then(|o| doc.resolve(o).as_f32())
+            .unwrap_or(0.0);
+        Some(FontMetrics::simple(first_char, &widths, missing_width))
+    }
+
     fn decode_text(
         &self,
         doc: &RawDocument,
@@ -1306,6 +1874,54 @@ impl RawFontResolver {
 // RawBackend helper functions
 // ---------------------------------------------------------------------------
 
+/// Decode an image XObject stream. JPEG and JPEG 2000 data is kept as is,
+/// with the filters in front of it undone; other filters are undone, falling
+/// back to the raw bytes on failure. `filter` names the image codec, or else
+/// the last filter of the chain.
+fn decode_image_xobject(name: &[u8], stream: &RawPdfStream) -> RawXObject {
+    let dict = &stream.dict;
+    let name_of = |obj: &RawPdfObject| {
+        obj.as_name()
+            .map(|n| String::from_utf8_lossy(n).to_string())
+    };
+    let chain = raw_stream::filter_chain(dict);
+    let filter = chain
+        .iter()
+        .find_map(|(name, _)| match *name {
+            b"DCTDecode" | b"DCT" => Some("DCTDecode".to_string()),
+            b"JPXDecode" => Some("JPXDecode".to_string()),
+            _ => None,
+        })
+        .or_else(|| {
+            chain
+                .last()
+                .map(|(name, _)| String::from_utf8_lossy(name).to_string())
+        });
+    let data = raw_stream::decompress_image(stream).unwrap_or_else(|_| stream.raw_data.clone());
+    let dimension = |key: &[u8]| {
+        raw_dict_get(dict, key)
+            .and_then(|v| v.as_i64())
+            .map(|v| v as u32)
+    };
+    let color_space = raw_dict_get(dict, b"ColorSpace").and_then(|cs| match cs {
+        RawPdfObject::Array(arr) => arr.first().and_then(name_of),
+        other => name_of(other),
+    });
+
+    RawXObject {
+        name: String::from_utf8_lossy(name).to_string(),
+        subtype: "Image".to_string(),
+        data,
+        filter,
+        width: dimension(b"Width"),
+        height: dimension(b"Height"),
+        bits_per_component: raw_dict_get(dict, b"BitsPerComponent")
+            .and_then(|b| b.as_i64())
+            .map(|b| b as u8),
+        color_space,
+    }
+}
+
 /// Resolve a PdfObject to a dictionary reference (following references).
 fn raw_resolve_dict<'a>(doc: &'a RawDocument, obj: &'a RawPdfObject) -> Option<&'a RawPdfDict> {
     let resolved = doc.resolve(obj);
@@ -1388,6 +2004,66 @@ mod raw_backend_tests {
         RawBackend::load_file(rel).ok()
     }
 
+    /// Assemble a PDF with a valid xref table from `objects` (numbered from 1).
+    fn assemble(objects: &[&str]) -> Vec<u8> {
+        let mut data = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        for (i, body) in objects.iter().enumerate() {
+            offsets.push(data.len());
+            data.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, body).as_bytes());
+        }
+        let xref = data.len();
+        data.extend_from_slice(
+            format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+        );
+        for offset in offsets {
+            data.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        data.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref
+            )
+            .as_bytes(),
+        );
+        data
+    }
+
+    #[test]
+    fn test_outline_named_destinations() {
+        let data = assemble(&[
+            "<< /Type /Catalog /Pages 2 0 R /Outlines 5 0 R /Dests 9 0 R \
+             /Names << /Dests << /Kids [10 0 R] >> >> >>",
+            "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>",
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>",
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>",
+            "<< /Type /Outlines /First 6 0 R /Last 8 0 R /Count 3 >>",
+            "<< /Title (Explicit) /Parent 5 0 R /Next 7 0 R /Dest [3 0 R /Fit] >>",
+            "<< /Title (By name) /Parent 5 0 R /Prev 6 0 R /Next 8 0 R /Dest /intro >>",
+            "<< /Title (By string) /Parent 5 0 R /Prev 7 0 R \
+             /A << /S /GoTo /D (chapter.2) >> >>",
+            "<< /intro << /D [3 0 R /XYZ 0 792 0] >> >>",
+            "<< /Limits [(chapter.1) (chapter.2)] \
+             /Names [(chapter.1) [3 0 R /Fit] (chapter.2) [4 0 R /Fit]] >>",
+        ]);
+        let raw = RawBackend::load_bytes(&data).unwrap();
+        let pages: Vec<_> = raw
+            .outline()
+            .unwrap()
+            .iter()
+            .map(|item| (item.title.clone(), item.page))
+            .collect();
+        assert_eq!(
+            pages,
+            vec![
+                ("Explicit".to_string(), Some(1)),
+                ("By name".to_string(), Some(1)),
+                ("By string".to_string(), Some(2)),
+            ]
+        );
+    }
+
     #[test]
     fn test_raw_backend_pages() {
         let Some(raw) = try_load("test-files/basic/trivial.pdf") else {
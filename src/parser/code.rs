@@ -0,0 +1,139 @@
+//! Code listing heuristics: monospace font names and language guessing.
+
+/// Name fragments of common monospace font families, lowercase.
+const MONOSPACE_NAME_HINTS: &[&str] = &[
+    "courier",
+    "mono",
+    "consol",
+    "menlo",
+    "monaco",
+    "inconsolata",
+    "lucidaconsole",
+    "lucida console",
+    "typewriter",
+    "sourcecode",
+    "source code",
+    "firacode",
+    "fira code",
+    "ocr-a",
+    "ocr-b",
+    "fixedsys",
+    "andale",
+    "letter gothic",
+    "lettergothic",
+];
+
+/// Whether a font's name marks it as monospaced (Courier, Consolas, any
+/// "...Mono" family). Subset prefixes like `ABCDEF+` are ignored.
+pub(crate) fn is_monospace_font_name(name: &str) -> bool {
+    let base = name.rsplit_once('+').map_or(name, |(_, base)| base);
+    let lower = base.to_lowercase();
+    // "Monotype Corsiva" 같은 비례 글꼴을 걸러낸다.
+    if lower.contains("monotype") {
+        return false;
+    }
+    MONOSPACE_NAME_HINTS.iter().any(|hint| lower.contains(hint))
+}
+
+/// Guess the language of a code listing from unambiguous cues.
+///
+/// Returns `None` unless the text carries a marker that few other languages
+/// share; a wrong fence label is worse than none.
+pub(crate) fn guess_language(code: &str) -> Option<String> {
+    let trimmed = code.trim_start();
+    let first_line = trimmed.lines().next().unwrap_or("");
+    let has = |needle: &str| code.contains(needle);
+    let line_starts = |prefix: &str| code.lines().any(|l| l.trim_start().starts_with(prefix));
+
+    let include = line_starts("#include");
+    let cpp = has("std::") || has("cout") || has("template<") || has("template <");
+    let rust = line_starts("fn ") || line_starts("pub fn ") || has("let mut ");
+    let go = line_starts("package main") || has("func main()");
+    let python = (line_starts("def ") || line_starts("class "))
+        && code.lines().any(|l| l.trim_end().ends_with(':'));
+    let javascript = has("console.log") || has("=> {") || has(" = require(");
+
+    // 앞선 규칙이 우선한다 (C++ 판정이 C보다 먼저).
+    let rules = [
+        (
+            "bash",
+            first_line.starts_with("#!/bin/bash") || first_line.starts_with("#!/bin/sh"),
+        ),
+        (
+            "python",
+            first_line.starts_with("#!") && first_line.contains("python"),
+        ),
+        ("php", trimmed.starts_with("<?php")),
+        ("xml", trimmed.starts_with("<?xml")),
+        (
+            "html",
+            trimmed.starts_with("<!DOCTYPE html") || has("<html"),
+        ),
+        ("cpp", include && cpp),
+        ("c", include),
+        ("rust", rust),
+        ("go", go),
+        (
+            "java",
+            has("public static void main") || has("System.out.print"),
+        ),
+        ("csharp", has("using System") || has("Console.Write")),
+        ("python", python),
+        ("javascript", javascript),
+        ("sql", is_sql(code)),
+        ("bash", line_starts("$ ")),
+    ];
+    rules
+        .iter()
+        .find(|(_, hit)| *hit)
+        .map(|(language, _)| language.to_string())
+}
+
+fn is_sql(code: &str) -> bool {
+    let upper = code.to_uppercase();
+    let first = upper.trim_start();
+    (first.starts_with("SELECT ") && upper.contains(" FROM "))
+        || first.starts_with("INSERT INTO ")
+        || first.starts_with("CREATE TABLE ")
+        || (first.starts_with("UPDATE ") && upper.contains(" SET "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monospace_font_names() {
+        assert!(is_monospace_font_name("Courier"));
+        assert!(is_monospace_font_name("ABCDEF+Consolas-Bold"));
+        assert!(is_monospace_font_name("DejaVuSansMono"));
+        assert!(is_monospace_font_name("SourceCodePro-Regular"));
+        assert!(!is_monospace_font_name("Helvetica"));
+        assert!(!is_monospace_font_name("MonotypeCorsiva"));
+        assert!(!is_monospace_font_name("Times-Roman"));
+    }
+
+    #[test]
+    fn test_guess_language() {
+        let guess = |s: &str| guess_language(s);
+        assert_eq!(guess("fn main() {\n    run();\n}").as_deref(), Some("rust"));
+        assert_eq!(
+            guess("def area(r):\n    return r * r").as_deref(),
+            Some("python")
+        );
+        assert_eq!(
+            guess("#include <stdio.h>\nint main(void) {}").as_deref(),
+            Some("c")
+        );
+        assert_eq!(
+            guess("#include <vector>\nstd::vector<int> v;").as_deref(),
+            Some("cpp")
+        );
+        assert_eq!(
+            guess("select id from users where x = 1").as_deref(),
+            Some("sql")
+        );
+        assert_eq!(guess("$ cargo build --release").as_deref(), Some("bash"));
+        assert_eq!(guess("x = 1\ny = 2"), None);
+    }
+}
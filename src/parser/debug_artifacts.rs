@@ -0,0 +1,183 @@
+//! Per-page debugging artifacts for reading-order bug reports.
+//!
+//! For page `N` three files are written into the chosen directory:
+//!
+//! - `page-N.content.txt` — the decoded content stream, one operator per
+//!   line, prefixed with its index (the numbering `BlockSource::ops` uses)
+//! - `page-N.spans.csv` — every text span with position, size and font
+//! - `page-N.blocks.txt` — the lines and blocks the layout pass detected,
+//!   with their bounds, in reading order
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+
+use super::backend::{PdfBackend, PdfValue};
+use super::layout::{LayoutAnalyzer, TextLine, TextSpan};
+use super::options::ParseOptions;
+
+/// Write the artifacts of `page_num` into `dir`, creating it if needed.
+pub(crate) fn dump_page(
+    backend: &dyn PdfBackend,
+    page_num: u32,
+    options: &ParseOptions,
+    dir: &Path,
+) -> Result<()> {
+    let pages = backend.pages();
+    let page_id = pages
+        .get(&page_num)
+        .ok_or(Error::PageOutOfRange(page_num, pages.len() as u32))?;
+    fs::create_dir_all(dir)?;
+
+    let operations = backend.page_operations(*page_id)?;
+    let mut content = String::new();
+    for (index, op) in operations.ops.iter().enumerate() {
+        let _ = write!(content, "{index}\t");
+        for operand in &op.operands {
+            write_value(&mut content, operand);
+            content.push(' ');
+        }
+        content.push_str(&op.operator);
+        content.push('\n');
+    }
+    fs::write(dir.join(format!("page-{page_num}.content.txt")), content)?;
+
+    // 실제 파싱과 같은 설정으로 레이아웃을 다시 돌려 경계를 기록한다.
+    let mut analyzer = LayoutAnalyzer::new(backend)
+        .with_ocr_suppression(options.suppress_low_confidence_ocr)
+        .with_column_detection(options.column_detection.clone())
+        .with_spacing(options.spacing.clone())
+        .with_deskew(options.deskew)
+        .with_heading_config(options.heading_config.clone());
+
+    let spans = analyzer.extract_page_spans(page_num)?;
+    let mut csv = String::from("op_start,op_end,x,y,width,font_size,font,bold,italic,text\n");
+    for span in &spans {
+        let _ = writeln!(
+            csv,
+            "{},{},{:.2},{:.2},{:.2},{:.2},{},{},{},{}",
+            span.ops[0],
+            span.ops[1],
+            span.x,
+            span.y,
+            span.width,
+            span.font_size,
+            csv_field(&span.font_name),
+            span.is_bold,
+            span.is_italic,
+            csv_field(&span.text),
+        );
+    }
+    fs::write(dir.join(format!("page-{page_num}.spans.csv")), csv)?;
+
+    let blocks = analyzer.extract_page_blocks(page_num)?;
+    let mut layout = String::new();
+    for (index, block) in blocks.iter().enumerate() {
+        let spans = block.lines.iter().flat_map(|line| &line.spans);
+        let _ = writeln!(
+            layout,
+            "block {index} {:?} level={} {}",
+            block.block_type,
+            block.heading_level,
+            bounds(spans)
+        );
+        for line in &block.lines {
+            let _ = writeln!(
+                layout,
+                "  line {} size={:.2} {}",
+                bounds(&line.spans),
+                line.font_size,
+                line_text(line)
+            );
+        }
+    }
+    fs::write(dir.join(format!("page-{page_num}.blocks.txt")), layout)?;
+    Ok(())
+}
+
+/// `[x0 y0 x1 y1]` around the spans, from baseline to font-size height.
+fn bounds<'s>(spans: impl IntoIterator<Item = &'s TextSpan>) -> String {
+    let mut b = [f32::MAX, f32::MAX, f32::MIN, f32::MIN];
+    for span in spans {
+        b[0] = b[0].min(span.x);
+        b[1] = b[1].min(span.y);
+        b[2] = b[2].max(span.x + span.width);
+        b[3] = b[3].max(span.y + span.font_size);
+    }
+    if b[0] > b[2] {
+        return "[]".to_string();
+    }
+    format!("[{:.2} {:.2} {:.2} {:.2}]", b[0], b[1], b[2], b[3])
+}
+
+fn line_text(line: &TextLine) -> String {
+    line.text().replace(['\n', '\r'], " ")
+}
+
+/// Quote a CSV field when it holds a comma, quote or line break.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Write an operand in PDF syntax.
+fn write_value(out: &mut String, value: &PdfValue) {
+    match value {
+        PdfValue::Integer(i) => {
+            let _ = write!(out, "{i}");
+        }
+        PdfValue::Real(r) => {
+            let _ = write!(out, "{r}");
+        }
+        PdfValue::Name(name) => {
+            let _ = write!(out, "/{}", String::from_utf8_lossy(name));
+        }
+        PdfValue::Str(bytes) => {
+            out.push('<');
+            for b in bytes {
+                let _ = write!(out, "{b:02X}");
+            }
+            out.push('>');
+        }
+        PdfValue::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(' ');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        PdfValue::Other => out.push('?'),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_quotes_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_write_value_uses_pdf_syntax() {
+        let mut out = String::new();
+        let value = PdfValue::Array(vec![
+            PdfValue::Str(b"Hi".to_vec()),
+            PdfValue::Integer(-120),
+            PdfValue::Name(b"F1".to_vec()),
+        ]);
+        write_value(&mut out, &value);
+        assert_eq!(out, "[<4869> -120 /F1]");
+    }
+}
@@ -0,0 +1,264 @@
+//! Definition list detection.
+//!
+//! Glossaries are set either inline, "Term — definition" or "Term:
+//! definition", or as a term column next to a definition column. Layout
+//! analysis turns the first into ordinary paragraphs — one per entry, or one
+//! for the whole list when it is single-spaced — and the second into a
+//! two-column table. These are recognised here and rewritten as
+//! [`ListStyle::Definition`](crate::model::ListStyle::Definition) items.
+
+use super::layout::{join_lines, span_ops, TextBlock};
+use super::pdf_parser::PlacedBlock;
+use super::spacing::SpacingPolicy;
+use crate::model::{Block, Paragraph, Table};
+
+/// Terms longer than this read as a sentence with a colon in it.
+const MAX_TERM_CHARS: usize = 40;
+
+/// Words allowed in a term.
+const MAX_TERM_WORDS: usize = 5;
+
+/// Consecutive items needed before a run counts as a glossary, so a lone
+/// "Note: ..." stays a paragraph.
+const MIN_ITEMS: usize = 2;
+
+/// Average words per definition for a two-column table to read as a glossary
+/// rather than key/value data ("Name | Jane Doe").
+const MIN_DEFINITION_WORDS: usize = 4;
+
+/// How an inline item separates its term from its definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Separator {
+    Dash,
+    Colon,
+}
+
+/// `blocks` with glossary tables and runs of "Term — definition"
+/// paragraphs rewritten as definition list items.
+pub(crate) fn detect_definition_lists(blocks: Vec<PlacedBlock>) -> Vec<PlacedBlock> {
+    let mut out: Vec<PlacedBlock> = Vec::with_capacity(blocks.len());
+    for (y, block, ops) in blocks {
+        match &block {
+            Block::Table(table) => match glossary_rows(table) {
+                Some(rows) => out.extend(rows.into_iter().map(|(term, def)| {
+                    (y, Block::Paragraph(Paragraph::definition(term, def)), ops)
+                })),
+                None => out.push((y, block, ops)),
+            },
+            _ => out.push((y, block, ops)),
+        }
+    }
+
+    let splits: Vec<Option<(Separator, String, String)>> = out
+        .iter()
+        .map(|(_, block, _)| inline_split(block))
+        .collect();
+    let mut start = 0;
+    while start < out.len() {
+        let Some((separator, ..)) = splits[start] else {
+            start += 1;
+            continue;
+        };
+        // 같은 구분자를 쓰는 항목이 이어진 만큼을 한 목록으로 본다.
+        let end = (start..out.len())
+            .find(|&i| !matches!(splits[i], Some((s, ..)) if s == separator))
+            .unwrap_or(out.len());
+        if end - start >= MIN_ITEMS {
+            for i in start..end {
+                if let Some((_, term, def)) = &splits[i] {
+                    out[i].1 = Block::Paragraph(Paragraph::definition(term.clone(), def.clone()));
+                }
+            }
+        }
+        start = end;
+    }
+    out
+}
+
+/// Entries of a text block whose lines open one "Term: definition" after
+/// another, as single-spaced glossaries are set. Lines that open no entry
+/// continue the one above; the block itself must open with an entry.
+pub(crate) fn block_entries(block: &TextBlock, policy: &SpacingPolicy) -> Option<Vec<PlacedBlock>> {
+    let first = split_entry(&block.lines.first()?.text_with(policy))?.0;
+    let mut entries: Vec<(usize, String, Vec<String>)> = Vec::new();
+    for (i, line) in block.lines.iter().enumerate() {
+        let text = line.text_with(policy);
+        match (split_entry(&text), entries.last_mut()) {
+            (Some((separator, term, def)), _) if separator == first => {
+                entries.push((i, term, vec![def]));
+            }
+            (_, Some((_, _, lines))) => lines.push(text),
+            (_, None) => return None,
+        }
+    }
+    if entries.len() < MIN_ITEMS {
+        return None;
+    }
+
+    let starts: Vec<usize> = entries.iter().map(|(i, ..)| *i).collect();
+    let ends = starts.iter().skip(1).copied().chain([block.lines.len()]);
+    let placed = entries
+        .into_iter()
+        .zip(ends)
+        .map(|((start, term, def_lines), end)| {
+            let def = join_lines(def_lines, policy);
+            let ops = span_ops(block.lines[start..end].iter().flat_map(|l| &l.spans));
+            (
+                block.lines[start].y,
+                Block::Paragraph(Paragraph::definition(term, def)),
+                ops,
+            )
+        })
+        .collect();
+    Some(placed)
+}
+
+/// Term and definition of a plain paragraph set as "Term — definition" or
+/// "Term: definition".
+fn inline_split(block: &Block) -> Option<(Separator, String, String)> {
+    let Block::Paragraph(p) = block else {
+        return None;
+    };
+    if p.is_heading() || p.is_list_item() {
+        return None;
+    }
+    split_entry(&p.plain_text())
+}
+
+/// Term and definition of `text` set as "Term — definition" or "Term:
+/// definition".
+fn split_entry(text: &str) -> Option<(Separator, String, String)> {
+    let text = text.trim();
+    // 먼저 나오는 구분자를 쓴다 — "Term: a — b" 의 용어는 "Term" 이다.
+    let dash = text.find(['—', '–']).map(|i| (i, Separator::Dash));
+    let colon = text
+        .match_indices(':')
+        .find(|(i, _)| text[i + 1..].starts_with(char::is_whitespace))
+        .map(|(i, _)| (i, Separator::Colon));
+    let (at, separator) = match (dash, colon) {
+        (Some(d), Some(c)) => {
+            if d.0 < c.0 {
+                d
+            } else {
+                c
+            }
+        }
+        (d, c) => d.or(c)?,
+    };
+    let term = text[..at].trim();
+    let rest = &text[at..];
+    let def = rest[rest.chars().next()?.len_utf8()..].trim();
+    if !is_term(term) || !def.chars().any(char::is_alphanumeric) {
+        return None;
+    }
+    Some((separator, term.to_string(), def.to_string()))
+}
+
+/// Short enough for a term, containing a letter, and not a bullet item.
+fn is_term(text: &str) -> bool {
+    !text.is_empty()
+        && text.chars().count() <= MAX_TERM_CHARS
+        && text.split_whitespace().count() <= MAX_TERM_WORDS
+        && text.chars().any(char::is_alphabetic)
+        && !text.starts_with(['•', '-', '*'])
+}
+
+/// Term and definition pairs of a two-column table whose left column holds
+/// terms and right column prose, skipping header rows.
+fn glossary_rows(table: &Table) -> Option<Vec<(String, String)>> {
+    let mut rows = Vec::new();
+    for row in table.rows.iter().filter(|r| !r.is_header) {
+        let [term, def] = row.cells.as_slice() else {
+            return None;
+        };
+        if term.colspan > 1 || term.rowspan > 1 || def.colspan > 1 || def.rowspan > 1 {
+            return None;
+        }
+        let (term, def) = (term.plain_text(), def.plain_text());
+        let (term, def) = (term.trim(), def.trim());
+        if !is_term(term) || def.is_empty() {
+            return None;
+        }
+        rows.push((term.to_string(), def.to_string()));
+    }
+    let words: usize = rows
+        .iter()
+        .map(|(_, def)| def.split_whitespace().count())
+        .sum();
+    (rows.len() >= MIN_ITEMS && words >= MIN_DEFINITION_WORDS * rows.len()).then_some(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{TableCell, TableRow};
+
+    fn table(rows: &[[&str; 2]]) -> Block {
+        let mut table = Table::new();
+        for [left, right] in rows {
+            table.add_row(TableRow::new(vec![
+                TableCell::text(*left),
+                TableCell::text(*right),
+            ]));
+        }
+        Block::Table(table)
+    }
+
+    fn paragraphs(texts: &[&str]) -> Vec<PlacedBlock> {
+        texts
+            .iter()
+            .map(|t| (0.0, Block::Paragraph(Paragraph::with_text(*t)), None))
+            .collect()
+    }
+
+    fn terms(blocks: &[PlacedBlock]) -> Vec<Option<String>> {
+        blocks
+            .iter()
+            .map(|(_, block, _)| match block {
+                Block::Paragraph(p) => p.definition_term().map(str::to_string),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_inline_runs() {
+        let blocks = detect_definition_lists(paragraphs(&[
+            "Glossary of terms used below.",
+            "Kerning — Adjusting the space between a pair of letters.",
+            "Leading — The vertical distance between baselines.",
+            "Tracking: Uniform spacing across a range of letters.",
+        ]));
+        assert_eq!(
+            terms(&blocks),
+            vec![None, Some("Kerning".into()), Some("Leading".into()), None]
+        );
+    }
+
+    #[test]
+    fn test_lone_or_long_lead_ins_stay_paragraphs() {
+        let blocks = detect_definition_lists(paragraphs(&[
+            "Note: the figures below are preliminary.",
+            "The committee reviewed the following items in detail: budget and staffing.",
+            "Meeting starts at 10:30 in room 4.",
+        ]));
+        assert!(terms(&blocks).iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_glossary_table() {
+        let glossary = table(&[
+            ["Baseline", "The line on which most letters sit."],
+            ["Glyph", "A single drawn shape for a character."],
+        ]);
+        let blocks = detect_definition_lists(vec![(0.0, glossary, None)]);
+        assert_eq!(
+            terms(&blocks),
+            vec![Some("Baseline".into()), Some("Glyph".into())]
+        );
+
+        let data = table(&[["Name", "Jane Doe"], ["Role", "Editor"]]);
+        let blocks = detect_definition_lists(vec![(0.0, data, None)]);
+        assert!(matches!(blocks[0].1, Block::Table(_)));
+    }
+}
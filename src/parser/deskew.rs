@@ -0,0 +1,169 @@
+//! Skew correction for text layers laid over slightly rotated scans.
+//!
+//! OCR engines place each recognised word where it sits on the scanned image.
+//! When the page went through the scanner at an angle, consecutive words of a
+//! line climb or sink steadily, and line grouping — which compares baselines —
+//! cuts the line into pieces. The angle is estimated from the slope between
+//! each span and the next span along its line; the median of those slopes is
+//! the page's skew, and spans are rotated about the centre of the text to undo
+//! it.
+
+use super::layout::TextSpan;
+
+/// Fewer neighbour pairs than this is too little evidence to rotate a page.
+const MIN_PAIRS: usize = 8;
+
+/// Smaller angles are within baseline jitter; leave the page alone.
+const MIN_ANGLE: f32 = 0.2 * std::f32::consts::PI / 180.0;
+
+/// Larger angles are deliberate (rotated labels, stamps), not scan skew.
+const MAX_ANGLE: f32 = 10.0 * std::f32::consts::PI / 180.0;
+
+/// Pair slopes this close to the median count as agreeing with it.
+const AGREEMENT: f32 = 0.5 * std::f32::consts::PI / 180.0;
+
+/// Dominant baseline angle of `spans` in radians, counter-clockwise, when
+/// the page is consistently skewed by a correctable amount.
+pub(crate) fn estimate_skew(spans: &[TextSpan]) -> Option<f32> {
+    let mut order: Vec<usize> = (0..spans.len())
+        .filter(|&i| spans[i].font_size > 0.0 && !spans[i].text.trim().is_empty())
+        .collect();
+    order.sort_by(|&a, &b| spans[a].x.total_cmp(&spans[b].x));
+
+    let mut angles = Vec::new();
+    for (pos, &i) in order.iter().enumerate() {
+        let span = &spans[i];
+        let size = span.font_size;
+        let end = span.x + advance(span);
+        // 오른쪽으로 가장 가까운 같은 크기의 스팬 — 같은 줄의 다음 단어로 본다.
+        let next = order[pos + 1..]
+            .iter()
+            .map(|&j| &spans[j])
+            .take_while(|n| n.x <= end + 2.0 * size)
+            .filter(|n| {
+                n.x >= end - 0.25 * size
+                    && (n.y - span.y).abs() < 0.5 * size
+                    && (n.font_size / size - 1.0).abs() < 0.2
+            })
+            .min_by(|a, b| a.x.total_cmp(&b.x));
+        if let Some(next) = next {
+            let dx = next.x - span.x;
+            if dx >= size {
+                angles.push((next.y - span.y).atan2(dx));
+            }
+        }
+    }
+    if angles.len() < MIN_PAIRS {
+        return None;
+    }
+
+    angles.sort_by(f32::total_cmp);
+    let median = angles[angles.len() / 2];
+    let agreeing = angles
+        .iter()
+        .filter(|a| (*a - median).abs() <= AGREEMENT)
+        .count();
+    if agreeing * 2 < angles.len() {
+        return None;
+    }
+    (MIN_ANGLE..=MAX_ANGLE)
+        .contains(&median.abs())
+        .then_some(median)
+}
+
+/// Width of `span` along its baseline, estimated from the character count
+/// when the font gave no advances.
+fn advance(span: &TextSpan) -> f32 {
+    if span.width > 0.0 {
+        span.width
+    } else {
+        span.text.chars().count() as f32 * span.font_size * 0.5
+    }
+}
+
+/// Rotate `spans` by `angle` radians (counter-clockwise) about the centre of
+/// their bounding box.
+pub(crate) fn rotate_spans(spans: &mut [TextSpan], angle: f32) {
+    if spans.is_empty() {
+        return;
+    }
+    let (mut min_x, mut max_x) = (f32::INFINITY, f32::NEG_INFINITY);
+    let (mut min_y, mut max_y) = (f32::INFINITY, f32::NEG_INFINITY);
+    for span in spans.iter() {
+        min_x = min_x.min(span.x);
+        max_x = max_x.max(span.x + advance(span));
+        min_y = min_y.min(span.y);
+        max_y = max_y.max(span.y);
+    }
+    let (cx, cy) = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+    let (sin, cos) = angle.sin_cos();
+
+    for span in spans.iter_mut() {
+        let (dx, dy) = (span.x - cx, span.y - cy);
+        let x = cx + dx * cos - dy * sin;
+        let shift = x - span.x;
+        span.x = x;
+        span.y = cy + dx * sin + dy * cos;
+        // 글자 위치는 기준선을 따라 잰 값이므로 시작점과 함께 옮긴다.
+        for char_x in &mut span.char_x {
+            *char_x += shift;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Four lines of five words each, 20pt apart, climbing by `degrees`.
+    fn skewed_page(degrees: f32) -> Vec<TextSpan> {
+        let slope = degrees.to_radians().tan();
+        let mut spans = Vec::new();
+        for line in 0..4 {
+            for word in 0..5 {
+                let x = 72.0 + word as f32 * 60.0;
+                let y = 700.0 - line as f32 * 20.0 + (x - 72.0) * slope;
+                let mut span = TextSpan::new("word".to_string(), x, y, 12.0, "Helvetica");
+                span.width = 50.0;
+                spans.push(span);
+            }
+        }
+        spans
+    }
+
+    #[test]
+    fn test_estimate_skew() {
+        let angle = estimate_skew(&skewed_page(1.5)).unwrap();
+        assert!(
+            (angle.to_degrees() - 1.5).abs() < 0.05,
+            "{}",
+            angle.to_degrees()
+        );
+        let angle = estimate_skew(&skewed_page(-3.0)).unwrap();
+        assert!(
+            (angle.to_degrees() + 3.0).abs() < 0.05,
+            "{}",
+            angle.to_degrees()
+        );
+
+        // Straight text, too little text, and a bare jitter are left alone
+        assert_eq!(estimate_skew(&skewed_page(0.0)), None);
+        assert_eq!(estimate_skew(&skewed_page(0.1)), None);
+        assert_eq!(estimate_skew(&skewed_page(2.0)[..4]), None);
+    }
+
+    #[test]
+    fn test_rotate_spans_levels_lines() {
+        let mut spans = skewed_page(2.0);
+        let angle = estimate_skew(&spans).unwrap();
+        rotate_spans(&mut spans, -angle);
+        for line in spans.chunks(5) {
+            let (lo, hi) = line.iter().fold((f32::MAX, f32::MIN), |(lo, hi), s| {
+                (lo.min(s.y), hi.max(s.y))
+            });
+            assert!(hi - lo < 0.01, "line still spans {}pt", hi - lo);
+        }
+        // Words keep their order and spacing along the line
+        assert!(spans[1].x > spans[0].x + 59.0 && spans[1].x < spans[0].x + 61.0);
+    }
+}
@@ -35,6 +35,150 @@ pub fn decode_text_simple(bytes: &[u8]) -> String {
     bytes.iter().map(|&b| b as char).collect()
 }
 
+// ---------------------------------------------------------------------------
+// Glyph widths
+// ---------------------------------------------------------------------------
+
+/// Glyph advance widths of a font, in 1/1000 text space units.
+///
+/// Built from `/FirstChar` + `/Widths` for simple fonts and from the
+/// descendant CIDFont's `/W` + `/DW` for Identity-encoded composite fonts.
+#[derive(Debug, Clone, Default)]
+pub struct FontMetrics {
+    /// Bytes per character code (1 for simple fonts, 2 for CID fonts).
+    pub code_width: usize,
+    /// Width used for codes without an explicit entry.
+    pub default_width: f32,
+    widths: HashMap<u32, f32>,
+}
+
+impl FontMetrics {
+    /// Metrics for a simple (single-byte) font.
+    pub fn simple(first_char: u32, widths: &[f32], missing_width: f32) -> Self {
+        Self {
+            code_width: 1,
+            default_width: missing_width,
+            widths: widths
+                .iter()
+                .enumerate()
+                .map(|(i, &w)| (first_char + i as u32, w))
+                .collect(),
+        }
+    }
+
+    /// Metrics for a two-byte CID font, from its `/W` entries.
+    pub fn cid(widths: HashMap<u32, f32>, default_width: f32) -> Self {
+        Self {
+            code_width: 2,
+            default_width,
+            widths,
+        }
+    }
+
+    /// Advance width of a single character code.
+    pub fn glyph_width(&self, code: u32) -> f32 {
+        self.widths
+            .get(&code)
+            .copied()
+            .unwrap_or(self.default_width)
+    }
+
+    /// Iterate over the character codes in `bytes`.
+    pub fn codes<'a>(&'a self, bytes: &'a [u8]) -> impl Iterator<Item = u32> + 'a {
+        bytes
+            .chunks(self.code_width.max(1))
+            .map(|chunk| chunk.iter().fold(0u32, |code, &b| code << 8 | u32::from(b)))
+    }
+
+    /// Total advance width of a string, in 1/1000 text space units.
+    pub fn string_width(&self, bytes: &[u8]) -> f32 {
+        self.codes(bytes).map(|c| self.glyph_width(c)).sum()
+    }
+
+    /// Width of the space glyph, when the font defines one (simple fonts only).
+    pub fn space_width(&self) -> Option<f32> {
+        if self.code_width != 1 {
+            return None;
+        }
+        self.widths.get(&32).copied().filter(|w| *w > 0.0)
+    }
+
+    /// Whether every glyph with an explicit width has the same advance, as in
+    /// Courier or a typewriter font.
+    ///
+    /// Requires a handful of explicit widths so that a subset carrying only
+    /// one or two glyphs, or a CID font relying on `/DW` alone, doesn't count.
+    pub fn is_fixed_pitch(&self) -> bool {
+        const MIN_GLYPHS: usize = 8;
+        let mut widths = self.widths.values().filter(|w| **w > 0.0);
+        let Some(&first) = widths.next() else {
+            return false;
+        };
+        let mut count = 1;
+        for &w in widths {
+            if (w - first).abs() > first * 0.01 {
+                return false;
+            }
+            count += 1;
+        }
+        count >= MIN_GLYPHS
+    }
+
+    /// Mean of the non-zero glyph widths, or `default_width` if there are none.
+    pub fn average_width(&self) -> f32 {
+        let (sum, count) = self
+            .widths
+            .values()
+            .filter(|w| **w > 0.0)
+            .fold((0.0f32, 0usize), |(s, n), w| (s + w, n + 1));
+        if count == 0 {
+            self.default_width
+        } else {
+            sum / count as f32
+        }
+    }
+}
+
+/// Parse a CIDFont `/W` array: `[c [w1 w2 ...] cfirst clast w ...]`.
+pub(crate) fn parse_cid_widths(items: &[WidthItem]) -> HashMap<u32, f32> {
+    let mut widths = HashMap::new();
+    let mut i = 0;
+    while i < items.len() {
+        match (items.get(i), items.get(i + 1), items.get(i + 2)) {
+            (Some(WidthItem::Number(first)), Some(WidthItem::List(list)), _) => {
+                let first = *first as u32;
+                for (offset, w) in list.iter().enumerate() {
+                    widths.insert(first + offset as u32, *w);
+                }
+                i += 2;
+            }
+            (
+                Some(WidthItem::Number(first)),
+                Some(WidthItem::Number(last)),
+                Some(WidthItem::Number(w)),
+            ) => {
+                let (first, last) = (*first as u32, *last as u32);
+                // 손상된 범위가 수백만 항목을 만들지 않도록 상한을 둔다.
+                if last >= first && last - first <= 0xFFFF {
+                    for cid in first..=last {
+                        widths.insert(cid, *w);
+                    }
+                }
+                i += 3;
+            }
+            _ => i += 1,
+        }
+    }
+    widths
+}
+
+/// One element of a `/W` array, resolved to plain numbers.
+#[derive(Debug, Clone)]
+pub(crate) enum WidthItem {
+    Number(f32),
+    List(Vec<f32>),
+}
+
 // ---------------------------------------------------------------------------
 // ToUnicode CMap parser
 // ---------------------------------------------------------------------------
@@ -603,6 +747,33 @@ pub(crate) fn is_likely_binary(text: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_font_metrics_simple() {
+        let m = FontMetrics::simple(32, &[278.0, 278.0, 355.0], 0.0);
+        assert_eq!(m.glyph_width(32), 278.0);
+        assert_eq!(m.glyph_width(34), 355.0);
+        assert_eq!(m.glyph_width(90), 0.0);
+        assert_eq!(m.string_width(b" !\""), 911.0);
+        assert_eq!(m.space_width(), Some(278.0));
+    }
+
+    #[test]
+    fn test_font_metrics_cid() {
+        let widths = parse_cid_widths(&[
+            WidthItem::Number(1.0),
+            WidthItem::List(vec![500.0, 600.0]),
+            WidthItem::Number(10.0),
+            WidthItem::Number(12.0),
+            WidthItem::Number(1000.0),
+        ]);
+        let m = FontMetrics::cid(widths, 1000.0);
+        assert_eq!(m.glyph_width(2), 600.0);
+        assert_eq!(m.glyph_width(11), 1000.0);
+        assert_eq!(m.glyph_width(99), 1000.0);
+        assert_eq!(m.string_width(&[0x00, 0x01, 0x00, 0x02]), 1100.0);
+        assert_eq!(m.space_width(), None);
+    }
+
     #[test]
     fn test_decode_text_simple_utf8() {
         assert_eq!(decode_text_simple(b"Hello"), "Hello");
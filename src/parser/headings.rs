@@ -0,0 +1,267 @@
+//! Heading detection for pages set in a single font size.
+//!
+//! Size contrast is the main heading signal, so a page whose lines all share
+//! one size — typewritten reports, plain exports from word processors — gets
+//! no headings from it. Such pages are scored line by line on the weaker
+//! style signals in [`HeadingSignals`](crate::render::HeadingSignals): bold,
+//! short, uppercase, set off by space, or starting with section numbering.
+
+use super::layout::TextLine;
+use crate::model::{Block, Page};
+use crate::render::{HeadingConfig, LevelNormalization};
+
+/// Lines with more visible characters than this read as prose.
+const SHORT_LINE_CHARS: usize = 60;
+
+/// A gap this many line pitches tall counts as space above.
+const SPACE_ABOVE_RATIO: f32 = 1.5;
+
+/// Typical baseline-to-baseline distance between consecutive lines.
+pub(crate) fn line_pitch(ys: &[f32]) -> Option<f32> {
+    let mut gaps: Vec<f32> = ys
+        .windows(2)
+        .map(|w| w[0] - w[1])
+        .filter(|g| *g > 0.0)
+        .collect();
+    if gaps.len() < 2 {
+        return None;
+    }
+    gaps.sort_by(f32::total_cmp);
+    Some(gaps[gaps.len() / 2])
+}
+
+/// Whether a line whose baseline is `gap` below the previous one is set off
+/// from it by extra space.
+pub(crate) fn has_space_above(gap: f32, pitch: Option<f32>) -> bool {
+    pitch.is_some_and(|p| gap > SPACE_ABOVE_RATIO * p)
+}
+
+/// Heading level for `line` from its style signals, or 0 when its score
+/// stays below the threshold.
+///
+/// Numbered lines take their level from the numbering depth ("2." → 2,
+/// "2.1" → 3); other headings are level 2, leaving level 1 for titles.
+pub(crate) fn style_heading_level(
+    line: &TextLine,
+    space_above: bool,
+    config: &HeadingConfig,
+) -> u8 {
+    if !config.detect_from_style {
+        return 0;
+    }
+    let weights = &config.signals;
+    let text = line.text();
+    let text = text.trim();
+    let numbering = numbering_depth(text, config.korean_patterns);
+
+    let mut score = 0.0;
+    if line.is_bold() {
+        score += weights.bold;
+    }
+    if line.is_uppercase() {
+        score += weights.uppercase;
+    }
+    if is_short(text) {
+        score += weights.short_line;
+    }
+    if space_above {
+        score += weights.space_above;
+    }
+    if numbering.is_some() {
+        score += weights.numbering;
+    }
+    if score < weights.min_score {
+        return 0;
+    }
+    numbering.map_or(2, |depth| (depth + 1).min(4))
+}
+
+/// Short enough for a heading and not ending like a sentence or a lead-in.
+fn is_short(text: &str) -> bool {
+    let visible = text.chars().filter(|c| !c.is_whitespace()).count();
+    visible <= SHORT_LINE_CHARS && !text.ends_with(['.', ',', ';', ':', '。'])
+}
+
+/// Depth of the section number `text` starts with: "3." and "IV." are 1,
+/// "3.2" is 2. With `korean`, "제1장" counts as 1, "제2절" as 2 and
+/// "제3조" as 3.
+fn numbering_depth(text: &str, korean: bool) -> Option<u8> {
+    if korean {
+        if let Some(rest) = text.strip_prefix('제') {
+            let rest = rest.trim_start();
+            let number = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+            if number.len() < rest.len() {
+                match number.trim_start().chars().next() {
+                    Some('편' | '장' | '부') => return Some(1),
+                    Some('절' | '관') => return Some(2),
+                    Some('조') => return Some(3),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // 번호 뒤에는 공백과 소문자가 아닌 글자가 와야 한다 — "3.5 kg" 같은 수치와 구분.
+    let (label, rest) = text.split_once(char::is_whitespace)?;
+    let first = rest.trim_start().chars().next()?;
+    if !first.is_alphabetic() || first.is_lowercase() {
+        return None;
+    }
+    if let Some(roman) = label.strip_suffix('.') {
+        if !roman.is_empty() && roman.chars().all(|c| matches!(c, 'I' | 'V' | 'X')) {
+            return Some(1);
+        }
+    }
+
+    let terminated = label.ends_with(['.', ')']);
+    let groups: Vec<&str> = label.trim_end_matches(['.', ')']).split('.').collect();
+    let numeric = |g: &&str| !g.is_empty() && g.len() <= 3 && g.bytes().all(|b| b.is_ascii_digit());
+    if !groups.iter().all(numeric) || (groups.len() == 1 && !terminated) {
+        return None;
+    }
+    Some(groups.len().min(4) as u8)
+}
+
+/// Heading levels repaired page by page in reading order, as
+/// [`LevelNormalization`] describes. Pages must be fed in page order.
+pub(crate) struct LevelNormalizer {
+    mode: LevelNormalization,
+    /// Open headings, outermost first: (detected level, repaired level)
+    open: Vec<(u8, u8)>,
+}
+
+impl LevelNormalizer {
+    pub(crate) fn new(mode: LevelNormalization) -> Self {
+        Self {
+            mode,
+            open: Vec::new(),
+        }
+    }
+
+    /// Repair the levels of the headings on `page`.
+    pub(crate) fn apply(&mut self, page: &mut Page) {
+        if self.mode == LevelNormalization::Off {
+            return;
+        }
+        for block in &mut page.elements {
+            if let Block::Paragraph(p) = block {
+                if let Some(level) = p.style.heading_level {
+                    p.style.heading_level = Some(self.level(level));
+                }
+            }
+        }
+    }
+
+    fn level(&mut self, detected: u8) -> u8 {
+        while self.open.last().is_some_and(|&(d, _)| d >= detected) {
+            self.open.pop();
+        }
+        let level = match (self.open.last(), self.mode) {
+            (Some(&(_, parent)), _) => (parent + 1).min(6),
+            (None, LevelNormalization::Anchored(top)) => top.clamp(1, 6),
+            (None, _) => detected,
+        };
+        self.open.push((detected, level));
+        level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TextSpan;
+
+    fn line(text: &str, bold: bool) -> TextLine {
+        let mut span = TextSpan::new(text.to_string(), 72.0, 700.0, 11.0, "Helvetica");
+        span.is_bold = bold;
+        TextLine::from_spans(vec![span])
+    }
+
+    #[test]
+    fn test_numbering_depth() {
+        assert_eq!(numbering_depth("1. Introduction", false), Some(1));
+        assert_eq!(numbering_depth("2.3 Related work", false), Some(2));
+        assert_eq!(numbering_depth("IV. Results", false), Some(1));
+        assert_eq!(numbering_depth("제2장 총칙", true), Some(1));
+        assert_eq!(numbering_depth("제3조(목적)", true), Some(3));
+        assert_eq!(numbering_depth("제3조(목적)", false), None);
+        assert_eq!(numbering_depth("2024 was a good year", false), None);
+        assert_eq!(numbering_depth("3.5 kg of flour", false), None);
+        assert_eq!(numbering_depth("Introduction", false), None);
+    }
+
+    #[test]
+    fn test_style_heading_level() {
+        let config = HeadingConfig::default();
+        assert_eq!(
+            style_heading_level(&line("Background", true), true, &config),
+            2
+        );
+        assert_eq!(
+            style_heading_level(&line("2.1 Setup", true), false, &config),
+            3
+        );
+        assert_eq!(
+            style_heading_level(&line("SUMMARY", false), true, &config),
+            2
+        );
+
+        // Bold alone, a sentence, and a plain numbered item are not enough
+        assert_eq!(
+            style_heading_level(&line("Background", true), false, &config),
+            0
+        );
+        assert_eq!(
+            style_heading_level(&line("This is bold.", true), true, &config),
+            0
+        );
+        assert_eq!(
+            style_heading_level(&line("1. Install it", false), true, &config),
+            0
+        );
+
+        let off = HeadingConfig {
+            detect_from_style: false,
+            ..HeadingConfig::default()
+        };
+        assert_eq!(
+            style_heading_level(&line("Background", true), true, &off),
+            0
+        );
+    }
+
+    #[test]
+    fn test_level_normalizer_spans_pages() {
+        use crate::model::Paragraph;
+
+        let page = |levels: &[u8]| {
+            let mut page = Page::letter(1);
+            for &level in levels {
+                page.add_paragraph(Paragraph::heading("Heading", level));
+            }
+            page
+        };
+        let levels = |page: &Page| -> Vec<u8> {
+            page.elements
+                .iter()
+                .filter_map(|b| match b {
+                    Block::Paragraph(p) => p.heading_level(),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let mut normalizer = LevelNormalizer::new(LevelNormalization::Compress);
+        let (mut first, mut second) = (page(&[3, 5, 6]), page(&[6, 4, 2, 5]));
+        normalizer.apply(&mut first);
+        normalizer.apply(&mut second);
+        assert_eq!(levels(&first), [3, 4, 5]);
+        // 다음 페이지도 앞 페이지의 제목 아래에 이어 붙는다.
+        assert_eq!(levels(&second), [5, 4, 2, 3]);
+
+        let mut anchored = LevelNormalizer::new(LevelNormalization::Anchored(1));
+        let mut page = page(&[3, 5, 2, 4]);
+        anchored.apply(&mut page);
+        assert_eq!(levels(&page), [1, 2, 1, 2]);
+    }
+}
@@ -0,0 +1,84 @@
+//! Size-bounded string interning.
+//!
+//! Every text span records its font name, and a large document produces
+//! millions of spans drawn from a handful of fonts. Interning hands out
+//! shared `Arc<str>` handles so those spans point at one allocation per
+//! distinct name instead of one per span.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Distinct strings kept by default. A page rarely uses more than a few
+/// dozen fonts; the cap only matters for hostile input that names a fresh
+/// font in every `Tf`.
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// Deduplicates strings into shared `Arc<str>` handles.
+///
+/// Once `capacity` distinct strings are held, new strings are still returned
+/// as fresh `Arc`s but no longer stored, so memory stays bounded.
+#[derive(Debug)]
+pub(crate) struct StringInterner {
+    strings: HashSet<Arc<str>>,
+    capacity: usize,
+}
+
+impl StringInterner {
+    /// Interner holding at most `capacity` distinct strings.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            strings: HashSet::new(),
+            capacity,
+        }
+    }
+
+    /// The shared handle for `s`.
+    pub(crate) fn intern(&mut self, s: &str) -> Arc<str> {
+        if let Some(existing) = self.strings.get(s) {
+            return Arc::clone(existing);
+        }
+        let handle: Arc<str> = Arc::from(s);
+        if self.strings.len() < self.capacity {
+            self.strings.insert(Arc::clone(&handle));
+        }
+        handle
+    }
+
+    /// Number of distinct strings held.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.strings.len()
+    }
+}
+
+impl Default for StringInterner {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_shares_allocation() {
+        let mut interner = StringInterner::default();
+        let a = interner.intern("Helvetica-Bold");
+        let b = interner.intern(&String::from("Helvetica-Bold"));
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn test_capacity_bounds_storage() {
+        let mut interner = StringInterner::with_capacity(2);
+        interner.intern("A");
+        interner.intern("B");
+        let c1 = interner.intern("C");
+        let c2 = interner.intern("C");
+        assert_eq!(&*c1, "C");
+        assert!(!Arc::ptr_eq(&c1, &c2));
+        assert_eq!(interner.len(), 2);
+    }
+}
@@ -3,14 +3,23 @@
 //! This module provides text extraction with position and font information,
 //! enabling proper heading detection, paragraph separation, and structure analysis.
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::{BTreeMap, HashMap};
 
-use super::backend::{get_number_from_value, PdfBackend, PdfValue};
+use std::sync::Arc;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use super::backend::{get_number_from_value, ContentOp, FontMetrics, PageId, PdfBackend, PdfValue};
+use super::intern::StringInterner;
+use super::spacing::SpacingPolicy;
 use crate::error::{Error, Result};
+use crate::model::{BlockSource, Word};
+use crate::render::HeadingConfig;
 
 /// A text span with position and style information.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TextSpan {
     /// The text content
     pub text: String,
@@ -22,22 +31,38 @@ pub struct TextSpan {
     pub width: f32,
     /// Font size in points
     pub font_size: f32,
-    /// Font name (e.g., "Helvetica-Bold")
-    pub font_name: String,
+    /// Font name (e.g., "Helvetica-Bold"), shared by every span in the font
+    pub font_name: Arc<str>,
     /// Whether the font appears to be bold
     pub is_bold: bool,
     /// Whether the font appears to be italic
     pub is_italic: bool,
+    /// Whether the font is monospaced (by name or by uniform glyph widths)
+    pub is_monospace: bool,
+    /// Left edge of each character along the baseline, from glyph advances.
+    /// Only recorded by an analyzer built `with_glyph_positions(true)` and
+    /// when the font carries widths; empty otherwise.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub char_x: Vec<f32>,
+    /// Indices of the first and last content-stream operator that drew the
+    /// span, counted across the page's content streams (see `BlockSource`)
+    pub ops: [u32; 2],
 }
 
 impl TextSpan {
     /// Create a new text span.
-    pub fn new(text: String, x: f32, y: f32, font_size: f32, font_name: String) -> Self {
-        let is_bold = font_name.to_lowercase().contains("bold")
-            || font_name.to_lowercase().contains("black")
-            || font_name.to_lowercase().contains("heavy");
-        let is_italic = font_name.to_lowercase().contains("italic")
-            || font_name.to_lowercase().contains("oblique");
+    pub fn new(
+        text: String,
+        x: f32,
+        y: f32,
+        font_size: f32,
+        font_name: impl Into<Arc<str>>,
+    ) -> Self {
+        let font_name = font_name.into();
+        let lower = font_name.to_lowercase();
+        let is_bold = lower.contains("bold") || lower.contains("black") || lower.contains("heavy");
+        let is_italic = lower.contains("italic") || lower.contains("oblique");
+        let is_monospace = super::code::is_monospace_font_name(&font_name);
 
         Self {
             text,
@@ -48,6 +73,9 @@ impl TextSpan {
             font_name,
             is_bold,
             is_italic,
+            is_monospace,
+            char_x: Vec::new(),
+            ops: [0, 0],
         }
     }
 
@@ -126,12 +154,19 @@ impl TextLine {
     /// Inserts spaces between spans based on their X coordinate gaps.
     /// For CJK characters, no space is inserted between adjacent characters.
     pub fn text(&self) -> String {
+        self.text_with(&SpacingPolicy::default())
+    }
+
+    /// Get the combined text of all spans, spaced by `policy`.
+    pub fn text_with(&self, policy: &SpacingPolicy) -> String {
         if self.spans.is_empty() {
             return String::new();
         }
 
         if self.spans.len() == 1 {
-            return self.spans[0].text.clone();
+            let mut text = self.spans[0].text.clone();
+            policy.insert_forced_spaces(&mut text);
+            return text;
         }
 
         let mut result = String::new();
@@ -158,25 +193,17 @@ impl TextLine {
 
             // Check if we need to insert a space
             // Gap threshold: if gap is more than 20% of average char width, insert space
-            let space_threshold = avg_char_width * 0.2;
+            let space_threshold = avg_char_width * policy.gap_ratio;
 
             // Get last char of previous span and first char of current span
             let prev_last_char = prev_span.text.chars().last();
             let curr_first_char = span.text.chars().next();
 
-            let should_insert_space = if gap > space_threshold {
-                // Check if both characters are CJK (no space needed between CJK chars)
-                let prev_is_cjk = prev_last_char
-                    .map(is_spaceless_script_char)
-                    .unwrap_or(false);
-                let curr_is_cjk = curr_first_char
-                    .map(is_spaceless_script_char)
-                    .unwrap_or(false);
-
-                // Don't insert space between CJK characters
-                !(prev_is_cjk && curr_is_cjk)
-            } else {
-                false
+            // Don't insert space between CJK characters
+            let gap = gap > space_threshold;
+            let should_insert_space = match (prev_last_char, curr_first_char) {
+                (Some(prev), Some(curr)) => policy.space_between(prev, curr, gap),
+                _ => gap,
             };
 
             // Also check if previous span ends with space or current starts with space
@@ -191,6 +218,7 @@ impl TextLine {
 
             result.push_str(&span.text);
         }
+        policy.insert_forced_spaces(&mut result);
 
         // Apply BiDi reordering for RTL scripts (Arabic, Hebrew, etc.)
         if super::bidi::contains_rtl(&result) {
@@ -212,6 +240,24 @@ impl TextLine {
         total_chars > 0 && bold_chars as f32 / total_chars as f32 > 0.5
     }
 
+    /// Check if the line is set predominantly in a monospace font and reads
+    /// like code rather than CJK text (fixed-width CJK fonts are common).
+    pub fn is_monospace(&self) -> bool {
+        let (mut mono, mut total, mut ascii) = (0usize, 0usize, 0usize);
+        for span in &self.spans {
+            for c in span.text.chars().filter(|c| !c.is_whitespace()) {
+                total += 1;
+                if span.is_monospace {
+                    mono += 1;
+                }
+                if c.is_ascii() {
+                    ascii += 1;
+                }
+            }
+        }
+        total > 0 && mono * 2 > total && ascii * 5 >= total * 4
+    }
+
     /// Check if the line appears to be uppercase.
     pub fn is_uppercase(&self) -> bool {
         let text = self.text();
@@ -257,6 +303,49 @@ impl Column {
     }
 }
 
+/// Thresholds for splitting a page into text columns.
+///
+/// The defaults suit letter and A4 pages. Narrow pages (A5, pocket books)
+/// with wide margins or indented blocks can be split where they should not
+/// be; raise the thresholds or turn detection off for those.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnDetectionConfig {
+    /// Read every page as a single column
+    pub disable: bool,
+    /// Minimum empty space between columns (points). The gutter must also
+    /// be at least five times the page's median font size.
+    pub min_gutter_width: f32,
+    /// Minimum width of each column (points)
+    pub min_column_width: f32,
+    /// Maximum number of columns side by side; a page that splits into more
+    /// is read as a single column
+    pub max_columns: usize,
+    /// Pages whose text is narrower than this (points) are never split
+    pub min_page_width: f32,
+}
+
+impl Default for ColumnDetectionConfig {
+    fn default() -> Self {
+        Self {
+            disable: false,
+            min_gutter_width: 60.0,
+            min_column_width: 80.0,
+            max_columns: 4,
+            min_page_width: 250.0,
+        }
+    }
+}
+
+impl ColumnDetectionConfig {
+    /// Configuration that reads every page as a single column.
+    pub fn disabled() -> Self {
+        Self {
+            disable: true,
+            ..Self::default()
+        }
+    }
+}
+
 /// Type of text block.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BlockType {
@@ -266,6 +355,8 @@ pub enum BlockType {
     Paragraph,
     /// A list item
     ListItem,
+    /// A code listing in a monospace font
+    Code,
     /// Unknown or unclassified
     Unknown,
 }
@@ -281,12 +372,84 @@ impl TextBlock {
     }
 
     /// Get the combined text of all lines.
+    ///
+    /// Lines are joined into running text here, where line boundaries are
+    /// still known, rather than left as newlines for text cleanup to guess
+    /// at: a space separates lines, except between Chinese or Japanese
+    /// characters, which do not use word spaces.
     pub fn text(&self) -> String {
-        self.lines
-            .iter()
-            .map(|l| l.text())
-            .collect::<Vec<_>>()
-            .join(" ")
+        self.text_with(&SpacingPolicy::default())
+    }
+
+    /// Get the block's text like [`TextBlock::text`], spaced by `policy`.
+    pub fn text_with(&self, policy: &SpacingPolicy) -> String {
+        join_lines(self.lines.iter().map(|l| l.text_with(policy)), policy)
+    }
+
+    /// Get the block's text with its line structure and indentation, for
+    /// code listings.
+    ///
+    /// Horizontal positions are converted to columns using the font's
+    /// character pitch, and vertical gaps larger than the usual line spacing
+    /// become blank lines.
+    pub fn code_text(&self) -> String {
+        let Some(first) = self.lines.first() else {
+            return String::new();
+        };
+        let pitch = code_char_pitch(&self.lines);
+        let left = self.lines.iter().map(|l| l.x).fold(f32::INFINITY, f32::min);
+        let line_spacing = self
+            .lines
+            .windows(2)
+            .map(|w| (w[0].y - w[1].y).abs())
+            .filter(|s| *s > 0.1)
+            .fold(f32::INFINITY, f32::min);
+        let line_spacing = if line_spacing.is_finite() {
+            line_spacing
+        } else {
+            first.font_size * 1.2
+        };
+
+        let mut out = String::new();
+        let mut prev_y: Option<f32> = None;
+        for line in &self.lines {
+            if let Some(prev_y) = prev_y {
+                out.push('\n');
+                // 빈 줄은 스팬이 없으므로 세로 간격으로 복원한다.
+                let skipped = ((prev_y - line.y).abs() / line_spacing).round() as usize;
+                for _ in 1..skipped.min(4) {
+                    out.push('\n');
+                }
+            }
+            prev_y = Some(line.y);
+
+            let mut column = 0usize;
+            let mut prev_end: Option<f32> = None;
+            for span in &line.spans {
+                let chars = span.text.chars().count();
+                let target = ((span.x - left) / pitch).round().max(0.0) as usize;
+                if target > column {
+                    out.extend(std::iter::repeat(' ').take(target - column));
+                    column = target;
+                } else if let Some(end) = prev_end {
+                    // 피치 추정이 어긋나도 떨어진 스팬 사이는 한 칸 띄운다.
+                    let separated = span.x - end > pitch * 0.5;
+                    if separated && !out.ends_with(' ') && !span.text.starts_with(' ') {
+                        out.push(' ');
+                        column += 1;
+                    }
+                }
+                out.push_str(&span.text);
+                column += chars;
+                let width = if span.width > 0.0 {
+                    span.width
+                } else {
+                    chars as f32 * pitch
+                };
+                prev_end = Some(span.x + width);
+            }
+        }
+        out.trim_end().to_string()
     }
 
     /// Check if the block is empty.
@@ -309,6 +472,29 @@ pub struct LayoutAnalyzer<'a> {
     text_op_count: Cell<u32>,
     /// 마지막으로 분석한 페이지의 XObject `Do` 호출 수.
     image_op_count: Cell<u32>,
+    /// 마지막으로 분석한 페이지에서 찾은 텍스트 단 수의 최댓값.
+    column_count: Cell<u32>,
+    /// 마지막으로 분석한 페이지의 콘텐츠 스트림에서 해석하지 못해 건너뛴 바이트 수.
+    skipped_content_bytes: Cell<u32>,
+    /// 이 시각이 지나면 콘텐츠 스트림 분석을 `Error::Timeout` 으로 중단한다.
+    deadline: Option<Instant>,
+    /// 스팬마다 글자별 x 위치(`TextSpan::char_x`)를 기록할지 여부.
+    glyph_positions: bool,
+    /// 다단 분할 임계값.
+    columns: ColumnDetectionConfig,
+    /// 문자 체계 경계의 띄어쓰기와 공백 삽입 임계값.
+    spacing: SpacingPolicy,
+    /// 기울어진 글줄을 바로잡을지 여부.
+    deskew: bool,
+    /// 글자 크기가 한 가지뿐인 페이지에서 제목을 고르는 스타일 신호 설정.
+    headings: HeadingConfig,
+    /// 마지막으로 분석한 페이지의 콘텐츠 스트림 객체와 각 스트림의 첫 오퍼레이터 번호.
+    content_streams: RefCell<Vec<(PageId, usize)>>,
+    /// 분석한 페이지의 오퍼레이터를 남겨 둘지 여부 — 벡터 그래픽·이미지 위치 계산이
+    /// 스트림을 다시 디코딩하지 않도록.
+    retain_ops: bool,
+    /// `retain_ops` 일 때 마지막으로 분석한 페이지의 오퍼레이터.
+    page_ops: RefCell<Option<Vec<ContentOp>>>,
 }
 
 /// What a page's content stream says about how its text was produced.
@@ -427,15 +613,94 @@ impl<'a> LayoutAnalyzer<'a> {
             ocr_text_suppressed: Cell::new(false),
             text_op_count: Cell::new(0),
             image_op_count: Cell::new(0),
+            column_count: Cell::new(0),
+            skipped_content_bytes: Cell::new(0),
+            deadline: None,
+            glyph_positions: false,
+            columns: ColumnDetectionConfig::default(),
+            spacing: SpacingPolicy::default(),
+            deskew: false,
+            headings: HeadingConfig::default(),
+            content_streams: RefCell::new(Vec::new()),
+            retain_ops: false,
+            page_ops: RefCell::new(None),
         }
     }
 
+    /// Abort content stream analysis with [`Error::Timeout`] once `deadline`
+    /// has passed.
+    pub fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Fail with [`Error::Timeout`] if the deadline has passed.
+    pub fn check_deadline(&self) -> Result<()> {
+        if super::options::is_expired(self.deadline) {
+            return Err(Error::Timeout(
+                "page analysis exceeded its time budget".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Enable or disable dropping of low-confidence OCR text layers.
     pub fn with_ocr_suppression(mut self, enabled: bool) -> Self {
         self.suppress_low_confidence_ocr = enabled;
         self
     }
 
+    /// Record each character's position in `TextSpan::char_x`. Costs one
+    /// text decode per glyph, so it is off by default.
+    pub fn with_glyph_positions(mut self, enabled: bool) -> Self {
+        self.glyph_positions = enabled;
+        self
+    }
+
+    /// Set the thresholds used to split pages into columns.
+    pub fn with_column_detection(mut self, config: ColumnDetectionConfig) -> Self {
+        self.columns = config;
+        self
+    }
+
+    /// Set the rules for putting word spaces into extracted text.
+    pub fn with_spacing(mut self, policy: SpacingPolicy) -> Self {
+        self.spacing = policy;
+        self
+    }
+
+    /// Rotate spans back to horizontal when the page's text is slightly
+    /// skewed. See [`ParseOptions::deskew`](super::ParseOptions::deskew).
+    pub fn with_deskew(mut self, enabled: bool) -> Self {
+        self.deskew = enabled;
+        self
+    }
+
+    /// Heading detection settings. See
+    /// [`ParseOptions::heading_config`](super::ParseOptions::heading_config).
+    pub fn with_heading_config(mut self, config: HeadingConfig) -> Self {
+        self.headings = config;
+        self
+    }
+
+    /// Keep the decoded operators of the page analysed last, for
+    /// [`take_page_ops`](Self::take_page_ops).
+    pub fn with_retained_ops(mut self, enabled: bool) -> Self {
+        self.retain_ops = enabled;
+        self
+    }
+
+    /// The decoded operators of the page analysed last, when
+    /// [`with_retained_ops`](Self::with_retained_ops) is on.
+    pub fn take_page_ops(&self) -> Option<Vec<ContentOp>> {
+        self.page_ops.borrow_mut().take()
+    }
+
+    /// The rules for putting word spaces into extracted text.
+    pub fn spacing(&self) -> &SpacingPolicy {
+        &self.spacing
+    }
+
     /// Whether any page analysed so far had its OCR text layer dropped.
     pub fn ocr_text_suppressed(&self) -> bool {
         self.ocr_text_suppressed.get()
@@ -448,6 +713,36 @@ impl<'a> LayoutAnalyzer<'a> {
         (self.text_op_count.get(), self.image_op_count.get())
     }
 
+    /// Bytes of the content streams of the page analysed last that were
+    /// skipped as unparseable.
+    pub fn skipped_content_bytes(&self) -> u32 {
+        self.skipped_content_bytes.get()
+    }
+
+    /// Text columns found on the page analysed last: 1 for a single-column
+    /// page, 0 when no text was laid out.
+    pub fn column_count(&self) -> u32 {
+        self.column_count.get()
+    }
+
+    /// Most common font size of the text analysed so far, if any.
+    pub fn body_font_size(&self) -> Option<f32> {
+        (!self.font_stats.size_histogram.is_empty()).then_some(self.font_stats.body_size)
+    }
+
+    /// 이번 페이지에서 찾은 단 수를 기록한다. 한 페이지를 여러 번 나눠
+    /// 해석하므로 가장 큰 값을 남긴다.
+    fn record_columns(&self, columns: usize) {
+        let columns = u32::try_from(columns).unwrap_or(u32::MAX);
+        self.column_count.set(self.column_count.get().max(columns));
+    }
+
+    /// Source of operators `ops[0]..=ops[1]` of the page analysed last,
+    /// with the content streams holding them.
+    pub fn block_source(&self, ops: [u32; 2]) -> BlockSource {
+        source_for_ops(&self.content_streams.borrow(), ops[0], ops[1])
+    }
+
     /// Get mutable reference to font statistics (for external use).
     pub fn font_stats_mut(&mut self) -> &mut FontStatistics {
         &mut self.font_stats
@@ -499,8 +794,15 @@ impl<'a> LayoutAnalyzer<'a> {
             );
         }
 
-        let content = self.backend.page_content(*page_id)?;
-        let (spans, signals) = self.parse_operations(&content, &fonts, *page_id)?;
+        let content = self.backend.page_operations(*page_id)?;
+        *self.content_streams.borrow_mut() = content.streams;
+        self.skipped_content_bytes
+            .set(u32::try_from(content.skipped_bytes).unwrap_or(u32::MAX));
+        let (spans, signals) = self.parse_operations(&content.ops, &fonts, *page_id)?;
+        let spans = coalesce_glyph_spans(spans);
+        if self.retain_ops {
+            *self.page_ops.borrow_mut() = Some(content.ops);
+        }
 
         if self.suppress_low_confidence_ocr && signals.is_ocr_layer_over_scan() {
             let text = spans
@@ -518,9 +820,34 @@ impl<'a> LayoutAnalyzer<'a> {
             }
         }
 
+        if self.deskew {
+            let mut spans = spans;
+            if let Some(angle) = super::deskew::estimate_skew(&spans) {
+                log::debug!(
+                    "Page {}: straightening text skewed by {:.2}°",
+                    page_num,
+                    angle.to_degrees()
+                );
+                super::deskew::rotate_spans(&mut spans, -angle);
+            }
+            return Ok(spans);
+        }
+
         Ok(spans)
     }
 
+    /// Words on a page with their bounding boxes, numbered by line.
+    ///
+    /// Header and footer lines are kept, since a viewer highlights matches
+    /// anywhere on the page. Build the analyzer `with_glyph_positions(true)`
+    /// for boxes from glyph advances; without it each span's width is spread
+    /// evenly over its characters.
+    pub fn extract_page_words(&self, page_num: u32) -> Result<Vec<Word>> {
+        let spans = self.extract_page_spans(page_num)?;
+        let lines = self.group_spans_into_lines(spans);
+        Ok(words_from_lines(&lines))
+    }
+
     /// Extract structured text blocks from a page.
     pub fn extract_page_blocks(&mut self, page_num: u32) -> Result<Vec<TextBlock>> {
         // Get page dimensions for header/footer filtering
@@ -559,14 +886,14 @@ impl<'a> LayoutAnalyzer<'a> {
     /// keeping layout.rs free from concrete PDF library types.
     fn parse_operations(
         &self,
-        content: &[u8],
+        operations: &[ContentOp],
         fonts: &HashMap<Vec<u8>, FontInfo>,
         page_id: super::backend::PageId,
     ) -> Result<(Vec<TextSpan>, PageTextLayerSignals)> {
-        let operations = self.backend.decode_content(content)?;
         // 페이지 오퍼레이터 통계 리셋 — 같은 페이지를 재분석해도(fallback 경로)
         // 마지막 호출의 집계가 그대로 유효하도록 진입 시점에 0으로 되돌린다.
         self.text_op_count.set(0);
+        self.column_count.set(0);
         self.image_op_count.set(0);
         let page_area = {
             let (w, h) = self.backend.page_dimensions(page_id);
@@ -580,16 +907,31 @@ impl<'a> LayoutAnalyzer<'a> {
         let mut signals = PageTextLayerSignals::default();
 
         let mut spans = Vec::new();
-        let mut current_font = String::new();
+        // 스팬마다 글꼴 이름을 복제하지 않도록 공유 핸들로 돌려 쓴다.
+        let mut font_names = StringInterner::default();
+        let mut current_font: Arc<str> = font_names.intern("");
         let mut current_font_name: Vec<u8> = Vec::new();
         let mut current_font_size: f32 = 12.0;
+        // 글리프 폭 — 스팬 폭 계산과 텍스트 행렬 전진, TJ 공백 판정에 쓴다.
+        let mut current_metrics: Option<Arc<FontMetrics>> = None;
+        let mut current_fixed_pitch = false;
+        let mut metrics_by_font: HashMap<Vec<u8>, Option<Arc<FontMetrics>>> = HashMap::new();
+        // Tf 로 고른 글꼴도 그래픽 상태의 일부다.
+        let mut font_stack: Vec<SavedFont> = Vec::new();
         let mut text_matrix = TextMatrix::default();
+        // Tc/Tw/Tz/TL — 그래픽 상태의 일부라 q/Q 로 저장·복원된다.
+        let mut text_state = TextState::default();
+        let mut text_state_stack: Vec<TextState> = Vec::new();
         let mut in_text_block = false;
         // Current Transformation Matrix (starts as identity [1,0,0,1,0,0])
         let mut ctm: [f32; 6] = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
         let mut ctm_stack: Vec<[f32; 6]> = Vec::new();
 
-        for op in &operations {
+        for (i, op) in operations.iter().enumerate() {
+            // 시계 호출 비용을 줄이려고 1024 개마다 확인한다.
+            if i % 1024 == 0 {
+                self.check_deadline()?;
+            }
             // 페이지 판별용 오퍼레이터 통계 — 아래 본 match 의 가드 조건과
             // 무관하게 항상 집계한다 (`Do` arm 은 page_area 가드가 있음).
             match op.operator.as_str() {
@@ -601,6 +943,14 @@ impl<'a> LayoutAnalyzer<'a> {
                 "q" => {
                     ctm_stack.push(ctm);
                     render_mode_stack.push(render_mode);
+                    text_state_stack.push(text_state);
+                    font_stack.push((
+                        Arc::clone(&current_font),
+                        current_font_name.clone(),
+                        current_font_size,
+                        current_metrics.clone(),
+                        current_fixed_pitch,
+                    ));
                 }
                 "Q" => {
                     if let Some(saved) = ctm_stack.pop() {
@@ -609,6 +959,18 @@ impl<'a> LayoutAnalyzer<'a> {
                     if let Some(saved) = render_mode_stack.pop() {
                         render_mode = saved;
                     }
+                    if let Some(saved) = text_state_stack.pop() {
+                        text_state = saved;
+                    }
+                    if let Some(saved) = font_stack.pop() {
+                        (
+                            current_font,
+                            current_font_name,
+                            current_font_size,
+                            current_metrics,
+                            current_fixed_pitch,
+                        ) = saved;
+                    }
                 }
                 "Tr" if !op.operands.is_empty() => {
                     if let Some(mode) = get_number_from_value(&op.operands[0]) {
@@ -633,7 +995,8 @@ impl<'a> LayoutAnalyzer<'a> {
                         get_number_from_value(&op.operands[4]).unwrap_or(0.0),
                         get_number_from_value(&op.operands[5]).unwrap_or(0.0),
                     ];
-                    ctm = concat_matrix(&ctm, &cm);
+                    // CTM' = cm × CTM: 새 행렬이 먼저 적용되고 기존 변환이 뒤따른다.
+                    ctm = concat_matrix(&cm, &ctm);
                 }
                 "BT" => {
                     in_text_block = true;
@@ -645,19 +1008,40 @@ impl<'a> LayoutAnalyzer<'a> {
                 "Tf" if op.operands.len() >= 2 => {
                     if let PdfValue::Name(font_name) = &op.operands[0] {
                         current_font_name = font_name.clone();
-                        if let Some(info) = fonts.get(font_name.as_slice()) {
-                            current_font = info.name.clone();
-                        } else {
-                            current_font =
-                                String::from_utf8_lossy(font_name.as_slice()).to_string();
-                        }
+                        current_font = match fonts.get(font_name.as_slice()) {
+                            Some(info) => font_names.intern(&info.name),
+                            None => font_names.intern(&String::from_utf8_lossy(font_name)),
+                        };
                     }
                     current_font_size = get_number_from_value(&op.operands[1]).unwrap_or(12.0);
+                    current_metrics = metrics_by_font
+                        .entry(current_font_name.clone())
+                        .or_insert_with(|| self.backend.font_metrics(page_id, &current_font_name))
+                        .clone();
+                    current_fixed_pitch = current_metrics
+                        .as_deref()
+                        .is_some_and(FontMetrics::is_fixed_pitch);
+                }
+                "Tc" if !op.operands.is_empty() => {
+                    text_state.char_spacing = get_number_from_value(&op.operands[0]).unwrap_or(0.0);
+                }
+                "Tw" if !op.operands.is_empty() => {
+                    text_state.word_spacing = get_number_from_value(&op.operands[0]).unwrap_or(0.0);
+                }
+                "Tz" if !op.operands.is_empty() => {
+                    text_state.horizontal_scaling =
+                        get_number_from_value(&op.operands[0]).unwrap_or(100.0) / 100.0;
+                }
+                "TL" if !op.operands.is_empty() => {
+                    text_state.leading = get_number_from_value(&op.operands[0]);
                 }
                 "Td" | "TD" if op.operands.len() >= 2 => {
                     let tx = get_number_from_value(&op.operands[0]).unwrap_or(0.0);
                     let ty = get_number_from_value(&op.operands[1]).unwrap_or(0.0);
-                    text_matrix.translate(tx, ty);
+                    if op.operator == "TD" {
+                        text_state.leading = Some(-ty);
+                    }
+                    text_matrix.move_line(tx, ty);
                 }
                 "Tm" if op.operands.len() >= 6 => {
                     text_matrix.set(
@@ -670,95 +1054,77 @@ impl<'a> LayoutAnalyzer<'a> {
                     );
                 }
                 "T*" => {
-                    text_matrix.next_line();
+                    text_matrix.next_line(text_state.line_leading(current_font_size));
                 }
                 "Tj" | "TJ" if in_text_block => {
-                    let text = if op.operator == "TJ" {
-                        // TJ: array of strings and positioning adjustments
-                        // Numbers indicate kerning/spacing adjustments in 1/1000 text space units
-                        // Large negative values (like -200 to -300) often indicate word spaces
-                        if let Some(PdfValue::Array(arr)) = op.operands.first() {
-                            let mut combined = String::new();
-
-                            for item in arr {
-                                match item {
-                                    PdfValue::Str(bytes) => {
-                                        combined.push_str(&self.backend.decode_text(
-                                            page_id,
-                                            &current_font_name,
-                                            bytes,
-                                        ));
-                                    }
-                                    PdfValue::Integer(n) => {
-                                        let adjustment = -(*n as f32);
-                                        maybe_insert_space_tj(&mut combined, adjustment);
-                                    }
-                                    PdfValue::Real(n) => {
-                                        let adjustment = -n;
-                                        maybe_insert_space_tj(&mut combined, adjustment);
-                                    }
-                                    _ => {}
-                                }
-                            }
-                            combined
-                        } else {
-                            String::new()
-                        }
-                    } else {
-                        // Tj: single string
-                        if let Some(PdfValue::Str(bytes)) = op.operands.first() {
-                            self.backend.decode_text(page_id, &current_font_name, bytes)
-                        } else {
-                            String::new()
-                        }
-                    };
-
-                    if !text.trim().is_empty() {
+                    let (text, advance, offsets) = self.show_text(
+                        op,
+                        page_id,
+                        &current_font_name,
+                        current_metrics.as_deref(),
+                        current_font_size,
+                        &text_state,
+                    );
+                    let span = place_span(
+                        text,
+                        advance,
+                        &mut text_matrix,
+                        &ctm,
+                        current_font_size,
+                        &current_font,
+                        current_fixed_pitch,
+                    );
+                    if let Some(mut span) = span {
                         count_render_mode(
-                            &text,
+                            &span.text,
                             render_mode,
                             &mut total_chars,
                             &mut invisible_chars,
                         );
-                        let (tx, ty) = text_matrix.get_position();
-                        let (x, y) = apply_ctm(&ctm, tx, ty);
-                        let effective_size =
-                            current_font_size * text_matrix.get_scale() * ctm_y_scale(&ctm);
-                        spans.push(TextSpan::new(
-                            text,
-                            x,
-                            y,
-                            effective_size,
-                            current_font.clone(),
-                        ));
+                        span.char_x = char_positions(&span, advance, &offsets);
+                        span.ops = [i as u32, i as u32];
+                        spans.push(span);
                     }
                 }
                 "'" | "\"" => {
-                    text_matrix.next_line();
+                    if op.operator == "\"" && op.operands.len() >= 3 {
+                        // aw ac string " — sets word and character spacing first.
+                        if let Some(aw) = get_number_from_value(&op.operands[0]) {
+                            text_state.word_spacing = aw;
+                        }
+                        if let Some(ac) = get_number_from_value(&op.operands[1]) {
+                            text_state.char_spacing = ac;
+                        }
+                    }
+                    text_matrix.next_line(text_state.line_leading(current_font_size));
                     if in_text_block {
-                        let text_idx = if op.operator == "\"" { 2 } else { 0 };
-                        if let Some(PdfValue::Str(bytes)) = op.operands.get(text_idx) {
-                            let text = self.backend.decode_text(page_id, &current_font_name, bytes);
-
-                            if !text.trim().is_empty() {
-                                count_render_mode(
-                                    &text,
-                                    render_mode,
-                                    &mut total_chars,
-                                    &mut invisible_chars,
-                                );
-                                let (tx, ty) = text_matrix.get_position();
-                                let (x, y) = apply_ctm(&ctm, tx, ty);
-                                let effective_size =
-                                    current_font_size * text_matrix.get_scale() * ctm_y_scale(&ctm);
-                                spans.push(TextSpan::new(
-                                    text,
-                                    x,
-                                    y,
-                                    effective_size,
-                                    current_font.clone(),
-                                ));
-                            }
+                        let (text, advance, offsets) = self.show_text(
+                            op,
+                            page_id,
+                            &current_font_name,
+                            current_metrics.as_deref(),
+                            current_font_size,
+                            &text_state,
+                        );
+                        let span = place_span(
+                            text,
+                            advance,
+                            &mut text_matrix,
+                            &ctm,
+                            current_font_size,
+                            &current_font,
+                            current_fixed_pitch,
+                        );
+                        if let Some(mut span) = span {
+                            count_render_mode(
+                                &span.text,
+                                render_mode,
+                                &mut total_chars,
+                                &mut invisible_chars,
+                            );
+                            span.char_x = char_positions(&span, advance, &offsets);
+                            span.ops = [i as u32, i as u32];
+                            spans.push(span);
                         }
                     }
                 }
@@ -773,6 +1139,95 @@ impl<'a> LayoutAnalyzer<'a> {
         Ok((spans, signals))
     }
 
+    /// Decode the string operand(s) of a text-showing operator.
+    ///
+    /// Returns the text and, when the font's glyph widths are known, the
+    /// horizontal advance in text space units (font size, character and word
+    /// spacing, and horizontal scaling applied; text matrix not). TJ spacing
+    /// uses the font's own space width. With glyph positions enabled and
+    /// widths known, the third value is each character's offset from the
+    /// start in the same units; otherwise it is empty.
+    fn show_text(
+        &self,
+        op: &super::backend::ContentOp,
+        page_id: super::backend::PageId,
+        font_name: &[u8],
+        metrics: Option<&FontMetrics>,
+        font_size: f32,
+        state: &TextState,
+    ) -> (String, Option<f32>, Vec<f32>) {
+        let mut advance = metrics.map(|_| 0.0f32);
+        let mut offsets: Vec<f32> = Vec::new();
+        let record = self.glyph_positions && metrics.is_some();
+        let add_string =
+            |bytes: &[u8], out: &mut String, advance: &mut Option<f32>, offsets: &mut Vec<f32>| {
+                let decoded = self.backend.decode_text(page_id, font_name, bytes);
+                if let (Some(adv), Some(m)) = (advance.as_mut(), metrics) {
+                    if record {
+                        // 글리프 하나씩 디코딩해 글자마다 펜 위치를 붙인다. 합친 결과가
+                        // 통째 디코딩과 다르면(합자·다중 바이트 매핑) 폭을 고르게 나눈다.
+                        let mut pen = *adv;
+                        let mut per_glyph = String::new();
+                        let mut glyph_offsets = Vec::new();
+                        for code in bytes.chunks(m.code_width.max(1)) {
+                            let glyph = self.backend.decode_text(page_id, font_name, code);
+                            glyph_offsets.extend(glyph.chars().map(|_| pen));
+                            per_glyph.push_str(&glyph);
+                            pen += state.string_advance(m, code, font_size);
+                        }
+                        if per_glyph == decoded {
+                            offsets.extend(glyph_offsets);
+                        } else {
+                            let count = decoded.chars().count();
+                            let step = (pen - *adv) / count.max(1) as f32;
+                            offsets.extend((0..count).map(|i| *adv + step * i as f32));
+                        }
+                    }
+                    *adv += state.string_advance(m, bytes, font_size);
+                }
+                out.push_str(&decoded);
+            };
+
+        let mut text = String::new();
+        match op.operator.as_str() {
+            "TJ" => {
+                // TJ: array of strings and positioning adjustments
+                // Numbers indicate kerning/spacing adjustments in 1/1000 text space units
+                // Large negative values (like -200 to -300) often indicate word spaces
+                if let Some(PdfValue::Array(arr)) = op.operands.first() {
+                    for item in arr {
+                        let adjustment = match item {
+                            PdfValue::Str(bytes) => {
+                                add_string(bytes, &mut text, &mut advance, &mut offsets);
+                                continue;
+                            }
+                            PdfValue::Integer(n) => -(*n as f32),
+                            PdfValue::Real(n) => -n,
+                            _ => continue,
+                        };
+                        let len = text.len();
+                        maybe_insert_space_tj(&mut text, adjustment, metrics, &self.spacing);
+                        if record && text.len() > len {
+                            // 끼워 넣은 공백은 간격이 시작되는 펜 위치에 둔다.
+                            offsets.push(advance.unwrap_or(0.0));
+                        }
+                        if let Some(adv) = advance.as_mut() {
+                            *adv += adjustment / 1000.0 * font_size * state.horizontal_scaling;
+                        }
+                    }
+                }
+            }
+            _ => {
+                // Tj / ' take the string first; " takes it after word and char spacing.
+                let text_idx = if op.operator == "\"" { 2 } else { 0 };
+                if let Some(PdfValue::Str(bytes)) = op.operands.get(text_idx) {
+                    add_string(bytes, &mut text, &mut advance, &mut offsets);
+                }
+            }
+        }
+        (text, advance, offsets)
+    }
+
     /// Detect columns in a page based on vertical gap (gutter) detection.
     ///
     /// This looks for vertical empty spaces between text regions to identify
@@ -797,7 +1252,7 @@ impl<'a> LayoutAnalyzer<'a> {
         let page_width = max_x - min_x;
 
         // Don't detect columns if page is too narrow
-        if page_width < 250.0 {
+        if !self.may_split_columns(spans) {
             return vec![Column {
                 left: min_x - 10.0,
                 right: max_x + 10.0,
@@ -905,9 +1360,12 @@ impl<'a> LayoutAnalyzer<'a> {
             page_width
         );
 
-        // Require a minimum gap width for column detection (at least 12 points)
-        if gap_width < 12.0 {
-            log::debug!("Gap too small (< 12pt), treating as single column");
+        // Require a minimum gap width for column detection
+        if gap_width < self.columns.min_gutter_width {
+            log::debug!(
+                "Gap too small (< {}pt), treating as single column",
+                self.columns.min_gutter_width
+            );
             return vec![Column {
                 left: min_x - 10.0,
                 right: max_x + 10.0,
@@ -919,7 +1377,7 @@ impl<'a> LayoutAnalyzer<'a> {
         let gutter_center =
             min_x + (best_gap_start as f32 + best_gap_len as f32 / 2.0) * slice_width;
 
-        // Validate that both columns have reasonable width (at least 80 points each)
+        // Validate that both columns have reasonable width
         let left_col_width = gutter_center - min_x;
         let right_col_width = max_x - gutter_center;
 
@@ -929,7 +1387,8 @@ impl<'a> LayoutAnalyzer<'a> {
             right_col_width
         );
 
-        if left_col_width < 80.0 || right_col_width < 80.0 {
+        let min_width = self.columns.min_column_width;
+        if left_col_width < min_width || right_col_width < min_width {
             log::debug!("Column too narrow, treating as single column");
             return vec![Column {
                 left: min_x - 10.0,
@@ -986,9 +1445,26 @@ impl<'a> LayoutAnalyzer<'a> {
     /// other complex structures. Each segmented region is processed independently
     /// as a single-column block.
     fn group_spans_into_lines(&self, spans: Vec<TextSpan>) -> Vec<TextLine> {
+        let mut regions = self.layout_regions(spans);
+        if regions.len() <= 1 {
+            return self.group_spans_into_lines_single_column(regions.pop().unwrap_or_default());
+        }
+        regions
+            .into_iter()
+            .flat_map(|region| self.group_spans_into_lines_single_column(region))
+            .collect()
+    }
+
+    /// Split a page's spans into the regions XY-Cut finds on a multi-column
+    /// layout, in reading order. A single-column page is one region.
+    pub fn layout_regions(&self, spans: Vec<TextSpan>) -> Vec<Vec<TextSpan>> {
         if spans.is_empty() {
             return vec![];
         }
+        if !self.may_split_columns(&spans) {
+            self.record_columns(1);
+            return vec![spans];
+        }
 
         // Convert spans to XY-cut blocks
         let blocks: Vec<super::xycut::Block> = spans
@@ -1007,42 +1483,57 @@ impl<'a> LayoutAnalyzer<'a> {
         // indentation, which previously fragmented pages into dozens of
         // groups on Hancom-produced PDFs.
         let median_font = median_font_size(&spans);
-        let min_x_gap = (median_font * 5.0).max(60.0);
+        let min_x_gap = (median_font * 5.0).max(self.columns.min_gutter_width);
         let min_y_gap = (median_font * 3.0).max(36.0);
 
-        let groups = super::xycut::xycut_segment(&blocks, min_x_gap, min_y_gap);
+        let (groups, columns) = super::xycut::xycut_columns(
+            &blocks,
+            min_x_gap,
+            min_y_gap,
+            self.columns.min_column_width,
+        );
 
         log::debug!(
-            "XY-Cut segmented {} spans into {} groups (median_font={:.1}, min_x_gap={:.1}, min_y_gap={:.1})",
+            "XY-Cut segmented {} spans into {} groups, {} columns (median_font={:.1}, min_x_gap={:.1}, min_y_gap={:.1})",
             spans.len(),
             groups.len(),
+            columns,
             median_font,
             min_x_gap,
             min_y_gap,
         );
 
-        if groups.len() <= 1 {
-            // Single column — use simple grouping
-            return self.group_spans_into_lines_single_column(spans);
+        if groups.len() <= 1 || columns > self.columns.max_columns {
+            // Single column
+            self.record_columns(1);
+            return vec![spans];
         }
+        self.record_columns(columns.max(1));
 
-        // Multi-column: process each group independently
-        let mut all_lines = Vec::new();
-        for group in &groups {
-            // Match spans to this group's blocks by position
-            let group_spans: Vec<TextSpan> = spans
-                .iter()
-                .filter(|s| {
-                    group
-                        .iter()
-                        .any(|b| (s.x - b.x).abs() < 1.0 && (s.y - b.y).abs() < 1.0)
-                })
-                .cloned()
-                .collect();
-            let lines = self.group_spans_into_lines_single_column(group_spans);
-            all_lines.extend(lines);
-        }
-        all_lines
+        // Multi-column: match spans to each group's blocks by position
+        groups
+            .iter()
+            .map(|group| {
+                spans
+                    .iter()
+                    .filter(|s| {
+                        group
+                            .iter()
+                            .any(|b| (s.x - b.x).abs() < 1.0 && (s.y - b.y).abs() < 1.0)
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Whether the column thresholds allow splitting a page holding `spans`.
+    fn may_split_columns(&self, spans: &[TextSpan]) -> bool {
+        let min_x = spans.iter().map(|s| s.x).fold(f32::MAX, f32::min);
+        let max_x = spans.iter().map(|s| s.x + s.width).fold(f32::MIN, f32::max);
+        !self.columns.disable
+            && self.columns.max_columns >= 2
+            && max_x - min_x >= self.columns.min_page_width
     }
 
     /// Group spans into lines using the legacy column-detection approach.
@@ -1181,12 +1672,25 @@ impl<'a> LayoutAnalyzer<'a> {
         lines
     }
 
-    /// Detect headings based on font size hierarchy.
+    /// Detect headings based on font size hierarchy, falling back to style
+    /// signals on pages set in a single size.
     fn detect_headings(&self, mut lines: Vec<TextLine>) -> Vec<TextLine> {
         // Snapshot each line's font size so neighbour lookups aren't polluted
         // by mutations inside the loop.
         let sizes: Vec<f32> = lines.iter().map(|l| l.font_size).collect();
         let body_size = self.font_stats.body_size;
+        // 본문보다 1.5pt 이상 큰 글자가 없으면 크기로는 제목을 찾을 수 없다.
+        let single_size = self
+            .font_stats
+            .heading_sizes
+            .iter()
+            .all(|&s| s < body_size + 1.5);
+        let ys: Vec<f32> = lines.iter().map(|l| l.y).collect();
+        let pitch = if single_size {
+            super::headings::line_pitch(&ys)
+        } else {
+            None
+        };
 
         for (i, line) in lines.iter_mut().enumerate() {
             let visible_chars: usize = line
@@ -1194,7 +1698,7 @@ impl<'a> LayoutAnalyzer<'a> {
                 .chars()
                 .filter(|c| !c.is_whitespace() && !c.is_ascii_punctuation())
                 .count();
-            if visible_chars < 3 {
+            if visible_chars < 3 || line.is_monospace() {
                 continue;
             }
 
@@ -1210,6 +1714,18 @@ impl<'a> LayoutAnalyzer<'a> {
                 }
             }
 
+            if single_size {
+                // 페이지 첫 줄은 위가 비어 있는 것으로 본다.
+                let space_above =
+                    i == 0 || super::headings::has_space_above(ys[i - 1] - ys[i], pitch);
+                let level = super::headings::style_heading_level(line, space_above, &self.headings);
+                if level > 0 {
+                    line.is_heading = true;
+                    line.heading_level = level;
+                }
+                continue;
+            }
+
             let level = self
                 .font_stats
                 .get_heading_level(line.font_size, line.is_bold() || line.is_uppercase());
@@ -1271,23 +1787,7 @@ impl<'a> LayoutAnalyzer<'a> {
             if should_break {
                 // Create block from current lines
                 if !current_block_lines.is_empty() {
-                    let block_type = if current_block_lines.iter().any(|l| l.is_heading) {
-                        BlockType::Heading
-                    } else {
-                        BlockType::Paragraph
-                    };
-                    let mut block =
-                        TextBlock::new(std::mem::take(&mut current_block_lines), block_type);
-                    if block_type == BlockType::Heading {
-                        block.heading_level = block
-                            .lines
-                            .iter()
-                            .filter(|l| l.is_heading)
-                            .map(|l| l.heading_level)
-                            .min()
-                            .unwrap_or(0);
-                    }
-                    blocks.push(block);
+                    blocks.push(finish_block(std::mem::take(&mut current_block_lines)));
                 }
             }
 
@@ -1296,22 +1796,7 @@ impl<'a> LayoutAnalyzer<'a> {
 
         // Don't forget the last block
         if !current_block_lines.is_empty() {
-            let block_type = if current_block_lines.iter().any(|l| l.is_heading) {
-                BlockType::Heading
-            } else {
-                BlockType::Paragraph
-            };
-            let mut block = TextBlock::new(current_block_lines, block_type);
-            if block_type == BlockType::Heading {
-                block.heading_level = block
-                    .lines
-                    .iter()
-                    .filter(|l| l.is_heading)
-                    .map(|l| l.heading_level)
-                    .min()
-                    .unwrap_or(0);
-            }
-            blocks.push(block);
+            blocks.push(finish_block(current_block_lines));
         }
 
         blocks
@@ -1343,6 +1828,20 @@ impl<'a> LayoutAnalyzer<'a> {
         curr_line: &TextLine,
         avg_spacing: f32,
     ) -> bool {
+        // Code listings never share a block with prose. Within a listing,
+        // indentation varies freely and single blank lines are part of the
+        // code, so only a wider gap or a size change ends it.
+        let prev_mono = prev_line.is_monospace();
+        if prev_mono != curr_line.is_monospace() {
+            return true;
+        }
+        if prev_mono && !prev_line.is_heading && !curr_line.is_heading {
+            let spacing = (prev_line.y - curr_line.y).abs();
+            let size = prev_line.font_size.max(curr_line.font_size);
+            return spacing > size * 3.5
+                || (prev_line.font_size - curr_line.font_size).abs() >= 2.0;
+        }
+
         // Heading always starts a new block, UNLESS the previous line is
         // also a heading of the same level sitting close by (within ~2x
         // line-height). This merges decorative stacked titles on covers —
@@ -1393,6 +1892,29 @@ impl<'a> LayoutAnalyzer<'a> {
     }
 }
 
+/// Build a block from grouped lines, classifying it as a heading (at its most
+/// prominent line's level), a code listing, or a paragraph.
+fn finish_block(lines: Vec<TextLine>) -> TextBlock {
+    let block_type = if lines.iter().any(|l| l.is_heading) {
+        BlockType::Heading
+    } else if lines.iter().all(TextLine::is_monospace) {
+        BlockType::Code
+    } else {
+        BlockType::Paragraph
+    };
+    let mut block = TextBlock::new(lines, block_type);
+    if block_type == BlockType::Heading {
+        block.heading_level = block
+            .lines
+            .iter()
+            .filter(|l| l.is_heading)
+            .map(|l| l.heading_level)
+            .min()
+            .unwrap_or(0);
+    }
+    block
+}
+
 /// Filter out header/footer text spans (page numbers, running headers).
 ///
 /// Removes spans in the top/bottom margin that contain only numbers or short
@@ -1499,7 +2021,10 @@ struct TextMatrix {
     d: f32,
     e: f32, // X translation
     f: f32, // Y translation
-    line_y: f32,
+    /// Translation of the line matrix (`Tlm`): where the current line
+    /// started, which `Td`, `TD` and `T*` move from.
+    line_e: f32,
+    line_f: f32,
 }
 
 impl Default for TextMatrix {
@@ -1511,7 +2036,8 @@ impl Default for TextMatrix {
             d: 1.0,
             e: 0.0,
             f: 0.0,
-            line_y: 0.0,
+            line_e: 0.0,
+            line_f: 0.0,
         }
     }
 }
@@ -1524,21 +2050,27 @@ impl TextMatrix {
         self.d = d;
         self.e = e;
         self.f = f;
-        self.line_y = f;
+        self.line_e = e;
+        self.line_f = f;
     }
 
-    fn translate(&mut self, tx: f32, ty: f32) {
-        self.e += tx * self.a + ty * self.c;
-        self.f += tx * self.b + ty * self.d;
-        if ty != 0.0 {
-            self.line_y = self.f;
-        }
+    /// `Td`: start a new line offset from the start of the current one, not
+    /// from wherever the last string left the pen.
+    fn move_line(&mut self, tx: f32, ty: f32) {
+        self.line_e += tx * self.a + ty * self.c;
+        self.line_f += tx * self.b + ty * self.d;
+        self.e = self.line_e;
+        self.f = self.line_f;
     }
 
-    fn next_line(&mut self) {
-        // Default line leading (could be set by TL operator)
-        self.f -= 12.0 * self.d;
-        self.line_y = self.f;
+    /// Move the pen past a shown string, leaving the line start in place.
+    fn advance(&mut self, tx: f32) {
+        self.e += tx * self.a;
+        self.f += tx * self.b;
+    }
+
+    fn next_line(&mut self, leading: f32) {
+        self.move_line(0.0, -leading);
     }
 
     fn get_position(&self) -> (f32, f32) {
@@ -1551,41 +2083,240 @@ impl TextMatrix {
     }
 }
 
+/// The font selected by `Tf` — interned name, resource name, size, glyph
+/// widths and fixed pitch — as saved by `q`.
+type SavedFont = (Arc<str>, Vec<u8>, f32, Option<Arc<FontMetrics>>, bool);
+
+/// Text state parameters that affect glyph positioning (PDF 32000-1 §9.3).
+#[derive(Debug, Clone, Copy)]
+struct TextState {
+    /// `Tc`, in unscaled text space units, added after every glyph
+    char_spacing: f32,
+    /// `Tw`, added after every single-byte code 32
+    word_spacing: f32,
+    /// `Tz` as a factor (100% = 1.0)
+    horizontal_scaling: f32,
+    /// `TL`; `None` until set by `TL` or `TD`
+    leading: Option<f32>,
+}
+
+impl Default for TextState {
+    fn default() -> Self {
+        Self {
+            char_spacing: 0.0,
+            word_spacing: 0.0,
+            horizontal_scaling: 1.0,
+            leading: None,
+        }
+    }
+}
+
+impl TextState {
+    /// Leading used by `T*`, `'` and `"`.
+    ///
+    /// The spec default is 0, but streams that move lines without ever
+    /// setting a leading would then stack every line on one baseline, so
+    /// those step by the font size — single spacing, in the same unscaled
+    /// text space units, so it also holds under `1 Tf` with a scaled `Tm`.
+    fn line_leading(&self, font_size: f32) -> f32 {
+        self.leading.unwrap_or(font_size)
+    }
+
+    /// Horizontal advance of a shown string: `(w0 × Tfs + Tc + Tw) × Th`
+    /// summed over its glyphs.
+    fn string_advance(&self, metrics: &FontMetrics, bytes: &[u8], font_size: f32) -> f32 {
+        let single_byte = metrics.code_width == 1;
+        let advance: f32 = metrics
+            .codes(bytes)
+            .map(|code| {
+                let mut tx = metrics.glyph_width(code) / 1000.0 * font_size + self.char_spacing;
+                if single_byte && code == 32 {
+                    tx += self.word_spacing;
+                }
+                tx
+            })
+            .sum();
+        advance * self.horizontal_scaling
+    }
+}
+
 /// Insert a space into `text` if it doesn't already end with one and the
 /// last character is not from a spaceless script (CJK/Japanese).
 /// Insert a space in TJ array based on kerning adjustment, with script-aware thresholds.
 ///
-/// TJ adjustments are in 1/1000 text space units. The threshold for inserting a space
-/// varies by script:
+/// TJ adjustments are in 1/1000 text space units. When the font's glyph widths
+/// are known the threshold scales with them, so condensed and wide fonts are
+/// judged against their own word spacing:
+/// - Latin: 70% of the font's space glyph width
+/// - Hangul (Korean): 50% of the font's average glyph width
+///
+/// Without widths the fixed thresholds apply:
 /// - Latin: 200 units (~33% of typical char width ~600)
 /// - Hangul (Korean): 500 units (~50% of typical char width 1000)
 ///   Korean uses word spaces, but kerning between syllables is typically 100-300 units.
 /// - CJK (Chinese/Japanese): never insert spaces (handled by is_spaceless_script_char)
-fn maybe_insert_space_tj(text: &mut String, adjustment: f32) {
+fn maybe_insert_space_tj(
+    text: &mut String,
+    adjustment: f32,
+    metrics: Option<&FontMetrics>,
+    policy: &SpacingPolicy,
+) {
     if text.is_empty() || text.ends_with(' ') || text.ends_with('\u{00A0}') {
         return;
     }
 
     if let Some(last_char) = text.chars().last() {
-        if is_spaceless_script_char(last_char) {
+        if policy.is_spaceless(last_char) {
             return;
         }
 
-        let threshold = if is_hangul_char(last_char) {
-            500.0
-        } else {
-            200.0
-        };
+        let threshold = policy.tj_threshold(
+            last_char,
+            metrics.and_then(FontMetrics::space_width),
+            metrics.map(FontMetrics::average_width),
+        );
         if adjustment > threshold {
             text.push(' ');
         }
     }
 }
 
-/// Concatenate two PDF transformation matrices (right-multiply: result = a × b).
+/// Build a span for shown text at the current text position, then advance the
+/// text matrix past it when the advance is known.
+///
+/// Whitespace-only strings produce no span but still move the pen.
+fn place_span(
+    text: String,
+    advance: Option<f32>,
+    text_matrix: &mut TextMatrix,
+    ctm: &[f32; 6],
+    font_size: f32,
+    font_name: &Arc<str>,
+    fixed_pitch: bool,
+) -> Option<TextSpan> {
+    let (tx, ty) = text_matrix.get_position();
+    let (x, y) = apply_ctm(ctm, tx, ty);
+    let width = advance.map(|adv| {
+        text_matrix.advance(adv);
+        let (ex, ey) = text_matrix.get_position();
+        let (end_x, end_y) = apply_ctm(ctm, ex, ey);
+        (end_x - x).hypot(end_y - y)
+    });
+
+    if text.trim().is_empty() {
+        return None;
+    }
+    let effective_size = font_size * text_matrix.get_scale() * ctm_y_scale(ctm);
+    let mut span = TextSpan::new(text, x, y, effective_size, Arc::clone(font_name));
+    if let Some(width) = width {
+        span.width = width;
+    }
+    span.is_monospace |= fixed_pitch;
+    Some(span)
+}
+
+/// Map text-space character offsets from [`LayoutAnalyzer::show_text`] onto
+/// the placed span, scaling by the ratio of its width to the advance. Empty
+/// when there are no offsets or they do not line up with the span's text.
+fn char_positions(span: &TextSpan, advance: Option<f32>, offsets: &[f32]) -> Vec<f32> {
+    match advance {
+        Some(advance)
+            if advance.abs() > f32::EPSILON
+                && !offsets.is_empty()
+                && offsets.len() == span.text.chars().count() =>
+        {
+            let scale = span.width / advance;
+            offsets.iter().map(|o| span.x + o * scale).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Build words from grouped lines. A word ends at whitespace, and at a gap
+/// between spans wide enough that [`TextLine::text`] would put a space there.
+fn words_from_lines(lines: &[TextLine]) -> Vec<Word> {
+    struct Pending {
+        text: String,
+        bbox: [f32; 4],
+    }
+
+    fn flush(pending: &mut Option<Pending>, line: u32, words: &mut Vec<Word>) {
+        if let Some(word) = pending.take() {
+            words.push(Word {
+                text: word.text,
+                bbox: word.bbox,
+                line,
+            });
+        }
+    }
+
+    let mut words = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let index = index as u32;
+        let mut pending: Option<Pending> = None;
+        let mut prev_end: Option<f32> = None;
+        for span in &line.spans {
+            let chars: Vec<char> = span.text.chars().collect();
+            if chars.is_empty() {
+                continue;
+            }
+            let width = if span.width > 0.0 {
+                span.width
+            } else {
+                chars.len() as f32 * span.font_size * 0.5
+            };
+            let step = width / chars.len() as f32;
+            let positioned = span.char_x.len() == chars.len();
+            let left = |i: usize| {
+                if positioned {
+                    span.char_x[i]
+                } else {
+                    span.x + step * i as f32
+                }
+            };
+            if prev_end.is_some_and(|end| span.x - end > step * 0.2) {
+                flush(&mut pending, index, &mut words);
+            }
+            let (y0, y1) = (span.bottom(), span.top());
+            for (i, &c) in chars.iter().enumerate() {
+                if c.is_whitespace() {
+                    flush(&mut pending, index, &mut words);
+                    continue;
+                }
+                let x0 = left(i);
+                let x1 = if i + 1 < chars.len() {
+                    left(i + 1)
+                } else {
+                    span.x + width
+                };
+                match pending.as_mut() {
+                    Some(word) => {
+                        word.text.push(c);
+                        word.bbox[0] = word.bbox[0].min(x0);
+                        word.bbox[1] = word.bbox[1].min(y0);
+                        word.bbox[2] = word.bbox[2].max(x1);
+                        word.bbox[3] = word.bbox[3].max(y1);
+                    }
+                    None => {
+                        pending = Some(Pending {
+                            text: c.to_string(),
+                            bbox: [x0, y0, x1, y1],
+                        });
+                    }
+                }
+            }
+            prev_end = Some(span.x + width);
+        }
+        flush(&mut pending, index, &mut words);
+    }
+    words
+}
+
+/// Concatenate two PDF transformation matrices (result = a × b): the result
+/// applies `a` first, then `b`, as `cm` composes `cm × CTM`.
 /// Matrix form: `[a, b, c, d, e, f]` where a point `(x,y)` transforms as
 /// `x' = a*x + c*y + e`,  `y' = b*x + d*y + f`.
-fn concat_matrix(a: &[f32; 6], b: &[f32; 6]) -> [f32; 6] {
+pub(super) fn concat_matrix(a: &[f32; 6], b: &[f32; 6]) -> [f32; 6] {
     [
         a[0] * b[0] + a[1] * b[2],
         a[0] * b[1] + a[1] * b[3],
@@ -1624,24 +2355,57 @@ fn ctm_y_scale(ctm: &[f32; 6]) -> f32 {
     (ctm[2] * ctm[2] + ctm[3] * ctm[3]).sqrt().max(0.01)
 }
 
-/// Check if a character is a Hangul (Korean) syllable or jamo.
-fn is_hangul_char(c: char) -> bool {
-    let code = c as u32;
-    // Hangul Syllables
-    (0xAC00..=0xD7AF).contains(&code)
-    // Hangul Jamo
-    || (0x1100..=0x11FF).contains(&code)
-    // Hangul Compatibility Jamo
-    || (0x3130..=0x318F).contains(&code)
-    // Hangul Jamo Extended-A/B
-    || (0xA960..=0xA97F).contains(&code)
-    || (0xD7B0..=0xD7FF).contains(&code)
-}
-
 /// Check if a character is a CJK (Chinese/Japanese/Korean) character.
 ///
 /// CJK characters typically don't need spaces between them.
 /// Compute the median font size from a slice of spans.
+/// Character pitch of a code listing: the median per-character advance of
+/// measured spans, or 0.6 em (Courier) when no widths are known.
+fn code_char_pitch(lines: &[TextLine]) -> f32 {
+    let mut pitches: Vec<f32> = lines
+        .iter()
+        .flat_map(|l| &l.spans)
+        .filter_map(|s| {
+            let chars = s.text.chars().count();
+            (s.width > 0.0 && chars >= 2).then(|| s.width / chars as f32)
+        })
+        .collect();
+    if pitches.is_empty() {
+        let size = lines.first().map_or(10.0, |l| l.font_size);
+        return (size * 0.6).max(1.0);
+    }
+    pitches.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    pitches[pitches.len() / 2].max(1.0)
+}
+
+/// Range of operators that drew `spans`, from the first to the last.
+pub(crate) fn span_ops<'s>(spans: impl IntoIterator<Item = &'s TextSpan>) -> Option<[u32; 2]> {
+    spans.into_iter().fold(None, |range, span| match range {
+        None => Some(span.ops),
+        Some([first, last]) => Some([first.min(span.ops[0]), last.max(span.ops[1])]),
+    })
+}
+
+/// Source of operators `first..=last`, given a page's content streams as
+/// `(object id, index of first operator)` in drawing order.
+fn source_for_ops(streams: &[(PageId, usize)], first: u32, last: u32) -> BlockSource {
+    // 연산자 범위와 겹치는 스트림 — 다음 스트림의 시작이 범위 시작보다 뒤여야 한다.
+    let streams = streams
+        .iter()
+        .enumerate()
+        .filter(|&(i, &(_, start))| {
+            let end = streams.get(i + 1).map_or(usize::MAX, |&(_, next)| next);
+            start < end && start <= last as usize && end > first as usize
+        })
+        .map(|(_, &(id, _))| id)
+        .collect();
+    BlockSource {
+        streams,
+        first_op: first,
+        last_op: last,
+    }
+}
+
 fn median_font_size(spans: &[TextSpan]) -> f32 {
     if spans.is_empty() {
         return 12.0;
@@ -1651,28 +2415,76 @@ fn median_font_size(spans: &[TextSpan]) -> f32 {
     sizes[sizes.len() / 2]
 }
 
-/// Check if character is from a script that doesn't use word spaces.
-/// Chinese and Japanese don't use spaces between words, but Korean does.
-fn is_spaceless_script_char(c: char) -> bool {
-    let code = c as u32;
-
-    // CJK Unified Ideographs (Chinese characters, used in Chinese/Japanese)
-    (0x4E00..=0x9FFF).contains(&code)
-    // CJK Unified Ideographs Extension A
-    || (0x3400..=0x4DBF).contains(&code)
-    // CJK Unified Ideographs Extension B-F
-    || (0x20000..=0x2A6DF).contains(&code)
-    || (0x2A700..=0x2B73F).contains(&code)
-    || (0x2B740..=0x2B81F).contains(&code)
-    || (0x2B820..=0x2CEAF).contains(&code)
-    || (0x2CEB0..=0x2EBEF).contains(&code)
-    // Hiragana (Japanese)
-    || (0x3040..=0x309F).contains(&code)
-    // Katakana (Japanese)
-    || (0x30A0..=0x30FF).contains(&code)
-    // NOTE: Hangul (Korean) is NOT included - Korean uses word spaces like English
-    // CJK Symbols and Punctuation
-    || (0x3000..=0x303F).contains(&code)
+/// Join the texts of consecutive lines of one paragraph, spacing line breaks
+/// by `policy`: a line break counts as a gap.
+pub(crate) fn join_lines(
+    lines: impl IntoIterator<Item = String>,
+    policy: &SpacingPolicy,
+) -> String {
+    let mut out = String::new();
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let space = match (out.chars().next_back(), line.chars().next()) {
+            (Some(prev), Some(next)) => policy.space_between(prev, next, true),
+            _ => false,
+        };
+        if space {
+            out.push(' ');
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+/// Gap below which two glyph spans are one run of text, as a fraction of
+/// the font size. Word spaces are wider: about a quarter of an em.
+const GLYPH_GAP_RATIO: f32 = 0.1;
+
+/// Join spans drawn one after another in the same font, size and style on
+/// the same baseline with no word space between them.
+///
+/// Some generators emit one `Tj` per glyph, leaving thousands of
+/// one-character spans that slow every later pass and make word spacing
+/// unreliable, since the space threshold is taken from a single glyph's
+/// width. Runs in drawing order, before line grouping. Spans without a
+/// measured width are left to [`merge_fragmented_spans`].
+fn coalesce_glyph_spans(spans: Vec<TextSpan>) -> Vec<TextSpan> {
+    let mut result: Vec<TextSpan> = Vec::with_capacity(spans.len());
+    for span in spans {
+        if let Some(prev) = result.last_mut() {
+            if continues_run(prev, &span) {
+                // 글리프 위치는 양쪽 모두 있을 때만 이어 붙인다.
+                if prev.char_x.is_empty() || span.char_x.is_empty() {
+                    prev.char_x.clear();
+                } else {
+                    prev.char_x.extend_from_slice(&span.char_x);
+                }
+                prev.width = span.x + span.width - prev.x;
+                prev.text.push_str(&span.text);
+                prev.ops[1] = prev.ops[1].max(span.ops[1]);
+                continue;
+            }
+        }
+        result.push(span);
+    }
+    result
+}
+
+/// Whether `next` carries on the text of `prev` without a break.
+fn continues_run(prev: &TextSpan, next: &TextSpan) -> bool {
+    if prev.width <= 0.0 || next.width <= 0.0 {
+        return false;
+    }
+    let same_style = prev.font_name == next.font_name
+        && (prev.font_size - next.font_size).abs() < 0.01
+        && prev.is_bold == next.is_bold
+        && prev.is_italic == next.is_italic;
+    let tolerance = prev.font_size * GLYPH_GAP_RATIO;
+    let gap = next.x - (prev.x + prev.width);
+    same_style && (prev.y - next.y).abs() < 0.01 && gap.abs() < tolerance
 }
 
 /// Merge adjacent fragmented spans that likely form words.
@@ -1744,6 +2556,7 @@ fn merge_fragmented_spans(spans: Vec<TextSpan>) -> Vec<TextSpan> {
             let new_end = span.x + span.font_size * 0.6 * span.text.chars().count() as f32;
             prev.width = new_end - prev.x;
             prev.text.push_str(&span.text);
+            prev.ops[1] = prev.ops[1].max(span.ops[1]);
         } else {
             was_fragment.push(is_fragment);
             result.push(span);
@@ -1803,6 +2616,22 @@ mod tests {
         assert!(span2.is_italic);
     }
 
+    #[test]
+    fn test_join_lines() {
+        let join = |lines: &[&str]| {
+            join_lines(
+                lines.iter().map(|l| l.to_string()),
+                &SpacingPolicy::default(),
+            )
+        };
+        assert_eq!(
+            join(&["The quick", " brown fox ", "", "jumps."]),
+            "The quick brown fox jumps."
+        );
+        assert_eq!(join(&["日本語の", "文章です。"]), "日本語の文章です。");
+        assert_eq!(join(&["한국어는", "띄어 씁니다."]), "한국어는 띄어 씁니다.");
+    }
+
     #[test]
     fn test_merge_fragmented_spans_single_chars() {
         // Simulate per-character rendering: "Hello" as 5 separate spans
@@ -1815,9 +2644,12 @@ mod tests {
                 y: 500.0,
                 width: 0.0, // width=0 is the fragmentation signal
                 font_size: 12.0,
-                font_name: "Helvetica".to_string(),
+                font_name: "Helvetica".into(),
                 is_bold: false,
                 is_italic: false,
+                is_monospace: false,
+                char_x: Vec::new(),
+                ops: [0, 0],
             })
             .collect();
 
@@ -1826,6 +2658,26 @@ mod tests {
         assert_eq!(merged[0].text, "Hello");
     }
 
+    #[test]
+    fn test_words_from_lines_split_on_gaps() {
+        // 폭만 알려진 스팬: 글자 위치는 폭을 고르게 나눠 잡는다.
+        let mut left = TextSpan::new("Hel".to_string(), 100.0, 500.0, 12.0, "Helvetica");
+        left.width = 18.0;
+        let mut right = TextSpan::new("lo there".to_string(), 118.0, 500.0, 12.0, "Helvetica");
+        right.width = 48.0;
+        let mut far = TextSpan::new("end".to_string(), 200.0, 500.0, 12.0, "Helvetica");
+        far.width = 18.0;
+        let line = TextLine::from_spans(vec![left, right, far]);
+
+        let words = words_from_lines(&[line]);
+        let texts: Vec<&str> = words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(texts, vec!["Hello", "there", "end"]);
+        assert_eq!(words[0].bbox[0], 100.0);
+        assert_eq!(words[0].bbox[2], 130.0);
+        assert_eq!(words[2].bbox, [200.0, 497.6, 218.0, 509.6]);
+        assert!(words.iter().all(|w| w.line == 0));
+    }
+
     #[test]
     fn test_merge_fragmented_spans_preserves_normal() {
         // Normal multi-character spans should not be merged unnecessarily
@@ -1836,9 +2688,12 @@ mod tests {
                 y: 500.0,
                 width: 30.0,
                 font_size: 12.0,
-                font_name: "Helvetica".to_string(),
+                font_name: "Helvetica".into(),
                 is_bold: false,
                 is_italic: false,
+                is_monospace: false,
+                char_x: Vec::new(),
+                ops: [0, 0],
             },
             TextSpan {
                 text: "World".to_string(),
@@ -1846,9 +2701,12 @@ mod tests {
                 y: 500.0,
                 width: 30.0,
                 font_size: 12.0,
-                font_name: "Helvetica".to_string(),
+                font_name: "Helvetica".into(),
                 is_bold: false,
                 is_italic: false,
+                is_monospace: false,
+                char_x: Vec::new(),
+                ops: [0, 0],
             },
         ];
 
@@ -1857,6 +2715,42 @@ mod tests {
         assert_eq!(merged.len(), 2);
     }
 
+    fn glyph(text: &str, x: f32, width: f32) -> TextSpan {
+        let mut span = TextSpan::new(text.to_string(), x, 500.0, 10.0, "Helvetica");
+        span.width = width;
+        span
+    }
+
+    #[test]
+    fn test_coalesce_glyph_spans_joins_runs_not_words() {
+        // "Hi" 뒤에 단어 간격(0.28em), 이어서 "yo" — 같은 글꼴의 글리프별 스팬.
+        let spans = vec![
+            glyph("H", 100.0, 7.2),
+            glyph("i", 107.2, 2.2),
+            glyph("y", 112.2, 5.0),
+            glyph("o", 117.2, 5.6),
+        ];
+        let merged = coalesce_glyph_spans(spans);
+        let texts: Vec<&str> = merged.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["Hi", "yo"]);
+        assert!((merged[0].width - 9.4).abs() < 1e-4);
+
+        let line = TextLine::from_spans(merged);
+        assert_eq!(line.text(), "Hi yo");
+    }
+
+    #[test]
+    fn test_coalesce_glyph_spans_keeps_style_changes() {
+        let mut bold = glyph("b", 105.0, 5.0);
+        bold.is_bold = true;
+        let lower = TextSpan {
+            y: 490.0,
+            ..glyph("c", 110.0, 5.0)
+        };
+        let merged = coalesce_glyph_spans(vec![glyph("a", 100.0, 5.0), bold, lower]);
+        assert_eq!(merged.len(), 3);
+    }
+
     #[test]
     fn test_column_contains() {
         let col = Column {
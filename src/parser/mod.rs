@@ -1,24 +1,40 @@
 //! PDF parsing module.
 
+mod annotation;
 pub mod backend;
 pub mod bidi;
 pub mod cmap_table;
+mod code;
+mod debug_artifacts;
+mod definitions;
+mod deskew;
 pub(crate) mod encoding;
 pub(crate) mod font;
+mod headings;
+mod intern;
 mod layout;
-mod ocr_gate;
+pub(crate) mod ocr_gate;
 mod options;
 mod pdf_parser;
+mod peek;
 pub(crate) mod predefined_cmap;
 pub mod raw;
+mod raw_text;
+mod revision;
+mod spacing;
 pub mod stream;
 mod table_detector;
+mod vector;
 pub mod xycut;
 
 pub use layout::{
-    BlockType, Column, FontStatistics, LayoutAnalyzer, TextBlock, TextLine, TextSpan,
+    BlockType, Column, ColumnDetectionConfig, FontStatistics, LayoutAnalyzer, TextBlock, TextLine,
+    TextSpan,
 };
-pub use options::{ErrorMode, ExtractMode, ParseOptions};
-pub use pdf_parser::PdfParser;
+pub use options::{ErrorMode, ExtractMode, ExtractionStrategy, ParseOptions};
+pub use pdf_parser::{PdfParser, XFA_DATASETS_ID};
+pub(crate) use peek::peek_data;
+pub use peek::DocumentInfo;
+pub use spacing::{Script, ScriptSpacing, SpacingPolicy};
 pub use stream::{PageStreamOptions, ParseEvent};
 pub use table_detector::{DetectedTable, TableDetector, TableDetectorConfig, TableRowData};
@@ -33,6 +33,16 @@ pub fn is_incoherent_text(text: &str) -> bool {
     m.word_like_ratio < MAX_WORD_LIKE_RATIO && m.coherent_char_ratio < MAX_COHERENT_CHAR_RATIO
 }
 
+/// How much the text reads like language, from 0.0 (noise) to 1.0.
+///
+/// The mean of the word-like and coherent-character ratios, with no minimum
+/// length — used to compare two readings of the same region, not to judge one
+/// in isolation. `None` for text without any visible character.
+pub(crate) fn text_confidence(text: &str) -> Option<f32> {
+    let m = TextMetrics::measure(text)?;
+    Some((m.word_like_ratio + m.coherent_char_ratio) / 2.0)
+}
+
 struct TextMetrics {
     /// Share of whitespace-delimited tokens that are at least two characters long
     /// and contain a letter. Real prose is mostly words; OCR noise is mostly
@@ -50,6 +60,15 @@ impl TextMetrics {
         if chars < MIN_CHARS {
             return None;
         }
+        Self::measure(text)
+    }
+
+    /// Ratios for any non-blank text, however short.
+    fn measure(text: &str) -> Option<Self> {
+        let chars = text.chars().filter(|c| !c.is_whitespace()).count();
+        if chars == 0 {
+            return None;
+        }
 
         let coherent = text.chars().filter(|c| is_coherent(*c)).count();
 
@@ -140,6 +159,15 @@ mod tests {
         assert!(!is_incoherent_text(greek));
     }
 
+    #[test]
+    fn confidence_ranks_prose_above_garbage() {
+        let garbage = text_confidence(OCR_GARBAGE).unwrap();
+        let prose = text_confidence(ENGLISH_PROSE).unwrap();
+        assert!(prose > 0.9, "{prose}");
+        assert!(garbage < 0.5, "{garbage}");
+        assert_eq!(text_confidence("  \n "), None);
+    }
+
     #[test]
     fn ignores_text_too_short_to_judge() {
         assert!(!is_incoherent_text("φ ∽ ㄱ υ Φ"));
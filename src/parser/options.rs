@@ -1,6 +1,13 @@
 //! Parsing options and configuration.
 
-use crate::render::PageSelection;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::model::BlockFilter;
+use crate::render::{HeadingConfig, PageSelection};
+
+use super::layout::ColumnDetectionConfig;
+use super::spacing::SpacingPolicy;
 
 /// Options for parsing PDF documents.
 #[derive(Debug, Clone)]
@@ -33,6 +40,16 @@ pub struct ParseOptions {
     /// Password for encrypted documents
     pub password: Option<String>,
 
+    /// Further passwords to try, in order, when `password` is unset or does
+    /// not open the document. Each is tried as a user and as an owner password.
+    pub passwords: Vec<String>,
+
+    /// Refuse to extract from a document whose permissions forbid copying,
+    /// failing with [`Error::ExtractionForbidden`](crate::Error::ExtractionForbidden).
+    /// Opening it with the owner password lifts the restriction. Default
+    /// `false`: the flags are ignored, as most extraction tools do.
+    pub respect_extraction_permission: bool,
+
     /// Whether to drop an invisible OCR text layer whose text is not readable.
     ///
     /// Searchable scans carry the OCR result as invisible text over the page
@@ -40,6 +57,124 @@ pub struct ParseOptions {
     /// scan — that layer decodes to meaningless characters, which are worse than
     /// no text at all. Default `true`; set `false` to keep the raw layer.
     pub suppress_low_confidence_ocr: bool,
+
+    /// Whether to capture path-drawn graphics (charts, diagrams) as SVG.
+    ///
+    /// Each page with a qualifying drawing gets one `ResourceType::Vector`
+    /// resource, referenced by a `Block::Image` after the page's text.
+    /// Default `false`.
+    pub extract_vector_graphics: bool,
+
+    /// Which block kinds parsed pages keep. Pruning happens after quality
+    /// metrics are taken, so filtering text out does not raise a "no text"
+    /// warning. Default keeps everything.
+    pub block_filter: BlockFilter,
+
+    /// Wall-clock budget for the whole document. Pages that have not started
+    /// when it runs out are reported as timed out instead of parsed. `None`
+    /// (default) means no limit. Ignored on wasm32, which has no clock.
+    pub timeout: Option<Duration>,
+
+    /// Wall-clock budget for analysing a single page's content stream, so one
+    /// pathological page (millions of operators) cannot stall the rest. In
+    /// lenient mode a page that runs over is left out and listed in
+    /// `ExtractionQuality::timed_out_pages`; strict mode fails the parse.
+    pub page_timeout: Option<Duration>,
+
+    /// How page text is extracted. `Auto` (default) uses the layout
+    /// interpreter and falls back to a stream-order dump on pages where it
+    /// recovers almost nothing; the page records which one was used in
+    /// `Page::text_strategy`.
+    pub extraction_strategy: ExtractionStrategy,
+
+    /// Which revision of an incrementally updated file to parse, 1-based and
+    /// oldest first. `None` (default) parses the latest; see
+    /// `Document::revisions` for what each revision changed.
+    pub revision: Option<u32>,
+
+    /// Whether to record every word with its bounding box in `Page::words`,
+    /// for highlighting search hits in a viewer. Costs a second pass over
+    /// each page's content stream. Default `false`.
+    pub extract_words: bool,
+
+    /// Thresholds for splitting pages into text columns. Turn detection off
+    /// with `ColumnDetectionConfig::disabled()` for single-column documents
+    /// whose margins or indents get mistaken for a gutter.
+    pub column_detection: ColumnDetectionConfig,
+
+    /// Where word spaces go in extracted text: which scripts are written
+    /// without them, how boundaries between scripts are spaced, and the gap
+    /// and `TJ` thresholds for inserting one.
+    pub spacing: SpacingPolicy,
+
+    /// Straighten slightly rotated text before assembling lines. Text layers
+    /// that OCR put over skewed scans drift up or down along each line, which
+    /// otherwise splits one line into several. The dominant baseline angle is
+    /// estimated per page and spans are rotated back when it is between about
+    /// 0.2° and 10°. Span and block positions are then in the straightened
+    /// frame; `Page::words` keeps page coordinates. Default `false`.
+    pub deskew: bool,
+
+    /// Heading detection settings. Headings are normally told apart by font
+    /// size; on pages set in a single size, lines are instead scored on the
+    /// style signals in [`HeadingConfig::signals`] (bold, short, uppercase,
+    /// space above, section numbering) unless `detect_from_style` is off.
+    pub heading_config: HeadingConfig,
+
+    /// Detect the language of each paragraph and record it in
+    /// `ParagraphStyle::language`, for bilingual documents whose blocks go
+    /// to different tokenizers. Paragraphs too short to tell stay untagged.
+    /// Default `false`.
+    pub tag_languages: bool,
+
+    /// Wrap runs of consecutive list items in `Block::List` (see
+    /// [`Page::group_lists`](crate::model::Page::group_lists)), so JSON
+    /// consumers and renderers see where each list starts and ends.
+    /// Markdown and text output are unchanged. Default `false`.
+    pub group_lists: bool,
+
+    /// Record the SHA-256 of the source file in `Document::provenance`.
+    /// Hashing is a full extra pass over the input, so it is off by default;
+    /// turn it on whenever output is rendered with
+    /// `RenderOptions::with_provenance`, or `source_sha256` is left out.
+    /// `Unpdf::with_provenance` and the CLI `--provenance` flag set both.
+    pub source_hash: bool,
+
+    /// Keep peak memory low on big documents, at the cost of speed.
+    ///
+    /// Objects are read from the file only when first needed, rather than
+    /// all parsed when the document is opened, and page content streams are
+    /// never kept. Pages are parsed one at a time, each page's decompressed
+    /// content stream, spans and layout state dropped before the next is
+    /// read, instead of parsing pages in parallel ahead of the consumer. For
+    /// text-only extraction, memory beyond the returned document stays under
+    /// about twice the file size. Images are not
+    /// decoded a second time for `Document::resources`; each page's own images
+    /// are moved there. Default `false`.
+    pub low_memory: bool,
+
+    /// Directory to write each page's images to as soon as the page is
+    /// parsed. Their `Resource::data` is then left empty, with
+    /// `Resource::filename` naming the written file. `None` (default) keeps
+    /// image data in memory.
+    pub resource_dir: Option<PathBuf>,
+
+    /// Images decoded at once on each page when extracting resources. `0`
+    /// (default) uses as many as the rayon pool has threads; `1` decodes
+    /// them one after another. Ignored, as `1`, when `parallel` is off or in
+    /// low-memory mode.
+    pub resource_threads: usize,
+
+    /// Decoded image bytes allowed across the whole document. Once reached,
+    /// images not yet decoded are skipped and a warning is logged. The
+    /// pages' images and the separate copies in `Document::resources` are
+    /// counted separately. `None` (default) is unbounded.
+    pub max_resource_memory: Option<usize>,
+
+    /// Directory to dump each page's decoded content stream, text spans and
+    /// detected lines and blocks to, for bug reports about reading order.
+    /// `None` (default) writes nothing.
+    pub debug_artifacts: Option<PathBuf>,
 }
 
 impl ParseOptions {
@@ -54,6 +189,12 @@ impl ParseOptions {
         self
     }
 
+    /// Enable or disable SVG capture of vector graphics.
+    pub fn with_vector_graphics(mut self, enabled: bool) -> Self {
+        self.extract_vector_graphics = enabled;
+        self
+    }
+
     /// Set error mode.
     pub fn with_error_mode(mut self, mode: ErrorMode) -> Self {
         self.error_mode = mode;
@@ -102,12 +243,189 @@ impl ParseOptions {
         self
     }
 
+    /// Parse revision `n` (1-based, oldest first) of an incrementally
+    /// updated file instead of the latest.
+    pub fn with_revision(mut self, n: u32) -> Self {
+        self.revision = Some(n);
+        self
+    }
+
+    /// Record word bounding boxes in `Page::words`.
+    pub fn with_words(mut self, enabled: bool) -> Self {
+        self.extract_words = enabled;
+        self
+    }
+
+    /// Set the column detection thresholds.
+    pub fn with_column_detection(mut self, config: ColumnDetectionConfig) -> Self {
+        self.column_detection = config;
+        self
+    }
+
+    /// Set the word spacing rules.
+    pub fn with_spacing(mut self, policy: SpacingPolicy) -> Self {
+        self.spacing = policy;
+        self
+    }
+
+    /// Enable or disable straightening of skewed text before line assembly.
+    pub fn with_deskew(mut self, enabled: bool) -> Self {
+        self.deskew = enabled;
+        self
+    }
+
+    /// Set the heading detection settings.
+    pub fn with_heading_config(mut self, config: HeadingConfig) -> Self {
+        self.heading_config = config;
+        self
+    }
+
+    /// Enable or disable per-paragraph language detection.
+    pub fn with_language_tags(mut self, enabled: bool) -> Self {
+        self.tag_languages = enabled;
+        self
+    }
+
+    /// Enable or disable recording the source file's SHA-256 in provenance.
+    pub fn with_source_hash(mut self, enabled: bool) -> Self {
+        self.source_hash = enabled;
+        self
+    }
+
+    /// Enable or disable grouping list items into `Block::List`.
+    pub fn with_list_grouping(mut self, enabled: bool) -> Self {
+        self.group_lists = enabled;
+        self
+    }
+
+    /// Parse one page at a time and drop each page's working data before the
+    /// next, to keep peak memory low.
+    pub fn with_low_memory(mut self, enabled: bool) -> Self {
+        self.low_memory = enabled;
+        self
+    }
+
+    /// Write page images to `dir` as pages are parsed instead of keeping
+    /// their data in memory.
+    pub fn with_resource_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.resource_dir = Some(dir.into());
+        self
+    }
+
+    /// Decode up to `threads` images of a page at once (`0` = one per
+    /// thread of the rayon pool).
+    pub fn with_resource_threads(mut self, threads: usize) -> Self {
+        self.resource_threads = threads;
+        self
+    }
+
+    /// Stop decoding images once `bytes` of image data have been decoded.
+    pub fn with_max_resource_memory(mut self, bytes: usize) -> Self {
+        self.max_resource_memory = Some(bytes);
+        self
+    }
+
+    /// Dump per-page debugging artifacts (content stream operators, span
+    /// CSV, line and block boundaries) into `dir`.
+    pub fn with_debug_artifacts(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.debug_artifacts = Some(dir.into());
+        self
+    }
+
     /// Set password for encrypted documents.
     pub fn with_password(mut self, password: impl Into<String>) -> Self {
         self.password = Some(password.into());
         self
     }
 
+    /// Set passwords to try in turn until one opens the document, e.g. the
+    /// known passwords of an archive of mixed files.
+    pub fn with_passwords(mut self, passwords: Vec<String>) -> Self {
+        self.passwords = passwords;
+        self
+    }
+
+    /// Honour (`true`) or ignore (`false`, default) a document's permission
+    /// to copy its content.
+    pub fn respect_extraction_permission(mut self, respect: bool) -> Self {
+        self.respect_extraction_permission = respect;
+        self
+    }
+
+    /// `password` followed by `passwords`, in the order they are tried.
+    pub(crate) fn password_candidates(&self) -> Vec<&str> {
+        self.password
+            .iter()
+            .chain(&self.passwords)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// One-line summary of the options, recorded in extraction provenance.
+    ///
+    /// The password is never included.
+    pub fn summary(&self) -> String {
+        let pages = match &self.pages {
+            PageSelection::All => "all".to_string(),
+            PageSelection::Range(r) => format!("{}-{}", r.start(), r.end()),
+            PageSelection::Pages(p) => p
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        };
+        format!(
+            "mode={:?}; errors={:?}; resources={}; vectors={}; pages={}; ocr_suppression={}",
+            self.extract_mode,
+            self.error_mode,
+            self.extract_resources,
+            self.extract_vector_graphics,
+            pages,
+            self.suppress_low_confidence_ocr
+        )
+        .to_lowercase()
+    }
+
+    /// Set the text extraction strategy.
+    pub fn with_extraction_strategy(mut self, strategy: ExtractionStrategy) -> Self {
+        self.extraction_strategy = strategy;
+        self
+    }
+
+    /// Give up on pages not yet parsed once `timeout` has elapsed.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Give up on any single page that takes longer than `timeout`.
+    pub fn with_page_timeout(mut self, timeout: Duration) -> Self {
+        self.page_timeout = Some(timeout);
+        self
+    }
+
+    /// Keep only the block kinds `filter` allows.
+    pub fn with_block_filter(mut self, filter: BlockFilter) -> Self {
+        self.block_filter = filter;
+        self
+    }
+
+    /// Keep only tables.
+    pub fn only_tables(self) -> Self {
+        self.with_block_filter(BlockFilter::only_tables())
+    }
+
+    /// Keep only headings.
+    pub fn only_headings(self) -> Self {
+        self.with_block_filter(BlockFilter::only_headings())
+    }
+
+    /// Drop image blocks and their resources.
+    pub fn skip_images(mut self) -> Self {
+        self.block_filter.images = false;
+        self
+    }
+
     /// Set the minimum image dimension (pixels). Images with width OR
     /// height below this value are dropped as decorative. `0` keeps all.
     pub fn with_min_image_dimension(mut self, min_px: u32) -> Self {
@@ -126,11 +444,46 @@ impl Default for ParseOptions {
             parallel: true,
             pages: PageSelection::All,
             password: None,
+            passwords: Vec::new(),
+            respect_extraction_permission: false,
             suppress_low_confidence_ocr: true,
+            extract_vector_graphics: false,
+            block_filter: BlockFilter::all(),
+            timeout: None,
+            page_timeout: None,
+            extraction_strategy: ExtractionStrategy::Auto,
+            revision: None,
+            extract_words: false,
+            column_detection: ColumnDetectionConfig::default(),
+            spacing: SpacingPolicy::default(),
+            deskew: false,
+            heading_config: HeadingConfig::default(),
+            tag_languages: false,
+            group_lists: false,
+            source_hash: false,
+            low_memory: false,
+            resource_dir: None,
+            resource_threads: 0,
+            max_resource_memory: None,
+            debug_artifacts: None,
         }
     }
 }
 
+/// When a budget started now runs out. Always `None` on wasm32, where
+/// `Instant::now` panics.
+pub(crate) fn deadline_after(timeout: Option<Duration>) -> Option<Instant> {
+    if cfg!(target_arch = "wasm32") {
+        return None;
+    }
+    timeout.map(|t| Instant::now() + t)
+}
+
+/// Whether `deadline` has passed.
+pub(crate) fn is_expired(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|d| Instant::now() >= d)
+}
+
 /// Error handling mode during parsing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ErrorMode {
@@ -153,6 +506,19 @@ pub enum ExtractMode {
     StructureOnly,
 }
 
+/// How page text is extracted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExtractionStrategy {
+    /// Layout interpreter first; switch to `RawText` on a page whose text
+    /// operators yielded far less text than a stream-order dump does
+    #[default]
+    Auto,
+    /// Always use the layout interpreter (positions, headings, tables)
+    ContentStream,
+    /// Always decode text operators in stream order, skipping layout analysis
+    RawText,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
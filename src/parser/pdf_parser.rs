@@ -1,16 +1,31 @@
 //! PDF document parser.
 
+use std::collections::BTreeSet;
 use std::io::Read;
-#[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
+use std::time::Instant;
 
+use crate::detect::{detect_format_from_bytes, sniff_format};
 #[cfg(not(target_arch = "wasm32"))]
-use crate::detect::detect_format_from_path;
+use crate::detect::{detect_format_from_path, sniff_format_from_path};
 use crate::error::{Error, Result};
-use crate::model::{Block, Document, OutlineItem, Page, Paragraph, Resource, ResourceType};
+use crate::model::{
+    detect_language, Block, Document, OutlineItem, Page, Paragraph, Provenance, Resource,
+    ResourceType, Revision, Table, TableDetection, TableRejection, TextStrategy,
+};
+use crate::vfs::FileSource;
 
-use super::backend::{PdfBackend, RawBackend, RawXObject};
-use super::options::{ErrorMode, ExtractMode, ParseOptions};
+use super::annotation::{convert_annotations, has_markup};
+use super::backend::{ContentOp, ImageLimits, PdfBackend, RawBackend, RawXObject, StructureReport};
+use super::definitions::{block_entries, detect_definition_lists};
+use super::layout::{span_ops, LayoutAnalyzer, TextSpan};
+use super::options::{
+    deadline_after, is_expired, ErrorMode, ExtractMode, ExtractionStrategy, ParseOptions,
+};
+use super::raw::{RawDocument, ReadSeek};
+use super::raw_text::{extract_raw_text, RawPageText};
+use super::spacing::SpacingPolicy;
+use super::table_detector::{DetectedTable, TableDetector, TableDetectorConfig};
 
 /// PDF document parser.
 pub struct PdfParser {
@@ -30,16 +45,41 @@ impl PdfParser {
     pub fn open_with_options<P: AsRef<Path>>(path: P, options: ParseOptions) -> Result<Self> {
         let path = path.as_ref();
 
-        // Verify it's a PDF
-        detect_format_from_path(path)?;
+        // Verify it's a PDF, naming the actual format when it is something else
+        if let Err(e) = detect_format_from_path(path) {
+            return Err(match e {
+                Error::UnknownFormat => sniff_format_from_path(path)?.not_pdf_error(),
+                e => e,
+            });
+        }
 
-        // Decryption (empty password) is attempted inside RawDocument::load().
+        // Decryption (empty password, then the given ones) is attempted while loading.
         // If we get here, the PDF is usable (either not encrypted, or decrypted).
-        let backend: Box<dyn PdfBackend> = Box::new(RawBackend::load_file(path)?);
+        let data = std::fs::read(path)?;
+        let backend: Box<dyn PdfBackend> = Box::new(load_backend(data, &options)?);
 
         Ok(Self { backend, options })
     }
 
+    /// Open `path` on `source` instead of the local file system; see
+    /// [`crate::vfs`].
+    pub fn open_from_source<S, P>(source: &S, path: P, options: ParseOptions) -> Result<Self>
+    where
+        S: FileSource + ?Sized,
+        P: AsRef<Path>,
+    {
+        let data = source.read(path.as_ref())?;
+        // open_with_options 와 같은 검사: 이름 붙일 수 있는 다른 형식이면 그 이름으로 보고한다.
+        if let Err(e) = detect_format_from_bytes(&data) {
+            return Err(match e {
+                Error::UnknownFormat => sniff_format(&data).not_pdf_error(),
+                e => e,
+            });
+        }
+        let backend: Box<dyn PdfBackend> = Box::new(load_backend(data, &options)?);
+        Ok(Self { backend, options })
+    }
+
     /// Parse a PDF from bytes.
     pub fn from_bytes(data: &[u8]) -> Result<Self> {
         Self::from_bytes_with_options(data, ParseOptions::default())
@@ -47,7 +87,44 @@ impl PdfParser {
 
     /// Parse a PDF from bytes with custom options.
     pub fn from_bytes_with_options(data: &[u8], options: ParseOptions) -> Result<Self> {
-        let backend: Box<dyn PdfBackend> = Box::new(RawBackend::load_bytes(data)?);
+        let passwords = options.password_candidates();
+        let backend = if options.low_memory {
+            RawBackend::load_lazy_with_passwords(data.to_vec(), options.revision, &passwords)
+        } else {
+            RawBackend::load_with_passwords(data, options.revision, &passwords)
+        };
+        let backend = backend.map_err(|e| match e {
+            Error::UnknownFormat => sniff_format(data).not_pdf_error(),
+            e => e,
+        })?;
+        let backend = backend.with_source_sha256(source_hash(data, &options));
+        let backend: Box<dyn PdfBackend> = Box::new(backend);
+        Ok(Self { backend, options })
+    }
+
+    /// Build a parser over a document already loaded with
+    /// [`RawDocument::load`], so a file read for custom low-level processing
+    /// need not be loaded again. `revision` and `low_memory` have no effect,
+    /// and provenance carries no source hash.
+    pub fn from_raw_document(doc: RawDocument, options: ParseOptions) -> Self {
+        let backend: Box<dyn PdfBackend> = Box::new(RawBackend::from_document(doc));
+        Self { backend, options }
+    }
+
+    /// Parse a PDF from a seekable reader without reading all of it: the
+    /// xref sections and object streams are read up front, everything else
+    /// when parsing reaches it. Meant for remote files behind an HTTP
+    /// range-request reader, where only the selected pages are fetched.
+    ///
+    /// `revision` has no effect and provenance carries no source hash;
+    /// encrypted documents are read whole.
+    pub fn from_seekable<R>(reader: R, options: ParseOptions) -> Result<Self>
+    where
+        R: ReadSeek + 'static,
+    {
+        let passwords = options.password_candidates();
+        let backend = RawBackend::load_seekable(reader, &passwords)?;
+        let backend: Box<dyn PdfBackend> = Box::new(backend);
         Ok(Self { backend, options })
     }
 
@@ -57,8 +134,10 @@ impl PdfParser {
     }
 
     /// Parse a PDF from a reader with custom options.
-    pub fn from_reader_with_options<R: Read>(reader: R, options: ParseOptions) -> Result<Self> {
-        let backend: Box<dyn PdfBackend> = Box::new(RawBackend::load_reader(reader)?);
+    pub fn from_reader_with_options<R: Read>(mut reader: R, options: ParseOptions) -> Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let backend: Box<dyn PdfBackend> = Box::new(load_backend(data, &options)?);
         Ok(Self { backend, options })
     }
 
@@ -78,6 +157,11 @@ impl PdfParser {
 
         // Snapshot page map so we can do resource extraction inside the handler.
         let page_ids = self.backend.pages();
+        // 문서 리소스는 페이지 이미지와 별도 사본이므로 한도도 따로 센다.
+        let resource_limits = ImageLimits::new(
+            opts.image_limits.concurrency,
+            self.options.max_resource_memory,
+        );
 
         let quality = run_stream(&*self.backend, &opts, |ev| match ev {
             ParseEvent::DocumentStart {
@@ -91,12 +175,20 @@ impl PdfParser {
                 document.form_fields = form_fields;
                 ControlFlow::Continue(())
             }
-            ParseEvent::PageParsed(page) => {
+            ParseEvent::PageParsed(mut page) => {
                 if self.options.extract_resources
                     && self.options.extract_mode != ExtractMode::StructureOnly
+                    && self.options.block_filter.images
                 {
-                    if let Some(page_id) = page_ids.get(&page.number) {
-                        if let Ok(xobjects) = self.backend.page_xobjects(*page_id) {
+                    // 저메모리·디스크 배출 모드에서는 이미지를 다시 디코딩하지 않고 페이지가
+                    // 이미 가진 것을 옮긴다 — 같은 이미지가 메모리에 두 벌 남지 않도록.
+                    if self.options.low_memory || self.options.resource_dir.is_some() {
+                        document.resources.extend(std::mem::take(&mut page.images));
+                    } else if let Some(page_id) = page_ids.get(&page.number) {
+                        if let Ok(xobjects) = self
+                            .backend
+                            .page_xobjects_bounded(*page_id, &resource_limits)
+                        {
                             for xobj in xobjects {
                                 let key = format!("page{}_{}", page.number, xobj.name);
                                 if let Some(r) = Self::convert_xobject(xobj) {
@@ -106,6 +198,12 @@ impl PdfParser {
                         }
                     }
                 }
+                // 벡터 그래픽은 페이지 파싱 중에 만들어지므로 여기서 문서 리소스로 옮긴다.
+                for (id, resource) in &page.images {
+                    if resource.is_vector() {
+                        document.resources.insert(id.clone(), resource.clone());
+                    }
+                }
                 document.add_page(page);
                 ControlFlow::Continue(())
             }
@@ -126,9 +224,38 @@ impl PdfParser {
             return Err(e);
         }
 
+        // 북마크가 없으면 본문에 인쇄된 목차로 대신하고, 그 항목으로 제목 수준도 잡는다.
+        if document.outline.is_none() {
+            if let Some(toc) = document.detect_toc() {
+                document.apply_toc_headings(&toc);
+                document.outline = Some(toc.outline);
+            }
+        }
+
+        if document
+            .metadata
+            .title
+            .as_deref()
+            .map_or(true, |t| t.trim().is_empty())
+        {
+            document.metadata.inferred_title = document.infer_title();
+        }
+
+        if document.metadata.has_xfa && self.options.extract_resources {
+            if let Some(datasets) = self.backend.xfa_datasets() {
+                let xml = Resource::new(datasets, "application/xml", ResourceType::Other);
+                document.resources.insert(XFA_DATASETS_ID.to_string(), xml);
+            }
+        }
+
         let mut final_q = quality;
         final_q.encrypted = document.metadata.encrypted;
         document.extraction_quality = final_q;
+        document.revisions = self.backend.revisions();
+        document.provenance = Some(Provenance::new(
+            self.backend.source_sha256(),
+            self.options.summary(),
+        ));
 
         Ok(document)
     }
@@ -158,6 +285,26 @@ impl PdfParser {
         self.backend.pages().len() as u32
     }
 
+    /// Incremental-update revisions of the file, oldest first, up to the one
+    /// selected by `ParseOptions::revision`. Empty when the file was never
+    /// updated in place.
+    pub fn revisions(&self) -> Vec<Revision> {
+        self.backend.revisions()
+    }
+
+    /// Structural damage found while opening the file: broken xref entries
+    /// and streams in filters that cannot be decoded.
+    pub fn structure_report(&self) -> StructureReport {
+        self.backend.structure_report()
+    }
+
+    /// The low-level document the parser reads from: objects, trailer,
+    /// catalog and page tree, for processing unpdf does not cover (custom
+    /// annotation handling, say) without loading the file a second time.
+    pub fn raw_document(&self) -> Option<&RawDocument> {
+        self.backend.raw_document()
+    }
+
     /// Check if the document is encrypted.
     pub fn is_encrypted(&self) -> bool {
         self.backend.metadata().encrypted
@@ -168,6 +315,101 @@ impl PdfParser {
         self.backend.metadata().version
     }
 
+    /// Base font names used across all pages, sorted and deduplicated.
+    ///
+    /// Subset prefixes (`ABCDEF+`) are kept so embedded subsets stay
+    /// distinguishable from referenced system fonts. Pages whose font
+    /// dictionary cannot be read are skipped.
+    pub fn font_names(&self) -> Vec<String> {
+        let mut names = BTreeSet::new();
+        for page_id in self.backend.pages().values() {
+            if let Ok(fonts) = self.backend.page_fonts(*page_id) {
+                names.extend(fonts.into_iter().map(|f| f.base_font));
+            }
+        }
+        names.into_iter().filter(|n| !n.is_empty()).collect()
+    }
+
+    /// Decoded text spans of one page, in content-stream order.
+    ///
+    /// This is the decoding layer on its own: every text-showing operation
+    /// with its position, font and style flags, before line grouping,
+    /// header/footer filtering, table or heading detection. Use it to feed a
+    /// custom layout model. An unreadable OCR layer is still dropped unless
+    /// `suppress_low_confidence_ocr` is off, and `page_timeout` applies.
+    pub fn extract_spans(&self, page_num: u32) -> Result<Vec<TextSpan>> {
+        self.check_extraction_permitted()?;
+        LayoutAnalyzer::new(&*self.backend)
+            .with_ocr_suppression(self.options.suppress_low_confidence_ocr)
+            .with_deadline(deadline_after(self.options.page_timeout))
+            .extract_page_spans(page_num)
+    }
+
+    /// Honour `respect_extraction_permission` for entry points that read
+    /// content without going through `run_stream`.
+    fn check_extraction_permitted(&self) -> Result<()> {
+        self.backend
+            .metadata()
+            .check_extraction_permitted(self.options.respect_extraction_permission)
+    }
+
+    /// Size in bytes of one page's decoded content streams, all parts
+    /// together. Unusually large streams are the usual cause of slow pages.
+    pub fn page_content_len(&self, page_num: u32) -> Result<usize> {
+        let pages = self.backend.pages();
+        let page_id = *pages
+            .get(&page_num)
+            .ok_or(Error::PageOutOfRange(page_num, pages.len() as u32))?;
+        Ok(self.backend.page_content(page_id)?.len())
+    }
+
+    /// Tables of the selected pages, each with its page number, in page
+    /// order.
+    ///
+    /// A fast path for callers that only want tabular data: each page's
+    /// spans go through header/footer filtering and table detection and
+    /// nothing else — no heading detection, paragraphs, images or
+    /// annotations, and no cleanup or rendering. Tables that [`parse`]
+    /// would demote to paragraphs for low confidence are left out.
+    ///
+    /// Pages run in parallel unless `parallel` is off or `low_memory` is
+    /// on. `timeout` and `page_timeout` apply; in lenient mode a page that
+    /// fails is skipped with a warning, in strict mode its error is
+    /// returned.
+    ///
+    /// [`parse`]: PdfParser::parse
+    pub fn extract_tables(&self) -> Result<Vec<(u32, Table)>> {
+        use rayon::prelude::*;
+
+        self.check_extraction_permitted()?;
+        let deadline = deadline_after(self.options.timeout);
+        let targets: Vec<u32> = self
+            .backend
+            .pages()
+            .into_keys()
+            .filter(|n| self.options.pages.includes(*n))
+            .collect();
+        let extract = |page_num: u32| {
+            let tables = extract_page_tables(&*self.backend, page_num, &self.options, deadline);
+            (page_num, tables)
+        };
+        let results: Vec<_> = if self.options.parallel && !self.options.low_memory {
+            targets.par_iter().map(|&n| extract(n)).collect()
+        } else {
+            targets.iter().map(|&n| extract(n)).collect()
+        };
+
+        let mut tables = Vec::new();
+        for (page_num, result) in results {
+            match result {
+                Ok(found) => tables.extend(found.into_iter().map(|t| (page_num, t))),
+                Err(e) if self.options.error_mode == ErrorMode::Strict => return Err(e),
+                Err(e) => log::warn!("Failed to extract tables from page {}: {}", page_num, e),
+            }
+        }
+        Ok(tables)
+    }
+
     /// Stream pages in `page_num` ASC order via the provided callback.
     ///
     /// The callback receives `ParseEvent::DocumentStart`, then `PageParsed` /
@@ -205,32 +447,79 @@ impl PdfParser {
     }
 }
 
+/// Load the file bytes `data` the way `options` asks for: lazily in
+/// low-memory mode, handing the bytes over to the backend.
+fn load_backend(data: Vec<u8>, options: &ParseOptions) -> Result<RawBackend> {
+    let passwords = options.password_candidates();
+    let hash = source_hash(&data, options);
+    let backend = if options.low_memory {
+        RawBackend::load_lazy_with_passwords(data, options.revision, &passwords)?
+    } else {
+        RawBackend::load_with_passwords(&data, options.revision, &passwords)?
+    };
+    Ok(backend.with_source_sha256(hash))
+}
+
+/// SHA-256 of the whole input, when `source_hash` asks for it.
+fn source_hash(data: &[u8], options: &ParseOptions) -> Option<String> {
+    options.source_hash.then(|| crate::model::sha256_hex(data))
+}
+
 // ---------------------------------------------------------------------------
 // Module-level free functions (backend-agnostic page parsing)
 // ---------------------------------------------------------------------------
 
 /// Parse a single page without requiring `&PdfParser`. Enables per-page
 /// parallel invocation in `run_stream`.
+///
+/// `deadline` is the document-wide one; the page's own `page_timeout` budget
+/// is layered on top and whichever ends first wins.
 pub(crate) fn parse_single_page(
     backend: &dyn PdfBackend,
     page_num: u32,
     options: &ParseOptions,
+    images: &ImageLimits,
+    deadline: Option<Instant>,
 ) -> Result<Page> {
     let (width, height) = get_page_dimensions_fn(backend, page_num)?;
     let mut page = Page::new(page_num, width, height);
+    // 레이아웃 경로로 만든 블록의 첫 줄 기준선 — 이미지를 본문 사이에 끼울 때 쓴다.
+    let mut text_tops: Vec<f32> = Vec::new();
+    let wants_vector =
+        options.extract_vector_graphics && options.extract_mode != ExtractMode::StructureOnly;
+    let wants_images = options.extract_resources
+        && options.extract_mode != ExtractMode::StructureOnly
+        && options.block_filter.images;
+    // 벡터 그래픽과 이미지 위치는 레이아웃 분석이 디코딩한 오퍼레이터를 다시 쓴다.
+    let mut page_ops: Option<Vec<ContentOp>> = None;
 
-    if options.extract_mode != ExtractMode::StructureOnly {
+    if options.extract_mode != ExtractMode::StructureOnly
+        && options.extraction_strategy == ExtractionStrategy::RawText
+    {
+        let raw = extract_raw_text(backend, page_num, &options.spacing)?;
+        use_raw_text(&mut page, raw);
+    } else if options.extract_mode != ExtractMode::StructureOnly {
+        let deadline = page_deadline(deadline, options);
         // One analyzer per page: the text paths below share its font statistics and
         // its record of whether an unreadable OCR layer was dropped.
         let mut analyzer = super::layout::LayoutAnalyzer::new(backend)
-            .with_ocr_suppression(options.suppress_low_confidence_ocr);
+            .with_ocr_suppression(options.suppress_low_confidence_ocr)
+            .with_column_detection(options.column_detection.clone())
+            .with_spacing(options.spacing.clone())
+            .with_deskew(options.deskew)
+            .with_heading_config(options.heading_config.clone())
+            .with_retained_ops(wants_vector || wants_images)
+            .with_deadline(deadline);
 
-        match extract_page_with_tables_fn(&mut analyzer, page_num) {
+        match extract_page_with_tables_fn(&mut analyzer, page_num, &mut page.table_candidates) {
             Ok(blocks) if !blocks.is_empty() => {
-                for block in blocks {
-                    page.add_block(block);
+                for (y, block, ops) in detect_definition_lists(blocks) {
+                    text_tops.push(y);
+                    page.add_block_with_source(block, ops.map(|ops| analyzer.block_source(ops)));
                 }
             }
+            // 시간 초과 후 fallback 으로 같은 스트림을 다시 훑으면 예산을 두 번 쓴다.
+            Err(e @ Error::Timeout(_)) => return Err(e),
             _ => {
                 fallback_text_extraction_fn(&analyzer, &mut page, page_num, options)?;
             }
@@ -240,18 +529,115 @@ pub(crate) fn parse_single_page(
         let (text_ops, image_ops) = analyzer.page_op_counts();
         page.text_op_count = text_ops;
         page.image_op_count = image_ops;
+        page.skipped_content_bytes = analyzer.skipped_content_bytes();
+        page.body_font_size = analyzer.body_font_size();
+        page.column_count = analyzer.column_count();
+        page_ops = analyzer.take_page_ops();
+
+        if options.extraction_strategy == ExtractionStrategy::Auto {
+            fall_back_to_raw_text(backend, &mut page, &options.spacing);
+            if page.text_strategy == TextStrategy::RawText {
+                text_tops.clear();
+            }
+        }
+    }
+
+    // 읽기 순서 버그 보고용 산출물 — 실패해도 파싱은 계속한다.
+    if let Some(dir) = &options.debug_artifacts {
+        if let Err(e) = super::debug_artifacts::dump_page(backend, page_num, options, dir) {
+            log::warn!("debug artifacts for page {} not written: {}", page_num, e);
+        }
+    }
+
+    // 문단별 언어 — 짧아서 가릴 수 없는 문단은 비워 둔다.
+    if options.tag_languages {
+        for block in &mut page.elements {
+            if let Block::Paragraph(p) = block {
+                p.style.language = detect_language(&p.plain_text()).map(str::to_string);
+            }
+        }
+    }
+
+    // 단어 경계 상자 — 글리프 위치를 기록하며 스트림을 한 번 더 읽는다. 검색어를
+    // 머리말·꼬리말에서도 강조해야 하므로 여백 필터는 거치지 않는다.
+    if options.extract_words && options.extract_mode != ExtractMode::StructureOnly {
+        page.words = LayoutAnalyzer::new(backend)
+            .with_ocr_suppression(options.suppress_low_confidence_ocr)
+            .with_glyph_positions(true)
+            .with_column_detection(options.column_detection.clone())
+            .with_spacing(options.spacing.clone())
+            .extract_page_words(page_num)
+            .unwrap_or_default();
+    }
+
+    // 주석(메모·강조) — 마크업 주석이 있을 때만 덮인 글자를 찾으려고 스팬을 다시 뽑는다.
+    let raw_annotations = backend
+        .pages()
+        .get(&page_num)
+        .map(|id| backend.page_annotations(*id))
+        .unwrap_or_default();
+    if !raw_annotations.is_empty() {
+        let spans =
+            if options.extract_mode != ExtractMode::StructureOnly && has_markup(&raw_annotations) {
+                LayoutAnalyzer::new(backend)
+                    .with_ocr_suppression(false)
+                    .extract_page_spans(page_num)
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+        page.annotations = convert_annotations(raw_annotations, &spans);
+    }
+
+    // 레이아웃 분석을 거치지 않았으면(RawText 전략 등) 여기서 한 번만 디코딩한다.
+    if (wants_vector || wants_images) && page_ops.is_none() {
+        page_ops = backend
+            .pages()
+            .get(&page_num)
+            .and_then(|id| backend.page_operations(*id).ok())
+            .map(|content| content.ops);
+    }
+    let page_ops = page_ops.unwrap_or_default();
+
+    // 그린 자리가 알려진 그림은 윗변보다 아래에서 시작하는 첫 본문 블록 앞에 끼운다.
+    // 자리를 모르면 (Form XObject 안에서 그려졌거나 본문 위치가 없으면) 페이지 말미에 둔다.
+    let drawn_index = |top: f32, len: usize| {
+        if text_tops.is_empty() {
+            len
+        } else {
+            text_tops
+                .iter()
+                .position(|&y| y < top)
+                .unwrap_or(text_tops.len())
+        }
+    };
+    let mut placed: Vec<(usize, Block)> = Vec::new();
+
+    // 벡터 그래픽(차트·도식) — 같은 자리의 이미지 XObject 보다 앞에 둔다.
+    if wants_vector {
+        if let Some(block) = capture_vector_drawing(&page_ops, &mut page, options) {
+            let index = match &block {
+                Block::Image { y: Some(top), .. } => drawn_index(*top, page.elements.len()),
+                _ => page.elements.len(),
+            };
+            placed.push((index, block));
+        }
     }
 
     // 이미지(XObject) 수집 — extract_resources 가 활성화된 경우.
-    // 현재는 정확한 Y 좌표 해석이 안 되어 페이지 말미에 순서대로 append.
-    // 향후 Phase 2 에서 content-stream 의 Do 연산자 위치 해석으로 interleave 예정.
+    // `Do` 시점의 CTM 으로 놓인 자리를 구해 본문 블록 사이에 끼운다.
     // id 는 확장자 포함: `page{N}_{name}.{ext}`. 이 id 를 곧 이미지의
     // 파일명으로도 사용하므로 writer 측에서 별도 suggested_filename 호출 불필요.
-    if options.extract_resources && options.extract_mode != ExtractMode::StructureOnly {
+    if wants_images {
         let pages = backend.pages();
         if let Some(page_id) = pages.get(&page_num) {
-            if let Ok(xobjects) = backend.page_xobjects(*page_id) {
+            if let Ok(xobjects) = backend.page_xobjects_bounded(*page_id, images) {
+                let mut placements = super::vector::xobject_placements(&page_ops);
                 for xobj in xobjects {
+                    let placement = placements
+                        .iter()
+                        .position(|p| p.name == xobj.name)
+                        .map(|i| placements.remove(i));
                     let base_id = format!("page{}_{}", page_num, xobj.name);
                     if let Some(resource) = convert_xobject_pub(xobj) {
                         // 뷰어가 렌더할 수 있는 이미지 포맷만 MD/디스크에 포함.
@@ -288,7 +674,12 @@ pub(crate) fn parse_single_page(
                             *bw = resource.width.map(|w| w as f32);
                             *bh = resource.height.map(|h| h as f32);
                         }
-                        page.add_block(img_block);
+                        let mut index = page.elements.len();
+                        if let Some(p) = placement {
+                            img_block = placed_image(img_block, p.bbox);
+                            index = drawn_index(p.bbox[3], index);
+                        }
+                        placed.push((index, img_block));
                         page.images.push((id, resource));
                     }
                 }
@@ -296,9 +687,185 @@ pub(crate) fn parse_single_page(
         }
     }
 
+    // 같은 자리에 여럿이면 그린 순서를 지키도록 뒤에서부터 끼운다.
+    placed.sort_by_key(|(index, _)| *index);
+    for (index, block) in placed.into_iter().rev() {
+        if page.block_sources.len() == page.elements.len() {
+            page.block_sources.insert(index, None);
+        }
+        page.elements.insert(index, block);
+    }
+
+    // 이미지까지 자리를 잡은 뒤 묶어야 목록 사이의 그림이 목록을 끊는다.
+    if options.group_lists {
+        page.group_lists();
+    }
+
     Ok(page)
 }
 
+/// The tables of one page for [`PdfParser::extract_tables`]: table detection
+/// as in `extract_page_with_tables_fn`, with the text around the tables
+/// dropped instead of laid out.
+fn extract_page_tables(
+    backend: &dyn PdfBackend,
+    page_num: u32,
+    options: &ParseOptions,
+    deadline: Option<Instant>,
+) -> Result<Vec<Table>> {
+    if is_expired(deadline) {
+        return Err(Error::Timeout(format!(
+            "document time budget ran out before page {}",
+            page_num
+        )));
+    }
+    let analyzer = LayoutAnalyzer::new(backend)
+        .with_ocr_suppression(options.suppress_low_confidence_ocr)
+        .with_column_detection(options.column_detection.clone())
+        .with_spacing(options.spacing.clone())
+        .with_deskew(options.deskew)
+        .with_deadline(page_deadline(deadline, options));
+    let mut spans = analyzer.extract_page_spans(page_num)?;
+    analyzer.filter_spans_for_page(&mut spans, page_num);
+    if spans.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // 다단 페이지는 단마다 먼저 찾고, 없으면 페이지 전체로 찾는다.
+    let column_detector = TableDetector::with_config(TableDetectorConfig::for_column());
+    let page_detector = TableDetector::new();
+    let mut detector = &column_detector;
+    let mut found: Vec<DetectedTable> = Vec::new();
+    let regions = analyzer.layout_regions(spans.clone());
+    if regions.len() > 1 {
+        for region in regions {
+            found.extend(column_detector.detect(region).0);
+        }
+        analyzer.check_deadline()?;
+    }
+    if found.is_empty() {
+        detector = &page_detector;
+        found = page_detector.detect(spans).0;
+        analyzer.check_deadline()?;
+    }
+
+    Ok(found
+        .iter()
+        .filter(|detected| detected.confidence >= TABLE_CONFIDENCE_THRESHOLD)
+        .map(|detected| detector.to_table_model(detected))
+        .filter(|table| !table.is_empty())
+        .collect())
+}
+
+/// The page's own `page_timeout` budget, cut short by the document-wide
+/// `deadline` when that ends first.
+fn page_deadline(deadline: Option<Instant>, options: &ParseOptions) -> Option<Instant> {
+    match (deadline, deadline_after(options.page_timeout)) {
+        (Some(doc), Some(page)) => Some(doc.min(page)),
+        (doc, page) => doc.or(page),
+    }
+}
+
+/// Give an image block the position and drawn size (in points) of its
+/// placement `[x0, y0, x1, y1]`; `y` is the top edge, as for vector drawings.
+fn placed_image(block: Block, bbox: [f32; 4]) -> Block {
+    match block {
+        Block::Image {
+            resource_id,
+            alt_text,
+            ..
+        } => Block::Image {
+            resource_id,
+            alt_text,
+            width: Some(bbox[2] - bbox[0]),
+            height: Some(bbox[3] - bbox[1]),
+            x: Some(bbox[0]),
+            y: Some(bbox[3]),
+        },
+        other => other,
+    }
+}
+
+/// Capture the page's vector drawing as an SVG resource.
+///
+/// The resource goes into `page.images` under `page{N}_vector.svg`; the
+/// returned `Block::Image` carries the drawing's bounding box in points.
+fn capture_vector_drawing(
+    ops: &[ContentOp],
+    page: &mut Page,
+    options: &ParseOptions,
+) -> Option<Block> {
+    let drawing = super::vector::capture_page_drawing(ops, page.width * page.height)?;
+
+    // 장식용 작은 도형은 이미지와 같은 기준으로 거른다 (1pt = 1px 로 간주).
+    let min_px = options.min_image_dimension as f32;
+    if drawing.width < min_px || drawing.height < min_px {
+        return None;
+    }
+
+    let id = format!("page{}_vector.svg", page.number);
+    let resource = Resource::new(
+        drawing.svg.into_bytes(),
+        "image/svg+xml",
+        ResourceType::Vector,
+    )
+    .with_dimensions(drawing.width.round() as u32, drawing.height.round() as u32);
+    page.images.push((id.clone(), resource));
+
+    Some(Block::Image {
+        resource_id: id,
+        alt_text: None,
+        width: Some(drawing.width),
+        height: Some(drawing.height),
+        x: Some(drawing.x),
+        y: Some(drawing.y),
+    })
+}
+
+/// Auto 전략의 페이지별 대체 — 레이아웃 해석기가 텍스트 오퍼레이터 수보다도
+/// 적은 글자를 건진 페이지만 스트림 순서 덤프를 떠 보고, 그쪽이 두 배 넘게
+/// 많을 때 갈아끼운다. OCR 게이트가 일부러 버린 페이지는 건드리지 않는다.
+fn fall_back_to_raw_text(backend: &dyn PdfBackend, page: &mut Page, spacing: &SpacingPolicy) {
+    if page.ocr_text_suppressed || page.text_op_count == 0 {
+        return;
+    }
+    let mut text = String::new();
+    for block in &page.elements {
+        block.append_plain_text(&mut text);
+    }
+    let content_chars = count_visible_chars(&text);
+    if content_chars >= page.text_op_count as usize {
+        return;
+    }
+    let Ok(raw) = extract_raw_text(backend, page.number, spacing) else {
+        return;
+    };
+    if count_visible_chars(&raw.text) > content_chars * 2 {
+        log::debug!(
+            "page {}: layout analysis recovered {} chars, using stream-order text",
+            page.number,
+            content_chars
+        );
+        page.elements.clear();
+        page.block_sources.clear();
+        use_raw_text(page, raw);
+    }
+}
+
+/// Replace the page's text with a stream-order dump.
+fn use_raw_text(page: &mut Page, raw: RawPageText) {
+    if !raw.text.is_empty() {
+        page.add_paragraph(Paragraph::with_text(raw.text));
+    }
+    page.text_op_count = raw.text_op_count;
+    page.image_op_count = raw.image_op_count;
+    page.text_strategy = TextStrategy::RawText;
+}
+
+fn count_visible_chars(text: &str) -> usize {
+    text.chars().filter(|c| !c.is_whitespace()).count()
+}
+
 /// Free-function version of `PdfParser::convert_xobject` so `parse_single_page`
 /// (and other `run_stream` consumers) can use it without needing `&self`.
 pub(crate) fn convert_xobject_pub(xobj: RawXObject) -> Option<Resource> {
@@ -344,24 +911,24 @@ fn get_page_dimensions_fn(backend: &dyn PdfBackend, page_num: u32) -> Result<(f3
 /// (Y within 1.5pt of each other) into a single paragraph. Recovers
 /// table-row structure that XY-Cut over-segmented into per-cell blocks.
 /// Headings, tables, images, and rule blocks are never merged.
-fn merge_same_row_paragraphs(elements: Vec<(f32, Block)>) -> Vec<(f32, Block)> {
+fn merge_same_row_paragraphs(elements: Vec<PlacedBlock>) -> Vec<PlacedBlock> {
     // Tolerance ≈ half of body line height. Table cells in Hancom PDFs
     // frequently sit on slightly offset baselines within the same visual row
     // (header centred vs. body top-aligned). 6pt catches most real rows
     // without merging across line breaks.
     const ROW_Y_TOLERANCE: f32 = 6.0;
-    let mut out: Vec<(f32, Block)> = Vec::with_capacity(elements.len());
-    for (y, block) in elements {
+    let mut out: Vec<PlacedBlock> = Vec::with_capacity(elements.len());
+    for (y, block, ops) in elements {
         let Block::Paragraph(p) = &block else {
-            out.push((y, block));
+            out.push((y, block, ops));
             continue;
         };
         if p.style.heading_level.is_some() || p.style.list_info.is_some() {
-            out.push((y, block));
+            out.push((y, block, ops));
             continue;
         }
         // Can we merge into the previous?
-        if let Some((prev_y, Block::Paragraph(prev_p))) = out.last_mut().map(|(y, b)| (y, b)) {
+        if let Some((prev_y, Block::Paragraph(prev_p), prev_ops)) = out.last_mut() {
             if (*prev_y - y).abs() <= ROW_Y_TOLERANCE
                 && prev_p.style.heading_level.is_none()
                 && prev_p.style.list_info.is_none()
@@ -376,18 +943,30 @@ fn merge_same_row_paragraphs(elements: Vec<(f32, Block)>) -> Vec<(f32, Block)> {
                 }
                 combined.push_str(&cur_text);
                 *prev_p = Paragraph::with_text(combined);
+                *prev_ops = match (*prev_ops, ops) {
+                    (Some(a), Some(b)) => Some([a[0].min(b[0]), a[1].max(b[1])]),
+                    (a, b) => a.or(b),
+                };
                 continue;
             }
         }
-        out.push((y, block));
+        out.push((y, block, ops));
     }
     out
 }
 
+/// A block with the baseline of its first line and the range of content
+/// operators that drew it.
+pub(super) type PlacedBlock = (f32, Block, Option<[u32; 2]>);
+
+/// Blocks in reading order, each with the baseline of its first line (PDF
+/// coordinates, y up) so images can be slotted in between, and the range of
+/// operators that drew it. Table regions left as text go to `candidates`.
 fn extract_page_with_tables_fn(
     analyzer: &mut super::layout::LayoutAnalyzer,
     page_num: u32,
-) -> Result<Vec<Block>> {
+    candidates: &mut Vec<TableDetection>,
+) -> Result<Vec<PlacedBlock>> {
     let mut spans = analyzer.extract_page_spans(page_num)?;
 
     // Apply header/footer filter before table detection so page numbers
@@ -398,10 +977,40 @@ fn extract_page_with_tables_fn(
         return Ok(vec![]);
     }
 
-    let table_detector = super::table_detector::TableDetector::new();
-    let (detected_tables, remaining_spans) = table_detector.detect(spans.clone());
+    // 다단 페이지는 단마다 표를 찾는다. 단 하나에 든 작은 표는 페이지
+    // 전체로 보면 단 경계와 표 열이 뒤섞여 놓친다.
+    let regions = analyzer.layout_regions(spans.clone());
+    if regions.len() > 1 {
+        let table_detector = TableDetector::with_config(TableDetectorConfig::for_column());
+        let detected: Vec<_> = regions
+            .into_iter()
+            .map(|r| table_detector.detect_with_candidates(r))
+            .collect();
+        analyzer.check_deadline()?;
+        if detected.iter().any(|(tables, _, _)| !tables.is_empty()) {
+            log::debug!("Detected tables within columns on page {}", page_num);
+            let mut blocks = Vec::new();
+            for (tables, rejected, remaining) in detected {
+                candidates.extend(rejected);
+                blocks.extend(place_tables_and_text(
+                    analyzer,
+                    &table_detector,
+                    &tables,
+                    remaining,
+                    candidates,
+                ));
+            }
+            return Ok(blocks);
+        }
+    }
+
+    let table_detector = TableDetector::new();
+    let (detected_tables, rejected, remaining_spans) =
+        table_detector.detect_with_candidates(spans.clone());
+    candidates.extend(rejected);
+    analyzer.check_deadline()?;
 
-    let mut blocks: Vec<Block> = Vec::new();
+    let mut blocks: Vec<PlacedBlock> = Vec::new();
 
     if !detected_tables.is_empty() {
         log::debug!(
@@ -409,76 +1018,22 @@ fn extract_page_with_tables_fn(
             detected_tables.len(),
             page_num
         );
-
-        let mut elements: Vec<(f32, Block)> = Vec::new();
-
-        const TABLE_CONFIDENCE_THRESHOLD: f32 = 0.4;
-        for detected in &detected_tables {
-            if detected.confidence < TABLE_CONFIDENCE_THRESHOLD {
-                log::debug!(
-                    "Table at y={} has low confidence ({:.2}), converting to paragraphs",
-                    detected.top_y,
-                    detected.confidence
-                );
-                for row in &detected.rows {
-                    let text = row
-                        .spans
-                        .iter()
-                        .map(|s| s.text.as_str())
-                        .collect::<Vec<_>>()
-                        .join("  ");
-                    if !text.trim().is_empty() {
-                        elements.push((row.y, Block::Paragraph(Paragraph::with_text(text))));
-                    }
-                }
-            } else {
-                let table = table_detector.to_table_model(detected);
-                if !table.is_empty() {
-                    elements.push((detected.top_y, Block::Table(table)));
-                }
-            }
-        }
-
-        if !remaining_spans.is_empty() {
-            let a = &mut *analyzer;
-            for span in &remaining_spans {
-                a.font_stats_mut().add_size(span.font_size);
-            }
-            a.font_stats_mut().analyze();
-
-            let lines = a.group_spans_into_lines_pub(remaining_spans);
-            let lines = a.detect_headings_pub(lines);
-            let text_blocks = a.group_lines_into_blocks_pub(lines);
-
-            for block in text_blocks {
-                if !block.is_empty() {
-                    let text = block.text();
-                    let y_pos = block.lines.first().map(|l| l.y).unwrap_or(0.0);
-                    let para_block = match block.block_type {
-                        super::layout::BlockType::Heading => {
-                            let level = block.heading_level.clamp(1, 6);
-                            Block::Paragraph(Paragraph::heading(text, level))
-                        }
-                        super::layout::BlockType::Paragraph | super::layout::BlockType::Unknown => {
-                            Block::Paragraph(Paragraph::with_text(text))
-                        }
-                        super::layout::BlockType::ListItem => {
-                            Block::Paragraph(Paragraph::with_text(format!("• {}", text)))
-                        }
-                    };
-                    elements.push((y_pos, para_block));
-                }
-            }
-        }
-
-        elements.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
-        let merged = merge_same_row_paragraphs(elements);
-        blocks = merged.into_iter().map(|(_, block)| block).collect();
+        blocks = place_tables_and_text(
+            analyzer,
+            &table_detector,
+            &detected_tables,
+            remaining_spans,
+            candidates,
+        );
     } else {
         let text_blocks = analyzer.extract_page_blocks(page_num)?;
         for block in text_blocks {
+            if let Some(entries) = glossary_block_entries(&block, analyzer.spacing()) {
+                blocks.extend(entries);
+                continue;
+            }
             if !block.is_empty() {
-                let text = block.text();
+                let text = block.text_with(analyzer.spacing());
                 log::debug!(
                     "Block type: {:?}, heading_level: {}, text preview: {}",
                     block.block_type,
@@ -502,8 +1057,11 @@ fn extract_page_with_tables_fn(
                     super::layout::BlockType::ListItem => {
                         Block::Paragraph(Paragraph::with_text(format!("• {}", text)))
                     }
+                    super::layout::BlockType::Code => code_block(&block),
                 };
-                blocks.push(para_block);
+                let y_pos = block.lines.first().map(|l| l.y).unwrap_or(0.0);
+                let ops = span_ops(block.lines.iter().flat_map(|l| &l.spans));
+                blocks.push((y_pos, para_block, ops));
             }
         }
     }
@@ -511,6 +1069,120 @@ fn extract_page_with_tables_fn(
     Ok(blocks)
 }
 
+/// Detected tables below this confidence are kept as paragraphs of text.
+const TABLE_CONFIDENCE_THRESHOLD: f32 = 0.4;
+
+/// Resource id of an XFA form's `datasets` packet, kept when resources
+/// are extracted.
+pub const XFA_DATASETS_ID: &str = "xfa_datasets";
+
+/// Blocks for `detected` tables and the text around them, top to bottom.
+/// Tables demoted to text for low confidence go to `candidates`.
+fn place_tables_and_text(
+    analyzer: &mut super::layout::LayoutAnalyzer,
+    table_detector: &TableDetector,
+    detected_tables: &[DetectedTable],
+    remaining_spans: Vec<TextSpan>,
+    candidates: &mut Vec<TableDetection>,
+) -> Vec<PlacedBlock> {
+    let mut elements: Vec<PlacedBlock> = Vec::new();
+
+    for detected in detected_tables {
+        if detected.confidence < TABLE_CONFIDENCE_THRESHOLD {
+            log::debug!(
+                "Table at y={} has low confidence ({:.2}), converting to paragraphs",
+                detected.top_y,
+                detected.confidence
+            );
+            candidates.push(TableDetection {
+                rejected: Some(TableRejection::LowConfidence),
+                ..detected.detection()
+            });
+            for row in &detected.rows {
+                let text = row
+                    .spans
+                    .iter()
+                    .map(|s| s.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                if !text.trim().is_empty() {
+                    let paragraph = Block::Paragraph(Paragraph::with_text(text));
+                    elements.push((row.y, paragraph, span_ops(&row.spans)));
+                }
+            }
+        } else {
+            let table = table_detector.to_table_model(detected);
+            if !table.is_empty() {
+                let ops = span_ops(detected.rows.iter().flat_map(|r| &r.spans));
+                elements.push((detected.top_y, Block::Table(table), ops));
+            }
+        }
+    }
+
+    if !remaining_spans.is_empty() {
+        let a = &mut *analyzer;
+        for span in &remaining_spans {
+            a.font_stats_mut().add_size(span.font_size);
+        }
+        a.font_stats_mut().analyze();
+
+        let lines = a.group_spans_into_lines_pub(remaining_spans);
+        let lines = a.detect_headings_pub(lines);
+        let text_blocks = a.group_lines_into_blocks_pub(lines);
+
+        for block in text_blocks {
+            if let Some(entries) = glossary_block_entries(&block, a.spacing()) {
+                elements.extend(entries);
+                continue;
+            }
+            if !block.is_empty() {
+                let text = block.text_with(a.spacing());
+                let y_pos = block.lines.first().map(|l| l.y).unwrap_or(0.0);
+                let para_block = match block.block_type {
+                    super::layout::BlockType::Heading => {
+                        let level = block.heading_level.clamp(1, 6);
+                        Block::Paragraph(Paragraph::heading(text, level))
+                    }
+                    super::layout::BlockType::Paragraph | super::layout::BlockType::Unknown => {
+                        Block::Paragraph(Paragraph::with_text(text))
+                    }
+                    super::layout::BlockType::ListItem => {
+                        Block::Paragraph(Paragraph::with_text(format!("• {}", text)))
+                    }
+                    super::layout::BlockType::Code => code_block(&block),
+                };
+                let ops = span_ops(block.lines.iter().flat_map(|l| &l.spans));
+                elements.push((y_pos, para_block, ops));
+            }
+        }
+    }
+
+    elements.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    merge_same_row_paragraphs(elements)
+}
+
+/// Definition list items for a paragraph block set as a single-spaced
+/// glossary.
+fn glossary_block_entries(
+    block: &super::layout::TextBlock,
+    spacing: &SpacingPolicy,
+) -> Option<Vec<PlacedBlock>> {
+    match block.block_type {
+        super::layout::BlockType::Paragraph | super::layout::BlockType::Unknown => {
+            block_entries(block, spacing)
+        }
+        _ => None,
+    }
+}
+
+/// Convert a monospace text block into a `Block::CodeBlock`, keeping its
+/// line structure and indentation.
+fn code_block(block: &super::layout::TextBlock) -> Block {
+    let content = block.code_text();
+    let language = super::code::guess_language(&content);
+    Block::code(content, language)
+}
+
 fn fallback_text_extraction_fn(
     analyzer: &super::layout::LayoutAnalyzer,
     page: &mut Page,
@@ -519,14 +1191,14 @@ fn fallback_text_extraction_fn(
 ) -> Result<()> {
     match analyzer.extract_page_spans(page_num) {
         Ok(spans) if !spans.is_empty() => {
+            // 줄 간격으로 문단을 나누고 문단 안의 줄은 이어 붙인다 — 렌더 뒤
+            // 정리 단계가 줄바꿈만 보고 문단을 추측하지 않아도 되도록.
             let lines = analyzer.group_spans_into_lines_pub(spans);
-            let text = lines
-                .iter()
-                .map(|l| l.text())
-                .collect::<Vec<_>>()
-                .join("\n");
-            if !text.trim().is_empty() {
-                page.add_paragraph(Paragraph::with_text(text));
+            for block in analyzer.group_lines_into_blocks_pub(lines) {
+                let text = block.text_with(analyzer.spacing());
+                if !text.is_empty() {
+                    page.add_paragraph(Paragraph::with_text(text));
+                }
             }
         }
         Ok(_) => {}
@@ -0,0 +1,69 @@
+//! Metadata-only open: the trailer, Info dictionary, page tree and outline,
+//! without reading page contents, fonts or images.
+
+use serde::Serialize;
+
+use crate::error::{Error, Result};
+use crate::model::{EncryptionKind, Metadata};
+
+use super::backend::{PdfBackend, RawBackend, RawOutlineItem};
+use super::raw::RawDocument;
+use super::stream::document_metadata;
+
+/// What [`crate::peek`] reads from a PDF without parsing its pages.
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentInfo {
+    /// Document metadata, as [`Document::metadata`](crate::Document) holds
+    /// it after a full parse. For a document that needs a user password
+    /// only the version, page count and encryption are known.
+    pub metadata: Metadata,
+
+    /// Number of bookmarks, nested ones included; 0 when the outline is
+    /// missing or encrypted
+    pub outline_count: usize,
+
+    /// Size of the file in bytes
+    pub file_size: u64,
+}
+
+/// Read `data`'s [`DocumentInfo`]. Objects are parsed only when looked up,
+/// so the cost grows with the page count rather than the file size.
+pub(crate) fn peek_data(data: Vec<u8>) -> Result<DocumentInfo> {
+    let file_size = data.len() as u64;
+    let mut doc = RawDocument::load_structure(data)?;
+    if doc.is_encrypted() {
+        let mut metadata = Metadata::with_version(doc.version.clone());
+        metadata.page_count = doc.page_count();
+        let data = doc.into_data().unwrap_or_default();
+        doc = match RawDocument::load(&data) {
+            Ok(doc) => doc,
+            // 비밀번호 없이는 문자열을 읽을 수 없으니, 암호화되지 않은 구조만 본다.
+            Err(Error::Encrypted) => {
+                metadata.encrypted = true;
+                metadata.encryption = Some(EncryptionKind::UserPassword);
+                return Ok(DocumentInfo {
+                    metadata,
+                    outline_count: 0,
+                    file_size,
+                });
+            }
+            Err(e) => return Err(e),
+        };
+    }
+
+    let backend = RawBackend::from_document(doc);
+    let page_count = backend.pages().len() as u32;
+    let outline_count = backend.outline().map_or(0, |items| count_items(&items));
+    Ok(DocumentInfo {
+        metadata: document_metadata(backend.metadata(), page_count),
+        outline_count,
+        file_size,
+    })
+}
+
+fn count_items(items: &[RawOutlineItem]) -> usize {
+    items
+        .iter()
+        .map(|item| 1 + count_items(&item.children))
+        .sum()
+}
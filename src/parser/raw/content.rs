@@ -5,8 +5,43 @@ use crate::parser::backend::{ContentOp, PdfValue};
 
 /// Parse a content stream into a sequence of operations.
 pub fn parse_content_stream(data: &[u8]) -> Result<Vec<ContentOp>> {
-    let mut ops = Vec::new();
+    Ok(decode_content_stream(data).ops)
+}
+
+/// Parse a content stream like [`parse_content_stream`], also returning the
+/// byte offset of each operation's operator in `data`.
+pub fn parse_content_stream_with_offsets(data: &[u8]) -> Result<(Vec<ContentOp>, Vec<usize>)> {
+    let decoded = decode_content_stream(data);
+    Ok((decoded.ops, decoded.offsets))
+}
+
+/// Operations decoded from a content stream by [`decode_content_stream`].
+#[derive(Debug, Clone, Default)]
+pub struct DecodedContent {
+    /// Operations in stream order
+    pub ops: Vec<ContentOp>,
+    /// Byte offset of each operation's operator
+    pub offsets: Vec<usize>,
+    /// Bytes dropped as unparseable
+    pub skipped_bytes: usize,
+}
+
+/// Decode a content stream, skipping what cannot be parsed instead of
+/// failing.
+///
+/// Decoding resumes at the next operator boundary after stray delimiters and
+/// binary junk, an unknown operator outside a `BX`/`EX` section together with
+/// its operands, an operator short of operands, and an unterminated string
+/// (up to the end of its line). Surplus operands in front of a fixed-arity
+/// operator are dropped, keeping the last ones, and an array left open ends
+/// at the next operator. All of it is counted in
+/// [`DecodedContent::skipped_bytes`], apart from the implicitly closed array.
+pub fn decode_content_stream(data: &[u8]) -> DecodedContent {
+    let mut decoded = DecodedContent::default();
     let mut operand_stack: Vec<PdfValue> = Vec::new();
+    // 각 피연산자의 시작 위치 — 버리는 구간의 바이트 수를 세기 위함.
+    let mut operand_starts: Vec<usize> = Vec::new();
+    let mut compat_depth = 0usize;
     let len = data.len();
     let mut i = 0;
 
@@ -27,15 +62,35 @@ pub fn parse_content_stream(data: &[u8]) -> Result<Vec<ContentOp>> {
 
         // Literal string (...)
         if data[i] == b'(' {
-            let (val, next) = parse_literal_string(data, i);
-            operand_stack.push(val);
-            i = next;
+            let (val, next, closed) = scan_literal_string(data, i);
+            if closed {
+                operand_starts.push(i);
+                operand_stack.push(val);
+                i = next;
+            } else {
+                // 닫히지 않은 문자열은 스트림 끝까지 삼키므로 그 줄만 버리고 잇는다.
+                let line_end = data[i..]
+                    .iter()
+                    .position(|&b| b == b'\n' || b == b'\r')
+                    .map_or(len, |n| i + n);
+                decoded.skipped_bytes += line_end - i;
+                i = line_end;
+            }
+            continue;
+        }
+
+        // Inline dictionary <<...>> (marked-content properties)
+        if data[i] == b'<' && i + 1 < len && data[i + 1] == b'<' {
+            operand_starts.push(i);
+            operand_stack.push(PdfValue::Other);
+            i = skip_dictionary(data, i);
             continue;
         }
 
         // Hex string <...>
         if data[i] == b'<' && i + 1 < len && data[i + 1] != b'<' {
             let (val, next) = parse_hex_string(data, i);
+            operand_starts.push(i);
             operand_stack.push(val);
             i = next;
             continue;
@@ -44,6 +99,7 @@ pub fn parse_content_stream(data: &[u8]) -> Result<Vec<ContentOp>> {
         // Array [...]
         if data[i] == b'[' {
             let (val, next) = parse_array(data, i);
+            operand_starts.push(i);
             operand_stack.push(val);
             i = next;
             continue;
@@ -52,6 +108,7 @@ pub fn parse_content_stream(data: &[u8]) -> Result<Vec<ContentOp>> {
         // Name /Foo
         if data[i] == b'/' {
             let (val, next) = parse_name(data, i);
+            operand_starts.push(i);
             operand_stack.push(val);
             i = next;
             continue;
@@ -60,6 +117,7 @@ pub fn parse_content_stream(data: &[u8]) -> Result<Vec<ContentOp>> {
         // Number (integer or real), or negative sign
         if data[i] == b'-' || data[i] == b'+' || data[i] == b'.' || data[i].is_ascii_digit() {
             let (val, next) = parse_number(data, i);
+            operand_starts.push(i);
             operand_stack.push(val);
             i = next;
             continue;
@@ -72,8 +130,9 @@ pub fn parse_content_stream(data: &[u8]) -> Result<Vec<ContentOp>> {
             while i < len && data[i].is_ascii_alphabetic() {
                 i += 1;
             }
-            // Check for * suffix (T*, b*, B*)
-            if i < len && data[i] == b'*' {
+            // Check for * suffix (T*, b*, B*), or the digit of d0/d1
+            let type3_width = &data[start..i] == b"d" && matches!(data.get(i), Some(b'0' | b'1'));
+            if type3_width || (i < len && data[i] == b'*') {
                 i += 1;
             }
 
@@ -82,33 +141,53 @@ pub fn parse_content_stream(data: &[u8]) -> Result<Vec<ContentOp>> {
 
             match token_str {
                 "true" | "false" | "null" => {
+                    operand_starts.push(start);
                     operand_stack.push(PdfValue::Other);
+                    continue;
                 }
                 "BI" => {
                     // Inline image: skip until EI
                     i = skip_inline_image(data, i);
-                    // Emit BI as an operator with no operands (stack should be empty)
-                    ops.push(ContentOp {
-                        operator: "BI".to_string(),
-                        operands: std::mem::take(&mut operand_stack),
-                    });
                 }
-                _ => {
-                    // It's an operator
-                    ops.push(ContentOp {
-                        operator: token_str.to_string(),
-                        operands: std::mem::take(&mut operand_stack),
-                    });
+                "BX" => compat_depth += 1,
+                "EX" => compat_depth = compat_depth.saturating_sub(1),
+                _ => {}
+            }
+
+            // 모르는 오퍼레이터는 BX/EX 호환 구간 안에서만 허용된다.
+            let arity = operator_arity(token_str);
+            let segment_start = operand_starts.first().copied().unwrap_or(start);
+            if (arity.is_none() && compat_depth == 0)
+                || arity.is_some_and(|(min, _)| operand_stack.len() < min)
+            {
+                decoded.skipped_bytes += i - segment_start;
+                operand_stack.clear();
+                operand_starts.clear();
+                continue;
+            }
+            if let Some((_, Some(max))) = arity {
+                if operand_stack.len() > max {
+                    let surplus = operand_stack.len() - max;
+                    decoded.skipped_bytes += operand_starts[surplus] - segment_start;
+                    operand_stack.drain(..surplus);
                 }
             }
+            operand_starts.clear();
+            decoded.offsets.push(start);
+            decoded.ops.push(ContentOp {
+                operator: token_str.to_string(),
+                operands: std::mem::take(&mut operand_stack),
+            });
             continue;
         }
 
         // Special single-char operators: ' and "
         if data[i] == b'\'' || data[i] == b'"' {
             let op = (data[i] as char).to_string();
+            decoded.offsets.push(i);
             i += 1;
-            ops.push(ContentOp {
+            operand_starts.clear();
+            decoded.ops.push(ContentOp {
                 operator: op,
                 operands: std::mem::take(&mut operand_stack),
             });
@@ -116,10 +195,35 @@ pub fn parse_content_stream(data: &[u8]) -> Result<Vec<ContentOp>> {
         }
 
         // Skip unknown bytes
+        decoded.skipped_bytes += 1;
         i += 1;
     }
 
-    Ok(ops)
+    // 오퍼레이터 없이 끝난 피연산자
+    if let Some(&first) = operand_starts.first() {
+        decoded.skipped_bytes += len - first;
+    }
+
+    decoded
+}
+
+/// Operand count range `(min, max)` of a content stream operator, `max`
+/// being `None` for colour operators taking any number; `None` for an
+/// operator PDF does not define.
+fn operator_arity(op: &str) -> Option<(usize, Option<usize>)> {
+    let n = match op {
+        "b" | "B" | "b*" | "B*" | "BI" | "BT" | "BX" | "EI" | "EMC" | "ET" | "EX" | "f" | "F"
+        | "f*" | "h" | "ID" | "n" | "q" | "Q" | "s" | "S" | "T*" | "W" | "W*" => 0,
+        "BMC" | "CS" | "cs" | "Do" | "g" | "G" | "gs" | "i" | "j" | "J" | "M" | "MP" | "ri"
+        | "sh" | "Tc" | "Tj" | "TJ" | "TL" | "Tr" | "Ts" | "Tw" | "Tz" | "w" | "'" => 1,
+        "BDC" | "d" | "d0" | "DP" | "l" | "m" | "Td" | "TD" | "Tf" => 2,
+        "\"" | "rg" | "RG" => 3,
+        "k" | "K" | "re" | "v" | "y" => 4,
+        "c" | "cm" | "d1" | "Tm" => 6,
+        "sc" | "SC" | "scn" | "SCN" => return Some((1, None)),
+        _ => return None,
+    };
+    Some((n, Some(n)))
 }
 
 fn is_whitespace(b: u8) -> bool {
@@ -139,6 +243,12 @@ fn is_delimiter(b: u8) -> bool {
 
 /// Parse a literal string starting at `(`, returns (PdfValue, next_index).
 fn parse_literal_string(data: &[u8], start: usize) -> (PdfValue, usize) {
+    let (val, next, _) = scan_literal_string(data, start);
+    (val, next)
+}
+
+/// [`parse_literal_string`], also telling whether the closing `)` was found.
+fn scan_literal_string(data: &[u8], start: usize) -> (PdfValue, usize, bool) {
     let mut i = start + 1; // skip opening '('
     let mut result = Vec::new();
     let mut depth = 1;
@@ -232,7 +342,7 @@ fn parse_literal_string(data: &[u8], start: usize) -> (PdfValue, usize) {
         }
     }
 
-    (PdfValue::Str(result), i)
+    (PdfValue::Str(result), i, depth == 0)
 }
 
 /// Parse a hex string starting at `<`, returns (PdfValue, next_index).
@@ -401,6 +511,10 @@ fn parse_array(data: &[u8], start: usize) -> (PdfValue, usize) {
                 "true" | "false" | "null" => {
                     elements.push(PdfValue::Other);
                 }
+                // 닫는 `]` 가 빠진 배열 — 오퍼레이터 앞에서 끝낸다.
+                _ if operator_arity(token).is_some() => {
+                    return (PdfValue::Array(elements), token_start);
+                }
                 _ => {
                     // Names without / in arrays are unusual; treat as Other
                     elements.push(PdfValue::Other);
@@ -416,6 +530,32 @@ fn parse_array(data: &[u8], start: usize) -> (PdfValue, usize) {
     (PdfValue::Array(elements), i)
 }
 
+/// Skip an inline dictionary starting at `<<`, nested ones and strings
+/// included; returns the index past its closing `>>`.
+fn skip_dictionary(data: &[u8], start: usize) -> usize {
+    let len = data.len();
+    let mut depth = 0usize;
+    let mut i = start;
+    while i < len {
+        match data[i] {
+            b'<' if data.get(i + 1) == Some(&b'<') => {
+                depth += 1;
+                i += 2;
+            }
+            b'>' if data.get(i + 1) == Some(&b'>') => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            b'(' => i = scan_literal_string(data, i).1,
+            _ => i += 1,
+        }
+    }
+    len
+}
+
 /// Skip an inline image block. Called right after consuming `BI`.
 /// We need to find `ID` (image data marker), then scan for `EI`.
 fn skip_inline_image(data: &[u8], start: usize) -> usize {
@@ -513,4 +653,92 @@ mod tests {
         let ops = parse_content_stream(data).unwrap();
         assert_eq!(ops[1].operator, "T*");
     }
+
+    fn operators(decoded: &DecodedContent) -> Vec<&str> {
+        decoded.ops.iter().map(|op| op.operator.as_str()).collect()
+    }
+
+    #[test]
+    fn test_well_formed_stream_skips_nothing() {
+        let data = b"/Span <</MCID 0 /Alt (a >> b)>> BDC BT /F1 12 Tf 1 0 0 1 72 700 Tm \
+                     [(Hello) -100 (World)] TJ ET EMC 0 0 1 sc q 1 0 0 1 0 0 cm /Im0 Do Q";
+        let decoded = decode_content_stream(data);
+        assert_eq!(decoded.skipped_bytes, 0);
+        assert_eq!(
+            operators(&decoded),
+            ["BDC", "BT", "Tf", "Tm", "TJ", "ET", "EMC", "sc", "q", "cm", "Do", "Q"]
+        );
+        assert_eq!(decoded.ops[0].operands.len(), 2);
+    }
+
+    #[test]
+    fn test_unknown_operator_dropped_with_operands() {
+        let data = b"BT 12 34 Qx 72 700 Td (Hi) Tj ET";
+        let decoded = decode_content_stream(data);
+        assert_eq!(operators(&decoded), ["BT", "Td", "Tj", "ET"]);
+        assert_eq!(decoded.skipped_bytes, "12 34 Qx".len());
+        assert_eq!(decoded.offsets.len(), decoded.ops.len());
+    }
+
+    #[test]
+    fn test_unknown_operator_kept_in_compatibility_section() {
+        let decoded = decode_content_stream(b"BX 1 Qx EX");
+        assert_eq!(operators(&decoded), ["BX", "Qx", "EX"]);
+        assert_eq!(decoded.skipped_bytes, 0);
+    }
+
+    #[test]
+    fn test_surplus_operands_keep_the_last() {
+        let decoded = decode_content_stream(b"9 9 72 700 Td");
+        assert_eq!(decoded.ops.len(), 1);
+        assert!(matches!(
+            decoded.ops[0].operands[..],
+            [PdfValue::Integer(72), PdfValue::Integer(700)]
+        ));
+        assert_eq!(decoded.skipped_bytes, "9 9 ".len());
+    }
+
+    #[test]
+    fn test_operator_short_of_operands_is_skipped() {
+        let decoded = decode_content_stream(b"BT 12 Tf (Hi) Tj ET");
+        assert_eq!(operators(&decoded), ["BT", "Tj", "ET"]);
+        assert_eq!(decoded.skipped_bytes, "12 Tf".len());
+    }
+
+    #[test]
+    fn test_binary_junk_skipped() {
+        let decoded = decode_content_stream(b"BT \xff\xfe} ) ET");
+        assert_eq!(operators(&decoded), ["BT", "ET"]);
+        assert_eq!(decoded.skipped_bytes, 4);
+    }
+
+    #[test]
+    fn test_unterminated_string_skips_its_line() {
+        let decoded = decode_content_stream(b"BT (never closed Tj\n(Next) Tj ET");
+        assert_eq!(operators(&decoded), ["BT", "Tj", "ET"]);
+        assert_eq!(decoded.skipped_bytes, "(never closed Tj".len());
+    }
+
+    #[test]
+    fn test_unclosed_array_ends_at_operator() {
+        let decoded = decode_content_stream(b"BT [(Hello) -100 (World) TJ ET");
+        assert_eq!(operators(&decoded), ["BT", "TJ", "ET"]);
+        assert!(matches!(
+            &decoded.ops[1].operands[..],
+            [PdfValue::Array(items)] if items.len() == 3
+        ));
+    }
+
+    #[test]
+    fn test_type3_glyph_width_operators() {
+        let decoded = decode_content_stream(b"500 0 d0 500 0 0 0 500 500 d1");
+        assert_eq!(operators(&decoded), ["d0", "d1"]);
+        assert_eq!(decoded.skipped_bytes, 0);
+    }
+
+    #[test]
+    fn test_trailing_operands_counted() {
+        let decoded = decode_content_stream(b"BT ET 1 2");
+        assert_eq!(decoded.skipped_bytes, 3);
+    }
 }
@@ -29,6 +29,9 @@ pub struct EncryptionParams {
     pub encrypt_metadata: bool,
 }
 
+/// /P bit 5: copying or otherwise extracting text and graphics is allowed.
+pub const PERMISSION_COPY: i32 = 1 << 4;
+
 /// The standard 32-byte padding used in PDF encryption (ISO 32000-1, Table 20).
 const PADDING: [u8; 32] = [
     0x28, 0xBF, 0x4E, 0x5E, 0x4E, 0x75, 0x8A, 0x41, 0x64, 0x00, 0x4E, 0x56, 0xFF, 0xFA, 0x01, 0x08,
@@ -41,12 +44,7 @@ pub fn compute_encryption_key(params: &EncryptionParams, password: &[u8]) -> Vec
     let key_len = (params.key_length / 8) as usize;
 
     // Step a: Pad or truncate password to exactly 32 bytes
-    let mut padded = Vec::with_capacity(32);
-    let take = password.len().min(32);
-    padded.extend_from_slice(&password[..take]);
-    if padded.len() < 32 {
-        padded.extend_from_slice(&PADDING[..32 - padded.len()]);
-    }
+    let padded = pad_password(password);
 
     // Steps b-f: MD5(padded || O || P || fileID [|| 0xFFFFFFFF])
     let mut hasher = Md5::new();
@@ -113,6 +111,58 @@ pub fn authenticate_user_password(params: &EncryptionParams, password: &[u8]) ->
     None
 }
 
+/// Verify an owner password and return the encryption key if correct
+/// (Algorithm 7, PDF spec).
+///
+/// The owner password unlocks the user password stored in /O, which then
+/// authenticates as usual.
+pub fn authenticate_owner_password(params: &EncryptionParams, password: &[u8]) -> Option<Vec<u8>> {
+    let key = owner_key(params, password);
+
+    let mut user_password = params.owner_hash.get(..32)?.to_vec();
+    if params.revision == 2 {
+        user_password = rc4_crypt(&key, &user_password);
+    } else {
+        // R3+: 20 RC4 passes with XOR-modified keys, from 19 down to 0
+        for i in (0..=19u8).rev() {
+            let modified_key: Vec<u8> = key.iter().map(|&b| b ^ i).collect();
+            user_password = rc4_crypt(&modified_key, &user_password);
+        }
+    }
+
+    authenticate_user_password(params, &user_password)
+}
+
+/// RC4 key derived from the owner password (Algorithm 3, steps a-d).
+fn owner_key(params: &EncryptionParams, password: &[u8]) -> Vec<u8> {
+    let key_len = if params.revision == 2 {
+        5
+    } else {
+        (params.key_length / 8) as usize
+    };
+
+    let mut hash = Md5::digest(pad_password(password)).to_vec();
+    if params.revision >= 3 {
+        for _ in 0..50 {
+            hash = Md5::digest(&hash).to_vec();
+        }
+    }
+
+    hash.truncate(key_len);
+    hash
+}
+
+/// Pad or truncate a password to exactly 32 bytes.
+fn pad_password(password: &[u8]) -> Vec<u8> {
+    let mut padded = Vec::with_capacity(32);
+    let take = password.len().min(32);
+    padded.extend_from_slice(&password[..take]);
+    if padded.len() < 32 {
+        padded.extend_from_slice(&PADDING[..32 - padded.len()]);
+    }
+    padded
+}
+
 /// RC4 encrypt/decrypt (symmetric operation).
 fn rc4_crypt(key: &[u8], data: &[u8]) -> Vec<u8> {
     let mut cipher = Rc4::new_from_slice(key).expect("RC4 key length 1-256");
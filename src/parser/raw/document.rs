@@ -1,14 +1,76 @@
 //! PDF document structure.
 
-use std::collections::{BTreeMap, HashMap};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::sync::OnceLock;
 
 use crate::error::{Error, Result};
 
 use super::crypt::{self, EncryptionParams};
+use super::seekable::{ReadSeek, SeekableBytes};
 use super::stream;
 use super::tokenizer::{self, dict_get, PdfDict, PdfObject, PdfStream};
 use super::xref::{self, XrefEntry};
 
+/// Most pages read from one page tree; the rest is cut off.
+pub const MAX_PAGES: u32 = 1_000_000;
+
+/// Deepest page tree nesting followed.
+const MAX_PAGE_TREE_DEPTH: usize = 256;
+
+/// Damage found while walking the page tree. Pages past the damage are
+/// still returned where they can be reached.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageTreeDamage {
+    /// The catalog has no usable `/Pages` reference.
+    pub missing_root: bool,
+    /// Nodes reached a second time, through a cycle or a kid listed twice.
+    pub revisited_nodes: usize,
+    /// Kids that are missing, not references, or not page tree nodes.
+    pub unreadable_nodes: usize,
+    /// `/Pages` nodes whose `/Count` differs from the pages found under them.
+    pub count_mismatches: usize,
+    /// The tree was cut off at [`MAX_PAGES`] pages or too deep a nesting.
+    pub truncated: bool,
+}
+
+impl PageTreeDamage {
+    /// Whether the page tree was intact.
+    pub fn is_clean(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for PageTreeDamage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if self.missing_root {
+            parts.push("no /Pages root".to_string());
+        }
+        if self.revisited_nodes > 0 {
+            parts.push(format!(
+                "{} cyclic or repeated node(s)",
+                self.revisited_nodes
+            ));
+        }
+        if self.unreadable_nodes > 0 {
+            parts.push(format!("{} unreadable node(s)", self.unreadable_nodes));
+        }
+        if self.count_mismatches > 0 {
+            parts.push(format!("{} /Count mismatch(es)", self.count_mismatches));
+        }
+        if self.truncated {
+            parts.push("truncated".to_string());
+        }
+        if parts.is_empty() {
+            write!(f, "intact")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
 /// A parsed PDF document.
 pub struct RawDocument {
     /// All loaded objects, keyed by (object_number, generation_number).
@@ -17,11 +79,68 @@ pub struct RawDocument {
     trailer: PdfDict,
     /// PDF version string (e.g., "1.4", "1.7").
     pub version: String,
+    /// Objects not yet read, for a document opened with [`RawDocument::load_lazy`].
+    lazy: Option<LazyObjects>,
+    /// Uncompressed xref entries whose object did not parse at its offset,
+    /// counted by an eager load.
+    unreadable_entries: usize,
+    /// Whether decryption needed a password, rather than opening with the
+    /// empty user password of an owner-password-only document.
+    user_password: bool,
+    /// The /P permission flags that apply, for an encrypted document not
+    /// opened with its owner password.
+    permissions: Option<i32>,
+}
+
+/// The file of a lazily loaded document, and each uncompressed object's
+/// offset along with the object once it has been read.
+struct LazyObjects {
+    data: LazySource,
+    entries: HashMap<(u32, u16), LazyEntry>,
+}
+
+/// Where a lazily loaded document's objects are read from.
+enum LazySource {
+    /// The whole file, in memory
+    Memory(Vec<u8>),
+    /// Byte ranges fetched from a seekable reader as objects are read
+    Seekable(SeekableBytes),
+}
+
+/// An object's offset, and the object once read (`None` if it failed to parse).
+type LazyEntry = (usize, OnceLock<Option<PdfObject>>);
+
+impl LazyObjects {
+    fn get(&self, id: (u32, u16)) -> Option<&PdfObject> {
+        let (offset, cell) = self.entries.get(&id)?;
+        cell.get_or_init(|| self.parse(*offset)).as_ref()
+    }
+
+    fn parse(&self, offset: usize) -> Option<PdfObject> {
+        match &self.data {
+            LazySource::Memory(data) => tokenizer::parse_object(data, offset)
+                .ok()
+                .map(|(obj, _)| obj),
+            LazySource::Seekable(source) => source
+                .parse_at(offset, |window| tokenizer::parse_object(window, 0))
+                .ok(),
+        }
+    }
 }
 
 impl RawDocument {
     /// Load a PDF document from bytes.
     pub fn load(data: &[u8]) -> Result<Self> {
+        Self::load_with_passwords::<&[u8]>(data, &[])
+    }
+
+    /// Load a PDF document from bytes, trying each of `passwords` in turn,
+    /// as a user and then as an owner password, when the document does not
+    /// open with the empty user password.
+    ///
+    /// Fails with [`Error::Encrypted`] when a password is needed and none
+    /// was given, and with [`Error::InvalidPassword`] when none of them fits.
+    pub fn load_with_passwords<P: AsRef<[u8]>>(data: &[u8], passwords: &[P]) -> Result<Self> {
         // 1. Parse PDF version from header: %PDF-X.Y
         let version = parse_version(data)?;
 
@@ -30,6 +149,7 @@ impl RawDocument {
 
         // 3. Load all objects from xref entries
         let mut objects = HashMap::new();
+        let mut unreadable_entries = 0;
 
         // First pass: load all uncompressed objects
         for (&(obj_num, gen_num), &entry) in &xref_table.entries {
@@ -40,32 +160,29 @@ impl RawDocument {
                     }
                     Err(_) => {
                         // Skip objects that fail to parse (e.g., corrupted)
+                        unreadable_entries += 1;
                     }
                 }
             }
         }
 
         // Collect compressed xref entries for later ObjStm extraction
-        let mut compressed_groups: HashMap<u32, Vec<(u32, u16, u32)>> = HashMap::new();
-        for (&(obj_num, gen_num), &entry) in &xref_table.entries {
-            if let XrefEntry::Compressed(stream_obj, index) = entry {
-                compressed_groups
-                    .entry(stream_obj)
-                    .or_default()
-                    .push((obj_num, gen_num, index));
-            }
-        }
+        let compressed_groups = compressed_groups(&xref_table);
 
         let mut doc = RawDocument {
             objects,
             trailer,
             version,
+            lazy: None,
+            unreadable_entries,
+            user_password: false,
+            permissions: None,
         };
 
         // Decrypt before ObjStm extraction: ObjStm streams are encrypted and must
         // be decrypted before their compressed content can be decompressed and parsed.
         if doc.is_encrypted() {
-            doc.try_decrypt()?;
+            doc.try_decrypt(passwords)?;
         }
 
         // Second pass: extract compressed objects from ObjStm streams (now decrypted)
@@ -86,8 +203,137 @@ impl RawDocument {
         Ok(doc)
     }
 
-    /// Attempt decryption with an empty user password (covers owner-password-only PDFs).
-    fn try_decrypt(&mut self) -> Result<()> {
+    /// Load a PDF document that reads its objects from `data` only when they
+    /// are first asked for, instead of parsing every object up front.
+    ///
+    /// Objects inside object streams are still extracted at load time, and
+    /// encrypted documents are loaded eagerly, since decryption rewrites every
+    /// object. Use [`RawDocument::get_object_uncached`] for objects read once,
+    /// such as page content streams, so they are not kept for the life of the
+    /// document.
+    pub fn load_lazy(data: Vec<u8>) -> Result<Self> {
+        Self::load_lazy_with_passwords::<&[u8]>(data, &[])
+    }
+
+    /// [`RawDocument::load_lazy`], trying `passwords` on an encrypted
+    /// document as [`RawDocument::load_with_passwords`] does.
+    pub fn load_lazy_with_passwords<P: AsRef<[u8]>>(
+        data: Vec<u8>,
+        passwords: &[P],
+    ) -> Result<Self> {
+        let version = parse_version(&data)?;
+        let (xref_table, trailer) = xref::parse_xref_chain(&data)?;
+        if dict_get(&trailer, b"Encrypt").is_some() {
+            return Self::load_with_passwords(&data, passwords);
+        }
+        Ok(Self::lazy_from_xref(
+            LazySource::Memory(data),
+            version,
+            &xref_table,
+            trailer,
+        ))
+    }
+
+    /// Load a PDF document from a seekable reader, such as a file or an
+    /// HTTP range-request adapter, reading only the header, the xref
+    /// sections and object streams up front and every other object when it
+    /// is first asked for. A page's content is fetched when the page is
+    /// parsed, so a few pages of a large remote file cost a few requests.
+    ///
+    /// Encrypted documents are read whole and loaded as by
+    /// [`RawDocument::load_with_passwords`], since decryption rewrites
+    /// every object.
+    pub fn load_seekable<R, P>(reader: R, passwords: &[P]) -> Result<Self>
+    where
+        R: ReadSeek + 'static,
+        P: AsRef<[u8]>,
+    {
+        const HEAD: usize = 1024;
+
+        let source = SeekableBytes::new(reader)?;
+        let version = parse_version(&source.read_at(0, HEAD)?)?;
+        let tail = source.read_at(source.len().saturating_sub(HEAD), HEAD)?;
+        let start = xref::find_startxref(&tail)?;
+        let (xref_table, trailer) = xref::xref_chain_from(start, source.len(), |offset| {
+            source.parse_at(offset, |window| Ok((xref::parse_xref_at(window, 0)?, 0)))
+        })?;
+        if dict_get(&trailer, b"Encrypt").is_some() {
+            let data = source.read_at(0, source.len())?;
+            return Self::load_with_passwords(&data, passwords);
+        }
+        let source = LazySource::Seekable(source);
+        Ok(Self::lazy_from_xref(source, version, &xref_table, trailer))
+    }
+
+    /// [`RawDocument::load_lazy`] without decrypting an encrypted document.
+    /// Its strings and streams, including object streams, stay unreadable;
+    /// what is left is the plain structure — page tree, numbers and names.
+    pub(crate) fn load_structure(data: Vec<u8>) -> Result<Self> {
+        let version = parse_version(&data)?;
+        let (xref_table, trailer) = xref::parse_xref_chain(&data)?;
+        Ok(Self::lazy_from_xref(
+            LazySource::Memory(data),
+            version,
+            &xref_table,
+            trailer,
+        ))
+    }
+
+    /// The file bytes of a document lazily loaded from memory.
+    pub(crate) fn into_data(self) -> Option<Vec<u8>> {
+        match self.lazy?.data {
+            LazySource::Memory(data) => Some(data),
+            LazySource::Seekable(_) => None,
+        }
+    }
+
+    fn lazy_from_xref(
+        data: LazySource,
+        version: String,
+        xref_table: &xref::XrefTable,
+        trailer: PdfDict,
+    ) -> Self {
+        let entries = xref_table
+            .entries
+            .iter()
+            .filter_map(|(&id, &entry)| match entry {
+                XrefEntry::Uncompressed(offset) => Some((id, (offset, OnceLock::new()))),
+                _ => None,
+            })
+            .collect();
+        let lazy = LazyObjects { data, entries };
+
+        let mut objects = HashMap::new();
+        for (stream_obj_num, entries) in &compressed_groups(xref_table) {
+            let Some((offset, _)) = lazy.entries.get(&(*stream_obj_num, 0)) else {
+                continue;
+            };
+            let Some(PdfObject::Stream(pdf_stream)) = lazy.parse(*offset) else {
+                continue;
+            };
+            if let Ok(mut extracted) = extract_objstm_objects(&pdf_stream) {
+                for &(obj_num, gen_num, index) in entries {
+                    if let Some(obj) = extracted.remove(&(index as usize)) {
+                        objects.insert((obj_num, gen_num), obj);
+                    }
+                }
+            }
+        }
+
+        RawDocument {
+            objects,
+            trailer,
+            version,
+            lazy: Some(lazy),
+            unreadable_entries: 0,
+            user_password: false,
+            permissions: None,
+        }
+    }
+
+    /// Attempt decryption with an empty user password (covers owner-password-only
+    /// PDFs), then with each of `passwords`.
+    fn try_decrypt<P: AsRef<[u8]>>(&mut self, passwords: &[P]) -> Result<()> {
         let params = match self.encryption_params() {
             Some(p) => p,
             None => {
@@ -106,7 +352,33 @@ impl RawDocument {
         }
 
         // Try empty password (most common case: owner-password-only)
-        let key = crypt::authenticate_user_password(&params, b"").ok_or(Error::Encrypted)?;
+        let (key, owner) = match crypt::authenticate_user_password(&params, b"") {
+            // 소유자 암호가 주어졌다면 권한 제한도 풀린다.
+            Some(key) => {
+                let owner = passwords.iter().any(|password| {
+                    crypt::authenticate_owner_password(&params, password.as_ref()).is_some()
+                });
+                (key, owner)
+            }
+            None if passwords.is_empty() => return Err(Error::Encrypted),
+            None => {
+                let found = passwords
+                    .iter()
+                    .find_map(|password| {
+                        let password = password.as_ref();
+                        crypt::authenticate_user_password(&params, password)
+                            .map(|key| (key, false))
+                            .or_else(|| {
+                                crypt::authenticate_owner_password(&params, password)
+                                    .map(|key| (key, true))
+                            })
+                    })
+                    .ok_or(Error::InvalidPassword)?;
+                self.user_password = true;
+                found
+            }
+        };
+        self.permissions = (!owner).then_some(params.permissions);
 
         // Decrypt all objects (except the Encrypt dict itself)
         let encrypt_obj_id = dict_get(&self.trailer, b"Encrypt").and_then(|o| o.as_reference());
@@ -213,7 +485,26 @@ impl RawDocument {
 
     /// Get an object by its ID (object_number, generation_number).
     pub fn get_object(&self, id: (u32, u16)) -> Option<&PdfObject> {
-        self.objects.get(&id)
+        match self.objects.get(&id) {
+            Some(obj) => Some(obj),
+            None => self.lazy.as_ref()?.get(id),
+        }
+    }
+
+    /// Get an object without keeping it loaded: a lazily loaded document
+    /// parses an object it has not read yet and hands it over instead of
+    /// storing it. Otherwise the same as [`RawDocument::get_object`].
+    pub fn get_object_uncached(&self, id: (u32, u16)) -> Option<Cow<'_, PdfObject>> {
+        if let Some(lazy) = &self.lazy {
+            if !self.objects.contains_key(&id) {
+                let (offset, cell) = lazy.entries.get(&id)?;
+                return match cell.get() {
+                    Some(obj) => obj.as_ref().map(Cow::Borrowed),
+                    None => lazy.parse(*offset).map(Cow::Owned),
+                };
+            }
+        }
+        self.get_object(id).map(Cow::Borrowed)
     }
 
     /// Resolve a PdfObject: if it's a Reference, follow it to the actual object.
@@ -222,7 +513,7 @@ impl RawDocument {
         let mut current = obj;
         for _ in 0..10 {
             if let PdfObject::Reference(n, g) = current {
-                if let Some(resolved) = self.objects.get(&(*n, *g)) {
+                if let Some(resolved) = self.get_object((*n, *g)) {
                     current = resolved;
                 } else {
                     return current;
@@ -250,27 +541,34 @@ impl RawDocument {
 
     /// Get all pages as (1-based page_number -> (obj_num, gen_num)).
     /// Traverses the page tree: Catalog -> Pages -> recursive Kids.
+    ///
+    /// A damaged tree yields the pages that could be reached; see
+    /// [`RawDocument::page_tree`] for what was wrong with it.
     pub fn pages(&self) -> BTreeMap<u32, (u32, u16)> {
-        let mut result = BTreeMap::new();
-        let mut page_num = 1u32;
-
-        let catalog = match self.catalog() {
-            Ok(c) => c,
-            Err(_) => return result,
-        };
-
-        let pages_ref = match dict_get(catalog, b"Pages") {
-            Some(r) => r,
-            None => return result,
-        };
-
-        let pages_id = match pages_ref.as_reference() {
-            Some(id) => id,
-            None => return result,
-        };
+        self.page_tree().0
+    }
 
-        self.collect_pages(pages_id, &mut result, &mut page_num);
-        result
+    /// Walk the page tree, returning the reachable pages and the damage
+    /// found on the way. Cycles, nodes reached twice, unreadable kids and
+    /// trees deeper than 256 levels or with more than [`MAX_PAGES`] pages
+    /// are cut off instead of looping or overflowing the stack.
+    pub fn page_tree(&self) -> (BTreeMap<u32, (u32, u16)>, PageTreeDamage) {
+        let mut result = BTreeMap::new();
+        let mut damage = PageTreeDamage::default();
+
+        let root = self
+            .catalog()
+            .ok()
+            .and_then(|catalog| dict_get(catalog, b"Pages"))
+            .and_then(PdfObject::as_reference);
+        match root {
+            Some(root) => {
+                let mut visited = HashSet::new();
+                self.collect_pages(root, 0, &mut visited, &mut result, &mut damage);
+            }
+            None => damage.missing_root = true,
+        }
+        (result, damage)
     }
 
     /// Get the number of pages.
@@ -299,6 +597,49 @@ impl RawDocument {
         dict_get(&self.trailer, b"Encrypt").is_some()
     }
 
+    /// Whether the document is encrypted with a user password, so it only
+    /// opened because one of the given passwords fit. An encrypted document
+    /// for which this is `false` carries owner-password restrictions only.
+    pub fn requires_user_password(&self) -> bool {
+        self.user_password
+    }
+
+    /// Whether the document's permissions allow copying or otherwise
+    /// extracting its text and graphics (bit 5 of /P). Always `true` for an
+    /// unencrypted document or one opened with its owner password.
+    pub fn permits_copying(&self) -> bool {
+        self.permissions
+            .map_or(true, |p| p & crypt::PERMISSION_COPY != 0)
+    }
+
+    /// Cross-reference entries pointing at something that is not a
+    /// parsable object — a sign the xref table no longer matches the file.
+    /// Always 0 for a lazily loaded document, which reads objects on demand.
+    pub fn unreadable_entries(&self) -> usize {
+        self.unreadable_entries
+    }
+
+    /// Filters of the loaded streams that can be neither decoded nor passed
+    /// through as an image codec, with the number of streams using each.
+    pub fn unsupported_filters(&self) -> BTreeMap<String, usize> {
+        let mut filters = BTreeMap::new();
+        for stream in self.objects.values().filter_map(PdfObject::as_stream) {
+            let names: Vec<&[u8]> = match dict_get(&stream.dict, b"Filter") {
+                Some(PdfObject::Name(name)) => vec![name.as_slice()],
+                Some(PdfObject::Array(items)) => {
+                    items.iter().filter_map(PdfObject::as_name).collect()
+                }
+                _ => Vec::new(),
+            };
+            for name in names.into_iter().filter(|n| !stream::is_known_filter(n)) {
+                *filters
+                    .entry(String::from_utf8_lossy(name).into_owned())
+                    .or_insert(0) += 1;
+            }
+        }
+        filters
+    }
+
     // -----------------------------------------------------------------------
     // Private helpers
     // -----------------------------------------------------------------------
@@ -307,32 +648,61 @@ impl RawDocument {
     fn collect_pages(
         &self,
         node_id: (u32, u16),
+        depth: usize,
+        visited: &mut HashSet<(u32, u16)>,
         result: &mut BTreeMap<u32, (u32, u16)>,
-        page_num: &mut u32,
+        damage: &mut PageTreeDamage,
     ) {
+        // 이미 방문한 노드 — 순환 참조이거나 같은 자식을 두 번 가리킨다.
+        if !visited.insert(node_id) {
+            damage.revisited_nodes += 1;
+            return;
+        }
+        if result.len() >= MAX_PAGES as usize {
+            damage.truncated = true;
+            return;
+        }
+
         let dict = match self.get_dict(node_id) {
             Ok(d) => d,
-            Err(_) => return,
+            Err(_) => {
+                damage.unreadable_nodes += 1;
+                return;
+            }
         };
 
         let type_name = dict_get(dict, b"Type").and_then(|o| o.as_name());
+        let kids = dict_get(dict, b"Kids").map(|o| self.resolve(o));
 
-        match type_name {
-            Some(b"Page") => {
-                result.insert(*page_num, node_id);
-                *page_num += 1;
+        match (type_name, kids) {
+            // /Type 이 빠진 잎 노드도 페이지로 본다.
+            (Some(b"Page"), _) | (None, None) => {
+                result.insert(result.len() as u32 + 1, node_id);
             }
-            Some(b"Pages") | None => {
-                // Pages node — recurse into Kids
-                if let Some(kids) = dict_get(dict, b"Kids").and_then(|o| o.as_array()) {
-                    for kid in kids {
-                        if let Some(kid_id) = kid.as_reference() {
-                            self.collect_pages(kid_id, result, page_num);
+            (Some(b"Pages") | None, kids) => {
+                if depth >= MAX_PAGE_TREE_DEPTH {
+                    damage.truncated = true;
+                    return;
+                }
+                let Some(kids) = kids.and_then(PdfObject::as_array) else {
+                    damage.unreadable_nodes += 1;
+                    return;
+                };
+                let before = result.len();
+                for kid in kids {
+                    match kid.as_reference() {
+                        Some(kid_id) => {
+                            self.collect_pages(kid_id, depth + 1, visited, result, damage)
                         }
+                        None => damage.unreadable_nodes += 1,
                     }
                 }
+                let count = dict_get(dict, b"Count").and_then(|o| self.resolve(o).as_i64());
+                if !damage.truncated && count.is_some_and(|c| c != (result.len() - before) as i64) {
+                    damage.count_mismatches += 1;
+                }
             }
-            _ => {}
+            _ => damage.unreadable_nodes += 1,
         }
     }
 }
@@ -388,6 +758,21 @@ fn parse_version(data: &[u8]) -> Result<String> {
     Ok(version.to_string())
 }
 
+/// Compressed xref entries grouped by the object stream holding them, as
+/// (object number, generation, index in the stream).
+fn compressed_groups(xref_table: &xref::XrefTable) -> HashMap<u32, Vec<(u32, u16, u32)>> {
+    let mut groups: HashMap<u32, Vec<(u32, u16, u32)>> = HashMap::new();
+    for (&(obj_num, gen_num), &entry) in &xref_table.entries {
+        if let XrefEntry::Compressed(stream_obj, index) = entry {
+            groups
+                .entry(stream_obj)
+                .or_default()
+                .push((obj_num, gen_num, index));
+        }
+    }
+    groups
+}
+
 /// Extract objects from an ObjStm (Object Stream).
 ///
 /// The stream contains N objects. The dictionary has:
@@ -489,6 +874,21 @@ mod tests {
         assert!(!doc.version.is_empty());
     }
 
+    #[test]
+    fn test_load_lazy_matches_eager() {
+        let Some(data) = try_read("test-files/basic/trivial.pdf") else {
+            return;
+        };
+        let eager = RawDocument::load(&data).unwrap();
+        let lazy = RawDocument::load_lazy(data).unwrap();
+        assert_eq!(lazy.pages(), eager.pages());
+        let first_page_id = lazy.pages()[&1];
+        assert_eq!(
+            lazy.get_dict(first_page_id).ok(),
+            eager.get_dict(first_page_id).ok()
+        );
+    }
+
     #[test]
     fn test_catalog_accessible() {
         let Some(data) = try_read("test-files/basic/trivial.pdf") else {
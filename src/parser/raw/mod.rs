@@ -3,9 +3,11 @@
 pub mod content;
 pub mod crypt;
 pub mod document;
+mod seekable;
 pub mod stream;
 pub mod tokenizer;
 pub mod xref;
 
-pub use document::RawDocument;
+pub use document::{PageTreeDamage, RawDocument};
+pub use seekable::ReadSeek;
 pub use tokenizer::{PdfDict, PdfObject, PdfStream};
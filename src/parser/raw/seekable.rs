@@ -0,0 +1,171 @@
+//! Reading a PDF through `Read + Seek`, fetching only the byte ranges that
+//! are parsed.
+//!
+//! Objects are parsed from a window read at their xref offset. Their length
+//! is not known up front, so a window the parser fails on, or uses up
+//! entirely, is doubled and read again until the object fits, the end of
+//! the file is reached or the window hits [`MAX_WINDOW`]. Reads go through a small cache of aligned blocks, so
+//! neighbouring small objects (page dictionaries, fonts) share a fetch.
+
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::{Mutex, PoisonError};
+
+use crate::error::{Error, Result};
+
+/// Size of the first window read for an object. Most objects other than
+/// content and image streams fit.
+const FIRST_WINDOW: usize = 4 * 1024;
+
+/// Largest window read for one object. An object still unparsed at this size
+/// is reported as corrupted rather than reading on, so one bad xref offset
+/// cannot pull the rest of a remote file into memory.
+const MAX_WINDOW: usize = 64 * 1024 * 1024;
+
+/// Size of the aligned blocks fetched from the reader.
+const BLOCK: usize = 16 * 1024;
+
+/// Blocks kept before the cache is emptied (4 MiB).
+const MAX_CACHED_BLOCKS: usize = 256;
+
+/// A seekable reader that PDF objects can be parsed from.
+pub trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// A file read on demand through a [`ReadSeek`] source.
+pub(crate) struct SeekableBytes {
+    inner: Mutex<Inner>,
+    len: usize,
+    /// [`MAX_WINDOW`], lowered in tests
+    max_window: usize,
+}
+
+struct Inner {
+    reader: Box<dyn ReadSeek>,
+    /// Blocks read so far, by index
+    blocks: HashMap<usize, Vec<u8>>,
+}
+
+impl Inner {
+    fn block(&mut self, index: usize, file_len: usize) -> Result<&[u8]> {
+        if !self.blocks.contains_key(&index) {
+            // 한 번 읽고 마는 콘텐츠 스트림이 쌓이지 않도록 가득 차면 통째로 비운다.
+            if self.blocks.len() >= MAX_CACHED_BLOCKS {
+                self.blocks.clear();
+            }
+            let start = index * BLOCK;
+            let mut buf = vec![0; BLOCK.min(file_len.saturating_sub(start))];
+            self.reader.seek(SeekFrom::Start(start as u64))?;
+            self.reader.read_exact(&mut buf)?;
+            self.blocks.insert(index, buf);
+        }
+        Ok(&self.blocks[&index])
+    }
+}
+
+impl SeekableBytes {
+    pub(crate) fn new<R: ReadSeek + 'static>(mut reader: R) -> Result<Self> {
+        let len = reader.seek(SeekFrom::End(0))? as usize;
+        Ok(Self {
+            inner: Mutex::new(Inner {
+                reader: Box::new(reader),
+                blocks: HashMap::new(),
+            }),
+            len,
+            max_window: MAX_WINDOW,
+        })
+    }
+
+    /// Length of the file in bytes.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Up to `len` bytes from `offset`, fewer at the end of the file.
+    pub(crate) fn read_at(&self, offset: usize, len: usize) -> Result<Vec<u8>> {
+        let end = offset.saturating_add(len).min(self.len);
+        let mut buf = Vec::with_capacity(end.saturating_sub(offset));
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut pos = offset;
+        while pos < end {
+            let block = inner.block(pos / BLOCK, self.len)?;
+            let from = pos % BLOCK;
+            let to = block.len().min(from + (end - pos));
+            buf.extend_from_slice(&block[from..to]);
+            pos += to - from;
+        }
+        Ok(buf)
+    }
+
+    /// Run `parse` on the bytes from `offset`. It returns the value and the
+    /// position it stopped at within the window; a result that reaches the
+    /// window's end may be cut short, so it only counts at the end of the
+    /// file. Fails with [`Error::Corrupted`] once the window would exceed
+    /// [`MAX_WINDOW`].
+    pub(crate) fn parse_at<T>(
+        &self,
+        offset: usize,
+        parse: impl Fn(&[u8]) -> Result<(T, usize)>,
+    ) -> Result<T> {
+        let mut size = FIRST_WINDOW;
+        loop {
+            let window = self.read_at(offset, size)?;
+            let at_end = offset + window.len() >= self.len;
+            match parse(&window) {
+                Ok((value, end)) if end < window.len() || at_end => return Ok(value),
+                Err(e) if at_end => return Err(e),
+                _ if size >= self.max_window => {
+                    return Err(Error::Corrupted(format!(
+                        "object at offset {} does not parse within {} bytes",
+                        offset, self.max_window
+                    )))
+                }
+                _ => size = (size * 2).min(self.max_window),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::raw::tokenizer::{parse_object, PdfObject};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_parse_at_gives_up_at_the_window_cap() {
+        let mut data = b"%PDF-1.4\n1 0 obj\n(".to_vec();
+        data.extend(std::iter::repeat(b'A').take(BLOCK * 8));
+        let mut source = SeekableBytes::new(Cursor::new(data)).unwrap();
+        source.max_window = BLOCK;
+
+        let err = source.parse_at(9, |w| parse_object(w, 0)).unwrap_err();
+        assert!(matches!(err, Error::Corrupted(_)), "{err}");
+        // 한도까지만 읽었다.
+        let cached = source.inner.lock().unwrap().blocks.len();
+        assert!(cached <= 2, "read {cached} blocks");
+    }
+
+    #[test]
+    fn test_parse_at_grows_window_past_long_objects() {
+        let mut data = b"%PDF-1.4\n".to_vec();
+        let offset = data.len();
+        let body = "A".repeat(BLOCK * 2);
+        data.extend_from_slice(format!("1 0 obj\n({body})\nendobj\n").as_bytes());
+        let unterminated = data.len();
+        data.extend_from_slice(b"2 0 obj\n<</Type/Page");
+        let source = SeekableBytes::new(Cursor::new(data.clone())).unwrap();
+
+        let obj = source.parse_at(offset, |w| parse_object(w, 0)).unwrap();
+        assert!(matches!(obj, PdfObject::Str(s) if s.len() == body.len()));
+        assert!(source
+            .parse_at(unterminated, |w| parse_object(w, 0))
+            .is_err());
+        assert_eq!(
+            source.read_at(BLOCK - 3, 6).unwrap(),
+            &data[BLOCK - 3..BLOCK + 3]
+        );
+        assert_eq!(source.read_at(data.len() - 4, 100).unwrap(), b"Page");
+    }
+}
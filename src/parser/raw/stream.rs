@@ -2,43 +2,89 @@
 
 use super::tokenizer::{dict_get, PdfDict, PdfObject, PdfStream};
 use crate::error::{Error, Result};
+use std::borrow::Cow;
 use std::io::Read;
 
 /// Decompress a PDF stream based on its Filter entry.
-/// Also applies predictor decoding from DecodeParms if present.
+///
+/// Filter arrays are undone in order, each stage followed by the predictor
+/// of its own `DecodeParms` entry. A filter that cannot be decoded, image
+/// codecs included, fails with an error naming it.
 pub fn decompress(stream: &PdfStream) -> Result<Vec<u8>> {
-    let filter = dict_get(&stream.dict, b"Filter");
-
-    let decompressed = match filter {
-        None => return Ok(stream.raw_data.clone()),
-        Some(PdfObject::Name(name)) => decompress_single(name, &stream.raw_data)?,
-        Some(PdfObject::Array(filters)) => {
-            let mut data = stream.raw_data.clone();
-            for f in filters {
-                if let Some(name) = f.as_name() {
-                    data = decompress_single(name, &data)?;
-                }
-            }
-            data
-        }
-        _ => return Ok(stream.raw_data.clone()),
+    decode_chain(stream, false)
+}
+
+/// Decompress an image stream up to its image codec: the filters in front of
+/// a `DCTDecode` or `JPXDecode` stage are undone and the codec data is kept.
+/// Same as [`decompress`] for images without such a stage.
+pub fn decompress_image(stream: &PdfStream) -> Result<Vec<u8>> {
+    decode_chain(stream, true)
+}
+
+/// The stream's filters in decoding order, each with its `DecodeParms`.
+pub fn filter_chain(dict: &PdfDict) -> Vec<(&[u8], Option<&PdfDict>)> {
+    let names: Vec<&[u8]> = match dict_get(dict, b"Filter") {
+        Some(PdfObject::Name(name)) => vec![name],
+        Some(PdfObject::Array(filters)) => filters.iter().filter_map(|f| f.as_name()).collect(),
+        _ => Vec::new(),
     };
+    let parms = dict_get(dict, b"DecodeParms").or_else(|| dict_get(dict, b"DP"));
+    names
+        .iter()
+        .enumerate()
+        .map(|(i, &name)| {
+            let parms = match parms {
+                // 필터 배열과 같은 순서의 배열, 또는 단일 필터용 사전.
+                Some(PdfObject::Array(items)) => items.get(i).and_then(|p| p.as_dict()),
+                Some(other) if names.len() == 1 => other.as_dict(),
+                _ => None,
+            };
+            (name, parms)
+        })
+        .collect()
+}
 
-    // Apply predictor decoding if DecodeParms is present
-    let decode_parms = dict_get(&stream.dict, b"DecodeParms");
-    if let Some(parms) = decode_parms {
-        if let Some(parms_dict) = parms.as_dict() {
-            return apply_predictor(parms_dict, &decompressed);
+fn decode_chain(stream: &PdfStream, keep_image_codec: bool) -> Result<Vec<u8>> {
+    let mut data = Cow::Borrowed(stream.raw_data.as_slice());
+    for (name, parms) in filter_chain(&stream.dict) {
+        if keep_image_codec && IMAGE_CODECS.contains(&name) {
+            break;
+        }
+        let mut decoded = decompress_single(name, &data)?;
+        if let Some(parms) = parms {
+            decoded = apply_predictor(parms, &decoded)?;
         }
+        data = Cow::Owned(decoded);
     }
+    Ok(data.into_owned())
+}
 
-    Ok(decompressed)
+/// Image codecs whose data is kept encoded rather than decoded.
+const IMAGE_CODECS: &[&[u8]] = &[b"DCTDecode", b"DCT", b"JPXDecode"];
+
+/// Whether streams using `filter_name` can be decoded, or are images kept in
+/// their own codec.
+pub fn is_known_filter(filter_name: &[u8]) -> bool {
+    IMAGE_CODECS.contains(&filter_name)
+        || matches!(
+            filter_name,
+            b"FlateDecode"
+                | b"Fl"
+                | b"ASCIIHexDecode"
+                | b"AHx"
+                | b"ASCII85Decode"
+                | b"A85"
+                | b"RunLengthDecode"
+                | b"RL"
+        )
 }
 
 fn decompress_single(filter_name: &[u8], data: &[u8]) -> Result<Vec<u8>> {
     match filter_name {
         b"FlateDecode" | b"Fl" => decompress_flate(data),
         b"ASCIIHexDecode" | b"AHx" => decode_ascii_hex(data),
+        b"ASCII85Decode" | b"A85" => decode_ascii85(data),
+        b"RunLengthDecode" | b"RL" => Ok(decode_run_length(data)),
         _ => Err(Error::PdfParse(format!(
             "unsupported filter: {}",
             String::from_utf8_lossy(filter_name)
@@ -339,6 +385,79 @@ fn decode_ascii_hex(data: &[u8]) -> Result<Vec<u8>> {
     Ok(result)
 }
 
+/// ASCII85: 5 chars → 4 bytes, `z` for four zero bytes, `~>` ends the data.
+fn decode_ascii85(data: &[u8]) -> Result<Vec<u8>> {
+    let body = data.strip_prefix(b"<~").unwrap_or(data);
+    let mut result = Vec::with_capacity(body.len() * 4 / 5);
+    let mut group = [0u8; 5];
+    let mut len = 0;
+    for &b in body {
+        match b {
+            b'~' => break,
+            b'z' if len == 0 => result.extend_from_slice(&[0; 4]),
+            b'!'..=b'u' => {
+                group[len] = b - b'!';
+                len += 1;
+                if len == 5 {
+                    result.extend_from_slice(&ascii85_word(&group)?.to_be_bytes());
+                    len = 0;
+                }
+            }
+            _ if b.is_ascii_whitespace() || b == 0 => {}
+            _ => {
+                return Err(Error::PdfParse(format!(
+                    "invalid character 0x{:02x} in ASCII85Decode",
+                    b
+                )))
+            }
+        }
+    }
+    match len {
+        0 => {}
+        1 => {
+            return Err(Error::PdfParse(
+                "truncated group in ASCII85Decode".to_string(),
+            ))
+        }
+        _ => {
+            // 짧은 마지막 그룹은 'u' 로 채워 디코드한 뒤 len-1 바이트만 남긴다.
+            group[len..].fill(b'u' - b'!');
+            result.extend_from_slice(&ascii85_word(&group)?.to_be_bytes()[..len - 1]);
+        }
+    }
+    Ok(result)
+}
+
+fn ascii85_word(group: &[u8; 5]) -> Result<u32> {
+    let value = group.iter().fold(0u64, |acc, &d| acc * 85 + d as u64);
+    u32::try_from(value)
+        .map_err(|_| Error::PdfParse("group out of range in ASCII85Decode".to_string()))
+}
+
+/// RunLength: a length byte n < 128 copies the next n+1 bytes, n > 128
+/// repeats the next byte 257-n times, 128 ends the data.
+fn decode_run_length(data: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(data.len() * 2);
+    let mut i = 0;
+    while let Some(&n) = data.get(i) {
+        match n {
+            0..=127 => {
+                let end = (i + 2 + n as usize).min(data.len());
+                result.extend_from_slice(&data[i + 1..end]);
+                i = end;
+            }
+            128 => break,
+            _ => {
+                if let Some(&b) = data.get(i + 1) {
+                    result.resize(result.len() + 257 - n as usize, b);
+                }
+                i += 2;
+            }
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -391,4 +510,91 @@ mod tests {
         };
         assert!(decompress(&stream).is_err());
     }
+
+    fn filtered(filter: PdfObject, raw_data: &[u8]) -> PdfStream {
+        let mut dict = BTreeMap::new();
+        dict.insert(b"Filter".to_vec(), filter);
+        PdfStream {
+            dict,
+            raw_data: raw_data.to_vec(),
+        }
+    }
+
+    fn names(filters: &[&str]) -> PdfObject {
+        PdfObject::Array(
+            filters
+                .iter()
+                .map(|f| PdfObject::Name(f.as_bytes().to_vec()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_decode_ascii85() {
+        let decoded = decode_ascii85(b"9jqo^BlbD-BleB1DJ+*+F(f,q~>").unwrap();
+        assert_eq!(decoded, b"Man is distinguished");
+        assert_eq!(decode_ascii85(b"<~z9jqo^~>").unwrap(), b"\0\0\0\0Man ");
+        // 짧은 마지막 그룹과 사이사이 공백.
+        assert_eq!(decode_ascii85(b"9jq o^\nBl~>").unwrap(), b"Man i");
+        assert!(decode_ascii85(b"9jqo{~>").is_err());
+    }
+
+    #[test]
+    fn test_decode_run_length() {
+        // 3 바이트 그대로, 'z' 5 번 반복, EOD 뒤는 무시.
+        let data = [2, b'a', b'b', b'c', 252, b'z', 128, b'x'];
+        assert_eq!(decode_run_length(&data), b"abczzzzz");
+    }
+
+    #[test]
+    fn test_filter_chain_is_undone_in_order() {
+        use flate2::write::ZlibEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"Hello Chain").unwrap();
+        let hex: String = encoder
+            .finish()
+            .unwrap()
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect();
+
+        let stream = filtered(names(&["ASCIIHexDecode", "FlateDecode"]), hex.as_bytes());
+        assert_eq!(decompress(&stream).unwrap(), b"Hello Chain");
+        let stream = filtered(names(&["AHx", "RL"]), b"02616263FC7A80>");
+        assert_eq!(decompress(&stream).unwrap(), b"abczzzzz");
+    }
+
+    #[test]
+    fn test_decode_parms_apply_to_their_own_stage() {
+        // PNG Up 예측자: 두 번째 행은 첫 행에 더해진다.
+        let rows = [2u8, 1, 2, 2, 1, 1];
+        let mut parms = BTreeMap::new();
+        parms.insert(b"Predictor".to_vec(), PdfObject::Integer(12));
+        parms.insert(b"Columns".to_vec(), PdfObject::Integer(2));
+        let hex: String = rows.iter().map(|b| format!("{:02X}", b)).collect();
+        let mut stream = filtered(names(&["AHx", "RL"]), b"");
+        stream.raw_data = format!("05{}80", hex).into_bytes();
+        stream.dict.insert(
+            b"DecodeParms".to_vec(),
+            PdfObject::Array(vec![PdfObject::Null, PdfObject::Dict(parms)]),
+        );
+        assert_eq!(decompress(&stream).unwrap(), [1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn test_image_codec_data_is_kept() {
+        let stream = filtered(names(&["ASCIIHexDecode", "DCTDecode"]), b"FFD8FFD9>");
+        assert_eq!(decompress_image(&stream).unwrap(), [0xFF, 0xD8, 0xFF, 0xD9]);
+        assert!(decompress(&stream).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_filter_in_chain_is_named() {
+        let stream = filtered(names(&["AHx", "LZWDecode"]), b"00>");
+        let err = decompress(&stream).unwrap_err().to_string();
+        assert!(err.contains("LZWDecode"), "{}", err);
+    }
 }
@@ -17,7 +17,7 @@ pub enum XrefEntry {
 }
 
 /// Result of parsing a single xref section: entries + trailer dictionary.
-type XrefParseResult = Result<(Vec<((u32, u16), XrefEntry)>, PdfDict)>;
+pub(crate) type XrefParseResult = Result<(Vec<((u32, u16), XrefEntry)>, PdfDict)>;
 
 /// Parsed xref table.
 #[derive(Debug, Default)]
@@ -76,20 +76,32 @@ pub fn find_startxref(data: &[u8]) -> Result<usize> {
 /// Returns the merged xref table and the final (newest) trailer dictionary.
 pub fn parse_xref_chain(data: &[u8]) -> Result<(XrefTable, PdfDict)> {
     let start_offset = find_startxref(data)?;
+    xref_chain_from(start_offset, data.len(), |offset| {
+        parse_xref_at(data, offset)
+    })
+}
+
+/// Follow the xref chain of a `len`-byte file from `start_offset`, reading
+/// each section with `section`.
+pub(crate) fn xref_chain_from(
+    start_offset: usize,
+    len: usize,
+    mut section: impl FnMut(usize) -> XrefParseResult,
+) -> Result<(XrefTable, PdfDict)> {
     let mut table = XrefTable::default();
     let mut newest_trailer: Option<PdfDict> = None;
 
     let mut offset = Some(start_offset);
 
     while let Some(xref_offset) = offset {
-        if xref_offset >= data.len() {
+        if xref_offset >= len {
             return Err(Error::PdfParse(format!(
                 "xref offset {} beyond file size",
                 xref_offset
             )));
         }
 
-        let (entries, trailer) = parse_xref_at(data, xref_offset)?;
+        let (entries, trailer) = section(xref_offset)?;
 
         // Merge entries: newest wins (don't overwrite existing entries)
         for (key, entry) in entries {
@@ -112,9 +124,50 @@ pub fn parse_xref_chain(data: &[u8]) -> Result<(XrefTable, PdfDict)> {
     Ok((table, trailer))
 }
 
+/// The file's revisions, oldest first: for each one, the length of the file
+/// as of that revision and its merged xref table.
+///
+/// An incremental update appends new and rewritten objects, an xref section
+/// and a trailer ending in `%%EOF`, so every `%%EOF` closes a revision and
+/// the bytes up to it are a complete file. Markers whose prefix has no
+/// readable xref chain (the first-page trailer of a linearized file, a stray
+/// `%%EOF` inside a stream) are skipped, as are markers that repeat the
+/// previous `startxref`.
+pub fn revision_tables(data: &[u8]) -> Vec<(usize, XrefTable)> {
+    const MARKER: &[u8] = b"%%EOF";
+
+    let mut revisions = Vec::new();
+    let mut last_startxref = None;
+    let mut pos = 0;
+    while let Some(found) = data[pos..].windows(MARKER.len()).position(|w| w == MARKER) {
+        let mut end = pos + found + MARKER.len();
+        pos = end;
+        // 마커 뒤의 줄바꿈까지 해당 리비전에 포함한다.
+        if data.get(end) == Some(&b'\r') {
+            end += 1;
+        }
+        if data.get(end) == Some(&b'\n') {
+            end += 1;
+        }
+
+        let prefix = &data[..end];
+        let Ok(startxref) = find_startxref(prefix) else {
+            continue;
+        };
+        if last_startxref == Some(startxref) {
+            continue;
+        }
+        if let Ok((table, _)) = parse_xref_chain(prefix) {
+            last_startxref = Some(startxref);
+            revisions.push((end, table));
+        }
+    }
+    revisions
+}
+
 /// Parse an xref section at the given offset, returning entries and the trailer dict.
 /// Handles both traditional xref tables and xref streams.
-fn parse_xref_at(data: &[u8], offset: usize) -> XrefParseResult {
+pub(crate) fn parse_xref_at(data: &[u8], offset: usize) -> XrefParseResult {
     let pos = skip_whitespace_simple(data, offset);
 
     // Check if this is a traditional xref table or an xref stream
@@ -442,6 +495,21 @@ mod tests {
         assert!(table.entries.contains_key(&(5, 0)));
     }
 
+    #[test]
+    fn test_revision_tables_skip_unreadable_markers() {
+        // 선형화 파일의 첫 페이지 트레일러처럼 startxref 0 으로 끝나는 마커는 리비전이 아니다.
+        let mut buf = b"%PDF-1.4\ntrailer\n<< >>\nstartxref\n0\n%%EOF\n".to_vec();
+        let xref_start = buf.len();
+        buf.extend_from_slice(b"xref\n0 2\n0000000000 65535 f \n0000000009 00000 n \n");
+        buf.extend_from_slice(b"trailer\n<< /Size 2 /Root 1 0 R >>\n");
+        buf.extend_from_slice(format!("startxref\n{}\n%%EOF\n", xref_start).as_bytes());
+
+        let revisions = revision_tables(&buf);
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].0, buf.len());
+        assert!(revisions[0].1.entries.contains_key(&(1, 0)));
+    }
+
     #[test]
     fn test_read_field() {
         // 2-byte big-endian
@@ -0,0 +1,115 @@
+//! Stream-order text extraction, the fallback text strategy.
+//!
+//! Decodes every text-showing operator in content-stream order and ignores
+//! positions, text objects and rendering modes — roughly what `pdftotext -raw`
+//! produces. The layout interpreter is stricter: it only places text shown
+//! inside `BT`/`ET` and needs usable text matrices, so malformed generators
+//! can leave it with almost nothing while this reading still recovers the
+//! words.
+
+use super::backend::{get_number_from_value, PdfBackend, PdfValue};
+use super::spacing::SpacingPolicy;
+use crate::error::{Error, Result};
+
+/// Text recovered from one page by [`extract_raw_text`].
+#[derive(Debug, Default)]
+pub(crate) struct RawPageText {
+    /// Decoded text, one line per text line, with blank lines removed.
+    pub text: String,
+    /// Text-showing operators (`Tj`/`TJ`/`'`/`"`) seen.
+    pub text_op_count: u32,
+    /// XObject `Do` invocations seen.
+    pub image_op_count: u32,
+}
+
+/// Decode the text operators of `page_num` in stream order.
+///
+/// Line breaks come from `T*`, `'`, `"`, `ET` and `Td`/`TD` with a vertical
+/// offset; `TJ` adjustments moving right by more than the policy's
+/// `tj_space_threshold` become spaces.
+pub(crate) fn extract_raw_text(
+    backend: &dyn PdfBackend,
+    page_num: u32,
+    spacing: &SpacingPolicy,
+) -> Result<RawPageText> {
+    let pages = backend.pages();
+    let page_id = *pages
+        .get(&page_num)
+        .ok_or(Error::PageOutOfRange(page_num, pages.len() as u32))?;
+    let content = backend.page_content(page_id)?;
+    let operations = backend.decode_content(&content)?;
+
+    let mut result = RawPageText::default();
+    let mut out = String::new();
+    let mut font: Vec<u8> = Vec::new();
+    let show = |out: &mut String, font: &[u8], value: Option<&PdfValue>| {
+        if let Some(PdfValue::Str(bytes)) = value {
+            out.push_str(&backend.decode_text(page_id, font, bytes));
+        }
+    };
+
+    for op in &operations {
+        match op.operator.as_str() {
+            "Tf" => {
+                if let Some(PdfValue::Name(name)) = op.operands.first() {
+                    font = name.clone();
+                }
+            }
+            "Tj" => {
+                result.text_op_count += 1;
+                show(&mut out, &font, op.operands.first());
+            }
+            "'" => {
+                result.text_op_count += 1;
+                new_line(&mut out);
+                show(&mut out, &font, op.operands.first());
+            }
+            "\"" => {
+                result.text_op_count += 1;
+                new_line(&mut out);
+                show(&mut out, &font, op.operands.get(2));
+            }
+            "TJ" => {
+                result.text_op_count += 1;
+                let Some(PdfValue::Array(items)) = op.operands.first() else {
+                    continue;
+                };
+                for item in items {
+                    match get_number_from_value(item) {
+                        Some(n) if -n > spacing.tj_space_threshold => {
+                            if !out.ends_with(char::is_whitespace) {
+                                out.push(' ');
+                            }
+                        }
+                        Some(_) => {}
+                        None => show(&mut out, &font, Some(item)),
+                    }
+                }
+            }
+            "Td" | "TD" => {
+                let dy = op.operands.get(1).and_then(get_number_from_value);
+                if dy.is_some_and(|dy| dy != 0.0) {
+                    new_line(&mut out);
+                }
+            }
+            "T*" | "ET" => new_line(&mut out),
+            "Do" => result.image_op_count += 1,
+            _ => {}
+        }
+    }
+
+    result.text = out
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    spacing.insert_forced_spaces(&mut result.text);
+    Ok(result)
+}
+
+fn new_line(out: &mut String) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
@@ -0,0 +1,114 @@
+//! Incremental-update revisions of a raw document.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use crate::error::{Error, Result};
+use crate::model::Revision;
+
+use super::raw::tokenizer::{dict_get, PdfObject};
+use super::raw::xref::{self, XrefTable};
+use super::raw::RawDocument;
+
+/// Each revision's file length and merged xref table, oldest first.
+type RevisionTables = Vec<(usize, XrefTable)>;
+
+type ObjectId = (u32, u16);
+
+/// Bytes making up revision `revision` (1-based) of `data`, with the file's
+/// revision tables. `None`, or the newest revision, is the whole file.
+pub(crate) fn revision_slice(
+    data: &[u8],
+    revision: Option<u32>,
+) -> Result<(&[u8], RevisionTables)> {
+    let mut tables = xref::revision_tables(data);
+    let count = tables.len().max(1);
+    let Some(n) = revision else {
+        return Ok((data, tables));
+    };
+    if n == 0 || n as usize > count {
+        return Err(Error::Other(format!(
+            "revision {} does not exist; the file has {} revision(s)",
+            n, count
+        )));
+    }
+    if n as usize == count {
+        return Ok((data, tables));
+    }
+    tables.truncate(n as usize);
+    let end = tables[n as usize - 1].0;
+    Ok((&data[..end], tables))
+}
+
+/// Describe `tables` (oldest first) against the pages of `doc`, the document
+/// loaded from the newest of them. Empty unless there is more than one.
+///
+/// A page counts as revised by a revision when it existed in the previous
+/// revision and one of its content streams, or the array listing them, was
+/// rewritten or replaced.
+pub(crate) fn describe_revisions(
+    doc: &RawDocument,
+    tables: &[(usize, XrefTable)],
+) -> Vec<Revision> {
+    if tables.len() < 2 {
+        return Vec::new();
+    }
+
+    let pages: Vec<(u32, ObjectId, Vec<ObjectId>)> = doc
+        .pages()
+        .into_iter()
+        .map(|(number, id)| (number, id, content_ids(doc, id)))
+        .collect();
+
+    let mut revisions = Vec::with_capacity(tables.len());
+    let mut previous: Option<&XrefTable> = None;
+    for (index, (byte_len, table)) in tables.iter().enumerate() {
+        let changed: HashSet<ObjectId> = table
+            .entries
+            .iter()
+            .filter(|(id, entry)| previous.map_or(true, |p| p.entries.get(id) != Some(entry)))
+            .map(|(id, _)| *id)
+            .collect();
+        let revised_pages = match previous {
+            Some(previous) => pages
+                .iter()
+                .filter(|(_, page_id, contents)| {
+                    previous.entries.contains_key(page_id)
+                        && contents.iter().any(|id| changed.contains(id))
+                })
+                .map(|(number, _, _)| *number)
+                .collect(),
+            None => Vec::new(),
+        };
+        revisions.push(Revision {
+            number: index as u32 + 1,
+            byte_len: *byte_len,
+            changed_objects: changed.len(),
+            revised_pages,
+        });
+        previous = Some(table);
+    }
+    revisions
+}
+
+/// Objects holding a page's content: the `/Contents` streams and, when the
+/// streams are listed in an indirect array, that array.
+fn content_ids(doc: &RawDocument, page: ObjectId) -> Vec<ObjectId> {
+    let Some(contents) = doc
+        .get_dict(page)
+        .ok()
+        .and_then(|d| dict_get(d, b"Contents"))
+    else {
+        return Vec::new();
+    };
+    let mut ids: Vec<ObjectId> = contents.as_reference().into_iter().collect();
+    // 내용 스트림 자체는 지연 로드 문서에 남기지 않는다.
+    let resolved = match contents.as_reference() {
+        Some(id) => doc.get_object_uncached(id),
+        None => Some(Cow::Borrowed(contents)),
+    };
+    if let Some(PdfObject::Array(items)) = resolved.as_deref() {
+        ids.extend(items.iter().filter_map(PdfObject::as_reference));
+    }
+    ids
+}
@@ -0,0 +1,268 @@
+//! Word spacing between scripts.
+//!
+//! Chinese and Japanese are written without spaces between words, Korean
+//! and Latin-script languages with them. Where two scripts meet, documents
+//! differ: a Korean manual may write `API를` or `API 를`, a Chinese one
+//! `使用Rust` or `使用 Rust`. [`SpacingPolicy`] says which scripts are
+//! spaceless, how boundaries between scripts are spaced, and how large a gap
+//! must be to count as a word space.
+
+/// The script of a character, as far as word spacing is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    /// CJK ideographs (Chinese characters, Japanese kanji, Korean hanja)
+    Han,
+    /// Japanese hiragana and katakana
+    Kana,
+    /// Korean syllables and jamo
+    Hangul,
+    /// CJK symbols and punctuation (`、`, `。`, `「` and so on)
+    CjkSymbol,
+    /// Latin letters and ASCII digits
+    Latin,
+    /// Anything else: other scripts, punctuation, whitespace
+    Other,
+}
+
+impl Script {
+    /// The script of `c`.
+    pub fn of(c: char) -> Self {
+        let code = c as u32;
+        if c.is_ascii_alphanumeric() || (matches!(code, 0xC0..=0x24F) && c.is_alphabetic()) {
+            Script::Latin
+        } else if is_han(code) {
+            Script::Han
+        } else if (0x3040..=0x30FF).contains(&code) {
+            // Hiragana, Katakana
+            Script::Kana
+        } else if is_hangul(code) {
+            Script::Hangul
+        } else if (0x3000..=0x303F).contains(&code) {
+            Script::CjkSymbol
+        } else {
+            Script::Other
+        }
+    }
+}
+
+fn is_han(code: u32) -> bool {
+    // CJK Unified Ideographs
+    (0x4E00..=0x9FFF).contains(&code)
+    // CJK Unified Ideographs Extension A
+    || (0x3400..=0x4DBF).contains(&code)
+    // CJK Unified Ideographs Extension B-F
+    || (0x20000..=0x2A6DF).contains(&code)
+    || (0x2A700..=0x2B73F).contains(&code)
+    || (0x2B740..=0x2B81F).contains(&code)
+    || (0x2B820..=0x2CEAF).contains(&code)
+    || (0x2CEB0..=0x2EBEF).contains(&code)
+}
+
+fn is_hangul(code: u32) -> bool {
+    // Hangul Syllables
+    (0xAC00..=0xD7AF).contains(&code)
+    // Hangul Jamo
+    || (0x1100..=0x11FF).contains(&code)
+    // Hangul Compatibility Jamo
+    || (0x3130..=0x318F).contains(&code)
+    // Hangul Jamo Extended-A/B
+    || (0xA960..=0xA97F).contains(&code)
+    || (0xD7B0..=0xD7FF).contains(&code)
+}
+
+/// How the boundary between two scripts is spaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptSpacing {
+    /// A space wherever the glyphs are set apart, as within a script
+    Auto,
+    /// Always a space, even where the text runs on without a gap
+    Always,
+    /// Never a space, whatever the gap
+    Never,
+}
+
+/// Rules for putting word spaces into extracted text.
+///
+/// The defaults reproduce the long-standing behaviour: Han, kana and CJK
+/// symbols never get spaces between them, every other boundary gets one
+/// where there is a gap.
+///
+/// ```
+/// use unpdf::{Script, ScriptSpacing, SpacingPolicy};
+///
+/// // `使用Rust编写` → `使用 Rust 编写`
+/// let policy =
+///     SpacingPolicy::default().with_boundary(Script::Han, Script::Latin, ScriptSpacing::Always);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpacingPolicy {
+    /// Scripts written without word spaces: two adjacent characters from
+    /// these never get a space between them. Default Han, kana and CJK
+    /// symbols.
+    pub spaceless: Vec<Script>,
+
+    /// Spacing of boundaries between two scripts, either way round. A
+    /// boundary listed here overrides `spaceless`; the first match wins.
+    pub boundaries: Vec<(Script, Script, ScriptSpacing)>,
+
+    /// Gap between two pieces of text, as a fraction of the average
+    /// character width, above which they are separated by a space.
+    /// Default 0.2.
+    pub gap_ratio: f32,
+
+    /// `TJ` adjustment, in thousandths of text space, read as a word space
+    /// after Latin and other text when the font's widths are unknown.
+    /// Default 200.
+    pub tj_space_threshold: f32,
+
+    /// The same adjustment as a fraction of the font's space glyph width,
+    /// used when the widths are known. Default 0.7.
+    pub tj_space_ratio: f32,
+
+    /// `TJ` adjustment read as a word space after Hangul when the font's
+    /// widths are unknown; kerning between syllables often runs to 300.
+    /// Default 500.
+    pub hangul_tj_space_threshold: f32,
+
+    /// The same as a fraction of the font's average glyph width, used when
+    /// the widths are known. Default 0.5.
+    pub hangul_tj_space_ratio: f32,
+}
+
+impl Default for SpacingPolicy {
+    fn default() -> Self {
+        Self {
+            spaceless: vec![Script::Han, Script::Kana, Script::CjkSymbol],
+            boundaries: Vec::new(),
+            gap_ratio: 0.2,
+            tj_space_threshold: 200.0,
+            tj_space_ratio: 0.7,
+            hangul_tj_space_threshold: 500.0,
+            hangul_tj_space_ratio: 0.5,
+        }
+    }
+}
+
+impl SpacingPolicy {
+    /// Space the boundary between scripts `a` and `b` as `spacing`.
+    pub fn with_boundary(mut self, a: Script, b: Script, spacing: ScriptSpacing) -> Self {
+        self.boundaries.push((a, b, spacing));
+        self
+    }
+
+    /// Whether `c` belongs to a script written without word spaces.
+    pub fn is_spaceless(&self, c: char) -> bool {
+        self.spaceless.contains(&Script::of(c))
+    }
+
+    /// Whether a space goes between `prev` and `next`, given whether the
+    /// glyphs are set apart by more than [`SpacingPolicy::gap_ratio`].
+    pub fn space_between(&self, prev: char, next: char, gap: bool) -> bool {
+        match self.boundary(prev, next) {
+            Some(ScriptSpacing::Always) => true,
+            Some(ScriptSpacing::Never) => false,
+            _ => gap && !(self.is_spaceless(prev) && self.is_spaceless(next)),
+        }
+    }
+
+    /// Insert a space wherever `text` crosses a boundary spaced
+    /// [`ScriptSpacing::Always`] without one.
+    pub fn insert_forced_spaces(&self, text: &mut String) {
+        if !self.boundaries.iter().any(|b| b.2 == ScriptSpacing::Always) {
+            return;
+        }
+        let mut out = String::with_capacity(text.len() + 8);
+        let mut prev: Option<char> = None;
+        for c in text.chars() {
+            if prev.is_some_and(|p| self.boundary(p, c) == Some(ScriptSpacing::Always)) {
+                out.push(' ');
+            }
+            out.push(c);
+            prev = Some(c);
+        }
+        *text = out;
+    }
+
+    /// `TJ` adjustment above which a word space follows `last`, from the
+    /// font's space glyph and average widths when known.
+    pub(crate) fn tj_threshold(
+        &self,
+        last: char,
+        space_width: Option<f32>,
+        avg: Option<f32>,
+    ) -> f32 {
+        if Script::of(last) == Script::Hangul {
+            avg.map(|w| w * self.hangul_tj_space_ratio)
+                .filter(|t| *t > 0.0)
+                .unwrap_or(self.hangul_tj_space_threshold)
+        } else {
+            space_width
+                .map(|w| w * self.tj_space_ratio)
+                .unwrap_or(self.tj_space_threshold)
+        }
+    }
+
+    fn boundary(&self, prev: char, next: char) -> Option<ScriptSpacing> {
+        if prev.is_whitespace() || next.is_whitespace() {
+            return None;
+        }
+        let (a, b) = (Script::of(prev), Script::of(next));
+        self.boundaries
+            .iter()
+            .find(|(x, y, _)| (*x == a && *y == b) || (*x == b && *y == a))
+            .map(|rule| rule.2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_of() {
+        assert_eq!(Script::of('中'), Script::Han);
+        assert_eq!(Script::of('か'), Script::Kana);
+        assert_eq!(Script::of('カ'), Script::Kana);
+        assert_eq!(Script::of('한'), Script::Hangul);
+        assert_eq!(Script::of('。'), Script::CjkSymbol);
+        assert_eq!(Script::of('a'), Script::Latin);
+        assert_eq!(Script::of('é'), Script::Latin);
+        assert_eq!(Script::of('7'), Script::Latin);
+        assert_eq!(Script::of('.'), Script::Other);
+    }
+
+    #[test]
+    fn test_default_spacing() {
+        let policy = SpacingPolicy::default();
+        assert!(!policy.space_between('中', '文', true));
+        assert!(policy.space_between('中', 'R', true));
+        assert!(policy.space_between('한', '글', true));
+        assert!(!policy.space_between('한', 'A', false));
+    }
+
+    #[test]
+    fn test_boundary_overrides() {
+        let policy = SpacingPolicy::default()
+            .with_boundary(Script::Latin, Script::Han, ScriptSpacing::Always)
+            .with_boundary(Script::Hangul, Script::Latin, ScriptSpacing::Never);
+        assert!(policy.space_between('中', 'R', false));
+        assert!(policy.space_between('R', '中', false));
+        assert!(!policy.space_between('를', 'A', true));
+
+        let mut text = "使用Rust编写, 또는 API를".to_string();
+        policy.insert_forced_spaces(&mut text);
+        assert_eq!(text, "使用 Rust 编写, 또는 API를");
+    }
+
+    #[test]
+    fn test_tj_threshold() {
+        let policy = SpacingPolicy {
+            tj_space_threshold: 150.0,
+            ..SpacingPolicy::default()
+        };
+        assert_eq!(policy.tj_threshold('a', None, None), 150.0);
+        assert_eq!(policy.tj_threshold('a', Some(250.0), None), 175.0);
+        assert_eq!(policy.tj_threshold('한', None, None), 500.0);
+        assert_eq!(policy.tj_threshold('한', None, Some(900.0)), 450.0);
+    }
+}
@@ -2,13 +2,19 @@
 
 use std::cmp::{Ord, Ordering, Reverse};
 use std::collections::BinaryHeap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use crate::error::Error;
-use crate::model::{ExtractionQuality, FormField, Metadata, Outline, Page};
-use crate::render::PageSelection;
+use crate::model::{BlockFilter, ExtractionQuality, FormField, Metadata, Outline, Page};
+use crate::render::{HeadingConfig, PageSelection};
 
-use super::options::{ErrorMode, ExtractMode, ParseOptions};
+use super::backend::ImageLimits;
+use super::layout::ColumnDetectionConfig;
+use super::options::{
+    deadline_after, is_expired, ErrorMode, ExtractMode, ExtractionStrategy, ParseOptions,
+};
+use super::spacing::SpacingPolicy;
 
 /// 페이지 단위 스트리밍 파싱 이벤트.
 ///
@@ -51,15 +57,52 @@ pub struct PageStreamOptions {
     pub min_image_dimension: u32,
     pub pages: PageSelection,
     pub password: Option<String>,
+    /// 차례로 시도할 암호. `ParseOptions::passwords` 참고.
+    pub passwords: Vec<String>,
+    /// 복사 금지 권한을 따를지 여부. `ParseOptions::respect_extraction_permission` 참고.
+    pub respect_extraction_permission: bool,
+    /// 파싱할 리비전. `ParseOptions::revision` 참고.
+    pub revision: Option<u32>,
     pub parallel: bool,
     /// 읽을 수 없는 OCR 텍스트 레이어를 버릴지 여부. `ParseOptions` 참고.
     pub suppress_low_confidence_ocr: bool,
+    /// 벡터 그래픽을 SVG 리소스로 캡처할지 여부. `ParseOptions` 참고.
+    pub extract_vector_graphics: bool,
     /// 동시에 in-flight 상태로 둘 페이지 수의 상한. 기본 cores*2.
     pub window_size: usize,
     pub emit_progress_every: u32,
     /// Some 이면 페이지 파싱 직후 리소스(이미지)를 이 디렉토리로 즉시 flush,
     /// `Document.resources` 에는 적재하지 않음. 대용량 문서 메모리 보호.
     pub flush_resources_to: Option<PathBuf>,
+    /// 페이지에 남길 블록 종류. `ParseOptions` 참고.
+    pub block_filter: BlockFilter,
+    /// 문서 전체 시간 예산. `ParseOptions::timeout` 참고.
+    pub timeout: Option<Duration>,
+    /// 페이지별 시간 예산. `ParseOptions::page_timeout` 참고.
+    pub page_timeout: Option<Duration>,
+    /// 텍스트 추출 전략. `ParseOptions` 참고.
+    pub extraction_strategy: ExtractionStrategy,
+    /// 단어별 경계 상자를 기록할지 여부. `ParseOptions::extract_words` 참고.
+    pub extract_words: bool,
+    /// 다단 분할 임계값. `ParseOptions::column_detection` 참고.
+    pub column_detection: ColumnDetectionConfig,
+    /// 띄어쓰기 규칙. `ParseOptions::spacing` 참고.
+    pub spacing: SpacingPolicy,
+    /// 기울어진 글줄을 바로잡을지 여부. `ParseOptions::deskew` 참고.
+    pub deskew: bool,
+    /// 제목 감지 설정. `ParseOptions::heading_config` 참고.
+    pub heading_config: HeadingConfig,
+    /// 문단마다 언어를 감지할지 여부. `ParseOptions::tag_languages` 참고.
+    pub tag_languages: bool,
+    /// 목록 항목을 `Block::List` 로 묶을지 여부. `ParseOptions::group_lists` 참고.
+    pub group_lists: bool,
+    /// 페이지를 하나씩 파싱해 메모리 피크를 낮출지 여부. `ParseOptions::low_memory` 참고.
+    pub low_memory: bool,
+    /// 이미지 디코딩 동시성과 메모리 한도. `ParseOptions::resource_threads`,
+    /// `ParseOptions::max_resource_memory` 참고. 복제본끼리 한도를 공유한다.
+    pub image_limits: ImageLimits,
+    /// 페이지별 디버그 산출물을 쓸 디렉토리. `ParseOptions::debug_artifacts` 참고.
+    pub debug_artifacts: Option<PathBuf>,
 }
 
 impl Default for PageStreamOptions {
@@ -71,14 +114,32 @@ impl Default for PageStreamOptions {
             min_image_dimension: 64,
             pages: PageSelection::All,
             password: None,
+            passwords: Vec::new(),
+            respect_extraction_permission: false,
+            revision: None,
             parallel: true,
             suppress_low_confidence_ocr: true,
+            extract_vector_graphics: false,
             #[cfg(not(target_arch = "wasm32"))]
             window_size: rayon::current_num_threads().saturating_mul(2).max(2),
             #[cfg(target_arch = "wasm32")]
             window_size: 2,
             emit_progress_every: 16,
             flush_resources_to: None,
+            block_filter: BlockFilter::all(),
+            timeout: None,
+            page_timeout: None,
+            extraction_strategy: ExtractionStrategy::Auto,
+            extract_words: false,
+            column_detection: ColumnDetectionConfig::default(),
+            spacing: SpacingPolicy::default(),
+            deskew: false,
+            heading_config: HeadingConfig::default(),
+            tag_languages: false,
+            group_lists: false,
+            low_memory: false,
+            image_limits: ImageLimits::default(),
+            debug_artifacts: None,
         }
     }
 }
@@ -92,13 +153,46 @@ impl From<&ParseOptions> for PageStreamOptions {
             min_image_dimension: o.min_image_dimension,
             pages: o.pages.clone(),
             password: o.password.clone(),
+            passwords: o.passwords.clone(),
+            respect_extraction_permission: o.respect_extraction_permission,
+            revision: o.revision,
             parallel: o.parallel,
             suppress_low_confidence_ocr: o.suppress_low_confidence_ocr,
+            extract_vector_graphics: o.extract_vector_graphics,
+            block_filter: o.block_filter,
+            timeout: o.timeout,
+            page_timeout: o.page_timeout,
+            extraction_strategy: o.extraction_strategy,
+            extract_words: o.extract_words,
+            column_detection: o.column_detection.clone(),
+            spacing: o.spacing.clone(),
+            deskew: o.deskew,
+            heading_config: o.heading_config.clone(),
+            tag_languages: o.tag_languages,
+            group_lists: o.group_lists,
+            low_memory: o.low_memory,
+            image_limits: ImageLimits::new(image_concurrency(o), o.max_resource_memory),
+            flush_resources_to: o.resource_dir.clone(),
+            debug_artifacts: o.debug_artifacts.clone(),
             ..Self::default()
         }
     }
 }
 
+/// 한 페이지에서 동시에 디코딩할 이미지 수.
+fn image_concurrency(o: &ParseOptions) -> usize {
+    if !o.parallel || o.low_memory {
+        return 1;
+    }
+    match o.resource_threads {
+        #[cfg(not(target_arch = "wasm32"))]
+        0 => rayon::current_num_threads(),
+        #[cfg(target_arch = "wasm32")]
+        0 => 1,
+        n => n,
+    }
+}
+
 /// 진척도 카운터 — consumer 스레드가 직접 inc 하도록 노출.
 pub(crate) struct ProgressCounter {
     pub done: u32,
@@ -203,7 +297,8 @@ impl<T> ReorderBuffer<T> {
 use rayon::prelude::*;
 use std::ops::ControlFlow;
 
-use super::backend::PdfBackend;
+use super::backend::{PdfBackend, PdfMetadataRaw};
+use super::headings::LevelNormalizer;
 use super::pdf_parser::{convert_outline_item_pub, parse_pdf_date_pub, parse_single_page};
 
 /// Sample the first `max_pages` pages of content stream operators to detect
@@ -242,6 +337,31 @@ fn detect_scan_pdf(
     image_ops > 0
 }
 
+/// The document's [`Metadata`] from what the backend read, for a document
+/// of `page_count` pages.
+pub(crate) fn document_metadata(raw: PdfMetadataRaw, page_count: u32) -> Metadata {
+    let mut metadata = Metadata::with_version(raw.version);
+    metadata.title = raw.title;
+    metadata.author = raw.author;
+    metadata.subject = raw.subject;
+    metadata.keywords = raw.keywords;
+    metadata.creator = raw.creator;
+    metadata.producer = raw.producer;
+    metadata.language = raw.language;
+    metadata.encrypted = raw.encrypted;
+    metadata.encryption = raw.encryption;
+    metadata.has_xfa = raw.has_xfa;
+    metadata.page_count = page_count;
+    if let Some(date_str) = raw.creation_date {
+        metadata.created = parse_pdf_date_pub(&date_str);
+    }
+    if let Some(date_str) = raw.mod_date {
+        metadata.modified = parse_pdf_date_pub(&date_str);
+    }
+    metadata.sanitize();
+    metadata
+}
+
 /// 페이지를 page_num ASC 순서로 스트리밍. 콜백이 `Break`를 반환하면 조기 종료.
 /// 반환값은 누적된 `ExtractionQuality`.
 pub(crate) fn run_stream<F>(
@@ -254,24 +374,29 @@ where
 {
     use crate::model::QualityAccumulator;
 
+    let deadline = deadline_after(opts.timeout);
+
     // 1. Metadata / outline / form_fields 수집 후 DocumentStart emit
-    let page_map = backend.pages();
+    let (page_map, damage) = backend.page_tree();
+    if !damage.is_clean() {
+        if opts.error_mode == ErrorMode::Strict {
+            return Err(crate::error::Error::Corrupted(format!(
+                "damaged page tree: {}",
+                damage
+            )));
+        }
+        log::warn!(
+            "damaged page tree ({}); extracting the {} reachable page(s)",
+            damage,
+            page_map.len()
+        );
+    }
     let total: u32 = page_map.len() as u32;
     let meta_raw = backend.metadata();
-    let mut metadata = Metadata::with_version(meta_raw.version);
-    metadata.title = meta_raw.title;
-    metadata.author = meta_raw.author;
-    metadata.subject = meta_raw.subject;
-    metadata.keywords = meta_raw.keywords;
-    metadata.creator = meta_raw.creator;
-    metadata.producer = meta_raw.producer;
-    metadata.encrypted = meta_raw.encrypted;
-    metadata.page_count = total;
-    if let Some(date_str) = meta_raw.creation_date {
-        metadata.created = parse_pdf_date_pub(&date_str);
-    }
-    if let Some(date_str) = meta_raw.mod_date {
-        metadata.modified = parse_pdf_date_pub(&date_str);
+    meta_raw.check_extraction_permitted(opts.respect_extraction_permission)?;
+    let metadata = document_metadata(meta_raw, total);
+    if metadata.has_xfa && opts.error_mode == ErrorMode::Lenient {
+        log::warn!("document contains an XFA form; its content may not be extractable");
     }
 
     let outline = backend
@@ -317,20 +442,60 @@ where
         min_image_dimension: opts.min_image_dimension,
         pages: opts.pages.clone(),
         password: opts.password.clone(),
+        passwords: opts.passwords.clone(),
+        respect_extraction_permission: opts.respect_extraction_permission,
+        revision: opts.revision,
         parallel: opts.parallel,
         suppress_low_confidence_ocr: opts.suppress_low_confidence_ocr,
+        extract_vector_graphics: opts.extract_vector_graphics,
+        block_filter: opts.block_filter,
+        timeout: opts.timeout,
+        page_timeout: opts.page_timeout,
+        extraction_strategy: opts.extraction_strategy,
+        extract_words: opts.extract_words,
+        column_detection: opts.column_detection.clone(),
+        spacing: opts.spacing.clone(),
+        deskew: opts.deskew,
+        heading_config: opts.heading_config.clone(),
+        tag_languages: opts.tag_languages,
+        group_lists: opts.group_lists,
+        // 원본 해시는 백엔드를 열 때 이미 구했다 (`PdfParser` 참고). 페이지 단위에서는 쓰지 않는다.
+        source_hash: false,
+        low_memory: opts.low_memory,
+        resource_dir: opts.flush_resources_to.clone(),
+        resource_threads: opts.image_limits.concurrency,
+        max_resource_memory: opts.image_limits.ceiling.as_ref().map(|c| c.limit()),
+        debug_artifacts: opts.debug_artifacts.clone(),
+    };
+    // 문서 예산이 바닥나면 남은 페이지는 파싱하지 않고 곧바로 시간 초과로 보고한다.
+    let parse_page = |page_num: u32| {
+        if is_expired(deadline) {
+            return Err(Error::Timeout(format!(
+                "document time budget ran out before page {}",
+                page_num
+            )));
+        }
+        let mut page =
+            parse_single_page(backend, page_num, &parse_opts, &opts.image_limits, deadline)?;
+        if let Some(dir) = &opts.flush_resources_to {
+            flush_page_resources(&mut page, dir)?;
+        }
+        Ok(page)
     };
 
     // 3. 실행
     let mut quality = QualityAccumulator::new();
     let mut progress = ProgressCounter::new(targets.len() as u32, opts.emit_progress_every);
     let mut reorder: ReorderBuffer<crate::error::Result<Page>> = ReorderBuffer::new(first_expected);
+    let mut levels = LevelNormalizer::new(opts.heading_config.normalize_levels);
 
     // flush_ready: 준비된 페이지를 consumer 에 전달한다.
     fn flush_ready<F2>(
         reorder: &mut ReorderBuffer<crate::error::Result<Page>>,
         quality: &mut QualityAccumulator,
         progress: &mut ProgressCounter,
+        levels: &mut LevelNormalizer,
+        filter: &BlockFilter,
         on_event: &mut F2,
     ) -> ControlFlow<()>
     where
@@ -338,7 +503,7 @@ where
     {
         while let Some((n, item)) = reorder.try_pop_next() {
             match item {
-                Ok(page) => {
+                Ok(mut page) => {
                     if page.ocr_text_suppressed {
                         quality.note_suppressed_ocr_page();
                     }
@@ -348,11 +513,18 @@ where
                         quality.accumulate(&buf);
                         quality.accumulate("\n");
                     }
+                    // 제목 수준은 문서 전체의 읽기 순서로 고치므로 페이지 순서대로 넘겨야 한다.
+                    levels.apply(&mut page);
+                    // 품질 측정 이후에 걸러야 필터가 "텍스트 없음" 경고를 만들지 않는다.
+                    page.retain_blocks(filter);
                     if let ControlFlow::Break(_) = on_event(ParseEvent::PageParsed(page)) {
                         return ControlFlow::Break(());
                     }
                 }
                 Err(err) => {
+                    if matches!(err, Error::Timeout(_)) {
+                        quality.note_timed_out_page(n);
+                    }
                     if let ControlFlow::Break(_) = on_event(ParseEvent::PageFailed {
                         page: n,
                         error: err,
@@ -374,7 +546,7 @@ where
     let mut strict_err: Option<Error> = None;
 
     #[cfg(not(target_arch = "wasm32"))]
-    let effective_parallel = opts.parallel && targets.len() > 1;
+    let effective_parallel = opts.parallel && !opts.low_memory && targets.len() > 1;
     #[cfg(target_arch = "wasm32")]
     let effective_parallel = false;
 
@@ -384,7 +556,7 @@ where
         // A bounded channel here would deadlock because the consumer (on_event) is
         // on the current thread and cannot run concurrently with std::thread::scope.
         let (tx, rx) = crossbeam_channel::unbounded::<(u32, crate::error::Result<Page>)>();
-        let parse_opts_ref = &parse_opts;
+        let parse_page = &parse_page;
         let targets_ref = &targets;
 
         // Spawn a dedicated OS thread for the producer so the consumer can run on
@@ -398,7 +570,7 @@ where
                 targets_ref
                     .par_iter()
                     .for_each_with(tx_for_producer, |tx, &page_num| {
-                        let r = parse_single_page(backend, page_num, parse_opts_ref);
+                        let r = parse_page(page_num);
                         let _ = tx.send((page_num, r));
                     });
             });
@@ -417,9 +589,14 @@ where
                     }
                 };
                 reorder.push(page_num, item);
-                if let ControlFlow::Break(_) =
-                    flush_ready(&mut reorder, &mut quality, &mut progress, &mut on_event)
-                {
+                if let ControlFlow::Break(_) = flush_ready(
+                    &mut reorder,
+                    &mut quality,
+                    &mut progress,
+                    &mut levels,
+                    &opts.block_filter,
+                    &mut on_event,
+                ) {
                     cancelled = true;
                     break;
                 }
@@ -431,7 +608,7 @@ where
 
     if !effective_parallel {
         for &page_num in &targets {
-            let item = match parse_single_page(backend, page_num, &parse_opts) {
+            let item = match parse_page(page_num) {
                 Ok(p) => Ok(p),
                 Err(e) => {
                     if opts.error_mode == ErrorMode::Strict {
@@ -443,9 +620,14 @@ where
                 }
             };
             reorder.push(page_num, item);
-            if let ControlFlow::Break(_) =
-                flush_ready(&mut reorder, &mut quality, &mut progress, &mut on_event)
-            {
+            if let ControlFlow::Break(_) = flush_ready(
+                &mut reorder,
+                &mut quality,
+                &mut progress,
+                &mut levels,
+                &opts.block_filter,
+                &mut on_event,
+            ) {
                 cancelled = true;
                 break;
             }
@@ -459,9 +641,14 @@ where
     if !cancelled {
         while !reorder.is_empty() {
             reorder.skip_to_next_present();
-            if let ControlFlow::Break(_) =
-                flush_ready(&mut reorder, &mut quality, &mut progress, &mut on_event)
-            {
+            if let ControlFlow::Break(_) = flush_ready(
+                &mut reorder,
+                &mut quality,
+                &mut progress,
+                &mut levels,
+                &opts.block_filter,
+                &mut on_event,
+            ) {
                 break;
             }
         }
@@ -477,6 +664,24 @@ where
     Ok(final_q)
 }
 
+/// 페이지 이미지를 `dir` 에 써 두고 메모리에서는 내용만 비운다. 메타데이터와
+/// 쓴 파일 이름(`Resource::filename`)은 남는다. 리소스 id 가 곧 파일 이름이다.
+fn flush_page_resources(page: &mut Page, dir: &Path) -> crate::error::Result<()> {
+    if page.images.is_empty() {
+        return Ok(());
+    }
+    std::fs::create_dir_all(dir)?;
+    for (id, resource) in &mut page.images {
+        if resource.data.is_empty() {
+            continue;
+        }
+        std::fs::write(dir.join(id.as_str()), &resource.data)?;
+        resource.filename = Some(id.clone());
+        resource.data = Vec::new();
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod reorder_tests {
     use super::*;
@@ -5,9 +5,10 @@
 
 use std::collections::BTreeMap;
 
-use crate::model::{Table, TableCell, TableRow};
+use crate::model::{Table, TableCell, TableDetection, TableRejection, TableRow};
 
-use super::layout::TextSpan;
+use super::layout::{join_lines, TextSpan};
+use super::spacing::SpacingPolicy;
 
 /// A detected table region with its content.
 #[derive(Debug, Clone)]
@@ -26,6 +27,20 @@ pub struct DetectedTable {
     pub rows: Vec<TableRowData>,
     /// Confidence score (0.0 - 1.0) for this table detection
     pub confidence: f32,
+    /// Alignment score of each row against `columns`, top to bottom
+    pub alignment: Vec<f32>,
+}
+
+impl DetectedTable {
+    /// The detection record for this table, as kept on the model `Table`.
+    pub fn detection(&self) -> TableDetection {
+        TableDetection {
+            bbox: [self.left_x, self.bottom_y, self.right_x, self.top_y],
+            confidence: self.confidence,
+            alignment: self.alignment.clone(),
+            rejected: None,
+        }
+    }
 }
 
 /// A row of text spans in a table.
@@ -52,6 +67,8 @@ pub struct TableDetectorConfig {
     pub min_alignment_ratio: f32,
     /// Minimum gap between columns (points)
     pub min_column_gap: f32,
+    /// Merge rows that continue the wrapped cell text of the row above
+    pub merge_continuation_rows: bool,
 }
 
 impl Default for TableDetectorConfig {
@@ -63,8 +80,63 @@ impl Default for TableDetectorConfig {
             y_tolerance_factor: 0.4,
             min_alignment_ratio: 0.3,
             min_column_gap: 20.0, // Increased from 15 to prevent splitting within cells
+            merge_continuation_rows: true,
+        }
+    }
+}
+
+/// One detected row's cell texts, before continuation rows are merged.
+struct CellLine {
+    cells: Vec<String>,
+    /// Baseline of the row
+    y: f32,
+    /// Largest font size in the row
+    font_size: f32,
+}
+
+/// Rows with `lines`' continuation rows folded into the row above.
+///
+/// Text wrapped inside a cell puts its later lines on rows of their own. A
+/// row continues the one above when its first column is empty and it sits
+/// about one line below: no more than 1.6× its font size, and clearly
+/// closer than any two rows that do start in the first column. Tables whose
+/// rows are all set one line apart so keep their deliberately blank first
+/// cells.
+fn merge_continuation_rows(lines: Vec<CellLine>) -> Vec<Vec<String>> {
+    let starts: Vec<f32> = lines
+        .iter()
+        .filter(|line| line.cells.first().is_some_and(|c| !c.is_empty()))
+        .map(|line| line.y)
+        .collect();
+    let pitch = starts.windows(2).map(|w| w[0] - w[1]).reduce(f32::min);
+
+    let policy = SpacingPolicy::default();
+    let mut rows: Vec<Vec<String>> = Vec::with_capacity(lines.len());
+    let mut last_y: Option<f32> = None;
+    for line in lines {
+        let continues = match (rows.last(), last_y) {
+            (Some(_), Some(above)) => {
+                let gap = above - line.y;
+                line.cells.first().is_some_and(|c| c.is_empty())
+                    && line.cells.iter().any(|c| !c.is_empty())
+                    && gap <= line.font_size * 1.6
+                    && pitch.map_or(true, |p| gap < p * 0.8)
+            }
+            _ => false,
+        };
+        last_y = Some(line.y);
+        match rows.last_mut() {
+            Some(row) if continues => {
+                for (cell, more) in row.iter_mut().zip(line.cells) {
+                    if !more.is_empty() {
+                        *cell = join_lines([std::mem::take(cell), more], &policy);
+                    }
+                }
+            }
+            _ => rows.push(line.cells),
         }
     }
+    rows
 }
 
 /// Check if any span in the slice contains CJK characters.
@@ -90,6 +162,20 @@ pub struct TableDetector {
     config: TableDetectorConfig,
 }
 
+impl TableDetectorConfig {
+    /// Configuration for one column of a multi-column page.
+    ///
+    /// Tables set within a column are narrow, so their columns sit closer
+    /// together and there are fewer of them.
+    pub fn for_column() -> Self {
+        Self {
+            max_columns: 6,
+            min_column_gap: 12.0,
+            ..Self::default()
+        }
+    }
+}
+
 impl TableDetector {
     /// Create a new table detector with default configuration.
     pub fn new() -> Self {
@@ -127,6 +213,16 @@ impl TableDetector {
     ///
     /// Returns detected tables and the spans that were NOT part of tables.
     pub fn detect(&self, spans: Vec<TextSpan>) -> (Vec<DetectedTable>, Vec<TextSpan>) {
+        let (tables, _, unused) = self.detect_with_candidates(spans);
+        (tables, unused)
+    }
+
+    /// Like [`detect`](Self::detect), also returning the candidate regions
+    /// that were turned down, each with the reason.
+    pub fn detect_with_candidates(
+        &self,
+        spans: Vec<TextSpan>,
+    ) -> (Vec<DetectedTable>, Vec<TableDetection>, Vec<TextSpan>) {
         log::debug!("TableDetector: starting with {} spans", spans.len());
 
         if spans.len() < self.config.min_rows * self.config.min_columns {
@@ -135,11 +231,12 @@ impl TableDetector {
                 spans.len(),
                 self.config.min_rows * self.config.min_columns
             );
-            return (vec![], spans);
+            return (vec![], vec![], spans);
         }
 
-        // Step 1: Group spans into rows by Y position
-        let rows = self.group_into_rows(&spans);
+        // Step 1: Group spans into rows by Y position, remembering where each
+        // span came from so table spans can be removed by index
+        let (rows, row_indices) = self.group_into_rows_indexed(&spans);
         log::debug!("TableDetector: grouped into {} rows", rows.len());
 
         if rows.len() < self.config.min_rows {
@@ -148,7 +245,7 @@ impl TableDetector {
                 rows.len(),
                 self.config.min_rows
             );
-            return (vec![], spans);
+            return (vec![], vec![], spans);
         }
 
         // Step 2: Detect column boundaries from text edges
@@ -165,7 +262,7 @@ impl TableDetector {
                 columns.len(),
                 self.config.min_columns
             );
-            return (vec![], spans);
+            return (vec![], vec![], spans);
         }
 
         // Step 3: Find table regions (contiguous rows with consistent column alignment)
@@ -174,13 +271,13 @@ impl TableDetector {
 
         if table_regions.is_empty() {
             log::debug!("TableDetector: no table regions found");
-            return (vec![], spans);
+            return (vec![], vec![], spans);
         }
 
         // Step 4: Convert regions to detected tables
         let mut detected_tables = Vec::new();
-        let mut used_span_indices: std::collections::HashSet<usize> =
-            std::collections::HashSet::new();
+        let mut rejected = Vec::new();
+        let mut used = vec![false; spans.len()];
 
         for (start_row, end_row) in table_regions {
             let table_rows: Vec<TableRowData> = rows[start_row..=end_row].to_vec();
@@ -207,6 +304,18 @@ impl TableDetector {
 
             // Re-detect columns for this specific table region
             let table_columns = self.detect_columns(&table_rows);
+            let alignment: Vec<f32> = table_rows
+                .iter()
+                .map(|row| self.calculate_alignment_score(row, &table_columns))
+                .collect();
+            let mut reject = |reason| {
+                rejected.push(TableDetection {
+                    bbox: [left_x, bottom_y, right_x, top_y],
+                    confidence: 0.0,
+                    alignment: alignment.clone(),
+                    rejected: Some(reason),
+                })
+            };
 
             if table_columns.len() >= self.config.min_columns {
                 // Reject tables with too many columns (likely word-level splitting)
@@ -216,12 +325,14 @@ impl TableDetector {
                         table_columns.len(),
                         self.config.max_columns
                     );
+                    reject(TableRejection::TooManyColumns);
                     continue;
                 }
 
                 // Check if this is actually a list pattern, not a real table
                 if self.is_list_pattern(&table_rows, &table_columns) {
                     log::debug!("TableDetector: skipping region — detected as list pattern");
+                    reject(TableRejection::ListPattern);
                     continue;
                 }
 
@@ -231,6 +342,7 @@ impl TableDetector {
                 // has shorter cell content.
                 if Self::is_multicolumn_layout(&table_rows, &table_columns, right_x) {
                     log::debug!("TableDetector: skipping region — looks like 2-column page layout");
+                    reject(TableRejection::PageColumns);
                     continue;
                 }
 
@@ -240,6 +352,7 @@ impl TableDetector {
                 // not a real table.
                 if Self::is_sparse_misdetection(&table_rows, &table_columns) {
                     log::debug!("TableDetector: skipping region — sparse column occupancy");
+                    reject(TableRejection::SparseColumns);
                     continue;
                 }
 
@@ -251,18 +364,8 @@ impl TableDetector {
                 );
 
                 // Mark spans as used
-                for row in &table_rows {
-                    for span in &row.spans {
-                        // Find index in original spans
-                        for (i, orig_span) in spans.iter().enumerate() {
-                            if (orig_span.x - span.x).abs() < 0.1
-                                && (orig_span.y - span.y).abs() < 0.1
-                                && orig_span.text == span.text
-                            {
-                                used_span_indices.insert(i);
-                            }
-                        }
-                    }
+                for &i in row_indices[start_row..=end_row].iter().flatten() {
+                    used[i] = true;
                 }
 
                 detected_tables.push(DetectedTable {
@@ -273,6 +376,7 @@ impl TableDetector {
                     columns: table_columns,
                     rows: table_rows,
                     confidence,
+                    alignment,
                 });
             }
         }
@@ -280,23 +384,27 @@ impl TableDetector {
         // Return unused spans
         let unused_spans: Vec<TextSpan> = spans
             .into_iter()
-            .enumerate()
-            .filter(|(i, _)| !used_span_indices.contains(i))
-            .map(|(_, span)| span)
+            .zip(used)
+            .filter(|(_, used)| !used)
+            .map(|(span, _)| span)
             .collect();
 
-        (detected_tables, unused_spans)
+        (detected_tables, rejected, unused_spans)
     }
 
     /// Group spans into rows by Y position.
+    #[cfg(test)]
     fn group_into_rows(&self, spans: &[TextSpan]) -> Vec<TableRowData> {
-        if spans.is_empty() {
-            return vec![];
-        }
+        self.group_into_rows_indexed(spans).0
+    }
 
+    /// Group spans into rows by Y position. Alongside each row, returns the
+    /// indices in `spans` of the row's spans, in the same order.
+    fn group_into_rows_indexed(&self, spans: &[TextSpan]) -> (Vec<TableRowData>, Vec<Vec<usize>>) {
         // Sort by Y (descending for PDF coords) then X
-        let mut sorted_spans = spans.to_vec();
-        sorted_spans.sort_by(|a, b| {
+        let mut order: Vec<usize> = (0..spans.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&spans[a], &spans[b]);
             let y_cmp = b.y.partial_cmp(&a.y).unwrap_or(std::cmp::Ordering::Equal);
             if y_cmp == std::cmp::Ordering::Equal {
                 a.x.partial_cmp(&b.x).unwrap_or(std::cmp::Ordering::Equal)
@@ -306,42 +414,41 @@ impl TableDetector {
         });
 
         let mut rows: Vec<TableRowData> = Vec::new();
-        let mut current_row_spans: Vec<TextSpan> = Vec::new();
+        let mut row_indices: Vec<Vec<usize>> = Vec::new();
+        let mut current: Vec<usize> = Vec::new();
         let mut current_y: Option<f32> = None;
 
-        for span in sorted_spans {
+        let mut finish_row = |current: &mut Vec<usize>| {
+            if current.is_empty() {
+                return;
+            }
+            let row_spans: Vec<TextSpan> = current.iter().map(|&i| spans[i].clone()).collect();
+            let avg_y = row_spans.iter().map(|s| s.y).sum::<f32>() / row_spans.len() as f32;
+            rows.push(TableRowData {
+                y: avg_y,
+                spans: row_spans,
+            });
+            row_indices.push(std::mem::take(current));
+        };
+
+        for i in order {
+            let span = &spans[i];
             let y_tolerance = span.font_size * self.config.y_tolerance_factor;
 
             match current_y {
-                Some(y) if (span.y - y).abs() <= y_tolerance => {
-                    current_row_spans.push(span);
-                }
+                Some(y) if (span.y - y).abs() <= y_tolerance => {}
                 _ => {
-                    if !current_row_spans.is_empty() {
-                        let avg_y = current_row_spans.iter().map(|s| s.y).sum::<f32>()
-                            / current_row_spans.len() as f32;
-                        rows.push(TableRowData {
-                            y: avg_y,
-                            spans: std::mem::take(&mut current_row_spans),
-                        });
-                    }
+                    finish_row(&mut current);
                     current_y = Some(span.y);
-                    current_row_spans.push(span);
                 }
             }
+            current.push(i);
         }
 
         // Don't forget the last row
-        if !current_row_spans.is_empty() {
-            let avg_y =
-                current_row_spans.iter().map(|s| s.y).sum::<f32>() / current_row_spans.len() as f32;
-            rows.push(TableRowData {
-                y: avg_y,
-                spans: current_row_spans,
-            });
-        }
+        finish_row(&mut current);
 
-        rows
+        (rows, row_indices)
     }
 
     /// Detect column boundaries from text edges.
@@ -530,13 +637,11 @@ impl TableDetector {
     pub fn to_table_model(&self, detected: &DetectedTable) -> Table {
         let mut table = Table::new();
 
-        // First row is treated as header
-        table.header_rows = if detected.rows.len() > 1 { 1 } else { 0 };
-
         // Store column widths for reference
         let columns = &detected.columns;
 
-        for (row_idx, row_data) in detected.rows.iter().enumerate() {
+        let mut lines: Vec<CellLine> = Vec::with_capacity(detected.rows.len());
+        for row_data in &detected.rows {
             // Create a cell content vector for each column
             let mut cell_contents: Vec<Vec<String>> = vec![Vec::new(); columns.len()];
 
@@ -553,14 +658,30 @@ impl TableDetector {
                 }
             }
 
+            lines.push(CellLine {
+                cells: cell_contents
+                    .into_iter()
+                    .map(|contents| contents.join(" "))
+                    .collect(),
+                y: row_data.y,
+                font_size: row_data
+                    .spans
+                    .iter()
+                    .map(|s| s.font_size)
+                    .fold(0.0, f32::max),
+            });
+        }
+        let rows = if self.config.merge_continuation_rows {
+            merge_continuation_rows(lines)
+        } else {
+            lines.into_iter().map(|line| line.cells).collect()
+        };
+        // First row is treated as header
+        table.header_rows = if rows.len() > 1 { 1 } else { 0 };
+
+        for (row_idx, texts) in rows.into_iter().enumerate() {
             // Build cells from collected content
-            let cells: Vec<TableCell> = cell_contents
-                .into_iter()
-                .map(|contents| {
-                    let text = contents.join(" ");
-                    TableCell::text(text)
-                })
-                .collect();
+            let cells: Vec<TableCell> = texts.into_iter().map(TableCell::text).collect();
 
             let table_row = if row_idx == 0 && table.header_rows > 0 {
                 TableRow::header(cells)
@@ -583,6 +704,7 @@ impl TableDetector {
             .collect();
         table.column_widths = Some(widths);
 
+        table.detection = Some(detected.detection());
         table
     }
 
@@ -969,9 +1091,12 @@ mod tests {
             y,
             width: text.len() as f32 * 6.0, // Approximate width
             font_size: 12.0,
-            font_name: "Helvetica".to_string(),
+            font_name: "Helvetica".into(),
             is_bold: false,
             is_italic: false,
+            is_monospace: false,
+            char_x: Vec::new(),
+            ops: [0, 0],
         }
     }
 
@@ -991,6 +1116,25 @@ mod tests {
         assert_eq!(rows[1].spans.len(), 2);
     }
 
+    #[test]
+    fn test_group_into_rows_tracks_indices() {
+        let detector = TableDetector::new();
+        let spans = vec![
+            make_span("A2", 10.0, 85.0),
+            make_span("B1", 60.0, 100.0),
+            make_span("B2", 60.0, 85.0),
+            make_span("A1", 10.0, 100.0),
+        ];
+
+        let (rows, indices) = detector.group_into_rows_indexed(&spans);
+        assert_eq!(indices, vec![vec![3, 1], vec![0, 2]]);
+        for (row, row_indices) in rows.iter().zip(&indices) {
+            for (span, &i) in row.spans.iter().zip(row_indices) {
+                assert_eq!(span.text, spans[i].text);
+            }
+        }
+    }
+
     #[test]
     fn test_detect_columns() {
         let detector = TableDetector::new();
@@ -1074,12 +1218,74 @@ mod tests {
                 },
             ],
             confidence: 1.0,
+            alignment: vec![1.0, 1.0],
         };
 
         let table = detector.to_table_model(&detected);
         assert_eq!(table.row_count(), 2);
         assert_eq!(table.column_count(), 2);
         assert_eq!(table.header_rows, 1);
+        let detection = table.detection.unwrap();
+        assert_eq!(detection.bbox[1], detected.bottom_y);
+        assert_eq!(detection.alignment, [1.0, 1.0]);
+    }
+
+    fn table_of(rows: &[(f32, &str, &str)]) -> Table {
+        let rows: Vec<TableRowData> = rows
+            .iter()
+            .map(|&(y, first, second)| TableRowData {
+                y,
+                spans: [(first, 10.0), (second, 60.0)]
+                    .into_iter()
+                    .filter(|(text, _)| !text.is_empty())
+                    .map(|(text, x)| make_span(text, x, y))
+                    .collect(),
+            })
+            .collect();
+        let detected = DetectedTable {
+            top_y: rows[0].y,
+            bottom_y: rows[rows.len() - 1].y,
+            left_x: 10.0,
+            right_x: 150.0,
+            columns: vec![10.0, 60.0],
+            alignment: vec![1.0; rows.len()],
+            rows,
+            confidence: 1.0,
+        };
+        TableDetector::new().to_table_model(&detected)
+    }
+
+    fn column(table: &Table, index: usize) -> Vec<String> {
+        table
+            .rows
+            .iter()
+            .map(|row| row.cells[index].plain_text())
+            .collect()
+    }
+
+    #[test]
+    fn test_wrapped_cells_merge_into_row_above() {
+        let table = table_of(&[
+            (100.0, "Item", "Description"),
+            (80.0, "A", "first line of a"),
+            (66.0, "", "long description"),
+            (46.0, "B", "short"),
+        ]);
+        assert_eq!(table.row_count(), 3);
+        assert_eq!(column(&table, 0), ["Item", "A", "B"]);
+        assert_eq!(column(&table, 1)[1], "first line of a long description");
+    }
+
+    #[test]
+    fn test_blank_first_cells_kept_in_tight_tables() {
+        // 모든 행이 한 줄 간격이면 빈 첫 칸은 의도된 것(묶음 표시)이다.
+        let table = table_of(&[
+            (100.0, "Group", "Member"),
+            (86.0, "Fruit", "Apple"),
+            (72.0, "", "Pear"),
+            (58.0, "Veg", "Leek"),
+        ]);
+        assert_eq!(table.row_count(), 4);
     }
 
     #[test]
@@ -1099,12 +1305,15 @@ mod tests {
             make_span("운영관리", 80.0, 280.0),
         ];
 
-        let (tables, remaining) = detector.detect(spans);
+        let (tables, rejected, remaining) = detector.detect_with_candidates(spans);
         assert!(
             tables.is_empty(),
             "Numbered list should not be detected as a table"
         );
         assert_eq!(remaining.len(), 10);
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].rejected, Some(TableRejection::ListPattern));
+        assert_eq!(rejected[0].alignment.len(), 5);
     }
 
     #[test]
@@ -1135,9 +1344,12 @@ mod tests {
             y,
             width: 0.0,
             font_size,
-            font_name: "Helvetica".to_string(),
+            font_name: "Helvetica".into(),
             is_bold: false,
             is_italic: false,
+            is_monospace: false,
+            char_x: Vec::new(),
+            ops: [0, 0],
         }
     }
 
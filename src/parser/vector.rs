@@ -0,0 +1,550 @@
+//! Vector graphics capture.
+//!
+//! Charts and diagrams drawn with path operators carry no text and no image
+//! XObject, so the text pipeline drops them entirely. This pass replays a
+//! page's path construction and painting operators and serialises what was
+//! painted as a single SVG, so the drawing survives conversion as a graphic.
+//!
+//! Only paths are captured: text, images, shadings and Form XObjects are not
+//! followed. Pages whose paths are just rules, frames or table borders
+//! produce nothing — see [`capture_page_drawing`].
+//!
+//! The same graphics-state replay also locates XObjects: [`xobject_placements`]
+//! reports where each `Do` lands, so image blocks can be slotted into the text
+//! flow at their position.
+
+use std::fmt::Write as _;
+
+use super::backend::{get_number_from_value, ContentOp, PdfValue};
+use super::layout::concat_matrix;
+
+/// Minimum number of painted paths for a page to count as a drawing.
+const MIN_PAINTED_PATHS: usize = 4;
+
+/// Paths covering at least this fraction of the page are backgrounds or
+/// frames, not part of a figure.
+const BACKGROUND_COVERAGE: f32 = 0.9;
+
+/// A page's vector drawing, serialised as SVG.
+#[derive(Debug, Clone)]
+pub(crate) struct VectorDrawing {
+    /// Standalone SVG document
+    pub svg: String,
+    /// Left edge of the drawing in PDF points
+    pub x: f32,
+    /// Top edge of the drawing in PDF points (PDF coordinates, y up)
+    pub y: f32,
+    /// Width in points
+    pub width: f32,
+    /// Height in points
+    pub height: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GraphicsState {
+    ctm: [f32; 6],
+    fill: [u8; 3],
+    stroke: [u8; 3],
+    line_width: f32,
+}
+
+impl Default for GraphicsState {
+    fn default() -> Self {
+        Self {
+            ctm: [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            fill: [0, 0, 0],
+            stroke: [0, 0, 0],
+            line_width: 1.0,
+        }
+    }
+}
+
+/// Axis-aligned bounding box in device space.
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    min_x: f32,
+    min_y: f32,
+    max_x: f32,
+    max_y: f32,
+}
+
+impl Bounds {
+    fn point(x: f32, y: f32) -> Self {
+        Self {
+            min_x: x,
+            min_y: y,
+            max_x: x,
+            max_y: y,
+        }
+    }
+
+    fn include(&mut self, x: f32, y: f32) {
+        self.min_x = self.min_x.min(x);
+        self.min_y = self.min_y.min(y);
+        self.max_x = self.max_x.max(x);
+        self.max_y = self.max_y.max(y);
+    }
+
+    fn union(&mut self, other: &Bounds) {
+        self.include(other.min_x, other.min_y);
+        self.include(other.max_x, other.max_y);
+    }
+
+    fn area(&self) -> f32 {
+        (self.max_x - self.min_x) * (self.max_y - self.min_y)
+    }
+}
+
+/// Path under construction, already mapped to device space.
+#[derive(Debug, Default)]
+struct PathBuilder {
+    /// SVG path data with y still pointing up; flipped when serialised.
+    segments: Vec<Segment>,
+    bounds: Option<Bounds>,
+    current: (f32, f32),
+    has_curve: bool,
+    has_diagonal: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Segment {
+    Move(f32, f32),
+    Line(f32, f32),
+    Curve(f32, f32, f32, f32, f32, f32),
+    Close,
+}
+
+impl PathBuilder {
+    fn touch(&mut self, x: f32, y: f32) {
+        match self.bounds.as_mut() {
+            Some(b) => b.include(x, y),
+            None => self.bounds = Some(Bounds::point(x, y)),
+        }
+    }
+
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.segments.push(Segment::Move(x, y));
+        self.touch(x, y);
+        self.current = (x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (cx, cy) = self.current;
+        if (x - cx).abs() > 0.5 && (y - cy).abs() > 0.5 {
+            self.has_diagonal = true;
+        }
+        self.segments.push(Segment::Line(x, y));
+        self.touch(x, y);
+        self.current = (x, y);
+    }
+
+    fn curve_to(&mut self, p: [(f32, f32); 3]) {
+        self.has_curve = true;
+        self.segments.push(Segment::Curve(
+            p[0].0, p[0].1, p[1].0, p[1].1, p[2].0, p[2].1,
+        ));
+        for (x, y) in p {
+            self.touch(x, y);
+        }
+        self.current = p[2];
+    }
+
+    fn close(&mut self) {
+        self.segments.push(Segment::Close);
+    }
+}
+
+/// A painted path ready for serialisation.
+#[derive(Debug)]
+struct PaintedPath {
+    segments: Vec<Segment>,
+    bounds: Bounds,
+    fill: Option<([u8; 3], bool)>,
+    stroke: Option<([u8; 3], f32)>,
+}
+
+/// Replay `ops` and return the page's drawing, if it has one worth keeping.
+///
+/// A page qualifies when it paints at least [`MIN_PAINTED_PATHS`] paths and
+/// at least one of them is a curve, a diagonal line, or a coloured fill.
+/// Axis-aligned black/grey strokes alone are rules and table borders, which
+/// the table detector and text flow already account for.
+pub(crate) fn capture_page_drawing(ops: &[ContentOp], page_area: f32) -> Option<VectorDrawing> {
+    let mut gs = GraphicsState::default();
+    let mut stack: Vec<GraphicsState> = Vec::new();
+    let mut path = PathBuilder::default();
+    let mut painted: Vec<PaintedPath> = Vec::new();
+    let mut figure_like = false;
+
+    let num = |op: &ContentOp, i: usize| op.operands.get(i).and_then(get_number_from_value);
+
+    for op in ops {
+        match op.operator.as_str() {
+            "q" => stack.push(gs),
+            "Q" => {
+                if let Some(saved) = stack.pop() {
+                    gs = saved;
+                }
+            }
+            "cm" if op.operands.len() >= 6 => {
+                let m = [
+                    num(op, 0).unwrap_or(1.0),
+                    num(op, 1).unwrap_or(0.0),
+                    num(op, 2).unwrap_or(0.0),
+                    num(op, 3).unwrap_or(1.0),
+                    num(op, 4).unwrap_or(0.0),
+                    num(op, 5).unwrap_or(0.0),
+                ];
+                gs.ctm = concat_matrix(&m, &gs.ctm);
+            }
+            "w" => gs.line_width = num(op, 0).unwrap_or(1.0),
+            "g" | "G" | "rg" | "RG" | "k" | "K" | "sc" | "SC" | "scn" | "SCN" => {
+                let values: Vec<f32> = op
+                    .operands
+                    .iter()
+                    .filter_map(get_number_from_value)
+                    .collect();
+                if let Some(rgb) = color_from_components(&values) {
+                    if op.operator.chars().all(|c| c.is_ascii_lowercase()) {
+                        gs.fill = rgb;
+                    } else {
+                        gs.stroke = rgb;
+                    }
+                }
+            }
+            "m" => {
+                if let (Some(x), Some(y)) = (num(op, 0), num(op, 1)) {
+                    let (x, y) = transform(&gs.ctm, x, y);
+                    path.move_to(x, y);
+                }
+            }
+            "l" => {
+                if let (Some(x), Some(y)) = (num(op, 0), num(op, 1)) {
+                    let (x, y) = transform(&gs.ctm, x, y);
+                    path.line_to(x, y);
+                }
+            }
+            "c" | "v" | "y" => {
+                let values: Vec<f32> = op
+                    .operands
+                    .iter()
+                    .filter_map(get_number_from_value)
+                    .collect();
+                let points: Vec<(f32, f32)> = values
+                    .chunks_exact(2)
+                    .map(|p| transform(&gs.ctm, p[0], p[1]))
+                    .collect();
+                let curve = match (op.operator.as_str(), points.as_slice()) {
+                    ("c", [p1, p2, p3]) => Some([*p1, *p2, *p3]),
+                    ("v", [p2, p3]) => Some([path.current, *p2, *p3]),
+                    ("y", [p1, p3]) => Some([*p1, *p3, *p3]),
+                    _ => None,
+                };
+                if let Some(curve) = curve {
+                    path.curve_to(curve);
+                }
+            }
+            "h" => path.close(),
+            "re" if op.operands.len() >= 4 => {
+                let (x, y, w, h) = (
+                    num(op, 0).unwrap_or(0.0),
+                    num(op, 1).unwrap_or(0.0),
+                    num(op, 2).unwrap_or(0.0),
+                    num(op, 3).unwrap_or(0.0),
+                );
+                let corners = [(x, y), (x + w, y), (x + w, y + h), (x, y + h)];
+                for (i, (cx, cy)) in corners.into_iter().enumerate() {
+                    let (px, py) = transform(&gs.ctm, cx, cy);
+                    if i == 0 {
+                        path.move_to(px, py);
+                    } else {
+                        path.line_to(px, py);
+                    }
+                }
+                path.close();
+            }
+            "S" | "s" | "f" | "F" | "f*" | "B" | "B*" | "b" | "b*" | "n" => {
+                let finished = std::mem::take(&mut path);
+                let operator = op.operator.as_str();
+                if operator == "n" {
+                    continue;
+                }
+                let Some(bounds) = finished.bounds else {
+                    continue;
+                };
+                if page_area > 0.0 && bounds.area() / page_area >= BACKGROUND_COVERAGE {
+                    continue;
+                }
+                let mut segments = finished.segments;
+                if matches!(operator, "s" | "b" | "b*") {
+                    segments.push(Segment::Close);
+                }
+                let fills = !matches!(operator, "S" | "s");
+                let strokes = matches!(operator, "S" | "s" | "B" | "B*" | "b" | "b*");
+                let even_odd = operator.ends_with('*');
+                if finished.has_curve || finished.has_diagonal || (fills && !is_neutral(gs.fill)) {
+                    figure_like = true;
+                }
+                painted.push(PaintedPath {
+                    segments,
+                    bounds,
+                    fill: fills.then_some((gs.fill, even_odd)),
+                    stroke: strokes.then(|| (gs.stroke, gs.line_width * ctm_scale(&gs.ctm))),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    if painted.len() < MIN_PAINTED_PATHS || !figure_like {
+        return None;
+    }
+    Some(to_svg(&painted))
+}
+
+fn to_svg(paths: &[PaintedPath]) -> VectorDrawing {
+    let mut bounds = paths[0].bounds;
+    let mut pad: f32 = 0.0;
+    for p in paths {
+        bounds.union(&p.bounds);
+        if let Some((_, width)) = p.stroke {
+            pad = pad.max(width / 2.0);
+        }
+    }
+    let min_x = bounds.min_x - pad;
+    let max_y = bounds.max_y + pad;
+    let width = bounds.max_x - bounds.min_x + pad * 2.0;
+    let height = bounds.max_y - bounds.min_y + pad * 2.0;
+
+    // SVG 는 y 축이 아래로 향하므로 그림의 위쪽 모서리를 원점으로 뒤집는다.
+    let fx = |x: f32| fmt_num(x - min_x);
+    let fy = |y: f32| fmt_num(max_y - y);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {w} {h}\" width=\"{w}\" height=\"{h}\">\n",
+        w = fmt_num(width),
+        h = fmt_num(height)
+    );
+    for p in paths {
+        let mut d = String::new();
+        for seg in &p.segments {
+            if !d.is_empty() {
+                d.push(' ');
+            }
+            let _ = match *seg {
+                Segment::Move(x, y) => write!(d, "M{} {}", fx(x), fy(y)),
+                Segment::Line(x, y) => write!(d, "L{} {}", fx(x), fy(y)),
+                Segment::Curve(x1, y1, x2, y2, x3, y3) => write!(
+                    d,
+                    "C{} {} {} {} {} {}",
+                    fx(x1),
+                    fy(y1),
+                    fx(x2),
+                    fy(y2),
+                    fx(x3),
+                    fy(y3)
+                ),
+                Segment::Close => write!(d, "Z"),
+            };
+        }
+        let _ = write!(svg, "<path d=\"{}\"", d);
+        match p.fill {
+            Some((color, even_odd)) => {
+                let _ = write!(svg, " fill=\"{}\"", hex(color));
+                if even_odd {
+                    svg.push_str(" fill-rule=\"evenodd\"");
+                }
+            }
+            None => svg.push_str(" fill=\"none\""),
+        }
+        if let Some((color, width)) = p.stroke {
+            let _ = write!(
+                svg,
+                " stroke=\"{}\" stroke-width=\"{}\"",
+                hex(color),
+                fmt_num(width)
+            );
+        }
+        svg.push_str("/>\n");
+    }
+    svg.push_str("</svg>\n");
+
+    VectorDrawing {
+        svg,
+        x: min_x,
+        y: max_y,
+        width,
+        height,
+    }
+}
+
+/// Map gray, RGB or CMYK components (0..1) to 8-bit RGB.
+fn color_from_components(values: &[f32]) -> Option<[u8; 3]> {
+    let to_u8 = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    match values {
+        [g] => Some([to_u8(*g); 3]),
+        [r, g, b] => Some([to_u8(*r), to_u8(*g), to_u8(*b)]),
+        [c, m, y, k] => Some([
+            to_u8((1.0 - c) * (1.0 - k)),
+            to_u8((1.0 - m) * (1.0 - k)),
+            to_u8((1.0 - y) * (1.0 - k)),
+        ]),
+        _ => None,
+    }
+}
+
+fn is_neutral(c: [u8; 3]) -> bool {
+    c[0] == c[1] && c[1] == c[2]
+}
+
+fn hex(c: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", c[0], c[1], c[2])
+}
+
+/// Format a coordinate with at most two decimals and no trailing zeros.
+fn fmt_num(v: f32) -> String {
+    let s = format!("{:.2}", v);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+/// Where an XObject is painted on the page.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct XObjectPlacement {
+    /// Resource name, without the leading slash
+    pub name: String,
+    /// Bounding box of the transformed unit square, `[x0, y0, x1, y1]` in
+    /// PDF points (y up)
+    pub bbox: [f32; 4],
+}
+
+/// Replay the graphics state of `ops` and record every `Do`, in paint order.
+///
+/// An XObject paints into the unit square, so the CTM at `Do` maps that
+/// square onto the page. Form XObjects are reported like images; their
+/// contents are not followed.
+pub(crate) fn xobject_placements(ops: &[ContentOp]) -> Vec<XObjectPlacement> {
+    let mut ctm = GraphicsState::default().ctm;
+    let mut stack: Vec<[f32; 6]> = Vec::new();
+    let mut placements = Vec::new();
+    let num = |op: &ContentOp, i: usize| op.operands.get(i).and_then(get_number_from_value);
+
+    for op in ops {
+        match op.operator.as_str() {
+            "q" => stack.push(ctm),
+            "Q" => {
+                if let Some(saved) = stack.pop() {
+                    ctm = saved;
+                }
+            }
+            "cm" if op.operands.len() >= 6 => {
+                let m = [
+                    num(op, 0).unwrap_or(1.0),
+                    num(op, 1).unwrap_or(0.0),
+                    num(op, 2).unwrap_or(0.0),
+                    num(op, 3).unwrap_or(1.0),
+                    num(op, 4).unwrap_or(0.0),
+                    num(op, 5).unwrap_or(0.0),
+                ];
+                ctm = concat_matrix(&m, &ctm);
+            }
+            "Do" => {
+                let Some(PdfValue::Name(name)) = op.operands.first() else {
+                    continue;
+                };
+                let (x, y) = transform(&ctm, 0.0, 0.0);
+                let mut b = Bounds::point(x, y);
+                for (x, y) in [(1.0, 0.0), (0.0, 1.0), (1.0, 1.0)] {
+                    let (x, y) = transform(&ctm, x, y);
+                    b.include(x, y);
+                }
+                placements.push(XObjectPlacement {
+                    name: String::from_utf8_lossy(name).to_string(),
+                    bbox: [b.min_x, b.min_y, b.max_x, b.max_y],
+                });
+            }
+            _ => {}
+        }
+    }
+    placements
+}
+
+fn transform(m: &[f32; 6], x: f32, y: f32) -> (f32, f32) {
+    (m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5])
+}
+
+/// Uniform scale factor of a matrix, for line widths.
+fn ctm_scale(m: &[f32; 6]) -> f32 {
+    (m[0] * m[3] - m[1] * m[2]).abs().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ops(content: &[u8]) -> Vec<ContentOp> {
+        super::super::raw::content::parse_content_stream(content).unwrap()
+    }
+
+    const A4_AREA: f32 = 595.0 * 842.0;
+
+    #[test]
+    fn test_table_borders_are_not_a_drawing() {
+        let content = b"0 0 0 RG 1 w 50 700 m 300 700 l S 50 680 m 300 680 l S \
+                        50 660 m 300 660 l S 50 640 m 300 640 l S";
+        assert!(capture_page_drawing(&ops(content), A4_AREA).is_none());
+    }
+
+    #[test]
+    fn test_bar_chart_becomes_svg() {
+        let content = b"1 0 0 rg 100 100 20 50 re f 0 0 1 rg 130 100 20 80 re f \
+                        0 1 0 rg 160 100 20 30 re f 0 G 100 100 m 200 100 l S";
+        let drawing = capture_page_drawing(&ops(content), A4_AREA).unwrap();
+        assert!(drawing.svg.starts_with("<svg"));
+        assert!(drawing.svg.contains("fill=\"#ff0000\""));
+        assert_eq!(drawing.svg.matches("<path").count(), 4);
+        // 100pt of bars plus half the 1pt axis stroke on each side
+        assert!((drawing.width - 101.0).abs() < 0.01);
+        assert!((drawing.y - 180.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_svg_y_axis_is_flipped() {
+        let content = b"0 0 1 rg 0 0 10 10 re f 0 0 1 rg 0 90 10 10 re f \
+                        0 0 1 rg 90 0 10 10 re f 0 0 1 rg 90 90 10 10 re f";
+        let drawing = capture_page_drawing(&ops(content), A4_AREA).unwrap();
+        // The rectangle at PDF y=90 is at the top of the SVG.
+        assert!(
+            drawing.svg.contains("M0 10 L10 10 L10 0 L0 0 Z"),
+            "{}",
+            drawing.svg
+        );
+    }
+
+    #[test]
+    fn test_page_background_is_ignored() {
+        let content = b"1 g 0 0 595 842 re f 0 0 1 RG 10 10 m 50 50 l S";
+        assert!(capture_page_drawing(&ops(content), A4_AREA).is_none());
+    }
+
+    #[test]
+    fn test_fmt_num() {
+        assert_eq!(fmt_num(10.0), "10");
+        assert_eq!(fmt_num(1.254), "1.25");
+        assert_eq!(fmt_num(-0.001), "0");
+    }
+
+    #[test]
+    fn test_xobject_placement_follows_ctm() {
+        let placements = xobject_placements(&ops(b"q 200 0 0 100 72 500 cm /Im1 Do Q \
+              q 0.5 0 0 0.5 0 0 cm 100 0 0 100 10 20 cm /Im2 Do Q"));
+        assert_eq!(placements.len(), 2);
+        assert_eq!(placements[0].name, "Im1");
+        assert_eq!(placements[0].bbox, [72.0, 500.0, 272.0, 600.0]);
+        assert_eq!(placements[1].bbox, [5.0, 10.0, 55.0, 60.0]);
+    }
+}
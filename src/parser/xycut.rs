@@ -20,30 +20,56 @@ impl Block {
 
 /// Segment blocks into reading-order groups using recursive XY-cut.
 pub fn xycut_segment(blocks: &[Block], min_x_gap: f32, min_y_gap: f32) -> Vec<Vec<Block>> {
+    xycut_columns(blocks, min_x_gap, min_y_gap, 0.0).0
+}
+
+/// Segment blocks like [`xycut_segment`], making a vertical cut only when
+/// both sides are at least `min_width` wide.
+///
+/// Also returns the number of columns: the most groups found side by side
+/// in any horizontal band.
+pub fn xycut_columns(
+    blocks: &[Block],
+    min_x_gap: f32,
+    min_y_gap: f32,
+    min_width: f32,
+) -> (Vec<Vec<Block>>, usize) {
     if blocks.is_empty() {
-        return vec![];
+        return (vec![], 0);
     }
     if blocks.len() == 1 {
-        return vec![blocks.to_vec()];
+        return (vec![blocks.to_vec()], 1);
     }
 
     let mut result = Vec::new();
-    xycut_recursive(blocks, min_x_gap, min_y_gap, &mut result);
+    let limits = Limits {
+        min_x_gap,
+        min_y_gap,
+        min_width,
+    };
+    let columns = xycut_recursive(blocks, &limits, &mut result);
 
     if result.is_empty() && !blocks.is_empty() {
         result.push(blocks.to_vec());
     }
 
-    result
+    (result, columns.max(1))
+}
+
+struct Limits {
+    min_x_gap: f32,
+    min_y_gap: f32,
+    min_width: f32,
 }
 
-fn xycut_recursive(blocks: &[Block], min_x_gap: f32, min_y_gap: f32, result: &mut Vec<Vec<Block>>) {
+/// Returns the number of columns side by side in `blocks`.
+fn xycut_recursive(blocks: &[Block], limits: &Limits, result: &mut Vec<Vec<Block>>) -> usize {
     if blocks.is_empty() {
-        return;
+        return 0;
     }
     if blocks.len() == 1 {
         result.push(blocks.to_vec());
-        return;
+        return 1;
     }
 
     let min_x = blocks.iter().map(|b| b.x).fold(f32::MAX, f32::min);
@@ -51,27 +77,27 @@ fn xycut_recursive(blocks: &[Block], min_x_gap: f32, min_y_gap: f32, result: &mu
     let min_y = blocks.iter().map(|b| b.bottom()).fold(f32::MAX, f32::min);
     let max_y = blocks.iter().map(|b| b.y).fold(f32::MIN, f32::max);
 
-    let v_gap = find_best_vertical_gap(blocks, min_x, max_x, min_x_gap);
-    let h_gap = find_best_horizontal_gap(blocks, min_y, max_y, min_y_gap);
+    let v_gap = find_best_vertical_gap(blocks, min_x, max_x, limits.min_x_gap)
+        .filter(|&(x, _)| x - min_x >= limits.min_width && max_x - x >= limits.min_width);
+    let h_gap = find_best_horizontal_gap(blocks, min_y, max_y, limits.min_y_gap);
 
     match (v_gap, h_gap) {
         (Some((v_pos, v_width)), Some((_h_pos, h_height))) if v_width >= h_height => {
             let (left, right) = split_vertical(blocks, v_pos);
-            xycut_recursive(&left, min_x_gap, min_y_gap, result);
-            xycut_recursive(&right, min_x_gap, min_y_gap, result);
+            xycut_recursive(&left, limits, result) + xycut_recursive(&right, limits, result)
         }
         (_, Some((h_pos, _))) => {
             let (top, bottom) = split_horizontal(blocks, h_pos);
-            xycut_recursive(&top, min_x_gap, min_y_gap, result);
-            xycut_recursive(&bottom, min_x_gap, min_y_gap, result);
+            let top = xycut_recursive(&top, limits, result);
+            top.max(xycut_recursive(&bottom, limits, result))
         }
         (Some((v_pos, _)), None) => {
             let (left, right) = split_vertical(blocks, v_pos);
-            xycut_recursive(&left, min_x_gap, min_y_gap, result);
-            xycut_recursive(&right, min_x_gap, min_y_gap, result);
+            xycut_recursive(&left, limits, result) + xycut_recursive(&right, limits, result)
         }
         (None, None) => {
             result.push(blocks.to_vec());
+            1
         }
     }
 }
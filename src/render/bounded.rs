@@ -0,0 +1,255 @@
+//! Keeping rendered output within [`RenderOptions::max_output_bytes`].
+//!
+//! Output is cut between blocks, which in rendered Markdown and text are
+//! separated by blank lines. Blank lines inside fenced code do not separate
+//! blocks. A block larger than the budget on its own is cut between lines,
+//! and a line larger than the budget between characters.
+
+use std::ops::Range;
+
+use super::{OverflowStrategy, RenderOptions};
+
+/// How markers are written into the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MarkerSyntax {
+    /// `<!-- ... -->`, invisible in rendered Markdown
+    Comment,
+    /// `[...]`, for plain text
+    Bracket,
+    /// `.. ...`, a reStructuredText comment
+    RstComment,
+    /// `// ...`, an AsciiDoc line comment
+    AsciiDocComment,
+}
+
+impl MarkerSyntax {
+    fn wrap(self, text: &str) -> String {
+        match self {
+            MarkerSyntax::Comment => format!("<!-- {} -->", text),
+            MarkerSyntax::Bracket => format!("[{}]", text),
+            MarkerSyntax::RstComment => format!(".. {}", text),
+            MarkerSyntax::AsciiDocComment => format!("// {}", text),
+        }
+    }
+}
+
+/// `content` bounded as `options` ask: one element unless the overflow
+/// strategy is [`OverflowStrategy::Split`] and the content is over the limit.
+pub(crate) fn bound_output(
+    content: String,
+    options: &RenderOptions,
+    syntax: MarkerSyntax,
+) -> Vec<String> {
+    let Some(max_bytes) = options.max_output_bytes else {
+        return vec![content];
+    };
+    if content.len() <= max_bytes {
+        return vec![content];
+    }
+    let blocks = blocks(&content);
+    if blocks.is_empty() {
+        // 공백뿐인 출력(텍스트 층 없는 스캔본의 페이지 구분자 등) — 남길 블록이 없다.
+        return vec![String::new()];
+    }
+
+    match options.overflow {
+        OverflowStrategy::Truncate => vec![truncate(&content, &blocks, max_bytes)],
+        OverflowStrategy::SummarizeTail => {
+            let summary = |blocks: usize, bytes: usize| {
+                syntax.wrap(&format!(
+                    "truncated: {} more blocks, {} bytes omitted",
+                    blocks, bytes
+                ))
+            };
+            // 생략 수치는 전체 크기를 넘지 않으므로 그 자릿수만큼 자리를 잡아 둔다.
+            let reserve = summary(blocks.len(), content.len()).len() + 2;
+            if reserve >= max_bytes {
+                return vec![truncate(&content, &blocks, max_bytes)];
+            }
+            let units = units(&content, &blocks, max_bytes - reserve);
+            let end = fill(&units, 0, max_bytes - reserve);
+            let cut = units[end - 1].end;
+            let omitted = blocks.iter().filter(|b| b.end > cut).count();
+            let mut part = content[..cut].to_string();
+            part.push_str("\n\n");
+            part.push_str(&summary(omitted, content.len() - cut));
+            vec![part]
+        }
+        OverflowStrategy::Split => {
+            let marker = |part: usize, total: usize| {
+                syntax.wrap(&format!("continued in part {} of {}", part, total))
+            };
+            // 조각 수는 바이트 수를 넘지 않으므로 그 자릿수로 표시 자리를 잡는다.
+            let estimate = content.len();
+            let reserve = marker(estimate, estimate).len() + 2;
+            if reserve >= max_bytes {
+                return vec![truncate(&content, &blocks, max_bytes)];
+            }
+            let budget = max_bytes - reserve;
+            let units = units(&content, &blocks, budget);
+            let mut spans = Vec::new();
+            let mut start = 0;
+            while start < units.len() {
+                let end = fill(&units, start, budget);
+                spans.push(units[start].start..units[end - 1].end);
+                start = end;
+            }
+            let total = spans.len();
+            spans
+                .into_iter()
+                .enumerate()
+                .map(|(i, span)| {
+                    let mut part = content[span].to_string();
+                    if i + 1 < total {
+                        part.push_str("\n\n");
+                        part.push_str(&marker(i + 2, total));
+                    }
+                    part
+                })
+                .collect()
+        }
+    }
+}
+
+/// The leading blocks of `content` that fit in `max_bytes`.
+fn truncate(content: &str, blocks: &[Range<usize>], max_bytes: usize) -> String {
+    let units = units(content, blocks, max_bytes);
+    match units.first() {
+        Some(first) => content[first.start..units[fill(&units, 0, max_bytes) - 1].end].to_string(),
+        None => String::new(),
+    }
+}
+
+/// Index one past the last unit, starting at `start`, that fits in `budget`
+/// together with everything between them. Always takes at least one unit.
+fn fill(units: &[Range<usize>], start: usize, budget: usize) -> usize {
+    let mut end = start + 1;
+    while end < units.len() && units[end].end - units[start].start <= budget {
+        end += 1;
+    }
+    end
+}
+
+/// Byte ranges of the blocks of `content`, without the blank lines between
+/// them.
+fn blocks(content: &str) -> Vec<Range<usize>> {
+    let mut blocks = Vec::new();
+    let mut current: Option<Range<usize>> = None;
+    let mut in_fence = false;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let text = line.trim_end_matches(['\n', '\r']);
+        let trimmed = text.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if text.trim().is_empty() && !in_fence {
+            blocks.extend(current.take());
+            continue;
+        }
+        let end = start + text.len();
+        match current.as_mut() {
+            Some(block) => block.end = end,
+            None => current = Some(start..end),
+        }
+    }
+    blocks.extend(current);
+    blocks
+}
+
+/// `blocks` broken into pieces of at most `budget` bytes: whole blocks where
+/// they fit, else their lines, else runs of characters.
+fn units(content: &str, blocks: &[Range<usize>], budget: usize) -> Vec<Range<usize>> {
+    let mut units = Vec::new();
+    for block in blocks {
+        if block.len() <= budget {
+            units.push(block.clone());
+            continue;
+        }
+        let mut offset = block.start;
+        for line in content[block.clone()].split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            let end = start + line.trim_end_matches(['\n', '\r']).len();
+            if end - start <= budget {
+                units.push(start..end);
+                continue;
+            }
+            let mut from = start;
+            while from < end {
+                let mut to = (from + budget).min(end);
+                while !content.is_char_boundary(to) {
+                    to -= 1;
+                }
+                if to == from {
+                    // 예산보다 긴 글자 하나 — 통째로 넣는다.
+                    to = from + content[from..].chars().next().map_or(1, char::len_utf8);
+                }
+                units.push(from..to);
+                from = to;
+            }
+        }
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(max_bytes: usize, overflow: OverflowStrategy) -> RenderOptions {
+        RenderOptions::new().with_max_output_bytes(max_bytes, overflow)
+    }
+
+    #[test]
+    fn test_blocks_skip_fenced_blank_lines() {
+        let content = "# Title\n\n```\na\n\nb\n```\n\nEnd";
+        let ranges: Vec<&str> = blocks(content).into_iter().map(|r| &content[r]).collect();
+        assert_eq!(ranges, vec!["# Title", "```\na\n\nb\n```", "End"]);
+    }
+
+    #[test]
+    fn test_truncate_at_block_boundary() {
+        let content = "First block.\n\nSecond block.\n\nThird block.".to_string();
+        let options = options(30, OverflowStrategy::Truncate);
+        let parts = bound_output(content, &options, MarkerSyntax::Comment);
+        assert_eq!(parts, vec!["First block.\n\nSecond block."]);
+    }
+
+    #[test]
+    fn test_long_line_cut_between_characters() {
+        let content = "가나다라마바사".to_string();
+        let options = options(10, OverflowStrategy::Truncate);
+        let parts = bound_output(content, &options, MarkerSyntax::Bracket);
+        assert_eq!(parts, vec!["가나다"]);
+    }
+
+    #[test]
+    fn test_split_parts_stay_within_limit() {
+        let content: Vec<String> = (0..40)
+            .map(|i| format!("Paragraph number {}.", i))
+            .collect();
+        let content = content.join("\n\n");
+        let options = options(200, OverflowStrategy::Split);
+        let parts = bound_output(content, &options, MarkerSyntax::Comment);
+        assert!(parts.len() > 1);
+        assert!(parts.iter().all(|p| p.len() <= 200));
+        assert!(parts[0].ends_with(&format!("<!-- continued in part 2 of {} -->", parts.len())));
+        assert!(parts.last().unwrap().ends_with("Paragraph number 39."));
+    }
+
+    #[test]
+    fn test_whitespace_only_content_yields_one_empty_part() {
+        for overflow in [
+            OverflowStrategy::Truncate,
+            OverflowStrategy::SummarizeTail,
+            OverflowStrategy::Split,
+        ] {
+            let content = "\n\x0c".repeat(50);
+            let parts = bound_output(content, &options(40, overflow), MarkerSyntax::Bracket);
+            assert_eq!(parts, vec![String::new()]);
+        }
+    }
+}
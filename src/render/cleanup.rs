@@ -1,8 +1,24 @@
 //! Text cleanup pipeline for LLM training data preparation.
 
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
 use regex::Regex;
 use unicode_normalization::UnicodeNormalization;
 
+use crate::error::{Error, Result};
+use crate::model::Metadata;
+
+use super::mojibake::{repair_mojibake, MojibakeReport};
+
+/// A `&'static Regex` compiled on first use and shared by every pipeline.
+macro_rules! static_regex {
+    ($pattern:expr) => {{
+        static RE: OnceLock<Regex> = OnceLock::new();
+        RE.get_or_init(|| Regex::new($pattern).unwrap())
+    }};
+}
+
 /// Cleanup preset levels.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum CleanupPreset {
@@ -15,19 +31,244 @@ pub enum CleanupPreset {
     Aggressive,
 }
 
+/// Digit scripts folded to ASCII `0`–`9` by [`CleanupOptions::normalize_digits`].
+///
+/// Only decimal digits are mapped; numerals like 〇, 十 or Roman numerals are
+/// left alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DigitNormalization {
+    /// Full-width digits (０–９), common in CJK documents
+    pub fullwidth: bool,
+
+    /// Arabic-Indic digits (٠–٩)
+    pub arabic_indic: bool,
+
+    /// Extended Arabic-Indic digits used for Persian and Urdu (۰–۹)
+    pub extended_arabic_indic: bool,
+
+    /// Indic digits: Devanagari, Bengali, Gurmukhi, Gujarati, Oriya, Tamil,
+    /// Telugu, Kannada and Malayalam
+    pub indic: bool,
+
+    /// Southeast Asian digits: Thai, Lao, Myanmar and Khmer
+    pub southeast_asian: bool,
+}
+
+impl DigitNormalization {
+    /// Fold no digits.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Fold every supported script.
+    pub fn all() -> Self {
+        Self {
+            fullwidth: true,
+            arabic_indic: true,
+            extended_arabic_indic: true,
+            indic: true,
+            southeast_asian: true,
+        }
+    }
+
+    /// Whether any script is enabled.
+    pub fn is_enabled(&self) -> bool {
+        *self != Self::none()
+    }
+
+    /// ASCII digit for `c` if its script is enabled.
+    fn fold(&self, c: char) -> Option<char> {
+        let zero = match c as u32 {
+            0xFF10..=0xFF19 if self.fullwidth => 0xFF10,
+            0x0660..=0x0669 if self.arabic_indic => 0x0660,
+            0x06F0..=0x06F9 if self.extended_arabic_indic => 0x06F0,
+            // 인도계 문자는 블록마다 0x80 간격으로 같은 위치에 숫자가 있다.
+            cp @ 0x0966..=0x0D6F if self.indic && (cp & 0x7F) >= 0x66 && (cp & 0x7F) <= 0x6F => {
+                cp & !0x7F | 0x66
+            }
+            0x0E50..=0x0E59 if self.southeast_asian => 0x0E50,
+            0x0ED0..=0x0ED9 if self.southeast_asian => 0x0ED0,
+            0x1040..=0x1049 if self.southeast_asian => 0x1040,
+            0x17E0..=0x17E9 if self.southeast_asian => 0x17E0,
+            _ => return None,
+        };
+        char::from_digit(c as u32 - zero, 10)
+    }
+}
+
+/// Unicode normalization form applied when
+/// [`CleanupOptions::normalize_unicode`] is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NormalizationForm {
+    /// Canonical composition; changes no visible characters (default)
+    #[default]
+    Nfc,
+    /// Compatibility composition: full-width Latin → ASCII, circled and
+    /// superscript numbers → plain digits, ligatures → letters
+    Nfkc,
+    /// Compatibility decomposition: as NFKC, with accents split off as
+    /// combining marks
+    Nfkd,
+}
+
+impl NormalizationForm {
+    /// Whether this is a compatibility form (NFKC or NFKD).
+    pub fn is_compatibility(&self) -> bool {
+        !matches!(self, Self::Nfc)
+    }
+}
+
+/// A pattern whose matches are replaced by a placeholder during
+/// [PII redaction](PiiRedaction).
+#[derive(Debug, Clone)]
+pub struct RedactionRule {
+    regex: Regex,
+    placeholder: String,
+}
+
+impl RedactionRule {
+    /// Replace matches of the regular expression `pattern` with `placeholder`.
+    pub fn new(pattern: &str, placeholder: impl Into<String>) -> Result<Self> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| Error::Other(format!("Invalid redaction pattern: {}", e)))?;
+        Ok(Self {
+            regex,
+            placeholder: placeholder.into(),
+        })
+    }
+
+    /// The regular expression matched.
+    pub fn pattern(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    /// Text that replaces each match.
+    pub fn placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    /// Replace matches in `text`, counting them under the placeholder.
+    fn apply(&self, text: &str, counts: &mut BTreeMap<String, u32>) -> String {
+        let found = self.regex.find_iter(text).count() as u32;
+        if found == 0 {
+            return text.to_string();
+        }
+        *counts.entry(self.placeholder.clone()).or_default() += found;
+        self.regex
+            .replace_all(text, regex::NoExpand(&self.placeholder))
+            .into_owned()
+    }
+}
+
+/// Personal data masked by [`CleanupOptions::redact_pii`].
+///
+/// Built-in detectors are pattern-based and favour recall on common
+/// formats; they do not validate checksums. Custom rules run after them.
+#[derive(Debug, Clone, Default)]
+pub struct PiiRedaction {
+    /// E-mail addresses → `[EMAIL]`
+    pub emails: bool,
+
+    /// Phone numbers written with separators (`010-1234-5678`,
+    /// `+1 (555) 123-4567`) → `[PHONE]`
+    pub phone_numbers: bool,
+
+    /// National ID numbers: Korean resident registration numbers
+    /// (`900101-1234567`) and US social security numbers (`123-45-6789`)
+    /// → `[NATIONAL_ID]`
+    pub national_ids: bool,
+
+    /// Additional patterns, applied in order
+    pub custom: Vec<RedactionRule>,
+}
+
+impl PiiRedaction {
+    /// Placeholder for e-mail addresses.
+    pub const EMAIL: &'static str = "[EMAIL]";
+    /// Placeholder for phone numbers.
+    pub const PHONE: &'static str = "[PHONE]";
+    /// Placeholder for national ID numbers.
+    pub const NATIONAL_ID: &'static str = "[NATIONAL_ID]";
+
+    /// Redact nothing.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Enable every built-in detector.
+    pub fn all() -> Self {
+        Self {
+            emails: true,
+            phone_numbers: true,
+            national_ids: true,
+            custom: Vec::new(),
+        }
+    }
+
+    /// Add a custom rule.
+    pub fn with_rule(mut self, rule: RedactionRule) -> Self {
+        self.custom.push(rule);
+        self
+    }
+
+    /// Whether any detector or rule is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.emails || self.phone_numbers || self.national_ids || !self.custom.is_empty()
+    }
+
+    /// Built-in rules followed by custom ones, in application order.
+    fn rules(&self) -> Vec<RedactionRule> {
+        let builtin = |regex: &Regex, placeholder: &str| RedactionRule {
+            regex: regex.clone(),
+            placeholder: placeholder.to_string(),
+        };
+        let mut rules = Vec::new();
+        // 메일 주소에 숫자가 섞일 수 있으므로 번호 패턴보다 먼저 지운다.
+        if self.emails {
+            rules.push(builtin(
+                static_regex!(r"(?i)\b[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)*\.[a-z]{2,}\b"),
+                Self::EMAIL,
+            ));
+        }
+        if self.national_ids {
+            rules.push(builtin(
+                static_regex!(r"\b(?:\d{6}-[1-8]\d{6}|\d{3}-\d{2}-\d{4})\b"),
+                Self::NATIONAL_ID,
+            ));
+        }
+        if self.phone_numbers {
+            rules.push(builtin(
+                static_regex!(concat!(
+                    r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{2,4}\)[ .-]?|\b\d{2,4}[ .-])",
+                    r"\d{3,4}[ .-]\d{4}\b",
+                )),
+                Self::PHONE,
+            ));
+        }
+        rules.extend(self.custom.iter().cloned());
+        rules
+    }
+}
+
 /// Options for text cleanup.
 #[derive(Debug, Clone)]
 pub struct CleanupOptions {
-    /// Normalize Unicode to NFC form
+    /// Normalize Unicode (to NFC unless `normalization` says otherwise)
     pub normalize_unicode: bool,
 
+    /// Normalization form used by `normalize_unicode`. The compatibility
+    /// forms also fold enclosed alphanumerics that Unicode gives no
+    /// decomposition (❶, ⓫, ➀, 🅐) to their plain digits and letters.
+    pub normalization: NormalizationForm,
+
     /// Standardize bullet characters (•, ●, ○ → •)
     pub standardize_bullets: bool,
 
     /// Remove page numbers
     pub remove_page_numbers: bool,
 
-    /// Remove headers and footers
+    /// Remove headers and footers. Whole-document renderers drop running
+    /// heads that repeat a bookmark title (see `Document::running_heads`)
     pub remove_headers_footers: bool,
 
     /// Remove table of contents
@@ -39,7 +280,9 @@ pub struct CleanupOptions {
     /// Fix hyphenation at line breaks
     pub fix_hyphenation: bool,
 
-    /// Detect and flag mojibake (corrupted text)
+    /// Detect mojibake (raw CP949, Shift_JIS, GBK or UTF-8 bytes read as
+    /// Latin-1, decomposed Hangul, PUA clusters) and re-decode what can be;
+    /// counts land in [`CleanupReport::mojibake`]
     pub detect_mojibake: bool,
 
     /// Remove Private Use Area (PUA) characters
@@ -70,6 +313,19 @@ pub struct CleanupOptions {
     /// (e.g., orphan `-`, `- -`, `,`). These are usually layout-artefact
     /// fragments rather than meaningful content.
     pub drop_punctuation_only_lines: bool,
+
+    /// Insert word boundaries into Chinese/Japanese text for search indexing.
+    /// Han runs are split with a dictionary only when the `cjk-segment`
+    /// feature is enabled; otherwise only script changes are split.
+    pub segment_cjk: bool,
+
+    /// Fold localized decimal digits (full-width, Arabic-Indic, ...) to ASCII
+    /// for downstream number parsing
+    pub normalize_digits: DigitNormalization,
+
+    /// Replace personal data with placeholders. Fenced code blocks and the
+    /// metadata written to YAML frontmatter or JSON are redacted too.
+    pub redact_pii: PiiRedaction,
 }
 
 impl CleanupOptions {
@@ -86,6 +342,7 @@ impl CleanupOptions {
     pub fn minimal() -> Self {
         Self {
             normalize_unicode: true,
+            normalization: NormalizationForm::Nfc,
             standardize_bullets: false,
             remove_page_numbers: false,
             remove_headers_footers: false,
@@ -102,6 +359,9 @@ impl CleanupOptions {
             max_consecutive_newlines: 0,
             preserve_frontmatter: true,
             drop_punctuation_only_lines: false,
+            segment_cjk: false,
+            normalize_digits: DigitNormalization::none(),
+            redact_pii: PiiRedaction::none(),
         }
     }
 
@@ -109,6 +369,7 @@ impl CleanupOptions {
     pub fn standard() -> Self {
         Self {
             normalize_unicode: true,
+            normalization: NormalizationForm::Nfc,
             standardize_bullets: true,
             remove_page_numbers: true,
             remove_headers_footers: true,
@@ -129,6 +390,9 @@ impl CleanupOptions {
             // the observable prior behaviour.
             preserve_frontmatter: true,
             drop_punctuation_only_lines: true,
+            segment_cjk: false,
+            normalize_digits: DigitNormalization::none(),
+            redact_pii: PiiRedaction::none(),
         }
     }
 
@@ -136,6 +400,7 @@ impl CleanupOptions {
     pub fn aggressive() -> Self {
         Self {
             normalize_unicode: true,
+            normalization: NormalizationForm::Nfc,
             standardize_bullets: true,
             remove_page_numbers: true,
             remove_headers_footers: true,
@@ -152,8 +417,24 @@ impl CleanupOptions {
             max_consecutive_newlines: 2,
             preserve_frontmatter: true,
             drop_punctuation_only_lines: true,
+            segment_cjk: false,
+            normalize_digits: DigitNormalization::all(),
+            redact_pii: PiiRedaction::none(),
         }
     }
+
+    /// Normalize to `form` (and turn Unicode normalization on).
+    pub fn with_normalization(mut self, form: NormalizationForm) -> Self {
+        self.normalize_unicode = true;
+        self.normalization = form;
+        self
+    }
+
+    /// Replace personal data matched by `redaction` with placeholders.
+    pub fn with_pii_redaction(mut self, redaction: PiiRedaction) -> Self {
+        self.redact_pii = redaction;
+        self
+    }
 }
 
 impl Default for CleanupOptions {
@@ -162,28 +443,94 @@ impl Default for CleanupOptions {
     }
 }
 
+/// Where in the pipeline a [`CleanupStage`] runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StagePoint {
+    /// After character-level fixes (Unicode normalization, ligatures,
+    /// digits, bullets), before any line-level cleanup
+    AfterNormalization,
+    /// After line-level cleanup (page numbers, hyphenation, line merging),
+    /// before personal data is redacted — text a stage adds is redacted too
+    BeforeRedaction,
+    /// Last, after whitespace normalization
+    #[default]
+    End,
+}
+
+/// What a [`CleanupStage`] is told about the run it is part of.
+#[derive(Debug, Clone, Copy)]
+pub struct CleanupContext<'a> {
+    /// Options of the pipeline running the stage
+    pub options: &'a CleanupOptions,
+    /// Point at which the stage is running
+    pub point: StagePoint,
+}
+
+/// A user-defined cleanup step, added with [`CleanupPipeline::with_stage`].
+///
+/// Stages see prose only: fenced code blocks and YAML frontmatter are passed
+/// by them, as by the built-in steps.
+///
+/// ```
+/// use unpdf::render::{CleanupContext, CleanupPipeline, CleanupStage};
+///
+/// /// Writes "§ 12" citations with a non-breaking space.
+/// struct Citations;
+///
+/// impl CleanupStage for Citations {
+///     fn process(&self, text: &str, _ctx: &CleanupContext) -> String {
+///         text.replace("§ ", "§\u{a0}")
+///     }
+/// }
+///
+/// let pipeline = CleanupPipeline::default().with_stage(Box::new(Citations));
+/// assert_eq!(pipeline.process("See § 12."), "See §\u{a0}12.");
+/// ```
+pub trait CleanupStage: Send + Sync {
+    /// Cleaned `text`.
+    fn process(&self, text: &str, ctx: &CleanupContext) -> String;
+
+    /// Where the stage runs; [`StagePoint::End`] unless overridden.
+    fn point(&self) -> StagePoint {
+        StagePoint::End
+    }
+}
+
+/// What a cleanup pass changed, from [`CleanupPipeline::process_with_report`].
+#[derive(Debug, Clone, Default)]
+pub struct CleanupReport {
+    /// Matches replaced by redaction, keyed by placeholder
+    pub redactions: BTreeMap<String, u32>,
+
+    /// Mojibake repaired and left, when `detect_mojibake` is set
+    pub mojibake: MojibakeReport,
+}
+
 /// Text cleanup pipeline.
+///
+/// Regexes with fixed patterns are compiled once per process and shared;
+/// the rest are compiled when the pipeline is built. Reuse one pipeline
+/// across calls rather than building one per paragraph.
 pub struct CleanupPipeline {
     options: CleanupOptions,
-    page_number_regex: Regex,
-    toc_dot_leader_regex: Regex,
-    toc_dot_leader_inline_regex: Regex,
+    newline_limit_regex: Option<Regex>,
     ligature_map: Vec<(&'static str, &'static str)>,
+    redaction_rules: Vec<RedactionRule>,
+    stages: Vec<Box<dyn CleanupStage>>,
 }
 
 impl CleanupPipeline {
     /// Create a new cleanup pipeline with the given options.
     pub fn new(options: CleanupOptions) -> Self {
+        let newline_limit_regex = match options.max_consecutive_newlines {
+            0 => None,
+            max => Some(Regex::new(&format!(r"\n{{{},}}", max as usize + 1)).unwrap()),
+        };
         Self {
+            redaction_rules: options.redact_pii.rules(),
+            newline_limit_regex,
+            stages: Vec::new(),
             options,
-            page_number_regex: Regex::new(r"(?m)^[\s]*[-–—]?\s*\d+\s*[-–—]?\s*$").unwrap(),
-            // End-of-line dot leaders: "Chapter 1 ...... 6" → "Chapter 1 (p.6)"
-            toc_dot_leader_regex: Regex::new(r"\s*\.{4,}\s*(\d+)?\s*$").unwrap(),
-            // Inline dot leaders: "Chapter 1 ................ Chapter 2" → "Chapter 1 Chapter 2"
-            // and "Chapter 1 ................ 6 Chapter 2" → "Chapter 1 (p.6) Chapter 2"
-            // Threshold is 8+ dots (not 4+) to avoid matching prose ellipses like " .... ".
-            // Real TOC leaders are typically 15–30+ dots; 8 is the safe lower bound.
-            toc_dot_leader_inline_regex: Regex::new(r"\s+\.{8,}\s*(\d+)?\s+").unwrap(),
             ligature_map: vec![
                 ("\u{FB00}", "ff"),  // ﬀ
                 ("\u{FB01}", "fi"),  // ﬁ
@@ -201,8 +548,29 @@ impl CleanupPipeline {
         Self::new(CleanupOptions::from_preset(preset))
     }
 
+    /// Add a user-defined stage at the point it names. Stages at the same
+    /// point run in the order they were added.
+    pub fn with_stage(mut self, stage: Box<dyn CleanupStage>) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
     /// Process text through the cleanup pipeline.
     pub fn process(&self, text: &str) -> String {
+        self.process_with_redactions(text).0
+    }
+
+    /// Process text, also returning how many matches each redaction
+    /// placeholder replaced.
+    pub fn process_with_redactions(&self, text: &str) -> (String, BTreeMap<String, u32>) {
+        let (result, report) = self.process_with_report(text);
+        (result, report.redactions)
+    }
+
+    /// Process text, also returning what redaction and mojibake repair
+    /// changed.
+    pub fn process_with_report(&self, text: &str) -> (String, CleanupReport) {
+        let mut report = CleanupReport::default();
         let mut result = text.to_string();
 
         // Extract and preserve frontmatter if needed
@@ -215,21 +583,88 @@ impl CleanupPipeline {
         if let Some((fm, content)) = frontmatter {
             result = content;
             // Process content, then prepend frontmatter
-            result = self.process_content(&result);
+            result = self.process_content(&result, &mut report);
             result = format!("{}\n{}", fm, result);
         } else {
-            result = self.process_content(&result);
+            result = self.process_content(&result, &mut report);
         }
 
-        result
+        (result, report)
+    }
+
+    fn process_content(&self, text: &str, report: &mut CleanupReport) -> String {
+        // 펜스 코드 블록은 줄 구조와 들여쓰기가 내용이므로 정리하지 않는다.
+        // 단, 개인정보는 코드 안에서도 가린다.
+        let segments = split_code_fences(text);
+        if !segments.iter().any(|(is_code, _)| *is_code) {
+            return self.clean_prose(text, report);
+        }
+        segments
+            .into_iter()
+            .map(|(is_code, segment)| {
+                if is_code {
+                    self.redact(segment.trim_end(), &mut report.redactions)
+                } else {
+                    self.clean_prose(segment, report)
+                }
+            })
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
     }
 
-    fn process_content(&self, text: &str) -> String {
+    fn run_stages(&self, mut text: String, point: StagePoint) -> String {
+        let ctx = CleanupContext {
+            options: &self.options,
+            point,
+        };
+        for stage in self.stages.iter().filter(|s| s.point() == point) {
+            text = stage.process(&text, &ctx);
+        }
+        text
+    }
+
+    fn redact(&self, text: &str, counts: &mut BTreeMap<String, u32>) -> String {
         let mut result = text.to_string();
+        for rule in &self.redaction_rules {
+            result = rule.apply(&result, counts);
+        }
+        result
+    }
 
-        // Stage 1: Unicode normalization
+    /// Clean the text fields of `metadata` for output: repair them as
+    /// [`Metadata::sanitize`] does, then apply this pipeline's character
+    /// normalization and PII redaction. Values that change keep their
+    /// original in `raw_metadata`, itself redacted when redaction is on.
+    pub fn clean_metadata(&self, metadata: &Metadata) -> Metadata {
+        let mut metadata = metadata.clone();
+        metadata.sanitize();
+        // 본문 통계와 달리 메타데이터에서 가린 건수는 세지 않는다.
+        let redact = |value: &str| self.redact(value, &mut BTreeMap::new());
+        metadata.map_text_fields(|value| {
+            let cleaned = self.normalize_chars(value.to_string());
+            Some(redact(cleaned.trim())).filter(|v| !v.is_empty())
+        });
+        if self.options.redact_pii.is_enabled() {
+            metadata.inferred_title = metadata.inferred_title.as_deref().map(redact);
+            for value in metadata.raw_metadata.values_mut() {
+                *value = redact(value);
+            }
+        }
+        metadata
+    }
+
+    /// Stage 1: character-level normalization.
+    fn normalize_chars(&self, text: String) -> String {
+        let mut result = text;
+
+        // Unicode normalization
         if self.options.normalize_unicode {
-            result = result.nfc().collect();
+            result = match self.options.normalization {
+                NormalizationForm::Nfc => result.nfc().collect(),
+                NormalizationForm::Nfkc => fold_enclosed_alphanumerics(&result).nfkc().collect(),
+                NormalizationForm::Nfkd => fold_enclosed_alphanumerics(&result).nfkd().collect(),
+            };
         }
 
         // Fix ligatures
@@ -239,9 +674,13 @@ impl CleanupPipeline {
             }
         }
 
-        // Standardize bullets
-        if self.options.standardize_bullets {
-            result = self.standardize_bullets(&result);
+        // Fold localized digits to ASCII (１２３, ١٢٣ → 123)
+        if self.options.normalize_digits.is_enabled() {
+            let digits = self.options.normalize_digits;
+            result = result
+                .chars()
+                .map(|c| digits.fold(c).unwrap_or(c))
+                .collect();
         }
 
         // Remove PUA characters
@@ -254,9 +693,32 @@ impl CleanupPipeline {
             result = result.replace('\u{FFFD}', "");
         }
 
+        result
+    }
+
+    fn clean_prose(&self, text: &str, report: &mut CleanupReport) -> String {
+        // Repair mojibake before normalization, which folds some of the
+        // Latin-1 characters it is made of (NFKC turns "³" into "3").
+        let mut result = if self.options.detect_mojibake {
+            repair_mojibake(text, &mut report.mojibake)
+        } else {
+            text.to_string()
+        };
+
+        // Stage 1: Unicode normalization
+        result = self.normalize_chars(result);
+
+        // Standardize bullets
+        if self.options.standardize_bullets {
+            result = self.standardize_bullets(&result);
+        }
+
+        result = self.run_stages(result, StagePoint::AfterNormalization);
+
         // Stage 2: Line-level cleanup
         if self.options.remove_page_numbers {
-            result = self.page_number_regex.replace_all(&result, "").to_string();
+            let page_number = static_regex!(r"(?m)^[\s]*[-–—]?\s*\d+\s*[-–—]?\s*$");
+            result = page_number.replace_all(&result, "").to_string();
         }
 
         // Remove TOC dot leaders (e.g. "Chapter 1 .......... 6" → "Chapter 1 (p.6)")
@@ -291,16 +753,30 @@ impl CleanupPipeline {
             result = self.drop_punctuation_only_lines(&result);
         }
 
+        result = self.run_stages(result, StagePoint::BeforeRedaction);
+
+        // Redact personal data (before CJK segmentation, which may insert
+        // spaces inside a match)
+        result = self.redact(&result, &mut report.redactions);
+
+        // Insert CJK word boundaries (search indexing)
+        if self.options.segment_cjk {
+            result = super::segment::segment_cjk(&result);
+        }
+
         // Stage 3: Normalize whitespace
         if self.options.normalize_whitespace {
             result = self.normalize_whitespace(&result);
         }
 
         // Limit consecutive newlines
-        if self.options.max_consecutive_newlines > 0 {
-            result = self.limit_newlines(&result);
+        if let Some(re) = &self.newline_limit_regex {
+            let replacement = "\n".repeat(self.options.max_consecutive_newlines as usize);
+            result = re.replace_all(&result, replacement.as_str()).to_string();
         }
 
+        result = self.run_stages(result, StagePoint::End);
+
         result.trim().to_string()
     }
 
@@ -343,7 +819,7 @@ impl CleanupPipeline {
         // - "infor-\nmation" → "information"
         // - "infor- mation" → "information"
         // - "infor-\n mation" → "information"
-        let re = Regex::new(r"([a-zA-Z])-\s*\n?\s*([a-z])").unwrap();
+        let re = static_regex!(r"([a-zA-Z])-\s*\n?\s*([a-z])");
         re.replace_all(text, "$1$2").to_string()
     }
 
@@ -381,80 +857,37 @@ impl CleanupPipeline {
     fn normalize_whitespace(&self, text: &str) -> String {
         // Replace 3+ spaces with 2 spaces (preserve markdown indentation)
         // Keep single/double spaces as-is for markdown indent support
-        let re = Regex::new(r"[ ]{3,}").unwrap();
+        let re = static_regex!(r"[ ]{3,}");
         re.replace_all(text, "  ").to_string()
     }
 
-    fn limit_newlines(&self, text: &str) -> String {
-        let max = self.options.max_consecutive_newlines as usize;
-        let pattern = format!(r"\n{{{},}}", max + 1);
-        let re = Regex::new(&pattern).unwrap();
-        let replacement = "\n".repeat(max);
-        re.replace_all(text, replacement.as_str()).to_string()
-    }
-
+    /// Join lines that were broken mid-paragraph.
+    ///
+    /// Parsed documents arrive with paragraph lines already joined from
+    /// layout geometry; this pass is for text that still has hard-wrapped
+    /// lines (stream-order extraction, text from elsewhere). A line break is
+    /// kept at blank lines, after sentence-ending punctuation, before a
+    /// Markdown heading, list item, definition or table row, and after a
+    /// heading or table row; every other break becomes a space.
     fn merge_single_newlines(&self, text: &str) -> String {
-        // Replace single newlines with spaces, but preserve:
-        // 1. Paragraph breaks (2+ newlines)
-        // 2. Sentence endings (period/question mark/exclamation + newline)
-        // 3. Markdown headings (lines starting with #)
-        // 4. Markdown list items (lines starting with -, *, or numbers)
-        // 5. Markdown table rows (lines starting with |)
-        //
-        // This fixes PDF extraction that puts each word on a separate line
-        // while keeping logical paragraph structure.
-
-        const PARA_PLACEHOLDER: &str = "\u{0000}PARA\u{0000}";
-        const SENT_PLACEHOLDER: &str = "\u{0000}SENT\u{0000}";
-
-        // Step 1: Protect markdown block elements FIRST (before paragraph breaks)
-        // This ensures we don't lose the newline structure around headings
-
-        // Protect lines that start with markdown heading (# to ######)
-        // Pattern: start of line or newline, then 1-6 hashes + space + content
-        let re_heading_line = Regex::new(r"(?m)^(#{1,6}\s)").unwrap();
-        let protected = re_heading_line.replace_all(text, |caps: &regex::Captures| {
-            format!("\u{0000}H{}", &caps[1])
-        });
-
-        // Protect lines that start with list markers (-, *, or number.)
-        let re_list_line = Regex::new(r"(?m)^([-*]\s|[0-9]+\.\s)").unwrap();
-        let protected = re_list_line.replace_all(&protected, |caps: &regex::Captures| {
-            format!("\u{0000}L{}", &caps[1])
-        });
-
-        // Protect markdown table rows (lines starting with |)
-        let re_table_line = Regex::new(r"(?m)^(\|)").unwrap();
-        let protected = re_table_line.replace_all(&protected, |caps: &regex::Captures| {
-            format!("\u{0000}T{}", &caps[1])
-        });
-
-        // Step 2: Protect paragraph breaks (2+ newlines)
-        let re_para = Regex::new(r"\n{2,}").unwrap();
-        let protected = re_para.replace_all(&protected, PARA_PLACEHOLDER);
-
-        // Step 3: Protect sentence endings followed by newline
-        let re_sent = Regex::new(r"([.。!?！？])\s*\n").unwrap();
-        let protected = re_sent.replace_all(&protected, |caps: &regex::Captures| {
-            format!("{}{}", &caps[1], SENT_PLACEHOLDER)
-        });
-
-        // Step 4: Replace remaining single newlines with space
-        let merged = protected.replace('\n', " ");
-
-        // Step 5: Restore sentence breaks as single newline
-        let merged = merged.replace(SENT_PLACEHOLDER, "\n");
-
-        // Step 6: Restore paragraph breaks
-        let merged = merged.replace(PARA_PLACEHOLDER, "\n\n");
-
-        // Step 7: Restore markdown block markers with newline prefix
-        // Headings get their own line
-        let merged = merged.replace("\u{0000}H", "\n");
-        // List items get their own line
-        let merged = merged.replace("\u{0000}L", "\n");
-        // Table rows get their own line
-        merged.replace("\u{0000}T", "\n")
+        let mut out = String::with_capacity(text.len());
+        let mut prev: Option<&str> = None;
+        for line in text.split('\n') {
+            match prev {
+                None => out.push_str(line),
+                Some(prev) if keeps_line_break(prev, line) => {
+                    out.push('\n');
+                    out.push_str(line);
+                }
+                Some(_) => {
+                    out.truncate(out.trim_end().len());
+                    out.push(' ');
+                    out.push_str(line.trim_start());
+                }
+            }
+            prev = Some(line);
+        }
+        out
     }
 
     fn merge_list_markers(&self, text: &str) -> String {
@@ -469,22 +902,22 @@ impl CleanupPipeline {
         let mut result = text.to_string();
 
         // Bullet markers followed by newline (• \n, - \n, ■\n, etc.)
-        let re_bullet = Regex::new(r"([•\-■□▪▸►◆◇➤✓✗])\s*\n\s*").unwrap();
+        let re_bullet = static_regex!(r"([•\-■□▪▸►◆◇➤✓✗])\s*\n\s*");
         result = re_bullet.replace_all(&result, "$1 ").to_string();
 
         // Numbered list markers: "01. \n", "1. \n", "1) \n", "(1) \n"
-        let re_number = Regex::new(r"(\d{1,3}[.)]\s*)\n\s*").unwrap();
+        let re_number = static_regex!(r"(\d{1,3}[.)]\s*)\n\s*");
         result = re_number.replace_all(&result, "$1").to_string();
 
-        let re_paren_number = Regex::new(r"(\(\d{1,3}\)\s*)\n\s*").unwrap();
+        let re_paren_number = static_regex!(r"(\(\d{1,3}\)\s*)\n\s*");
         result = re_paren_number.replace_all(&result, "$1").to_string();
 
         // Korean list markers: "가. \n", "나. \n", etc.
-        let re_korean = Regex::new(r"([가-힣][.)]\s*)\n\s*").unwrap();
+        let re_korean = static_regex!(r"([가-힣][.)]\s*)\n\s*");
         result = re_korean.replace_all(&result, "$1").to_string();
 
         // Circled numbers: ❶, ❷, etc.
-        let re_circled = Regex::new(r"([❶-❿])\s*\n\s*").unwrap();
+        let re_circled = static_regex!(r"([❶-❿])\s*\n\s*");
         result = re_circled.replace_all(&result, "$1 ").to_string();
 
         result
@@ -496,11 +929,17 @@ impl CleanupPipeline {
         // "Introduction ............................"      → "Introduction"
         // "Chapter 1 ......... Chapter 2"               → "Chapter 1 Chapter 2"  (inline)
         // "Chapter 1 ......... 6 Chapter 2"             → "Chapter 1 (p.6) Chapter 2"  (inline+page)
+        // End-of-line dot leaders: "Chapter 1 ...... 6" → "Chapter 1 (p.6)"
+        let end_leader = static_regex!(r"\s*\.{4,}\s*(\d+)?\s*$");
+        // Inline dot leaders: "Chapter 1 ................ Chapter 2" → "Chapter 1 Chapter 2"
+        // and "Chapter 1 ................ 6 Chapter 2" → "Chapter 1 (p.6) Chapter 2"
+        // Threshold is 8+ dots (not 4+) to avoid matching prose ellipses like " .... ".
+        // Real TOC leaders are typically 15–30+ dots; 8 is the safe lower bound.
+        let inline_leader = static_regex!(r"\s+\.{8,}\s*(\d+)?\s+");
         text.lines()
             .map(|line| {
                 // Step 1: remove inline dot leaders (between TOC entries on same line)
-                let line = self
-                    .toc_dot_leader_inline_regex
+                let line = inline_leader
                     .replace_all(line, |caps: &regex::Captures| {
                         if let Some(page) = caps.get(1) {
                             format!(" (p.{}) ", page.as_str())
@@ -510,7 +949,7 @@ impl CleanupPipeline {
                     })
                     .into_owned();
                 // Step 2: remove end-of-line dot leaders
-                self.toc_dot_leader_regex
+                end_leader
                     .replace(&line, |caps: &regex::Captures| {
                         if let Some(page) = caps.get(1) {
                             format!(" (p.{})", page.as_str())
@@ -535,14 +974,15 @@ impl CleanupPipeline {
         const PLACEHOLDER: &str = "\u{0000}CJKPARA\u{0000}";
 
         // First, protect paragraph breaks (2+ newlines)
-        let re_para = Regex::new(r"\n{2,}").unwrap();
+        let re_para = static_regex!(r"\n{2,}");
         let protected = re_para.replace_all(text, PLACEHOLDER);
 
         // Pattern: CJK char (not followed by sentence-ending punctuation) + single newline + CJK char
         // Don't merge if the first char is followed by sentence-ending punctuation
-        let re = Regex::new(
-            r"([\p{Hangul}\p{Han}\p{Hiragana}\p{Katakana}])([^.。!?！？\n]?)\n([\p{Hangul}\p{Han}\p{Hiragana}\p{Katakana}])"
-        ).unwrap();
+        let re = static_regex!(concat!(
+            r"([\p{Hangul}\p{Han}\p{Hiragana}\p{Katakana}])([^.。!?！？\n]?)\n",
+            r"([\p{Hangul}\p{Han}\p{Hiragana}\p{Katakana}])",
+        ));
 
         let merged = re.replace_all(&protected, "$1$2$3").to_string();
 
@@ -551,6 +991,207 @@ impl CleanupPipeline {
     }
 }
 
+/// Replace enclosed alphanumerics that have no Unicode compatibility
+/// decomposition (negative, double-circled and sans-serif circled numbers,
+/// negative circled and squared letters) with their plain text. NFKC
+/// already handles ①, ⑴, ⒈, Ⓐ and 🄰.
+fn fold_enclosed_alphanumerics(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        let cp = c as u32;
+        let number = match cp {
+            0x24EB..=0x24F4 => Some(cp - 0x24EB + 11), // ⓫–⓴
+            0x24F5..=0x24FE => Some(cp - 0x24F5 + 1),  // ⓵–⓾
+            0x24FF | 0x1F10B | 0x1F10C => Some(0),     // ⓿ 🄋 🄌
+            0x2776..=0x277F => Some(cp - 0x2776 + 1),  // ❶–❿
+            0x2780..=0x2789 => Some(cp - 0x2780 + 1),  // ➀–➉
+            0x278A..=0x2793 => Some(cp - 0x278A + 1),  // ➊–➓
+            _ => None,
+        };
+        let letter = match cp {
+            0x1F150..=0x1F169 => char::from_u32(cp - 0x1F150 + 'A' as u32), // 🅐–🅩
+            0x1F170..=0x1F189 => char::from_u32(cp - 0x1F170 + 'A' as u32), // 🅰–🆉
+            _ => None,
+        };
+        match (number, letter) {
+            (Some(n), _) => out.push_str(&n.to_string()),
+            (None, Some(l)) => out.push(l),
+            (None, None) => out.push(c),
+        }
+    }
+    out
+}
+
+/// Repair a string from the document information dictionary: UTF-16 that
+/// was read one byte at a time, UTF-8 that was read as Latin-1 or
+/// Windows-1252, C1 bytes left over from PDFDocEncoding, NULs, control
+/// characters and runs of whitespace. Returns NFC text, or `None` when
+/// nothing is left.
+pub(crate) fn sanitize_metadata_value(value: &str) -> Option<String> {
+    let mut text = repair_utf16(value).unwrap_or_else(|| value.to_string());
+    if let Some(repaired) = repair_utf8_mojibake(&text) {
+        text = repaired;
+    }
+    let text: String = text
+        .chars()
+        .filter(|&c| c != '\0' && c != '\u{FEFF}')
+        .map(|c| match c {
+            '\u{80}'..='\u{9F}' => pdf_doc_c1(c),
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some(text.nfc().collect::<String>()).filter(|t| !t.is_empty())
+}
+
+/// The string's characters as bytes, when they all fit in one.
+fn latin1_bytes(text: &str) -> Option<Vec<u8>> {
+    text.chars().map(|c| u8::try_from(c as u32).ok()).collect()
+}
+
+/// Decode UTF-16 whose bytes ended up as characters: a `þÿ`/`ÿþ` byte
+/// order mark, or every other character a NUL.
+fn repair_utf16(text: &str) -> Option<String> {
+    let mut bytes = latin1_bytes(text)?;
+    let big_endian = if bytes.starts_with(&[0xFE, 0xFF]) {
+        bytes.drain(..2);
+        true
+    } else if bytes.starts_with(&[0xFF, 0xFE]) {
+        bytes.drain(..2);
+        false
+    } else {
+        // BOM 없이 NUL이 절반 이상이면 NUL이 몰린 쪽이 상위 바이트다.
+        let nuls = bytes.iter().filter(|&&b| b == 0).count();
+        if bytes.len() < 2 || nuls * 2 < bytes.len() {
+            return None;
+        }
+        let even = bytes.iter().step_by(2).filter(|&&b| b == 0).count();
+        even * 2 >= nuls
+    };
+    if bytes.len() % 2 == 1 {
+        bytes.push(0);
+    }
+    let units = bytes.chunks(2).map(|pair| match big_endian {
+        true => u16::from_be_bytes([pair[0], pair[1]]),
+        false => u16::from_le_bytes([pair[0], pair[1]]),
+    });
+    char::decode_utf16(units)
+        .collect::<std::result::Result<String, _>>()
+        .ok()
+}
+
+/// Decode UTF-8 that was read as Latin-1 or Windows-1252 ("CafÃ©").
+fn repair_utf8_mojibake(text: &str) -> Option<String> {
+    if text.is_ascii() {
+        return None;
+    }
+    let bytes: Vec<u8> = text
+        .chars()
+        .map(|c| match u8::try_from(c as u32) {
+            Ok(b) => Some(b),
+            Err(_) => CP1252_HIGH
+                .iter()
+                .position(|&h| h == c)
+                .map(|i| 0x80 + i as u8),
+        })
+        .collect::<Option<_>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// Windows-1252 characters for bytes 0x80–0x9F (NUL where undefined).
+pub(super) const CP1252_HIGH: [char; 32] = [
+    '€', '\0', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\0', 'Ž', '\0', '\0', '‘',
+    '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\0', 'ž', 'Ÿ',
+];
+
+/// PDFDocEncoding characters for bytes 0x80–0x9F, which a Latin-1 read
+/// leaves as C1 controls.
+fn pdf_doc_c1(c: char) -> char {
+    const PDF_DOC_HIGH: [char; 32] = [
+        '•', '†', '‡', '…', '—', '–', 'ƒ', '⁄', '‹', '›', '−', '‰', '„', '“', '”', '‘', '’', '‚',
+        '™', 'ﬁ', 'ﬂ', 'Ł', 'Œ', 'Š', 'Ÿ', 'Ž', 'ı', 'ł', 'œ', 'š', 'ž', ' ',
+    ];
+    PDF_DOC_HIGH[c as usize - 0x80]
+}
+
+/// Whether the break between `prev` and `next` ends a line on purpose
+/// (see `merge_single_newlines`).
+fn keeps_line_break(prev: &str, next: &str) -> bool {
+    let (prev, next) = (prev.trim(), next.trim_start());
+    if prev.is_empty() || next.trim_end().is_empty() {
+        return true;
+    }
+    let ends_sentence = prev.ends_with(['.', '。', '!', '?', '！', '？']);
+    let prev_is_block = is_heading_line(prev) || prev.starts_with('|');
+    let next_is_block = is_heading_line(next)
+        || is_list_line(next)
+        || next.starts_with('|')
+        || next.starts_with(": ");
+    ends_sentence || prev_is_block || next_is_block
+}
+
+fn is_heading_line(line: &str) -> bool {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    (1..=6).contains(&hashes) && line[hashes..].starts_with(char::is_whitespace)
+}
+
+fn is_list_line(line: &str) -> bool {
+    if line.starts_with("- ") || line.starts_with("* ") {
+        return true;
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    digits > 0 && line[digits..].starts_with(". ")
+}
+
+/// Split Markdown into alternating prose and fenced-code segments, in order.
+/// A fence left open runs to the end of the text, as in CommonMark.
+fn split_code_fences(text: &str) -> Vec<(bool, &str)> {
+    let mut segments = Vec::new();
+    let mut segment_start = 0;
+    let mut open: Option<(char, usize)> = None;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let trimmed = line.trim_start_matches(' ');
+        if line.len() - trimmed.len() > 3 {
+            continue;
+        }
+        let Some(fence_char) = trimmed.chars().next().filter(|c| *c == '`' || *c == '~') else {
+            continue;
+        };
+        let run = trimmed.chars().take_while(|c| *c == fence_char).count();
+        if run < 3 {
+            continue;
+        }
+        match open {
+            None => {
+                let info = &trimmed[run..];
+                if fence_char == '`' && info.contains('`') {
+                    continue;
+                }
+                if line_start > segment_start {
+                    segments.push((false, &text[segment_start..line_start]));
+                }
+                segment_start = line_start;
+                open = Some((fence_char, run));
+            }
+            Some((c, len)) if c == fence_char && run >= len && trimmed[run..].trim().is_empty() => {
+                segments.push((true, &text[segment_start..offset]));
+                segment_start = offset;
+                open = None;
+            }
+            Some(_) => {}
+        }
+    }
+    if segment_start < text.len() {
+        segments.push((open.is_some(), &text[segment_start..]));
+    }
+    segments
+}
+
 impl Default for CleanupPipeline {
     fn default() -> Self {
         Self::new(CleanupOptions::default())
@@ -623,6 +1264,70 @@ mod tests {
         assert!(result.contains("title: Test"));
     }
 
+    #[test]
+    fn test_sanitize_metadata_value() {
+        // BOM 없는 UTF-16BE/LE, 바이트로 읽힌 BOM
+        assert_eq!(
+            sanitize_metadata_value("\0T\0i\0t\0l\0e").as_deref(),
+            Some("Title")
+        );
+        assert_eq!(
+            sanitize_metadata_value("T\0i\0t\0l\0e\0").as_deref(),
+            Some("Title")
+        );
+        assert_eq!(
+            sanitize_metadata_value("\u{FE}\u{FF}\0O\0K").as_deref(),
+            Some("OK")
+        );
+        // Latin-1/CP1252로 읽힌 UTF-8
+        assert_eq!(sanitize_metadata_value("CafÃ©").as_deref(), Some("Café"));
+        assert_eq!(sanitize_metadata_value("A â€” B").as_deref(), Some("A — B"));
+        // PDFDocEncoding C1 바이트, 제어 문자와 공백
+        assert_eq!(sanitize_metadata_value("A\u{84}B").as_deref(), Some("A—B"));
+        assert_eq!(
+            sanitize_metadata_value(" Report\t\r\n v2\0\0").as_deref(),
+            Some("Report v2")
+        );
+        assert_eq!(sanitize_metadata_value("\0 \u{FEFF}"), None);
+        // 정상 값은 그대로
+        for clean in ["Café", "보고서 2024", "naïve résumé"] {
+            assert_eq!(sanitize_metadata_value(clean).as_deref(), Some(clean));
+        }
+    }
+
+    #[test]
+    fn test_clean_metadata_normalizes_characters() {
+        let metadata = Metadata {
+            title: Some("Ｅｆﬁcient\0".to_string()),
+            ..Default::default()
+        };
+        let options = CleanupOptions::standard().with_normalization(NormalizationForm::Nfkc);
+        let pipeline = CleanupPipeline::new(options);
+        let cleaned = pipeline.clean_metadata(&metadata);
+        assert_eq!(cleaned.title.as_deref(), Some("Efficient"));
+        assert_eq!(cleaned.raw_metadata["title"], "Ｅｆﬁcient\0");
+    }
+
+    #[test]
+    fn test_clean_metadata_redacts_pii() {
+        let metadata = Metadata {
+            title: Some("Notes for jane.doe@example.com".to_string()),
+            author: Some("Jane Doe <jane.doe@example.com>".to_string()),
+            keywords: Some("hr, 010-1234-5678".to_string()),
+            ..Default::default()
+        };
+        let options = CleanupOptions::minimal().with_pii_redaction(PiiRedaction::all());
+        let cleaned = CleanupPipeline::new(options).clean_metadata(&metadata);
+        assert_eq!(cleaned.title.as_deref(), Some("Notes for [EMAIL]"));
+        assert_eq!(cleaned.author.as_deref(), Some("Jane Doe <[EMAIL]>"));
+        assert_eq!(cleaned.keywords.as_deref(), Some("hr, [PHONE]"));
+        assert!(cleaned.raw_metadata.values().all(|v| !v.contains('@')));
+
+        let frontmatter = cleaned.to_yaml_frontmatter();
+        assert!(!frontmatter.contains("jane.doe@"));
+        assert!(!frontmatter.contains("1234-5678"));
+    }
+
     #[test]
     fn test_merge_single_newlines() {
         let pipeline = CleanupPipeline::from_preset(CleanupPreset::Standard);
@@ -632,6 +1337,40 @@ mod tests {
         assert!(result.contains("\n\n") || result.contains("New paragraph"));
     }
 
+    #[test]
+    fn test_merge_single_newlines_keeps_block_lines() {
+        let pipeline = CleanupPipeline::new(CleanupOptions {
+            merge_single_newlines: true,
+            ..CleanupOptions::minimal()
+        });
+        let text = "# Title\nwrapped\nline\n- item\ncontinued\n| a |\n| b |\nafter\ntable";
+        assert_eq!(
+            pipeline.process(text),
+            "# Title\nwrapped line\n- item continued\n| a |\n| b |\nafter table"
+        );
+        assert_eq!(
+            pipeline.process("Glyph\n: A drawn\nshape"),
+            "Glyph\n: A drawn shape"
+        );
+        // NUL bytes in the input are not mistaken for internal markers.
+        assert_eq!(pipeline.process("a\u{0}H\nb"), "a\u{0}H b");
+    }
+
+    #[test]
+    fn test_nfkc_folding() {
+        let text = "Ｆｕｌｌ ｗｉｄｔｈ x² ① ❷ ⓬ ➉ 🅐 ﬁle café";
+        let nfc = CleanupPipeline::new(CleanupOptions::minimal()).process(text);
+        assert_eq!(nfc, text);
+
+        let options = CleanupOptions::minimal().with_normalization(NormalizationForm::Nfkc);
+        let nfkc = CleanupPipeline::new(options).process(text);
+        assert_eq!(nfkc, "Full width x2 1 2 12 10 A file café");
+
+        let options = CleanupOptions::minimal().with_normalization(NormalizationForm::Nfkd);
+        let nfkd = CleanupPipeline::new(options).process("café ①");
+        assert_eq!(nfkd, "cafe\u{301} 1");
+    }
+
     #[test]
     fn test_remove_replacement_char() {
         let pipeline = CleanupPipeline::from_preset(CleanupPreset::Standard);
@@ -732,4 +1471,145 @@ mod tests {
             result
         );
     }
+
+    #[test]
+    fn test_segment_cjk_opt_in() {
+        let text = "東京タワーへ行く";
+        let standard = CleanupPipeline::from_preset(CleanupPreset::Standard);
+        assert_eq!(standard.process(text), text);
+
+        let mut options = CleanupOptions::standard();
+        options.segment_cjk = true;
+        let pipeline = CleanupPipeline::new(options);
+        assert!(pipeline.process(text).contains(" タワー へ "));
+    }
+
+    #[test]
+    fn test_normalize_digits_per_script() {
+        let text = "합계 １２３원, ٤٥ ريال, ۶۷ تومان, मूल्य ८९, ราคา ๑๐";
+        let standard = CleanupPipeline::from_preset(CleanupPreset::Standard);
+        assert_eq!(standard.process(text), text);
+
+        let mut options = CleanupOptions::standard();
+        options.normalize_digits = DigitNormalization {
+            fullwidth: true,
+            ..DigitNormalization::none()
+        };
+        let pipeline = CleanupPipeline::new(options);
+        assert_eq!(
+            pipeline.process(text),
+            "합계 123원, ٤٥ ريال, ۶۷ تومان, मूल्य ८९, ราคา ๑๐"
+        );
+
+        let aggressive = CleanupPipeline::from_preset(CleanupPreset::Aggressive);
+        assert_eq!(
+            aggressive.process(text),
+            "합계 123원, 45 ريال, 67 تومان, मूल्य 89, ราคา 10"
+        );
+    }
+
+    #[test]
+    fn test_normalize_digits_indic_blocks() {
+        let digits = DigitNormalization::all();
+        // Bengali, Tamil and Malayalam digit five
+        assert_eq!(digits.fold('\u{09EB}'), Some('5'));
+        assert_eq!(digits.fold('\u{0BEB}'), Some('5'));
+        assert_eq!(digits.fold('\u{0D6B}'), Some('5'));
+        // Letters in the same blocks are untouched
+        assert_eq!(digits.fold('\u{0995}'), None);
+        assert_eq!(digits.fold('\u{0BE5}'), None);
+        assert_eq!(DigitNormalization::none().fold('１'), None);
+    }
+
+    #[test]
+    fn test_code_fences_left_intact() {
+        let text = "Install it\nwith cargo.\n\n```rust\nfn main() {\n    let  x = 1;\n}\n```\n\n\
+                    Done.";
+        let result = CleanupPipeline::from_preset(CleanupPreset::Standard).process(text);
+        assert_eq!(
+            result,
+            "Install it with cargo.\n\n```rust\nfn main() {\n    let  x = 1;\n}\n```\n\nDone."
+        );
+    }
+
+    #[test]
+    fn test_split_code_fences() {
+        let segments = split_code_fences("a\n````\n```\nb\n````\nc");
+        assert_eq!(
+            segments,
+            vec![(false, "a\n"), (true, "````\n```\nb\n````\n"), (false, "c")]
+        );
+        // Unclosed fence runs to the end
+        assert_eq!(split_code_fences("~~~\nx"), vec![(true, "~~~\nx")]);
+    }
+
+    #[test]
+    fn test_pii_redaction() {
+        let options = CleanupOptions::minimal().with_pii_redaction(PiiRedaction::all());
+        let pipeline = CleanupPipeline::new(options);
+        let text = "Mail jane.doe+hr@mail.example.co.kr or call 010-1234-5678, \
+                    +1 (555) 123-4567. RRN 900101-1234567, SSN 123-45-6789.\n\n\
+                    Filed 2024-01-15, page 12 of 300, ISBN 978-0-13-110362-7.";
+        let (result, counts) = pipeline.process_with_redactions(text);
+        assert_eq!(
+            result,
+            "Mail [EMAIL] or call [PHONE], [PHONE]. RRN [NATIONAL_ID], SSN [NATIONAL_ID].\n\n\
+             Filed 2024-01-15, page 12 of 300, ISBN 978-0-13-110362-7."
+        );
+        assert_eq!(counts.get("[EMAIL]"), Some(&1));
+        assert_eq!(counts.get("[PHONE]"), Some(&2));
+        assert_eq!(counts.get("[NATIONAL_ID]"), Some(&2));
+
+        // Off by default, and code blocks are redacted too
+        let code = "```\nssh admin@host.example.com\n```";
+        assert_eq!(
+            CleanupPipeline::new(CleanupOptions::minimal()).process(code),
+            code
+        );
+        assert_eq!(pipeline.process(code), "```\nssh [EMAIL]\n```");
+    }
+
+    #[test]
+    fn test_pii_redaction_custom_rule() {
+        let rule = RedactionRule::new(r"\bEMP-\d{5}\b", "[EMPLOYEE_ID]").unwrap();
+        assert_eq!(rule.placeholder(), "[EMPLOYEE_ID]");
+        let options =
+            CleanupOptions::minimal().with_pii_redaction(PiiRedaction::none().with_rule(rule));
+        assert!(options.redact_pii.is_enabled());
+        let (result, counts) = CleanupPipeline::new(options)
+            .process_with_redactions("EMP-00042 and EMP-00043 cost $5");
+        // `$` in the placeholder or text is not treated as a group reference
+        assert_eq!(result, "[EMPLOYEE_ID] and [EMPLOYEE_ID] cost $5");
+        assert_eq!(counts.get("[EMPLOYEE_ID]"), Some(&2));
+
+        assert!(RedactionRule::new("(", "[X]").is_err());
+    }
+
+    struct Tag(StagePoint, &'static str);
+
+    impl CleanupStage for Tag {
+        fn process(&self, text: &str, ctx: &CleanupContext) -> String {
+            assert_eq!(ctx.point, self.0);
+            format!("{}{}", text, self.1)
+        }
+
+        fn point(&self) -> StagePoint {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_custom_stages_run_at_their_points() {
+        let options = CleanupOptions::standard().with_pii_redaction(PiiRedaction::all());
+        let pipeline = CleanupPipeline::new(options)
+            .with_stage(Box::new(Tag(StagePoint::End, "  ")))
+            .with_stage(Box::new(Tag(
+                StagePoint::BeforeRedaction,
+                " a@b.example.com",
+            )))
+            .with_stage(Box::new(Tag(StagePoint::AfterNormalization, "\nwrapped")))
+            .with_stage(Box::new(Tag(StagePoint::End, "!")));
+        // 앞 단계가 붙인 줄은 줄 병합과 개인정보 가림을 거치고, 마지막 단계의 공백은 남는다.
+        assert_eq!(pipeline.process("Text"), "Text wrapped [EMAIL]  !");
+    }
 }
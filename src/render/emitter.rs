@@ -0,0 +1,70 @@
+//! Block dispatch shared by the output formats.
+//!
+//! Each format implements [`BlockEmitter`] and decides how a paragraph, table,
+//! code listing, … is written; [`emit_block`] is the single place that maps
+//! the document model's block variants onto those calls, so a new variant is
+//! handled (or deliberately ignored) by every format at once.
+
+use crate::model::{Block, Paragraph, Table};
+
+/// Per-format output for each kind of block.
+///
+/// Every method appends to `out`, which holds the output rendered so far.
+pub(crate) trait BlockEmitter {
+    /// A paragraph, heading or list item.
+    fn paragraph(&mut self, out: &mut String, para: &Paragraph);
+
+    /// A grouped list: by default its items one after another, exactly as
+    /// they are written when not grouped.
+    fn list(&mut self, out: &mut String, _ordered: bool, items: &[Paragraph]) {
+        for item in items {
+            self.paragraph(out, item);
+        }
+    }
+
+    /// A table.
+    fn table(&mut self, out: &mut String, table: &Table);
+
+    /// An image reference.
+    fn image(&mut self, out: &mut String, resource_id: &str, alt_text: Option<&str>);
+
+    /// A code listing; `content` lines are separated by `\n`.
+    fn code_block(&mut self, out: &mut String, content: &str, language: Option<&str>);
+
+    /// A horizontal rule.
+    fn horizontal_rule(&mut self, out: &mut String);
+
+    /// Unstructured text.
+    fn raw(&mut self, out: &mut String, content: &str);
+
+    /// A page or section break: by default just ensures a blank line.
+    fn page_break(&mut self, out: &mut String) {
+        if !out.is_empty() && !out.ends_with("\n\n") {
+            out.push_str("\n\n");
+        }
+    }
+}
+
+/// Dispatch one block to `emitter`.
+pub(crate) fn emit_block<E: BlockEmitter + ?Sized>(
+    emitter: &mut E,
+    out: &mut String,
+    block: &Block,
+) {
+    match block {
+        Block::Paragraph(p) => emitter.paragraph(out, p),
+        Block::List { ordered, items } => emitter.list(out, *ordered, items),
+        Block::Table(t) => emitter.table(out, t),
+        Block::Image {
+            resource_id,
+            alt_text,
+            ..
+        } => emitter.image(out, resource_id, alt_text.as_deref()),
+        Block::CodeBlock { language, content } => {
+            emitter.code_block(out, content, language.as_deref())
+        }
+        Block::HorizontalRule => emitter.horizontal_rule(out),
+        Block::PageBreak | Block::SectionBreak => emitter.page_break(out),
+        Block::Raw { content } => emitter.raw(out, content),
+    }
+}
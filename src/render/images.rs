@@ -0,0 +1,418 @@
+//! Converting extracted images for direct use on the web.
+//!
+//! PDF 이미지는 CMYK JPEG, 수천 픽셀짜리 스캔, 색상 프로파일이 박힌 사진처럼
+//! 브라우저에 그대로 올리기 어려운 경우가 많다. 디코딩할 수 없는 포맷(JPEG 2000,
+//! JBIG2 등)은 손대지 않고 원본을 남긴다.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageEncoder, ImageFormat as Codec, ImageReader};
+
+use super::naming::id_stem;
+use crate::model::{Block, Document, Page, Resource};
+
+/// Quality of JPEGs re-encoded after downscaling.
+const JPEG_QUALITY: u8 = 90;
+
+/// Format extracted images are converted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// PNG
+    Png,
+    /// Lossless WebP
+    Webp,
+}
+
+impl ImageFormat {
+    fn codec(self) -> Codec {
+        match self {
+            Self::Png => Codec::Png,
+            Self::Webp => Codec::WebP,
+        }
+    }
+}
+
+/// How extracted images are converted before they are written out.
+///
+/// The default changes nothing. Images in a format that cannot be decoded
+/// (JPEG 2000, JBIG2, raw pixel data) are always left as they are. Available
+/// with the `image-convert` feature.
+///
+/// ```
+/// use unpdf::render::{ImageConversion, ImageFormat, RenderOptions};
+///
+/// let options = RenderOptions::new().with_image_conversion(
+///     ImageConversion::new()
+///         .with_format(ImageFormat::Png)
+///         .with_max_dimension(2000)
+///         .with_stripped_color_profiles(true),
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImageConversion {
+    /// Re-encode every image in this format. `None` keeps each image's own.
+    pub format: Option<ImageFormat>,
+    /// Downscale images wider or taller than this many pixels, keeping the
+    /// aspect ratio.
+    pub max_dimension: Option<u32>,
+    /// Drop embedded ICC color profiles. Otherwise a re-encoded image keeps
+    /// its profile where the target format can carry one.
+    pub strip_color_profiles: bool,
+}
+
+impl ImageConversion {
+    /// A conversion that changes nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert every image to `format`.
+    pub fn with_format(mut self, format: ImageFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Downscale images larger than `pixels` in either dimension.
+    pub fn with_max_dimension(mut self, pixels: u32) -> Self {
+        self.max_dimension = Some(pixels);
+        self
+    }
+
+    /// Drop embedded color profiles.
+    pub fn with_stripped_color_profiles(mut self, strip: bool) -> Self {
+        self.strip_color_profiles = strip;
+        self
+    }
+
+    /// Whether this conversion leaves every image as it is.
+    pub fn is_noop(&self) -> bool {
+        self.format.is_none() && self.max_dimension.is_none() && !self.strip_color_profiles
+    }
+
+    /// `resource` converted, or `None` when it stays as it is: there is
+    /// nothing to change, it is not an image, or it cannot be decoded.
+    pub fn convert(&self, resource: &Resource) -> Option<Resource> {
+        if self.is_noop() || !resource.is_image() {
+            return None;
+        }
+        let codec = Codec::from_mime_type(&resource.mime_type)?;
+        let target = self.format.map_or(codec, ImageFormat::codec);
+        if target == codec && self.max_dimension.is_none() {
+            // 프로파일만 지울 때는 다시 인코딩하지 않아 화질이 그대로다.
+            let data = strip_color_profile(&resource.data, codec)?;
+            return Some(Resource {
+                data,
+                ..resource.clone()
+            });
+        }
+
+        let (mut image, icc) = match decode(&resource.data, codec) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                log::warn!("image left unconverted: {e}");
+                return None;
+            }
+        };
+        let too_large = self
+            .max_dimension
+            .filter(|&max| image.width() > max || image.height() > max);
+        if let Some(max) = too_large {
+            image = image.resize(max, max, FilterType::Lanczos3);
+        } else if target == codec {
+            return self
+                .strip_color_profiles
+                .then(|| strip_color_profile(&resource.data, codec))
+                .flatten()
+                .map(|data| Resource {
+                    data,
+                    ..resource.clone()
+                });
+        }
+
+        let icc = icc.filter(|_| !self.strip_color_profiles);
+        let data = match encode(&image, target, icc) {
+            Ok(data) => data,
+            Err(e) => {
+                log::warn!("image left unconverted: {e}");
+                return None;
+            }
+        };
+        let mut converted = resource.clone();
+        converted.data = data;
+        converted.mime_type = target.to_mime_type().to_string();
+        converted.width = Some(image.width());
+        converted.height = Some(image.height());
+        converted.bits_per_component = Some(8);
+        if codec == Codec::Jpeg && target != Codec::Jpeg {
+            // CMYK JPEG 은 디코딩하면서 RGB 로 바뀐다.
+            converted.color_space = Some(
+                if image.color().has_color() {
+                    "DeviceRGB"
+                } else {
+                    "DeviceGray"
+                }
+                .to_string(),
+            );
+        }
+        Some(converted)
+    }
+
+    /// Convert the images of a page being streamed: the entries of
+    /// `page.images` and, when the extension changes, the blocks that show
+    /// them. Call before the page's images are named, written out and the
+    /// page is rendered.
+    pub fn apply_to_page(&self, page: &mut Page) {
+        if self.is_noop() {
+            return;
+        }
+        let mut renames: HashMap<String, String> = HashMap::new();
+        for (id, resource) in &mut page.images {
+            let Some(converted) = self.convert(resource) else {
+                continue;
+            };
+            let renamed = format!("{}.{}", id_stem(id), converted.extension());
+            if renamed != *id {
+                renames.insert(std::mem::replace(id, renamed.clone()), renamed);
+            }
+            *resource = converted;
+        }
+        rename_blocks(&mut page.elements, |id| renames.get(id).cloned());
+    }
+
+    /// Convert every image of a parsed document: `doc.resources`, the images
+    /// carried by pages and the ids of the blocks that show them.
+    pub fn apply(&self, doc: &mut Document) {
+        if self.is_noop() {
+            return;
+        }
+        for page in &mut doc.pages {
+            self.apply_to_page(page);
+        }
+        // 문서 리소스 키는 확장자 없이 저장되기도 하므로 확장자를 뗀 이름으로 잇는다.
+        let mut extensions: HashMap<String, String> = HashMap::new();
+        let resources = std::mem::take(&mut doc.resources);
+        for (id, resource) in resources {
+            let Some(converted) = self.convert(&resource) else {
+                doc.resources.insert(id, resource);
+                continue;
+            };
+            let stem = id_stem(&id).to_string();
+            let id = if stem == id {
+                id
+            } else {
+                format!("{stem}.{}", converted.extension())
+            };
+            extensions.insert(stem, converted.extension().to_string());
+            doc.resources.insert(id, converted);
+        }
+        for page in &mut doc.pages {
+            rename_blocks(&mut page.elements, |id| {
+                let stem = id_stem(id);
+                let ext = extensions.get(stem)?;
+                (stem != id).then(|| format!("{stem}.{ext}"))
+            });
+        }
+    }
+}
+
+/// Give the resource ids of `blocks` the names `rename` returns for them.
+fn rename_blocks(blocks: &mut [Block], rename: impl Fn(&str) -> Option<String>) {
+    for resource_id in blocks.iter_mut().flat_map(Block::resource_ids_mut) {
+        if let Some(name) = rename(resource_id) {
+            *resource_id = name;
+        }
+    }
+}
+
+/// Decode `data`, along with its embedded color profile.
+fn decode(data: &[u8], codec: Codec) -> image::ImageResult<(DynamicImage, Option<Vec<u8>>)> {
+    let mut decoder = ImageReader::with_format(Cursor::new(data), codec).into_decoder()?;
+    let icc = decoder.icc_profile().ok().flatten();
+    Ok((DynamicImage::from_decoder(decoder)?, icc))
+}
+
+fn encode(image: &DynamicImage, codec: Codec, icc: Option<Vec<u8>>) -> image::ImageResult<Vec<u8>> {
+    fn write(
+        image: &DynamicImage,
+        mut encoder: impl ImageEncoder,
+        icc: Option<Vec<u8>>,
+    ) -> image::ImageResult<()> {
+        if let Some(icc) = icc {
+            // 프로파일을 담지 못하는 인코더면 프로파일 없이 쓴다.
+            let _ = encoder.set_icc_profile(icc);
+        }
+        image.write_with_encoder(encoder)
+    }
+
+    let mut out = Vec::new();
+    match codec {
+        Codec::Jpeg => {
+            // JPEG 에는 알파가 없다.
+            let image = DynamicImage::ImageRgb8(image.to_rgb8());
+            write(
+                &image,
+                JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY),
+                icc,
+            )?
+        }
+        Codec::WebP => write(image, WebPEncoder::new_lossless(&mut out), icc)?,
+        _ => write(image, PngEncoder::new(&mut out), icc)?,
+    }
+    Ok(out)
+}
+
+/// `data` without its ICC profile, or `None` when it has none (or is in a
+/// format whose profile is not removed here).
+fn strip_color_profile(data: &[u8], codec: Codec) -> Option<Vec<u8>> {
+    match codec {
+        Codec::Jpeg => strip_jpeg_profile(data),
+        Codec::Png => strip_png_profile(data),
+        _ => None,
+    }
+}
+
+/// Drop the `APP2 ICC_PROFILE` segments of a JPEG.
+fn strip_jpeg_profile(data: &[u8]) -> Option<Vec<u8>> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut out = data[..2].to_vec();
+    let mut pos = 2;
+    let mut stripped = false;
+    while pos + 4 <= data.len() && data[pos] == 0xFF {
+        let marker = data[pos + 1];
+        if marker == 0xDA {
+            // 스캔 데이터부터는 세그먼트 구조가 아니다.
+            break;
+        }
+        let len = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        let end = pos + 2 + len;
+        if len < 2 || end > data.len() {
+            return None;
+        }
+        if marker == 0xE2 && data[pos + 4..end].starts_with(b"ICC_PROFILE\0") {
+            stripped = true;
+        } else {
+            out.extend_from_slice(&data[pos..end]);
+        }
+        pos = end;
+    }
+    out.extend_from_slice(&data[pos..]);
+    stripped.then_some(out)
+}
+
+/// Drop the `iCCP` chunk of a PNG.
+fn strip_png_profile(data: &[u8]) -> Option<Vec<u8>> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if !data.starts_with(SIGNATURE) {
+        return None;
+    }
+    let mut out = SIGNATURE.to_vec();
+    let mut pos = SIGNATURE.len();
+    let mut stripped = false;
+    while pos + 8 <= data.len() {
+        let len = u32::from_be_bytes(data[pos..pos + 4].try_into().ok()?) as usize;
+        // 길이, 종류, 데이터, CRC
+        let end = pos.checked_add(12 + len).filter(|&end| end <= data.len())?;
+        if &data[pos + 4..pos + 8] == b"iCCP" {
+            stripped = true;
+        } else {
+            out.extend_from_slice(&data[pos..end]);
+        }
+        pos = end;
+    }
+    stripped.then_some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    fn jpeg(width: u32, height: u32) -> Resource {
+        let pixels = RgbImage::from_pixel(width, height, Rgb([200, 40, 40]));
+        let image = DynamicImage::ImageRgb8(pixels);
+        let data = encode(&image, Codec::Jpeg, None).unwrap();
+        Resource::jpeg(data).with_dimensions(width, height)
+    }
+
+    /// `resource` with an `APP2 ICC_PROFILE` segment right after SOI.
+    fn with_profile(mut resource: Resource) -> Resource {
+        let payload = b"ICC_PROFILE\0\x01\x01fake-profile";
+        let mut segment = vec![0xFF, 0xE2];
+        segment.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        segment.extend_from_slice(payload);
+        resource.data.splice(2..2, segment);
+        resource
+    }
+
+    #[test]
+    fn test_noop_leaves_images_alone() {
+        assert!(ImageConversion::new().is_noop());
+        assert!(ImageConversion::new().convert(&jpeg(8, 8)).is_none());
+    }
+
+    #[test]
+    fn test_convert_to_png_and_downscale() {
+        let conversion = ImageConversion::new()
+            .with_format(ImageFormat::Png)
+            .with_max_dimension(50);
+        let converted = conversion.convert(&jpeg(200, 100)).unwrap();
+        assert_eq!(converted.mime_type, "image/png");
+        assert_eq!((converted.width, converted.height), (Some(50), Some(25)));
+        assert!(converted.data.starts_with(b"\x89PNG"));
+
+        // 이미 작으면 포맷이 같을 때 다시 인코딩하지 않는다.
+        let capped = ImageConversion::new().with_max_dimension(500);
+        assert!(capped.convert(&jpeg(200, 100)).is_none());
+    }
+
+    #[test]
+    fn test_webp_output() {
+        let conversion = ImageConversion::new().with_format(ImageFormat::Webp);
+        let converted = conversion.convert(&jpeg(16, 16)).unwrap();
+        assert_eq!(converted.mime_type, "image/webp");
+        assert_eq!(&converted.data[8..12], b"WEBP");
+    }
+
+    #[test]
+    fn test_strip_jpeg_profile_without_reencoding() {
+        let original = jpeg(16, 16);
+        let profiled = with_profile(original.clone());
+        let conversion = ImageConversion::new().with_stripped_color_profiles(true);
+        let stripped = conversion.convert(&profiled).unwrap();
+        assert_eq!(stripped.data, original.data);
+        assert!(conversion.convert(&original).is_none());
+    }
+
+    #[test]
+    fn test_undecodable_images_are_kept() {
+        let jp2 = Resource::image(vec![0, 0, 0, 12], "image/jp2");
+        let conversion = ImageConversion::new().with_format(ImageFormat::Png);
+        assert!(conversion.convert(&jp2).is_none());
+    }
+
+    #[test]
+    fn test_apply_renames_blocks() {
+        let mut doc = Document::new();
+        let mut page = Page::a4(1);
+        page.add_block(Block::image("page1_Im0.jpg"));
+        page.images.push(("page1_Im0.jpg".into(), jpeg(8, 8)));
+        doc.add_page(page);
+        doc.add_resource("page1_Im0".into(), jpeg(8, 8));
+
+        ImageConversion::new()
+            .with_format(ImageFormat::Png)
+            .apply(&mut doc);
+
+        let ids: Vec<&str> = doc.blocks().flat_map(|(_, b)| b.resource_ids()).collect();
+        assert_eq!(ids, ["page1_Im0.png"]);
+        assert_eq!(doc.pages[0].images[0].0, "page1_Im0.png");
+        assert_eq!(doc.resources["page1_Im0"].mime_type, "image/png");
+    }
+}
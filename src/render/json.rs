@@ -1,7 +1,9 @@
 //! JSON rendering for PDF documents.
 
 use crate::error::{Error, Result};
-use crate::model::Document;
+use crate::model::{Block, BlockFilter, Document, Stamped};
+
+use super::{CleanupPipeline, RenderOptions};
 
 /// JSON output format.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -11,16 +13,166 @@ pub enum JsonFormat {
     Pretty,
     /// Compact JSON without extra whitespace
     Compact,
+    /// Pretty-printed JSON with derived views added: every table block also
+    /// carries a `records` array (see `Table::to_records`) and, when it was
+    /// detected from text layout, a `detection` object with its confidence
+    /// and row alignment scores; every block with a known origin a `source`
+    /// object (see `Document::block_source`); and pages the regions the
+    /// table detector turned down as `table_candidates`
+    Detailed,
+}
+
+/// JSON Schema (draft 2020-12) of the document JSON output, as text.
+///
+/// Generated at build time from `schema/document.schema.json` with the crate
+/// version in its `$id`.
+pub const JSON_SCHEMA: &str = include_str!(concat!(env!("OUT_DIR"), "/document.schema.json"));
+
+/// JSON Schema of the document JSON output, for validating output or
+/// generating types in other languages.
+///
+/// Covers every format of [`to_json`] and [`to_json_with_options`]: the
+/// `records` and `source` block fields of [`JsonFormat::Detailed`] and the
+/// provenance fields are optional properties.
+pub fn schema() -> serde_json::Value {
+    serde_json::from_str(JSON_SCHEMA).expect("embedded JSON Schema is valid JSON")
 }
 
 /// Convert a document to JSON.
+///
+/// The output starts with `schema_version` ([`crate::SCHEMA_VERSION`]);
+/// read it back with [`Document::from_json`].
 pub fn to_json(doc: &Document, format: JsonFormat) -> Result<String> {
+    let stamped = Stamped::new(doc);
     let result = match format {
-        JsonFormat::Pretty => serde_json::to_string_pretty(doc),
-        JsonFormat::Compact => serde_json::to_string(doc),
+        JsonFormat::Pretty => serde_json::to_string_pretty(&stamped),
+        JsonFormat::Compact => serde_json::to_string(&stamped),
+        JsonFormat::Detailed => {
+            let mut value = serde_json::to_value(&stamped).map_err(json_err)?;
+            add_block_details(doc, &mut value);
+            serde_json::to_string_pretty(&value)
+        }
+    };
+
+    result.map_err(json_err)
+}
+
+fn json_err(e: serde_json::Error) -> Error {
+    Error::Render(format!("JSON serialization error: {}", e))
+}
+
+/// Insert `records` and `detection` next to each serialized table block,
+/// `source` into each block whose origin was recorded, and
+/// `table_candidates` into each page that has some.
+fn add_block_details(doc: &Document, value: &mut serde_json::Value) {
+    let Some(pages) = value.get_mut("pages").and_then(|p| p.as_array_mut()) else {
+        return;
     };
+    for (page, page_value) in doc.pages.iter().zip(pages.iter_mut()) {
+        if !page.table_candidates.is_empty() {
+            if let (Some(obj), Ok(candidates)) = (
+                page_value.as_object_mut(),
+                serde_json::to_value(&page.table_candidates),
+            ) {
+                obj.insert("table_candidates".to_string(), candidates);
+            }
+        }
+        let Some(elements) = page_value
+            .get_mut("elements")
+            .and_then(|e| e.as_array_mut())
+        else {
+            continue;
+        };
+        for (index, (block, block_value)) in page.elements.iter().zip(elements).enumerate() {
+            let Some(obj) = block_value.as_object_mut() else {
+                continue;
+            };
+            if let Block::Table(table) = block {
+                let records = table
+                    .to_records()
+                    .into_iter()
+                    .map(serde_json::Value::Object)
+                    .collect();
+                obj.insert("records".to_string(), serde_json::Value::Array(records));
+                if let Some(Ok(detection)) = table.detection.as_ref().map(serde_json::to_value) {
+                    obj.insert("detection".to_string(), detection);
+                }
+            }
+            if let Some(source) = page.block_source(index) {
+                if let Ok(source) = serde_json::to_value(source) {
+                    obj.insert("source".to_string(), source);
+                }
+            }
+        }
+    }
+}
 
-    result.map_err(|e| Error::Render(format!("JSON serialization error: {}", e)))
+/// Convert a document to JSON, honouring render options that affect JSON
+/// output: the block filter, languages, `include_provenance` and the PII
+/// redaction of the cleanup options, which is applied to the metadata.
+pub fn to_json_with_options(
+    doc: &Document,
+    format: JsonFormat,
+    options: &RenderOptions,
+) -> Result<String> {
+    if !options.block_filter.is_all() || !options.languages.is_empty() {
+        let mut filtered = doc.clone();
+        for page in &mut filtered.pages {
+            page.retain_blocks(&options.block_filter);
+            page.retain_blocks_where(|block| options.keeps(block));
+        }
+        if !options.block_filter.images {
+            filtered.resources.clear();
+        }
+        let options = RenderOptions {
+            block_filter: BlockFilter::all(),
+            languages: Vec::new(),
+            ..options.clone()
+        };
+        return to_json_with_options(&filtered, format, &options);
+    }
+    // 프론트매터와 같이 메타데이터도 가린다.
+    if let Some(cleanup) = options
+        .cleanup
+        .as_ref()
+        .filter(|cleanup| cleanup.redact_pii.is_enabled())
+    {
+        let mut redacted = doc.clone();
+        redacted.metadata = CleanupPipeline::new(cleanup.clone()).clean_metadata(&doc.metadata);
+        let options = RenderOptions {
+            cleanup: None,
+            ..options.clone()
+        };
+        return to_json_with_options(&redacted, format, &options);
+    }
+
+    let provenance = doc
+        .provenance
+        .as_ref()
+        .filter(|_| options.include_provenance);
+    let Some(provenance) = provenance else {
+        return to_json(doc, format);
+    };
+
+    let mut value = serde_json::to_value(Stamped::new(doc)).map_err(json_err)?;
+    if format == JsonFormat::Detailed {
+        add_block_details(doc, &mut value);
+    }
+    if let serde_json::Value::Object(ref mut map) = value {
+        map.insert(
+            "provenance".to_string(),
+            serde_json::to_value(provenance).map_err(json_err)?,
+        );
+        map.insert(
+            "content_sha256".to_string(),
+            serde_json::Value::String(doc.content_hash()),
+        );
+    }
+    let result = match format {
+        JsonFormat::Compact => serde_json::to_string(&value),
+        JsonFormat::Pretty | JsonFormat::Detailed => serde_json::to_string_pretty(&value),
+    };
+    result.map_err(json_err)
 }
 
 #[cfg(test)]
@@ -42,6 +194,48 @@ mod tests {
         assert!(json.contains('\n')); // Pretty has newlines
     }
 
+    #[test]
+    fn test_provenance_is_opt_in() {
+        let mut doc = Document::new();
+        doc.provenance = Some(crate::model::Provenance::new(
+            Some("ab".into()),
+            "mode=full",
+        ));
+
+        let plain = to_json(&doc, JsonFormat::Compact).unwrap();
+        assert!(!plain.contains("provenance"));
+
+        let options = RenderOptions::new().with_provenance(true);
+        let json = to_json_with_options(&doc, JsonFormat::Compact, &options).unwrap();
+        assert!(json.contains("\"source_sha256\":\"ab\""));
+        assert!(json.contains("\"content_sha256\""));
+    }
+
+    #[test]
+    fn test_detailed_adds_table_records() {
+        use crate::model::{Block, Table, TableCell, TableRow};
+
+        let mut table = Table::with_header(1);
+        table.add_row(TableRow::header(vec![
+            TableCell::text("Item"),
+            TableCell::text("Qty"),
+        ]));
+        table.add_row(TableRow::from_strings(["Bolt", "4"]));
+        let mut page = Page::letter(1);
+        page.add_block(Block::Table(table));
+        let mut doc = Document::new();
+        doc.add_page(page);
+
+        let detailed = to_json(&doc, JsonFormat::Detailed).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&detailed).unwrap();
+        let records = &value["pages"][0]["elements"][0]["records"];
+        assert_eq!(records[0]["Item"], "Bolt");
+        assert_eq!(records[0]["Qty"], "4");
+
+        let pretty = to_json(&doc, JsonFormat::Pretty).unwrap();
+        assert!(!pretty.contains("\"records\""));
+    }
+
     #[test]
     fn test_to_json_compact() {
         let mut doc = Document::new();
@@ -50,5 +244,6 @@ mod tests {
 
         let json = to_json(&doc, JsonFormat::Compact).unwrap();
         assert!(!json.contains('\n')); // Compact has no newlines
+        assert!(json.starts_with(r#"{"schema_version":1,"metadata":"#));
     }
 }
@@ -1,13 +1,20 @@
 //! Markdown rendering for PDF documents.
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use crate::error::Result;
 use crate::model::{
-    Alignment, Block, Document, InlineContent, ListInfo, ListStyle, NumberStyle, Page, Paragraph,
-    Table, TextRun, TextStyle,
+    Alignment, Annotation, Block, Document, InlineContent, ListInfo, ListStyle, NumberStyle, Page,
+    Paragraph, Table, TextRun, TextStyle,
 };
 
+use super::bounded::{bound_output, MarkerSyntax};
+use super::emitter::{emit_block, BlockEmitter};
+use super::warnings::{block_warnings, page_warnings, warning_comments};
 use super::{
-    CleanupPipeline, ExtractionStats, PageMarkerStyle, RenderOptions, RenderResult, TableFallback,
+    AnnotationStyle, CleanupPipeline, DefinitionStyle, ExtractionStats, ListNumbering,
+    PageMarkerStyle, RenderOptions, RenderResult, TableFallback,
 };
 
 /// Convert a document to Markdown.
@@ -16,6 +23,15 @@ pub fn to_markdown(doc: &Document, options: &RenderOptions) -> Result<String> {
     renderer.render(doc)
 }
 
+/// Convert a document to Markdown, split into parts of at most
+/// `options.max_output_bytes` each when the overflow strategy is
+/// [`OverflowStrategy::Split`](super::OverflowStrategy::Split). Otherwise
+/// the one element is what [`to_markdown`] returns.
+pub fn to_markdown_parts(doc: &Document, options: &RenderOptions) -> Result<Vec<String>> {
+    let renderer = MarkdownRenderer::new(options.clone());
+    renderer.render_parts(doc)
+}
+
 /// Convert a document to Markdown with statistics.
 pub fn to_markdown_with_stats(doc: &Document, options: &RenderOptions) -> Result<RenderResult> {
     let mut options = options.clone();
@@ -28,6 +44,15 @@ pub fn to_markdown_with_stats(doc: &Document, options: &RenderOptions) -> Result
 pub struct MarkdownRenderer {
     options: RenderOptions,
     stats: ExtractionStats,
+    list_counter: ListCounter,
+    footnotes: Vec<String>,
+    cleanup: Option<Arc<CleanupPipeline>>,
+    /// Page headings by the placeholder standing in for them until cleanup
+    /// has run.
+    page_headings: Vec<(String, String)>,
+    /// Data URIs of the images embedded under `inline_images_max_bytes`, by
+    /// resource id. They are linked by path until cleanup has run.
+    inline_images: HashMap<String, String>,
 }
 
 impl MarkdownRenderer {
@@ -36,19 +61,43 @@ impl MarkdownRenderer {
         Self {
             options,
             stats: ExtractionStats::new(),
+            list_counter: ListCounter::default(),
+            footnotes: Vec::new(),
+            cleanup: None,
+            page_headings: Vec::new(),
+            inline_images: HashMap::new(),
         }
     }
 
-    /// Render a document to Markdown.
+    /// Use an already built cleanup pipeline — one with custom
+    /// [`CleanupStage`](super::CleanupStage)s, or one shared across
+    /// renderers — instead of building one from `options.cleanup`. Cleanup
+    /// still runs only when `options.cleanup` is set.
+    pub fn with_cleanup_pipeline(mut self, pipeline: Arc<CleanupPipeline>) -> Self {
+        self.cleanup = Some(pipeline);
+        self
+    }
+
+    /// Render a document to Markdown. Output over `max_output_bytes` is
+    /// bounded as the overflow strategy says; when splitting, this is the
+    /// first part.
     pub fn render(mut self, doc: &Document) -> Result<String> {
-        let result = self.render_internal(doc)?;
-        Ok(result)
+        let content = self.render_internal(doc)?;
+        Ok(self.bound(content).swap_remove(0))
+    }
+
+    /// Render a document to Markdown, split into parts when the overflow
+    /// strategy is [`OverflowStrategy::Split`](super::OverflowStrategy::Split).
+    pub fn render_parts(mut self, doc: &Document) -> Result<Vec<String>> {
+        let content = self.render_internal(doc)?;
+        Ok(self.bound(content))
     }
 
     /// Render a document to Markdown with extraction statistics.
     pub fn render_with_stats(mut self, doc: &Document) -> Result<RenderResult> {
         self.options.collect_stats = true;
         let content = self.render_internal(doc)?;
+        let content = self.bound(content).swap_remove(0);
 
         // Count words and characters in final content
         self.stats.count_text(&content);
@@ -57,11 +106,26 @@ impl MarkdownRenderer {
     }
 
     fn render_internal(&mut self, doc: &Document) -> Result<String> {
+        let (stripped, deduplicated) = self.options.prepare_document(doc);
+        let doc = &*stripped;
+        if self.options.collect_stats {
+            self.stats.deduplicated_blocks += deduplicated as u32;
+        }
         let mut output = String::new();
 
+        if self.options.inline_images_max_bytes.is_some() {
+            self.inline_images = doc
+                .pages
+                .iter()
+                .flat_map(|page| &page.elements)
+                .flat_map(Block::resource_ids)
+                .filter_map(|id| Some((id.to_string(), self.options.inline_image_uri(id, doc)?)))
+                .collect();
+        }
+
         // Add frontmatter if requested
-        if self.options.include_frontmatter {
-            output.push_str(&doc.metadata.to_yaml_frontmatter());
+        if let Some(frontmatter) = self.options.frontmatter_for(doc) {
+            output.push_str(&frontmatter);
         }
 
         // Render selected pages
@@ -71,6 +135,15 @@ impl MarkdownRenderer {
             }
         }
 
+        if !self.footnotes.is_empty() {
+            if !output.ends_with("\n\n") {
+                output.push('\n');
+            }
+            for (i, note) in self.footnotes.iter().enumerate() {
+                output.push_str(&format!("[^note-{}]: {}\n", i + 1, note));
+            }
+        }
+
         // Render form fields section
         if !doc.form_fields.is_empty() {
             output.push_str("\n---\n\n");
@@ -87,13 +160,33 @@ impl MarkdownRenderer {
 
         // Apply cleanup if configured
         if let Some(ref cleanup_options) = self.options.cleanup {
-            let pipeline = CleanupPipeline::new(cleanup_options.clone());
-            output = pipeline.process(&output);
+            let pipeline = self
+                .cleanup
+                .clone()
+                .unwrap_or_else(|| Arc::new(CleanupPipeline::new(cleanup_options.clone())));
+            let (cleaned, report) = pipeline.process_with_report(&output);
+            output = cleaned;
+            if self.options.collect_stats {
+                self.stats.add_redactions(&report.redactions);
+                self.stats.add_mojibake(&report.mojibake);
+            }
+        }
+        for (placeholder, heading) in std::mem::take(&mut self.page_headings) {
+            output = output.replacen(&placeholder, &heading, 1);
+        }
+        // 긴 base64 문자열이 정리 단계를 거치지 않도록 정리 뒤에 넣는다.
+        for (id, uri) in std::mem::take(&mut self.inline_images) {
+            let link = format!("]({}{})", self.options.image_path_prefix, id);
+            output = output.replace(&link, &format!("]({})", uri));
         }
 
         Ok(output.trim().to_string())
     }
 
+    fn bound(&self, content: String) -> Vec<String> {
+        bound_output(content, &self.options, MarkerSyntax::Comment)
+    }
+
     fn render_page(&mut self, output: &mut String, page: &Page) {
         if self.options.page_markers == PageMarkerStyle::Comment {
             if !output.is_empty() && !output.ends_with("\n\n") {
@@ -101,51 +194,91 @@ impl MarkdownRenderer {
             }
             output.push_str(&format!("<!-- page {} -->\n\n", page.number));
         }
+        if let Some(heading) = self.options.page_heading(page.number) {
+            self.list_counter.close();
+            if !output.is_empty() && !output.ends_with("\n\n") {
+                output.push_str(if output.ends_with('\n') { "\n" } else { "\n\n" });
+            }
+            // 정리 단계가 쪽 번호로 보고 지우거나 다음 줄과 합치지 않도록, 정리가
+            // 끝날 때까지는 살아남는 주석 자리표시자로 둔다.
+            let placeholder = format!("<!-- page heading {} -->", page.number);
+            output.push_str(&placeholder);
+            output.push_str("\n\n");
+            self.page_headings.push((placeholder, heading));
+        }
         if self.options.collect_stats {
             self.stats.add_page();
         }
-        for block in &page.elements {
-            self.render_block(output, block);
+        if self.options.inline_warnings {
+            output.push_str(&warning_comments(&page_warnings(page)));
         }
-    }
-
-    fn render_block(&mut self, output: &mut String, block: &Block) {
-        match block {
-            Block::Paragraph(p) => self.render_paragraph(output, p),
-            Block::Table(t) => {
-                if self.options.collect_stats {
-                    self.stats.add_table();
-                }
-                self.render_table(output, t);
+        let anchors = match self.options.annotations {
+            AnnotationStyle::Footnote => self.footnote_anchors(page),
+            _ => Vec::new(),
+        };
+        for (index, block) in page.elements.iter().enumerate() {
+            if !self.options.keeps(block) {
+                continue;
             }
-            Block::Image {
-                resource_id,
-                alt_text,
-                ..
-            } => {
-                if self.options.collect_stats {
-                    self.stats.add_image();
-                }
-                self.render_image(output, resource_id, alt_text.as_deref());
+            if !is_list_block(&self.options, block) && self.list_counter.close() {
+                // 목록 뒤 블록이 마지막 항목의 게으른 연속 줄로 읽히지 않도록 빈 줄을 둔다.
+                output.push('\n');
             }
-            Block::HorizontalRule => {
-                if self.options.collect_stats {
-                    self.stats.add_horizontal_rule();
-                }
-                output.push_str("\n---\n\n");
+            if self.options.inline_warnings {
+                output.push_str(&warning_comments(&block_warnings(block)));
             }
-            Block::PageBreak | Block::SectionBreak => {
-                if !output.ends_with("\n\n") {
-                    output.push_str("\n\n");
-                }
+            emit_block(self, output, block);
+            for (_, annotation) in anchors.iter().filter(|(at, _)| *at == index) {
+                // 문단 끝 줄바꿈 앞에 각주 참조를 끼워 넣는다.
+                let body = output.trim_end_matches('\n').len();
+                let tail = output.split_off(body);
+                self.footnotes.push(annotation.summary());
+                output.push_str(&format!("[^note-{}]", self.footnotes.len()));
+                output.push_str(&tail);
             }
-            Block::Raw { content } => {
-                output.push_str(content);
-                output.push_str("\n\n");
+        }
+        if self.options.annotations == AnnotationStyle::Blockquote {
+            for annotation in &page.annotations {
+                if self.list_counter.close() {
+                    output.push('\n');
+                }
+                output.push_str(&format!("> {}\n\n", annotation.summary()));
             }
         }
     }
 
+    /// Which rendered paragraph each of the page's annotations attaches to:
+    /// the first whose text contains the covered text, else the page's last
+    /// paragraph. Annotations on a page without paragraphs are dropped.
+    fn footnote_anchors<'p>(&self, page: &'p Page) -> Vec<(usize, &'p Annotation)> {
+        let paragraphs: Vec<(usize, String)> = page
+            .elements
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| self.options.keeps(block))
+            .filter_map(|(i, block)| match block {
+                Block::Paragraph(para) if !para.is_empty() => Some((i, squash(&para.plain_text()))),
+                _ => None,
+            })
+            .collect();
+        let Some(&(last, _)) = paragraphs.last() else {
+            return Vec::new();
+        };
+        page.annotations
+            .iter()
+            .map(|annotation| {
+                let covered = annotation.covered_text.as_deref().map(squash);
+                let hit = covered.filter(|c| !c.is_empty()).and_then(|covered| {
+                    paragraphs
+                        .iter()
+                        .find(|(_, text)| text.contains(&covered))
+                        .map(|&(i, _)| i)
+                });
+                (hit.unwrap_or(last), annotation)
+            })
+            .collect()
+    }
+
     fn render_paragraph(&mut self, output: &mut String, para: &Paragraph) {
         if para.is_empty() {
             return;
@@ -165,12 +298,36 @@ impl MarkdownRenderer {
             return;
         }
 
+        // Handle definition list items
+        if let Some(term) = para.definition_term() {
+            if self.options.collect_stats {
+                self.stats.add_list_item();
+            }
+            output.push_str(&definition_prefix(&self.options, term));
+            self.render_inline_content(output, &para.content);
+            output.push_str("\n\n");
+            return;
+        }
+
         // Handle list items
-        if let Some(ref list_info) = para.style.list_info {
+        let task = split_task_marker(&self.options, &para.content);
+        if para.style.list_info.is_some() || task.is_some() {
             if self.options.collect_stats {
                 self.stats.add_list_item();
             }
-            self.render_list_item(output, para, list_info);
+            let (checked, content) = match task {
+                Some((checked, content)) => (Some(checked), content),
+                None => (None, para.content.clone()),
+            };
+            let prefix = list_item_prefix(
+                &self.options,
+                &mut self.list_counter,
+                para.style.list_info.as_ref(),
+                checked,
+            );
+            output.push_str(&prefix);
+            self.render_inline_content(output, &content);
+            output.push('\n');
             return;
         }
 
@@ -182,36 +339,6 @@ impl MarkdownRenderer {
         output.push_str("\n\n");
     }
 
-    fn render_list_item(&self, output: &mut String, para: &Paragraph, list_info: &ListInfo) {
-        let indent = "  ".repeat(list_info.level as usize);
-
-        let marker = match &list_info.style {
-            ListStyle::Unordered { marker: _ } => {
-                format!("{}", self.options.list_marker)
-            }
-            ListStyle::Ordered { number_style, .. } => {
-                let num = list_info.item_number.unwrap_or(1);
-                match number_style {
-                    NumberStyle::Decimal => format!("{}.", num),
-                    NumberStyle::LowerAlpha => {
-                        format!("{}.", char::from_u32('a' as u32 + num - 1).unwrap_or('a'))
-                    }
-                    NumberStyle::UpperAlpha => {
-                        format!("{}.", char::from_u32('A' as u32 + num - 1).unwrap_or('A'))
-                    }
-                    NumberStyle::LowerRoman => format!("{}.", to_roman(num).to_lowercase()),
-                    NumberStyle::UpperRoman => format!("{}.", to_roman(num)),
-                }
-            }
-        };
-
-        output.push_str(&indent);
-        output.push_str(&marker);
-        output.push(' ');
-        self.render_inline_content(output, &para.content);
-        output.push('\n');
-    }
-
     fn render_inline_content(&self, output: &mut String, content: &[InlineContent]) {
         for item in content {
             match item {
@@ -307,7 +434,7 @@ impl MarkdownRenderer {
             output.push('|');
             for cell in &row.cells {
                 let content = cell.plain_text().replace('\n', " ");
-                output.push_str(&format!(" {} |", content.trim()));
+                output.push_str(&format!(" {} |", self.escape_cell(content.trim())));
             }
             output.push('\n');
 
@@ -330,6 +457,16 @@ impl MarkdownRenderer {
         output.push('\n');
     }
 
+    /// Markdown table cell text: escaped like body text when escaping is on,
+    /// and pipes escaped regardless since they would split the cell.
+    fn escape_cell(&self, text: &str) -> String {
+        if self.options.escape_special_chars {
+            escape_markdown(text)
+        } else {
+            text.replace('|', "\\|")
+        }
+    }
+
     fn render_table_html(&self, output: &mut String, table: &Table) {
         output.push_str("<table>\n");
 
@@ -367,22 +504,232 @@ impl MarkdownRenderer {
 
             let content = cell.plain_text();
             output.push_str(&format!("<{}{}>", tag, attrs));
-            output.push_str(&content);
+            output.push_str(&escape_html(&content));
             output.push_str(&format!("</{}>", tag));
         }
 
         output.push_str("</tr>\n");
     }
 
-    fn render_image(&self, output: &mut String, _resource_id: &str, alt_text: Option<&str>) {
+    fn render_image(&self, output: &mut String, resource_id: &str, alt_text: Option<&str>) {
+        if self.inline_images.contains_key(resource_id) {
+            let path = format!("{}{}", self.options.image_path_prefix, resource_id);
+            output.push_str(&format!("![{}]({})\n\n", alt_text.unwrap_or(""), path));
+            return;
+        }
         let alt = alt_text.unwrap_or("Image");
         output.push_str(&format!("\n<!-- [{}] -->\n\n", alt));
     }
 }
 
+impl BlockEmitter for MarkdownRenderer {
+    fn paragraph(&mut self, out: &mut String, para: &Paragraph) {
+        self.render_paragraph(out, para);
+    }
+
+    fn table(&mut self, out: &mut String, table: &Table) {
+        if self.options.collect_stats {
+            self.stats.add_table();
+        }
+        self.render_table(out, table);
+    }
+
+    fn image(&mut self, out: &mut String, resource_id: &str, alt_text: Option<&str>) {
+        if self.options.collect_stats {
+            self.stats.add_image();
+        }
+        self.render_image(out, resource_id, alt_text);
+    }
+
+    fn code_block(&mut self, out: &mut String, content: &str, language: Option<&str>) {
+        if self.options.collect_stats {
+            self.stats.add_code_block();
+        }
+        out.push_str(&fenced_code_block(content, language));
+    }
+
+    fn horizontal_rule(&mut self, out: &mut String) {
+        if self.options.collect_stats {
+            self.stats.add_horizontal_rule();
+        }
+        out.push_str("\n---\n\n");
+    }
+
+    fn raw(&mut self, out: &mut String, content: &str) {
+        out.push_str(content);
+        out.push_str("\n\n");
+    }
+}
+
+/// Running ordered-list numbers per nesting level, for
+/// [`ListNumbering::Renumber`]. A non-list block closes the list.
+#[derive(Debug, Default)]
+pub(crate) struct ListCounter {
+    numbers: Vec<Option<u32>>,
+}
+
+impl ListCounter {
+    /// Number of the next item described by `list_info`.
+    fn next(&mut self, list_info: &ListInfo) -> u32 {
+        let level = list_info.level as usize;
+        self.numbers.truncate(level + 1);
+        self.numbers.resize(level + 1, None);
+        match list_info.style {
+            ListStyle::Ordered { start, .. } => {
+                let number = self.numbers[level].map_or(start, |n| n + 1);
+                self.numbers[level] = Some(number);
+                number
+            }
+            ListStyle::Unordered { .. } | ListStyle::Definition { .. } => {
+                self.numbers[level] = None;
+                0
+            }
+        }
+    }
+
+    /// End the current list. Returns whether a list was open.
+    pub(crate) fn close(&mut self) -> bool {
+        let open = !self.numbers.is_empty();
+        self.numbers.clear();
+        open
+    }
+}
+
+/// Whether `block` renders as a list item under `options`. Definition list
+/// items render as paragraphs of their own and do not count.
+pub(crate) fn is_list_block(options: &RenderOptions, block: &Block) -> bool {
+    match block {
+        Block::Paragraph(p) => {
+            p.style.heading_level.is_none()
+                && p.definition_term().is_none()
+                && (p.style.list_info.is_some() || split_task_marker(options, &p.content).is_some())
+        }
+        _ => false,
+    }
+}
+
+/// Indentation, marker and (for task items) checkbox of a list item. A
+/// checkbox paragraph without list info renders as a top-level bullet.
+pub(crate) fn list_item_prefix(
+    options: &RenderOptions,
+    counter: &mut ListCounter,
+    list_info: Option<&ListInfo>,
+    checked: Option<bool>,
+) -> String {
+    let default_info = ListInfo::bullet(0);
+    let list_info = list_info.unwrap_or(&default_info);
+    let counted = counter.next(list_info);
+    let indent = "  ".repeat(list_info.level as usize);
+
+    let marker = match &list_info.style {
+        ListStyle::Unordered { .. } | ListStyle::Definition { .. } => {
+            bullet_for_level(options, list_info.level).to_string()
+        }
+        ListStyle::Ordered { number_style, .. } => {
+            let num = match options.list_numbering {
+                ListNumbering::Preserve => list_info.item_number.unwrap_or(1),
+                ListNumbering::Renumber => counted,
+            };
+            match number_style {
+                NumberStyle::Decimal => format!("{}.", num),
+                NumberStyle::LowerAlpha => {
+                    format!("{}.", char::from_u32('a' as u32 + num - 1).unwrap_or('a'))
+                }
+                NumberStyle::UpperAlpha => {
+                    format!("{}.", char::from_u32('A' as u32 + num - 1).unwrap_or('A'))
+                }
+                NumberStyle::LowerRoman => format!("{}.", to_roman(num).to_lowercase()),
+                NumberStyle::UpperRoman => format!("{}.", to_roman(num)),
+            }
+        }
+    };
+
+    match checked {
+        Some(true) => format!("{}{} [x] ", indent, marker),
+        Some(false) => format!("{}{} [ ] ", indent, marker),
+        None => format!("{}{} ", indent, marker),
+    }
+}
+
+/// Start of a definition list item, up to where the definition begins.
+pub(crate) fn definition_prefix(options: &RenderOptions, term: &str) -> String {
+    let term = if options.escape_special_chars {
+        escape_markdown(term)
+    } else {
+        term.to_string()
+    };
+    match options.definition_style {
+        DefinitionStyle::DefinitionList => format!("{}\n: ", term),
+        DefinitionStyle::BoldTerm => format!("**{}** — ", term),
+    }
+}
+
+/// Bullet for a nesting level, cycling through `-`, `*`, `+` (starting
+/// from `list_marker`) when marker rotation is on.
+fn bullet_for_level(options: &RenderOptions, level: u8) -> char {
+    if !options.rotate_list_markers {
+        return options.list_marker;
+    }
+    let rotation: Vec<char> = std::iter::once(options.list_marker)
+        .chain(
+            ['-', '*', '+']
+                .into_iter()
+                .filter(|c| *c != options.list_marker),
+        )
+        .collect();
+    rotation[level as usize % rotation.len()]
+}
+
+/// Checkbox glyphs that open a task item, with whether they mean "done".
+const TASK_MARKERS: &[(char, bool)] = &[
+    ('☐', false),
+    ('❏', false),
+    ('❑', false),
+    ('☑', true),
+    ('☒', true),
+    ('✅', true),
+];
+
+/// If task lists are enabled and `content` opens with a checkbox glyph
+/// (optionally after a bullet), return whether it is checked and the
+/// content with the glyph removed.
+pub(crate) fn split_task_marker(
+    options: &RenderOptions,
+    content: &[InlineContent],
+) -> Option<(bool, Vec<InlineContent>)> {
+    if !options.task_lists {
+        return None;
+    }
+    let Some(InlineContent::Text(first)) = content.first() else {
+        return None;
+    };
+    let text = first.text.trim_start();
+    let text = text
+        .strip_prefix(['•', '-', '*'])
+        .map_or(text, str::trim_start);
+    let mut chars = text.chars();
+    let glyph = chars.next()?;
+    let &(_, checked) = TASK_MARKERS.iter().find(|(c, _)| *c == glyph)?;
+    let rest = chars.as_str();
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let mut stripped = content.to_vec();
+    if let InlineContent::Text(run) = &mut stripped[0] {
+        run.text = rest.trim_start().to_string();
+    }
+    Some((checked, stripped))
+}
+
 /// Escape special Markdown characters.
 /// Only escape characters that could be misinterpreted as Markdown syntax.
 /// We minimize escaping to improve readability of extracted text.
+/// Collapse whitespace runs to single spaces.
+fn squash(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 fn escape_markdown(text: &str) -> String {
     let mut result = String::with_capacity(text.len());
     for c in text.chars() {
@@ -402,6 +749,35 @@ fn escape_markdown(text: &str) -> String {
     result
 }
 
+/// Escape text for an HTML element body.
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Render a fenced code block, followed by a blank line.
+///
+/// The fence is one backtick longer than the longest backtick run in the code
+/// (minimum three), so listings that themselves contain fences stay intact.
+pub(crate) fn fenced_code_block(content: &str, language: Option<&str>) -> String {
+    let longest_run = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat((longest_run + 1).max(3));
+    format!(
+        "{fence}{}\n{}\n{fence}\n\n",
+        language.unwrap_or(""),
+        content.trim_end_matches('\n')
+    )
+}
+
 /// Convert number to Roman numerals.
 fn to_roman(mut num: u32) -> String {
     let numerals = [
@@ -487,6 +863,25 @@ mod tests {
         assert!(result.contains("title:"));
     }
 
+    #[test]
+    fn test_frontmatter_is_redacted() {
+        use crate::render::{CleanupOptions, PiiRedaction};
+
+        let mut doc = Document::new();
+        doc.metadata.author = Some("jane.doe@example.com".to_string());
+        let mut page = Page::letter(1);
+        page.add_paragraph(Paragraph::with_text("Contact jane.doe@example.com"));
+        doc.add_page(page);
+
+        let cleanup = CleanupOptions::minimal().with_pii_redaction(PiiRedaction::all());
+        let options = RenderOptions::new()
+            .with_frontmatter(true)
+            .with_cleanup(cleanup);
+        let result = to_markdown(&doc, &options).unwrap();
+        assert!(result.contains("author: \"[EMAIL]\""));
+        assert!(!result.contains("jane.doe@"));
+    }
+
     #[test]
     fn test_page_markers_comment_inserted() {
         let mut doc = Document::new();
@@ -566,6 +961,99 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_standard_cleanup_drops_running_heads() {
+        use crate::model::{Outline, OutlineItem};
+        use crate::render::{CleanupOptions, CleanupPreset};
+        let mut doc = Document::new();
+        for number in 1..=3 {
+            let mut page = Page::letter(number);
+            page.add_paragraph(if number == 1 {
+                Paragraph::heading("Installation", 1)
+            } else {
+                Paragraph::with_text(format!("{} Chapter 2 · Installation", number + 10))
+            });
+            page.add_paragraph(Paragraph::with_text(format!("Step {}.", number)));
+            doc.add_page(page);
+        }
+        let mut outline = Outline::new();
+        outline.add_item(OutlineItem::new("Installation", Some(1), 0));
+        doc.outline = Some(outline);
+
+        let minimal = RenderOptions::new().with_minimal_cleanup();
+        let raw = to_markdown(&doc, &minimal).unwrap();
+        assert!(raw.contains("12 Chapter 2 · Installation"), "{}", raw);
+
+        let options =
+            RenderOptions::new().with_cleanup(CleanupOptions::from_preset(CleanupPreset::Standard));
+        let cleaned = to_markdown(&doc, &options).unwrap();
+        assert!(
+            cleaned.starts_with("# Installation\n\nStep 1."),
+            "{}",
+            cleaned
+        );
+        assert!(!cleaned.contains("Chapter 2"), "{}", cleaned);
+        assert!(cleaned.ends_with("Step 3."), "{}", cleaned);
+        let text = crate::render::to_text(&doc, &options).unwrap();
+        assert!(!text.contains("Chapter 2"), "{}", text);
+    }
+
+    #[test]
+    fn test_block_dedup_counts_in_stats() {
+        use crate::model::BlockDedup;
+        let mut doc = Document::new();
+        for number in 1..=3 {
+            let mut page = Page::letter(number);
+            page.add_paragraph(Paragraph::heading("Northwind Sales Kickoff", 1));
+            page.add_paragraph(Paragraph::with_text(format!("Slide {} body.", number)));
+            doc.add_page(page);
+        }
+
+        let plain = to_markdown(&doc, &RenderOptions::new()).unwrap();
+        assert_eq!(plain.matches("Northwind Sales Kickoff").count(), 3);
+
+        let options = RenderOptions::new().with_block_dedup(BlockDedup::new());
+        let result = to_markdown_with_stats(&doc, &options).unwrap();
+        assert_eq!(result.content.matches("Northwind Sales Kickoff").count(), 1);
+        assert!(
+            result.content.ends_with("Slide 3 body."),
+            "{}",
+            result.content
+        );
+        assert_eq!(result.stats.deduplicated_blocks, 2);
+    }
+
+    #[test]
+    fn test_page_headings_survive_cleanup() {
+        use crate::render::{CleanupOptions, CleanupPreset};
+        let mut doc = Document::new();
+        for i in 1..=2u32 {
+            let mut page = Page::letter(i);
+            page.add_paragraph(Paragraph::with_text(format!("Body of page {}", i)));
+            doc.add_page(page);
+        }
+
+        // "— 2 —" 는 쪽 번호 줄로, "Page 2" 는 다음 줄과 합쳐질 줄로 보일 수 있다.
+        for template in ["## Page {n}", "— {n} —", "Page {n}"] {
+            let options = RenderOptions::new()
+                .with_page_heading_template(template)
+                .with_cleanup(CleanupOptions::from_preset(CleanupPreset::Aggressive));
+            let result = to_markdown(&doc, &options).unwrap();
+            let first = template.replace("{n}", "1");
+            let second = template.replace("{n}", "2");
+            assert!(
+                result.starts_with(&format!("{}\n\nBody of page 1", first)),
+                "{}",
+                result
+            );
+            assert!(
+                result.contains(&format!("\n\n{}\n\nBody of page 2", second)),
+                "{}",
+                result
+            );
+        }
+    }
+
     #[test]
     fn test_page_markers_respect_page_selection() {
         let mut doc = Document::new();
@@ -623,4 +1111,189 @@ mod tests {
             "marker must appear after frontmatter"
         );
     }
+
+    #[test]
+    fn test_table_cells_escaped_per_format() {
+        use crate::model::{Block, TableCell, TableRow};
+        use crate::render::TableFallback;
+
+        let mut table = Table::new();
+        table.add_row(TableRow::new(vec![
+            TableCell::text("a | b"),
+            TableCell::text("x < y"),
+        ]));
+        let mut doc = Document::new();
+        let mut page = Page::letter(1);
+        page.add_block(Block::Table(table.clone()));
+        doc.add_page(page);
+
+        let result = to_markdown(&doc, &RenderOptions::default()).unwrap();
+        assert!(
+            result.contains("| a \\| b |"),
+            "pipe must be escaped:\n{}",
+            result
+        );
+
+        table.add_row(TableRow::new(vec![TableCell::text("M & N").colspan(2)]));
+        doc.pages[0].elements = vec![Block::Table(table)];
+        let options = RenderOptions::default().with_table_fallback(TableFallback::Html);
+        let result = to_markdown(&doc, &options).unwrap();
+        assert!(result.contains("<td>x &lt; y</td>"), "{}", result);
+        assert!(result.contains("M &amp; N</td>"), "{}", result);
+    }
+
+    #[test]
+    fn test_list_renumbering_and_marker_rotation() {
+        let item = |text: &str, info: ListInfo| {
+            let mut p = Paragraph::with_text(text);
+            p.style.list_info = Some(info);
+            p
+        };
+        let mut page = Page::letter(1);
+        page.add_paragraph(item("First", ListInfo::numbered(0, 3)));
+        page.add_paragraph(item("Nested", ListInfo::bullet(1)));
+        page.add_paragraph(item("Second", ListInfo::numbered(0, 7)));
+        page.add_paragraph(Paragraph::with_text("After the list."));
+        let mut doc = Document::new();
+        doc.add_page(page);
+
+        let mut options = RenderOptions::new();
+        options.cleanup = None;
+        let preserved = to_markdown(&doc, &options).unwrap();
+        assert_eq!(
+            preserved,
+            "3. First\n  - Nested\n7. Second\n\nAfter the list."
+        );
+
+        let options = options
+            .with_list_numbering(ListNumbering::Renumber)
+            .with_rotated_list_markers(true);
+        let renumbered = to_markdown(&doc, &options).unwrap();
+        assert_eq!(
+            renumbered,
+            "1. First\n  * Nested\n2. Second\n\nAfter the list."
+        );
+    }
+
+    #[test]
+    fn test_checkbox_paragraphs_become_task_items() {
+        let mut page = Page::letter(1);
+        page.add_paragraph(Paragraph::with_text("☐ Draft report"));
+        page.add_paragraph(Paragraph::with_text("• ☑ Send invoice"));
+        page.add_paragraph(Paragraph::with_text("☐not a checkbox"));
+        let mut doc = Document::new();
+        doc.add_page(page);
+
+        let mut options = RenderOptions::new();
+        options.cleanup = None;
+        let plain = to_markdown(&doc, &options).unwrap();
+        assert!(plain.starts_with("☐ Draft report"));
+
+        let tasks = to_markdown(&doc, &options.with_task_lists(true)).unwrap();
+        assert_eq!(
+            tasks,
+            "- [ ] Draft report\n- [x] Send invoice\n\n☐not a checkbox"
+        );
+    }
+
+    #[test]
+    fn test_block_filter_prunes_output() {
+        let mut page = Page::letter(1);
+        page.add_paragraph(Paragraph::heading("Results", 1));
+        page.add_paragraph(Paragraph::with_text("Some prose."));
+        let mut table = Table::new();
+        table.add_row(crate::model::TableRow::from_strings(["a", "b"]));
+        page.add_block(Block::Table(table));
+        page.add_block(Block::image("img1"));
+        let mut doc = Document::new();
+        doc.add_page(page);
+
+        let headings = to_markdown(&doc, &RenderOptions::new().only_headings()).unwrap();
+        assert_eq!(headings, "# Results");
+
+        let tables = to_markdown(&doc, &RenderOptions::new().only_tables()).unwrap();
+        assert!(tables.contains("| a | b |"));
+        assert!(!tables.contains("Results") && !tables.contains("prose"));
+
+        let no_images = to_markdown(&doc, &RenderOptions::new().skip_images()).unwrap();
+        assert!(no_images.contains("Some prose.") && !no_images.contains("img1"));
+    }
+
+    #[test]
+    fn test_small_images_inlined_as_data_uris() {
+        use crate::model::Resource;
+
+        let mut page = Page::letter(1);
+        page.add_block(Block::image("logo.png"));
+        page.add_block(Block::image("photo.jpg"));
+        let mut doc = Document::new();
+        doc.add_page(page);
+        doc.add_resource("logo".into(), Resource::png(b"foo".to_vec()));
+        doc.add_resource("photo".into(), Resource::jpeg(vec![0xFF; 64]));
+
+        let options = RenderOptions::new()
+            .with_image_prefix("images/")
+            .with_inline_images_max_bytes(32);
+        let md = to_markdown(&doc, &options).unwrap();
+        assert!(md.contains("![](data:image/png;base64,Zm9v)"), "{md}");
+        // 기준보다 큰 이미지는 예전처럼 주석으로 남는다.
+        assert!(!md.contains("data:image/jpeg"), "{md}");
+        assert!(md.contains("<!-- [Image] -->"), "{md}");
+
+        let default = to_markdown(&doc, &RenderOptions::new()).unwrap();
+        assert!(!default.contains("data:"), "{default}");
+    }
+    #[test]
+    fn test_annotations_as_blockquotes_and_footnotes() {
+        let note = |kind, covered: Option<&str>, contents: &str| crate::model::Annotation {
+            kind,
+            author: Some("Dana".to_string()),
+            modified: None,
+            contents: Some(contents.to_string()),
+            covered_text: covered.map(str::to_string),
+            rect: [0.0; 4],
+        };
+        let mut page = Page::letter(1);
+        page.add_paragraph(Paragraph::with_text("Revenue grew 12% this year."));
+        page.add_paragraph(Paragraph::with_text("Costs were flat."));
+        page.annotations = vec![
+            note(
+                crate::model::AnnotationKind::Highlight,
+                Some("grew 12%"),
+                "Source?",
+            ),
+            note(crate::model::AnnotationKind::Note, None, "Looks good"),
+        ];
+        let mut doc = Document::new();
+        doc.add_page(page);
+
+        let plain = to_markdown(&doc, &RenderOptions::new()).unwrap();
+        assert!(!plain.contains("Dana"), "{}", plain);
+
+        let quoted = to_markdown(
+            &doc,
+            &RenderOptions::new().with_annotations(AnnotationStyle::Blockquote),
+        )
+        .unwrap();
+        assert!(
+            quoted.ends_with(
+                "Costs were flat.\n\n> **Highlight** (Dana): “grew 12%” — Source?\n\n\
+                 > **Note** (Dana): Looks good"
+            ),
+            "{}",
+            quoted
+        );
+
+        let footnoted = to_markdown(
+            &doc,
+            &RenderOptions::new().with_annotations(AnnotationStyle::Footnote),
+        )
+        .unwrap();
+        assert_eq!(
+            footnoted,
+            "Revenue grew 12% this year.[^note-1]\n\nCosts were flat.[^note-2]\n\n\
+             [^note-1]: **Highlight** (Dana): “grew 12%” — Source?\n\
+             [^note-2]: **Note** (Dana): Looks good"
+        );
+    }
 }
@@ -0,0 +1,505 @@
+//! reStructuredText and AsciiDoc rendering.
+//!
+//! Both formats walk the document exactly as Markdown does — page selection,
+//! block and language filters, then [`emit_block`] for each block — and
+//! differ only in syntax, which [`Dialect`] selects. Tables are written in
+//! each format's native structure (`list-table` and `|===`) rather than
+//! converted from Markdown pipes, so multi-line and merged cells survive.
+
+use std::sync::Arc;
+
+use unicode_width::UnicodeWidthStr;
+
+use super::bounded::{bound_output, MarkerSyntax};
+use super::cleanup::CleanupPipeline;
+use super::emitter::{emit_block, BlockEmitter};
+use super::options::RenderOptions;
+use crate::error::Result;
+use crate::model::{Document, InlineContent, ListStyle, Paragraph, Table, TextStyle};
+
+/// Convert a document to reStructuredText, as Sphinx and docutils read it.
+pub fn to_rst(doc: &Document, options: &RenderOptions) -> Result<String> {
+    Ok(render(doc, options, Dialect::Rst))
+}
+
+/// Convert a document to AsciiDoc, as Asciidoctor and Antora read it.
+pub fn to_asciidoc(doc: &Document, options: &RenderOptions) -> Result<String> {
+    Ok(render(doc, options, Dialect::AsciiDoc))
+}
+
+fn render(doc: &Document, options: &RenderOptions, dialect: Dialect) -> String {
+    let mut renderer = MarkupRenderer::new(options, dialect);
+    let mut output = String::new();
+    for page in &options.prepare_document(doc).0.pages {
+        if !options.page_selection.includes(page.number) {
+            continue;
+        }
+        for block in page.elements.iter().filter(|b| options.keeps(b)) {
+            emit_block(&mut renderer, &mut output, block);
+        }
+    }
+    let output = output.trim().to_string();
+    let syntax = match dialect {
+        Dialect::Rst => MarkerSyntax::RstComment,
+        Dialect::AsciiDoc => MarkerSyntax::AsciiDocComment,
+    };
+    bound_output(output, options, syntax).swap_remove(0)
+}
+
+/// Target markup language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dialect {
+    Rst,
+    AsciiDoc,
+}
+
+/// RST section underline characters, by heading level.
+const RST_UNDERLINES: [char; 6] = ['=', '-', '~', '^', '"', '\''];
+
+struct MarkupRenderer<'a> {
+    options: &'a RenderOptions,
+    dialect: Dialect,
+    cleanup: Option<Arc<CleanupPipeline>>,
+    /// Nesting level of the list item written last, while inside a list.
+    list_level: Option<usize>,
+}
+
+impl<'a> MarkupRenderer<'a> {
+    fn new(options: &'a RenderOptions, dialect: Dialect) -> Self {
+        Self {
+            options,
+            dialect,
+            cleanup: options
+                .cleanup
+                .clone()
+                .map(|cleanup| Arc::new(CleanupPipeline::new(cleanup))),
+            list_level: None,
+        }
+    }
+
+    /// `text` cleaned when cleanup is configured, keeping the whitespace at
+    /// its edges that separates it from neighbouring runs.
+    fn clean(&self, text: &str) -> String {
+        let Some(pipeline) = &self.cleanup else {
+            return text.to_string();
+        };
+        let cleaned = pipeline.process(text);
+        if cleaned.is_empty() {
+            return cleaned;
+        }
+        let lead = if text.starts_with(char::is_whitespace) {
+            " "
+        } else {
+            ""
+        };
+        let trail = if text.ends_with(char::is_whitespace) {
+            " "
+        } else {
+            ""
+        };
+        format!("{}{}{}", lead, cleaned, trail)
+    }
+
+    fn escape(&self, text: &str) -> String {
+        if !self.options.escape_special_chars {
+            return text.to_string();
+        }
+        match self.dialect {
+            Dialect::Rst => escape_rst(text),
+            Dialect::AsciiDoc => escape_asciidoc(text),
+        }
+    }
+
+    /// Inline content on one line, with styles and links in the dialect's
+    /// syntax.
+    fn inline(&self, content: &[InlineContent]) -> String {
+        let mut out = String::new();
+        for item in content {
+            match item {
+                InlineContent::Text(run) => {
+                    let text = self.clean(&run.text);
+                    // 앞뒤 공백은 강조 표시 밖에 둬야 마크업으로 인식된다.
+                    let body = text.trim();
+                    if body.is_empty() {
+                        out.push_str(&text);
+                        continue;
+                    }
+                    if text.starts_with(' ') {
+                        out.push(' ');
+                    }
+                    out.push_str(&self.styled(&self.escape(body), &run.style));
+                    if text.ends_with(' ') {
+                        out.push(' ');
+                    }
+                }
+                InlineContent::LineBreak => match self.dialect {
+                    Dialect::AsciiDoc if self.options.preserve_line_breaks => out.push_str(" +\n"),
+                    _ => out.push(' '),
+                },
+                InlineContent::Link { text, url, .. } => match self.dialect {
+                    Dialect::Rst => out.push_str(&format!("`{} <{}>`__", text, url)),
+                    Dialect::AsciiDoc => out.push_str(&format!("link:{}[{}]", url, text)),
+                },
+                InlineContent::Image { alt_text, .. } => {
+                    out.push_str(&self.escape(alt_text.as_deref().unwrap_or("")));
+                }
+            }
+        }
+        out.trim().to_string()
+    }
+
+    fn styled(&self, text: &str, style: &TextStyle) -> String {
+        match self.dialect {
+            // RST 은 강조를 중첩할 수 없으므로 굵게를 우선한다.
+            Dialect::Rst if style.bold => format!("**{}**", text),
+            Dialect::Rst if style.italic => format!("*{}*", text),
+            Dialect::Rst => text.to_string(),
+            Dialect::AsciiDoc => {
+                let mut result = text.to_string();
+                if style.italic {
+                    result = format!("__{}__", result);
+                }
+                if style.bold {
+                    result = format!("**{}**", result);
+                }
+                result
+            }
+        }
+    }
+
+    fn heading(&self, out: &mut String, level: u8, text: &str) {
+        let level = level.clamp(1, 6) as usize;
+        match self.dialect {
+            Dialect::Rst => {
+                let underline = RST_UNDERLINES[level - 1]
+                    .to_string()
+                    .repeat(display_width(text));
+                out.push_str(&format!("{}\n{}\n\n", text, underline));
+            }
+            // 레벨 0(`=`)은 문서 제목이므로 섹션은 `==` 부터 시작한다.
+            Dialect::AsciiDoc => {
+                out.push_str(&format!("{} {}\n\n", "=".repeat((level + 1).min(6)), text));
+            }
+        }
+    }
+
+    fn list_item(&mut self, out: &mut String, para: &Paragraph, text: &str) -> bool {
+        let Some(info) = &para.style.list_info else {
+            return false;
+        };
+        let level = info.level as usize;
+        if matches!(info.style, ListStyle::Definition { .. }) {
+            self.end_list(out);
+        }
+        match (&info.style, self.dialect) {
+            (ListStyle::Definition { term }, Dialect::Rst) => {
+                out.push_str(&format!("{}\n   {}\n\n", self.escape(term), text));
+            }
+            (ListStyle::Definition { term }, Dialect::AsciiDoc) => {
+                out.push_str(&format!("{}:: {}\n\n", self.escape(term), text));
+            }
+            (ListStyle::Unordered { .. }, Dialect::Rst) => {
+                self.rst_item(out, level, "- ", text);
+            }
+            (ListStyle::Ordered { .. }, Dialect::Rst) => {
+                let marker = format!("{}. ", info.item_number.unwrap_or(1));
+                self.rst_item(out, level, &marker, text);
+            }
+            (ListStyle::Unordered { .. }, Dialect::AsciiDoc) => {
+                out.push_str(&format!("{} {}\n", "*".repeat(level + 1), text));
+            }
+            (ListStyle::Ordered { .. }, Dialect::AsciiDoc) => {
+                out.push_str(&format!("{} {}\n", ".".repeat(level + 1), text));
+            }
+        }
+        if !matches!(info.style, ListStyle::Definition { .. }) {
+            self.list_level = Some(level);
+        }
+        true
+    }
+
+    /// An RST bullet or enumerated item. Nested lists are indented to the
+    /// parent's text and need a blank line on either side.
+    fn rst_item(&self, out: &mut String, level: usize, marker: &str, text: &str) {
+        if self.list_level.is_some_and(|previous| previous != level) {
+            out.push('\n');
+        }
+        out.push_str(&format!("{}{}{}\n", "   ".repeat(level), marker, text));
+    }
+
+    /// Close the list written last, if any, with a blank line.
+    fn end_list(&mut self, out: &mut String) {
+        if self.list_level.take().is_some() && !out.ends_with("\n\n") {
+            out.push('\n');
+        }
+    }
+
+    fn cell_text(&self, cell: &crate::model::TableCell) -> String {
+        let text = cell.plain_text();
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.escape(&self.clean(&text))
+    }
+
+    fn rst_table(&self, out: &mut String, table: &Table) {
+        // list-table 은 셀 폭을 맞출 필요가 없어 여러 줄 셀도 깨지지 않는다.
+        let columns = table.column_count();
+        out.push_str(".. list-table::\n");
+        if table.header_rows > 0 {
+            out.push_str(&format!("   :header-rows: {}\n", table.header_rows));
+        }
+        out.push('\n');
+        for row in table.text_grid() {
+            let padding = std::iter::repeat(String::new());
+            for (i, cell) in row.into_iter().chain(padding).take(columns).enumerate() {
+                let text = cell.split_whitespace().collect::<Vec<_>>().join(" ");
+                let text = self.escape(&self.clean(&text));
+                let marker = if i == 0 { "* -" } else { "  -" };
+                if text.is_empty() {
+                    out.push_str(&format!("   {}\n", marker));
+                } else {
+                    out.push_str(&format!("   {} {}\n", marker, text));
+                }
+            }
+        }
+        out.push('\n');
+    }
+
+    fn asciidoc_table(&self, out: &mut String, table: &Table) {
+        let mut attrs = format!("cols=\"{}*\"", table.column_count());
+        if table.header_rows > 0 {
+            attrs.insert_str(0, "%header,");
+        }
+        out.push_str(&format!("[{}]\n|===\n", attrs));
+        for row in &table.rows {
+            let cells: Vec<String> = row
+                .cells
+                .iter()
+                .map(|cell| {
+                    let span = match (cell.colspan, cell.rowspan) {
+                        (1, 1) => String::new(),
+                        (c, 1) => format!("{}+", c),
+                        (1, r) => format!(".{}+", r),
+                        (c, r) => format!("{}.{}+", c, r),
+                    };
+                    let text = self.cell_text(cell).replace('|', "\\|");
+                    format!("{}|{}", span, text).trim_end().to_string()
+                })
+                .collect();
+            out.push_str(&cells.join(" "));
+            out.push('\n');
+        }
+        out.push_str("|===\n\n");
+    }
+}
+
+impl BlockEmitter for MarkupRenderer<'_> {
+    fn paragraph(&mut self, out: &mut String, para: &Paragraph) {
+        let text = self.inline(&para.content);
+        if text.is_empty() {
+            return;
+        }
+        if let Some(level) = para.heading_level() {
+            self.end_list(out);
+            self.heading(out, level, &text);
+            return;
+        }
+        if !self.list_item(out, para, &text) {
+            self.end_list(out);
+            out.push_str(&text);
+            out.push_str("\n\n");
+        }
+    }
+
+    fn table(&mut self, out: &mut String, table: &Table) {
+        if table.is_empty() || table.column_count() == 0 {
+            return;
+        }
+        self.end_list(out);
+        match self.dialect {
+            Dialect::Rst => self.rst_table(out, table),
+            Dialect::AsciiDoc => self.asciidoc_table(out, table),
+        }
+    }
+
+    fn image(&mut self, out: &mut String, resource_id: &str, alt_text: Option<&str>) {
+        self.end_list(out);
+        let path = format!("{}{}", self.options.image_path_prefix, resource_id);
+        let alt = alt_text.unwrap_or("");
+        match self.dialect {
+            Dialect::Rst if alt.is_empty() => out.push_str(&format!(".. image:: {}\n\n", path)),
+            Dialect::Rst => {
+                out.push_str(&format!(".. image:: {}\n   :alt: {}\n\n", path, alt));
+            }
+            Dialect::AsciiDoc => {
+                out.push_str(&format!("image::{}[{}]\n\n", path, alt.replace(']', "\\]")));
+            }
+        }
+    }
+
+    fn code_block(&mut self, out: &mut String, content: &str, language: Option<&str>) {
+        let content = content.trim_end_matches('\n');
+        if content.trim().is_empty() {
+            return;
+        }
+        self.end_list(out);
+        match self.dialect {
+            Dialect::Rst => {
+                match language {
+                    Some(lang) => out.push_str(&format!(".. code-block:: {}\n\n", lang)),
+                    None => out.push_str("::\n\n"),
+                }
+                for line in content.lines() {
+                    if line.is_empty() {
+                        out.push('\n');
+                    } else {
+                        out.push_str(&format!("   {}\n", line));
+                    }
+                }
+                out.push('\n');
+            }
+            Dialect::AsciiDoc => {
+                if let Some(lang) = language {
+                    out.push_str(&format!("[source,{}]\n", lang));
+                }
+                // 본문에 `----` 줄이 있으면 그보다 긴 구분선을 쓴다.
+                let longest = content
+                    .lines()
+                    .filter(|l| !l.is_empty() && l.chars().all(|c| c == '-'))
+                    .map(str::len)
+                    .max()
+                    .unwrap_or(0);
+                let delimiter = "-".repeat(longest.max(3) + 1);
+                out.push_str(&format!("{}\n{}\n{}\n\n", delimiter, content, delimiter));
+            }
+        }
+    }
+
+    fn horizontal_rule(&mut self, out: &mut String) {
+        self.end_list(out);
+        match self.dialect {
+            Dialect::Rst => out.push_str("----\n\n"),
+            Dialect::AsciiDoc => out.push_str("'''\n\n"),
+        }
+    }
+
+    fn raw(&mut self, out: &mut String, content: &str) {
+        let text = self.escape(self.clean(content).trim());
+        if !text.is_empty() {
+            self.end_list(out);
+            out.push_str(&text);
+            out.push_str("\n\n");
+        }
+    }
+}
+
+/// Columns `text` takes in a monospace font, as docutils measures an RST
+/// underline: East Asian wide characters and emoji count two.
+fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Backslash-escape RST inline markup characters.
+fn escape_rst(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '`' | '_' | '|') {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Replace characters that open AsciiDoc formatting with attribute
+/// references, which Asciidoctor prints literally without leaving a stray
+/// backslash where no formatting would have applied.
+fn escape_asciidoc(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '*' => result.push_str("{asterisk}"),
+            '`' => result.push_str("{backtick}"),
+            '|' => result.push_str("{vbar}"),
+            '+' => result.push_str("{plus}"),
+            '^' => result.push_str("{caret}"),
+            '~' => result.push_str("{tilde}"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Block, ListInfo, Page, TableCell, TableRow, TextRun};
+
+    fn sample() -> Document {
+        let mut doc = Document::new();
+        let mut page = Page::a4(1);
+        page.add_paragraph(Paragraph::heading("Overview", 1));
+        let mut para = Paragraph::with_text("Uses ");
+        para.add_run(TextRun::bold("fast"));
+        para.add_text(" paths.");
+        page.add_paragraph(para);
+        for (text, level) in [("First", 0), ("Nested", 1)] {
+            let mut item = Paragraph::with_text(text);
+            item.style.list_info = Some(ListInfo::bullet(level));
+            page.add_paragraph(item);
+        }
+        page.add_paragraph(Paragraph::with_text("After the list."));
+        let mut table = Table::new();
+        table.add_row(TableRow::header(vec![
+            TableCell::text("Key"),
+            TableCell::text("Value"),
+        ]));
+        table.add_row(TableRow::new(vec![
+            TableCell::text("a"),
+            TableCell::text("one\ntwo"),
+        ]));
+        table.header_rows = 1;
+        page.add_block(Block::Table(table));
+        page.add_block(Block::CodeBlock {
+            language: Some("rust".into()),
+            content: "fn main() {}".into(),
+        });
+        doc.add_page(page);
+        doc
+    }
+
+    #[test]
+    fn test_rst() {
+        let rst = to_rst(&sample(), &RenderOptions::default()).unwrap();
+        assert!(rst.starts_with("Overview\n========\n\nUses **fast** paths.\n\n"));
+        assert!(rst.contains("- First\n\n   - Nested\n\nAfter the list."));
+        assert!(rst.contains(".. list-table::\n   :header-rows: 1\n\n   * - Key\n     - Value\n"));
+        assert!(rst.contains("   * - a\n     - one two\n"));
+        assert!(rst.contains(".. code-block:: rust\n\n   fn main() {}"));
+    }
+
+    #[test]
+    fn test_asciidoc() {
+        let adoc = to_asciidoc(&sample(), &RenderOptions::default()).unwrap();
+        assert!(adoc.starts_with("== Overview\n\nUses **fast** paths.\n\n"));
+        assert!(adoc.contains("* First\n** Nested\n\nAfter the list."));
+        assert!(adoc.contains("[%header,cols=\"2*\"]\n|===\n|Key |Value\n|a |one two\n|===\n"));
+        assert!(adoc.contains("[source,rust]\n----\nfn main() {}\n----"));
+    }
+
+    #[test]
+    fn test_asciidoc_spans() {
+        let mut table = Table::new();
+        let mut wide = TableCell::text("Total");
+        wide.colspan = 2;
+        table.add_row(TableRow::new(vec![wide]));
+        table.add_row(TableRow::new(vec![
+            TableCell::text("x"),
+            TableCell::text("y"),
+        ]));
+        let options = RenderOptions::default();
+        let renderer = MarkupRenderer::new(&options, Dialect::AsciiDoc);
+        let mut out = String::new();
+        renderer.asciidoc_table(&mut out, &table);
+        assert!(out.contains("2+|Total\n|x |y\n"));
+    }
+}
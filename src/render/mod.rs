@@ -1,19 +1,42 @@
 //! Rendering module for converting documents to various output formats.
 
+mod bounded;
 mod cleanup;
+mod emitter;
+#[cfg(feature = "image-convert")]
+mod images;
 mod json;
 mod markdown;
+mod markup;
+mod mojibake;
+mod naming;
 mod options;
 mod result;
+mod segment;
 pub mod streaming;
 mod text;
 pub mod visitor;
+mod warnings;
 
-pub use cleanup::{CleanupOptions, CleanupPipeline, CleanupPreset};
-pub use json::{to_json, JsonFormat};
-pub use markdown::{to_markdown, to_markdown_with_stats, MarkdownRenderer};
-pub use options::{HeadingConfig, PageMarkerStyle, PageSelection, RenderOptions, TableFallback};
+pub(crate) use cleanup::sanitize_metadata_value;
+pub use cleanup::{
+    CleanupContext, CleanupOptions, CleanupPipeline, CleanupPreset, CleanupReport, CleanupStage,
+    DigitNormalization, NormalizationForm, PiiRedaction, RedactionRule, StagePoint,
+};
+#[cfg(feature = "image-convert")]
+pub use images::{ImageConversion, ImageFormat};
+pub use json::{schema, to_json, to_json_with_options, JsonFormat, JSON_SCHEMA};
+pub use markdown::{to_markdown, to_markdown_parts, to_markdown_with_stats, MarkdownRenderer};
+pub use markup::{to_asciidoc, to_rst};
+pub use mojibake::{mojibake_score, MojibakeReport};
+pub use naming::{ResourceNaming, SafeFileNames};
+pub use options::{
+    AnnotationStyle, DefinitionStyle, HeadingConfig, HeadingSignals, LevelNormalization,
+    ListNumbering, OverflowStrategy, PageMarkerStyle, PageSelection, RenderOptions, TableFallback,
+    TextLayout,
+};
 pub use result::{ExtractionStats, RenderResult};
 pub use streaming::{collect_content, RenderEvent, StreamingRenderer};
-pub use text::to_text;
+pub(crate) use text::to_text_with_pipeline;
+pub use text::{to_single_line_text, to_text, to_text_parts};
 pub use visitor::{CompositeVisitor, DefaultVisitor, DocumentVisitor, VisitorAction};
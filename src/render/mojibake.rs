@@ -0,0 +1,372 @@
+//! Mojibake detection and repair for cleanup's `detect_mojibake` option.
+//!
+//! Text from a font without a usable `ToUnicode` map often comes out as its
+//! raw code bytes read through WinAnsiEncoding: UTF-8 becomes "CafÃ©" and
+//! CP949 Korean becomes "ÇÑ±Û". Such spans are long runs of Latin-1 and
+//! Windows-1252 characters that real Latin text rarely has. Each run is
+//! turned back into bytes and decoded as UTF-8, CP949, Shift_JIS and GBK;
+//! the first decoding that yields clean text in its own script replaces the
+//! run. Other damage (Private Use Area and U+FFFD clusters, Hangul jamo
+//! that do not form syllables) cannot be undone and is only counted.
+
+use std::collections::BTreeMap;
+
+use encoding_rs::{Encoding, EUC_KR, GBK, SHIFT_JIS};
+use unicode_normalization::UnicodeNormalization;
+
+/// High characters a run needs before it is decoded as UTF-8. Two is one
+/// accented letter ("Ã©"), which Latin text cannot produce as valid UTF-8.
+const MIN_UTF8_RUN: usize = 2;
+
+/// High characters a run needs before it is decoded as a double-byte
+/// encoding: two CJK characters. Shorter runs ("öß") decode by accident.
+const MIN_CJK_RUN: usize = 4;
+
+/// Share of a line's visible characters that, once PUA or U+FFFD, marks
+/// the line as undecodable.
+const DAMAGED_LINE_RATIO: f32 = 1.0 / 3.0;
+
+/// What mojibake repair found in a text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MojibakeReport {
+    /// Spans re-decoded, keyed by the encoding that decoded them
+    /// (`UTF-8`, `CP949`, `Shift_JIS`, `GBK`) or `Hangul jamo` for
+    /// decomposed syllables that were recomposed
+    pub repairs: BTreeMap<String, u32>,
+
+    /// Spans and lines detected as mojibake that no decoding fixed
+    pub unrepaired: u32,
+}
+
+impl MojibakeReport {
+    fn repaired(&mut self, label: &str) {
+        *self.repairs.entry(label.to_string()).or_default() += 1;
+    }
+
+    /// Add another report's counts to this one.
+    pub fn merge(&mut self, other: &MojibakeReport) {
+        for (label, count) in &other.repairs {
+            *self.repairs.entry(label.clone()).or_default() += count;
+        }
+        self.unrepaired += other.unrepaired;
+    }
+
+    /// Total number of repaired spans.
+    pub fn repair_count(&self) -> u32 {
+        self.repairs.values().sum()
+    }
+}
+
+/// How much of `text` looks like mojibake, from 0.0 (none) to 1.0: the
+/// share of its visible characters that sit in a suspicious Latin-1 run,
+/// are Private Use Area or U+FFFD, or are Hangul jamo that do not compose
+/// into syllables.
+pub fn mojibake_score(text: &str) -> f32 {
+    let visible = text.chars().filter(|c| !c.is_whitespace()).count();
+    if visible == 0 {
+        return 0.0;
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let mut suspicious = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some(end) = high_run(&chars, i) {
+            let run: String = chars[i..end].iter().collect();
+            if count_high(&chars[i..end]) >= MIN_CJK_RUN || decode_utf8(&run).is_some() {
+                suspicious += end - i;
+            }
+            i = end;
+        } else if is_conjoining_jamo(chars[i]) {
+            let end = jamo_run(&chars, i);
+            let composed: String = chars[i..end].iter().copied().nfc().collect();
+            suspicious += composed.chars().filter(|&c| is_conjoining_jamo(c)).count();
+            i = end;
+        } else {
+            suspicious += usize::from(is_damaged(chars[i]));
+            i += 1;
+        }
+    }
+    (suspicious as f32 / visible as f32).min(1.0)
+}
+
+/// Repair the mojibake in `text` that can be decoded, counting what was
+/// repaired and what was left in `report`.
+pub(crate) fn repair_mojibake(text: &str, report: &mut MojibakeReport) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let hint = ScriptHint::of(&chars);
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some(end) = high_run(&chars, i) {
+            let run: String = chars[i..end].iter().collect();
+            match redecode(&run, count_high(&chars[i..end]), hint) {
+                Some((decoded, label)) => {
+                    out.push_str(&decoded);
+                    report.repaired(label);
+                }
+                None => {
+                    report.unrepaired += u32::from(count_high(&chars[i..end]) >= MIN_CJK_RUN);
+                    out.push_str(&run);
+                }
+            }
+            i = end;
+        } else if is_conjoining_jamo(chars[i]) {
+            let end = jamo_run(&chars, i);
+            let run: String = chars[i..end].iter().collect();
+            let composed: String = run.nfc().collect();
+            if composed.chars().any(is_conjoining_jamo) {
+                report.unrepaired += 1;
+            } else if composed != run {
+                report.repaired("Hangul jamo");
+            }
+            out.push_str(&composed);
+            i = end;
+        } else {
+            out.push(chars[i]);
+            i += 1;
+        }
+    }
+    report.unrepaired += out.lines().filter(|line| is_damaged_line(line)).count() as u32;
+    out
+}
+
+/// Script the rest of the text is written in, used to pick between
+/// encodings that can all decode the same bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScriptHint {
+    Korean,
+    Japanese,
+    Chinese,
+    Unknown,
+}
+
+impl ScriptHint {
+    fn of(chars: &[char]) -> Self {
+        let count = |f: fn(char) -> bool| chars.iter().filter(|&&c| f(c)).count();
+        let (hangul, kana, han) = (count(is_hangul), count(is_kana), count(is_han));
+        if hangul > 0 && hangul >= kana && hangul >= han {
+            ScriptHint::Korean
+        } else if kana > 0 {
+            ScriptHint::Japanese
+        } else if han > 0 {
+            ScriptHint::Chinese
+        } else {
+            ScriptHint::Unknown
+        }
+    }
+
+    /// Double-byte encodings to try, most likely first.
+    fn candidates(self) -> [Candidate; 3] {
+        const CP949: Candidate = (EUC_KR, "CP949", is_clean_korean);
+        const SJIS: Candidate = (SHIFT_JIS, "Shift_JIS", is_clean_japanese);
+        const GB: Candidate = (GBK, "GBK", is_clean_chinese);
+        match self {
+            ScriptHint::Japanese => [SJIS, CP949, GB],
+            ScriptHint::Chinese => [GB, SJIS, CP949],
+            ScriptHint::Korean | ScriptHint::Unknown => [CP949, SJIS, GB],
+        }
+    }
+}
+
+/// An encoding, its label in [`MojibakeReport::repairs`], and a check that
+/// decoded text is plausible for it.
+type Candidate = (&'static Encoding, &'static str, fn(&str) -> bool);
+
+/// Decode `run` as the first encoding that gives clean text.
+fn redecode(run: &str, high: usize, hint: ScriptHint) -> Option<(String, &'static str)> {
+    if high >= MIN_UTF8_RUN {
+        if let Some(decoded) = decode_utf8(run) {
+            return Some((decoded, "UTF-8"));
+        }
+    }
+    if high < MIN_CJK_RUN {
+        return None;
+    }
+    let bytes = code_bytes(run)?;
+    hint.candidates()
+        .into_iter()
+        .find_map(|(encoding, label, is_clean)| {
+            let (decoded, had_errors) = encoding.decode_without_bom_handling(&bytes);
+            (!had_errors && is_clean(&decoded)).then(|| (decoded.into_owned(), label))
+        })
+}
+
+/// `run` decoded as UTF-8, when it is valid and not plain ASCII.
+fn decode_utf8(run: &str) -> Option<String> {
+    let decoded = String::from_utf8(code_bytes(run)?).ok()?;
+    (!decoded.is_ascii() && !decoded.chars().any(|c| c.is_control() || is_damaged(c)))
+        .then_some(decoded)
+}
+
+/// The end of the run of mojibake candidates starting at `start`, if one
+/// starts there: high characters, and ASCII letters and symbols right after
+/// one, which double-byte encodings use as trail bytes.
+fn high_run(chars: &[char], start: usize) -> Option<usize> {
+    code_byte(chars[start]).filter(|&b| b >= 0x80)?;
+    let mut end = start + 1;
+    while end < chars.len() {
+        let high = code_byte(chars[end]).is_some_and(|b| b >= 0x80);
+        let trail = ('\u{40}'..='\u{7E}').contains(&chars[end])
+            && code_byte(chars[end - 1]).is_some_and(|b| b >= 0x80);
+        if !high && !trail {
+            break;
+        }
+        end += 1;
+    }
+    Some(end)
+}
+
+fn count_high(chars: &[char]) -> usize {
+    chars.iter().filter(|c| !c.is_ascii()).count()
+}
+
+fn jamo_run(chars: &[char], start: usize) -> usize {
+    let mut end = start;
+    while end < chars.len() && (is_conjoining_jamo(chars[end]) || is_hangul(chars[end])) {
+        end += 1;
+    }
+    end
+}
+
+/// The byte a WinAnsiEncoding read turned into `c`.
+fn code_byte(c: char) -> Option<u8> {
+    match u8::try_from(c as u32) {
+        Ok(b) => Some(b),
+        Err(_) => super::cleanup::CP1252_HIGH
+            .iter()
+            .position(|&h| h == c)
+            .map(|i| 0x80 + i as u8),
+    }
+}
+
+fn code_bytes(run: &str) -> Option<Vec<u8>> {
+    run.chars().map(code_byte).collect()
+}
+
+fn is_damaged_line(line: &str) -> bool {
+    let visible = line.chars().filter(|c| !c.is_whitespace()).count();
+    let damaged = line.chars().filter(|&c| is_damaged(c)).count();
+    damaged >= 3 && damaged as f32 >= visible as f32 * DAMAGED_LINE_RATIO
+}
+
+/// Private Use Area or U+FFFD.
+fn is_damaged(c: char) -> bool {
+    matches!(c as u32, 0xE000..=0xF8FF | 0xF0000..=0xFFFFD | 0x100000..=0x10FFFD | 0xFFFD)
+}
+
+fn is_conjoining_jamo(c: char) -> bool {
+    matches!(c as u32, 0x1100..=0x11FF | 0xA960..=0xA97F | 0xD7B0..=0xD7FF)
+}
+
+fn is_hangul(c: char) -> bool {
+    matches!(c as u32, 0xAC00..=0xD7A3)
+}
+
+fn is_kana(c: char) -> bool {
+    matches!(c as u32, 0x3041..=0x30FF)
+}
+
+fn is_han(c: char) -> bool {
+    matches!(c as u32, 0x4E00..=0x9FFF | 0x3400..=0x4DBF)
+}
+
+/// CJK punctuation, full-width forms and other symbols all three
+/// encodings share.
+fn is_cjk_symbol(c: char) -> bool {
+    matches!(c as u32, 0x3000..=0x303F | 0xFF01..=0xFF5E | 0x2010..=0x203B | 0x00B7)
+}
+
+/// Mostly common Hangul syllables (Korean text also mixes in some Hanja).
+/// CP949 decodes almost any pair of high bytes into one of its 8,822
+/// extension syllables, so those do not count.
+fn is_clean_korean(text: &str) -> bool {
+    is_clean(
+        text,
+        is_common_hangul,
+        |c| is_han(c) || is_cjk_symbol(c),
+        0.8,
+    )
+}
+
+/// One of the 2,350 syllables of KS X 1001, which EUC-KR text is written in.
+fn is_common_hangul(c: char) -> bool {
+    let mut buf = [0; 4];
+    is_hangul(c) && EUC_KR.encode(c.encode_utf8(&mut buf)).0.first() >= Some(&0xB0)
+}
+
+/// Kanji and kana with at least one kana; half-width katakana, which any
+/// Latin-1 symbol byte decodes to, does not count.
+fn is_clean_japanese(text: &str) -> bool {
+    is_clean(text, is_kana, |c| is_han(c) || is_cjk_symbol(c), 0.0)
+}
+
+fn is_clean_chinese(text: &str) -> bool {
+    is_clean(text, is_han, is_cjk_symbol, 0.0)
+}
+
+/// Whether the non-ASCII characters of `text` are all `main` or `also`,
+/// with at least one and at least `min_share` of them `main`.
+fn is_clean(text: &str, main: fn(char) -> bool, also: fn(char) -> bool, min_share: f32) -> bool {
+    let (mut total, mut primary) = (0, 0);
+    for c in text.chars().filter(|c| !c.is_ascii()) {
+        total += 1;
+        if main(c) {
+            primary += 1;
+        } else if !also(c) {
+            return false;
+        }
+    }
+    primary > 0 && primary as f32 >= total as f32 * min_share
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn winansi(bytes: &[u8]) -> String {
+        encoding_rs::WINDOWS_1252
+            .decode_without_bom_handling(bytes)
+            .0
+            .into_owned()
+    }
+
+    #[test]
+    fn test_repair_redecodes_cjk_and_utf8() {
+        let korean = winansi(&EUC_KR.encode("한글 문서").0);
+        let japanese = winansi(&SHIFT_JIS.encode("ひらがなの文書").0);
+        let chinese = winansi(&GBK.encode("中文文档").0);
+        let text = format!("{korean}\nCafÃ© Größe\n{japanese} {chinese}");
+
+        let mut report = MojibakeReport::default();
+        let repaired = repair_mojibake(&text, &mut report);
+        assert_eq!(repaired, "한글 문서\nCafé Größe\nひらがなの文書 中文文档");
+        assert_eq!(report.repairs["CP949"], 2);
+        assert_eq!(report.repairs["UTF-8"], 1);
+        assert_eq!(report.repairs["Shift_JIS"], 1);
+        assert_eq!(report.repairs["GBK"], 1);
+        assert_eq!(report.unrepaired, 0);
+    }
+
+    #[test]
+    fn test_repair_leaves_latin_text_alone() {
+        let text = "Ærø, façade, naïve, Größe ±3 µm, «quoted» — 50 €";
+        let mut report = MojibakeReport::default();
+        assert_eq!(repair_mojibake(text, &mut report), text);
+        assert_eq!(report, MojibakeReport::default());
+        assert_eq!(mojibake_score(text), 0.0);
+    }
+
+    #[test]
+    fn test_jamo_and_damaged_lines() {
+        let decomposed: String = "한국어".nfd().collect();
+        let text = format!("{decomposed} \u{1100}\n\u{E001}\u{E002}\u{FFFD}x");
+        let mut report = MojibakeReport::default();
+        let repaired = repair_mojibake(&text, &mut report);
+        assert!(repaired.starts_with("한국어 "));
+        assert_eq!(report.repairs["Hangul jamo"], 1);
+        assert_eq!(report.unrepaired, 2);
+
+        assert!(mojibake_score("ÇÑ±Û ¹®¼\u{AD}") > 0.5);
+        assert!(mojibake_score(&text) > 0.25);
+    }
+}
@@ -0,0 +1,381 @@
+//! File names for extracted resources.
+//!
+//! The parser names an image after the page and the XObject it was drawn
+//! from (`page3_Im7.jpg`). XObject names are chosen by the producer and the
+//! same image may be reached under another name after the file is re-saved,
+//! so a naming scheme lets links stay the same across re-runs.
+//!
+//! Whatever the scheme, names come from the PDF and may not be valid file
+//! names everywhere; [`SafeFileNames`] makes them so before they are written.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::model::{sha256_hex, Block, Document, Page, Resource};
+
+/// How resources are named when they are written out and linked.
+///
+/// Templates are filled per image from these placeholders, each optionally
+/// zero-padded as `{page:03}`:
+///
+/// - `{page}`: page number
+/// - `{index}`: 1-based position of the image on its page, in reading order
+/// - `{hash}`: first 16 hex digits of the SHA-256 of the image bytes
+/// - `{id}`: the parser's id without its extension (`page3_Im7`)
+/// - `{ext}`: file extension for the image format (`jpg`, `png`, ...)
+///
+/// A template should include `{index}` or `{hash}` so two images never get
+/// the same name; with `{hash}` identical images share one.
+///
+/// ```
+/// use unpdf::render::{RenderOptions, ResourceNaming};
+///
+/// let options = RenderOptions::new()
+///     .with_resource_naming(ResourceNaming::template("{page:03}_{index:02}.{ext}"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ResourceNaming {
+    /// Keep the parser's ids (default)
+    #[default]
+    Id,
+    /// Fill a template such as `"{page:03}_{index:02}.{ext}"`
+    Template(String),
+}
+
+impl ResourceNaming {
+    /// Name resources from `template`.
+    pub fn template(template: impl Into<String>) -> Self {
+        Self::Template(template.into())
+    }
+
+    /// Name resources after their content, `{hash}.{ext}`.
+    pub fn content_hash() -> Self {
+        Self::Template("{hash}.{ext}".to_string())
+    }
+
+    /// The file name for `resource`, the `index`th image of page `page`,
+    /// which the parser called `id`.
+    pub fn file_name(&self, page: u32, index: usize, id: &str, resource: &Resource) -> String {
+        let Self::Template(template) = self else {
+            return id.to_string();
+        };
+        let mut out = String::with_capacity(template.len() + 16);
+        let mut rest = template.as_str();
+        while let Some(open) = rest.find('{') {
+            let Some(close) = rest[open..].find('}').map(|c| open + c) else {
+                break;
+            };
+            out.push_str(&rest[..open]);
+            let (key, width) = match rest[open + 1..close].split_once(':') {
+                Some((key, spec)) => (key, spec.parse::<usize>().ok()),
+                None => (&rest[open + 1..close], None),
+            };
+            let value = match key {
+                "page" => page.to_string(),
+                "index" => index.to_string(),
+                "hash" => sha256_hex(&resource.data)[..16].to_string(),
+                "id" => id_stem(id).to_string(),
+                "ext" => resource.extension().to_string(),
+                // 모르는 자리표시자는 그대로 둔다.
+                _ => rest[open..=close].to_string(),
+            };
+            match width {
+                Some(width) => out.push_str(&format!("{:0>width$}", value, width = width)),
+                None => out.push_str(&value),
+            }
+            rest = &rest[close + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Rename the images of a page being streamed: the entries of
+    /// `page.images` and the blocks that show them. Call before the page's
+    /// images are written out and the page is rendered.
+    pub fn apply_to_page(&self, page: &mut Page) {
+        if *self == Self::Id {
+            return;
+        }
+        let renames = self.page_renames(page, |id| {
+            page.images.iter().find(|(i, _)| i == id).map(|(_, r)| r)
+        });
+        for (id, _) in &mut page.images {
+            if let Some(name) = renames.get(id.as_str()) {
+                *id = name.clone();
+            }
+        }
+        rename_blocks(page, &renames);
+    }
+
+    /// Rename every resource of a parsed document: the keys of
+    /// `doc.resources`, the images carried by pages and the blocks that show
+    /// them.
+    pub fn apply(&self, doc: &mut Document) {
+        if *self == Self::Id {
+            return;
+        }
+        let mut resource_renames: HashMap<String, String> = HashMap::new();
+        for page in &mut doc.pages {
+            let resources = &doc.resources;
+            let renames = self.page_renames(page, |id| {
+                page.images
+                    .iter()
+                    .find(|(i, _)| i == id)
+                    .map(|(_, r)| r)
+                    .or_else(|| resources.get(id))
+                    .or_else(|| resources.get(id_stem(id)))
+            });
+            for (id, _) in &mut page.images {
+                if let Some(name) = renames.get(id.as_str()) {
+                    *id = name.clone();
+                }
+            }
+            rename_blocks(page, &renames);
+            for (id, name) in renames {
+                // 문서 리소스 키는 확장자 없이 저장되기도 한다.
+                let key = if doc.resources.contains_key(&id) {
+                    id
+                } else {
+                    id_stem(&id).to_string()
+                };
+                resource_renames.insert(key, name);
+            }
+        }
+        let resources = std::mem::take(&mut doc.resources);
+        doc.resources = resources
+            .into_iter()
+            .map(|(id, resource)| match resource_renames.get(&id) {
+                Some(name) => (name.clone(), resource),
+                None => (id, resource),
+            })
+            .collect();
+    }
+
+    /// New names for the images of `page`, numbered in reading order, then
+    /// those not shown by any block in drawing order.
+    fn page_renames<'a>(
+        &self,
+        page: &'a Page,
+        resource: impl Fn(&str) -> Option<&'a Resource>,
+    ) -> HashMap<String, String> {
+        let mut ids: Vec<&str> = Vec::new();
+        for block in &page.elements {
+            for id in block.resource_ids() {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+        }
+        for (id, _) in &page.images {
+            if !ids.contains(&id.as_str()) {
+                ids.push(id);
+            }
+        }
+        ids.into_iter()
+            .filter_map(|id| resource(id).map(|r| (id, r)))
+            .enumerate()
+            .map(|(i, (id, r))| (id.to_string(), self.file_name(page.number, i + 1, id, r)))
+            .collect()
+    }
+}
+
+/// Longest file name issued by [`SafeFileNames`], in bytes, before any
+/// `_N` suffix. Most file systems allow 255.
+const MAX_FILE_NAME_BYTES: usize = 240;
+
+/// Device names Windows reserves regardless of extension.
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// File names for resources written into one directory, valid on Windows,
+/// macOS and Linux and distinct even where the file system ignores case.
+///
+/// Resource ids and embedded file names come from the PDF and may hold
+/// `:`, `/` or `?`, or differ from another only in case (`Im1` and `IM1`),
+/// so writing them as-is can fail or overwrite an earlier file.
+///
+/// ```
+/// use unpdf::render::SafeFileNames;
+///
+/// let mut names = SafeFileNames::new();
+/// assert_eq!(names.claim("page1_Im1.jpg"), "page1_Im1.jpg");
+/// assert_eq!(names.claim("page1_IM1.jpg"), "page1_IM1_2.jpg");
+/// assert_eq!(names.claim("chart: Q3?.png"), "chart_ Q3_.png");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SafeFileNames {
+    /// Lowercased names already issued.
+    taken: HashSet<String>,
+}
+
+impl SafeFileNames {
+    /// An empty set of names.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `name` made valid by [`SafeFileNames::sanitize`] and, if another name
+    /// issued by this set already matches it ignoring case, numbered
+    /// `stem_2.ext`, `stem_3.ext`, ...
+    pub fn claim(&mut self, name: &str) -> String {
+        let name = Self::sanitize(name);
+        if self.taken.insert(name.to_lowercase()) {
+            return name;
+        }
+        let (stem, ext) = split_extension(&name);
+        (2u32..)
+            .map(|n| format!("{}_{}{}", stem, n, ext))
+            .find(|candidate| self.taken.insert(candidate.to_lowercase()))
+            .expect("unbounded suffixes")
+    }
+
+    /// `name` as a single path component every common file system accepts:
+    /// path separators, `<>:"|?*` and control characters become `_`,
+    /// trailing dots and spaces are dropped, Windows device names such as
+    /// `CON` get a leading `_`, and over-long names are shortened keeping
+    /// their extension. An empty result becomes `resource`.
+    pub fn sanitize(name: &str) -> String {
+        let mut safe: String = name
+            .chars()
+            .map(|c| match c {
+                '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+                c if c.is_control() => '_',
+                c => c,
+            })
+            .collect();
+        // Windows 는 끝의 점·공백을 조용히 지워 다른 이름과 겹치게 만든다.
+        safe.truncate(safe.trim_end_matches(['.', ' ']).len());
+        if safe.is_empty() {
+            return "resource".to_string();
+        }
+
+        let device = safe.split('.').next().unwrap_or_default().trim_end();
+        if RESERVED_NAMES
+            .iter()
+            .any(|r| r.eq_ignore_ascii_case(device))
+        {
+            safe.insert(0, '_');
+        }
+
+        if safe.len() > MAX_FILE_NAME_BYTES {
+            let (stem, ext) = split_extension(&safe);
+            let mut cut = MAX_FILE_NAME_BYTES.saturating_sub(ext.len());
+            while !stem.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            safe = format!("{}{}", &stem[..cut], ext);
+        }
+        safe
+    }
+}
+
+/// `name` split before the dot of its extension (`("a", ".jpg")`); names
+/// without one, or whose only dot leads, have an empty extension.
+fn split_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(dot) if dot > 0 && name.len() - dot <= 16 => name.split_at(dot),
+        _ => (name, ""),
+    }
+}
+
+/// `id` without its file extension.
+pub(super) fn id_stem(id: &str) -> &str {
+    id.rsplit_once('.').map_or(id, |(stem, _)| stem)
+}
+
+fn rename_blocks(page: &mut Page, renames: &HashMap<String, String>) {
+    for resource_id in page.elements.iter_mut().flat_map(Block::resource_ids_mut) {
+        if let Some(name) = renames.get(resource_id.as_str()) {
+            *resource_id = name.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ResourceType;
+
+    fn jpeg(data: &[u8]) -> Resource {
+        Resource::new(data.to_vec(), "image/jpeg".to_string(), ResourceType::Image)
+    }
+
+    #[test]
+    fn test_template_placeholders() {
+        let naming = ResourceNaming::template("{page:03}_{index:02}_{id}.{ext}");
+        let name = naming.file_name(7, 2, "page7_Im4.jpg", &jpeg(b"x"));
+        assert_eq!(name, "007_02_page7_Im4.jpg");
+
+        let hashed = ResourceNaming::content_hash().file_name(1, 1, "a", &jpeg(b"x"));
+        assert_eq!(hashed, format!("{}.jpg", &sha256_hex(b"x")[..16]));
+        // 모르는 자리표시자와 닫히지 않은 중괄호는 그대로.
+        let odd = ResourceNaming::template("{other}-{page").file_name(1, 1, "a", &jpeg(b"x"));
+        assert_eq!(odd, "{other}-{page");
+    }
+
+    #[test]
+    fn test_indexes_follow_reading_order() {
+        let mut page = Page::letter(2);
+        page.elements.push(Block::image("page2_Im9.jpg"));
+        page.elements.push(Block::image("page2_Im1.jpg"));
+        page.images.push(("page2_Im1.jpg".to_string(), jpeg(b"a")));
+        page.images.push(("page2_Im9.jpg".to_string(), jpeg(b"b")));
+
+        ResourceNaming::template("p{page}-{index}.{ext}").apply_to_page(&mut page);
+        let ids: Vec<&str> = page.images.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["p2-2.jpg", "p2-1.jpg"]);
+        assert!(matches!(
+            &page.elements[0],
+            Block::Image { resource_id, .. } if resource_id == "p2-1.jpg"
+        ));
+    }
+
+    #[test]
+    fn test_sanitize_file_names() {
+        assert_eq!(SafeFileNames::sanitize("page1_Im1.jpg"), "page1_Im1.jpg");
+        assert_eq!(SafeFileNames::sanitize("a/b\\c|d.png"), "a_b_c_d.png");
+        assert_eq!(SafeFileNames::sanitize("tab\there.jpg"), "tab_here.jpg");
+        assert_eq!(SafeFileNames::sanitize("report. . "), "report");
+        assert_eq!(SafeFileNames::sanitize("..."), "resource");
+        assert_eq!(SafeFileNames::sanitize("con.jpg"), "_con.jpg");
+        assert_eq!(SafeFileNames::sanitize("LPT1"), "_LPT1");
+        assert_eq!(SafeFileNames::sanitize("console.jpg"), "console.jpg");
+
+        let long = format!("{}.jpg", "가".repeat(100));
+        let short = SafeFileNames::sanitize(&long);
+        assert!(short.len() <= MAX_FILE_NAME_BYTES);
+        assert!(short.ends_with("가.jpg"));
+    }
+
+    #[test]
+    fn test_claimed_names_are_unique_ignoring_case() {
+        let mut names = SafeFileNames::new();
+        assert_eq!(names.claim("Im1.jpg"), "Im1.jpg");
+        assert_eq!(names.claim("IM1.JPG"), "IM1_2.JPG");
+        assert_eq!(names.claim("im1.jpg"), "im1_3.jpg");
+        // 정리한 뒤에 겹치는 이름도 번호가 붙는다.
+        assert_eq!(names.claim("a:b.png"), "a_b.png");
+        assert_eq!(names.claim("a?b.png"), "a_b_2.png");
+        assert_eq!(names.claim("a_b_2.png"), "a_b_2_2.png");
+        assert_eq!(names.claim(".hidden"), ".hidden");
+        assert_eq!(names.claim(".HIDDEN"), ".HIDDEN_2");
+    }
+
+    #[test]
+    fn test_apply_renames_document_resources() {
+        let mut doc = Document::new();
+        let mut page = Page::letter(1);
+        page.elements.push(Block::image("page1_Im0.jpg"));
+        doc.add_page(page);
+        // 일반 파싱은 확장자 없는 키로 리소스를 둔다.
+        doc.resources.insert("page1_Im0".to_string(), jpeg(b"a"));
+
+        ResourceNaming::template("{page}_{index}.{ext}").apply(&mut doc);
+        assert!(doc.resources.contains_key("1_1.jpg"));
+        assert!(matches!(
+            &doc.pages[0].elements[0],
+            Block::Image { resource_id, .. } if resource_id == "1_1.jpg"
+        ));
+    }
+}
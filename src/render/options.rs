@@ -1,6 +1,9 @@
 //! Rendering options and configuration.
 
-use super::CleanupOptions;
+use super::naming::id_stem;
+use super::{CleanupOptions, CleanupPipeline, ResourceNaming};
+use crate::model::{Block, BlockDedup, BlockFilter, Document, Paragraph, Resource};
+use std::borrow::Cow;
 use std::ops::RangeInclusive;
 use std::path::PathBuf;
 
@@ -13,6 +16,13 @@ pub struct RenderOptions {
     /// Prefix for image paths in output (e.g., "./images/")
     pub image_path_prefix: String,
 
+    /// Embed images of at most this many bytes in Markdown output as
+    /// base64 `data:` URIs instead of linking to a file, for
+    /// self-contained output. Only formats browsers display (JPEG, PNG,
+    /// GIF, WebP, BMP, SVG) are embedded; writers leave embedded images out
+    /// of the image directory. `None` (default) links every image.
+    pub inline_images_max_bytes: Option<usize>,
+
     /// How to render complex tables
     pub table_fallback: TableFallback,
 
@@ -28,26 +38,100 @@ pub struct RenderOptions {
     /// Character to use for unordered list markers
     pub list_marker: char,
 
+    /// Cycle bullet markers by nesting level: `list_marker` at the top
+    /// level, then the remaining of `-`, `*`, `+`
+    pub rotate_list_markers: bool,
+
+    /// Whether ordered list items keep their source numbers
+    pub list_numbering: ListNumbering,
+
+    /// Render items starting with a checkbox glyph (☐, ☑, ☒) as GFM task
+    /// list items (`- [ ]` / `- [x]`)
+    pub task_lists: bool,
+
+    /// How definition list items (glossary entries) are written
+    pub definition_style: DefinitionStyle,
+
     /// Escape special Markdown characters
     pub escape_special_chars: bool,
 
     /// Text cleanup options
     pub cleanup: Option<CleanupOptions>,
 
+    /// Drop paragraphs and lists repeating an earlier one (see
+    /// `Document::duplicate_blocks`); off by default
+    pub dedup_blocks: Option<BlockDedup>,
+
     /// Page selection
     pub page_selection: PageSelection,
 
-    /// Heading detection configuration
-    pub heading_config: Option<HeadingConfig>,
+    /// Block kinds to render; everything else is skipped
+    pub block_filter: BlockFilter,
+
+    /// Render only paragraphs tagged with one of these languages (ISO 639-1
+    /// codes, see `ParseOptions::tag_languages`, which conversions turn on
+    /// when this is set). Untagged paragraphs and other blocks are kept.
+    /// Empty keeps every language.
+    pub languages: Vec<String>,
 
-    /// Width for wrapping long lines (0 = no wrap)
+    /// Heading detection configuration, passed to the parser when converting
+    pub heading_config: Option<HeadingConfig>,
+    /// Width for wrapping long lines in plain text output (0 = no wrap).
+    /// Same as `wrap_width: Some(n)`; ignored when `wrap_width` is set.
     pub line_width: u32,
 
+    /// Re-wrap plain text paragraphs at this many terminal columns, CJK
+    /// characters counting two. Line breaks from the source are dropped and
+    /// Chinese and Japanese text may break between characters. `None` keeps
+    /// the source lines (or wraps at `line_width` when that is non-zero).
+    pub wrap_width: Option<usize>,
+
+    /// How extracted images are named when written out and linked
+    pub resource_naming: ResourceNaming,
+
+    /// How extracted images are converted before they are written out
+    #[cfg(feature = "image-convert")]
+    pub image_conversion: super::ImageConversion,
+
+    /// Whether plain text output reflows blocks or keeps the page layout
+    pub text_layout: TextLayout,
+
     /// Collect extraction statistics during rendering
     pub collect_stats: bool,
 
     /// Style for page boundary markers in Markdown output.
     pub page_markers: PageMarkerStyle,
+
+    /// Heading written at the start of every rendered page in Markdown and
+    /// plain text output, `{n}` standing for the page number — e.g.
+    /// `"## Page {n}"`, as note-taking tools such as Obsidian expect.
+    /// Cleanup never strips or merges it. `None` (default) writes none.
+    pub page_heading_template: Option<String>,
+
+    /// How annotations (reviewer comments, highlights) appear in Markdown
+    /// output.
+    pub annotations: AnnotationStyle,
+
+    /// Embed extraction provenance (source SHA-256, unpdf version, parse
+    /// options, timestamp) into frontmatter and JSON output.
+    pub include_provenance: bool,
+
+    /// Write non-fatal extraction warnings into Markdown output as
+    /// `<!-- unpdf: … -->` comments, each just before the page or block it
+    /// concerns: low-confidence tables, undecodable characters, scanned
+    /// pages and pages whose layout could not be analysed.
+    pub inline_warnings: bool,
+
+    /// Fall back to `Metadata::inferred_title` in frontmatter when the PDF
+    /// has no authored title. The line is tagged `title_source: inferred`.
+    pub use_inferred_title: bool,
+
+    /// Upper bound on the size of Markdown and plain text output, in bytes.
+    /// Longer output is cut at block boundaries as `overflow` says.
+    pub max_output_bytes: Option<usize>,
+
+    /// What to do with output longer than `max_output_bytes`
+    pub overflow: OverflowStrategy,
 }
 
 impl RenderOptions {
@@ -98,6 +182,30 @@ impl RenderOptions {
         self
     }
 
+    /// Cycle bullet markers by nesting level.
+    pub fn with_rotated_list_markers(mut self, rotate: bool) -> Self {
+        self.rotate_list_markers = rotate;
+        self
+    }
+
+    /// Set how ordered list items are numbered.
+    pub fn with_list_numbering(mut self, numbering: ListNumbering) -> Self {
+        self.list_numbering = numbering;
+        self
+    }
+
+    /// Render checkbox items as GFM task list items.
+    pub fn with_task_lists(mut self, enable: bool) -> Self {
+        self.task_lists = enable;
+        self
+    }
+
+    /// Set how definition list items are written.
+    pub fn with_definition_style(mut self, style: DefinitionStyle) -> Self {
+        self.definition_style = style;
+        self
+    }
+
     /// Set cleanup options.
     pub fn with_cleanup(mut self, cleanup: CleanupOptions) -> Self {
         self.cleanup = Some(cleanup);
@@ -136,12 +244,72 @@ impl RenderOptions {
         self
     }
 
+    /// Drop repeated paragraphs and lists, such as a title block on every
+    /// slide, keeping the first occurrence within `dedup`'s scope.
+    pub fn with_block_dedup(mut self, dedup: BlockDedup) -> Self {
+        self.dedup_blocks = Some(dedup);
+        self
+    }
+
     /// Set page selection.
     pub fn with_pages(mut self, selection: PageSelection) -> Self {
         self.page_selection = selection;
         self
     }
 
+    /// Render only the block kinds `filter` allows.
+    pub fn with_block_filter(mut self, filter: BlockFilter) -> Self {
+        self.block_filter = filter;
+        self
+    }
+
+    /// Render only tables.
+    pub fn only_tables(self) -> Self {
+        self.with_block_filter(BlockFilter::only_tables())
+    }
+
+    /// Render only headings.
+    pub fn only_headings(self) -> Self {
+        self.with_block_filter(BlockFilter::only_headings())
+    }
+
+    /// Leave image references out of the output.
+    pub fn skip_images(mut self) -> Self {
+        self.block_filter.images = false;
+        self
+    }
+
+    /// Render only paragraphs in `languages`, plus untagged ones.
+    pub fn with_languages<I, S>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.languages = languages.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether `block` passes `block_filter` and `languages`.
+    pub(crate) fn keeps(&self, block: &Block) -> bool {
+        if !self.block_filter.allows(block) {
+            return false;
+        }
+        match block {
+            Block::Paragraph(p) if !self.languages.is_empty() => self.keeps_language(p),
+            Block::List { items, .. } if !self.languages.is_empty() => {
+                items.iter().any(|p| self.keeps_language(p))
+            }
+            _ => true,
+        }
+    }
+
+    fn keeps_language(&self, p: &Paragraph) -> bool {
+        p.style
+            .language
+            .as_ref()
+            .map_or(true, |l| self.languages.contains(l))
+    }
+
     /// Set specific page range.
     pub fn with_page_range(mut self, range: RangeInclusive<u32>) -> Self {
         self.page_selection = PageSelection::Range(range);
@@ -171,6 +339,75 @@ impl RenderOptions {
         self.line_width = width;
         self
     }
+
+    /// Re-wrap plain text paragraphs at `width` columns (`None` = no wrap).
+    pub fn with_wrap_width(mut self, width: Option<usize>) -> Self {
+        self.wrap_width = width;
+        self
+    }
+
+    /// Columns plain text paragraphs are wrapped at, 0 for none.
+    pub(crate) fn text_wrap_width(&self) -> usize {
+        self.wrap_width.unwrap_or(self.line_width as usize)
+    }
+
+    /// Embed images of at most `max_bytes` in Markdown output as `data:`
+    /// URIs (see [`inline_images_max_bytes`](Self::inline_images_max_bytes)).
+    pub fn with_inline_images_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.inline_images_max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Whether `resource` is embedded as a data URI rather than written out
+    /// and linked.
+    pub fn inlines_image(&self, resource: &Resource) -> bool {
+        self.inline_images_max_bytes.is_some_and(|max| {
+            !resource.data.is_empty() && resource.data.len() <= max && resource.is_web_image()
+        })
+    }
+
+    /// Link target for the image `resource_id` in `doc`: a data URI when it
+    /// is small enough to embed, otherwise the prefixed file path.
+    pub(crate) fn image_target(&self, resource_id: &str, doc: &Document) -> String {
+        self.inline_image_uri(resource_id, doc)
+            .unwrap_or_else(|| format!("{}{}", self.image_path_prefix, resource_id))
+    }
+
+    /// Data URI for the image `resource_id` in `doc`, if it is embedded.
+    pub(crate) fn inline_image_uri(&self, resource_id: &str, doc: &Document) -> Option<String> {
+        self.inline_images_max_bytes?;
+        // 문서 리소스 키는 확장자 없이 저장되기도 한다.
+        doc.pages
+            .iter()
+            .flat_map(|page| &page.images)
+            .find(|(id, _)| id == resource_id)
+            .map(|(_, r)| r)
+            .or_else(|| doc.resources.get(resource_id))
+            .or_else(|| doc.resources.get(id_stem(resource_id)))
+            .filter(|r| self.inlines_image(r))
+            .map(Resource::to_data_uri)
+    }
+
+    /// Set how extracted images are named (see [`ResourceNaming`]).
+    pub fn with_resource_naming(mut self, naming: ResourceNaming) -> Self {
+        self.resource_naming = naming;
+        self
+    }
+
+    /// Set how extracted images are converted (see [`ImageConversion`]).
+    ///
+    /// [`ImageConversion`]: super::ImageConversion
+    #[cfg(feature = "image-convert")]
+    pub fn with_image_conversion(mut self, conversion: super::ImageConversion) -> Self {
+        self.image_conversion = conversion;
+        self
+    }
+
+    /// Set how plain text output is laid out.
+    pub fn with_text_layout(mut self, layout: TextLayout) -> Self {
+        self.text_layout = layout;
+        self
+    }
 }
 
 impl Default for RenderOptions {
@@ -178,18 +415,38 @@ impl Default for RenderOptions {
         Self {
             image_dir: None,
             image_path_prefix: String::new(),
+            inline_images_max_bytes: None,
             table_fallback: TableFallback::Markdown,
             max_heading_level: 6,
             include_frontmatter: false,
             preserve_line_breaks: false,
             list_marker: '-',
+            rotate_list_markers: false,
+            list_numbering: ListNumbering::Preserve,
+            task_lists: false,
+            definition_style: DefinitionStyle::DefinitionList,
             escape_special_chars: true,
             cleanup: Some(CleanupOptions::standard()), // Enable standard cleanup by default
+            dedup_blocks: None,
             page_selection: PageSelection::All,
+            block_filter: BlockFilter::all(),
+            languages: Vec::new(),
             heading_config: None,
             line_width: 0,
+            wrap_width: None,
+            resource_naming: ResourceNaming::Id,
+            #[cfg(feature = "image-convert")]
+            image_conversion: super::ImageConversion::default(),
+            text_layout: TextLayout::Reflow,
             collect_stats: false,
             page_markers: PageMarkerStyle::None,
+            page_heading_template: None,
+            annotations: AnnotationStyle::None,
+            include_provenance: false,
+            inline_warnings: false,
+            use_inferred_title: false,
+            max_output_bytes: None,
+            overflow: OverflowStrategy::Truncate,
         }
     }
 }
@@ -206,6 +463,96 @@ impl RenderOptions {
         self.page_markers = style;
         self
     }
+
+    /// Start every page with a heading from `template`, `{n}` replaced by
+    /// the page number (e.g. `"## Page {n}"`).
+    pub fn with_page_heading_template(mut self, template: impl Into<String>) -> Self {
+        self.page_heading_template = Some(template.into());
+        self
+    }
+
+    /// The page heading for page `number`, if a template is set.
+    pub(crate) fn page_heading(&self, number: u32) -> Option<String> {
+        let template = self.page_heading_template.as_ref()?;
+        Some(template.replace("{n}", &number.to_string()))
+    }
+
+    /// Set how annotations are rendered.
+    pub fn with_annotations(mut self, style: AnnotationStyle) -> Self {
+        self.annotations = style;
+        self
+    }
+
+    /// Embed extraction provenance. Markdown output gains a frontmatter
+    /// block even when frontmatter is otherwise off.
+    ///
+    /// The source file's SHA-256 is only known when the document was parsed
+    /// with `ParseOptions::with_source_hash(true)`; set both, or use
+    /// [`Unpdf::with_provenance`](crate::Unpdf::with_provenance), which does.
+    pub fn with_provenance(mut self, include: bool) -> Self {
+        self.include_provenance = include;
+        self
+    }
+
+    /// Write extraction warnings into Markdown output as HTML comments.
+    pub fn with_inline_warnings(mut self, enable: bool) -> Self {
+        self.inline_warnings = enable;
+        self
+    }
+
+    /// Use the inferred title in frontmatter when no title was authored.
+    pub fn with_inferred_title(mut self, use_inferred: bool) -> Self {
+        self.use_inferred_title = use_inferred;
+        self
+    }
+
+    /// Keep Markdown and plain text output within `max_bytes`, handling
+    /// longer output with `overflow`.
+    pub fn with_max_output_bytes(mut self, max_bytes: usize, overflow: OverflowStrategy) -> Self {
+        self.max_output_bytes = Some(max_bytes);
+        self.overflow = overflow;
+        self
+    }
+
+    /// `doc` as the whole-document renderers see it: without its running
+    /// heads when cleanup removes headers and footers (see
+    /// [`Document::running_heads`]) and without repeated blocks when
+    /// `dedup_blocks` is set. Also returns the number of blocks deduplicated.
+    pub(crate) fn prepare_document<'d>(&self, doc: &'d Document) -> (Cow<'d, Document>, usize) {
+        let removes = self
+            .cleanup
+            .as_ref()
+            .is_some_and(|c| c.remove_headers_footers)
+            && !doc.running_heads().is_empty();
+        if !removes && self.dedup_blocks.is_none() {
+            return (Cow::Borrowed(doc), 0);
+        }
+        let mut doc = doc.clone();
+        if removes {
+            doc.remove_running_heads();
+        }
+        let deduplicated = match &self.dedup_blocks {
+            Some(dedup) => doc.remove_duplicate_blocks(dedup),
+            None => 0,
+        };
+        (Cow::Owned(doc), deduplicated)
+    }
+
+    /// Frontmatter for `doc` under these options, if any is to be emitted.
+    pub(crate) fn frontmatter_for(&self, doc: &Document) -> Option<String> {
+        let provenance = doc.provenance.as_ref().filter(|_| self.include_provenance);
+        if self.include_frontmatter || provenance.is_some() {
+            let metadata = match &self.cleanup {
+                Some(cleanup) => {
+                    Cow::Owned(CleanupPipeline::new(cleanup.clone()).clean_metadata(&doc.metadata))
+                }
+                None => Cow::Borrowed(&doc.metadata),
+            };
+            Some(metadata.yaml_frontmatter(provenance, self.use_inferred_title))
+        } else {
+            None
+        }
+    }
 }
 
 /// Style for page boundary markers in Markdown output.
@@ -218,6 +565,43 @@ pub enum PageMarkerStyle {
     Comment,
 }
 
+/// Rendering of annotations in Markdown output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnnotationStyle {
+    /// Leave annotations out (default)
+    #[default]
+    None,
+    /// A blockquote per annotation after the page's content
+    Blockquote,
+    /// A footnote reference after the paragraph holding the covered text
+    /// (or the page's last paragraph), with the notes collected at the end
+    Footnote,
+}
+
+/// Numbering of ordered list items in Markdown output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListNumbering {
+    /// Keep each item's number from the source (default)
+    #[default]
+    Preserve,
+    /// Number items consecutively from the list's start value, restarting
+    /// after any non-list block. Fixes gaps left by page breaks or
+    /// misdetected items.
+    Renumber,
+}
+
+/// Markdown syntax for definition list items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DefinitionStyle {
+    /// The term on its own line, the definition below it after `: `
+    /// (Pandoc and PHP Markdown Extra definition lists; default)
+    #[default]
+    DefinitionList,
+    /// A paragraph per item, the term in bold followed by an em dash. Reads
+    /// the same in renderers without definition list support.
+    BoldTerm,
+}
+
 /// How to render complex tables that can't be expressed in simple Markdown.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TableFallback {
@@ -230,6 +614,41 @@ pub enum TableFallback {
     Ascii,
 }
 
+/// Layout of plain text output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextLayout {
+    /// Text block by block in reading order, paragraphs joined into lines
+    #[default]
+    Reflow,
+    /// Keep the visual layout, like `pdftotext -layout`: words are padded
+    /// with spaces to about their position on the page and each printed
+    /// line stays a line, so fixed-width columns line up. Pages are
+    /// separated by a form feed. Needs the words from
+    /// `ParseOptions::with_words`; pages without them are reflowed.
+    Preserve,
+}
+
+/// What to do when output exceeds [`RenderOptions::max_output_bytes`].
+///
+/// Output is only ever cut between blocks — paragraphs, tables, code
+/// listings — unless a single block is over the limit on its own; that block
+/// is cut between lines, and a line that is still too long between
+/// characters. Never inside a UTF-8 sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowStrategy {
+    /// Keep the leading blocks that fit and drop the rest
+    #[default]
+    Truncate,
+    /// Like `Truncate`, ending with a marker that says how many blocks and
+    /// bytes were left out (`<!-- truncated: ... -->` in Markdown)
+    SummarizeTail,
+    /// Split into consecutive parts of at most the limit each, every part
+    /// but the last ending with a continuation marker. `to_markdown` and
+    /// `to_text` return the first part; `to_markdown_parts` and
+    /// `to_text_parts` return them all.
+    Split,
+}
+
 /// Page selection for rendering.
 #[derive(Debug, Clone, Default)]
 pub enum PageSelection {
@@ -311,6 +730,12 @@ pub struct HeadingConfig {
 
     /// Korean-specific heading patterns (e.g., "제1장", "1.", "가.")
     pub korean_patterns: bool,
+
+    /// Style signals used when a page is set in a single font size
+    pub signals: HeadingSignals,
+
+    /// Repair of the detected levels across the document
+    pub normalize_levels: LevelNormalization,
 }
 
 impl Default for HeadingConfig {
@@ -321,6 +746,81 @@ impl Default for HeadingConfig {
             detect_from_style: true,
             use_outline: true,
             korean_patterns: true,
+            signals: HeadingSignals::default(),
+            normalize_levels: LevelNormalization::default(),
+        }
+    }
+}
+
+impl HeadingConfig {
+    /// Set the style signal weights.
+    pub fn with_signals(mut self, signals: HeadingSignals) -> Self {
+        self.signals = signals;
+        self
+    }
+
+    /// Set how detected heading levels are repaired.
+    pub fn with_normalize_levels(mut self, normalization: LevelNormalization) -> Self {
+        self.normalize_levels = normalization;
+        self
+    }
+}
+
+/// Repair of detected heading levels, which can jump (H1 straight to H4)
+/// when font sizes are uneven.
+///
+/// Headings are taken in reading order across the whole document. A heading
+/// nests under the nearest earlier heading with a smaller detected level and
+/// is placed one level below it, so gaps close and no heading sits more than
+/// one level deeper than its parent. Headings with no such parent are top
+/// level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LevelNormalization {
+    /// Keep the detected levels (default)
+    #[default]
+    Off,
+    /// Close gaps; top-level headings keep their detected level
+    Compress,
+    /// Close gaps and put every top-level heading at this level (1 or 2)
+    Anchored(u8),
+}
+
+/// Weights of the style signals that pick out headings on pages where every
+/// line shares one font size, so font size alone finds none.
+///
+/// Each line scores the sum of the weights of the signals it shows, and lines
+/// reaching `min_score` become headings. With the defaults a short bold line
+/// set off by extra space qualifies, while a numbered list item does not.
+#[derive(Debug, Clone)]
+pub struct HeadingSignals {
+    /// Most of the line's text is bold
+    pub bold: f32,
+
+    /// The line is short and does not end like a sentence
+    pub short_line: f32,
+
+    /// All letters in the line are uppercase
+    pub uppercase: f32,
+
+    /// The gap above the line is clearly wider than the page's line pitch
+    pub space_above: f32,
+
+    /// The line starts with section numbering ("2.", "3.1", "IV.", "제1장")
+    pub numbering: f32,
+
+    /// Score a line needs to be promoted
+    pub min_score: f32,
+}
+
+impl Default for HeadingSignals {
+    fn default() -> Self {
+        Self {
+            bold: 0.4,
+            short_line: 0.2,
+            uppercase: 0.3,
+            space_above: 0.25,
+            numbering: 0.2,
+            min_score: 0.7,
         }
     }
 }
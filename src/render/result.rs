@@ -1,7 +1,9 @@
 //! Rendering result with metadata and statistics.
 
+use super::mojibake::MojibakeReport;
 use crate::model::Metadata;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Result of rendering a document, including content and statistics.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +72,26 @@ pub struct ExtractionStats {
 
     /// Number of horizontal rules
     pub horizontal_rule_count: u32,
+
+    /// Number of code blocks
+    pub code_block_count: u32,
+
+    /// Matches replaced by cleanup PII redaction, keyed by placeholder
+    #[serde(default)]
+    pub redactions: BTreeMap<String, u32>,
+
+    /// Mojibake spans cleanup re-decoded, keyed by the encoding that
+    /// decoded them (`CP949`, `Shift_JIS`, `GBK`, `UTF-8`, `Hangul jamo`)
+    #[serde(default)]
+    pub mojibake_repairs: BTreeMap<String, u32>,
+
+    /// Mojibake spans and lines cleanup detected but could not decode
+    #[serde(default)]
+    pub mojibake_unrepaired: u32,
+
+    /// Repeated paragraphs and lists dropped by block deduplication
+    #[serde(default)]
+    pub deduplicated_blocks: u32,
 }
 
 impl ExtractionStats {
@@ -108,6 +130,31 @@ impl ExtractionStats {
         self.horizontal_rule_count += 1;
     }
 
+    /// Increment code block count.
+    pub fn add_code_block(&mut self) {
+        self.code_block_count += 1;
+    }
+
+    /// Add redaction counts, keyed by placeholder.
+    pub fn add_redactions(&mut self, counts: &BTreeMap<String, u32>) {
+        for (placeholder, count) in counts {
+            *self.redactions.entry(placeholder.clone()).or_default() += count;
+        }
+    }
+
+    /// Add the counts from a cleanup pass's mojibake repair.
+    pub fn add_mojibake(&mut self, report: &MojibakeReport) {
+        for (label, count) in &report.repairs {
+            *self.mojibake_repairs.entry(label.clone()).or_default() += count;
+        }
+        self.mojibake_unrepaired += report.unrepaired;
+    }
+
+    /// Total number of redacted matches.
+    pub fn redaction_count(&self) -> u32 {
+        self.redactions.values().sum()
+    }
+
     /// Increment page count.
     pub fn add_page(&mut self) {
         self.page_count += 1;
@@ -133,6 +180,13 @@ impl ExtractionStats {
         self.char_count += other.char_count;
         self.heading_count += other.heading_count;
         self.horizontal_rule_count += other.horizontal_rule_count;
+        self.code_block_count += other.code_block_count;
+        self.add_redactions(&other.redactions);
+        for (label, count) in &other.mojibake_repairs {
+            *self.mojibake_repairs.entry(label.clone()).or_default() += count;
+        }
+        self.mojibake_unrepaired += other.mojibake_unrepaired;
+        self.deduplicated_blocks += other.deduplicated_blocks;
     }
 }
 
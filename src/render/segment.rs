@@ -0,0 +1,135 @@
+//! CJK word segmentation for search-oriented plain-text output.
+//!
+//! Chinese and Japanese are written without spaces, so a whitespace tokenizer
+//! sees a whole sentence as one token. This stage inserts word boundaries:
+//!
+//! - Between runs of different scripts (Han ↔ Hiragana ↔ Katakana), which
+//!   separates most Japanese content words from their okurigana and particles.
+//! - Inside Han runs, using the jieba dictionary when the `cjk-segment`
+//!   feature is enabled. Without the feature Han runs are kept intact.
+//!
+//! Hangul is left untouched: Korean is already spaced by eojeol.
+
+/// Script class of a character, for boundary detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Han,
+    Hiragana,
+    Katakana,
+    Other,
+}
+
+fn script_of(c: char) -> Script {
+    match c {
+        '\u{3040}'..='\u{309F}' => Script::Hiragana,
+        // Katakana, phonetic extensions and the prolonged sound mark (ー).
+        '\u{30A0}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => {
+            Script::Katakana
+        }
+        '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FA1F}'
+        | '々' => Script::Han,
+        _ => Script::Other,
+    }
+}
+
+/// Insert word boundaries (single spaces) into CJK text.
+pub fn segment_cjk(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + text.len() / 4);
+    let mut run = String::new();
+    let mut run_script = Script::Other;
+    let mut prev_cjk = Script::Other;
+
+    for c in text.chars() {
+        let script = script_of(c);
+        if script != run_script && !run.is_empty() {
+            flush_run(&mut out, &run, run_script, &mut prev_cjk);
+            run.clear();
+        }
+        run_script = script;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        flush_run(&mut out, &run, run_script, &mut prev_cjk);
+    }
+    out
+}
+
+fn flush_run(out: &mut String, run: &str, script: Script, prev_cjk: &mut Script) {
+    if script == Script::Other {
+        out.push_str(run);
+        *prev_cjk = Script::Other;
+        return;
+    }
+    // Only CJK runs that directly follow another CJK run need a separator;
+    // existing whitespace or punctuation already acts as one.
+    if *prev_cjk != Script::Other {
+        out.push(' ');
+    }
+    if script == Script::Han {
+        push_words(out, run);
+    } else {
+        out.push_str(run);
+    }
+    *prev_cjk = script;
+}
+
+#[cfg(feature = "cjk-segment")]
+fn push_words(out: &mut String, run: &str) {
+    use jieba_rs::Jieba;
+    use std::sync::OnceLock;
+
+    // 사전 로딩이 무거우므로 프로세스당 한 번만 만든다.
+    static JIEBA: OnceLock<Jieba> = OnceLock::new();
+    let jieba = JIEBA.get_or_init(Jieba::new);
+
+    for (i, word) in jieba.cut(run, true).into_iter().enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+}
+
+#[cfg(not(feature = "cjk-segment"))]
+fn push_words(out: &mut String, run: &str) {
+    out.push_str(run);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "cjk-segment"))]
+    #[test]
+    fn test_japanese_script_boundaries() {
+        assert_eq!(segment_cjk("日本語を勉強する"), "日本語 を 勉強 する");
+        assert_eq!(segment_cjk("コーヒーを飲む"), "コーヒー を 飲 む");
+    }
+
+    #[test]
+    fn test_leaves_latin_and_hangul_alone() {
+        assert_eq!(segment_cjk("Hello, world"), "Hello, world");
+        assert_eq!(segment_cjk("한국어 문장입니다."), "한국어 문장입니다.");
+        assert_eq!(segment_cjk("API를 사용"), "API를 사용");
+    }
+
+    #[test]
+    fn test_script_change_always_splits() {
+        let segmented = segment_cjk("漢字ひらがなカタカナ");
+        assert!(segmented.contains(" ひらがな カタカナ"), "{segmented}");
+    }
+
+    #[test]
+    fn test_no_space_after_punctuation() {
+        assert_eq!(segment_cjk("東京、大阪。"), "東京、大阪。");
+    }
+
+    #[cfg(feature = "cjk-segment")]
+    #[test]
+    fn test_chinese_dictionary_segmentation() {
+        assert_eq!(segment_cjk("我们是学生"), "我们 是 学生");
+    }
+}
@@ -30,8 +30,14 @@
 //! }
 //! ```
 
-use crate::model::{Block, Document, Metadata};
+use std::cell::RefCell;
 
+use crate::model::{Block, Document, Metadata, Page, Paragraph};
+
+use super::markdown::{
+    definition_prefix, is_list_block, list_item_prefix, split_task_marker, ListCounter,
+};
+use super::warnings::{block_warnings, page_warnings, warning_comments};
 use super::{PageMarkerStyle, RenderOptions};
 
 /// Events emitted during streaming rendering.
@@ -39,8 +45,8 @@ use super::{PageMarkerStyle, RenderOptions};
 pub enum RenderEvent {
     /// Document rendering has started.
     DocumentStart {
-        /// Document metadata
-        metadata: Metadata,
+        /// Document metadata, boxed to keep the other events small
+        metadata: Box<Metadata>,
         /// Total number of pages
         page_count: u32,
     },
@@ -131,6 +137,7 @@ pub struct StreamingRenderer<'a> {
     options: RenderOptions,
     state: StreamState,
     current_page_number: u32,
+    list_counter: RefCell<ListCounter>,
 }
 
 impl<'a> StreamingRenderer<'a> {
@@ -141,6 +148,7 @@ impl<'a> StreamingRenderer<'a> {
             options,
             state: StreamState::Initial,
             current_page_number: 0,
+            list_counter: RefCell::new(ListCounter::default()),
         }
     }
 
@@ -159,6 +167,13 @@ impl<'a> StreamingRenderer<'a> {
         self.current_page_number
     }
 
+    /// Whether `page` opens with a marker, heading or warning block.
+    fn has_page_preamble(&self, page: &Page) -> bool {
+        self.options.page_markers == PageMarkerStyle::Comment
+            || self.options.page_heading_template.is_some()
+            || !self.render_page_warnings(page).is_empty()
+    }
+
     /// Find the next page that should be rendered (respecting page selection).
     fn find_next_page(&self, start_index: usize) -> Option<usize> {
         for i in start_index..self.doc.pages.len() {
@@ -177,38 +192,90 @@ impl<'a> StreamingRenderer<'a> {
         self.render_block(block)
     }
 
+    /// The page's extraction warnings as `<!-- unpdf: … -->` lines, when
+    /// `inline_warnings` is set; otherwise empty. Block warnings are part of
+    /// [`render_block_public`](Self::render_block_public).
+    pub fn render_page_warnings(&self, page: &Page) -> String {
+        if !self.options.inline_warnings {
+            return String::new();
+        }
+        warning_comments(&page_warnings(page))
+    }
+
     /// Render a single block to string.
     pub(crate) fn render_block(&self, block: &Block) -> String {
-        match block {
-            Block::Paragraph(p) => {
-                if p.is_empty() {
-                    return String::new();
-                }
+        if !self.options.keeps(block) {
+            return String::new();
+        }
+        let mut body = self.render_block_body(block);
+        if self.options.inline_warnings && !body.is_empty() {
+            body.insert_str(0, &warning_comments(&block_warnings(block)));
+        }
+        if !body.is_empty()
+            && !is_list_block(&self.options, block)
+            && self.list_counter.borrow_mut().close()
+        {
+            // 목록 뒤 블록이 마지막 항목의 연속 줄로 읽히지 않도록 빈 줄을 둔다.
+            return format!("\n{}", body);
+        }
+        body
+    }
 
-                let mut output = String::new();
+    /// A paragraph, heading or list item.
+    fn render_paragraph(&self, p: &Paragraph) -> String {
+        if p.is_empty() {
+            return String::new();
+        }
 
-                // Handle headings
-                if let Some(level) = p.style.heading_level {
-                    let level = level.min(self.options.max_heading_level);
-                    let prefix = "#".repeat(level as usize);
-                    output.push_str(&prefix);
-                    output.push(' ');
-                    self.render_inline_content(&mut output, &p.content);
-                    output.push_str("\n\n");
-                    return output;
-                }
+        let mut output = String::new();
+
+        // Handle headings
+        if let Some(level) = p.style.heading_level {
+            let level = level.min(self.options.max_heading_level);
+            let prefix = "#".repeat(level as usize);
+            output.push_str(&prefix);
+            output.push(' ');
+            self.render_inline_content(&mut output, &p.content);
+            output.push_str("\n\n");
+            return output;
+        }
 
-                // Handle list items
-                if let Some(ref list_info) = p.style.list_info {
-                    self.render_list_item(&mut output, p, list_info);
-                    return output;
-                }
+        // Handle definition list items
+        if let Some(term) = p.definition_term() {
+            output.push_str(&definition_prefix(&self.options, term));
+            self.render_inline_content(&mut output, &p.content);
+            output.push_str("\n\n");
+            return output;
+        }
 
-                // Normal paragraph
-                self.render_inline_content(&mut output, &p.content);
-                output.push_str("\n\n");
-                output
-            }
+        // Handle list items
+        let task = split_task_marker(&self.options, &p.content);
+        if p.style.list_info.is_some() || task.is_some() {
+            let (checked, content) = match task {
+                Some((checked, content)) => (Some(checked), content),
+                None => (None, p.content.clone()),
+            };
+            output.push_str(&list_item_prefix(
+                &self.options,
+                &mut self.list_counter.borrow_mut(),
+                p.style.list_info.as_ref(),
+                checked,
+            ));
+            self.render_inline_content(&mut output, &content);
+            output.push('\n');
+            return output;
+        }
+
+        // Normal paragraph
+        self.render_inline_content(&mut output, &p.content);
+        output.push_str("\n\n");
+        output
+    }
+
+    fn render_block_body(&self, block: &Block) -> String {
+        match block {
+            Block::Paragraph(p) => self.render_paragraph(p),
+            Block::List { items, .. } => items.iter().map(|p| self.render_paragraph(p)).collect(),
             Block::Table(t) => {
                 if t.is_empty() {
                     return String::new();
@@ -253,9 +320,12 @@ impl<'a> StreamingRenderer<'a> {
                 ..
             } => {
                 let alt = alt_text.as_deref().unwrap_or("");
-                let path = format!("{}{}", self.options.image_path_prefix, resource_id);
+                let path = self.options.image_target(resource_id, self.doc);
                 format!("![{}]({})\n\n", alt, path)
             }
+            Block::CodeBlock { language, content } => {
+                super::markdown::fenced_code_block(content, language.as_deref())
+            }
             Block::HorizontalRule => "\n---\n\n".to_string(),
             Block::PageBreak | Block::SectionBreak => "\n\n".to_string(),
             Block::Raw { content } => format!("{}\n\n", content),
@@ -287,7 +357,7 @@ impl<'a> StreamingRenderer<'a> {
                     alt_text,
                 } => {
                     let alt = alt_text.as_deref().unwrap_or("");
-                    let path = format!("{}{}", self.options.image_path_prefix, resource_id);
+                    let path = self.options.image_target(resource_id, self.doc);
                     output.push_str(&format!("![{}]({})", alt, path));
                 }
             }
@@ -329,43 +399,6 @@ impl<'a> StreamingRenderer<'a> {
 
         result
     }
-
-    fn render_list_item(
-        &self,
-        output: &mut String,
-        para: &crate::model::Paragraph,
-        list_info: &crate::model::ListInfo,
-    ) {
-        let indent = "  ".repeat(list_info.level as usize);
-
-        let marker = match &list_info.style {
-            crate::model::ListStyle::Unordered { .. } => {
-                format!("{}", self.options.list_marker)
-            }
-            crate::model::ListStyle::Ordered { number_style, .. } => {
-                let num = list_info.item_number.unwrap_or(1);
-                match number_style {
-                    crate::model::NumberStyle::Decimal => format!("{}.", num),
-                    crate::model::NumberStyle::LowerAlpha => {
-                        format!("{}.", char::from_u32('a' as u32 + num - 1).unwrap_or('a'))
-                    }
-                    crate::model::NumberStyle::UpperAlpha => {
-                        format!("{}.", char::from_u32('A' as u32 + num - 1).unwrap_or('A'))
-                    }
-                    crate::model::NumberStyle::LowerRoman => {
-                        format!("{}.", to_roman(num).to_lowercase())
-                    }
-                    crate::model::NumberStyle::UpperRoman => format!("{}.", to_roman(num)),
-                }
-            }
-        };
-
-        output.push_str(&indent);
-        output.push_str(&marker);
-        output.push(' ');
-        self.render_inline_content(output, &para.content);
-        output.push('\n');
-    }
 }
 
 impl<'a> Iterator for StreamingRenderer<'a> {
@@ -375,15 +408,13 @@ impl<'a> Iterator for StreamingRenderer<'a> {
         loop {
             match self.state {
                 StreamState::Initial => {
-                    if self.options.include_frontmatter {
+                    if let Some(frontmatter) = self.options.frontmatter_for(self.doc) {
                         self.state = StreamState::Frontmatter;
-                        return Some(RenderEvent::Frontmatter(
-                            self.doc.metadata.to_yaml_frontmatter(),
-                        ));
+                        return Some(RenderEvent::Frontmatter(frontmatter));
                     }
                     self.state = StreamState::DocumentStarted;
                     return Some(RenderEvent::DocumentStart {
-                        metadata: self.doc.metadata.clone(),
+                        metadata: Box::new(self.doc.metadata.clone()),
                         page_count: self.doc.page_count(),
                     });
                 }
@@ -391,7 +422,7 @@ impl<'a> Iterator for StreamingRenderer<'a> {
                 StreamState::Frontmatter => {
                     self.state = StreamState::DocumentStarted;
                     return Some(RenderEvent::DocumentStart {
-                        metadata: self.doc.metadata.clone(),
+                        metadata: Box::new(self.doc.metadata.clone()),
                         page_count: self.doc.page_count(),
                     });
                 }
@@ -401,7 +432,7 @@ impl<'a> Iterator for StreamingRenderer<'a> {
                     if let Some(page_idx) = self.find_next_page(0) {
                         let page = &self.doc.pages[page_idx];
                         self.current_page_number = page.number;
-                        if self.options.page_markers == PageMarkerStyle::Comment {
+                        if self.has_page_preamble(page) {
                             self.state = StreamState::PageMarker {
                                 page_index: page_idx,
                             };
@@ -453,7 +484,7 @@ impl<'a> Iterator for StreamingRenderer<'a> {
                     if let Some(page_idx) = self.find_next_page(next_page) {
                         let page = &self.doc.pages[page_idx];
                         self.current_page_number = page.number;
-                        if self.options.page_markers == PageMarkerStyle::Comment {
+                        if self.has_page_preamble(page) {
                             self.state = StreamState::PageMarker {
                                 page_index: page_idx,
                             };
@@ -473,7 +504,15 @@ impl<'a> Iterator for StreamingRenderer<'a> {
 
                 StreamState::PageMarker { page_index } => {
                     let page = &self.doc.pages[page_index];
-                    let marker = format!("<!-- page {} -->\n\n", page.number);
+                    let mut marker = String::new();
+                    if self.options.page_markers == PageMarkerStyle::Comment {
+                        marker.push_str(&format!("<!-- page {} -->\n\n", page.number));
+                    }
+                    if let Some(heading) = self.options.page_heading(page.number) {
+                        marker.push_str(&heading);
+                        marker.push_str("\n\n");
+                    }
+                    marker.push_str(&self.render_page_warnings(page));
                     self.state = StreamState::InPage {
                         page_index,
                         block_index: 0,
@@ -509,34 +548,6 @@ fn escape_markdown(text: &str) -> String {
     result
 }
 
-/// Convert number to Roman numerals.
-fn to_roman(mut num: u32) -> String {
-    let numerals = [
-        (1000, "M"),
-        (900, "CM"),
-        (500, "D"),
-        (400, "CD"),
-        (100, "C"),
-        (90, "XC"),
-        (50, "L"),
-        (40, "XL"),
-        (10, "X"),
-        (9, "IX"),
-        (5, "V"),
-        (4, "IV"),
-        (1, "I"),
-    ];
-
-    let mut result = String::new();
-    for (value, symbol) in numerals {
-        while num >= value {
-            result.push_str(symbol);
-            num -= value;
-        }
-    }
-    result
-}
-
 /// Collect all content from a streaming renderer into a single string.
 pub fn collect_content(renderer: StreamingRenderer<'_>) -> String {
     let mut output = String::new();
@@ -674,4 +685,27 @@ mod tests {
             content
         );
     }
+
+    #[test]
+    fn test_streaming_renderer_inlines_small_images() {
+        let mut doc = Document::new();
+        let mut page = Page::letter(1);
+        page.add_block(Block::image("page1_Im0.png"));
+        page.add_block(Block::image("page1_Im1.png"));
+        let small = crate::model::Resource::png(b"foo".to_vec());
+        let large = crate::model::Resource::png(vec![0; 100]);
+        page.images.push(("page1_Im0.png".into(), small));
+        page.images.push(("page1_Im1.png".into(), large));
+        doc.add_page(page);
+
+        let options = RenderOptions::default()
+            .with_image_prefix("images/")
+            .with_inline_images_max_bytes(64);
+        let content = collect_content(StreamingRenderer::new(&doc, options));
+        assert!(
+            content.contains("](data:image/png;base64,Zm9v)"),
+            "{content}"
+        );
+        assert!(content.contains("![](images/page1_Im1.png)"), "{content}");
+    }
 }
@@ -1,27 +1,516 @@
 //! Plain text rendering for PDF documents.
 
+use std::sync::Arc;
+
+use unicode_width::UnicodeWidthStr;
+
 use crate::error::Result;
-use crate::model::Document;
+use crate::model::{Document, ListStyle, Page, Paragraph, Table, Word};
+use crate::parser::Script;
 
-use super::{CleanupPipeline, RenderOptions};
+use super::bounded::{bound_output, MarkerSyntax};
+use super::emitter::{emit_block, BlockEmitter};
+use super::{CleanupPipeline, RenderOptions, TextLayout};
 
 /// Convert a document to plain text.
+///
+/// Paragraphs are cleaned (when cleanup is configured) and re-wrapped at
+/// `options.wrap_width` columns when it is set (see
+/// [`RenderOptions::wrap_width`]). Tables are laid out as aligned
+/// columns and code listings are copied verbatim; neither goes through
+/// cleanup, which would collapse their spacing. No Markdown syntax or
+/// escaping is ever produced.
+///
+/// With [`TextLayout::Preserve`] pages that carry words are instead laid out
+/// as on the page; see [`TextLayout`].
+///
+/// Output over `options.max_output_bytes` is bounded as `options.overflow`
+/// says; when splitting, this is the first part (see [`to_text_parts`]).
 pub fn to_text(doc: &Document, options: &RenderOptions) -> Result<String> {
-    let mut output = doc.plain_text();
+    to_text_with_pipeline(doc, options, None)
+}
+
+/// Convert a document to plain text, split into parts of at most
+/// `options.max_output_bytes` each when the overflow strategy is
+/// [`OverflowStrategy::Split`](super::OverflowStrategy::Split).
+pub fn to_text_parts(doc: &Document, options: &RenderOptions) -> Result<Vec<String>> {
+    let text = render_text(doc, options, None);
+    Ok(bound_output(text, options, MarkerSyntax::Bracket))
+}
+
+/// [`to_text`] with an already compiled cleanup pipeline matching
+/// `options.cleanup`.
+pub(crate) fn to_text_with_pipeline(
+    doc: &Document,
+    options: &RenderOptions,
+    pipeline: Option<Arc<CleanupPipeline>>,
+) -> Result<String> {
+    let text = render_text(doc, options, pipeline);
+    Ok(bound_output(text, options, MarkerSyntax::Bracket).swap_remove(0))
+}
 
-    // Apply cleanup if configured
-    if let Some(ref cleanup_options) = options.cleanup {
-        let pipeline = CleanupPipeline::new(cleanup_options.clone());
-        output = pipeline.process(&output);
+fn render_text(
+    doc: &Document,
+    options: &RenderOptions,
+    pipeline: Option<Arc<CleanupPipeline>>,
+) -> String {
+    let (stripped, _) = options.prepare_document(doc);
+    let doc = &*stripped;
+    if options.text_layout == TextLayout::Preserve {
+        return to_layout_text(doc, options);
     }
+    let mut renderer = TextRenderer::new(options, false);
+    if pipeline.is_some() {
+        renderer.cleanup = pipeline;
+    }
+    let mut output = String::new();
+    for page in &doc.pages {
+        if !options.page_selection.includes(page.number) {
+            continue;
+        }
+        // 제목은 문단 정리를 거치지 않고 그대로 쓴다.
+        if let Some(heading) = options.page_heading(page.number) {
+            output.push_str(&heading);
+            output.push_str("\n\n");
+        }
+        for block in page.elements.iter().filter(|b| options.keeps(b)) {
+            emit_block(&mut renderer, &mut output, block);
+        }
+    }
+    output.trim().to_string()
+}
+
+/// Convert a document to text with no line breaks inside blocks.
+///
+/// Every paragraph, table or code listing becomes a single line with its
+/// whitespace collapsed, and blocks are joined with `separator` (e.g.
+/// `"\n\n"`, or a custom delimiter such as `" ¶ "`). Table cells are separated
+/// by tabs and rows by `"; "`. Meant for embedding models and other
+/// consumers that treat newlines as noise; `wrap_width` is ignored.
+pub fn to_single_line_text(
+    doc: &Document,
+    options: &RenderOptions,
+    separator: &str,
+) -> Result<String> {
+    let mut renderer = TextRenderer::new(options, true);
+    let mut blocks = Vec::new();
+    for page in &options.prepare_document(doc).0.pages {
+        if !options.page_selection.includes(page.number) {
+            continue;
+        }
+        for block in page.elements.iter().filter(|b| options.keeps(b)) {
+            let mut out = String::new();
+            emit_block(&mut renderer, &mut out, block);
+            let line = flatten_whitespace(&out);
+            if !line.is_empty() {
+                blocks.push(line);
+            }
+        }
+    }
+    Ok(bound_output(blocks.join(separator), options, MarkerSyntax::Bracket).swap_remove(0))
+}
+
+fn to_layout_text(doc: &Document, options: &RenderOptions) -> String {
+    let mut renderer = TextRenderer::new(options, false);
+    let mut pages = Vec::new();
+    for page in &doc.pages {
+        if !options.page_selection.includes(page.number) {
+            continue;
+        }
+        let text = layout_page(page).unwrap_or_else(|| {
+            let mut out = String::new();
+            for block in page.elements.iter().filter(|b| options.keeps(b)) {
+                emit_block(&mut renderer, &mut out, block);
+            }
+            out
+        });
+        let text = match options.page_heading(page.number) {
+            Some(heading) => format!("{}\n\n{}", heading, text.trim_end()),
+            None => text.trim_end().to_string(),
+        };
+        pages.push(text);
+    }
+    pages.join("\n\x0c")
+}
+
+/// A page's words set out on a character grid: one row per printed line,
+/// each word starting at about its column on the page, blank rows for
+/// vertical gaps. `None` when the page has no words.
+fn layout_page(page: &Page) -> Option<String> {
+    if page.words.is_empty() {
+        return None;
+    }
+    let height = |w: &Word| w.bbox[3] - w.bbox[1];
+    let char_width = median(page.words.iter().filter_map(|w| {
+        let columns = text_width(&w.text) as f32;
+        let width = w.bbox[2] - w.bbox[0];
+        (width > 0.0 && columns > 0.0).then(|| width / columns)
+    }))
+    .unwrap_or_else(|| median(page.words.iter().map(height)).unwrap_or(10.0) * 0.5)
+    .max(0.5);
+    let line_height = median(page.words.iter().map(height))
+        .unwrap_or(10.0)
+        .max(1.0)
+        * 1.2;
+    let left = page
+        .words
+        .iter()
+        .map(|w| w.bbox[0])
+        .fold(f32::MAX, f32::min);
+
+    // 단이 나뉜 페이지도 같은 높이의 줄은 한 행에 놓는다.
+    let mut words: Vec<&Word> = page.words.iter().collect();
+    words.sort_by(|a, b| b.bbox[1].total_cmp(&a.bbox[1]));
+    let mut rows: Vec<(f32, Vec<&Word>)> = Vec::new();
+    for word in words {
+        match rows.last_mut() {
+            Some((y, row)) if *y - word.bbox[1] < height(word) * 0.5 => row.push(word),
+            _ => rows.push((word.bbox[1], vec![word])),
+        }
+    }
+
+    let mut out = String::new();
+    let mut previous_y: Option<f32> = None;
+    for (y, mut row) in rows {
+        if let Some(previous) = previous_y {
+            let blank = ((previous - y) / line_height).round() as usize;
+            for _ in 1..blank {
+                out.push('\n');
+            }
+        }
+        previous_y = Some(y);
+
+        row.sort_by(|a, b| a.bbox[0].total_cmp(&b.bbox[0]));
+        let mut line = String::new();
+        let mut len = 0;
+        for word in row {
+            let column = ((word.bbox[0] - left) / char_width).round() as usize;
+            let column = if len == 0 {
+                column
+            } else {
+                column.max(len + 1)
+            };
+            line.extend(std::iter::repeat(' ').take(column - len));
+            line.push_str(&word.text);
+            len = column + text_width(&word.text);
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Some(out)
+}
+
+fn median(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let mut values: Vec<f32> = values.collect();
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f32::total_cmp);
+    Some(values[values.len() / 2])
+}
 
-    Ok(output.trim().to_string())
+struct TextRenderer<'a> {
+    options: &'a RenderOptions,
+    cleanup: Option<Arc<CleanupPipeline>>,
+    single_line: bool,
+}
+
+impl<'a> TextRenderer<'a> {
+    fn new(options: &'a RenderOptions, single_line: bool) -> Self {
+        Self {
+            options,
+            cleanup: options
+                .cleanup
+                .clone()
+                .map(|cleanup| Arc::new(CleanupPipeline::new(cleanup))),
+            single_line,
+        }
+    }
+
+    /// Clean and wrap prose, then append it as its own block.
+    fn push_prose(&self, out: &mut String, text: &str, indent: &str, first_prefix: &str) {
+        let text = match &self.cleanup {
+            Some(pipeline) => pipeline.process(text),
+            None => text.trim().to_string(),
+        };
+        if text.is_empty() {
+            return;
+        }
+        let width = if self.single_line {
+            0
+        } else {
+            self.options.text_wrap_width()
+        };
+        let lines = if width == 0 {
+            text.lines().map(str::to_string).collect()
+        } else {
+            wrap_paragraph(&text, width.saturating_sub(text_width(indent)).max(1))
+        };
+        let mut prefix = first_prefix;
+        for line in lines {
+            out.push_str(prefix);
+            out.push_str(&line);
+            out.push('\n');
+            prefix = indent;
+        }
+        out.push('\n');
+    }
+}
+
+impl BlockEmitter for TextRenderer<'_> {
+    fn paragraph(&mut self, out: &mut String, para: &Paragraph) {
+        let Some(list_info) = &para.style.list_info else {
+            self.push_prose(out, &para.plain_text(), "", "");
+            return;
+        };
+        let marker = match &list_info.style {
+            ListStyle::Unordered { .. } => format!("{} ", self.options.list_marker),
+            ListStyle::Ordered { .. } => format!("{}. ", list_info.item_number.unwrap_or(1)),
+            // 용어는 plain_text 가 "용어: 정의" 로 앞에 붙인다.
+            ListStyle::Definition { .. } => String::new(),
+        };
+        let nesting = "  ".repeat(list_info.level as usize);
+        let indent = format!("{}{}", nesting, " ".repeat(text_width(&marker)));
+        let first = format!("{}{}", nesting, marker);
+        self.push_prose(out, &para.plain_text(), &indent, &first);
+    }
+
+    fn table(&mut self, out: &mut String, table: &Table) {
+        let table_text = if self.single_line {
+            table
+                .text_grid()
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|c| flatten_whitespace(c))
+                        .collect::<Vec<_>>()
+                })
+                .filter(|row| row.iter().any(|c| !c.is_empty()))
+                .map(|row| row.join("\t"))
+                .collect::<Vec<_>>()
+                .join("; ")
+        } else {
+            layout_table(table)
+        };
+        if !table_text.is_empty() {
+            out.push_str(&table_text);
+            out.push_str("\n\n");
+        }
+    }
+
+    fn image(&mut self, _out: &mut String, _resource_id: &str, _alt_text: Option<&str>) {}
+
+    fn code_block(&mut self, out: &mut String, content: &str, _language: Option<&str>) {
+        let content = content.trim_end();
+        if !content.is_empty() {
+            out.push_str(content);
+            out.push_str("\n\n");
+        }
+    }
+
+    fn horizontal_rule(&mut self, _out: &mut String) {}
+
+    fn raw(&mut self, out: &mut String, content: &str) {
+        self.push_prose(out, content, "", "");
+    }
+}
+
+/// Lay a table out as space-padded columns, with a dashed rule under the
+/// header rows. Columns whose body cells are all numbers are right-aligned.
+fn layout_table(table: &Table) -> String {
+    let grid: Vec<Vec<String>> = table
+        .text_grid()
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|cell| cell.split_whitespace().collect::<Vec<_>>().join(" "))
+                .collect()
+        })
+        .filter(|row: &Vec<String>| row.iter().any(|c| !c.is_empty()))
+        .collect();
+    let columns = grid.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+
+    let header_rows = (table.header_rows as usize).min(grid.len());
+    let cell = |row: &[String], col: usize| row.get(col).map_or("", String::as_str).to_string();
+    let widths: Vec<usize> = (0..columns)
+        .map(|col| {
+            grid.iter()
+                .map(|row| text_width(&cell(row, col)))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let numeric: Vec<bool> = (0..columns)
+        .map(|col| {
+            let mut body = grid[header_rows..]
+                .iter()
+                .map(|row| cell(row, col))
+                .filter(|c| !c.is_empty())
+                .peekable();
+            body.peek().is_some() && body.all(|c| is_numeric(&c))
+        })
+        .collect();
+
+    let mut lines = Vec::with_capacity(grid.len() + 1);
+    for (r, row) in grid.iter().enumerate() {
+        let cells: Vec<String> = (0..columns)
+            .map(|col| {
+                pad(
+                    &cell(row, col),
+                    widths[col],
+                    numeric[col] && r >= header_rows,
+                )
+            })
+            .collect();
+        lines.push(cells.join("  ").trim_end().to_string());
+        if header_rows > 0 && r + 1 == header_rows {
+            let rule: Vec<String> = widths.iter().map(|w| "-".repeat((*w).max(1))).collect();
+            lines.push(rule.join("  "));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Collapse every run of whitespace other than tabs into one space.
+fn flatten_whitespace(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut pending_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() && c != '\t' {
+            pending_space = true;
+            continue;
+        }
+        if pending_space && !result.is_empty() && c != '\t' && !result.ends_with('\t') {
+            result.push(' ');
+        }
+        pending_space = false;
+        result.push(c);
+    }
+    result
+}
+
+/// Whether a cell reads as a number, allowing grouping commas, a sign,
+/// a percent sign and common currency symbols.
+fn is_numeric(text: &str) -> bool {
+    let core: String = text
+        .trim_matches(|c: char| "$€£¥₩%+-()".contains(c) || c.is_whitespace())
+        .chars()
+        .filter(|c| *c != ',')
+        .collect();
+    !core.is_empty() && core.parse::<f64>().is_ok()
+}
+
+/// Greedily re-wrap a paragraph at `width` columns. Source line breaks
+/// become spaces, or nothing between two Chinese or Japanese characters.
+/// Lines break at spaces and between Chinese or Japanese characters, never
+/// before closing or after opening punctuation; a word wider than the line
+/// gets a line of its own.
+fn wrap_paragraph(text: &str, width: usize) -> Vec<String> {
+    let mut joined = String::with_capacity(text.len());
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let run_on = matches!(
+            (joined.chars().last(), line.chars().next()),
+            (Some(a), Some(b)) if is_spaceless(a) && is_spaceless(b)
+        );
+        if !joined.is_empty() && !run_on {
+            joined.push(' ');
+        }
+        joined.push_str(line);
+    }
+
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+    for word in joined.split_whitespace() {
+        for (i, unit) in break_units(word).into_iter().enumerate() {
+            let space = usize::from(i == 0 && !current.is_empty());
+            let unit_width = text_width(unit);
+            if current_width + space + unit_width > width && !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+                current_width = 0;
+            } else if space == 1 {
+                current.push(' ');
+                current_width += 1;
+            }
+            current.push_str(unit);
+            current_width += unit_width;
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Split a word where a line may break inside it: before and after each
+/// Chinese or Japanese character, keeping punctuation with its neighbour.
+fn break_units(word: &str) -> Vec<&str> {
+    let mut units = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<char> = None;
+    for (i, c) in word.char_indices() {
+        if let Some(p) = previous {
+            let breakable = (is_spaceless(p) || is_spaceless(c))
+                && !CLOSING_PUNCTUATION.contains(c)
+                && !OPENING_PUNCTUATION.contains(p);
+            if breakable {
+                units.push(&word[start..i]);
+                start = i;
+            }
+        }
+        previous = Some(c);
+    }
+    units.push(&word[start..]);
+    units
+}
+
+/// 줄 머리에 올 수 없는 문자.
+const CLOSING_PUNCTUATION: &str = "、。，．！？：；）」』】〕〉》ー…・,.!?:;)]}%";
+/// 줄 끝에 올 수 없는 문자.
+const OPENING_PUNCTUATION: &str = "（「『【〔〈《([{";
+
+/// Scripts written without spaces between words.
+fn is_spaceless(c: char) -> bool {
+    matches!(
+        Script::of(c),
+        Script::Han | Script::Kana | Script::CjkSymbol
+    )
+}
+
+/// Terminal columns `text` takes: two for East Asian wide characters
+/// (Hangul, Han, Kana, fullwidth forms) and emoji, none for combining marks.
+fn text_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// `text` padded with spaces to `width` columns, on the left when
+/// `right_align`.
+fn pad(text: &str, width: usize, right_align: bool) -> String {
+    let fill = " ".repeat(width.saturating_sub(text_width(text)));
+    if right_align {
+        fill + text
+    } else {
+        format!("{text}{fill}")
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{Page, Paragraph};
+    use crate::model::{Block, Page, TableCell, TableRow};
+
+    fn doc_with(blocks: Vec<Block>) -> Document {
+        let mut doc = Document::new();
+        let mut page = Page::letter(1);
+        for block in blocks {
+            page.add_block(block);
+        }
+        doc.add_page(page);
+        doc
+    }
 
     #[test]
     fn test_to_text() {
@@ -37,4 +526,148 @@ mod tests {
         assert!(result.contains("Hello, world!"));
         assert!(result.contains("Second paragraph."));
     }
+
+    #[test]
+    fn test_table_aligned_without_markdown() {
+        let mut table = Table::with_header(1);
+        table.add_row(TableRow::header(vec![
+            TableCell::text("Item"),
+            TableCell::text("Qty"),
+        ]));
+        table.add_row(TableRow::new(vec![
+            TableCell::text("a|b_c"),
+            TableCell::text("12"),
+        ]));
+        table.add_row(TableRow::new(vec![
+            TableCell::text("Widget"),
+            TableCell::text("3"),
+        ]));
+        let doc = doc_with(vec![Block::Table(table)]);
+
+        let text = to_text(&doc, &RenderOptions::default()).unwrap();
+        assert_eq!(text, "Item    Qty\n------  ---\na|b_c    12\nWidget    3");
+    }
+
+    #[test]
+    fn test_table_aligns_wide_characters() {
+        let mut table = Table::with_header(1);
+        table.add_row(TableRow::header(vec![
+            TableCell::text("품목"),
+            TableCell::text("수량"),
+        ]));
+        table.add_row(TableRow::new(vec![
+            TableCell::text("사과 🍎"),
+            TableCell::text("12"),
+        ]));
+        table.add_row(TableRow::new(vec![
+            TableCell::text("Pear"),
+            TableCell::text("3"),
+        ]));
+        let doc = doc_with(vec![Block::Table(table)]);
+
+        // 한글과 이모지는 두 칸을 차지하므로 다음 열이 같은 칸에서 시작한다.
+        let text = to_text(&doc, &RenderOptions::default()).unwrap();
+        assert_eq!(
+            text,
+            "품목     수량\n-------  ----\n사과 🍎    12\nPear        3"
+        );
+    }
+
+    #[test]
+    fn test_layout_counts_wide_characters() {
+        let word = |text: &str, x: f32, width: f32, y: f32| Word {
+            text: text.to_string(),
+            bbox: [x, y, x + width, y + 10.0],
+            line: 0,
+        };
+        let mut page = Page::letter(1);
+        page.words = vec![
+            word("이름", 72.0, 20.0, 700.0),
+            word("Score", 172.0, 25.0, 700.0),
+            word("Kim", 72.0, 15.0, 688.0),
+            word("90", 172.0, 10.0, 688.0),
+        ];
+
+        let text = layout_page(&page).unwrap();
+        let starts: Vec<usize> = text
+            .lines()
+            .map(|line| text_width(&line[..line.rfind(' ').unwrap() + 1]))
+            .collect();
+        assert_eq!(starts, [20, 20], "{}", text);
+    }
+
+    #[test]
+    fn test_wraps_at_line_width() {
+        let doc = doc_with(vec![
+            Block::Paragraph(Paragraph::with_text(
+                "The quick brown fox jumps over the lazy dog",
+            )),
+            Block::code("let x  =  1;", None),
+        ]);
+        let options = RenderOptions::default().with_line_width(16);
+        let text = to_text(&doc, &options).unwrap();
+        assert_eq!(
+            text,
+            "The quick brown\nfox jumps over\nthe lazy dog\n\nlet x  =  1;"
+        );
+    }
+
+    #[test]
+    fn test_wrap_width_rewraps_source_lines() {
+        let doc = doc_with(vec![Block::Paragraph(Paragraph::with_text(
+            "The quick\nbrown fox jumps over\nthe lazy dog",
+        ))]);
+        let mut options = RenderOptions::default().with_wrap_width(Some(20));
+        options.cleanup = None;
+        let text = to_text(&doc, &options).unwrap();
+        assert_eq!(text, "The quick brown fox\njumps over the lazy\ndog");
+    }
+
+    #[test]
+    fn test_wrap_width_counts_cjk_as_two_columns() {
+        // 한국어는 띄어쓰기에서만, 중국어·일본어는 글자 사이에서도 끊는다.
+        assert_eq!(
+            wrap_paragraph("한국어 문서를 터미널에서 읽는다", 14),
+            ["한국어 문서를", "터미널에서", "읽는다"]
+        );
+        assert_eq!(
+            wrap_paragraph("日本語の文書を読む。", 8),
+            ["日本語の", "文書を読", "む。"]
+        );
+        // 원문 줄바꿈은 한자 사이에서 공백 없이 이어진다.
+        assert_eq!(wrap_paragraph("中文\n文本", 20), ["中文文本"]);
+        assert_eq!(text_width("ab한글漢字"), 10);
+        assert_eq!(text_width("ｶﾀｶﾅ"), 4);
+        assert_eq!(text_width("e\u{301}🎉"), 3);
+    }
+
+    #[test]
+    fn test_wrap_keeps_punctuation_with_its_neighbour() {
+        assert_eq!(break_units("「日本」。"), ["「日", "本」。"]);
+        assert_eq!(
+            break_units("使用Rust编写"),
+            ["使", "用", "Rust", "编", "写"]
+        );
+    }
+
+    #[test]
+    fn test_single_line_text() {
+        let mut table = Table::with_header(1);
+        table.add_row(TableRow::header(vec![
+            TableCell::text("Item"),
+            TableCell::text("Qty"),
+        ]));
+        table.add_row(TableRow::from_strings(["Bolt", "4"]));
+        let doc = doc_with(vec![
+            Block::Paragraph(Paragraph::with_text("First line\nwrapped  here")),
+            Block::Table(table),
+            Block::code("fn main() {\n    run();\n}", None),
+        ]);
+
+        let text = to_single_line_text(&doc, &RenderOptions::default(), " | ").unwrap();
+        assert_eq!(
+            text,
+            "First line wrapped here | Item\tQty; Bolt\t4 | fn main() { run(); }"
+        );
+    }
 }
@@ -144,6 +144,19 @@ pub trait DocumentVisitor: Send + Sync {
         VisitorAction::Continue
     }
 
+    /// Called before rendering a code block.
+    ///
+    /// # Arguments
+    /// * `content` - The code text, lines separated by `\n`
+    /// * `language` - Guessed language, if any
+    ///
+    /// # Returns
+    /// Action indicating how to handle this code block
+    fn visit_code_block(&mut self, content: &str, language: Option<&str>) -> VisitorAction {
+        let _ = (content, language);
+        VisitorAction::Continue
+    }
+
     /// Called at the start of rendering a new page.
     ///
     /// # Arguments
@@ -317,6 +330,16 @@ impl DocumentVisitor for CompositeVisitor {
         VisitorAction::Continue
     }
 
+    fn visit_code_block(&mut self, content: &str, language: Option<&str>) -> VisitorAction {
+        for visitor in &mut self.visitors {
+            let action = visitor.visit_code_block(content, language);
+            if !matches!(action, VisitorAction::Continue) {
+                return action;
+            }
+        }
+        VisitorAction::Continue
+    }
+
     fn on_page_start(&mut self, page_number: u32) {
         for visitor in &mut self.visitors {
             visitor.on_page_start(page_number);
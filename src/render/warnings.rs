@@ -0,0 +1,105 @@
+//! Non-fatal extraction warnings tied to where they occur in the output.
+//!
+//! With `RenderOptions::inline_warnings` set, Markdown output carries each
+//! one as an `<!-- unpdf: … -->` comment just before the page or block it
+//! concerns, so a reviewer editing the file sees where to double-check.
+
+use crate::model::{Block, Page, TextStrategy};
+
+/// Tables scored below this were kept with at least one penalty (few rows,
+/// many columns, sparse cells) and are worth a second look.
+const LOW_TABLE_CONFIDENCE: f32 = 0.75;
+
+/// Warnings about the page as a whole.
+pub(crate) fn page_warnings(page: &Page) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if page.ocr_text_suppressed {
+        warnings.push("OCR text layer dropped as unreadable".to_string());
+    } else if page.text_op_count == 0 && page.image_op_count > 0 {
+        warnings.push("scanned page without a text layer".to_string());
+    }
+    if page.text_strategy == TextStrategy::RawText {
+        warnings.push("layout analysis failed; text is in content-stream order".to_string());
+    }
+    if page.skipped_content_bytes > 0 {
+        warnings.push(format!(
+            "{} bytes of unreadable page content skipped",
+            page.skipped_content_bytes
+        ));
+    }
+    warnings
+}
+
+/// Warnings about one block.
+pub(crate) fn block_warnings(block: &Block) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if let Block::Table(table) = block {
+        if let Some(detection) = table.detection.as_ref() {
+            if detection.confidence < LOW_TABLE_CONFIDENCE {
+                warnings.push(format!(
+                    "table detection low confidence ({:.2})",
+                    detection.confidence
+                ));
+            }
+        }
+    }
+    let mut text = String::new();
+    block.append_plain_text(&mut text);
+    let undecoded = text.chars().filter(|&c| c == '\u{FFFD}').count();
+    if undecoded > 0 {
+        warnings.push(format!("{undecoded} undecodable character(s)"));
+    }
+    warnings
+}
+
+/// `warnings` as HTML comment lines closed by a blank line, so cleanup does
+/// not join the last one with the text after it; empty for no warnings.
+pub(crate) fn warning_comments(warnings: &[String]) -> String {
+    let mut out = String::new();
+    for warning in warnings {
+        // 주석 안에는 `--` 가 올 수 없다.
+        out.push_str(&format!(
+            "<!-- unpdf: {} -->\n",
+            warning.replace("--", "- -")
+        ));
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Paragraph, Table, TableDetection};
+
+    #[test]
+    fn test_block_warnings() {
+        let mut table = Table::new();
+        table.detection = Some(TableDetection {
+            bbox: [0.0; 4],
+            confidence: 0.42,
+            alignment: Vec::new(),
+            rejected: None,
+        });
+        assert_eq!(
+            block_warnings(&Block::Table(table)),
+            ["table detection low confidence (0.42)"]
+        );
+
+        let para = Block::Paragraph(Paragraph::with_text("caf\u{FFFD} \u{FFFD}"));
+        assert_eq!(block_warnings(&para), ["2 undecodable character(s)"]);
+        assert!(block_warnings(&Block::Paragraph(Paragraph::with_text("ok"))).is_empty());
+    }
+
+    #[test]
+    fn test_warning_comments() {
+        let warnings = ["a --> b".to_string(), "c".to_string()];
+        assert_eq!(
+            warning_comments(&warnings),
+            "<!-- unpdf: a - -> b -->\n<!-- unpdf: c -->\n\n"
+        );
+        assert_eq!(warning_comments(&[]), "");
+    }
+}
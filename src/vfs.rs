@@ -0,0 +1,151 @@
+//! Pluggable file access.
+//!
+//! The path-based entry points read straight from the local disk. A
+//! [`FileSource`] lets the same parsing run against in-memory archives,
+//! object storage or zip containers without first copying each PDF to a
+//! temporary file:
+//!
+//! ```
+//! use unpdf::vfs::MemoryFileSource;
+//!
+//! let source = MemoryFileSource::new().with_file("inbox/report.pdf", b"%PDF-1.4".to_vec());
+//! // A truncated file still fails to parse, but is read from memory.
+//! assert!(unpdf::parse_from_source(&source, "inbox/report.pdf").is_err());
+//! assert!(unpdf::parse_from_source(&source, "missing.pdf").is_err());
+//! ```
+
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// A file system the parser reads PDFs from.
+///
+/// Only [`FileSource::open`] is required; override [`FileSource::read`]
+/// when the backing store can hand out a whole object more cheaply than
+/// through a reader (for example a single ranged GET).
+pub trait FileSource {
+    /// Open `path` for reading.
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + '_>>;
+
+    /// Read the whole of `path`.
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.open(path)?.read_to_end(&mut data)?;
+        Ok(data)
+    }
+}
+
+impl<T: FileSource + ?Sized> FileSource for &T {
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + '_>> {
+        (**self).open(path)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        (**self).read(path)
+    }
+}
+
+impl<T: FileSource + ?Sized> FileSource for Box<T> {
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + '_>> {
+        (**self).open(path)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        (**self).read(path)
+    }
+}
+
+/// The local file system, as used by [`crate::parse_file`].
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdFileSource;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileSource for StdFileSource {
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + '_>> {
+        Ok(Box::new(std::fs::File::open(path)?))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+}
+
+/// Files held in memory, keyed by path.
+///
+/// Useful for archives unpacked in memory and for tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFileSource {
+    files: HashMap<PathBuf, Vec<u8>>,
+}
+
+impl MemoryFileSource {
+    /// Create an empty file source.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, replacing any previous one at `path`.
+    pub fn with_file(mut self, path: impl Into<PathBuf>, data: Vec<u8>) -> Self {
+        self.insert(path, data);
+        self
+    }
+
+    /// Add a file, returning the previous contents at `path`.
+    pub fn insert(&mut self, path: impl Into<PathBuf>, data: Vec<u8>) -> Option<Vec<u8>> {
+        self.files.insert(path.into(), data)
+    }
+
+    /// Paths of the files held, in no particular order.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    fn get(&self, path: &Path) -> io::Result<&[u8]> {
+        self.files.get(path).map(Vec::as_slice).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{}: not found", path.display()),
+            )
+        })
+    }
+}
+
+impl FileSource for MemoryFileSource {
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + '_>> {
+        Ok(Box::new(self.get(path)?))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.get(path).map(<[u8]>::to_vec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A source that only implements `open`, to exercise the default `read`.
+    struct OpenOnly(MemoryFileSource);
+
+    impl FileSource for OpenOnly {
+        fn open(&self, path: &Path) -> io::Result<Box<dyn Read + '_>> {
+            self.0.open(path)
+        }
+    }
+
+    #[test]
+    fn test_memory_source_reads_and_reports_missing() {
+        let source = MemoryFileSource::new().with_file("a/b.pdf", b"data".to_vec());
+        assert_eq!(source.read(Path::new("a/b.pdf")).unwrap(), b"data");
+        let err = source.read(Path::new("a/c.pdf")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        let by_ref: &dyn FileSource = &source;
+        assert_eq!(
+            OpenOnly(source.clone()).read(Path::new("a/b.pdf")).unwrap(),
+            b"data"
+        );
+        assert_eq!(by_ref.read(Path::new("a/b.pdf")).unwrap(), b"data");
+    }
+}
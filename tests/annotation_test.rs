@@ -0,0 +1,48 @@
+//! Annotation extraction and Markdown rendering.
+
+mod common;
+
+use unpdf::{parse_bytes, render, AnnotationKind, AnnotationStyle, RenderOptions};
+
+#[test]
+fn test_highlight_and_note_extracted() {
+    let doc = parse_bytes(&common::annotated_pdf()).unwrap();
+    let annotations: Vec<_> = doc.annotations().collect();
+    assert_eq!(annotations.len(), 2, "{:?}", annotations);
+
+    let (page, highlight) = annotations[0];
+    assert_eq!(page, 1);
+    assert_eq!(highlight.kind, AnnotationKind::Highlight);
+    assert_eq!(highlight.author.as_deref(), Some("Dana"));
+    assert_eq!(highlight.contents.as_deref(), Some("Which world?"));
+    assert_eq!(highlight.covered_text.as_deref(), Some("World"));
+    assert_eq!(
+        highlight.modified.map(|d| d.to_rfc3339()),
+        Some("2024-03-01T09:30:00+00:00".to_string())
+    );
+
+    let (_, note) = annotations[1];
+    assert_eq!(note.kind, AnnotationKind::Note);
+    assert_eq!(note.covered_text, None);
+}
+
+#[test]
+fn test_annotations_rendered_only_on_request() {
+    let doc = parse_bytes(&common::annotated_pdf()).unwrap();
+
+    let plain = render::to_markdown(&doc, &RenderOptions::new()).unwrap();
+    assert!(!plain.contains("Which world"), "{}", plain);
+
+    let options = RenderOptions::new().with_annotations(AnnotationStyle::Footnote);
+    let markdown = render::to_markdown(&doc, &options).unwrap();
+    assert!(
+        markdown.contains("Hello World[^note-1][^note-2]"),
+        "{}",
+        markdown
+    );
+    assert!(
+        markdown.contains("[^note-1]: **Highlight** (Dana, 2024-03-01): “World” — Which world?"),
+        "{}",
+        markdown
+    );
+}
@@ -0,0 +1,30 @@
+//! Block-type filters on `ParseOptions`.
+//!
+//! Pruning happens after quality metrics are gathered, so a document parsed
+//! with only images kept is still reported as having text.
+
+mod common;
+
+use common::mixed_pdf;
+use unpdf::{parse_bytes_with_options, BlockFilter, ParseOptions};
+
+#[test]
+fn skip_images_drops_image_blocks_and_resources() {
+    let options = ParseOptions::new().with_resources(true).skip_images();
+    let doc = parse_bytes_with_options(&mixed_pdf(), options).unwrap();
+
+    assert_eq!(doc.pages.len(), 2);
+    assert!(doc.resources.is_empty());
+    assert!(doc.pages.iter().all(|p| p.images.is_empty()));
+    assert!(doc.plain_text().contains("Hello World"));
+}
+
+#[test]
+fn filtering_text_out_keeps_quality_metrics() {
+    let filter = BlockFilter::none().with_images(true);
+    let options = ParseOptions::new().with_block_filter(filter);
+    let doc = parse_bytes_with_options(&mixed_pdf(), options).unwrap();
+
+    assert!(!doc.plain_text().contains("Hello"));
+    assert!(!doc.extraction_quality.is_scan_pdf);
+}
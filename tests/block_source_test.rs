@@ -0,0 +1,60 @@
+mod common;
+
+use unpdf::{parse_bytes, render, BlockSource, JsonFormat};
+
+#[test]
+fn test_block_source_names_stream_and_operator() {
+    let doc = parse_bytes(&common::text_pdf()).unwrap();
+    let block = &doc.pages[0].elements[0];
+    // BT(0) Tf(1) Td(2) Tj(3) ET(4)
+    assert_eq!(
+        doc.block_source(block),
+        Some(&BlockSource {
+            streams: vec![(4, 0)],
+            first_op: 3,
+            last_op: 3,
+        })
+    );
+}
+
+#[test]
+fn test_block_source_spans_content_streams() {
+    let pdf = common::two_stream_pdf(
+        b"BT /F1 12 Tf 72 720 Td (First part of the paragraph) Tj ET\n",
+        b"BT /F1 12 Tf 72 706 Td (and its second line.) Tj ET\n",
+    );
+    let doc = parse_bytes(&pdf).unwrap();
+    let sources: Vec<&BlockSource> = doc.pages[0]
+        .elements
+        .iter()
+        .filter_map(|b| doc.block_source(b))
+        .collect();
+    let streams: Vec<(u32, u16)> = sources.iter().flat_map(|s| s.streams.clone()).collect();
+    assert!(
+        streams.contains(&(4, 0)) && streams.contains(&(6, 0)),
+        "{:?}",
+        sources
+    );
+    // Operators are numbered across both streams: the second Tj is the 9th.
+    assert_eq!(sources.iter().map(|s| s.last_op).max(), Some(8));
+}
+
+#[test]
+fn test_block_source_needs_document_reference() {
+    let doc = parse_bytes(&common::text_pdf()).unwrap();
+    let copy = doc.pages[0].elements[0].clone();
+    assert!(doc.block_source(&copy).is_none());
+}
+
+#[test]
+fn test_detailed_json_includes_source() {
+    let doc = parse_bytes(&common::text_pdf()).unwrap();
+    let detailed = render::to_json(&doc, JsonFormat::Detailed).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&detailed).unwrap();
+    let source = &value["pages"][0]["elements"][0]["source"];
+    assert_eq!(source["streams"][0], serde_json::json!([4, 0]));
+    assert_eq!(source["first_op"], 3);
+
+    let pretty = render::to_json(&doc, JsonFormat::Pretty).unwrap();
+    assert!(!pretty.contains("\"first_op\""));
+}
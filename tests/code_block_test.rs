@@ -0,0 +1,58 @@
+//! Monospace code listings become fenced code blocks.
+
+mod common;
+
+use unpdf::{render, Block, PdfParser, RenderOptions};
+
+const LISTING: &[u8] = b"BT /F1 10 Tf 72 740 Td (The entry point looks like this:) Tj ET\n\
+    BT /F2 10 Tf 72 710 Td (fn main() {) Tj ET\n\
+    BT /F2 10 Tf 96 698 Td (let x = 1;) Tj ET\n\
+    BT /F2 10 Tf 72 674 Td (}) Tj ET\n\
+    BT /F1 10 Tf 72 640 Td (That is all.) Tj ET\n";
+
+fn code_blocks(doc: &unpdf::Document) -> Vec<(Option<String>, String)> {
+    doc.pages[0]
+        .elements
+        .iter()
+        .filter_map(|b| match b {
+            Block::CodeBlock { language, content } => Some((language.clone(), content.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn courier_listing_keeps_lines_and_indentation() {
+    let pdf = common::code_listing_pdf("Courier", LISTING);
+    let doc = PdfParser::from_bytes(&pdf).unwrap().parse().unwrap();
+
+    // One blank line between `let` and `}` comes from the vertical gap.
+    assert_eq!(
+        code_blocks(&doc),
+        vec![(
+            Some("rust".to_string()),
+            "fn main() {\n    let x = 1;\n\n}".to_string()
+        )]
+    );
+    let paragraphs = doc.pages[0].elements.iter().filter(|b| b.is_paragraph());
+    assert_eq!(paragraphs.count(), 2);
+
+    let markdown = render::to_markdown(&doc, &RenderOptions::default()).unwrap();
+    assert!(markdown.contains("```rust\nfn main() {\n    let x = 1;\n\n}\n```"));
+}
+
+#[test]
+fn fixed_pitch_widths_detect_unnamed_monospace_font() {
+    let pdf = common::code_listing_pdf("ABCDEF+ListingFace", LISTING);
+    let doc = PdfParser::from_bytes(&pdf).unwrap().parse().unwrap();
+    assert_eq!(code_blocks(&doc).len(), 1);
+}
+
+#[test]
+fn code_survives_cleanup() {
+    let pdf = common::code_listing_pdf("Courier", LISTING);
+    let doc = PdfParser::from_bytes(&pdf).unwrap().parse().unwrap();
+    let options = RenderOptions::default().with_cleanup_preset(unpdf::CleanupPreset::Aggressive);
+    let markdown = render::to_markdown(&doc, &options).unwrap();
+    assert!(markdown.contains("fn main() {\n    let x = 1;\n"));
+}
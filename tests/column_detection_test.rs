@@ -0,0 +1,89 @@
+mod common;
+
+use unpdf::{parse_bytes_with_options, ColumnDetectionConfig, ParseOptions};
+
+/// Two lines of text at x=40 beside ten at x=330. The right
+/// lines sit half a line lower, and the left column is too sparse for the
+/// two to pass as a table.
+fn side_by_side_pdf() -> Vec<u8> {
+    let mut content = String::from("BT /F1 10 Tf\n");
+    for i in 0..10 {
+        let y = 700 - i * 14;
+        if i < 2 {
+            content.push_str(&format!(
+                "1 0 0 1 40 {} Tm (Left column line {}) Tj\n",
+                y,
+                i + 1
+            ));
+        }
+        content.push_str(&format!(
+            "1 0 0 1 330 {} Tm (Right column line {}) Tj\n",
+            y - 7,
+            i + 1
+        ));
+    }
+    content.push_str("ET\n");
+    common::helvetica_pdf(content.as_bytes())
+}
+
+fn text_with(config: ColumnDetectionConfig) -> String {
+    let options = ParseOptions::new().with_column_detection(config);
+    parse_bytes_with_options(&side_by_side_pdf(), options)
+        .unwrap()
+        .plain_text()
+}
+
+fn reads_as_columns(text: &str) -> bool {
+    let last_left = text.find("Left column line 2").expect("left text");
+    let first_right = text.find("Right column line 1").expect("right text");
+    last_left < first_right
+}
+
+#[test]
+fn test_default_splits_side_by_side_text() {
+    assert!(reads_as_columns(&text_with(
+        ColumnDetectionConfig::default()
+    )));
+}
+
+#[test]
+fn test_disabled_reads_single_column() {
+    let text = text_with(ColumnDetectionConfig::disabled());
+    assert!(!reads_as_columns(&text), "{}", text);
+}
+
+#[test]
+fn test_wider_gutter_prevents_split() {
+    let config = ColumnDetectionConfig {
+        min_gutter_width: 300.0,
+        ..Default::default()
+    };
+    assert!(!reads_as_columns(&text_with(config)));
+}
+
+#[test]
+fn test_wider_min_column_prevents_split() {
+    let config = ColumnDetectionConfig {
+        min_column_width: 150.0,
+        ..Default::default()
+    };
+    assert!(!reads_as_columns(&text_with(config)));
+}
+
+#[test]
+fn test_max_columns_caps_split() {
+    let config = ColumnDetectionConfig {
+        max_columns: 1,
+        ..Default::default()
+    };
+    assert!(!reads_as_columns(&text_with(config)));
+}
+
+#[test]
+fn test_min_page_width_prevents_split() {
+    let config = ColumnDetectionConfig {
+        min_page_width: 400.0,
+        ..Default::default()
+    };
+    assert!(!reads_as_columns(&text_with(config)));
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use unpdf::{parse_bytes, Block, Document};
+
+fn tables(doc: &Document) -> Vec<Vec<String>> {
+    doc.pages[0]
+        .elements
+        .iter()
+        .filter_map(|b| match b {
+            Block::Table(t) => Some(t.rows.iter().map(|r| r.plain_text()).collect()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_table_within_column_detected() {
+    let doc = parse_bytes(&common::column_table_pdf()).unwrap();
+    let tables = tables(&doc);
+    assert_eq!(tables.len(), 1, "{:#?}", doc.pages[0].elements);
+    assert_eq!(tables[0].len(), 4);
+    assert!(tables[0][2].contains("340M"), "{:?}", tables[0]);
+}
+
+#[test]
+fn test_column_table_keeps_reading_order() {
+    let text = parse_bytes(&common::column_table_pdf())
+        .unwrap()
+        .plain_text();
+    let table = text.find("340M").expect("table text");
+    let left = text.find("line 12").expect("left text");
+    let right = text.find("Right body").expect("right text");
+    assert!(left < table && table < right, "{}", text);
+}
+
+#[test]
+fn test_detailed_json_reports_table_detection() {
+    let doc = parse_bytes(&common::column_table_pdf()).unwrap();
+    let json = unpdf::render::to_json(&doc, unpdf::JsonFormat::Detailed).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let table = value["pages"][0]["elements"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["type"] == "table")
+        .unwrap();
+    let detection = &table["detection"];
+    assert!(
+        detection["confidence"].as_f64().unwrap() >= 0.4,
+        "{detection}"
+    );
+    assert_eq!(detection["alignment"].as_array().unwrap().len(), 4);
+    assert!(detection.get("rejected").is_none());
+
+    let pretty = unpdf::render::to_json(&doc, unpdf::JsonFormat::Pretty).unwrap();
+    assert!(!pretty.contains("\"detection\""));
+}
+
+#[test]
+fn test_numbered_list_is_a_rejected_candidate() {
+    let mut content = b"BT /F1 10 Tf".to_vec();
+    for (i, item) in ["Install", "Configure", "Deploy", "Monitor", "Retire"]
+        .iter()
+        .enumerate()
+    {
+        let y = 700 - 15 * i;
+        content.extend(format!(" 1 0 0 1 72 {y} Tm ({}.) Tj", i + 1).bytes());
+        content.extend(format!(" 1 0 0 1 100 {y} Tm ({item} the service) Tj").bytes());
+    }
+    content.extend(b" ET\n");
+    let doc = parse_bytes(&common::helvetica_pdf(&content)).unwrap();
+    assert!(tables(&doc).is_empty());
+    let candidates = &doc.pages[0].table_candidates;
+    assert_eq!(candidates.len(), 1, "{candidates:?}");
+    assert_eq!(
+        candidates[0].rejected,
+        Some(unpdf::TableRejection::ListPattern)
+    );
+}
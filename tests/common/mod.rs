@@ -22,7 +22,313 @@ pub fn image_only_pdf() -> Vec<u8> {
 
 /// One page with a single line of visible Helvetica text.
 pub fn text_pdf() -> Vec<u8> {
+    helvetica_pdf(b"BT /F1 12 Tf 72 720 Td (Hello World) Tj ET\n")
+}
+
+/// One page drawing `content` with Helvetica available as `/F1`.
+pub fn helvetica_pdf(content: &[u8]) -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+        HELVETICA.to_vec(),
+    ];
+    assemble(objects)
+}
+
+/// A two-column page: each column holds body text, and the left one a small
+/// three-by-four table after its text.
+pub fn column_table_pdf() -> Vec<u8> {
+    let mut content = String::from("BT /F1 10 Tf\n");
+    for i in 0..12 {
+        let y = 760 - i * 14;
+        content.push_str(&format!(
+            "1 0 0 1 40 {} Tm (Left body text of the column, line {}) Tj\n",
+            y,
+            i + 1
+        ));
+    }
+    for i in 0..24 {
+        let y = 760 - i * 14;
+        content.push_str(&format!(
+            "1 0 0 1 320 {} Tm (Right body text of the column, line {}) Tj\n",
+            y,
+            i + 1
+        ));
+    }
+    let rows = [
+        ["Model", "Params", "Score"],
+        ["Base", "110M", "81.2"],
+        ["Large", "340M", "84.6"],
+        ["Huge", "1.3B", "86.1"],
+    ];
+    for (i, row) in rows.iter().enumerate() {
+        let y = 560 - i as i32 * 14;
+        for (j, cell) in row.iter().enumerate() {
+            let x = 40 + j * 80;
+            content.push_str(&format!("1 0 0 1 {} {} Tm ({}) Tj\n", x, y, cell));
+        }
+    }
+    content.push_str("ET\n");
+    helvetica_pdf(content.as_bytes())
+}
+
+/// One page like [`helvetica_pdf`] whose catalog declares `/Lang`.
+pub fn lang_pdf(lang: &str, content: &[u8]) -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        format!("<</Type/Catalog/Pages 2 0 R/Lang({})>>", lang).into_bytes(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+        HELVETICA.to_vec(),
+    ];
+    assemble(objects)
+}
+
+/// A [`text_pdf`] whose trailer points at an Info dictionary with the
+/// entries `info` (e.g. `/Title(Report)`).
+pub fn info_pdf(info: &str) -> Vec<u8> {
+    let content = b"BT /F1 12 Tf 72 720 Td (Hello World) Tj ET\n";
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+        HELVETICA.to_vec(),
+        format!("<<{}>>", info).into_bytes(),
+    ];
+    assemble_with_trailer(objects, "/Info 6 0 R")
+}
+
+/// A Hello World page with two bookmarks: `Intro` and, nested in it,
+/// `Detail`.
+pub fn outlined_pdf() -> Vec<u8> {
+    let content = b"BT /F1 12 Tf 72 720 Td (Hello World) Tj ET\n";
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R/Outlines 6 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+        HELVETICA.to_vec(),
+        b"<</Type/Outlines/First 7 0 R/Last 7 0 R/Count 2>>".to_vec(),
+        b"<</Title(Intro)/Parent 6 0 R/Dest[3 0 R/Fit]/First 8 0 R/Last 8 0 R/Count 1>>".to_vec(),
+        b"<</Title(Detail)/Parent 7 0 R/Dest[3 0 R/Fit]>>".to_vec(),
+    ];
+    assemble(objects)
+}
+
+/// XML data filled into [`xfa_pdf`]'s form.
+pub const XFA_DATASETS: &str = "<xfa:datasets \
+    xmlns:xfa=\"http://www.xfa.org/schema/xfa-data/1.0/\"><xfa:data><form><name>Kim</name></form></xfa:data></xfa:datasets>";
+
+/// One Hello World page with an AcroForm (object 6) whose `/XFA` is `xfa`.
+/// Object 7 is a template packet, 8 the [`XFA_DATASETS`] packet and 9 a
+/// whole XDP holding both, so `xfa` is either `[(template) 7 0 R
+/// (datasets) 8 0 R]` or `9 0 R`.
+pub fn xfa_pdf(xfa: &str) -> Vec<u8> {
+    let content = b"BT /F1 12 Tf 72 720 Td (Hello World) Tj ET\n";
+    let template = "<template xmlns=\"http://www.xfa.org/schema/xfa-template/3.3/\"/>";
+    let xdp = format!(
+        "<xdp:xdp xmlns:xdp=\"http://ns.adobe.com/xdp/\">{template}{XFA_DATASETS}</xdp:xdp>"
+    );
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R/AcroForm 6 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+        HELVETICA.to_vec(),
+        format!("<</Fields[]/XFA {}>>", xfa).into_bytes(),
+        stream_object(
+            &format!("<</Length {}>>", template.len()),
+            template.as_bytes(),
+        ),
+        stream_object(
+            &format!("<</Length {}>>", XFA_DATASETS.len()),
+            XFA_DATASETS.as_bytes(),
+        ),
+        stream_object(&format!("<</Length {}>>", xdp.len()), xdp.as_bytes()),
+    ];
+    assemble(objects)
+}
+
+/// A Hello World page encrypted with 128-bit RC4 (revision 3) under
+/// `user` and `owner` passwords. An empty `user` leaves only the owner
+/// password's restrictions.
+pub fn encrypted_pdf(user: &str, owner: &str) -> Vec<u8> {
+    restricted_pdf(user, owner, -4)
+}
+
+/// [`encrypted_pdf`] with `/P` set to `permissions`.
+pub fn restricted_pdf(user: &str, owner: &str, permissions: i32) -> Vec<u8> {
+    use md5::{Digest, Md5};
+    use unpdf::parser::raw::crypt::{self, EncryptionParams};
+
+    const PADDING: [u8; 32] = [
+        0x28, 0xBF, 0x4E, 0x5E, 0x4E, 0x75, 0x8A, 0x41, 0x64, 0x00, 0x4E, 0x56, 0xFF, 0xFA, 0x01,
+        0x08, 0x2E, 0x2E, 0x00, 0xB6, 0xD0, 0x68, 0x3E, 0x80, 0x2F, 0x0C, 0xA9, 0xFE, 0x64, 0x53,
+        0x69, 0x7A,
+    ];
+    let pad = |password: &str| {
+        let mut padded = password.as_bytes().to_vec();
+        padded.extend_from_slice(&PADDING[..32 - padded.len()]);
+        padded
+    };
+    // RC4 with the key, then 19 passes with the key XORed by the pass number.
+    let rounds = |key: &[u8], mut data: Vec<u8>| {
+        for i in 0..=19u8 {
+            let round_key: Vec<u8> = key.iter().map(|b| b ^ i).collect();
+            data = crypt::decrypt_rc4(&round_key, &data);
+        }
+        data
+    };
+    let file_id = b"unpdf-test-id-01".to_vec();
+
+    let mut owner_key = Md5::digest(pad(owner)).to_vec();
+    for _ in 0..50 {
+        owner_key = Md5::digest(&owner_key).to_vec();
+    }
+    let owner_hash = rounds(&owner_key, pad(user));
+
+    let mut params = EncryptionParams {
+        version: 2,
+        revision: 3,
+        key_length: 128,
+        owner_hash,
+        user_hash: Vec::new(),
+        permissions,
+        file_id: file_id.clone(),
+        use_aes: false,
+        encrypt_metadata: true,
+    };
+    let key = crypt::compute_encryption_key(&params, user.as_bytes());
+    let mut hasher = Md5::new();
+    hasher.update(PADDING);
+    hasher.update(&file_id);
+    params.user_hash = rounds(&key, hasher.finalize().to_vec());
+    params.user_hash.extend_from_slice(&[0; 16]);
+
     let content = b"BT /F1 12 Tf 72 720 Td (Hello World) Tj ET\n";
+    let encrypted = crypt::decrypt_rc4(&crypt::object_key(&key, 4, 0, false), content);
+    let hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{b:02X}")).collect::<String>();
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", encrypted.len()), &encrypted),
+        HELVETICA.to_vec(),
+        format!(
+            "<</Filter/Standard/V 2/R 3/Length 128/P {}/O <{}>/U <{}>>>",
+            permissions,
+            hex(&params.owner_hash),
+            hex(&params.user_hash)
+        )
+        .into_bytes(),
+    ];
+    let id = hex(&file_id);
+    assemble_with_trailer(objects, &format!("/Encrypt 6 0 R/ID[<{id}><{id}>]"))
+}
+
+/// One page whose `/Contents` is an array of two streams, objects 4 and 6,
+/// with Helvetica as `/F1` (object 5).
+pub fn two_stream_pdf(first: &[u8], second: &[u8]) -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>>>/Contents[4 0 R 6 0 R]>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", first.len()), first),
+        HELVETICA.to_vec(),
+        stream_object(&format!("<</Length {}>>", second.len()), second),
+    ];
+    assemble(objects)
+}
+
+/// [`text_pdf`] plus a yellow highlight over "World" by "Dana" with a
+/// comment, and a sticky note.
+pub fn annotated_pdf() -> Vec<u8> {
+    let content = b"BT /F1 12 Tf 72 720 Td (Hello World) Tj ET\n";
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>>>/Contents 4 0 R/Annots[6 0 R 7 0 R]>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+        HELVETICA.to_vec(),
+        b"<</Type/Annot/Subtype/Highlight/Rect[100 716 136 732]\
+          /QuadPoints[101 732 136 732 101 716 136 716]\
+          /T(Dana)/M(D:20240301093000Z)/Contents(Which world?)>>"
+            .to_vec(),
+        b"<</Type/Annot/Subtype/Text/Rect[300 700 320 720]/Contents(Looks good)>>".to_vec(),
+    ];
+    assemble(objects)
+}
+
+/// One page drawn with a condensed simple font that carries `/Widths`:
+/// the space glyph is 200 units wide, every other glyph 400.
+pub fn condensed_font_pdf(content: &[u8]) -> Vec<u8> {
+    let widths: Vec<String> = (32..=126)
+        .map(|c| if c == 32 { "200" } else { "400" }.to_string())
+        .collect();
+    let font = format!(
+        "<</Type/Font/Subtype/Type1/BaseFont/Helvetica-Condensed\
+          /FirstChar 32/LastChar 126/Widths[{}]>>",
+        widths.join(" ")
+    );
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+        font.into_bytes(),
+    ];
+    assemble(objects)
+}
+
+/// One page with a Helvetica body font (`/F1`) and a second font (`/F2`)
+/// named `code_font` whose glyphs are all 600 units wide, as in Courier.
+pub fn code_listing_pdf(code_font: &str, content: &[u8]) -> Vec<u8> {
+    let widths = vec!["600"; 95].join(" ");
+    let mono = format!(
+        "<</Type/Font/Subtype/Type1/BaseFont/{}/FirstChar 32/LastChar 126/Widths[{}]>>",
+        code_font, widths
+    );
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R/F2 6 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+        b"<</Type/Font/Subtype/Type1/BaseFont/Helvetica>>".to_vec(),
+        mono.into_bytes(),
+    ];
+    assemble(objects)
+}
+
+/// One page with a caption and a three-bar chart drawn with path operators.
+pub fn chart_pdf() -> Vec<u8> {
+    let content = b"BT /F1 12 Tf 72 720 Td (Quarterly revenue) Tj ET\n\
+                    1 0 0 rg 100 400 40 120 re f\n\
+                    0 0.5 0 rg 160 400 40 200 re f\n\
+                    0 0 1 rg 220 400 40 80 re f\n\
+                    0 G 1 w 90 400 m 280 400 l S\n";
     let objects: Vec<Vec<u8>> = vec![
         b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
         b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
@@ -47,6 +353,69 @@ pub fn blank_pdf() -> Vec<u8> {
     assemble(objects)
 }
 
+/// `pages` pages of Helvetica text, each with `lines` numbered lines, all
+/// sharing one font object (object 3). Each line is a kerned `TJ` array, the
+/// way typesetters write text.
+pub fn many_pages_pdf(pages: usize, lines: usize) -> Vec<u8> {
+    let kids: Vec<String> = (0..pages).map(|i| format!("{} 0 R", 4 + 2 * i)).collect();
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        format!("<</Type/Pages/Kids[{}]/Count {}>>", kids.join(" "), pages).into_bytes(),
+        HELVETICA.to_vec(),
+    ];
+    for page in 0..pages {
+        let mut content = b"BT /F1 11 Tf 14 TL 72 780 Td\n".to_vec();
+        for line in 0..lines {
+            let text = format!(
+                "Page {} line {}: the quick brown fox jumps.",
+                page + 1,
+                line + 1
+            );
+            let chars: Vec<char> = text.chars().collect();
+            let pieces: Vec<String> = chars
+                .chunks(3)
+                .map(|c| format!("({})", c.iter().collect::<String>()))
+                .collect();
+            content.extend_from_slice(format!("T* [{}] TJ\n", pieces.join(" 15 ")).as_bytes());
+        }
+        content.extend_from_slice(b"ET\n");
+        objects.push(
+            format!(
+                "<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+                  /Resources<</Font<</F1 3 0 R>>>>/Contents {} 0 R>>",
+                5 + 2 * page
+            )
+            .into_bytes(),
+        );
+        objects.push(stream_object(
+            &format!("<</Length {}>>", content.len()),
+            &content,
+        ));
+    }
+    assemble(objects)
+}
+
+/// One page drawing `content` with `/F1` a CID-keyed font in the
+/// Adobe-Korea1 collection, `Identity-H` encoded and without a `ToUnicode`
+/// map: CIDs 1-95 are ASCII from the space on, CID 1086 is `가`.
+pub fn korea1_pdf(content: &[u8]) -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+        b"<</Type/Font/Subtype/Type0/BaseFont/HYSMyeongJo-Medium/Encoding/Identity-H\
+          /DescendantFonts[6 0 R]>>"
+            .to_vec(),
+        b"<</Type/Font/Subtype/CIDFontType0/BaseFont/HYSMyeongJo-Medium\
+          /CIDSystemInfo<</Registry(Adobe)/Ordering(Korea1)/Supplement 0>>>>"
+            .to_vec(),
+    ];
+    assemble(objects)
+}
+
 /// Two pages: page 1 text, page 2 image-only.
 pub fn mixed_pdf() -> Vec<u8> {
     let text_content = b"BT /F1 12 Tf 72 720 Td (Hello World) Tj ET\n";
@@ -71,6 +440,225 @@ pub fn mixed_pdf() -> Vec<u8> {
     assemble(objects)
 }
 
+/// One page with a 200×150 pt JPEG drawn between two lines of text.
+pub fn inline_image_pdf() -> Vec<u8> {
+    let content = b"BT /F1 12 Tf 14 TL 72 760 Td (Before the figure, the text) Tj\
+        (explains what it shows.) ' ET\n\
+        q 200 0 0 150 72 560 cm /Im0 Do Q\n\
+        BT /F1 12 Tf 14 TL 72 520 Td (After the figure, the text) Tj\
+        (discusses the results.) ' ET\n";
+    jpeg_page_pdf(content)
+}
+
+/// [`inline_image_pdf`] with `caption` set right below the image.
+pub fn captioned_image_pdf(caption: &str) -> Vec<u8> {
+    let content = format!(
+        "BT /F1 12 Tf 72 760 Td (The setup is described first.) Tj ET\n\
+         q 200 0 0 150 72 560 cm /Im0 Do Q\n\
+         BT /F1 10 Tf 72 540 Td ({}) Tj ET\n\
+         BT /F1 12 Tf 72 480 Td (The discussion follows the figure.) Tj ET\n",
+        caption
+    );
+    jpeg_page_pdf(content.as_bytes())
+}
+
+/// One page drawing `content` with Helvetica as `/F1` and a 400×300 JPEG
+/// as `/Im0`.
+fn jpeg_page_pdf(content: &[u8]) -> Vec<u8> {
+    let jpeg = [0xFF, 0xD8, 0xFF, 0xD9];
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>/XObject<</Im0 6 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+        HELVETICA.to_vec(),
+        stream_object(
+            &format!(
+                "<</Type/XObject/Subtype/Image/Width 400/Height 300/ColorSpace/DeviceRGB\
+                  /BitsPerComponent 8/Filter/DCTDecode/Length {}>>",
+                jpeg.len()
+            ),
+            &jpeg,
+        ),
+    ];
+    assemble(objects)
+}
+
+/// [`helvetica_pdf`] saying "Original wording", then two incremental
+/// updates: the second revision replaces the page's content stream with
+/// "Amended wording", the third only adds an annotation to the page.
+pub fn revised_pdf() -> Vec<u8> {
+    let original = b"BT /F1 12 Tf 72 720 Td (Original wording) Tj ET\n";
+    let amended = b"BT /F1 12 Tf 72 720 Td (Amended wording) Tj ET\n";
+    let pdf = helvetica_pdf(original);
+    let pdf = append_revision(
+        pdf,
+        vec![(
+            4,
+            stream_object(&format!("<</Length {}>>", amended.len()), amended),
+        )],
+    );
+    append_revision(
+        pdf,
+        vec![
+            (
+                3,
+                b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+                  /Resources<</Font<</F1 5 0 R>>>>/Contents 4 0 R/Annots[6 0 R]>>"
+                    .to_vec(),
+            ),
+            (
+                6,
+                b"<</Type/Annot/Subtype/Text/Rect[300 700 320 720]/Contents(Fine)>>".to_vec(),
+            ),
+        ],
+    )
+}
+
+/// Append an incremental update to `pdf` that (re)defines the numbered
+/// objects, chaining its xref section to the previous one with `/Prev`.
+fn append_revision(mut pdf: Vec<u8>, objects: Vec<(u32, Vec<u8>)>) -> Vec<u8> {
+    let tail = String::from_utf8_lossy(&pdf).into_owned();
+    let prev: usize = tail
+        .rsplit("startxref")
+        .next()
+        .and_then(|s| s.split_whitespace().next())
+        .and_then(|s| s.parse().ok())
+        .expect("startxref");
+    let prev_size: u32 = tail
+        .rsplit("/Size ")
+        .next()
+        .and_then(|s| s.split(|c: char| !c.is_ascii_digit()).next())
+        .and_then(|s| s.parse().ok())
+        .expect("/Size");
+
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (number, body) in &objects {
+        offsets.push((*number, pdf.len()));
+        pdf.extend_from_slice(format!("{number} 0 obj\n").as_bytes());
+        pdf.extend_from_slice(body);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref_start = pdf.len();
+    let size = objects
+        .iter()
+        .map(|(n, _)| n + 1)
+        .max()
+        .unwrap_or(0)
+        .max(prev_size);
+    pdf.extend_from_slice(b"xref\n");
+    for (number, offset) in offsets {
+        pdf.extend_from_slice(format!("{number} 1\n{offset:010} 00000 n \n").as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<</Size {size}/Root 1 0 R/Prev {prev}>>\nstartxref\n{xref_start}\n%%EOF\n"
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+/// One page drawing `count` JPEG images of `bytes` bytes each, side by side.
+pub fn many_images_pdf(count: usize, bytes: usize) -> Vec<u8> {
+    let mut content = String::new();
+    let mut names = String::new();
+    for i in 0..count {
+        content.push_str(&format!(
+            "q 40 0 0 40 {} 700 cm /Im{} Do Q\n",
+            20 + 50 * i,
+            i
+        ));
+        names.push_str(&format!("/Im{} {} 0 R", i, 5 + i));
+    }
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        format!(
+            "<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+             /Resources<</XObject<<{names}>>>>/Contents 4 0 R>>"
+        )
+        .into_bytes(),
+        stream_object(
+            &format!("<</Length {}>>", content.len()),
+            content.as_bytes(),
+        ),
+    ];
+    for i in 0..count {
+        let data = vec![i as u8; bytes];
+        let dict = format!(
+            "<</Type/XObject/Subtype/Image/Width 100/Height 100/ColorSpace/DeviceRGB\
+              /BitsPerComponent 8/Filter/DCTDecode/Length {bytes}>>"
+        );
+        objects.push(stream_object(&dict, &data));
+    }
+    assemble(objects)
+}
+
+/// One page of Helvetica text with an unreferenced stream in `filter` as
+/// object 6.
+pub fn filtered_stream_pdf(filter: &str) -> Vec<u8> {
+    let content = b"BT /F1 12 Tf 72 720 Td (Hello World) Tj ET\n";
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+        HELVETICA.to_vec(),
+        stream_object(
+            &format!("<</Length 4/Filter/{filter}>>"),
+            b"\x80\x0b\x60\x50",
+        ),
+    ];
+    assemble(objects)
+}
+
+/// One page whose content stream is `encoded`, declared with the `/Filter`
+/// value `filter` (e.g. `[/ASCII85Decode/FlateDecode]`), Helvetica as `/F1`.
+pub fn filtered_content_pdf(filter: &str, encoded: &[u8]) -> Vec<u8> {
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</Font<</F1 5 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(
+            &format!("<</Length {}/Filter{filter}>>", encoded.len()),
+            encoded,
+        ),
+        HELVETICA.to_vec(),
+    ];
+    assemble(objects)
+}
+
+/// One page drawing a 2×2 RGB image whose stream is `encoded`, declared with
+/// the `/Filter` value `filter`.
+pub fn filtered_image_pdf(filter: &str, encoded: &[u8]) -> Vec<u8> {
+    let content = b"q 100 0 0 100 72 600 cm /Im0 Do Q\n";
+    let objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        b"<</Type/Pages/Kids[3 0 R]/Count 1>>".to_vec(),
+        b"<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]\
+          /Resources<</XObject<</Im0 5 0 R>>>>/Contents 4 0 R>>"
+            .to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+        stream_object(
+            &format!(
+                "<</Type/XObject/Subtype/Image/Width 2/Height 2/ColorSpace/DeviceRGB\
+                  /BitsPerComponent 8/Filter{filter}/Length {}>>",
+                encoded.len()
+            ),
+            encoded,
+        ),
+    ];
+    assemble(objects)
+}
+
 /// A 1×1 grey image XObject — the CTM it is drawn with does the scaling.
 fn gray_pixel_image() -> Vec<u8> {
     stream_object(
@@ -80,6 +668,25 @@ fn gray_pixel_image() -> Vec<u8> {
     )
 }
 
+/// Leaf page for [`page_tree_pdf`], showing "Page" in Helvetica.
+pub const PAGE_TREE_LEAF: &str = "<</Type/Page/MediaBox[0 0 595 842]\
+    /Resources<</Font<</F1 2 0 R>>>>/Contents 3 0 R>>";
+
+/// A document whose page tree is made of `nodes`, numbered from object 4;
+/// the catalog's `/Pages` is `4 0 R`, Helvetica is `2 0 R` and the shared
+/// content stream `3 0 R`. Nodes may reference each other freely, cycles
+/// and missing objects included.
+pub fn page_tree_pdf(nodes: &[&str]) -> Vec<u8> {
+    let content = b"BT /F1 12 Tf 72 720 Td (Page) Tj ET\n";
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 4 0 R>>".to_vec(),
+        HELVETICA.to_vec(),
+        stream_object(&format!("<</Length {}>>", content.len()), content),
+    ];
+    objects.extend(nodes.iter().map(|node| node.as_bytes().to_vec()));
+    assemble(objects)
+}
+
 fn stream_object(dict: &str, data: &[u8]) -> Vec<u8> {
     let mut obj = dict.as_bytes().to_vec();
     obj.extend_from_slice(b"\nstream\n");
@@ -89,6 +696,11 @@ fn stream_object(dict: &str, data: &[u8]) -> Vec<u8> {
 }
 
 fn assemble(objects: Vec<Vec<u8>>) -> Vec<u8> {
+    assemble_with_trailer(objects, "")
+}
+
+/// [`assemble`] with `extra` entries in the trailer dictionary.
+fn assemble_with_trailer(objects: Vec<Vec<u8>>, extra: &str) -> Vec<u8> {
     let mut pdf = b"%PDF-1.4\n".to_vec();
     let mut offsets = Vec::with_capacity(objects.len());
     for (idx, body) in objects.iter().enumerate() {
@@ -105,7 +717,7 @@ fn assemble(objects: Vec<Vec<u8>>) -> Vec<u8> {
         pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
     }
     pdf.extend_from_slice(
-        format!("trailer\n<</Size {size}/Root 1 0 R>>\nstartxref\n{xref_start}\n%%EOF\n")
+        format!("trailer\n<</Size {size}/Root 1 0 R{extra}>>\nstartxref\n{xref_start}\n%%EOF\n")
             .as_bytes(),
     );
     pdf
@@ -0,0 +1,33 @@
+mod common;
+
+use unpdf::{parse_bytes, render, JsonFormat};
+
+#[test]
+fn test_malformed_operator_keeps_page_layout() {
+    let pdf = common::helvetica_pdf(
+        b"BT /F1 18 Tf 72 760 Td (Quarterly Report) Tj ET\n\
+          \xff\xfe 3 7 Qz\n\
+          BT /F1 12 Tf 72 720 Td (Revenue grew in every region.) Tj ET\n",
+    );
+    let doc = parse_bytes(&pdf).unwrap();
+    let page = &doc.pages[0];
+    let text = page.plain_text();
+    assert!(text.contains("Quarterly Report"), "{text}");
+    assert!(text.contains("Revenue grew in every region."), "{text}");
+    assert_eq!(page.stats().skipped_content_bytes, 8);
+}
+
+#[test]
+fn test_skipped_bytes_in_json_stats() {
+    let pdf = common::helvetica_pdf(b"BT /F1 12 Tf 72 720 Td 5 (Hello World) Tj ET\n");
+    let doc = parse_bytes(&pdf).unwrap();
+    let json: serde_json::Value =
+        serde_json::from_str(&render::to_json(&doc, JsonFormat::Compact).unwrap()).unwrap();
+    assert_eq!(json["pages"][0]["skipped_content_bytes"], 2);
+    assert_eq!(json["pages"][0]["stats"]["skipped_content_bytes"], 2);
+
+    let clean = parse_bytes(&common::text_pdf()).unwrap();
+    let json: serde_json::Value =
+        serde_json::from_str(&render::to_json(&clean, JsonFormat::Compact).unwrap()).unwrap();
+    assert!(json["pages"][0].get("skipped_content_bytes").is_none());
+}
@@ -0,0 +1,361 @@
+//! Golden-file regression tests over the PDF corpus in `test-files/`.
+//!
+//! Every `name.pdf` under the corpus directory is converted to Markdown and
+//! JSON and compared with the golden files beside it:
+//!
+//! ```text
+//! test-files/tables/invoice.pdf
+//! test-files/tables/invoice.golden.md     expected Markdown
+//! test-files/tables/invoice.golden.json   expected JSON (pretty)
+//! test-files/tables/invoice.corpus.json   optional tolerances, see `Tolerance`
+//! ```
+//!
+//! PDFs without golden files are skipped. To add a regression PDF, drop it
+//! into the corpus and record its output:
+//!
+//! ```text
+//! UPDATE_GOLDEN=1 cargo test --test corpus
+//! ```
+//!
+//! `CORPUS_DIR` points the runner at another directory and `CORPUS_FILTER`
+//! limits it to paths containing the given text.
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::Value;
+use unpdf::render::{to_json, to_markdown};
+use unpdf::{parse_file_with_options, JsonFormat, ParseOptions, RenderOptions};
+
+/// Per-file tolerances, read from `name.corpus.json`.
+///
+/// ```json
+/// { "min_similarity": 0.98, "float_tolerance": 0.5, "ignore": ["/metadata/producer"] }
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Tolerance {
+    /// Skip the file, giving the reason
+    skip: Option<String>,
+    /// Password to open the file with
+    password: Option<String>,
+    /// Compare Markdown output
+    markdown: bool,
+    /// Compare JSON output
+    json: bool,
+    /// Lowest line similarity (0.0-1.0) at which Markdown still matches
+    min_similarity: f64,
+    /// Largest difference at which two JSON numbers still match
+    float_tolerance: f64,
+    /// JSON pointers (`/pages/0/stats`) left out of the comparison
+    ignore: Vec<String>,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            skip: None,
+            password: None,
+            markdown: true,
+            json: true,
+            min_similarity: 1.0,
+            float_tolerance: 0.01,
+            ignore: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Report {
+    checked: usize,
+    updated: usize,
+    skipped: usize,
+    failures: Vec<String>,
+}
+
+#[test]
+fn corpus() {
+    let root = env::var("CORPUS_DIR").unwrap_or_else(|_| "test-files".to_string());
+    let root = Path::new(&root);
+    if !root.is_dir() {
+        eprintln!("corpus: {} not found, nothing to check", root.display());
+        return;
+    }
+    let update = env::var_os("UPDATE_GOLDEN").is_some_and(|v| v != "0");
+    let filter = env::var("CORPUS_FILTER").ok();
+
+    let report = run_corpus(root, update, filter.as_deref());
+    eprintln!(
+        "corpus: {} checked, {} updated, {} skipped, {} failed",
+        report.checked,
+        report.updated,
+        report.skipped,
+        report.failures.len()
+    );
+    assert!(
+        report.failures.is_empty(),
+        "{}\n\nRun with UPDATE_GOLDEN=1 to accept the new output.",
+        report.failures.join("\n\n")
+    );
+}
+
+fn run_corpus(root: &Path, update: bool, filter: Option<&str>) -> Report {
+    let mut pdfs = Vec::new();
+    collect_pdfs(root, &mut pdfs);
+    pdfs.sort();
+
+    let mut report = Report::default();
+    for pdf in pdfs {
+        if filter.is_some_and(|f| !pdf.to_string_lossy().contains(f)) {
+            continue;
+        }
+        if let Err(failure) = check_file(&pdf, update, &mut report) {
+            report
+                .failures
+                .push(format!("{}: {}", pdf.display(), failure));
+        }
+    }
+    report
+}
+
+fn collect_pdfs(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_pdfs(&path, out);
+        } else if path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("pdf"))
+        {
+            out.push(path);
+        }
+    }
+}
+
+/// `name.pdf` → `name.<suffix>` in the same directory.
+fn sibling(pdf: &Path, suffix: &str) -> PathBuf {
+    let stem = pdf.file_stem().unwrap_or_default().to_string_lossy();
+    pdf.with_file_name(format!("{}.{}", stem, suffix))
+}
+
+fn check_file(pdf: &Path, update: bool, report: &mut Report) -> Result<(), String> {
+    let tolerance = match fs::read_to_string(sibling(pdf, "corpus.json")) {
+        Ok(text) => serde_json::from_str(&text).map_err(|e| format!("corpus.json: {}", e))?,
+        Err(_) => Tolerance::default(),
+    };
+    if let Some(reason) = &tolerance.skip {
+        eprintln!("corpus: skipping {} ({})", pdf.display(), reason);
+        report.skipped += 1;
+        return Ok(());
+    }
+
+    let md_path = sibling(pdf, "golden.md");
+    let json_path = sibling(pdf, "golden.json");
+    let wants_md = tolerance.markdown && (update || md_path.exists());
+    let wants_json = tolerance.json && (update || json_path.exists());
+    if !wants_md && !wants_json {
+        report.skipped += 1;
+        return Ok(());
+    }
+
+    let mut options = ParseOptions::new();
+    if let Some(password) = &tolerance.password {
+        options = options.with_password(password.clone());
+    }
+    let doc = parse_file_with_options(pdf, options).map_err(|e| format!("parse failed: {}", e))?;
+
+    let mut outputs = Vec::new();
+    if wants_md {
+        let markdown = to_markdown(&doc, &RenderOptions::default()).map_err(|e| e.to_string())?;
+        outputs.push((md_path, markdown));
+    }
+    if wants_json {
+        let json = to_json(&doc, JsonFormat::Pretty).map_err(|e| e.to_string())?;
+        outputs.push((json_path, json + "\n"));
+    }
+
+    if update {
+        for (path, actual) in &outputs {
+            fs::write(path, actual).map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+        report.updated += 1;
+        return Ok(());
+    }
+
+    report.checked += 1;
+    for (path, actual) in &outputs {
+        let expected =
+            fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        if path.extension().is_some_and(|e| e == "json") {
+            compare_json(&expected, actual, &tolerance)?;
+        } else {
+            compare_markdown(&expected, actual, tolerance.min_similarity)?;
+        }
+    }
+    Ok(())
+}
+
+fn normalized_lines(text: &str) -> Vec<&str> {
+    text.lines().map(str::trim_end).collect()
+}
+
+fn compare_markdown(expected: &str, actual: &str, min_similarity: f64) -> Result<(), String> {
+    let (expected, actual) = (normalized_lines(expected), normalized_lines(actual));
+    let similarity = line_similarity(&expected, &actual);
+    if similarity >= min_similarity {
+        return Ok(());
+    }
+    let first_diff = expected
+        .iter()
+        .zip(&actual)
+        .position(|(e, a)| e != a)
+        .unwrap_or(expected.len().min(actual.len()));
+    Err(format!(
+        "Markdown differs (similarity {:.3} < {:.3}), first at line {}:\n  \
+         expected: {:?}\n  actual:   {:?}",
+        similarity,
+        min_similarity,
+        first_diff + 1,
+        expected.get(first_diff).copied().unwrap_or("<end>"),
+        actual.get(first_diff).copied().unwrap_or("<end>"),
+    ))
+}
+
+/// Share of lines the two texts have in common, in order: twice the longest
+/// common subsequence over the total line count.
+fn line_similarity(a: &[&str], b: &[&str]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let mut row = vec![0usize; b.len() + 1];
+    for x in a {
+        let mut diagonal = 0;
+        for (j, y) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if x == y {
+                diagonal + 1
+            } else {
+                above.max(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    2.0 * row[b.len()] as f64 / (a.len() + b.len()) as f64
+}
+
+fn compare_json(expected: &str, actual: &str, tolerance: &Tolerance) -> Result<(), String> {
+    let mut expected: Value =
+        serde_json::from_str(expected).map_err(|e| format!("golden JSON: {}", e))?;
+    let mut actual: Value = serde_json::from_str(actual).map_err(|e| e.to_string())?;
+    for pointer in &tolerance.ignore {
+        remove_pointer(&mut expected, pointer);
+        remove_pointer(&mut actual, pointer);
+    }
+    json_diff(&expected, &actual, tolerance.float_tolerance, "")
+        .map_or(Ok(()), |diff| Err(format!("JSON differs at {}", diff)))
+}
+
+fn remove_pointer(value: &mut Value, pointer: &str) {
+    let Some((parent, key)) = pointer.rsplit_once('/') else {
+        return;
+    };
+    match value.pointer_mut(parent) {
+        Some(Value::Object(map)) => {
+            map.remove(key);
+        }
+        Some(Value::Array(items)) => {
+            if let Ok(index) = key.parse::<usize>() {
+                if index < items.len() {
+                    items.remove(index);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Path to the first difference between `a` and `b`, with both values.
+fn json_diff(a: &Value, b: &Value, float_tolerance: f64, path: &str) -> Option<String> {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => {
+            let (x, y) = (x.as_f64()?, y.as_f64()?);
+            ((x - y).abs() > float_tolerance).then(|| format!("{}: {} != {}", path, x, y))
+        }
+        (Value::Array(xs), Value::Array(ys)) => {
+            if xs.len() != ys.len() {
+                return Some(format!("{}: {} items != {}", path, xs.len(), ys.len()));
+            }
+            xs.iter().zip(ys).enumerate().find_map(|(i, (x, y))| {
+                json_diff(x, y, float_tolerance, &format!("{}/{}", path, i))
+            })
+        }
+        (Value::Object(xs), Value::Object(ys)) => {
+            let one_sided = |k: &&String| xs.contains_key(*k) != ys.contains_key(*k);
+            if let Some(key) = xs.keys().chain(ys.keys()).find(one_sided) {
+                return Some(format!("{}/{}: present on one side only", path, key));
+            }
+            xs.iter().find_map(|(key, x)| {
+                json_diff(x, &ys[key], float_tolerance, &format!("{}/{}", path, key))
+            })
+        }
+        _ => (a != b).then(|| format!("{}: {} != {}", path, a, b)),
+    }
+}
+
+#[test]
+fn test_corpus_runner_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let nested = dir.path().join("basic");
+    fs::create_dir(&nested).unwrap();
+    fs::write(nested.join("hello.pdf"), common::text_pdf()).unwrap();
+    fs::write(dir.path().join("no-golden.pdf"), common::text_pdf()).unwrap();
+
+    let report = run_corpus(dir.path(), true, Some("basic"));
+    assert_eq!(report.updated, 1);
+    assert!(nested.join("hello.golden.md").exists());
+    assert!(nested.join("hello.golden.json").exists());
+
+    let report = run_corpus(dir.path(), false, None);
+    assert_eq!((report.checked, report.skipped), (1, 1));
+    assert!(report.failures.is_empty(), "{:?}", report.failures);
+
+    fs::write(nested.join("hello.golden.md"), "Something else\n").unwrap();
+    let report = run_corpus(dir.path(), false, None);
+    assert_eq!(report.failures.len(), 1);
+    assert!(
+        report.failures[0].contains("Markdown differs"),
+        "{}",
+        report.failures[0]
+    );
+
+    fs::write(nested.join("hello.corpus.json"), r#"{ "markdown": false }"#).unwrap();
+    assert!(run_corpus(dir.path(), false, None).failures.is_empty());
+}
+
+#[test]
+fn test_line_similarity() {
+    assert_eq!(line_similarity(&["a", "b", "c"], &["a", "b", "c"]), 1.0);
+    assert_eq!(line_similarity(&["a", "b"], &["a", "x"]), 0.5);
+    assert_eq!(line_similarity(&[], &["a"]), 0.0);
+}
+
+#[test]
+fn test_json_tolerance() {
+    let tolerance = Tolerance {
+        float_tolerance: 0.5,
+        ignore: vec!["/meta/producer".to_string()],
+        ..Tolerance::default()
+    };
+    let expected = r#"{"meta": {"producer": "A"}, "x": [1.0, 2.0]}"#;
+    let close = r#"{"meta": {"producer": "B"}, "x": [1.2, 2.0]}"#;
+    assert!(compare_json(expected, close, &tolerance).is_ok());
+    let err = compare_json(expected, r#"{"meta": {}, "x": [1.0, 3.0]}"#, &tolerance).unwrap_err();
+    assert!(err.contains("/x/1"), "{}", err);
+}
@@ -0,0 +1,42 @@
+//! Per-page debugging artifacts written alongside parsing.
+
+mod common;
+
+use std::fs;
+
+use unpdf::{parse_bytes_with_options, ParseOptions};
+
+#[test]
+fn artifacts_are_written_per_page() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path().join("debug");
+    let pdf = common::helvetica_pdf(
+        b"BT /F1 12 Tf 72 720 Td (Hello, World) Tj 0 -14 Td (Second line) Tj ET\n",
+    );
+    let options = ParseOptions::new().with_debug_artifacts(&dir);
+    let doc = parse_bytes_with_options(&pdf, options).unwrap();
+    assert!(doc.plain_text().contains("Second line"));
+
+    let content = fs::read_to_string(dir.join("page-1.content.txt")).unwrap();
+    assert!(content.lines().any(|l| l.ends_with("/F1 12 Tf")));
+    assert!(content.contains("Tj"));
+
+    let spans = fs::read_to_string(dir.join("page-1.spans.csv")).unwrap();
+    let mut rows = spans.lines();
+    assert!(rows.next().unwrap().starts_with("op_start,op_end,x,y"));
+    // 쉼표가 든 텍스트는 따옴표로 감싼다.
+    assert!(rows.any(|row| row.ends_with("\"Hello, World\"")));
+
+    let blocks = fs::read_to_string(dir.join("page-1.blocks.txt")).unwrap();
+    assert!(blocks.starts_with("block 0 "));
+    assert!(blocks.contains("  line [72.00 "));
+    assert!(blocks.contains("Second line"));
+}
+
+#[test]
+fn no_artifacts_by_default() {
+    let tmp = tempfile::tempdir().unwrap();
+    let options = ParseOptions::new().with_resource_dir(tmp.path());
+    parse_bytes_with_options(&common::text_pdf(), options).unwrap();
+    assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), 0);
+}
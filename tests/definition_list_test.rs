@@ -0,0 +1,65 @@
+mod common;
+
+use unpdf::{
+    parse_bytes, render, Block, DefinitionStyle, Document, Page, Paragraph, RenderOptions,
+};
+
+/// A glossary page: an intro paragraph, then single-spaced "Term:
+/// definition" entries, one of them wrapping onto a second line.
+fn glossary_pdf() -> Vec<u8> {
+    let lines: [(&str, f32); 5] = [
+        ("The following terms are used throughout this guide.", 700.0),
+        ("Baseline: The line on which most letters sit.", 670.0),
+        ("Kerning: Adjusting the space between a", 656.0),
+        ("particular pair of letters.", 642.0),
+        ("Leading: The vertical distance between baselines.", 628.0),
+    ];
+    let mut content = String::new();
+    for (text, y) in lines {
+        content.push_str(&format!("BT /F1 11 Tf 72 {y} Td ({text}) Tj ET\n"));
+    }
+    common::helvetica_pdf(content.as_bytes())
+}
+
+fn terms(doc: &Document) -> Vec<String> {
+    doc.pages[0]
+        .elements
+        .iter()
+        .filter_map(|b| match b {
+            Block::Paragraph(p) => p.definition_term().map(str::to_string),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_glossary_entries_become_definitions() {
+    let doc = parse_bytes(&glossary_pdf()).unwrap();
+    assert_eq!(terms(&doc), vec!["Baseline", "Kerning", "Leading"]);
+    // 본문 텍스트에는 용어가 그대로 남는다.
+    assert!(doc
+        .plain_text()
+        .contains("Kerning: Adjusting the space between a particular pair"));
+}
+
+#[test]
+fn test_definition_list_markdown() {
+    let mut doc = Document::new();
+    let mut page = Page::a4(1);
+    page.add_paragraph(Paragraph::definition(
+        "Baseline",
+        "The line letters sit on.",
+    ));
+    page.add_paragraph(Paragraph::definition("Glyph", "A drawn character shape."));
+    doc.add_page(page);
+
+    let md = render::to_markdown(&doc, &RenderOptions::default()).unwrap();
+    assert!(md.contains("Baseline\n: The line letters sit on.\n\nGlyph\n: A drawn character"));
+
+    let options = RenderOptions::default().with_definition_style(DefinitionStyle::BoldTerm);
+    let md = render::to_markdown(&doc, &options).unwrap();
+    assert!(md.contains("**Baseline** — The line letters sit on.\n\n**Glyph** — A drawn"));
+
+    let text = render::to_text(&doc, &RenderOptions::default()).unwrap();
+    assert!(text.contains("Baseline: The line letters sit on."));
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use unpdf::{parse_bytes_with_options, ParseOptions};
+
+const LINES: [&str; 4] = [
+    "The scanner fed this page at an angle",
+    "so every line of recognised words climbs",
+    "steadily from the left margin to the right",
+    "which used to break lines into pieces",
+];
+
+/// Each word placed on its own, as an OCR text layer does, climbing by 2°.
+fn skewed_scan_pdf() -> Vec<u8> {
+    let slope = 2.0_f32.to_radians().tan();
+    let mut content = String::new();
+    for (i, line) in LINES.iter().enumerate() {
+        let mut x = 72.0_f32;
+        for word in line.split(' ') {
+            let y = 700.0 - i as f32 * 18.0 + (x - 72.0) * slope;
+            content.push_str(&format!(
+                "BT /F1 12 Tf 1 0 0 1 {x:.2} {y:.2} Tm ({word}) Tj ET\n"
+            ));
+            // 글리프 폭은 400/1000 em, 낱말 사이는 글자 폭보다 조금 좁다.
+            x += 4.8 * word.len() as f32 + 3.0;
+        }
+    }
+    common::condensed_font_pdf(content.as_bytes())
+}
+
+fn text_lines(options: ParseOptions) -> Vec<String> {
+    let doc = parse_bytes_with_options(&skewed_scan_pdf(), options).unwrap();
+    doc.pages[0]
+        .plain_text()
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+#[test]
+fn test_skewed_lines_split_without_deskew() {
+    let lines = text_lines(ParseOptions::new());
+    assert!(
+        !LINES.iter().all(|l| lines.contains(&l.to_string())),
+        "{:?}",
+        lines
+    );
+}
+
+#[test]
+fn test_deskew_reassembles_lines() {
+    let lines = text_lines(ParseOptions::new().with_deskew(true));
+    assert_eq!(lines.join(" "), LINES.join(" "));
+}
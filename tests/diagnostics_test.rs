@@ -1,3 +1,5 @@
+mod common;
+
 use std::path::Path;
 use unpdf::{parse_file, ExtractionQuality};
 
@@ -154,3 +156,63 @@ fn test_table_extraction_basic() {
     });
     assert!(has_tables, "Table PDF should detect tables");
 }
+
+#[test]
+fn test_font_names_listed() {
+    let parser = unpdf::PdfParser::from_bytes(&common::text_pdf()).unwrap();
+    assert_eq!(parser.font_names(), ["Helvetica"]);
+}
+
+fn document_needing_review() -> unpdf::Document {
+    use unpdf::{Block, Page, Paragraph, Table, TableCell, TableDetection, TableRow};
+
+    let mut table = Table::new();
+    table.add_row(TableRow::header(vec![
+        TableCell::text("Item"),
+        TableCell::text("Qty"),
+    ]));
+    table.add_row(TableRow::from_strings(["Bolts", "12"]));
+    table.header_rows = 1;
+    table.detection = Some(TableDetection {
+        bbox: [72.0, 600.0, 300.0, 640.0],
+        confidence: 0.35,
+        alignment: vec![1.0, 0.5],
+        rejected: None,
+    });
+
+    let mut page = Page::new(1, 612.0, 792.0);
+    page.ocr_text_suppressed = true;
+    page.add_block(Block::Paragraph(Paragraph::with_text("Intro")));
+    page.add_block(Block::Table(table));
+    page.add_block(Block::Paragraph(Paragraph::with_text("Tot\u{FFFD}l")));
+    let mut doc = unpdf::Document::new();
+    doc.add_page(page);
+    doc
+}
+
+#[test]
+fn inline_warnings_sit_where_they_apply() {
+    let doc = document_needing_review();
+    let options = unpdf::RenderOptions::new().with_inline_warnings(true);
+    let markdown = unpdf::render::to_markdown(&doc, &options).unwrap();
+
+    let ocr = markdown
+        .find("<!-- unpdf: OCR text layer dropped as unreadable -->")
+        .unwrap();
+    let intro = markdown.find("Intro").unwrap();
+    let table_warning = markdown
+        .find("<!-- unpdf: table detection low confidence (0.35) -->\n\n| Item")
+        .unwrap();
+    let undecoded = markdown
+        .find("<!-- unpdf: 1 undecodable character(s) -->\n\nTot")
+        .unwrap();
+    assert!(ocr < intro && intro < table_warning && table_warning < undecoded);
+
+    let streamed =
+        unpdf::render::collect_content(unpdf::render::StreamingRenderer::new(&doc, options));
+    assert!(streamed.contains("<!-- unpdf: OCR text layer dropped as unreadable -->"));
+    assert!(streamed.contains("<!-- unpdf: table detection low confidence (0.35) -->\n\n| Item"));
+
+    let plain = unpdf::render::to_markdown(&doc, &unpdf::RenderOptions::new()).unwrap();
+    assert!(!plain.contains("<!-- unpdf:"));
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use std::sync::Arc;
+
+use unpdf::render::{self, CleanupPreset, RenderOptions};
+use unpdf::{ConvertOptions, OutputFormat, UnpdfEngine};
+
+#[test]
+fn test_engine_matches_one_shot_conversion() {
+    let pdf = common::many_pages_pdf(4, 20);
+    let render_options = RenderOptions::new().with_cleanup_preset(CleanupPreset::Standard);
+    let doc = unpdf::parse_bytes(&pdf).unwrap();
+    let expected = render::to_markdown(&doc, &render_options).unwrap();
+
+    let engine = Arc::new(UnpdfEngine::new().with_threads(2).unwrap());
+    assert_eq!(engine.threads(), 2);
+
+    let options = ConvertOptions::new()
+        .with_render_options(render_options)
+        .with_stats(true);
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            let engine = Arc::clone(&engine);
+            let (pdf, options, expected) = (&pdf, &options, &expected);
+            scope.spawn(move || {
+                for _ in 0..3 {
+                    let result = engine.convert(pdf, options).unwrap();
+                    assert_eq!(&result.content, expected);
+                    assert_eq!(result.stats.unwrap().page_count, 4);
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn test_engine_text_and_parse() {
+    let engine = UnpdfEngine::new();
+    let pdf = common::text_pdf();
+    let doc = engine.parse(&pdf).unwrap();
+    assert_eq!(doc.page_count(), 1);
+
+    let options = ConvertOptions::new().with_format(OutputFormat::Text);
+    let result = engine.convert(&pdf, &options).unwrap();
+    assert_eq!(result.mime_type, "text/plain");
+    assert_eq!(
+        result.content,
+        render::to_text(&doc, &RenderOptions::new()).unwrap()
+    );
+}
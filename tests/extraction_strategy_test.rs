@@ -0,0 +1,52 @@
+//! Text extraction strategies and the per-page `Auto` fallback.
+//!
+//! Text shown outside a `BT`/`ET` text object is invalid but common in
+//! sloppy generators. The layout interpreter ignores it; the stream-order
+//! reading does not, so `Auto` switches to it for such pages.
+
+mod common;
+
+use common::{helvetica_pdf, text_pdf};
+use unpdf::{parse_bytes_with_options, ExtractionStrategy, ParseOptions, TextStrategy};
+
+const ORPHANED_TEXT: &[u8] = b"/F1 12 Tf (Shown outside) Tj (a text object) ' \n";
+
+fn parse(pdf: &[u8], strategy: ExtractionStrategy) -> unpdf::Document {
+    let options = ParseOptions::new().with_extraction_strategy(strategy);
+    parse_bytes_with_options(pdf, options).unwrap()
+}
+
+#[test]
+fn auto_falls_back_when_layout_recovers_nothing() {
+    let doc = parse(&helvetica_pdf(ORPHANED_TEXT), ExtractionStrategy::Auto);
+
+    assert_eq!(doc.pages[0].text_strategy, TextStrategy::RawText);
+    assert_eq!(doc.plain_text().trim(), "Shown outside\na text object");
+    let json = unpdf::render::to_json(&doc, unpdf::JsonFormat::Compact).unwrap();
+    assert!(json.contains(r#""text_strategy":"raw_text""#), "{json}");
+}
+
+#[test]
+fn auto_keeps_layout_output_when_it_has_the_text() {
+    let doc = parse(&text_pdf(), ExtractionStrategy::Auto);
+
+    assert_eq!(doc.pages[0].text_strategy, TextStrategy::ContentStream);
+    assert!(doc.plain_text().contains("Hello World"));
+    let json = unpdf::render::to_json(&doc, unpdf::JsonFormat::Compact).unwrap();
+    assert!(!json.contains("text_strategy"), "{json}");
+}
+
+#[test]
+fn explicit_strategies_are_honoured() {
+    let doc = parse(
+        &helvetica_pdf(ORPHANED_TEXT),
+        ExtractionStrategy::ContentStream,
+    );
+    assert_eq!(doc.pages[0].text_strategy, TextStrategy::ContentStream);
+    assert!(doc.plain_text().trim().is_empty());
+
+    let doc = parse(&text_pdf(), ExtractionStrategy::RawText);
+    assert_eq!(doc.pages[0].text_strategy, TextStrategy::RawText);
+    assert_eq!(doc.plain_text().trim(), "Hello World");
+    assert_eq!(doc.pages[0].text_op_count, 1);
+}
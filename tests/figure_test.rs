@@ -0,0 +1,38 @@
+//! Images bundled with their captions and placement.
+
+mod common;
+
+use unpdf::{parse_bytes_with_options, ParseOptions};
+
+#[test]
+fn figure_carries_caption_page_and_region() {
+    let pdf = common::captioned_image_pdf("Figure 1. Apparatus used in the experiment");
+    let doc = parse_bytes_with_options(&pdf, ParseOptions::new().with_resources(true)).unwrap();
+    let figures = doc.figures();
+    assert_eq!(figures.len(), 1, "{:?}", doc.pages[0].elements);
+
+    let figure = &figures[0];
+    assert_eq!(figure.page, 1);
+    assert_eq!(
+        figure.caption.as_deref(),
+        Some("Figure 1. Apparatus used in the experiment")
+    );
+    assert_eq!(figure.bbox, Some([72.0, 560.0, 272.0, 710.0]));
+    assert_eq!(
+        figure.resource.map(|r| r.mime_type.as_str()),
+        Some("image/jpeg")
+    );
+
+    let sidecar = serde_json::to_value(figure).unwrap();
+    assert_eq!(sidecar["resource_id"], figure.resource_id);
+    assert!(sidecar.get("resource").is_none());
+}
+
+#[test]
+fn figure_without_caption() {
+    let options = ParseOptions::new().with_resources(true);
+    let doc = parse_bytes_with_options(&common::inline_image_pdf(), options).unwrap();
+    let figures = doc.figures();
+    assert_eq!(figures.len(), 1);
+    assert_eq!(figures[0].caption, None);
+}
@@ -0,0 +1,117 @@
+//! Streams encoded with filter chains: content streams and image XObjects.
+
+mod common;
+
+use std::io::Write;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use unpdf::parser::ErrorMode;
+use unpdf::{parse_bytes_with_options, Document, ParseOptions, PdfParser, ResourceType};
+
+fn flate(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn ascii85(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for chunk in data.chunks(4) {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        let mut value = u32::from_be_bytes(word);
+        let mut digits = [0u8; 5];
+        for d in digits.iter_mut().rev() {
+            *d = (value % 85) as u8 + b'!';
+            value /= 85;
+        }
+        out.extend_from_slice(&digits[..chunk.len() + 1]);
+    }
+    out.extend_from_slice(b"~>");
+    out
+}
+
+fn hex(data: &[u8]) -> Vec<u8> {
+    let mut out: Vec<u8> = data
+        .iter()
+        .flat_map(|b| format!("{:02X}", b).into_bytes())
+        .collect();
+    out.push(b'>');
+    out
+}
+
+/// RunLength with literal runs only.
+fn run_length(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for chunk in data.chunks(128) {
+        out.push(chunk.len() as u8 - 1);
+        out.extend_from_slice(chunk);
+    }
+    out.push(128);
+    out
+}
+
+const CONTENT: &[u8] = b"BT /F1 12 Tf 72 720 Td (Hello Filters) Tj ET\n";
+
+/// 픽스처 이미지는 2×2 라서 크기 필터를 끈다.
+fn parse_images(pdf: &[u8]) -> Document {
+    let options = ParseOptions::new()
+        .with_resources(true)
+        .with_min_image_dimension(0);
+    parse_bytes_with_options(pdf, options).unwrap()
+}
+
+fn page_text(pdf: &[u8]) -> String {
+    PdfParser::from_bytes(pdf)
+        .unwrap()
+        .parse()
+        .unwrap()
+        .plain_text()
+}
+
+#[test]
+fn ascii85_over_flate_content_is_decoded() {
+    let encoded = ascii85(&flate(CONTENT));
+    let pdf = common::filtered_content_pdf("[/ASCII85Decode/FlateDecode]", &encoded);
+    assert!(page_text(&pdf).contains("Hello Filters"));
+}
+
+#[test]
+fn run_length_content_is_decoded() {
+    let pdf = common::filtered_content_pdf("/RunLengthDecode", &run_length(CONTENT));
+    assert!(page_text(&pdf).contains("Hello Filters"));
+}
+
+#[test]
+fn unsupported_content_filter_fails_the_page_by_name() {
+    let pdf = common::filtered_content_pdf("[/ASCIIHexDecode/LZWDecode]", &hex(CONTENT));
+    let options = ParseOptions::new().with_error_mode(ErrorMode::Strict);
+    let err = parse_bytes_with_options(&pdf, options)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("unsupported filter: LZWDecode"), "{}", err);
+}
+
+#[test]
+fn image_codec_behind_ascii_filter_is_kept_as_jpeg() {
+    let jpeg = [0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10, 0xFF, 0xD9];
+    let pdf = common::filtered_image_pdf("[/ASCIIHexDecode/DCTDecode]", &hex(&jpeg));
+    let doc = parse_images(&pdf);
+    let image = doc
+        .resources
+        .values()
+        .find(|r| r.resource_type == ResourceType::Image)
+        .expect("image resource");
+    assert_eq!(image.mime_type, "image/jpeg");
+    assert_eq!(image.data, jpeg);
+}
+
+#[test]
+fn raw_image_behind_ascii85_and_flate_is_decoded() {
+    let pixels: Vec<u8> = (0..12).collect();
+    let pdf = common::filtered_image_pdf("[/A85/Fl]", &ascii85(&flate(&pixels)));
+    let doc = parse_images(&pdf);
+    let image = doc.resources.values().next().expect("image resource");
+    assert_eq!(image.data, pixels);
+}
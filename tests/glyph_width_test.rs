@@ -0,0 +1,104 @@
+//! Span widths and TJ word spacing derived from the font's `/Widths` and
+//! the text state (Tc, Tw, Tz, TL).
+
+mod common;
+
+use unpdf::parser::backend::RawBackend;
+use unpdf::parser::LayoutAnalyzer;
+
+fn page_spans(content: &[u8]) -> Vec<unpdf::parser::TextSpan> {
+    let pdf = common::condensed_font_pdf(content);
+    let backend = RawBackend::load_bytes(&pdf).unwrap();
+    LayoutAnalyzer::new(&backend).extract_page_spans(1).unwrap()
+}
+
+#[test]
+fn span_width_comes_from_glyph_widths() {
+    let spans = page_spans(b"BT /F1 10 Tf 72 720 Td (Hello) Tj ET\n");
+    assert_eq!(spans.len(), 1);
+    // 5 glyphs x 400/1000 x 10pt
+    assert!((spans[0].width - 20.0).abs() < 0.01, "{}", spans[0].width);
+}
+
+#[test]
+fn tj_gap_judged_against_the_font_space_width() {
+    // -180 is below the fixed 200-unit threshold but wider than 70% of this
+    // font's 200-unit space.
+    let spans = page_spans(b"BT /F1 10 Tf 72 720 Td [(Hello)-180(World)] TJ ET\n");
+    assert_eq!(spans[0].text, "Hello World");
+
+    // Kerning well below the space width stays inside the word.
+    let spans = page_spans(b"BT /F1 10 Tf 72 720 Td [(Hello)-40(World)] TJ ET\n");
+    assert_eq!(spans[0].text, "HelloWorld");
+}
+
+#[test]
+fn consecutive_tj_advance_the_pen() {
+    // The second string starts where the first ends, so the two are one run.
+    let spans = page_spans(b"BT /F1 10 Tf 72 720 Td (Hello) Tj (World) Tj ET\n");
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].text, "HelloWorld");
+    assert!((spans[0].width - 40.0).abs() < 0.01, "{}", spans[0].width);
+}
+
+#[test]
+fn glyph_per_tj_spans_are_coalesced() {
+    // One Tj per glyph; the Td leaves a word gap between "Hi" and "yo".
+    let content = b"BT /F1 10 Tf 72 720 Td (H) Tj (i) Tj 20 0 Td (y) Tj (o) Tj ET\n";
+    let spans = page_spans(content);
+    let texts: Vec<&str> = spans.iter().map(|s| s.text.as_str()).collect();
+    assert_eq!(texts, ["Hi", "yo"]);
+    assert_eq!(spans[1].ops, [6, 7]);
+
+    let doc = unpdf::parse_bytes(&common::condensed_font_pdf(content)).unwrap();
+    assert_eq!(doc.plain_text().trim(), "Hi yo");
+}
+
+#[test]
+fn horizontal_scaling_narrows_spans() {
+    let spans = page_spans(b"BT /F1 10 Tf 50 Tz 72 720 Td (Hello) Tj ET\n");
+    assert!((spans[0].width - 10.0).abs() < 0.01, "{}", spans[0].width);
+}
+
+#[test]
+fn char_and_word_spacing_widen_spans() {
+    // 5 glyphs x (4pt + 1pt Tc)
+    let spans = page_spans(b"BT /F1 10 Tf 1 Tc 72 720 Td (Hello) Tj ET\n");
+    assert!((spans[0].width - 25.0).abs() < 0.01, "{}", spans[0].width);
+
+    // 2 glyphs x 4pt + one 2pt space + 3pt Tw
+    let spans = page_spans(b"BT /F1 10 Tf 3 Tw 72 720 Td (a b) Tj ET\n");
+    assert!((spans[0].width - 13.0).abs() < 0.01, "{}", spans[0].width);
+}
+
+#[test]
+fn leading_drives_next_line() {
+    let spans = page_spans(b"BT /F1 10 Tf 20 TL 72 720 Td (One) Tj T* (Two) Tj ET\n");
+    assert!((spans[0].y - spans[1].y - 20.0).abs() < 0.01);
+
+    // TD sets the leading to -ty for the following T*.
+    let spans = page_spans(b"BT /F1 10 Tf 72 720 Td 0 -15 TD (One) Tj T* (Two) Tj ET\n");
+    assert!((spans[0].y - spans[1].y - 15.0).abs() < 0.01);
+
+    // Without TL, lines are a font size apart in text space, scaled by Tm.
+    let spans = page_spans(b"BT /F1 1 Tf 10 0 0 10 72 720 Tm (One) Tj T* (Two) Tj ET\n");
+    assert!((spans[0].y - spans[1].y - 10.0).abs() < 0.01);
+}
+
+#[test]
+fn new_lines_start_from_the_line_start() {
+    // T* and ' return to the x where the line began, not the end of the text.
+    let spans = page_spans(b"BT /F1 10 Tf 14 TL 72 720 Td (One) Tj T* (Two) Tj (Three) ' ET\n");
+    assert_eq!(spans.len(), 3);
+    assert!(spans.iter().all(|s| (s.x - 72.0).abs() < 0.01));
+
+    // Td offsets from the line start too.
+    let spans = page_spans(b"BT /F1 10 Tf 72 720 Td (Hello) Tj 100 0 Td (World) Tj ET\n");
+    assert!((spans[1].x - 172.0).abs() < 0.01, "{}", spans[1].x);
+}
+
+#[test]
+fn text_state_restored_by_grestore() {
+    let spans = page_spans(b"q 50 Tz Q BT /F1 10 Tf 72 720 Td (Hello) Tj ET\n");
+    assert!((spans[0].width - 20.0).abs() < 0.01, "{}", spans[0].width);
+}
@@ -0,0 +1,71 @@
+//! Text positioned through the current transformation matrix, with `q`/`Q`
+//! saving and restoring the graphics state around transformed groups.
+
+mod common;
+
+use unpdf::parser::backend::RawBackend;
+use unpdf::parser::{LayoutAnalyzer, TextSpan};
+
+fn page_spans(content: &[u8]) -> Vec<TextSpan> {
+    let pdf = common::condensed_font_pdf(content);
+    let backend = RawBackend::load_bytes(&pdf).unwrap();
+    LayoutAnalyzer::new(&backend).extract_page_spans(1).unwrap()
+}
+
+fn span<'a>(spans: &'a [TextSpan], text: &str) -> &'a TextSpan {
+    spans.iter().find(|s| s.text == text).unwrap()
+}
+
+fn assert_at(span: &TextSpan, x: f32, y: f32) {
+    assert!(
+        (span.x - x).abs() < 0.01 && (span.y - y).abs() < 0.01,
+        "{:?}",
+        span
+    );
+}
+
+#[test]
+fn translated_group_is_restored() {
+    let spans = page_spans(
+        b"q 1 0 0 1 100 50 cm BT /F1 10 Tf 10 20 Td (Inside) Tj ET Q\n\
+          BT /F1 10 Tf 10 20 Td (Outside) Tj ET\n",
+    );
+    assert_at(span(&spans, "Inside"), 110.0, 70.0);
+    assert_at(span(&spans, "Outside"), 10.0, 20.0);
+}
+
+#[test]
+fn nested_groups_compose() {
+    let spans = page_spans(
+        b"q 2 0 0 2 0 0 cm q 1 0 0 1 10 10 cm BT /F1 10 Tf 0 0 Td (Inner) Tj ET Q\n\
+          BT /F1 10 Tf 5 5 Td (Outer) Tj ET Q\n",
+    );
+    let inner = span(&spans, "Inner");
+    assert_at(inner, 20.0, 20.0);
+    assert!((inner.font_size - 20.0).abs() < 0.01);
+    // Width scales with the group too: 5 glyphs x 4pt x 2.
+    assert!((inner.width - 40.0).abs() < 0.01, "{}", inner.width);
+    assert_at(span(&spans, "Outer"), 10.0, 10.0);
+}
+
+#[test]
+fn flipped_page_space() {
+    // Browsers flip the y axis once and draw text with a flipped text matrix.
+    let spans = page_spans(
+        b"1 0 0 -1 0 842 cm BT /F1 10 Tf 1 0 0 -1 72 100 Tm (Top) Tj ET\n\
+          BT /F1 10 Tf 1 0 0 -1 72 200 Tm (Below) Tj ET\n",
+    );
+    assert_at(span(&spans, "Top"), 72.0, 742.0);
+    assert_at(span(&spans, "Below"), 72.0, 642.0);
+    assert!((span(&spans, "Top").font_size - 10.0).abs() < 0.01);
+}
+
+#[test]
+fn font_restored_by_grestore() {
+    let spans = page_spans(
+        b"BT /F1 10 Tf ET q BT /F1 24 Tf 72 500 Td (Big) Tj ET Q\n\
+          BT 72 700 Td (Small) Tj ET\n",
+    );
+    assert!((span(&spans, "Big").font_size - 24.0).abs() < 0.01);
+    assert!((span(&spans, "Small").font_size - 10.0).abs() < 0.01);
+}
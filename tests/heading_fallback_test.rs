@@ -0,0 +1,106 @@
+mod common;
+
+use unpdf::{
+    parse_bytes, parse_bytes_with_options, Block, Document, HeadingConfig, LevelNormalization,
+    ParseOptions,
+};
+
+/// A report set entirely in 11pt Helvetica: uppercase headings set off by
+/// extra space, body lines 14pt apart.
+fn single_size_pdf() -> Vec<u8> {
+    let lines: [(&str, f32); 8] = [
+        ("INTRODUCTION", 700.0),
+        (
+            "Every line of this report uses the same font and size,",
+            680.0,
+        ),
+        (
+            "so font statistics alone cannot tell headings apart.",
+            666.0,
+        ),
+        ("The body continues for a few more lines of prose.", 652.0),
+        ("2. RESULTS", 620.0),
+        ("Headings are recovered from their style instead,", 600.0),
+        ("using case, length, spacing and numbering signals.", 586.0),
+        ("Body lines stay ordinary paragraphs as before.", 572.0),
+    ];
+    let mut content = String::new();
+    for (text, y) in lines {
+        content.push_str(&format!("BT /F1 11 Tf 72 {y} Td ({text}) Tj ET\n"));
+    }
+    common::helvetica_pdf(content.as_bytes())
+}
+
+fn headings(doc: &Document) -> Vec<(u8, String)> {
+    doc.pages[0]
+        .elements
+        .iter()
+        .filter_map(|b| match b {
+            Block::Paragraph(p) => Some((p.heading_level()?, p.plain_text().trim().to_string())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_single_size_document_gets_headings() {
+    let doc = parse_bytes(&single_size_pdf()).unwrap();
+    assert_eq!(
+        headings(&doc),
+        vec![
+            (2, "INTRODUCTION".to_string()),
+            (2, "2. RESULTS".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_style_fallback_can_be_disabled() {
+    let config = HeadingConfig {
+        detect_from_style: false,
+        ..HeadingConfig::default()
+    };
+    let options = ParseOptions::new().with_heading_config(config);
+    let doc = parse_bytes_with_options(&single_size_pdf(), options).unwrap();
+    assert!(headings(&doc).is_empty());
+}
+
+/// An unnumbered heading followed by a three-level numbered one, which
+/// detection places two levels deeper.
+fn level_jump_pdf() -> Vec<u8> {
+    let lines: [(&str, f32); 6] = [
+        ("INTRODUCTION", 700.0),
+        (
+            "Every line of this report uses the same font and size,",
+            680.0,
+        ),
+        (
+            "so font statistics alone cannot tell headings apart.",
+            666.0,
+        ),
+        ("2.1.1 SETUP DETAILS", 634.0),
+        ("The numbered heading sits three levels down,", 614.0),
+        ("yet nothing comes between it and the first heading.", 600.0),
+    ];
+    let mut content = String::new();
+    for (text, y) in lines {
+        content.push_str(&format!("BT /F1 11 Tf 72 {y} Td ({text}) Tj ET\n"));
+    }
+    common::helvetica_pdf(content.as_bytes())
+}
+
+#[test]
+fn test_heading_levels_normalized() {
+    let levels = |normalization| {
+        let config = HeadingConfig::default().with_normalize_levels(normalization);
+        let options = ParseOptions::new().with_heading_config(config);
+        let doc = parse_bytes_with_options(&level_jump_pdf(), options).unwrap();
+        headings(&doc)
+            .into_iter()
+            .map(|(level, _)| level)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(levels(LevelNormalization::Off), [2, 4]);
+    assert_eq!(levels(LevelNormalization::Compress), [2, 3]);
+    assert_eq!(levels(LevelNormalization::Anchored(1)), [1, 2]);
+}
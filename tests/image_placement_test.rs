@@ -0,0 +1,48 @@
+//! Image blocks placed in the text flow at their drawn position.
+
+mod common;
+
+use unpdf::{parse_bytes_with_options, render, Block, ParseOptions, RenderOptions};
+
+fn parse_with_images() -> unpdf::Document {
+    let options = ParseOptions::new().with_resources(true);
+    parse_bytes_with_options(&common::inline_image_pdf(), options).unwrap()
+}
+
+#[test]
+fn test_image_interleaved_with_text() {
+    let doc = parse_with_images();
+    let page = &doc.pages[0];
+    let kinds: Vec<&str> = page
+        .elements
+        .iter()
+        .map(|b| match b {
+            Block::Image { .. } => "image",
+            _ => "text",
+        })
+        .collect();
+    assert_eq!(kinds, vec!["text", "image", "text"], "{:?}", page.elements);
+
+    let Block::Image {
+        x,
+        y,
+        width,
+        height,
+        ..
+    } = &page.elements[1]
+    else {
+        unreachable!();
+    };
+    assert_eq!((*x, *y), (Some(72.0), Some(710.0)));
+    assert_eq!((*width, *height), (Some(200.0), Some(150.0)));
+}
+
+#[test]
+fn test_markdown_image_between_paragraphs() {
+    let doc = parse_with_images();
+    let markdown = render::to_markdown(&doc, &RenderOptions::new()).unwrap();
+    let before = markdown.find("Before the figure").unwrap();
+    let image = markdown.find("<!-- [Image] -->").unwrap();
+    let after = markdown.find("After the figure").unwrap();
+    assert!(before < image && image < after, "{}", markdown);
+}
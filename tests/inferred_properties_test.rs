@@ -0,0 +1,46 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use unpdf::parse_bytes;
+
+const LINES: [&str; 4] = [
+    "The quarterly report covers the results of the survey",
+    "and the changes that were made to the plan for the year.",
+    "Most of the pages are set in one font and one column,",
+    "which is what the inferred properties should report.",
+];
+
+fn report_pdf(lang: &str) -> Vec<u8> {
+    let mut content = String::new();
+    for (i, line) in LINES.iter().enumerate() {
+        let y = 700 - i * 14;
+        content.push_str(&format!("BT /F1 11 Tf 72 {y} Td ({line}) Tj ET\n"));
+    }
+    common::lang_pdf(lang, content.as_bytes())
+}
+
+#[test]
+fn test_inferred_properties() {
+    let doc = parse_bytes(&report_pdf("en-GB")).unwrap();
+    let inferred = doc.inferred_properties();
+
+    assert_eq!(inferred.language.as_deref(), Some("en"));
+    assert_eq!(inferred.body_font_size, Some(11.0));
+    assert_eq!(inferred.columns, BTreeMap::from([(1, 1)]));
+    assert_eq!(inferred.table_count, 0);
+    assert_eq!(inferred.title, None);
+}
+
+#[test]
+fn test_declared_language_is_kept_apart() {
+    // The catalog claims German; the text says otherwise
+    let doc = parse_bytes(&report_pdf("de-DE")).unwrap();
+    assert_eq!(doc.metadata.language.as_deref(), Some("de-DE"));
+    assert_eq!(doc.inferred_properties().language.as_deref(), Some("en"));
+
+    let json = serde_json::to_value(&doc).unwrap();
+    assert_eq!(json["metadata"]["language"], "de-DE");
+    assert_eq!(json["pages"][0]["body_font_size"], 11.0);
+    assert_eq!(json["pages"][0]["column_count"], 1);
+}
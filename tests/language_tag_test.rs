@@ -0,0 +1,83 @@
+mod common;
+
+use unpdf::{
+    parse_bytes_with_options, render, Block, Document, JsonFormat, ParseOptions, RenderOptions,
+};
+
+const ENGLISH: &str = "The results of the survey show that most of the pages are set in one font.";
+
+/// `text` as Adobe-Korea1 CIDs for [`common::korea1_pdf`]: ASCII from the
+/// space on, and `가` for every Hangul syllable.
+fn cids(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '가' => "043E".to_string(),
+            c => format!("{:04X}", c as u32 - 31),
+        })
+        .collect()
+}
+
+/// An English paragraph above a Korean one.
+fn bilingual_pdf() -> Vec<u8> {
+    let korean = [
+        "가가 가가가 가가 가가가가 가가가 가가.",
+        "가가가 가가 가가가 가가 가가가.",
+    ];
+    let mut content = format!("BT /F1 11 Tf 72 700 Td <{}> Tj ET\n", cids(ENGLISH));
+    for (i, line) in korean.iter().enumerate() {
+        let y = 640 - 14 * i;
+        content.push_str(&format!(
+            "BT /F1 11 Tf 72 {} Td <{}> Tj ET\n",
+            y,
+            cids(line)
+        ));
+    }
+    common::korea1_pdf(content.as_bytes())
+}
+
+fn languages(doc: &Document) -> Vec<Option<String>> {
+    doc.pages[0]
+        .elements
+        .iter()
+        .filter_map(|b| match b {
+            Block::Paragraph(p) => Some(p.style.language.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_paragraphs_tagged_by_language() {
+    let options = ParseOptions::new().with_language_tags(true);
+    let doc = parse_bytes_with_options(&bilingual_pdf(), options).unwrap();
+    assert_eq!(
+        languages(&doc),
+        vec![Some("en".to_string()), Some("ko".to_string())]
+    );
+
+    let json = render::to_json(&doc, JsonFormat::Detailed).unwrap();
+    assert!(json.contains(r#""language": "ko""#));
+
+    // Off by default
+    let doc = parse_bytes_with_options(&bilingual_pdf(), ParseOptions::new()).unwrap();
+    assert_eq!(languages(&doc), vec![None, None]);
+}
+
+#[test]
+fn test_render_filters_by_language() {
+    let options = ParseOptions::new().with_language_tags(true);
+    let doc = parse_bytes_with_options(&bilingual_pdf(), options).unwrap();
+
+    let korean_only = RenderOptions::new().with_languages(["ko"]);
+    let md = render::to_markdown(&doc, &korean_only).unwrap();
+    assert!(md.contains("가가"));
+    assert!(!md.contains("survey"));
+
+    let english_only = RenderOptions::new().with_languages(["en"]);
+    let text = render::to_text(&doc, &english_only).unwrap();
+    assert!(text.contains("survey"));
+    assert!(!text.contains("가가"));
+
+    let json = render::to_json_with_options(&doc, JsonFormat::Compact, &english_only).unwrap();
+    assert!(!json.contains("가가"));
+}
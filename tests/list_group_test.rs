@@ -0,0 +1,83 @@
+//! Grouping consecutive list items into `Block::List`.
+
+mod common;
+
+use unpdf::render::{to_markdown, RenderOptions};
+use unpdf::{Block, Document, ParseOptions, PdfParser};
+
+/// An intro paragraph, two numbered items, a bulleted one and a closing
+/// paragraph. Each is set apart by a wider gap than its own lines, so each
+/// becomes a block of its own.
+fn list_pdf() -> Vec<u8> {
+    let paragraphs: [&[&str]; 5] = [
+        &[
+            "Shopping notes for",
+            "the weekend market",
+            "run on Saturday.",
+        ],
+        &["1. Apples from the", "orchard stall by", "the fountain"],
+        &["2. Bread from the", "bakery on the", "corner"],
+        &["- Butter if it is", "on offer"],
+        &["That is all for", "this week."],
+    ];
+    let mut content = String::from("BT /F1 12 Tf 72 720 Td\n");
+    for (i, lines) in paragraphs.iter().enumerate() {
+        for (j, line) in lines.iter().enumerate() {
+            if i + j > 0 {
+                let gap = if j == 0 { 40 } else { 12 };
+                content.push_str(&format!("0 -{gap} Td\n"));
+            }
+            content.push_str(&format!("({line}) Tj\n"));
+        }
+    }
+    content.push_str("ET\n");
+    common::helvetica_pdf(content.as_bytes())
+}
+
+fn parse(options: ParseOptions) -> Document {
+    PdfParser::from_bytes_with_options(&list_pdf(), options)
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn list_items_are_grouped_when_asked() {
+    let doc = parse(ParseOptions::new().with_list_grouping(true));
+    let kinds: Vec<_> = doc.pages[0]
+        .elements
+        .iter()
+        .map(|block| match block {
+            Block::List { ordered, items } => format!("list({ordered}, {})", items.len()),
+            Block::Paragraph(_) => "paragraph".to_string(),
+            other => format!("{other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        ["paragraph", "list(true, 2)", "list(false, 1)", "paragraph"]
+    );
+
+    let json = serde_json::to_value(&doc.pages[0].elements[1]).unwrap();
+    assert_eq!(json["type"], "list");
+    assert_eq!(json["ordered"], true);
+    assert_eq!(json["items"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn grouping_is_off_by_default() {
+    let doc = parse(ParseOptions::new());
+    assert!(doc.pages[0].elements.iter().all(Block::is_paragraph));
+}
+
+#[test]
+fn grouped_lists_render_as_before() {
+    let flat = parse(ParseOptions::new());
+    let grouped = parse(ParseOptions::new().with_list_grouping(true));
+    let options = RenderOptions::default();
+    assert_eq!(
+        to_markdown(&grouped, &options).unwrap(),
+        to_markdown(&flat, &options).unwrap()
+    );
+    assert_eq!(grouped.plain_text(), flat.plain_text());
+}
@@ -0,0 +1,73 @@
+//! Peak heap use of low-memory parsing, measured by a counting allocator.
+//!
+//! 할당 카운터가 프로세스 전역이므로 이 파일에는 테스트를 하나만 둔다.
+
+mod common;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use unpdf::{parse_file_with_options, ParseOptions};
+
+struct Counting;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let now = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(now, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Heap use at its highest while parsing `path`, beyond what was allocated
+/// before, and what the returned document still holds.
+fn measure(path: &std::path::Path, options: ParseOptions) -> (usize, usize) {
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let doc = parse_file_with_options(path, options).unwrap();
+    let peak = PEAK.load(Ordering::Relaxed) - baseline;
+    let retained = CURRENT.load(Ordering::Relaxed) - baseline;
+    assert_eq!(doc.page_count(), 200);
+    (peak, retained)
+}
+
+#[test]
+fn test_low_memory_text_only_peak() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("long.pdf");
+    let pdf = common::many_pages_pdf(200, 50);
+    std::fs::write(&path, &pdf).unwrap();
+    let input_len = pdf.len();
+    drop(pdf);
+
+    let (default_peak, _) = measure(&path, ParseOptions::new().text_only());
+    let (peak, retained) = measure(&path, ParseOptions::new().text_only().with_low_memory(true));
+
+    assert!(
+        peak < default_peak,
+        "{} bytes, {} by default",
+        peak,
+        default_peak
+    );
+    // 반환된 문서 자체(추출된 텍스트)는 호출자가 요청한 결과이므로 제외한다.
+    assert!(
+        peak - retained < 2 * input_len,
+        "{} bytes beyond the document for a {} byte file",
+        peak - retained,
+        input_len
+    );
+}
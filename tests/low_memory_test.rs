@@ -0,0 +1,54 @@
+mod common;
+
+use unpdf::{parse_bytes_with_options, render, ParseOptions, RenderOptions};
+
+#[test]
+fn test_low_memory_matches_default_output() {
+    let pdf = common::many_pages_pdf(6, 20);
+    let normal = parse_bytes_with_options(&pdf, ParseOptions::new()).unwrap();
+    let low = parse_bytes_with_options(&pdf, ParseOptions::new().with_low_memory(true)).unwrap();
+
+    let options = RenderOptions::default();
+    assert_eq!(low.page_count(), 6);
+    assert_eq!(
+        render::to_markdown(&low, &options).unwrap(),
+        render::to_markdown(&normal, &options).unwrap()
+    );
+}
+
+#[test]
+fn test_low_memory_keeps_images_as_resources() {
+    let doc = parse_bytes_with_options(
+        &common::inline_image_pdf(),
+        ParseOptions::new()
+            .with_resources(true)
+            .with_low_memory(true),
+    )
+    .unwrap();
+    assert!(doc.pages.iter().all(|p| p.images.is_empty()));
+    assert!(!doc.resources.is_empty());
+    assert!(doc.resources.values().all(|r| !r.data.is_empty()));
+}
+
+#[test]
+fn test_resource_dir_writes_images_to_disk() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path().join("images");
+    let doc = parse_bytes_with_options(
+        &common::inline_image_pdf(),
+        ParseOptions::new()
+            .with_resources(true)
+            .with_resource_dir(&dir),
+    )
+    .unwrap();
+
+    assert!(!doc.resources.is_empty());
+    for resource in doc.resources.values() {
+        assert!(resource.data.is_empty());
+        let name = resource
+            .filename
+            .as_deref()
+            .expect("flushed resource has a file name");
+        assert!(std::fs::metadata(dir.join(name)).unwrap().len() > 0);
+    }
+}
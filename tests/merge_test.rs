@@ -0,0 +1,31 @@
+//! Multi-file merge: `Unpdf::parse_many` renders several PDFs as one document.
+
+mod common;
+
+use unpdf::Unpdf;
+
+#[test]
+fn parse_many_emits_one_section_per_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let first = tmp.path().join("first.pdf");
+    let second = tmp.path().join("second.pdf");
+    std::fs::write(&first, common::text_pdf()).unwrap();
+    std::fs::write(&second, common::mixed_pdf()).unwrap();
+
+    let result = Unpdf::new()
+        .with_frontmatter()
+        .parse_many(&[&first, &second])
+        .unwrap();
+
+    let doc = result.document();
+    assert_eq!(doc.page_count(), 3);
+    assert_eq!(doc.metadata.page_count, 3);
+    assert_eq!(doc.metadata.sources, vec!["first.pdf", "second.pdf"]);
+
+    let markdown = result.to_markdown().unwrap();
+    let first_at = markdown.find("# first.pdf").expect("first section");
+    let second_at = markdown.find("# second.pdf").expect("second section");
+    assert!(first_at < second_at);
+    assert_eq!(markdown.matches("Hello World").count(), 2);
+    assert!(markdown.starts_with("---"));
+}
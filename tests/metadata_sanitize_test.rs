@@ -0,0 +1,49 @@
+//! Info dictionary strings repaired at parse time, originals kept.
+
+mod common;
+
+use unpdf::{parse_bytes, render, JsonFormat, RenderOptions};
+
+/// UTF-16BE without a byte order mark, UTF-8 stored as UTF-16 code units,
+/// trailing NULs and a blank creator.
+const DAMAGED: &str = "/Title(\\000T\\000e\\000s\\000t)\
+    /Author<FEFF00430061006600C300A9>\
+    /Subject(Quarterly  report\\000\\000)\
+    /Creator(   )";
+
+#[test]
+fn damaged_info_strings_are_repaired() {
+    let doc = parse_bytes(&common::info_pdf(DAMAGED)).unwrap();
+    let meta = &doc.metadata;
+    assert_eq!(meta.title.as_deref(), Some("Test"));
+    assert_eq!(meta.author.as_deref(), Some("Café"));
+    assert_eq!(meta.subject.as_deref(), Some("Quarterly report"));
+    assert_eq!(meta.creator, None);
+
+    assert_eq!(meta.raw_metadata["title"], "\0T\0e\0s\0t");
+    assert_eq!(meta.raw_metadata["author"], "CafÃ©");
+    assert_eq!(meta.raw_metadata["creator"], "   ");
+}
+
+#[test]
+fn clean_info_strings_keep_no_raw_values() {
+    let doc = parse_bytes(&common::info_pdf("/Title(Annual Report)")).unwrap();
+    assert_eq!(doc.metadata.title.as_deref(), Some("Annual Report"));
+    assert!(doc.metadata.raw_metadata.is_empty());
+
+    let json = render::to_json(&doc, JsonFormat::Compact).unwrap();
+    assert!(!json.contains("raw_metadata"));
+}
+
+#[test]
+fn frontmatter_and_json_use_repaired_values() {
+    let doc = parse_bytes(&common::info_pdf(DAMAGED)).unwrap();
+    let options = RenderOptions::new().with_frontmatter(true);
+    let markdown = render::to_markdown(&doc, &options).unwrap();
+    assert!(markdown.contains("title: \"Test\""), "{markdown}");
+    assert!(markdown.contains("Café"));
+
+    let json = render::to_json(&doc, JsonFormat::Compact).unwrap();
+    assert!(json.contains(r#""title":"Test""#));
+    assert!(json.contains(r#""raw_metadata":{"#));
+}
@@ -0,0 +1,28 @@
+mod common;
+
+use unpdf::render::{to_markdown_with_stats, CleanupOptions, RenderOptions};
+
+#[test]
+fn test_aggressive_cleanup_redecodes_cp949_text() {
+    // "한글 문서 test" in CP949, shown through a font without ToUnicode
+    let pdf = common::helvetica_pdf(
+        b"BT /F1 12 Tf 72 720 Td (\\307\\321\\261\\333 \\271\\256\\274\\255 test) Tj ET\n",
+    );
+    let doc = unpdf::parse_bytes(&pdf).unwrap();
+
+    let options = RenderOptions::new().with_cleanup(CleanupOptions::aggressive());
+    let result = to_markdown_with_stats(&doc, &options).unwrap();
+    assert!(
+        result.content.contains("한글 문서 test"),
+        "{}",
+        result.content
+    );
+    assert_eq!(result.stats.mojibake_repairs.get("CP949"), Some(&2));
+    assert_eq!(result.stats.mojibake_unrepaired, 0);
+
+    // Without the flag the text is left as extracted
+    let options = RenderOptions::new().with_cleanup(CleanupOptions::standard());
+    let result = to_markdown_with_stats(&doc, &options).unwrap();
+    assert!(!result.content.contains("한글"), "{}", result.content);
+    assert!(result.stats.mojibake_repairs.is_empty());
+}
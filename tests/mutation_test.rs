@@ -0,0 +1,54 @@
+//! Mutating a parsed document and rendering it again.
+
+mod common;
+
+use unpdf::render::{to_markdown, PageSelection, RenderOptions};
+use unpdf::{Block, Document, Paragraph, ParseOptions, PdfParser};
+
+fn parse(data: &[u8]) -> Document {
+    PdfParser::from_bytes(data).unwrap().parse().unwrap()
+}
+
+#[test]
+fn removed_pages_are_not_rendered() {
+    let mut doc = parse(&common::many_pages_pdf(3, 2));
+    doc.remove_pages(&PageSelection::parse("2").unwrap());
+
+    let markdown = to_markdown(&doc, &RenderOptions::default()).unwrap();
+    assert!(markdown.contains("Page 1 line 1"));
+    assert!(!markdown.contains("Page 2 line"));
+    assert!(markdown.contains("Page 3 line 2"));
+    assert_eq!(doc.get_page(3).unwrap().number, 3);
+}
+
+#[test]
+fn dropped_image_blocks_take_their_resources() {
+    let options = ParseOptions::new().with_resources(true);
+    let mut doc = PdfParser::from_bytes_with_options(&common::inline_image_pdf(), options)
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(!doc.resources.is_empty());
+    doc.retain_blocks(|_, block| !block.is_image());
+
+    assert!(doc.resources.is_empty());
+    assert!(doc.pages.iter().all(|page| page.images.is_empty()));
+    let markdown = to_markdown(&doc, &RenderOptions::default()).unwrap();
+    assert!(!markdown.contains("!["));
+    assert!(markdown.contains("discusses the results."));
+}
+
+#[test]
+fn rewritten_paragraphs_render_as_headings() {
+    let mut doc = parse(&common::text_pdf());
+    let mut first = true;
+    doc.map_paragraphs(|_, p| {
+        if std::mem::take(&mut first) {
+            *p = Paragraph::heading(p.plain_text(), 2);
+        }
+    });
+
+    let markdown = to_markdown(&doc, &RenderOptions::default()).unwrap();
+    assert!(markdown.trim_start().starts_with("## "), "{markdown}");
+    assert!(doc.pages[0].elements.iter().any(Block::is_paragraph));
+}
@@ -0,0 +1,73 @@
+//! Integration tests for byte-bounded Markdown and text output.
+
+use unpdf::model::{Document, Page, Paragraph};
+use unpdf::render::{
+    to_markdown, to_markdown_parts, to_text, to_text_parts, OverflowStrategy, RenderOptions,
+};
+
+/// Ten pages of a heading and three Korean and English paragraphs each.
+fn long_document() -> Document {
+    let mut doc = Document::new();
+    for n in 1..=10 {
+        let mut page = Page::a4(n);
+        page.add_paragraph(Paragraph::heading(format!("Chapter {}", n), 2));
+        for i in 0..3 {
+            page.add_paragraph(Paragraph::with_text(format!(
+                "문단 {}-{}: the quick brown fox jumps over the lazy dog, 한글 텍스트와 함께.",
+                n, i
+            )));
+        }
+        doc.add_page(page);
+    }
+    doc
+}
+
+#[test]
+fn test_output_under_limit_is_untouched() {
+    let doc = long_document();
+    let full = to_markdown(&doc, &RenderOptions::new()).unwrap();
+    let options = RenderOptions::new().with_max_output_bytes(full.len(), OverflowStrategy::Split);
+    assert_eq!(to_markdown_parts(&doc, &options).unwrap(), vec![full]);
+}
+
+#[test]
+fn test_truncate_keeps_whole_blocks() {
+    let doc = long_document();
+    let full = to_markdown(&doc, &RenderOptions::new()).unwrap();
+    let options = RenderOptions::new().with_max_output_bytes(500, OverflowStrategy::Truncate);
+    let bounded = to_markdown(&doc, &options).unwrap();
+
+    assert!(bounded.len() <= 500);
+    assert!(full.starts_with(&bounded));
+    // Cut at a blank line, never inside a paragraph
+    assert!(full[bounded.len()..].starts_with("\n\n"));
+}
+
+#[test]
+fn test_summarize_tail_reports_omitted_content() {
+    let doc = long_document();
+    let options = RenderOptions::new().with_max_output_bytes(500, OverflowStrategy::SummarizeTail);
+    let bounded = to_markdown(&doc, &options).unwrap();
+    assert!(bounded.len() <= 500);
+    assert!(bounded.ends_with("bytes omitted -->"), "{}", bounded);
+
+    let text = to_text(&doc, &options).unwrap();
+    assert!(text.len() <= 500);
+    assert!(text.ends_with("bytes omitted]"), "{}", text);
+}
+
+#[test]
+fn test_split_covers_everything() {
+    let doc = long_document();
+    let full = to_text(&doc, &RenderOptions::new()).unwrap();
+    let options = RenderOptions::new().with_max_output_bytes(400, OverflowStrategy::Split);
+    let parts = to_text_parts(&doc, &options).unwrap();
+
+    assert!(parts.len() > 1);
+    assert!(parts.iter().all(|p| p.len() <= 400));
+    let marker = |p: &str| p.rfind("\n\n[continued in part").map_or(p.len(), |at| at);
+    let joined: Vec<&str> = parts.iter().map(|p| &p[..marker(p)]).collect();
+    assert_eq!(joined.join("\n\n"), full);
+    // The plain call returns the first part
+    assert_eq!(to_text(&doc, &options).unwrap(), parts[0]);
+}
@@ -0,0 +1,129 @@
+//! Damaged page trees: cycles, `/Count` mismatches, missing kids and
+//! empty trees must neither loop nor panic.
+
+mod common;
+
+use common::{page_tree_pdf, PAGE_TREE_LEAF};
+use unpdf::parser::raw::{PageTreeDamage, RawDocument};
+use unpdf::parser::ErrorMode;
+use unpdf::{parse_bytes, parse_bytes_with_options, Error, ParseOptions, PdfParser};
+
+fn damage(pdf: &[u8]) -> PageTreeDamage {
+    PdfParser::from_bytes(pdf)
+        .unwrap()
+        .structure_report()
+        .page_tree
+}
+
+fn strict() -> ParseOptions {
+    ParseOptions::new().with_error_mode(ErrorMode::Strict)
+}
+
+#[test]
+fn intact_tree_reports_no_damage() {
+    let pdf = page_tree_pdf(&[
+        "<</Type/Pages/Kids[5 0 R 6 0 R]/Count 2>>",
+        PAGE_TREE_LEAF,
+        PAGE_TREE_LEAF,
+    ]);
+    assert!(damage(&pdf).is_clean());
+    assert_eq!(
+        parse_bytes_with_options(&pdf, strict())
+            .unwrap()
+            .pages
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn cycle_back_to_root_is_cut() {
+    // 6 은 다시 루트(4)를 자식으로 가진다.
+    let pdf = page_tree_pdf(&[
+        "<</Type/Pages/Kids[5 0 R 6 0 R]/Count 2>>",
+        PAGE_TREE_LEAF,
+        "<</Type/Pages/Kids[4 0 R]/Count 1>>",
+    ]);
+    let found = damage(&pdf);
+    assert_eq!(found.revisited_nodes, 1);
+    assert_eq!(found.count_mismatches, 2);
+
+    let doc = parse_bytes(&pdf).unwrap();
+    assert_eq!(doc.pages.len(), 1);
+    assert_eq!(doc.plain_text().trim(), "Page");
+    assert!(matches!(
+        parse_bytes_with_options(&pdf, strict()),
+        Err(Error::Corrupted(_))
+    ));
+}
+
+#[test]
+fn kid_listed_twice_counts_once() {
+    let pdf = page_tree_pdf(&["<</Type/Pages/Kids[5 0 R 5 0 R]/Count 2>>", PAGE_TREE_LEAF]);
+    let found = damage(&pdf);
+    assert_eq!(found.revisited_nodes, 1);
+    assert_eq!(parse_bytes(&pdf).unwrap().pages.len(), 1);
+}
+
+#[test]
+fn count_mismatch_and_missing_kids_keep_reachable_pages() {
+    let pdf = page_tree_pdf(&[
+        "<</Type/Pages/Kids[5 0 R 9 0 R 6 0 R]/Count 5>>",
+        PAGE_TREE_LEAF,
+        PAGE_TREE_LEAF,
+    ]);
+    let found = damage(&pdf);
+    assert_eq!(found.unreadable_nodes, 1);
+    assert_eq!(found.count_mismatches, 1);
+    assert_eq!(
+        found.to_string(),
+        "1 unreadable node(s), 1 /Count mismatch(es)"
+    );
+
+    let doc = parse_bytes(&pdf).unwrap();
+    assert_eq!(doc.pages.len(), 2);
+    assert_eq!(doc.metadata.page_count, 2);
+}
+
+#[test]
+fn empty_tree_parses_to_zero_pages() {
+    let pdf = page_tree_pdf(&["<</Type/Pages/Kids[]/Count 0>>"]);
+    assert!(damage(&pdf).is_clean());
+    let doc = parse_bytes_with_options(&pdf, strict()).unwrap();
+    assert!(doc.pages.is_empty());
+    assert_eq!(doc.plain_text(), "");
+}
+
+#[test]
+fn missing_kids_array_is_damage_not_a_page() {
+    let pdf = page_tree_pdf(&["<</Type/Pages/Count 1>>"]);
+    let found = damage(&pdf);
+    assert_eq!(found.unreadable_nodes, 1);
+    assert!(parse_bytes(&pdf).unwrap().pages.is_empty());
+}
+
+#[test]
+fn untyped_leaf_is_a_page() {
+    let leaf = PAGE_TREE_LEAF.replace("/Type/Page", "");
+    let pdf = page_tree_pdf(&["<</Kids[5 0 R]/Count 1>>", &leaf]);
+    assert!(damage(&pdf).is_clean());
+    assert_eq!(parse_bytes(&pdf).unwrap().pages.len(), 1);
+}
+
+#[test]
+fn very_deep_tree_is_truncated_without_overflow() {
+    // 루트부터 1000 단계로 중첩된 /Pages 사슬 끝에 페이지 하나.
+    let depth = 1000;
+    let mut nodes: Vec<String> = (0..depth)
+        .map(|i| format!("<</Type/Pages/Kids[{} 0 R]/Count 1>>", i + 5))
+        .collect();
+    nodes.push(PAGE_TREE_LEAF.to_string());
+    let nodes: Vec<&str> = nodes.iter().map(String::as_str).collect();
+    let pdf = page_tree_pdf(&nodes);
+
+    let doc = RawDocument::load(&pdf).unwrap();
+    let (pages, found) = doc.page_tree();
+    assert!(pages.is_empty());
+    assert!(found.truncated);
+    assert!(parse_bytes(&pdf).unwrap().pages.is_empty());
+}
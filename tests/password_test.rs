@@ -0,0 +1,113 @@
+//! Opening encrypted documents with candidate passwords.
+
+mod common;
+
+use unpdf::{EncryptionKind, Error, ParseOptions, PdfParser};
+
+fn open(data: &[u8], options: ParseOptions) -> unpdf::Result<PdfParser> {
+    PdfParser::from_bytes_with_options(data, options)
+}
+
+#[test]
+fn owner_password_only_opens_without_password() {
+    let data = common::encrypted_pdf("", "owner");
+    let doc = open(&data, ParseOptions::new()).unwrap().parse().unwrap();
+    assert!(doc.metadata.encrypted);
+    assert_eq!(
+        doc.metadata.encryption,
+        Some(EncryptionKind::OwnerPasswordOnly)
+    );
+    assert!(doc.plain_text().contains("Hello World"));
+}
+
+#[test]
+fn user_password_is_required() {
+    let data = common::encrypted_pdf("user", "owner");
+    assert!(matches!(
+        open(&data, ParseOptions::new()),
+        Err(Error::Encrypted)
+    ));
+}
+
+#[test]
+fn wrong_passwords_are_rejected() {
+    let data = common::encrypted_pdf("user", "owner");
+    let options = ParseOptions::new().with_passwords(vec!["guess".into(), "other".into()]);
+    assert!(matches!(open(&data, options), Err(Error::InvalidPassword)));
+}
+
+#[test]
+fn passwords_are_tried_until_one_opens() {
+    let data = common::encrypted_pdf("user", "owner");
+    let options = ParseOptions::new()
+        .with_password("guess")
+        .with_passwords(vec!["other".into(), "user".into()]);
+    let doc = open(&data, options).unwrap().parse().unwrap();
+    assert_eq!(doc.metadata.encryption, Some(EncryptionKind::UserPassword));
+    assert!(doc.plain_text().contains("Hello World"));
+}
+
+#[test]
+fn owner_password_opens_the_document() {
+    let data = common::encrypted_pdf("user", "owner");
+    let options = ParseOptions::new()
+        .with_passwords(vec!["owner".into()])
+        .with_low_memory(true);
+    let doc = open(&data, options).unwrap().parse().unwrap();
+    assert_eq!(doc.metadata.encryption, Some(EncryptionKind::UserPassword));
+    assert!(doc.plain_text().contains("Hello World"));
+}
+
+#[test]
+fn unencrypted_documents_report_no_encryption() {
+    let doc = open(&common::text_pdf(), ParseOptions::new())
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(!doc.metadata.encrypted);
+    assert_eq!(doc.metadata.encryption, None);
+}
+
+/// `/P` without bit 5, the copy permission.
+const NO_COPY: i32 = -4 & !0x10;
+
+#[test]
+fn copy_restrictions_are_ignored_by_default() {
+    let data = common::restricted_pdf("", "owner", NO_COPY);
+    let doc = open(&data, ParseOptions::new()).unwrap().parse().unwrap();
+    assert!(doc.plain_text().contains("Hello World"));
+}
+
+#[test]
+fn copy_restrictions_are_honoured_when_asked() {
+    let data = common::restricted_pdf("", "owner", NO_COPY);
+    let strict = ParseOptions::new().respect_extraction_permission(true);
+    let parser = open(&data, strict.clone()).unwrap();
+    assert!(matches!(parser.parse(), Err(Error::ExtractionForbidden)));
+
+    // 복사가 허용된 문서와 소유자 암호로 연 문서는 그대로 추출한다.
+    let allowed = common::restricted_pdf("", "owner", -4);
+    assert!(open(&allowed, strict.clone()).unwrap().parse().is_ok());
+    let owner = strict.with_passwords(vec!["owner".into()]);
+    let doc = open(&data, owner).unwrap().parse().unwrap();
+    assert!(doc.plain_text().contains("Hello World"));
+}
+
+#[test]
+fn copy_restrictions_cover_tables_and_spans() {
+    let data = common::restricted_pdf("", "owner", NO_COPY);
+    let strict = ParseOptions::new().respect_extraction_permission(true);
+    let parser = open(&data, strict).unwrap();
+    assert!(matches!(
+        parser.extract_tables(),
+        Err(Error::ExtractionForbidden)
+    ));
+    assert!(matches!(
+        parser.extract_spans(1),
+        Err(Error::ExtractionForbidden)
+    ));
+
+    let lenient = open(&data, ParseOptions::new()).unwrap();
+    assert!(lenient.extract_tables().is_ok());
+    assert!(lenient.extract_spans(1).is_ok());
+}
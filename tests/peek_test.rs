@@ -0,0 +1,56 @@
+//! Metadata-only open with `unpdf::peek`.
+
+mod common;
+
+use unpdf::{peek, peek_bytes, EncryptionKind, Error};
+
+#[test]
+fn peek_matches_full_parse_metadata() {
+    let data = common::info_pdf("/Title(Quarterly Report)/Author(Kim)");
+    let info = peek_bytes(&data).unwrap();
+    let doc = unpdf::parse_bytes(&data).unwrap();
+
+    assert_eq!(info.metadata.title.as_deref(), Some("Quarterly Report"));
+    assert_eq!(info.metadata.author, doc.metadata.author);
+    assert_eq!(info.metadata.pdf_version, "1.4");
+    assert_eq!(info.metadata.page_count, 1);
+    assert!(!info.metadata.encrypted);
+    assert_eq!(info.outline_count, 0);
+    assert_eq!(info.file_size, data.len() as u64);
+}
+
+#[test]
+fn peek_counts_nested_bookmarks() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("outlined.pdf");
+    std::fs::write(&path, common::outlined_pdf()).unwrap();
+
+    let info = peek(&path).unwrap();
+    assert_eq!(info.outline_count, 2);
+    assert_eq!(info.metadata.page_count, 1);
+}
+
+#[test]
+fn peek_reports_encryption_without_a_password() {
+    let locked = peek_bytes(&common::encrypted_pdf("user", "owner")).unwrap();
+    assert!(locked.metadata.encrypted);
+    assert_eq!(
+        locked.metadata.encryption,
+        Some(EncryptionKind::UserPassword)
+    );
+    assert_eq!(locked.metadata.page_count, 1);
+
+    let restricted = peek_bytes(&common::encrypted_pdf("", "owner")).unwrap();
+    assert_eq!(
+        restricted.metadata.encryption,
+        Some(EncryptionKind::OwnerPasswordOnly)
+    );
+}
+
+#[test]
+fn peek_rejects_other_formats() {
+    assert!(matches!(
+        peek_bytes(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+        Err(Error::NotPdf(_))
+    ));
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use unpdf::render::{
+    to_json_with_options, to_markdown_with_stats, CleanupOptions, JsonFormat, PiiRedaction,
+    RenderOptions,
+};
+
+#[test]
+fn test_markdown_stats_count_redactions() {
+    let pdf = common::helvetica_pdf(
+        b"BT /F1 12 Tf 72 720 Td (Contact kim@example.com or 02-123-4567.) Tj ET\n",
+    );
+    let doc = unpdf::parse_bytes(&pdf).unwrap();
+
+    let cleanup = CleanupOptions::standard().with_pii_redaction(PiiRedaction::all());
+    let options = RenderOptions::new().with_cleanup(cleanup);
+    let result = to_markdown_with_stats(&doc, &options).unwrap();
+    assert!(
+        result.content.contains("Contact [EMAIL] or [PHONE]."),
+        "{}",
+        result.content
+    );
+    assert_eq!(result.stats.redactions.get(PiiRedaction::EMAIL), Some(&1));
+    assert_eq!(result.stats.redactions.get(PiiRedaction::PHONE), Some(&1));
+    assert_eq!(result.stats.redaction_count(), 2);
+
+    // Presets leave personal data alone
+    let options = RenderOptions::new().with_cleanup(CleanupOptions::standard());
+    let result = to_markdown_with_stats(&doc, &options).unwrap();
+    assert!(result.content.contains("kim@example.com"));
+    assert_eq!(result.stats.redaction_count(), 0);
+}
+
+#[test]
+fn test_json_redacts_metadata() {
+    let pdf = common::info_pdf("/Author (kim@example.com) /Subject (Call 02-123-4567)");
+    let doc = unpdf::parse_bytes(&pdf).unwrap();
+
+    let cleanup = CleanupOptions::standard().with_pii_redaction(PiiRedaction::all());
+    let options = RenderOptions::new().with_cleanup(cleanup);
+    let json = to_json_with_options(&doc, JsonFormat::Pretty, &options).unwrap();
+    assert!(!json.contains("kim@example.com"), "{}", json);
+    assert!(!json.contains("02-123-4567"), "{}", json);
+    assert!(json.contains("[EMAIL]"), "{}", json);
+
+    // Without redaction the metadata is emitted as authored
+    let json = to_json_with_options(&doc, JsonFormat::Pretty, &RenderOptions::new()).unwrap();
+    assert!(json.contains("kim@example.com"));
+}
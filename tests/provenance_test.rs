@@ -0,0 +1,89 @@
+//! Extraction provenance: recorded on every parse, emitted only on request.
+//! The source hash is only computed when `ParseOptions::source_hash` is set.
+
+mod common;
+
+use unpdf::{
+    parse_bytes, parse_bytes_with_options, render, JsonFormat, ParseOptions, RenderOptions, Unpdf,
+};
+
+fn parse_hashed(data: &[u8]) -> unpdf::Document {
+    parse_bytes_with_options(data, ParseOptions::new().with_source_hash(true)).unwrap()
+}
+
+#[test]
+fn parser_records_source_hash() {
+    let doc = parse_hashed(&common::text_pdf());
+    let provenance = doc.provenance.as_ref().expect("provenance recorded");
+    let hash = provenance.source_sha256.as_deref().unwrap();
+    assert_eq!(hash.len(), 64);
+    assert!(provenance.generator.starts_with("unpdf "));
+
+    let again = parse_hashed(&common::text_pdf());
+    assert_eq!(
+        again.provenance.unwrap().source_sha256.as_deref(),
+        Some(hash)
+    );
+
+    let other = parse_hashed(&common::blank_pdf());
+    assert_ne!(
+        other.provenance.unwrap().source_sha256.as_deref(),
+        Some(hash)
+    );
+}
+
+#[test]
+fn provenance_only_in_output_when_enabled() {
+    let doc = parse_hashed(&common::text_pdf());
+    let hash = doc
+        .provenance
+        .as_ref()
+        .unwrap()
+        .source_sha256
+        .clone()
+        .unwrap();
+
+    let plain = render::to_markdown(&doc, &RenderOptions::new()).unwrap();
+    assert!(!plain.contains("provenance"));
+    assert!(!render::to_json(&doc, JsonFormat::Compact)
+        .unwrap()
+        .contains(&hash));
+
+    let options = RenderOptions::new().with_provenance(true);
+    let markdown = render::to_markdown(&doc, &options).unwrap();
+    assert!(markdown.starts_with("---\n"));
+    assert!(markdown.contains(&format!("source_sha256: \"{hash}\"")));
+
+    let json = render::to_json_with_options(&doc, JsonFormat::Compact, &options).unwrap();
+    assert!(json.contains(&hash));
+    assert!(json.contains(&doc.content_hash()));
+}
+
+#[test]
+fn source_hash_is_skipped_by_default() {
+    let doc = parse_bytes(&common::text_pdf()).unwrap();
+    let provenance = doc.provenance.expect("provenance recorded");
+    assert_eq!(provenance.source_sha256, None);
+
+    let lazy = ParseOptions::new()
+        .with_low_memory(true)
+        .with_source_hash(true);
+    let doc = parse_bytes_with_options(&common::text_pdf(), lazy).unwrap();
+    assert_eq!(doc.provenance.unwrap().source_sha256.unwrap().len(), 64);
+}
+
+#[test]
+fn builder_provenance_alone_includes_source_hash() {
+    let result = Unpdf::new()
+        .with_provenance()
+        .parse_bytes(&common::text_pdf())
+        .unwrap();
+    let hash = result
+        .document
+        .provenance
+        .as_ref()
+        .and_then(|p| p.source_sha256.clone())
+        .expect("source hash computed for provenance");
+    let markdown = result.to_markdown().unwrap();
+    assert!(markdown.contains(&format!("source_sha256: \"{hash}\"")));
+}
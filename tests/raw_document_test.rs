@@ -0,0 +1,29 @@
+//! Sharing one loaded document between low-level access and extraction.
+
+mod common;
+
+use unpdf::parser::raw::{PdfObject, RawDocument};
+use unpdf::{parse_bytes, ParseOptions, PdfParser};
+
+#[test]
+fn parser_exposes_its_raw_document() {
+    let parser = PdfParser::from_bytes(&common::annotated_pdf()).unwrap();
+    let raw = parser.raw_document().expect("raw backend");
+    let (_, page_id) = raw.pages().into_iter().next().unwrap();
+    let page = raw.get_dict(page_id).unwrap();
+    let annots = page.get(b"Annots".as_slice()).map(|v| raw.resolve(v));
+    assert!(matches!(annots, Some(PdfObject::Array(_))));
+}
+
+#[test]
+fn parser_built_from_loaded_document() {
+    let pdf = common::text_pdf();
+    let raw = RawDocument::load(&pdf).unwrap();
+    assert_eq!(raw.page_count(), 1);
+
+    let parser = PdfParser::from_raw_document(raw, ParseOptions::default());
+    assert_eq!(parser.raw_document().map(|d| d.page_count()), Some(1));
+    let doc = parser.parse().unwrap();
+    assert_eq!(doc.plain_text(), parse_bytes(&pdf).unwrap().plain_text());
+    assert_eq!(doc.provenance.and_then(|p| p.source_sha256), None);
+}
@@ -0,0 +1,53 @@
+//! Image extraction decoding several images at once, bounded by a memory
+//! ceiling.
+
+mod common;
+
+use unpdf::{parse_bytes_with_options, Block, Document, ParseOptions};
+
+fn parse(options: ParseOptions) -> Document {
+    let pdf = common::many_images_pdf(6, 1000);
+    parse_bytes_with_options(&pdf, options.with_resources(true)).unwrap()
+}
+
+fn image_ids(doc: &Document) -> Vec<String> {
+    doc.pages[0]
+        .elements
+        .iter()
+        .filter_map(|b| match b {
+            Block::Image { resource_id, .. } => Some(resource_id.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_parallel_decoding_matches_sequential() {
+    let sequential = parse(ParseOptions::new().with_resource_threads(1));
+    let parallel = parse(ParseOptions::new().with_resource_threads(4));
+    assert_eq!(image_ids(&sequential).len(), 6);
+    assert_eq!(image_ids(&parallel), image_ids(&sequential));
+    let data = |doc: &Document| -> Vec<Vec<u8>> {
+        doc.pages[0]
+            .images
+            .iter()
+            .map(|(_, r)| r.data.clone())
+            .collect()
+    };
+    assert_eq!(data(&parallel), data(&sequential));
+    assert_eq!(parallel.resources.len(), sequential.resources.len());
+}
+
+#[test]
+fn test_memory_ceiling_stops_decoding() {
+    // 1000바이트짜리 이미지 셋째에서 한도 2500 에 닿는다.
+    let options = ParseOptions::new()
+        .with_resource_threads(1)
+        .with_max_resource_memory(2500);
+    let doc = parse(options);
+    assert_eq!(
+        image_ids(&doc),
+        vec!["page1_Im0.jpg", "page1_Im1.jpg", "page1_Im2.jpg"]
+    );
+    assert_eq!(doc.resources.len(), 3);
+}
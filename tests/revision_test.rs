@@ -0,0 +1,56 @@
+//! Incremental-update revisions: enumeration, per-revision parsing and
+//! flagging pages whose content a later revision replaced.
+
+mod common;
+
+use unpdf::{parse_bytes, parse_bytes_with_options, ParseOptions, PdfParser};
+
+#[test]
+fn test_single_revision_reports_none() {
+    let doc = parse_bytes(&common::text_pdf()).unwrap();
+    assert!(doc.revisions.is_empty());
+    assert!(doc.revised_pages().is_empty());
+}
+
+#[test]
+fn test_revisions_are_enumerated() {
+    let data = common::revised_pdf();
+    let revisions = PdfParser::from_bytes(&data).unwrap().revisions();
+
+    let numbers: Vec<u32> = revisions.iter().map(|r| r.number).collect();
+    assert_eq!(numbers, vec![1, 2, 3]);
+    assert_eq!(revisions[2].byte_len, data.len());
+    assert!(revisions[0].byte_len < revisions[1].byte_len);
+    assert_eq!(revisions[1].changed_objects, 1);
+    assert_eq!(revisions[2].changed_objects, 2);
+}
+
+#[test]
+fn test_replaced_content_is_flagged() {
+    let doc = parse_bytes(&common::revised_pdf()).unwrap();
+    assert!(doc.plain_text().contains("Amended wording"));
+    assert_eq!(doc.revisions[1].revised_pages, vec![1]);
+    // 주석만 추가한 리비전은 본문을 바꾸지 않았다.
+    assert!(doc.revisions[2].revised_pages.is_empty());
+    assert_eq!(doc.revised_pages(), vec![1]);
+}
+
+#[test]
+fn test_parse_earlier_revision() {
+    let data = common::revised_pdf();
+    let original = parse_bytes_with_options(&data, ParseOptions::new().with_revision(1)).unwrap();
+    assert!(original.plain_text().contains("Original wording"));
+    assert!(original.revisions.is_empty());
+
+    let amended = parse_bytes_with_options(&data, ParseOptions::new().with_revision(2)).unwrap();
+    assert!(amended.plain_text().contains("Amended wording"));
+    assert_eq!(amended.revisions.len(), 2);
+    assert!(amended.pages[0].annotations.is_empty());
+}
+
+#[test]
+fn test_missing_revision_is_an_error() {
+    let data = common::revised_pdf();
+    let err = PdfParser::from_bytes_with_options(&data, ParseOptions::new().with_revision(4));
+    assert!(err.is_err());
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use serde_json::Value;
+use unpdf::model::{
+    Annotation, AnnotationKind, Block, Document, FieldType, FieldValue, FormField, ListInfo,
+    Outline, OutlineItem, Page, Paragraph, Provenance, Resource, ResourceType, Table, TableCell,
+    TableDetection, TableRejection, TableRow, TextRun, Word,
+};
+use unpdf::render::to_json_with_options;
+use unpdf::{parse_bytes_with_options, JsonFormat, ParseOptions, RenderOptions};
+
+fn assert_valid(json: &str) {
+    let validator = jsonschema::validator_for(&unpdf::schema()).unwrap();
+    let instance: Value = serde_json::from_str(json).unwrap();
+    let errors: Vec<String> = validator
+        .iter_errors(&instance)
+        .map(|e| format!("{} at {}", e, e.instance_path))
+        .collect();
+    assert!(errors.is_empty(), "{}", errors.join("\n"));
+}
+
+/// A document touching every block, inline and metadata variant.
+fn kitchen_sink() -> Document {
+    let mut doc = Document::new();
+    doc.metadata.title = Some("Schema".to_string());
+    doc.metadata.page_count = 1;
+    doc.metadata.encrypted = true;
+    doc.metadata.encryption = Some(unpdf::EncryptionKind::OwnerPasswordOnly);
+
+    let mut page = Page::new(1, 595.0, 842.0);
+    page.add_paragraph(Paragraph::heading("Title", 1));
+    let mut para = Paragraph::with_text("Item");
+    para.add_run(TextRun::bold(" bold"));
+    para.add_line_break();
+    para.style.list_info = Some(ListInfo::numbered(0, 1));
+    page.add_paragraph(para);
+    let mut bullet = Paragraph::with_text("Bullet");
+    bullet.style.list_info = Some(ListInfo::bullet(1));
+    page.add_paragraph(bullet);
+
+    let mut table = Table::with_header(1);
+    table.add_row(TableRow::header(vec![
+        TableCell::text("A"),
+        TableCell::text("B"),
+    ]));
+    table.add_row(TableRow::from_strings(["1", "2"]));
+    let detection = TableDetection {
+        bbox: [72.0, 600.0, 300.0, 640.0],
+        confidence: 0.7,
+        alignment: vec![1.0, 1.0],
+        rejected: None,
+    };
+    table.detection = Some(detection.clone());
+    page.add_table(table);
+    page.table_candidates.push(TableDetection {
+        rejected: Some(TableRejection::ListPattern),
+        ..detection
+    });
+
+    page.add_block(Block::Image {
+        resource_id: "img1".to_string(),
+        alt_text: None,
+        width: Some(10.0),
+        height: Some(10.0),
+        x: None,
+        y: None,
+    });
+    page.add_block(Block::CodeBlock {
+        language: Some("rust".to_string()),
+        content: "fn main() {}".to_string(),
+    });
+    page.add_block(Block::List {
+        ordered: false,
+        items: vec![Paragraph::with_text("Listed")],
+    });
+    page.add_block(Block::HorizontalRule);
+    page.add_block(Block::PageBreak);
+    page.add_block(Block::SectionBreak);
+    page.add_block(Block::Raw {
+        content: "raw".to_string(),
+    });
+    page.annotations.push(Annotation {
+        kind: AnnotationKind::StrikeOut,
+        author: Some("Reviewer".to_string()),
+        modified: None,
+        contents: Some("Remove".to_string()),
+        covered_text: None,
+        rect: [0.0, 0.0, 10.0, 10.0],
+    });
+    page.words.push(Word {
+        text: "Title".to_string(),
+        bbox: [72.0, 700.0, 100.0, 712.0],
+        line: 0,
+    });
+    doc.add_page(page);
+
+    doc.add_resource(
+        "img1".to_string(),
+        Resource::new(vec![0xFF], "image/jpeg", ResourceType::Image).with_dimensions(10, 10),
+    );
+    let mut outline = Outline::new();
+    let mut item = OutlineItem::new("Title", Some(1), 1);
+    item.add_child(OutlineItem::new("Sub", None, 2));
+    outline.add_item(item);
+    doc.outline = Some(outline);
+    doc.form_fields.push(FormField {
+        name: "choices".to_string(),
+        field_type: FieldType::ListBox,
+        value: Some(FieldValue::Choices(vec!["a".to_string()])),
+        default_value: Some(FieldValue::Boolean(false)),
+    });
+    doc.provenance = Some(Provenance::new(None, "default"));
+    doc
+}
+
+#[test]
+fn test_schema_is_versioned() {
+    let schema = unpdf::schema();
+    let id = schema["$id"].as_str().unwrap();
+    assert!(
+        id.contains(&format!("v{}", env!("CARGO_PKG_VERSION"))),
+        "{}",
+        id
+    );
+    assert!(jsonschema::meta::is_valid(&schema));
+    assert_eq!(
+        schema["properties"]["schema_version"]["const"],
+        unpdf::SCHEMA_VERSION
+    );
+}
+
+#[test]
+fn test_kitchen_sink_reads_back() {
+    let doc = kitchen_sink();
+    let json = unpdf::render::to_json(&doc, JsonFormat::Compact).unwrap();
+    let read = unpdf::Document::from_json(&json).unwrap();
+    assert_eq!(read.plain_text(), doc.plain_text());
+    assert_eq!(read.form_fields.len(), doc.form_fields.len());
+}
+
+#[test]
+fn test_kitchen_sink_matches_schema() {
+    let doc = kitchen_sink();
+    for format in [
+        JsonFormat::Pretty,
+        JsonFormat::Compact,
+        JsonFormat::Detailed,
+    ] {
+        assert_valid(&unpdf::render::to_json(&doc, format).unwrap());
+        let options = RenderOptions::new().with_provenance(true);
+        assert_valid(&to_json_with_options(&doc, format, &options).unwrap());
+    }
+}
+
+#[test]
+fn test_parsed_documents_match_schema() {
+    let resources = ParseOptions::new().with_resources(true).with_words(true);
+    let cases = [
+        (common::text_pdf(), ParseOptions::new()),
+        (common::mixed_pdf(), ParseOptions::new()),
+        (common::inline_image_pdf(), resources),
+        (common::revised_pdf(), ParseOptions::new()),
+        (common::column_table_pdf(), ParseOptions::new()),
+    ];
+    for (pdf, options) in cases {
+        let doc = parse_bytes_with_options(&pdf, options).unwrap();
+        assert_valid(&unpdf::render::to_json(&doc, JsonFormat::Detailed).unwrap());
+    }
+}
+
+#[test]
+fn test_schema_rejects_unknown_fields() {
+    let doc = kitchen_sink();
+    let mut value: Value = serde_json::to_value(&doc).unwrap();
+    value["pages"][0]["elements"][0]["colour"] = Value::from("red");
+    let validator = jsonschema::validator_for(&unpdf::schema()).unwrap();
+    assert!(!validator.is_valid(&value));
+}
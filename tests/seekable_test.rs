@@ -0,0 +1,67 @@
+//! Parsing from `Read + Seek` without reading the whole file.
+
+mod common;
+
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use unpdf::{parse_bytes_with_options, parse_seekable, PageSelection, ParseOptions};
+
+/// A reader that counts the bytes read through it, like the requests an
+/// HTTP range-request adapter would make.
+struct Counting {
+    inner: Cursor<Vec<u8>>,
+    read: Arc<AtomicUsize>,
+}
+
+impl Read for Counting {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read.fetch_add(n, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+impl Seek for Counting {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn seekable_parse_matches_in_memory_parse() {
+    let data = common::many_pages_pdf(3, 5);
+    let expected = unpdf::parse_bytes(&data).unwrap();
+    let doc = parse_seekable(Cursor::new(data), ParseOptions::new()).unwrap();
+    assert_eq!(doc.page_count(), 3);
+    assert_eq!(doc.plain_text(), expected.plain_text());
+}
+
+#[test]
+fn selected_pages_fetch_a_fraction_of_the_file() {
+    let data = common::many_pages_pdf(20, 1000);
+    let len = data.len();
+    let options = ParseOptions::new().with_pages(PageSelection::Range(2..=2));
+    let expected = parse_bytes_with_options(&data, options.clone()).unwrap();
+
+    let read = Arc::new(AtomicUsize::new(0));
+    let reader = Counting {
+        inner: Cursor::new(data),
+        read: Arc::clone(&read),
+    };
+    let doc = parse_seekable(reader, options).unwrap();
+
+    assert_eq!(doc.plain_text(), expected.plain_text());
+    assert!(doc.plain_text().contains("Page 2 line 1000"));
+    let read = read.load(Ordering::Relaxed);
+    assert!(read < len / 3, "read {read} of {len} bytes");
+}
+
+#[test]
+fn seekable_encrypted_documents_open_with_a_password() {
+    let data = common::encrypted_pdf("user", "owner");
+    let options = ParseOptions::new().with_password("user");
+    let doc = parse_seekable(Cursor::new(data), options).unwrap();
+    assert!(doc.plain_text().contains("Hello World"));
+}
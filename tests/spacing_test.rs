@@ -0,0 +1,46 @@
+mod common;
+
+use unpdf::{parse_bytes_with_options, ParseOptions, Script, ScriptSpacing, SpacingPolicy};
+
+/// `API가` in one string: A, P and I are CIDs 34, 49 and 42.
+const API_GA: &[u8] = b"BT /F1 12 Tf 72 720 Td <00220031002A043E> Tj ET\n";
+
+fn page_text(pdf: &[u8], options: ParseOptions) -> String {
+    let doc = parse_bytes_with_options(pdf, options).unwrap();
+    doc.pages[0].plain_text().trim().to_string()
+}
+
+#[test]
+fn test_hangul_latin_boundary_default_keeps_text() {
+    assert_eq!(
+        page_text(&common::korea1_pdf(API_GA), ParseOptions::new()),
+        "API가"
+    );
+}
+
+#[test]
+fn test_hangul_latin_boundary_forced_space() {
+    let policy = SpacingPolicy::default().with_boundary(
+        Script::Hangul,
+        Script::Latin,
+        ScriptSpacing::Always,
+    );
+    let options = ParseOptions::new().with_spacing(policy);
+    assert_eq!(page_text(&common::korea1_pdf(API_GA), options), "API 가");
+}
+
+#[test]
+fn test_tj_space_threshold_configurable() {
+    let pdf = common::helvetica_pdf(b"BT /F1 12 Tf 72 720 Td [(Hello) -150 (World)] TJ ET\n");
+    assert_eq!(page_text(&pdf, ParseOptions::new()), "HelloWorld");
+
+    let policy = SpacingPolicy {
+        tj_space_threshold: 100.0,
+        tj_space_ratio: 0.3,
+        ..SpacingPolicy::default()
+    };
+    assert_eq!(
+        page_text(&pdf, ParseOptions::new().with_spacing(policy)),
+        "Hello World"
+    );
+}
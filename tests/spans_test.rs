@@ -0,0 +1,33 @@
+//! Span-level access to the decoding layer via `PdfParser::extract_spans`.
+
+mod common;
+
+use common::{blank_pdf, text_pdf};
+use unpdf::{Error, PdfParser};
+
+#[test]
+fn spans_carry_position_and_font() {
+    let parser = PdfParser::from_bytes(&text_pdf()).unwrap();
+    let spans = parser.extract_spans(1).unwrap();
+
+    let text: String = spans.iter().map(|s| s.text.as_str()).collect();
+    assert!(text.contains("Hello"), "{text:?}");
+    let first = &spans[0];
+    assert_eq!(first.font_size, 12.0);
+    assert!(first.font_name.contains("Helvetica"), "{}", first.font_name);
+    assert!((first.x - 72.0).abs() < 0.5);
+    assert!((first.y - 720.0).abs() < 0.5);
+}
+
+#[test]
+fn blank_page_has_no_spans() {
+    let parser = PdfParser::from_bytes(&blank_pdf()).unwrap();
+    assert!(parser.extract_spans(1).unwrap().is_empty());
+}
+
+#[test]
+fn out_of_range_page_is_an_error() {
+    let parser = PdfParser::from_bytes(&text_pdf()).unwrap();
+    let err = parser.extract_spans(2).unwrap_err();
+    assert!(matches!(err, Error::PageOutOfRange(2, 1)), "{err}");
+}
@@ -0,0 +1,51 @@
+//! Structural damage reported without extracting any page.
+
+mod common;
+
+use unpdf::parser::backend::StructureReport;
+use unpdf::PdfParser;
+
+#[test]
+fn well_formed_file_reports_no_damage() {
+    let parser = PdfParser::from_bytes(&common::text_pdf()).unwrap();
+    assert_eq!(parser.structure_report(), StructureReport::default());
+    assert!(parser.structure_report().is_clean());
+}
+
+#[test]
+fn undecodable_filters_are_counted_by_name() {
+    let parser = PdfParser::from_bytes(&common::filtered_stream_pdf("LZWDecode")).unwrap();
+    let report = parser.structure_report();
+    assert_eq!(report.broken_xref_entries, 0);
+    assert_eq!(report.unsupported_filters.get("LZWDecode"), Some(&1));
+}
+
+#[test]
+fn image_codecs_are_not_unsupported() {
+    let parser = PdfParser::from_bytes(&common::filtered_stream_pdf("DCTDecode")).unwrap();
+    assert!(parser.structure_report().is_clean());
+}
+
+#[test]
+fn xref_entries_pointing_at_garbage_are_counted() {
+    let mut pdf = common::filtered_stream_pdf("FlateDecode");
+    // 오프셋을 유지한 채 객체 6 의 헤더를 망가뜨린다.
+    let at = pdf.windows(7).position(|w| w == b"6 0 obj").unwrap();
+    pdf[at..at + 7].copy_from_slice(b"garbage");
+
+    let parser = PdfParser::from_bytes(&pdf).unwrap();
+    let report = parser.structure_report();
+    assert_eq!(report.broken_xref_entries, 1);
+    assert!(!report.is_clean());
+}
+
+#[test]
+fn page_content_len_measures_decoded_streams() {
+    let content = b"BT /F1 12 Tf 72 720 Td (Hello World) Tj ET\n";
+    let parser = PdfParser::from_bytes(&common::helvetica_pdf(content)).unwrap();
+    assert_eq!(parser.page_content_len(1).unwrap(), content.len());
+    assert!(matches!(
+        parser.page_content_len(2),
+        Err(unpdf::Error::PageOutOfRange(2, 1))
+    ));
+}
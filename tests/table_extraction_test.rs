@@ -0,0 +1,94 @@
+//! Table-only extraction without the rest of the page pipeline.
+
+mod common;
+
+use std::io::Write;
+
+use unpdf::{parse_bytes, Block, PageSelection, ParseOptions, PdfParser, Table};
+
+fn rows(table: &Table) -> Vec<String> {
+    table.rows.iter().map(|r| r.plain_text()).collect()
+}
+
+#[test]
+fn tables_match_full_parse() {
+    let pdf = common::column_table_pdf();
+    let tables = PdfParser::from_bytes(&pdf)
+        .unwrap()
+        .extract_tables()
+        .unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].0, 1);
+    assert!(
+        rows(&tables[0].1)[2].contains("340M"),
+        "{:?}",
+        rows(&tables[0].1)
+    );
+
+    let doc = parse_bytes(&pdf).unwrap();
+    let parsed: Vec<&Table> = doc.pages[0]
+        .elements
+        .iter()
+        .filter_map(|b| match b {
+            Block::Table(t) => Some(t),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(rows(parsed[0]), rows(&tables[0].1));
+}
+
+#[test]
+fn pages_without_tables_yield_nothing() {
+    let tables = PdfParser::from_bytes(&common::text_pdf())
+        .unwrap()
+        .extract_tables()
+        .unwrap();
+    assert!(tables.is_empty());
+
+    let options = ParseOptions::new().with_pages(PageSelection::Pages(vec![2]));
+    let parser = PdfParser::from_bytes_with_options(&common::column_table_pdf(), options).unwrap();
+    assert!(parser.extract_tables().unwrap().is_empty());
+}
+
+#[test]
+fn extract_tables_from_path() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(&common::column_table_pdf()).unwrap();
+    let tables = unpdf::extract_tables(file.path(), ParseOptions::new().sequential()).unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(rows(&tables[0].1).len(), 4);
+}
+
+#[test]
+fn wrapped_cell_text_stays_in_its_row() {
+    // 설명 칸이 두 줄로 감긴 행: 두 번째 줄은 첫 칸이 비어 있다.
+    let lines: [(i32, &str, &str); 6] = [
+        (720, "Part", "Description"),
+        (696, "Bolt", "Hex head, zinc plated,"),
+        (682, "", "sold in packs of ten"),
+        (658, "Nut", "Nylon lock nut"),
+        (634, "Washer", "Flat washer, stainless,"),
+        (620, "", "fits M6 bolts"),
+    ];
+    let mut content = String::new();
+    for (y, part, description) in lines {
+        if !part.is_empty() {
+            content.push_str(&format!("BT /F1 10 Tf 72 {y} Td ({part}) Tj ET\n"));
+        }
+        content.push_str(&format!("BT /F1 10 Tf 200 {y} Td ({description}) Tj ET\n"));
+    }
+    let pdf = common::helvetica_pdf(content.as_bytes());
+    let tables = PdfParser::from_bytes(&pdf)
+        .unwrap()
+        .extract_tables()
+        .unwrap();
+    assert_eq!(tables.len(), 1);
+    let rows = rows(&tables[0].1);
+    assert_eq!(rows.len(), 4, "{rows:?}");
+    assert!(
+        rows[1].contains("zinc plated, sold in packs of ten"),
+        "{rows:?}"
+    );
+    assert!(rows[3].contains("stainless, fits M6 bolts"), "{rows:?}");
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use unpdf::render::to_text;
+use unpdf::{parse_bytes_with_options, ParseOptions, RenderOptions, TextLayout};
+
+/// A statement: a title, then three rows of date, description and amount
+/// at fixed x positions, with a gap before the total.
+fn statement_pdf() -> Vec<u8> {
+    let rows = [
+        ("2024-01-03", "Coffee", "4.50"),
+        ("2024-01-04", "Train ticket", "12.00"),
+        ("2024-01-09", "Books", "31.25"),
+    ];
+    let mut content = String::from("BT /F1 10 Tf\n1 0 0 1 72 760 Tm (Statement) Tj\n");
+    for (i, (date, what, amount)) in rows.iter().enumerate() {
+        let y = 720 - i * 14;
+        content.push_str(&format!("1 0 0 1 72 {} Tm ({}) Tj\n", y, date));
+        content.push_str(&format!("1 0 0 1 180 {} Tm ({}) Tj\n", y, what));
+        content.push_str(&format!("1 0 0 1 400 {} Tm ({}) Tj\n", y, amount));
+    }
+    content.push_str("1 0 0 1 180 640 Tm (Total) Tj 1 0 0 1 400 640 Tm (47.75) Tj\nET\n");
+    common::helvetica_pdf(content.as_bytes())
+}
+
+fn layout_text(options: ParseOptions) -> String {
+    let doc = parse_bytes_with_options(&statement_pdf(), options).unwrap();
+    let render = RenderOptions::new().with_text_layout(TextLayout::Preserve);
+    to_text(&doc, &render).unwrap()
+}
+
+#[test]
+fn test_layout_keeps_columns_aligned() {
+    let text = layout_text(ParseOptions::new().with_words(true));
+    let lines: Vec<&str> = text.lines().collect();
+    let rows: Vec<&&str> = lines.iter().filter(|l| l.starts_with("2024-")).collect();
+    assert_eq!(rows.len(), 3, "{}", text);
+
+    let column = |needle: &str| {
+        let line = lines.iter().find(|l| l.contains(needle)).unwrap();
+        line[..line.find(needle).unwrap()].chars().count()
+    };
+    assert_eq!(column("Coffee"), column("Train ticket"), "{}", text);
+    assert_eq!(column("4.50"), column("31.25"), "{}", text);
+    assert_eq!(column("Total"), column("Books"), "{}", text);
+    assert!(
+        column("4.50") > column("Coffee") + "Train ticket".len(),
+        "{}",
+        text
+    );
+}
+
+#[test]
+fn test_layout_keeps_vertical_gaps() {
+    let text = layout_text(ParseOptions::new().with_words(true));
+    let lines: Vec<&str> = text.lines().collect();
+    let books = lines.iter().position(|l| l.contains("Books")).unwrap();
+    let total = lines.iter().position(|l| l.contains("Total")).unwrap();
+    assert!(total > books + 1, "{}", text);
+    assert!(lines[books + 1].trim().is_empty());
+}
+
+#[test]
+fn test_layout_without_words_reflows() {
+    let text = layout_text(ParseOptions::new());
+    assert!(text.contains("Statement"), "{}", text);
+    assert!(text.contains("47.75"), "{}", text);
+}
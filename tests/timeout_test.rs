@@ -0,0 +1,54 @@
+//! Per-page and per-document time budgets.
+//!
+//! A zero budget expires immediately, which makes the timeout paths
+//! deterministic without needing a pathological fixture.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{mixed_pdf, text_pdf};
+use unpdf::parser::ErrorMode;
+use unpdf::{parse_bytes_with_options, Error, ParseOptions};
+
+#[test]
+fn page_timeout_skips_page_in_lenient_mode() {
+    let options = ParseOptions::new().with_page_timeout(Duration::ZERO);
+    let doc = parse_bytes_with_options(&text_pdf(), options).unwrap();
+
+    assert!(doc.pages.is_empty());
+    assert_eq!(doc.extraction_quality.timed_out_pages, vec![1]);
+    let warning = doc.extraction_quality.warning_message().unwrap();
+    assert!(warning.contains("timed out"), "{warning}");
+}
+
+#[test]
+fn page_timeout_fails_strict_parse() {
+    let options = ParseOptions::new()
+        .with_error_mode(ErrorMode::Strict)
+        .with_page_timeout(Duration::ZERO);
+    let err = parse_bytes_with_options(&text_pdf(), options).unwrap_err();
+    assert!(matches!(err, Error::Timeout(_)), "{err}");
+}
+
+#[test]
+fn document_timeout_reports_every_unparsed_page() {
+    let options = ParseOptions::new()
+        .sequential()
+        .with_timeout(Duration::ZERO);
+    let doc = parse_bytes_with_options(&mixed_pdf(), options).unwrap();
+
+    assert!(doc.pages.is_empty());
+    assert_eq!(doc.extraction_quality.timed_out_pages, vec![1, 2]);
+}
+
+#[test]
+fn generous_timeouts_change_nothing() {
+    let options = ParseOptions::new()
+        .with_timeout(Duration::from_secs(60))
+        .with_page_timeout(Duration::from_secs(60));
+    let doc = parse_bytes_with_options(&text_pdf(), options).unwrap();
+
+    assert!(doc.plain_text().contains("Hello World"));
+    assert!(doc.extraction_quality.timed_out_pages.is_empty());
+}
@@ -0,0 +1,88 @@
+//! Path-drawn charts exported as SVG resources.
+
+mod common;
+
+use unpdf::{render, Block, ParseOptions, PdfParser, RenderOptions, ResourceType};
+
+fn parse(vectors: bool) -> unpdf::Document {
+    let options = ParseOptions::new().with_vector_graphics(vectors);
+    PdfParser::from_bytes_with_options(&common::chart_pdf(), options)
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn chart_becomes_svg_resource_and_image_block() {
+    let doc = parse(true);
+
+    let resource = doc.get_resource("page1_vector.svg").expect("svg resource");
+    assert_eq!(resource.resource_type, ResourceType::Vector);
+    assert_eq!(resource.mime_type, "image/svg+xml");
+    let svg = String::from_utf8(resource.data.clone()).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(svg.matches("<path").count(), 4);
+
+    let page = &doc.pages[0];
+    let image = page.elements.iter().find_map(|b| match b {
+        Block::Image {
+            resource_id,
+            width,
+            height,
+            ..
+        } => Some((resource_id.clone(), *width, *height)),
+        _ => None,
+    });
+    let (id, width, height) = image.expect("image block");
+    assert_eq!(id, "page1_vector.svg");
+    // Bars and axis span 190x200pt, padded by half the 1pt axis stroke.
+    assert!((width.unwrap() - 191.0).abs() < 0.01);
+    assert!((height.unwrap() - 201.0).abs() < 0.01);
+
+    let markdown = render::to_markdown(&doc, &RenderOptions::new()).unwrap();
+    assert!(markdown.contains("Quarterly revenue"));
+    assert!(markdown.contains("<!-- [Image] -->"));
+}
+
+#[test]
+fn vectors_are_opt_in() {
+    let doc = parse(false);
+    assert!(doc.resources.is_empty());
+    assert!(!doc.pages[0]
+        .elements
+        .iter()
+        .any(|b| matches!(b, Block::Image { .. })));
+}
+
+#[test]
+fn drawing_sits_between_text_above_and_below_it() {
+    let content = b"BT /F1 12 Tf 14 TL 72 720 Td (Quarterly revenue rose in) Tj\
+                    (every region this year.) ' ET\n\
+                    1 0 0 rg 100 400 40 120 re f\n\
+                    0 0.5 0 rg 160 400 40 200 re f\n\
+                    0 0 1 rg 220 400 40 80 re f\n\
+                    0 G 1 w 90 400 m 280 400 l S\n\
+                    BT /F1 12 Tf 14 TL 72 300 Td (Source: internal figures,) Tj\
+                    (before audit.) ' ET\n";
+    let options = ParseOptions::new().with_vector_graphics(true);
+    let doc = PdfParser::from_bytes_with_options(&common::helvetica_pdf(content), options)
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    let kinds: Vec<&str> = doc.pages[0]
+        .elements
+        .iter()
+        .map(|b| {
+            let mut text = String::new();
+            b.append_plain_text(&mut text);
+            match b {
+                Block::Image { .. } => "image",
+                _ if text.contains("Quarterly") => "above",
+                _ if text.contains("Source") => "below",
+                _ => "other",
+            }
+        })
+        .collect();
+    assert_eq!(kinds, vec!["above", "image", "below"]);
+}
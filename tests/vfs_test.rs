@@ -0,0 +1,64 @@
+//! Parsing through a `FileSource` instead of the local file system.
+
+mod common;
+
+use std::cell::RefCell;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use unpdf::{parse_from_source, Error, FileSource, MemoryFileSource, Unpdf};
+
+#[test]
+fn parses_from_memory_without_touching_disk() {
+    let source = MemoryFileSource::new().with_file("archive/hello.pdf", common::text_pdf());
+
+    let doc = parse_from_source(&source, "archive/hello.pdf").unwrap();
+    assert!(doc.plain_text().contains("Hello World"));
+
+    let markdown = Unpdf::new()
+        .parse_from_source(&source, Path::new("archive/hello.pdf"))
+        .unwrap()
+        .to_markdown()
+        .unwrap();
+    assert!(markdown.contains("Hello World"));
+}
+
+#[test]
+fn missing_and_non_pdf_files_are_reported() {
+    let source = MemoryFileSource::new().with_file("page.html", b"<!DOCTYPE html><html>".to_vec());
+
+    match parse_from_source(&source, "absent.pdf") {
+        Err(Error::Io(e)) => assert_eq!(e.kind(), io::ErrorKind::NotFound),
+        other => panic!("expected a not-found error, got {:?}", other.map(|_| ())),
+    }
+    assert!(matches!(
+        parse_from_source(&source, "page.html"),
+        Err(Error::NotPdf(_))
+    ));
+}
+
+/// An adapter that only knows how to stream objects, like an object store.
+struct Streaming {
+    source: MemoryFileSource,
+    opened: RefCell<Vec<PathBuf>>,
+}
+
+impl FileSource for Streaming {
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + '_>> {
+        self.opened.borrow_mut().push(path.to_path_buf());
+        self.source.open(path)
+    }
+}
+
+#[test]
+fn custom_sources_only_need_open() {
+    let source = Streaming {
+        source: MemoryFileSource::new().with_file("bucket/key.pdf", common::text_pdf()),
+        opened: RefCell::new(Vec::new()),
+    };
+    let dynamic: &dyn FileSource = &source;
+
+    let doc = parse_from_source(dynamic, "bucket/key.pdf").unwrap();
+    assert!(doc.plain_text().contains("Hello World"));
+    assert_eq!(*source.opened.borrow(), [PathBuf::from("bucket/key.pdf")]);
+}
@@ -0,0 +1,54 @@
+//! Word bounding boxes (`ParseOptions::with_words`) from glyph advances.
+
+mod common;
+
+use unpdf::{parse_bytes, parse_bytes_with_options, ParseOptions, Word};
+
+fn words(content: &[u8]) -> Vec<Word> {
+    let data = common::condensed_font_pdf(content);
+    let options = ParseOptions::new().with_words(true);
+    let doc = parse_bytes_with_options(&data, options).unwrap();
+    doc.pages[0].words.clone()
+}
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 0.01, "{actual} != {expected}");
+}
+
+#[test]
+fn test_words_are_off_by_default() {
+    let doc = parse_bytes(&common::condensed_font_pdf(
+        b"BT /F1 12 Tf 72 720 Td (AB CD) Tj ET\n",
+    ))
+    .unwrap();
+    assert!(doc.pages[0].words.is_empty());
+}
+
+#[test]
+fn test_boxes_follow_glyph_advances() {
+    // 12pt: 글리프 400 단위 = 4.8pt, 공백 200 단위 = 2.4pt.
+    let words = words(b"BT /F1 12 Tf 72 720 Td (AB CD) Tj ET\n");
+    let texts: Vec<&str> = words.iter().map(|w| w.text.as_str()).collect();
+    assert_eq!(texts, vec!["AB", "CD"]);
+    assert_close(words[0].bbox[0], 72.0);
+    assert_close(words[0].bbox[2], 81.6);
+    assert_close(words[1].bbox[0], 84.0);
+    assert_close(words[1].bbox[2], 93.6);
+    assert!(words[0].bbox[1] < 720.0 && words[0].bbox[3] > 720.0);
+}
+
+#[test]
+fn test_tj_kerning_moves_following_glyphs() {
+    let words = words(b"BT /F1 12 Tf 72 720 Td [(AB) -1000 (CD)] TJ ET\n");
+    let texts: Vec<&str> = words.iter().map(|w| w.text.as_str()).collect();
+    assert_eq!(texts, vec!["AB", "CD"]);
+    assert_close(words[1].bbox[0], 93.6);
+    assert_close(words[1].bbox[2], 103.2);
+}
+
+#[test]
+fn test_words_carry_line_ids() {
+    let words = words(b"BT /F1 12 Tf 72 720 Td (AB CD) Tj 0 -20 Td (EF) Tj ET\n");
+    let lines: Vec<(&str, u32)> = words.iter().map(|w| (w.text.as_str(), w.line)).collect();
+    assert_eq!(lines, vec![("AB", 0), ("CD", 0), ("EF", 1)]);
+}
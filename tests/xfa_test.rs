@@ -0,0 +1,40 @@
+//! XFA forms flagged in the metadata, their datasets kept as a resource.
+
+mod common;
+
+use unpdf::{parse_bytes, parse_bytes_with_options, ParseOptions, XFA_DATASETS_ID};
+
+#[test]
+fn xfa_form_is_flagged() {
+    let doc = parse_bytes(&common::xfa_pdf("9 0 R")).unwrap();
+    assert!(doc.metadata.has_xfa);
+    // 리소스 추출을 켜지 않으면 datasets 는 남기지 않는다.
+    assert!(!doc.resources.contains_key(XFA_DATASETS_ID));
+
+    let plain = parse_bytes(&common::info_pdf("/Title(Plain)")).unwrap();
+    assert!(!plain.metadata.has_xfa);
+}
+
+#[test]
+fn datasets_packet_is_extracted_from_packet_array() {
+    let pdf = common::xfa_pdf("[(template) 7 0 R (datasets) 8 0 R]");
+    let options = ParseOptions::new().with_resources(true);
+    let doc = parse_bytes_with_options(&pdf, options).unwrap();
+    let datasets = &doc.resources[XFA_DATASETS_ID];
+    assert_eq!(datasets.mime_type, "application/xml");
+    assert_eq!(datasets.data, common::XFA_DATASETS.as_bytes());
+    assert_eq!(
+        datasets.suggested_filename(XFA_DATASETS_ID),
+        "xfa_datasets.xml"
+    );
+}
+
+#[test]
+fn datasets_packet_is_cut_from_single_xdp_stream() {
+    let options = ParseOptions::new().with_resources(true);
+    let doc = parse_bytes_with_options(&common::xfa_pdf("9 0 R"), options).unwrap();
+    assert_eq!(
+        doc.resources[XFA_DATASETS_ID].data,
+        common::XFA_DATASETS.as_bytes()
+    );
+}
@@ -1,4 +1,4 @@
-use unpdf::parser::xycut::{xycut_segment, Block};
+use unpdf::parser::xycut::{xycut_columns, xycut_segment, Block};
 
 fn make_block(x: f32, y: f32, w: f32, h: f32) -> Block {
     Block {
@@ -155,6 +155,7 @@ fn test_extraction_quality_serializes_is_scan_pdf() {
         encrypted: false,
         suppressed_ocr_pages: 0,
         is_scan_pdf: true,
+        timed_out_pages: Vec::new(),
     };
     let json = serde_json::to_string(&q).unwrap();
     assert!(
@@ -163,3 +164,31 @@ fn test_extraction_quality_serializes_is_scan_pdf() {
     );
     assert!(json.contains("true"), "is_scan_pdf should be true: {json}");
 }
+
+#[test]
+fn test_xycut_columns_counts_side_by_side_groups() {
+    let blocks = vec![
+        make_block(72.0, 750.0, 480.0, 12.0),
+        make_block(72.0, 700.0, 200.0, 12.0),
+        make_block(72.0, 680.0, 200.0, 12.0),
+        make_block(350.0, 700.0, 200.0, 12.0),
+        make_block(350.0, 680.0, 200.0, 12.0),
+    ];
+    let (groups, columns) = xycut_columns(&blocks, 20.0, 15.0, 0.0);
+    assert_eq!(groups.len(), 3);
+    assert_eq!(columns, 2);
+}
+
+#[test]
+fn test_xycut_columns_rejects_narrow_side() {
+    let blocks = vec![
+        make_block(72.0, 700.0, 30.0, 12.0),
+        make_block(72.0, 680.0, 30.0, 12.0),
+        make_block(200.0, 700.0, 300.0, 12.0),
+        make_block(200.0, 680.0, 300.0, 12.0),
+    ];
+    assert_eq!(xycut_columns(&blocks, 20.0, 15.0, 0.0).1, 2);
+    let (groups, columns) = xycut_columns(&blocks, 20.0, 15.0, 80.0);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(columns, 1);
+}